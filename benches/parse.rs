@@ -0,0 +1,66 @@
+//! Benchmarks of formula parsing, from short formulas to proteins written residue by residue.
+
+use chemistry_parser::ChemParser;
+use criterion::{criterion_group, criterion_main, BenchmarkId, Criterion};
+use std::hint::black_box;
+
+/// Formulas of the residues of the 20 standard amino acids in a peptide chain.
+const RESIDUES: [&str; 20] = [
+    "C3H5NO",
+    "C6H12N4O",
+    "C4H6N2O2",
+    "C4H5NO3",
+    "C3H5NOS",
+    "C5H7NO3",
+    "C5H8N2O2",
+    "C2H3NO",
+    "C6H7N3O",
+    "C6H11NO",
+    "C6H11NO",
+    "C6H12N2O",
+    "C5H9NOS",
+    "C9H9NO",
+    "C5H7NO",
+    "C3H5NO2",
+    "C4H7NO2",
+    "C11H10N2O",
+    "C9H9NO2",
+    "C5H9NO",
+];
+
+/// Writes a protein of `length` residues with each residue as a group, such as
+/// `(C3H5NO)(C6H12N4O)`, followed by the water of its ends.
+fn protein(length: usize) -> String {
+    let mut formula: String = RESIDUES
+        .iter()
+        .cycle()
+        .take(length)
+        .map(|residue| format!("({})", residue))
+        .collect();
+    formula.push_str("H2O");
+    formula
+}
+
+fn parse_formula(c: &mut Criterion) {
+    let parser = ChemParser::new();
+    let mut group = c.benchmark_group("parse_formula");
+    for formula in ["H2O", "C6H12O6", "K4[Fe(CN)6]", "CuSO4*5H2O"] {
+        group.bench_with_input(
+            BenchmarkId::new("formula", formula),
+            formula,
+            |b, formula| b.iter(|| parser.parse_formula(black_box(formula))),
+        );
+    }
+    for length in [100, 1000] {
+        let formula = protein(length);
+        group.bench_with_input(
+            BenchmarkId::new("protein", length),
+            &formula,
+            |b, formula| b.iter(|| parser.parse_formula(black_box(formula))),
+        );
+    }
+    group.finish();
+}
+
+criterion_group!(benches, parse_formula);
+criterion_main!(benches);
@@ -0,0 +1,108 @@
+#ifndef CHEMISTRY_PARSER_H
+#define CHEMISTRY_PARSER_H
+
+/* Generated with cbindgen from src/ffi.rs by `make header`; do not edit by hand. */
+
+#include <stdarg.h>
+#include <stdbool.h>
+#include <stddef.h>
+#include <stdint.h>
+#include <stdlib.h>
+
+// Opaque handle of a parsed equation.
+typedef struct ChemEquation ChemEquation;
+
+// Opaque handle of a parsed formula.
+typedef struct ChemFormula ChemFormula;
+
+#ifdef __cplusplus
+extern "C" {
+#endif // __cplusplus
+
+// Returns the message of the last failed call on this thread, or null if no call failed. The
+// message is owned by the library and valid until the next failed call on the thread.
+const char *chem_last_error(void);
+
+// Parses a formula, such as `H2SO4`, returning null on errors.
+//
+// # Safety
+//
+// `formula` must be null or a NUL-terminated string.
+struct ChemFormula *chem_parse_formula(const char *formula);
+
+// Returns the molecular mass of a formula in g/mol, or NaN for a null formula.
+//
+// # Safety
+//
+// `formula` must be null or a handle returned by [`chem_parse_formula`] and not yet freed.
+double chem_formula_mass(const struct ChemFormula *formula);
+
+// Returns the electric charge of a formula, zero for neutral species and null formulas.
+//
+// # Safety
+//
+// `formula` must be null or a handle returned by [`chem_parse_formula`] and not yet freed.
+int32_t chem_formula_charge(const struct ChemFormula *formula);
+
+// Returns the JSON of a formula, to be freed with [`chem_free_string`], or null on errors.
+//
+// # Safety
+//
+// `formula` must be null or a handle returned by [`chem_parse_formula`] and not yet freed.
+char *chem_formula_to_json(const struct ChemFormula *formula);
+
+// Parses an equation, such as `2H2 + O2 -> 2H2O`, returning null on errors.
+//
+// # Safety
+//
+// `equation` must be null or a NUL-terminated string.
+struct ChemEquation *chem_parse_equation(const char *equation);
+
+// Returns whether an equation conserves every element and the charge, false for null.
+//
+// # Safety
+//
+// `equation` must be null or a handle returned by [`chem_parse_equation`] and not yet freed.
+bool chem_equation_is_balanced(const struct ChemEquation *equation);
+
+// Returns an equation with the smallest balancing coefficients, each at most
+// `max_coefficient`, to be freed with [`chem_free_string`], or null if it cannot be balanced.
+//
+// # Safety
+//
+// `equation` must be null or a handle returned by [`chem_parse_equation`] and not yet freed.
+char *chem_equation_balance(const struct ChemEquation *equation, uint32_t max_coefficient);
+
+// Returns the JSON of an equation, to be freed with [`chem_free_string`], or null on errors.
+//
+// # Safety
+//
+// `equation` must be null or a handle returned by [`chem_parse_equation`] and not yet freed.
+char *chem_equation_to_json(const struct ChemEquation *equation);
+
+// Frees a formula returned by [`chem_parse_formula`]; null is ignored.
+//
+// # Safety
+//
+// `formula` must be null or a handle returned by [`chem_parse_formula`] and not yet freed.
+void chem_free_formula(struct ChemFormula *formula);
+
+// Frees an equation returned by [`chem_parse_equation`]; null is ignored.
+//
+// # Safety
+//
+// `equation` must be null or a handle returned by [`chem_parse_equation`] and not yet freed.
+void chem_free_equation(struct ChemEquation *equation);
+
+// Frees a string returned by the library; null is ignored.
+//
+// # Safety
+//
+// `text` must be null or a string returned by the library and not yet freed.
+void chem_free_string(char *text);
+
+#ifdef __cplusplus
+}  // extern "C"
+#endif  // __cplusplus
+
+#endif  /* CHEMISTRY_PARSER_H */
@@ -0,0 +1,198 @@
+//! # Acid-base Module
+//!
+//! This module classifies formulas as strong or weak acids or bases, from a built-in list of
+//! common compounds and from their written structure, a leading `H` or trailing `COOH` for acids
+//! and a trailing `OH` for bases, and writes the neutralization of an acid by a base.
+
+use crate::activity::salt_formula;
+use crate::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use crate::element::{composition_key, Equation, Formula};
+use crate::{ChemParseError, ChemParser};
+use serde::Serialize;
+
+/// Class of an acid or a base.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum AcidBase {
+    /// Acid that dissociates completely in water, such as `HCl`.
+    StrongAcid,
+    /// Acid that dissociates partially in water, such as `CH3COOH`.
+    WeakAcid,
+    /// Base that dissociates completely in water, such as `NaOH`.
+    StrongBase,
+    /// Base that reacts partially with water, such as `NH3`.
+    WeakBase,
+}
+
+impl AcidBase {
+    /// Checks whether the class is an acid.
+    pub fn is_acid(&self) -> bool {
+        matches!(self, AcidBase::StrongAcid | AcidBase::WeakAcid)
+    }
+
+    /// Checks whether the class is strong.
+    pub fn is_strong(&self) -> bool {
+        matches!(self, AcidBase::StrongAcid | AcidBase::StrongBase)
+    }
+}
+
+/// Common acids and bases with their class.
+pub const ACIDS_AND_BASES: &[(&str, AcidBase)] = &[
+    ("HCl", AcidBase::StrongAcid),
+    ("HBr", AcidBase::StrongAcid),
+    ("HI", AcidBase::StrongAcid),
+    ("HNO3", AcidBase::StrongAcid),
+    ("HClO3", AcidBase::StrongAcid),
+    ("HClO4", AcidBase::StrongAcid),
+    ("H2SO4", AcidBase::StrongAcid),
+    ("HF", AcidBase::WeakAcid),
+    ("HNO2", AcidBase::WeakAcid),
+    ("HClO", AcidBase::WeakAcid),
+    ("HClO2", AcidBase::WeakAcid),
+    ("HCN", AcidBase::WeakAcid),
+    ("H2S", AcidBase::WeakAcid),
+    ("H2SO3", AcidBase::WeakAcid),
+    ("H2CO3", AcidBase::WeakAcid),
+    ("H3PO4", AcidBase::WeakAcid),
+    ("H3BO3", AcidBase::WeakAcid),
+    ("HCOOH", AcidBase::WeakAcid),
+    ("CH3COOH", AcidBase::WeakAcid),
+    ("H2C2O4", AcidBase::WeakAcid),
+    ("LiOH", AcidBase::StrongBase),
+    ("NaOH", AcidBase::StrongBase),
+    ("KOH", AcidBase::StrongBase),
+    ("RbOH", AcidBase::StrongBase),
+    ("CsOH", AcidBase::StrongBase),
+    ("Ca(OH)2", AcidBase::StrongBase),
+    ("Sr(OH)2", AcidBase::StrongBase),
+    ("Ba(OH)2", AcidBase::StrongBase),
+    ("NH3", AcidBase::WeakBase),
+];
+
+impl ChemParser {
+    /// Classifies a formula as a strong or weak acid or base, or returns `None` for neither.
+    ///
+    /// Formulas of [`ACIDS_AND_BASES`] are recognized by their composition however they are
+    /// written. Other neutral formulas written with a leading `H` followed by a nonmetal group,
+    /// such as `HBrO`, or with a trailing `COOH` are weak acids, and inorganic formulas with a
+    /// trailing `OH` or `(OH)n`, such as `Mg(OH)2`, are weak bases. Water is neither.
+    pub fn classify_acid_base(
+        &self,
+        formula: &Formula,
+    ) -> Result<Option<AcidBase>, ChemParseError> {
+        if formula.charge != 0
+            || composition_key(formula) == composition_key(&self.parse_formula("H2O")?)
+        {
+            return Ok(None);
+        }
+        for (known, class) in ACIDS_AND_BASES {
+            if composition_key(formula) == composition_key(&self.parse_formula(known)?) {
+                return Ok(Some(*class));
+            }
+        }
+        if acid_parts(&formula.formula).is_some() {
+            Ok(Some(AcidBase::WeakAcid))
+        } else if base_parts(&formula.formula).is_some() && !formula.elements.contains_key("C") {
+            Ok(Some(AcidBase::WeakBase))
+        } else {
+            Ok(None)
+        }
+    }
+
+    /// Writes the balanced neutralization of an acid by a base, such as
+    /// `H2SO4 + 2NaOH -> Na2SO4 + 2H2O`. Hydroxides give the salt and water, and ammonia gives
+    /// the ammonium salt, such as `HCl + NH3 -> NH4Cl`.
+    ///
+    /// Fails with [`ChemParseError::CannotPredict`] if the first formula is not an acid or the
+    /// second not a base by [`ChemParser::classify_acid_base`].
+    pub fn neutralize(&self, acid: &Formula, base: &Formula) -> Result<Equation, ChemParseError> {
+        let reactants = format!("{} + {}", acid.formula, base.formula);
+        let products = self.neutralization_products(acid, base)?;
+        let equation =
+            self.parse_equation(&format!("{} -> {}", reactants, products.join(" + ")))?;
+        match equation.balance(DEFAULT_MAX_COEFFICIENT)? {
+            Balance::AlreadyBalanced => Ok(equation),
+            Balance::Balanced(balanced) => Ok(balanced),
+            _ => Err(ChemParseError::CannotPredict(
+                reactants,
+                String::from("the products cannot be balanced"),
+            )),
+        }
+    }
+
+    /// Returns the products of the neutralization of an acid by a base, as written in an
+    /// equation.
+    pub(crate) fn neutralization_products(
+        &self,
+        acid: &Formula,
+        base: &Formula,
+    ) -> Result<Vec<String>, ChemParseError> {
+        let cannot = |reason: &str| {
+            ChemParseError::CannotPredict(
+                format!("{} + {}", acid.formula, base.formula),
+                String::from(reason),
+            )
+        };
+        if !self
+            .classify_acid_base(acid)?
+            .is_some_and(|class| class.is_acid())
+        {
+            return Err(cannot(&format!("{} is not an acid", acid.formula)));
+        }
+        if self
+            .classify_acid_base(base)?
+            .is_none_or(|class| class.is_acid())
+        {
+            return Err(cannot(&format!("{} is not a base", base.formula)));
+        }
+        let (anion, protons) =
+            acid_parts(&acid.formula).ok_or_else(|| cannot("the anion of the acid is unknown"))?;
+        let anion = ion(anion, protons, '-');
+        if composition_key(base) == composition_key(&self.parse_formula("NH3")?) {
+            return Ok(vec![salt_formula("NH4^+", &anion)]);
+        }
+        let (cation, hydroxides) =
+            base_parts(&base.formula).ok_or_else(|| cannot("the cation of the base is unknown"))?;
+        Ok(vec![
+            salt_formula(&ion(cation, hydroxides, '+'), &anion),
+            String::from("H2O"),
+        ])
+    }
+}
+
+/// Splits an acid written like `H2SO4` or `CH3COOH` into its anion and number of acidic
+/// hydrogens, `SO4` and 2 or `CH3COO` and 1.
+fn acid_parts(written: &str) -> Option<(&str, u32)> {
+    if let Some(anion) = written.strip_suffix("COOH") {
+        return Some((&written[..anion.len() + 3], 1));
+    }
+    let rest = written.strip_prefix('H')?;
+    let digits = rest.len() - rest.trim_start_matches(|c: char| c.is_ascii_digit()).len();
+    let protons = match digits {
+        0 => 1,
+        _ => rest[..digits].parse().ok()?,
+    };
+    let anion = &rest[digits..];
+    anion
+        .starts_with(|c: char| c.is_ascii_uppercase())
+        .then_some((anion, protons))
+}
+
+/// Splits a base written like `Ca(OH)2` or `NaOH` into its cation and number of hydroxides,
+/// `Ca` and 2 or `Na` and 1.
+fn base_parts(written: &str) -> Option<(&str, u32)> {
+    let (cation, hydroxides) = match written.rsplit_once("(OH)") {
+        Some((cation, count)) => (cation, count.parse().ok()?),
+        None => (written.strip_suffix("OH")?, 1),
+    };
+    (!cation.is_empty() && cation.starts_with(|c: char| c.is_ascii_uppercase()))
+        .then_some((cation, hydroxides))
+}
+
+/// Writes an ion from its atoms, charge magnitude and sign, such as `SO4^2-`.
+fn ion(atoms: &str, charge: u32, sign: char) -> String {
+    match charge {
+        1 => format!("{}^{}", atoms, sign),
+        _ => format!("{}^{}{}", atoms, charge, sign),
+    }
+}
@@ -0,0 +1,196 @@
+//! # Activity Module
+//!
+//! This module embeds the activity series of metals and halogens and predicts single-replacement
+//! reactions, in which a free element displaces a less active one from its compound, such as
+//! `Zn + CuSO4 -> ZnSO4 + Cu`.
+
+use crate::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use crate::element::{gcd, Equation, Formula};
+use crate::ionic::{parse_ions, rule_anions, split_salt};
+use crate::{ChemParseError, ChemParser};
+
+/// Activity series of metals and hydrogen, from the most to the least active, with the cation
+/// each forms in a single replacement.
+pub const METAL_ACTIVITY_SERIES: &[(&str, &str)] = &[
+    ("Li", "Li^+"),
+    ("K", "K^+"),
+    ("Ba", "Ba^2+"),
+    ("Sr", "Sr^2+"),
+    ("Ca", "Ca^2+"),
+    ("Na", "Na^+"),
+    ("Mg", "Mg^2+"),
+    ("Al", "Al^3+"),
+    ("Mn", "Mn^2+"),
+    ("Zn", "Zn^2+"),
+    ("Cr", "Cr^3+"),
+    ("Fe", "Fe^2+"),
+    ("Cd", "Cd^2+"),
+    ("Co", "Co^2+"),
+    ("Ni", "Ni^2+"),
+    ("Sn", "Sn^2+"),
+    ("Pb", "Pb^2+"),
+    ("H2", "H^+"),
+    ("Cu", "Cu^2+"),
+    ("Ag", "Ag^+"),
+    ("Hg", "Hg^2+"),
+    ("Pt", "Pt^2+"),
+    ("Au", "Au^3+"),
+];
+
+/// Activity series of the halogens, from the most to the least active, with the anion each forms.
+pub const HALOGEN_ACTIVITY_SERIES: &[(&str, &str)] = &[
+    ("F2", "F^-"),
+    ("Cl2", "Cl^-"),
+    ("Br2", "Br^-"),
+    ("I2", "I^-"),
+];
+
+/// Metals active enough to displace hydrogen from cold water.
+const WATER_REACTIVE_METALS: &[&str] = &["Li", "K", "Ba", "Sr", "Ca", "Na"];
+
+/// Cations that compounds are split into besides those of the metal activity series.
+const OTHER_CATIONS: &[&str] = &["NH4^+", "Fe^3+", "Cu^+", "Hg2^2+"];
+
+impl ChemParser {
+    /// Predicts the single replacement between a free metal or halogen and a compound, given in
+    /// either order, such as `Zn` and `CuSO4`. Returns the balanced equation,
+    /// `Zn + CuSO4 -> ZnSO4 + Cu`, when the free element is more active than the one it would
+    /// displace by [`METAL_ACTIVITY_SERIES`] or [`HALOGEN_ACTIVITY_SERIES`], or `None`, no
+    /// reaction, when it is less active. Metals displace hydrogen from acids, and from water
+    /// only when they are as active as sodium.
+    ///
+    /// Fails if the reactants are not an element of a series and a compound with an ion of the
+    /// same series.
+    pub fn predict_single_replacement(
+        &self,
+        reactants: &[Formula],
+    ) -> Result<Option<Equation>, ChemParseError> {
+        let written: Vec<&str> = reactants
+            .iter()
+            .map(|formula| formula.formula.as_str())
+            .collect();
+        let error = |reason: &str| {
+            ChemParseError::EvaluationError(
+                format!("single replacement of {}", written.join(" + ")),
+                String::from(reason),
+            )
+        };
+        let (element, compound) = match reactants {
+            [first, second] if is_element(first) && !is_element(second) => (first, second),
+            [first, second] if is_element(second) && !is_element(first) => (second, first),
+            _ => return Err(error("the reactants must be an element and a compound")),
+        };
+
+        let metals = self.activity_series(METAL_ACTIVITY_SERIES)?;
+        let halogens = self.activity_series(HALOGEN_ACTIVITY_SERIES)?;
+        let cations = parse_ions(
+            self,
+            METAL_ACTIVITY_SERIES
+                .iter()
+                .map(|(_, ion)| *ion)
+                .chain(OTHER_CATIONS.iter().copied()),
+        )?;
+        let anions = parse_ions(self, rule_anions())?;
+        let [(_, cation, cation_formula), (_, anion, anion_formula)] =
+            split_salt(compound, &cations, &anions)
+                .ok_or_else(|| error("the compound is not made of known ions"))?;
+
+        let (metal, free_rank, ion_rank) = if let Some(free_rank) = rank(&metals, element) {
+            (true, free_rank, rank(&metals, cation_formula))
+        } else if let Some(free_rank) = rank(&halogens, element) {
+            (false, free_rank, rank(&halogens, anion_formula))
+        } else {
+            return Err(error("the element is not in an activity series"));
+        };
+        let ion_rank = ion_rank
+            .ok_or_else(|| error("the compound has no ion of the series of the element"))?;
+        let series = if metal {
+            METAL_ACTIVITY_SERIES
+        } else {
+            HALOGEN_ACTIVITY_SERIES
+        };
+        let (free, ion) = series[free_rank];
+        let water = cation == "H^+" && anion == "OH^-";
+        if free_rank >= ion_rank || (water && !WATER_REACTIVE_METALS.contains(&free)) {
+            return Ok(None);
+        }
+
+        let salt = if metal {
+            salt_formula(ion, anion)
+        } else {
+            salt_formula(cation, ion)
+        };
+        let released = series[ion_rank].0;
+        let equation = self.parse_equation(&format!(
+            "{} -> {} + {}",
+            written.join(" + "),
+            salt,
+            released
+        ))?;
+        match equation.balance(DEFAULT_MAX_COEFFICIENT)? {
+            Balance::AlreadyBalanced => Ok(Some(equation)),
+            Balance::Balanced(balanced) => Ok(Some(balanced)),
+            _ => Err(error("the products cannot be balanced")),
+        }
+    }
+
+    /// Parses the elements and ions of an activity series.
+    pub(crate) fn activity_series(
+        &self,
+        series: &[(&'static str, &'static str)],
+    ) -> Result<Vec<(Formula, Formula)>, ChemParseError> {
+        series
+            .iter()
+            .map(|(element, ion)| Ok((self.parse_formula(element)?, self.parse_formula(ion)?)))
+            .collect()
+    }
+}
+
+/// Checks whether a formula is a neutral element, such as `Zn` or `Cl2`.
+pub(crate) fn is_element(formula: &Formula) -> bool {
+    formula.charge == 0 && formula.elements.len() == 1
+}
+
+/// Returns the position in a series of the element of a free element or an ion.
+pub(crate) fn rank(series: &[(Formula, Formula)], formula: &Formula) -> Option<usize> {
+    let mut keys = formula.elements.keys();
+    let (Some(key), None) = (keys.next(), keys.next()) else {
+        return None;
+    };
+    series
+        .iter()
+        .position(|(element, _)| element.elements.contains_key(key))
+}
+
+/// Writes the neutral compound of a cation and an anion, such as `Al2(SO4)3` for `Al^3+` and
+/// `SO4^2-`.
+pub(crate) fn salt_formula(cation: &str, anion: &str) -> String {
+    let (cation, cation_charge) = ion_parts(cation);
+    let (anion, anion_charge) = ion_parts(anion);
+    let divisor = gcd(cation_charge, anion_charge);
+    format!(
+        "{}{}",
+        ion_group(cation, anion_charge / divisor),
+        ion_group(anion, cation_charge / divisor)
+    )
+}
+
+/// Splits an ion written like `SO4^2-` into its atoms and the magnitude of its charge.
+fn ion_parts(ion: &str) -> (&str, u32) {
+    let (atoms, charge) = ion.split_once('^').unwrap_or((ion, "1"));
+    let magnitude = charge.trim_end_matches(['+', '-']).parse().unwrap_or(1);
+    (atoms, magnitude)
+}
+
+/// Writes a count of an ion in a formula, in brackets for polyatomic ions such as `(OH)2`.
+fn ion_group(atoms: &str, count: u32) -> String {
+    match count {
+        1 => atoms.to_string(),
+        _ if atoms.chars().filter(char::is_ascii_uppercase).count() == 1
+            && !atoms.contains(|c: char| c.is_ascii_digit()) =>
+        {
+            format!("{}{}", atoms, count)
+        }
+        _ => format!("({}){}", atoms, count),
+    }
+}
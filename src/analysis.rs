@@ -0,0 +1,202 @@
+//! # Analysis Module
+//!
+//! This module detects whether an input is an element, a formula or an equation, so that callers
+//! do not need to name the kind of input: [`ChemParser::parse_any`] parses it with the matching
+//! grammar, and [`ChemParser::analyze`], the single entry point for frontends such as a web demo
+//! or a chatbot, returns everything the parser knows about it in one serializable [`Analysis`].
+
+use crate::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use crate::element::{Element, Equation, Formula};
+use crate::{ChemParseError, ChemParser};
+use serde::Serialize;
+use std::collections::BTreeMap;
+
+/// Kind of input recognized by [`ChemParser::analyze`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum InputKind {
+    /// Element symbol or name, such as `Fe` or `iron`.
+    Element,
+    /// Chemical formula, such as `H2SO4`.
+    Formula,
+    /// Chemical equation, such as `2H2 + O2 -> 2H2O`.
+    Equation,
+    /// Input that could not be parsed.
+    Invalid,
+}
+
+/// Input parsed with the grammar of its detected kind.
+#[derive(Debug, Clone)]
+pub enum Parsed {
+    /// Element given by its symbol or name.
+    Element(Element),
+    /// Chemical formula.
+    Formula(Formula),
+    /// Chemical equation.
+    Equation(Equation),
+}
+
+/// Everything known about an input. Fields that do not apply to its kind are empty.
+#[derive(Debug, Clone, Serialize)]
+pub struct Analysis {
+    /// Input as given.
+    pub input: String,
+    /// Kind of the input.
+    pub kind: InputKind,
+    /// Element data of an element input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub element: Option<Element>,
+    /// Formula in Hill notation, of a formula input.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub formula: Option<String>,
+    /// Atomic mass of an element or molar mass of a formula.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub mass: Option<f64>,
+    /// Monoisotopic mass of an element or a formula, when the isotope data is known.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub monoisotopic_mass: Option<f64>,
+    /// Charge of a formula.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub charge: Option<i32>,
+    /// Number of atoms of each element or isotope of a formula.
+    #[serde(skip_serializing_if = "BTreeMap::is_empty")]
+    pub composition: BTreeMap<String, u32>,
+    /// Whether an equation conserves every element, isotope and the charge as written.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub balanced: Option<bool>,
+    /// Balanced form of an unbalanced equation, or its balanced sub-reactions when it mixes
+    /// independent reactions.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub balanced_equations: Vec<String>,
+    /// Elements or isotopes of an equation that appear on only one side, so it can never balance.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub one_sided: Vec<String>,
+    /// Message of the error of an invalid input, or of an equation that could not be balanced.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// Code of the error, see [`ChemParseError::code`].
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error_code: Option<&'static str>,
+    /// Valid symbols similar to an unknown one in the input.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub suggestions: Vec<String>,
+}
+
+impl Analysis {
+    fn new(input: &str, kind: InputKind) -> Self {
+        Analysis {
+            input: input.to_string(),
+            kind,
+            element: None,
+            formula: None,
+            mass: None,
+            monoisotopic_mass: None,
+            charge: None,
+            composition: BTreeMap::new(),
+            balanced: None,
+            balanced_equations: Vec::new(),
+            one_sided: Vec::new(),
+            error: None,
+            error_code: None,
+            suggestions: Vec::new(),
+        }
+    }
+
+    fn with_error(mut self, error: &ChemParseError) -> Self {
+        self.error = Some(error.to_string());
+        self.error_code = Some(error.code());
+        self.suggestions = match error {
+            ChemParseError::InvalidElement(_, suggestions)
+            | ChemParseError::InvalidFormula(_, _, suggestions) => suggestions.clone(),
+            _ => Vec::new(),
+        };
+        self
+    }
+}
+
+impl ChemParser {
+    /// Parses an element, a formula or an equation, detected in that order. Inputs with an arrow or
+    /// an equals sign are parsed as equations, other inputs as an element symbol or name and then
+    /// as a formula.
+    ///
+    /// When the input does not match, the error is the one of the grammar it looks most like: the
+    /// equation error for inputs with an arrow, otherwise the formula error, which suggests valid
+    /// symbols for unknown ones.
+    pub fn parse_any(&self, input: &str) -> Result<Parsed, ChemParseError> {
+        let input = input.trim();
+        if is_equation(input) {
+            return self.parse_equation(input).map(Parsed::Equation);
+        }
+        if let Ok(element) = self.parse_element(input) {
+            return Ok(Parsed::Element(element.clone()));
+        }
+        self.parse_formula(input).map(Parsed::Formula)
+    }
+
+    /// Analyzes an element, a formula or an equation, detected as by [`ChemParser::parse_any`], and
+    /// returns its masses, composition and balance, or the error with suggestions when it cannot
+    /// be parsed.
+    pub fn analyze(&self, input: &str) -> Analysis {
+        let input = input.trim();
+        match self.parse_any(input) {
+            Ok(Parsed::Element(element)) => self.analyze_element(input, &element),
+            Ok(Parsed::Formula(formula)) => self.analyze_formula(input, &formula),
+            Ok(Parsed::Equation(equation)) => self.analyze_equation(input, &equation),
+            Err(e) => Analysis::new(input, InputKind::Invalid).with_error(&e),
+        }
+    }
+
+    fn analyze_element(&self, input: &str, element: &Element) -> Analysis {
+        let mut analysis = Analysis::new(input, InputKind::Element);
+        analysis.mass = Some(element.atomic_mass);
+        analysis.monoisotopic_mass = self.get_table().get_monoisotopic_mass(&element.symbol);
+        analysis.element = Some(element.clone());
+        analysis
+    }
+
+    fn analyze_formula(&self, input: &str, formula: &Formula) -> Analysis {
+        let mut analysis = Analysis::new(input, InputKind::Formula);
+        analysis.formula = Some(formula.canonical());
+        analysis.mass = Some(formula.mass);
+        analysis.monoisotopic_mass = formula.monoisotopic_mass(self.get_table());
+        analysis.charge = Some(formula.charge);
+        analysis.composition = formula
+            .elements
+            .iter()
+            .map(|(key, count)| (key.clone(), *count))
+            .collect();
+        analysis
+    }
+
+    fn analyze_equation(&self, input: &str, equation: &Equation) -> Analysis {
+        let mut analysis = Analysis::new(input, InputKind::Equation);
+        match equation.balance(DEFAULT_MAX_COEFFICIENT) {
+            Ok(Balance::AlreadyBalanced) => analysis.balanced = Some(true),
+            Ok(Balance::Balanced(balanced)) => {
+                analysis.balanced = Some(false);
+                analysis.balanced_equations = vec![balanced.to_parseable_string()];
+            }
+            Ok(Balance::Independent(reactions)) => {
+                analysis.balanced = Some(false);
+                analysis.balanced_equations = reactions
+                    .iter()
+                    .map(Equation::to_parseable_string)
+                    .collect();
+            }
+            Ok(Balance::Impossible(keys)) => {
+                analysis.balanced = Some(false);
+                analysis.one_sided = keys;
+            }
+            Err(e) => {
+                analysis.balanced = Some(false);
+                analysis = analysis.with_error(&e);
+            }
+        }
+        analysis
+    }
+}
+
+/// Checks whether the input has a reaction arrow or an equals sign.
+pub(crate) fn is_equation(input: &str) -> bool {
+    ["->", "=", "⇌"].iter().any(|arrow| input.contains(arrow))
+}
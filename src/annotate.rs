@@ -0,0 +1,77 @@
+//! # CSV annotation Module
+//!
+//! This module implements the `annotate-csv` command, which appends computed columns
+//! (molar mass, Hill formula and element counts) to a user CSV containing chemical formulas.
+
+use chemistry_parser::progress::ProgressSink;
+use chemistry_parser::ChemParser;
+use std::collections::BTreeMap;
+use std::error::Error;
+use std::io;
+
+/// Names of the columns appended to every row.
+const ANNOTATION_COLUMNS: [&str; 3] = ["molar_mass", "hill_formula", "elements_json"];
+
+/// Reads the CSV at `input`, parses the formula in `column` of every row and writes the rows
+/// with the annotation columns appended to `output`, or to stdout when no output path is given.
+/// Rows whose formula cannot be parsed get empty annotation columns and a warning on stderr.
+/// Progress is reported by the position in the input file. Returns the number of annotated rows.
+pub fn annotate_csv(
+    parser: &ChemParser,
+    input: &str,
+    column: &str,
+    output: Option<&str>,
+    progress: &mut dyn ProgressSink,
+) -> Result<usize, Box<dyn Error>> {
+    let mut reader = csv::ReaderBuilder::new()
+        .has_headers(true)
+        .from_path(input)?;
+    progress.start(std::fs::metadata(input)?.len());
+    let mut writer: csv::Writer<Box<dyn io::Write>> = match output {
+        Some(path) => csv::Writer::from_writer(Box::new(std::fs::File::create(path)?)),
+        None => csv::Writer::from_writer(Box::new(io::stdout())),
+    };
+
+    let headers = reader.headers()?.clone();
+    let column_id = headers
+        .iter()
+        .position(|header| header == column)
+        .ok_or_else(|| format!("Column '{}' not found in {}", column, input))?;
+
+    let mut new_headers = headers.clone();
+    for header in ANNOTATION_COLUMNS {
+        new_headers.push_field(header);
+    }
+    writer.write_record(&new_headers)?;
+
+    let mut rows = 0;
+    let mut record = csv::StringRecord::new();
+    for i in 0.. {
+        if !reader.read_record(&mut record)? {
+            break;
+        }
+        let formula = record.get(column_id).unwrap_or("").trim().to_string();
+        let parsed = parser.parse_formula(&formula);
+        progress.advance(reader.position().byte(), parsed.is_err());
+        match parsed {
+            Ok(parsed_formula) => {
+                let counts: BTreeMap<&String, &u32> = parsed_formula.elements.iter().collect();
+                record.push_field(&format!("{:.4}", parsed_formula.mass));
+                record.push_field(&parsed_formula.canonical());
+                record.push_field(&serde_json::to_string(&counts)?);
+            }
+            Err(e) => {
+                eprintln!("Warning: row {}: {}", i + 2, e);
+                for _ in ANNOTATION_COLUMNS {
+                    record.push_field("");
+                }
+            }
+        }
+        writer.write_record(&record)?;
+        rows += 1;
+    }
+
+    writer.flush()?;
+    progress.finish();
+    Ok(rows)
+}
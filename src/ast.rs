@@ -0,0 +1,459 @@
+//! # Syntax tree Module
+//!
+//! This module defines a typed syntax tree of formulas and equations, with the byte span of every
+//! node in the input, so that editors and linters can point at the parts of an input without
+//! walking the pairs of the grammar themselves.
+//!
+//! The tree follows the input as written: element symbols are not checked against a periodic
+//! table, and groups keep their nesting instead of being flattened into a composition.
+
+use crate::diagnostic::Diagnostic;
+use crate::element::{isotope_key, Arrow, Coefficient, State};
+use crate::options::GrammarOptions;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{charge_value, normalize_with_offsets, syntax_error, ChemParseError, ChemParser, Rule};
+use core::ops::Range;
+use pest::iterators::Pair;
+use pest::Parser;
+
+/// Syntax tree of a chemical formula, such as `CuSO4·5H2O`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct FormulaAst {
+    /// Elements, groups and charges of the formula in the order they are written.
+    pub nodes: Vec<FormulaNode>,
+    /// Molecules joined to the formula with a dot, such as the water of hydration.
+    pub adducts: Vec<AdductNode>,
+    /// Byte range of the formula in the input.
+    pub span: Range<usize>,
+}
+
+/// Part of a formula: an element, a bracketed group or a charge.
+#[derive(Debug, Clone, PartialEq)]
+pub enum FormulaNode {
+    /// An element or isotope with its count, such as `H2` or `[13C]`.
+    Element(ElementNode),
+    /// A group in round brackets or a complex in square brackets, such as `(OH)2`.
+    Group(GroupNode),
+    /// A charge, such as `^2-` in `SO4^2-`.
+    Charge(ChargeNode),
+}
+
+/// An element or isotope of a formula with its count.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ElementNode {
+    /// Element symbol as written, such as `C` in `[13C]`, or `D` and `T` for hydrogen isotopes.
+    pub symbol: String,
+    /// Mass number of an isotope written with one, such as 13 for `[13C]`.
+    pub mass_number: Option<u16>,
+    /// Number of atoms written after the symbol, 1 when there is no index.
+    pub count: u32,
+    /// Byte range of the symbol in the input, including the mass number of an isotope.
+    pub symbol_span: Range<usize>,
+    /// Byte range of the index in the input, if it is written.
+    pub count_span: Option<Range<usize>>,
+    /// Byte range of the symbol and its index in the input.
+    pub span: Range<usize>,
+}
+
+impl ElementNode {
+    /// Returns the key the element is counted under in a formula composition, such as `13C`.
+    pub fn key(&self) -> String {
+        match self.mass_number {
+            Some(mass_number) => isotope_key(&self.symbol, mass_number),
+            None => self.symbol.clone(),
+        }
+    }
+}
+
+/// Kind of brackets enclosing a group of a formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupKind {
+    /// A group in round brackets, such as `(NH4)`.
+    Group,
+    /// A coordination complex in square brackets, such as `[Fe(CN)6]`.
+    Complex,
+}
+
+/// A bracketed group of a formula with its multiplier.
+#[derive(Debug, Clone, PartialEq)]
+pub struct GroupNode {
+    /// Kind of brackets enclosing the group.
+    pub kind: GroupKind,
+    /// Formula inside the brackets.
+    pub formula: FormulaAst,
+    /// Multiplier written after the group, 1 when there is no index.
+    pub count: u32,
+    /// Whether the group is the repeat unit of a polymer, written with `n` as its index.
+    pub repeat: bool,
+    /// Byte range of the index in the input, if it is written.
+    pub count_span: Option<Range<usize>>,
+    /// Byte range of the group and its index in the input.
+    pub span: Range<usize>,
+}
+
+/// A charge written in a formula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ChargeNode {
+    /// Signed value of the charge, such as -2 for `^2-`.
+    pub value: i32,
+    /// Byte range of the charge in the input.
+    pub span: Range<usize>,
+}
+
+/// A molecule joined to a formula with a dot, such as `5H2O` in `CuSO4·5H2O`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AdductNode {
+    /// Number of molecules, 1 when there is no count.
+    pub count: u32,
+    /// Formula of the molecule.
+    pub formula: FormulaAst,
+    /// Byte range of the adduct in the input, including the dot.
+    pub span: Range<usize>,
+}
+
+impl FormulaAst {
+    /// Parses a formula into its syntax tree. Unicode subscripts and superscripts are accepted,
+    /// and spans refer to the input as given.
+    ///
+    /// Fails with [`ChemParseError::SyntaxError`] if the input is not a formula, or with
+    /// [`ChemParseError::InvalidIndexFormat`] if a count does not fit into 32 bits.
+    pub fn parse(input: &str) -> Result<Self, ChemParseError> {
+        let (normalized, offsets) = normalize_with_offsets(input);
+        let mut pairs = ChemParser::parse(Rule::formula, &normalized)
+            .map_err(|e| syntax_error("formula", &normalized, &e))?;
+        match pairs.next() {
+            Some(pair) => formula_ast(pair, &offsets),
+            None => Err(ChemParseError::ParsingError(
+                String::from("formula"),
+                String::from(input),
+            )),
+        }
+    }
+
+    /// Returns the total charge written in the formula, not counting its groups.
+    pub fn charge(&self) -> i32 {
+        self.nodes
+            .iter()
+            .map(|node| match node {
+                FormulaNode::Charge(charge) => charge.value,
+                _ => 0,
+            })
+            .sum()
+    }
+}
+
+/// Syntax tree of a chemical equation, such as `2H2 + O2 -> 2H2O`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct EquationAst {
+    /// Terms of the reactant side in the order they are written.
+    pub reactants: Vec<TermNode>,
+    /// Arrow separating reactants and products.
+    pub arrow: ArrowNode,
+    /// Reaction conditions over the arrow or in a note after the products, in their order.
+    pub conditions: Vec<ConditionNode>,
+    /// Terms of the product side in the order they are written.
+    pub products: Vec<TermNode>,
+    /// Byte range of the equation in the input.
+    pub span: Range<usize>,
+}
+
+/// The arrow of an equation.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ArrowNode {
+    /// Kind of the arrow.
+    pub arrow: Arrow,
+    /// Byte range of the arrow in the input.
+    pub span: Range<usize>,
+}
+
+/// A reaction condition of an equation, such as `Fe` in `->[Fe, 450°C]`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ConditionNode {
+    /// Text of the condition.
+    pub text: String,
+    /// Byte range of the condition in the input.
+    pub span: Range<usize>,
+}
+
+/// Species of an equation term.
+#[derive(Debug, Clone, PartialEq)]
+pub enum SpeciesNode {
+    /// A chemical formula.
+    Formula(FormulaAst),
+    /// An electron of a half-reaction, written as `e^-` or `e-`.
+    Electron(Range<usize>),
+    /// A photon of a photochemical reaction, written as `hν` or `hv`.
+    Photon(Range<usize>),
+    /// Heat absorbed or released, written as `heat` or `Δ`.
+    Heat(Range<usize>),
+}
+
+/// A term of an equation side: a species with its coefficient and state.
+#[derive(Debug, Clone, PartialEq)]
+pub struct TermNode {
+    /// Coefficient of the term, 1 when it is not written. Fractions such as `1/2` and decimal
+    /// numbers such as `0.5` are kept as written, in lowest terms.
+    pub coefficient: Coefficient,
+    /// Byte range of the coefficient in the input, if it is written.
+    pub coefficient_span: Option<Range<usize>>,
+    /// Species of the term.
+    pub species: SpeciesNode,
+    /// Physical state of the species with its byte range in the input, if it is written.
+    pub state: Option<(State, Range<usize>)>,
+    /// Byte range of the term in the input.
+    pub span: Range<usize>,
+}
+
+impl EquationAst {
+    /// Parses an equation into its syntax tree. Unicode subscripts and superscripts are accepted,
+    /// and spans refer to the input as given. The input is parsed as the grammar defines it,
+    /// without the tolerances of [`crate::options::ParseOptions`].
+    ///
+    /// Fails with [`ChemParseError::SyntaxError`] if the input is not an equation, or with
+    /// [`ChemParseError::InvalidCoefficientFormat`] or [`ChemParseError::InvalidIndexFormat`]
+    /// if a number does not fit into 32 bits or a coefficient is zero.
+    pub fn parse(input: &str) -> Result<Self, ChemParseError> {
+        EquationAst::parse_with_grammar(input, &GrammarOptions::default())
+    }
+
+    /// Parses an equation into its syntax tree like [`EquationAst::parse`], accepting the
+    /// additional tokens of grammar options, such as `==>` for an arrow. Spans and the positions
+    /// of syntax errors refer to the input as given, tokens included.
+    pub fn parse_with_grammar(
+        input: &str,
+        grammar: &GrammarOptions,
+    ) -> Result<Self, ChemParseError> {
+        let (normalized, offsets) = grammar.normalize_with_offsets(input);
+        let mut pairs = ChemParser::parse(Rule::equation, &normalized).map_err(|e| {
+            ChemParseError::SyntaxError(
+                String::from("equation"),
+                Box::new(Diagnostic::from_pest(&normalized, &e).map_to_original(input, &offsets)),
+            )
+        })?;
+        let equation = pairs.next().ok_or_else(|| {
+            ChemParseError::ParsingError(String::from("equation"), String::from(input))
+        })?;
+
+        let span = original_span(&equation, &offsets);
+        let mut reactants = Vec::new();
+        let mut products = Vec::new();
+        let mut arrow = ArrowNode {
+            arrow: Arrow::default(),
+            span: span.start..span.start,
+        };
+        let mut conditions = Vec::new();
+        for part in equation.into_inner() {
+            match part.as_rule() {
+                Rule::reactants => reactants = side_ast(part, &offsets)?,
+                Rule::products => products = side_ast(part, &offsets)?,
+                Rule::arrow => {
+                    arrow = ArrowNode {
+                        arrow: Arrow::from_symbol(part.as_str()).unwrap_or_default(),
+                        span: original_span(&part, &offsets),
+                    }
+                }
+                Rule::conditions | Rule::note => {
+                    conditions.extend(part.into_inner().map(|condition| ConditionNode {
+                        text: condition.as_str().trim().to_string(),
+                        span: original_span(&condition, &offsets),
+                    }))
+                }
+                _ => {}
+            }
+        }
+
+        Ok(EquationAst {
+            reactants,
+            arrow,
+            conditions,
+            products,
+            span,
+        })
+    }
+}
+
+/// Returns the byte range of a pair in the original input.
+fn original_span(pair: &Pair<Rule>, offsets: &[usize]) -> Range<usize> {
+    let span = pair.as_span();
+    offsets[span.start()]..offsets[span.end()]
+}
+
+/// Parses the number of a pair, failing with the given error when it does not fit.
+fn number(pair: &Pair<Rule>, error: fn(String) -> ChemParseError) -> Result<u32, ChemParseError> {
+    pair.as_str()
+        .parse()
+        .map_err(|_| error(pair.as_str().to_string()))
+}
+
+/// Builds the syntax tree of a `formula` or `adduct_formula` pair.
+fn formula_ast(formula: Pair<Rule>, offsets: &[usize]) -> Result<FormulaAst, ChemParseError> {
+    let span = original_span(&formula, offsets);
+    let mut nodes = Vec::new();
+    let mut adducts = Vec::new();
+    let mut pairs = formula.into_inner().peekable();
+    while let Some(pair) = pairs.next() {
+        let mut pair_span = original_span(&pair, offsets);
+        match pair.as_rule() {
+            Rule::element | Rule::isotope => {
+                let (symbol, mass_number) = match pair.as_rule() {
+                    Rule::isotope => isotope_parts(&pair)?,
+                    _ => (pair.as_str().to_string(), None),
+                };
+                let symbol_span = pair_span.clone();
+                let index = pairs.next_if(|next| next.as_rule() == Rule::index);
+                let count = match &index {
+                    Some(index) => number(index, ChemParseError::InvalidIndexFormat)?,
+                    None => 1,
+                };
+                let count_span = index.map(|index| original_span(&index, offsets));
+                if let Some(count_span) = &count_span {
+                    pair_span.end = count_span.end;
+                }
+                nodes.push(FormulaNode::Element(ElementNode {
+                    symbol,
+                    mass_number,
+                    count,
+                    symbol_span,
+                    count_span,
+                    span: pair_span,
+                }));
+            }
+            Rule::group | Rule::complex => {
+                let kind = match pair.as_rule() {
+                    Rule::group => GroupKind::Group,
+                    _ => GroupKind::Complex,
+                };
+                let inner = pair.into_inner().next().ok_or_else(|| {
+                    ChemParseError::ParsingError(String::from("group"), String::new())
+                })?;
+                let formula = formula_ast(inner, offsets)?;
+                let index = pairs
+                    .next_if(|next| matches!(next.as_rule(), Rule::index | Rule::repeat_index));
+                let repeat = index
+                    .as_ref()
+                    .is_some_and(|index| index.as_rule() == Rule::repeat_index);
+                let count = match &index {
+                    Some(index) if !repeat => number(index, ChemParseError::InvalidIndexFormat)?,
+                    _ => 1,
+                };
+                let count_span = index.map(|index| original_span(&index, offsets));
+                if let Some(count_span) = &count_span {
+                    pair_span.end = count_span.end;
+                }
+                nodes.push(FormulaNode::Group(GroupNode {
+                    kind,
+                    formula,
+                    count,
+                    repeat,
+                    count_span,
+                    span: pair_span,
+                }));
+            }
+            Rule::charge => nodes.push(FormulaNode::Charge(ChargeNode {
+                value: charge_value(&pair)?,
+                span: pair_span,
+            })),
+            Rule::adduct => {
+                let mut count = 1;
+                let mut adduct_formula = None;
+                for inner in pair.into_inner() {
+                    match inner.as_rule() {
+                        Rule::adduct_count => {
+                            count = number(&inner, ChemParseError::InvalidIndexFormat)?
+                        }
+                        Rule::adduct_formula => adduct_formula = Some(formula_ast(inner, offsets)?),
+                        _ => {}
+                    }
+                }
+                if let Some(formula) = adduct_formula {
+                    adducts.push(AdductNode {
+                        count,
+                        formula,
+                        span: pair_span,
+                    });
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(FormulaAst {
+        nodes,
+        adducts,
+        span,
+    })
+}
+
+/// Returns the element symbol and mass number of an `isotope` pair.
+fn isotope_parts(isotope: &Pair<Rule>) -> Result<(String, Option<u16>), ChemParseError> {
+    let mut symbol = String::new();
+    let mut mass_number = None;
+    for inner in isotope.clone().into_inner() {
+        match inner.as_rule() {
+            Rule::element => symbol = inner.as_str().to_string(),
+            Rule::mass_number => {
+                mass_number =
+                    Some(inner.as_str().parse().map_err(|_| {
+                        ChemParseError::InvalidIsotope(isotope.as_str().to_string())
+                    })?)
+            }
+            _ => {}
+        }
+    }
+    Ok((symbol, mass_number))
+}
+
+/// Builds the terms of a `reactants` or `products` pair.
+fn side_ast(side: Pair<Rule>, offsets: &[usize]) -> Result<Vec<TermNode>, ChemParseError> {
+    let mut terms: Vec<TermNode> = Vec::new();
+    let mut coefficient: Option<(Coefficient, Range<usize>)> = None;
+    for pair in side.into_inner() {
+        let pair_span = original_span(&pair, offsets);
+        match pair.as_rule() {
+            Rule::coefficient => {
+                coefficient = Some((pair.as_str().parse()?, pair_span));
+            }
+            Rule::formula | Rule::electron | Rule::photon | Rule::heat => {
+                let species = match pair.as_rule() {
+                    Rule::formula => SpeciesNode::Formula(formula_ast(pair, offsets)?),
+                    Rule::photon => SpeciesNode::Photon(pair_span.clone()),
+                    Rule::heat => SpeciesNode::Heat(pair_span.clone()),
+                    _ => SpeciesNode::Electron(pair_span.clone()),
+                };
+                let (value, coefficient_span) = match coefficient.take() {
+                    Some((value, span)) => (value, Some(span)),
+                    None => (
+                        Coefficient {
+                            numerator: 1,
+                            denominator: 1,
+                        },
+                        None,
+                    ),
+                };
+                let start = coefficient_span
+                    .as_ref()
+                    .map_or(pair_span.start, |span| span.start);
+                terms.push(TermNode {
+                    coefficient: value,
+                    coefficient_span,
+                    species,
+                    state: None,
+                    span: start..pair_span.end,
+                });
+            }
+            Rule::state => {
+                let state = pair
+                    .clone()
+                    .into_inner()
+                    .next()
+                    .and_then(|symbol| State::from_symbol(symbol.as_str()));
+                if let (Some(term), Some(state)) = (terms.last_mut(), state) {
+                    term.span.end = pair_span.end;
+                    term.state = Some((state, pair_span));
+                }
+            }
+            _ => {}
+        }
+    }
+    Ok(terms)
+}
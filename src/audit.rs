@@ -0,0 +1,230 @@
+//! # Audit log Module
+//!
+//! This module prints the results of CLI commands and appends them to an audit log,
+//! one JSON object per line with a timestamp, the command, its input, the result and its provenance.
+//! Failed lines of batch commands can also be written to a separate error log.
+
+use chemistry_parser::provenance::Provenance;
+use chemistry_parser::ChemParseError;
+use chrono::{SecondsFormat, Utc};
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::fs::{File, OpenOptions};
+use std::io::{self, Write};
+use std::process;
+
+/// Represents a single line of the audit log.
+#[derive(Serialize)]
+struct AuditEntry<'a> {
+    timestamp: String,
+    command: &'a str,
+    input: &'a str,
+    result: &'a str,
+    status: &'a str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    code: Option<&'a str>,
+    provenance: &'a Provenance,
+}
+
+/// Returns the JSON schema of a line of the audit log.
+pub fn audit_entry_schema() -> Value {
+    json!({
+        "$schema": "https://json-schema.org/draft/2020-12/schema",
+        "title": "audit_entry",
+        "type": "object",
+        "properties": {
+            "timestamp": { "type": "string", "format": "date-time" },
+            "command": { "type": "string" },
+            "input": { "type": "string" },
+            "result": { "type": "string" },
+            "status": { "enum": ["ok", "error"] },
+            "code": { "type": "string" },
+            "provenance": { "type": "object", "description": "See the provenance schema" }
+        },
+        "required": ["timestamp", "command", "input", "result", "status", "provenance"]
+    })
+}
+
+/// Format in which the `symbol`, `formula`, `equation` and `file` commands print their results.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum OutputFormat {
+    /// Human-readable text in the language of the interface.
+    #[default]
+    Plain,
+    /// JSON, one object per result, and one per line for the `file` command.
+    Json,
+    /// CSV with a header row.
+    Csv,
+}
+
+impl OutputFormat {
+    /// Returns the format with the given name, `plain`, `json` or `csv`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name.to_lowercase().as_str() {
+            "plain" | "text" => Some(OutputFormat::Plain),
+            "json" => Some(OutputFormat::Json),
+            "csv" => Some(OutputFormat::Csv),
+            _ => None,
+        }
+    }
+}
+
+/// Writes the fields of a record as a CSV line, quoted where needed, without the line break.
+pub fn csv_line<S: AsRef<[u8]>>(fields: &[S]) -> String {
+    let mut writer = csv::Writer::from_writer(Vec::new());
+    let bytes = writer
+        .write_record(fields)
+        .ok()
+        .and_then(|_| writer.into_inner().ok())
+        .unwrap_or_default();
+    String::from_utf8_lossy(&bytes).trim_end().to_string()
+}
+
+/// Prints command results and records them in the audit log when one is open.
+pub struct Output {
+    log: Option<File>,
+    command: String,
+    provenance: Provenance,
+    primary: Option<String>,
+}
+
+impl Output {
+    /// Creates a new Output for the given command, appending to the log file at `log_path` if given.
+    /// Every log entry carries the provenance of the results.
+    pub fn new(
+        log_path: Option<&str>,
+        command: &[&str],
+        provenance: Provenance,
+    ) -> io::Result<Self> {
+        let log = match log_path {
+            Some(path) => Some(OpenOptions::new().create(true).append(true).open(path)?),
+            None => None,
+        };
+        Ok(Output {
+            log,
+            command: command.first().copied().unwrap_or("help").to_string(),
+            provenance,
+            primary: None,
+        })
+    }
+
+    /// Returns the provenance recorded with every result.
+    pub fn provenance(&self) -> &Provenance {
+        &self.provenance
+    }
+
+    /// Returns the main result of the command, such as a balanced equation or a molar mass, which
+    /// is the last printed result unless a command sets it with [`Output::set_primary`].
+    pub fn primary(&self) -> Option<&str> {
+        self.primary.as_deref()
+    }
+
+    /// Sets the main result of the command.
+    pub fn set_primary(&mut self, primary: &str) {
+        self.primary = Some(primary.to_string());
+    }
+
+    /// Prints a result to stdout and records it. The process ends quietly when stdout is a pipe
+    /// that has been closed, such as by `head`, as there is no one left to read the results.
+    pub fn print(&mut self, input: &str, result: &str) {
+        let mut stdout = io::stdout().lock();
+        if let Err(e) = writeln!(stdout, "{}", result).and_then(|_| stdout.flush()) {
+            if e.kind() == io::ErrorKind::BrokenPipe {
+                process::exit(0);
+            }
+        }
+        self.primary = Some(result.to_string());
+        self.record(input, result, true);
+    }
+
+    /// Prints an error to stderr and records it.
+    pub fn error(&mut self, input: &str, error: &str) {
+        eprintln!("{}", error);
+        self.record(input, error, false);
+    }
+
+    /// Prints the message of a library error to stderr and records it with the error code.
+    pub fn fail(&mut self, input: &str, message: &str, error: &ChemParseError) {
+        eprintln!("{}", message);
+        self.record_failure(input, message, error);
+    }
+
+    /// Records the message of a library error with the error code without printing it.
+    pub fn record_failure(&mut self, input: &str, message: &str, error: &ChemParseError) {
+        self.write_entry(input, message, false, Some(error.code()));
+    }
+
+    /// Records a result in the audit log without printing it.
+    pub fn record(&mut self, input: &str, result: &str, success: bool) {
+        self.write_entry(input, result, success, None);
+    }
+
+    fn write_entry(&mut self, input: &str, result: &str, success: bool, code: Option<&str>) {
+        let Some(log) = self.log.as_mut() else {
+            return;
+        };
+
+        let entry = AuditEntry {
+            timestamp: Utc::now().to_rfc3339_opts(SecondsFormat::Millis, true),
+            command: &self.command,
+            input,
+            result,
+            status: if success { "ok" } else { "error" },
+            code,
+            provenance: &self.provenance,
+        };
+        let line = serde_json::to_string(&entry).unwrap_or_default();
+        if let Err(e) = writeln!(log, "{}", line) {
+            eprintln!("Failed to write audit log: {}", e);
+        }
+    }
+}
+
+/// Represents a failed line of a batch input in the error log.
+#[derive(Serialize)]
+struct FailedLine<'a> {
+    line: usize,
+    input: &'a str,
+    error: String,
+    code: &'static str,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    column: Option<usize>,
+    #[serde(skip_serializing_if = "<[String]>::is_empty")]
+    expected: &'a [String],
+}
+
+/// Writes the failed lines of a batch input, one JSON object per line, so they can be fixed and run again.
+pub struct ErrorLog {
+    file: File,
+}
+
+impl ErrorLog {
+    /// Creates the error log at `path`, appending to it when a job resumes instead of starting over.
+    pub fn open(path: &str, append: bool) -> io::Result<Self> {
+        let file = OpenOptions::new()
+            .create(true)
+            .write(true)
+            .append(append)
+            .truncate(!append)
+            .open(path)?;
+        Ok(ErrorLog { file })
+    }
+
+    /// Writes a failed line with its number, its text, the error and its code, including the column and the
+    /// expected rules of syntax errors.
+    pub fn write(&mut self, line: usize, input: &str, error: &ChemParseError) -> io::Result<()> {
+        let diagnostic = match error {
+            ChemParseError::SyntaxError(_, diagnostic) => Some(diagnostic),
+            _ => None,
+        };
+        let entry = FailedLine {
+            line,
+            input,
+            error: error.to_string(),
+            code: error.code(),
+            column: diagnostic.map(|diagnostic| diagnostic.column),
+            expected: diagnostic.map_or(&[], |diagnostic| &diagnostic.expected),
+        };
+        writeln!(self.file, "{}", serde_json::to_string(&entry)?)
+    }
+}
@@ -0,0 +1,343 @@
+//! # Balancing Module
+//!
+//! This module balances chemical equations with the smallest positive integer coefficients that
+//! conserve every element, isotope and the charge.
+
+use crate::element::{Equation, Term};
+use crate::ChemParseError;
+use std::collections::BTreeSet;
+
+/// Default upper bound of a coefficient when balancing an equation.
+pub const DEFAULT_MAX_COEFFICIENT: u32 = 1000;
+
+/// Largest number of terms searched for independent reactions, one bit per term of a subset.
+const MAX_SEARCHED_TERMS: usize = 20;
+
+/// Outcome of balancing a chemical equation.
+#[derive(Debug, Clone)]
+pub enum Balance {
+    /// The coefficients of the equation already conserve every element, isotope and the charge.
+    AlreadyBalanced,
+    /// The equation with the smallest balancing coefficients.
+    Balanced(Equation),
+    /// The equation mixes independent reactions that balance separately, so it has no single
+    /// solution; every balanced combination of it is a sum of these reactions.
+    Independent(Vec<Equation>),
+    /// The equation can never be balanced because these elements or isotopes appear on only one
+    /// side, which usually means a typo.
+    Impossible(Vec<String>),
+}
+
+impl Equation {
+    /// Balances the equation with the smallest positive integer coefficients, each at most
+    /// `max_coefficient`, that conserve every element, isotope and the charge.
+    ///
+    /// Returns [`Balance::AlreadyBalanced`] when the written coefficients conserve everything,
+    /// [`Balance::Independent`] when the equation mixes several reactions (see [`Equation::sub_reactions`])
+    /// and [`Balance::Impossible`] when an element appears on only one side; other equations that have
+    /// no solution within the cap are reported as [`ChemParseError::BalancingError`].
+    ///
+    /// The conservation equations are reduced exactly, and the remaining free coefficient is searched
+    /// up to the cap, so the smallest solution is found even when the straightforward one has huge
+    /// coefficients. Catalysts are not part of the balance, and the arrow and conditions are kept.
+    /// Photons and heat are left out of the balance and kept at the end of their side.
+    pub fn balance(&self, max_coefficient: u32) -> Result<Balance, ChemParseError> {
+        if let Some(matter) = self.without_energy() {
+            return Ok(match matter.balance(max_coefficient)? {
+                Balance::Balanced(equation) => Balance::Balanced(self.restore_energy(&equation)),
+                Balance::Independent(reactions) => Balance::Independent(
+                    reactions
+                        .iter()
+                        .map(|reaction| self.restore_energy(reaction))
+                        .collect(),
+                ),
+                balance => balance,
+            });
+        }
+        let one_sided = one_sided_keys(&self.reactant_terms, &self.product_terms);
+        if !one_sided.is_empty() {
+            return Ok(Balance::Impossible(one_sided));
+        }
+
+        let terms = self.terms();
+        let matrix = conservation_matrix(&terms, self.reactant_terms.len());
+        let conserved = matrix.iter().all(|row| {
+            row.iter()
+                .zip(&terms)
+                .map(|(count, term)| count * term.coefficient as i128)
+                .sum::<i128>()
+                == 0
+        });
+        if conserved {
+            return Ok(Balance::AlreadyBalanced);
+        }
+
+        if free_columns(&matrix, terms.len()) > 1 {
+            return self
+                .sub_reactions(max_coefficient)
+                .map(Balance::Independent);
+        }
+        let coefficients = smallest_coefficients(matrix, max_coefficient)
+            .map_err(|reason| ChemParseError::BalancingError(self.equation.clone(), reason))?;
+        Ok(Balance::Balanced(self.with_coefficients(&coefficients)))
+    }
+
+    /// Returns the elementary reactions contained in the equation, each balanced with the smallest
+    /// coefficients up to `max_coefficient` and keeping its terms on their original sides.
+    ///
+    /// An elementary reaction uses a subset of the terms that balances in exactly one way, so no smaller
+    /// subset balances on its own. For `H2 + O2 -> H2O + H2O2` these are `2H2 + O2 -> 2H2O` and
+    /// `H2 + O2 -> H2O2`. An ordinary equation contains itself as its only reaction.
+    pub fn sub_reactions(&self, max_coefficient: u32) -> Result<Vec<Equation>, ChemParseError> {
+        if let Some(matter) = self.without_energy() {
+            return Ok(matter
+                .sub_reactions(max_coefficient)?
+                .iter()
+                .map(|reaction| self.restore_energy(reaction))
+                .collect());
+        }
+        let terms = self.terms();
+        if terms.len() > MAX_SEARCHED_TERMS {
+            return Err(ChemParseError::BalancingError(
+                self.equation.clone(),
+                format!(
+                    "more than {} terms to search for independent reactions",
+                    MAX_SEARCHED_TERMS
+                ),
+            ));
+        }
+        let matrix = conservation_matrix(&terms, self.reactant_terms.len());
+
+        let mut reactions = Vec::new();
+        for subset in 1u32..(1 << terms.len()) {
+            let columns: Vec<usize> = (0..terms.len())
+                .filter(|column| subset >> column & 1 == 1)
+                .collect();
+            let submatrix: Vec<Vec<i128>> = matrix
+                .iter()
+                .map(|row| columns.iter().map(|&column| row[column]).collect())
+                .collect();
+            if free_columns(&submatrix, columns.len()) != 1 {
+                continue;
+            }
+            if let Ok(values) = smallest_coefficients(submatrix, max_coefficient) {
+                let mut coefficients = vec![0; terms.len()];
+                for (&column, value) in columns.iter().zip(values) {
+                    coefficients[column] = value;
+                }
+                reactions.push(self.with_coefficients(&coefficients));
+            }
+        }
+
+        if reactions.is_empty() {
+            return Err(ChemParseError::BalancingError(
+                self.equation.clone(),
+                format!(
+                    "no positive integer coefficients up to {} conserve every element and the charge",
+                    max_coefficient
+                ),
+            ));
+        }
+        Ok(reactions)
+    }
+
+    /// Returns the reactant terms followed by the product terms.
+    pub(crate) fn terms(&self) -> Vec<&Term> {
+        self.reactant_terms
+            .iter()
+            .chain(&self.product_terms)
+            .collect()
+    }
+
+    /// Returns the equation without its photon and heat terms, or `None` if it has none.
+    fn without_energy(&self) -> Option<Equation> {
+        let matter = |terms: &[Term]| -> Vec<Term> {
+            terms
+                .iter()
+                .filter(|term| !term.formula.is_energy())
+                .cloned()
+                .collect()
+        };
+        self.terms()
+            .iter()
+            .any(|term| term.formula.is_energy())
+            .then(|| self.with_terms(matter(&self.reactant_terms), matter(&self.product_terms)))
+    }
+
+    /// Adds the photon and heat terms of this equation to the end of the sides of a reaction
+    /// balanced without them.
+    fn restore_energy(&self, reaction: &Equation) -> Equation {
+        let side = |balanced: &[Term], written: &[Term]| -> Vec<Term> {
+            balanced
+                .iter()
+                .chain(written.iter().filter(|term| term.formula.is_energy()))
+                .cloned()
+                .collect()
+        };
+        reaction.with_terms(
+            side(&reaction.reactant_terms, &self.reactant_terms),
+            side(&reaction.product_terms, &self.product_terms),
+        )
+    }
+
+    /// Returns a copy of the equation with new coefficients for the reactant and then the product
+    /// terms, leaving out terms with a zero coefficient.
+    fn with_coefficients(&self, coefficients: &[u32]) -> Equation {
+        let mut coefficients = coefficients.iter();
+        let mut side = |terms: &[Term]| -> Vec<Term> {
+            terms
+                .iter()
+                .zip(coefficients.by_ref())
+                .filter(|(_, coefficient)| **coefficient > 0)
+                .map(|(term, coefficient)| Term {
+                    coefficient: *coefficient,
+                    formula: term.formula.clone(),
+                    state: term.state,
+                })
+                .collect()
+        };
+        let reactant_terms = side(&self.reactant_terms);
+        let product_terms = side(&self.product_terms);
+
+        let mut equation = Equation::from_terms(String::new(), reactant_terms, product_terms)
+            .with_arrow(self.arrow)
+            .with_conditions(self.conditions.clone(), self.catalysts.clone());
+        equation.equation = equation.to_parseable_string();
+        equation
+    }
+}
+
+/// Returns the composition keys that appear on only one side of the equation, in alphabetical order.
+fn one_sided_keys(reactants: &[Term], products: &[Term]) -> Vec<String> {
+    let keys = |terms: &[Term]| -> BTreeSet<String> {
+        terms
+            .iter()
+            .flat_map(|term| term.formula.elements.keys().cloned())
+            .collect()
+    };
+    keys(reactants)
+        .symmetric_difference(&keys(products))
+        .cloned()
+        .collect()
+}
+
+/// Builds one row per composition key, and one for the charge if any term is charged, with a column
+/// per term. Reactant counts are positive and product counts negative.
+fn conservation_matrix(terms: &[&Term], reactants: usize) -> Vec<Vec<i128>> {
+    let sign = |column: usize| if column < reactants { 1 } else { -1 };
+    let keys: BTreeSet<&String> = terms
+        .iter()
+        .flat_map(|term| term.formula.elements.keys())
+        .collect();
+
+    let mut matrix: Vec<Vec<i128>> = keys
+        .into_iter()
+        .map(|key| {
+            terms
+                .iter()
+                .enumerate()
+                .map(|(column, term)| {
+                    sign(column) * term.formula.elements.get(key).copied().unwrap_or(0) as i128
+                })
+                .collect()
+        })
+        .collect();
+    if terms.iter().any(|term| term.formula.charge != 0) {
+        matrix.push(
+            terms
+                .iter()
+                .enumerate()
+                .map(|(column, term)| sign(column) * term.formula.charge as i128)
+                .collect(),
+        );
+    }
+    matrix
+}
+
+/// Returns the number of columns of the matrix that are not pivots after reduction, which is the
+/// number of independent solutions of `matrix * x = 0`.
+fn free_columns(matrix: &[Vec<i128>], columns: usize) -> usize {
+    let mut reduced = matrix.to_vec();
+    columns - reduce(&mut reduced, columns).len()
+}
+
+/// Finds the smallest positive integer solution of `matrix * x = 0`, whose entries are at most
+/// `max_coefficient`, for a matrix with one free column, or describes why there is none.
+fn smallest_coefficients(
+    mut matrix: Vec<Vec<i128>>,
+    max_coefficient: u32,
+) -> Result<Vec<u32>, String> {
+    let columns = matrix.first().map_or(0, Vec::len);
+    let pivots = reduce(&mut matrix, columns);
+    let Some(free) = (0..columns).find(|column| !pivots.contains(column)) else {
+        return Err(String::from(
+            "only zero coefficients conserve every element and the charge",
+        ));
+    };
+
+    // Every solution is a multiple of one direction, so the first free value that makes all the
+    // pivot coefficients positive integers gives the smallest solution.
+    let cap = max_coefficient as i128;
+    for value in 1..=cap {
+        let mut solution = vec![0i128; columns];
+        solution[free] = value;
+        let solved = pivots.iter().enumerate().all(|(row, &pivot)| {
+            let rest = matrix[row][free] * value;
+            solution[pivot] = -rest / matrix[row][pivot];
+            rest % matrix[row][pivot] == 0 && (1..=cap).contains(&solution[pivot])
+        });
+        if solved {
+            return Ok(solution.into_iter().map(|value| value as u32).collect());
+        }
+    }
+
+    Err(format!(
+        "no positive integer coefficients up to {} conserve every element and the charge",
+        max_coefficient
+    ))
+}
+
+/// Reduces the matrix to reduced row echelon form with integer arithmetic and returns the pivot
+/// column of each leading row.
+pub(crate) fn reduce(matrix: &mut [Vec<i128>], columns: usize) -> Vec<usize> {
+    let mut pivots = Vec::new();
+    for column in 0..columns {
+        let rank = pivots.len();
+        let Some(row) = (rank..matrix.len()).find(|&row| matrix[row][column] != 0) else {
+            continue;
+        };
+        matrix.swap(rank, row);
+
+        let pivot_row = matrix[rank].clone();
+        for (other, row) in matrix.iter_mut().enumerate() {
+            let factor = row[column];
+            if other == rank || factor == 0 {
+                continue;
+            }
+            for (value, pivot_value) in row.iter_mut().zip(&pivot_row) {
+                *value = *value * pivot_row[column] - pivot_value * factor;
+            }
+            normalize_row(row);
+        }
+        pivots.push(column);
+    }
+    pivots
+}
+
+/// Divides a row by the greatest common divisor of its entries to keep the integers small.
+fn normalize_row(row: &mut [i128]) {
+    let divisor = row
+        .iter()
+        .fold(0, |divisor, &value| gcd(divisor, value.abs()));
+    if divisor > 1 {
+        row.iter_mut().for_each(|value| *value /= divisor);
+    }
+}
+
+pub(crate) fn gcd(a: i128, b: i128) -> i128 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
@@ -0,0 +1,71 @@
+//! # Bond Module
+//!
+//! This module predicts the type of the bond between two elements from the difference of their
+//! Pauling electronegativities.
+
+use crate::element::Element;
+use crate::ChemParseError;
+use serde::Serialize;
+use std::fmt::{self, Display, Formatter};
+
+/// Electronegativity difference from which a bond is polar covalent.
+pub const POLAR_THRESHOLD: f64 = 0.4;
+
+/// Electronegativity difference from which a bond is ionic.
+pub const IONIC_THRESHOLD: f64 = 1.7;
+
+/// Type of a chemical bond between two elements.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub enum BondType {
+    /// Electrons transferred from one atom to the other, such as in `NaCl`.
+    Ionic,
+    /// Electrons shared unequally, such as between hydrogen and oxygen.
+    PolarCovalent,
+    /// Electrons shared about equally, such as between carbon and hydrogen.
+    NonpolarCovalent,
+}
+
+impl Display for BondType {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let name = match self {
+            BondType::Ionic => "ionic",
+            BondType::PolarCovalent => "polar covalent",
+            BondType::NonpolarCovalent => "nonpolar covalent",
+        };
+        write!(f, "{}", name)
+    }
+}
+
+impl BondType {
+    /// Returns the bond type for an electronegativity difference: nonpolar covalent below
+    /// [`POLAR_THRESHOLD`], ionic from [`IONIC_THRESHOLD`] and polar covalent between them.
+    pub fn from_difference(difference: f64) -> BondType {
+        let difference = difference.abs();
+        if difference >= IONIC_THRESHOLD {
+            BondType::Ionic
+        } else if difference >= POLAR_THRESHOLD {
+            BondType::PolarCovalent
+        } else {
+            BondType::NonpolarCovalent
+        }
+    }
+}
+
+/// Predicts the type of the bond between two elements from their electronegativity difference,
+/// such as [`BondType::Ionic`] for sodium and chlorine.
+///
+/// Fails with [`ChemParseError::MissingData`] if the electronegativity of an element is not
+/// known, as for the lighter noble gases.
+pub fn predict_bond_type(a: &Element, b: &Element) -> Result<BondType, ChemParseError> {
+    match (a.electronegativity, b.electronegativity) {
+        (Some(first), Some(second)) => Ok(BondType::from_difference(first - second)),
+        _ => Err(ChemParseError::MissingData(
+            String::from("electronegativity"),
+            [a, b]
+                .iter()
+                .filter(|element| element.electronegativity.is_none())
+                .map(|element| element.symbol.clone())
+                .collect(),
+        )),
+    }
+}
@@ -0,0 +1,118 @@
+//! # Builder Module
+//!
+//! This module builds equations programmatically from coefficients and formulas, for example
+//! `EquationBuilder::new().reactant(2, "H2").reactant(1, "O2").product(2, "H2O").build(&parser)`,
+//! so that tools generating reactions do not have to format strings and parse them again.
+
+use crate::element::{Arrow, Equation, Formula, Term};
+use crate::{ChemParseError, ChemParser};
+
+/// Formula of a term, either still to be parsed or already parsed.
+#[derive(Debug, Clone)]
+enum Species {
+    Written(String),
+    Parsed(Formula),
+}
+
+/// Builder of an equation from its terms.
+#[derive(Debug, Clone, Default)]
+pub struct EquationBuilder {
+    reactants: Vec<(u32, Species)>,
+    products: Vec<(u32, Species)>,
+    arrow: Arrow,
+    conditions: Vec<String>,
+}
+
+impl EquationBuilder {
+    /// Creates a builder of an equation without terms and with an irreversible arrow.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a reactant written as a formula, such as `H2SO4` or `SO4^2-`.
+    pub fn reactant(mut self, coefficient: u32, formula: &str) -> Self {
+        self.reactants
+            .push((coefficient, Species::Written(formula.to_string())));
+        self
+    }
+
+    /// Adds a product written as a formula, such as `H2SO4` or `SO4^2-`.
+    pub fn product(mut self, coefficient: u32, formula: &str) -> Self {
+        self.products
+            .push((coefficient, Species::Written(formula.to_string())));
+        self
+    }
+
+    /// Adds an already parsed formula, such as [`Formula::electron`], as a reactant.
+    pub fn reactant_formula(mut self, coefficient: u32, formula: Formula) -> Self {
+        self.reactants.push((coefficient, Species::Parsed(formula)));
+        self
+    }
+
+    /// Adds an already parsed formula, such as [`Formula::electron`], as a product.
+    pub fn product_formula(mut self, coefficient: u32, formula: Formula) -> Self {
+        self.products.push((coefficient, Species::Parsed(formula)));
+        self
+    }
+
+    /// Sets the kind of arrow separating reactants and products.
+    pub fn arrow(mut self, arrow: Arrow) -> Self {
+        self.arrow = arrow;
+        self
+    }
+
+    /// Adds a reaction condition written over the arrow, such as `450°C`. Conditions that are
+    /// formulas, such as `Fe`, are catalysts, as in parsed equations.
+    pub fn condition(mut self, condition: &str) -> Self {
+        self.conditions.push(condition.to_string());
+        self
+    }
+
+    /// Parses the written formulas with the parser and builds the equation, in the order the terms
+    /// were added. Fails if a formula cannot be parsed, a coefficient is zero or a side has no terms.
+    pub fn build(&self, parser: &ChemParser) -> Result<Equation, ChemParseError> {
+        let reactant_terms = build_side(parser, &self.reactants)?;
+        let product_terms = build_side(parser, &self.products)?;
+        if reactant_terms.is_empty() || product_terms.is_empty() {
+            return Err(ChemParseError::ParsingError(
+                String::from("equation"),
+                String::from("both sides need at least one term"),
+            ));
+        }
+
+        let catalysts = self
+            .conditions
+            .iter()
+            .filter(|condition| parser.is_catalyst(condition))
+            .cloned()
+            .collect();
+        let mut equation = Equation::from_terms(String::new(), reactant_terms, product_terms)
+            .with_arrow(self.arrow)
+            .with_conditions(self.conditions.clone(), catalysts);
+        equation.equation = equation.to_parseable_string();
+        Ok(equation)
+    }
+}
+
+/// Parses the formulas of one side into terms.
+fn build_side(parser: &ChemParser, side: &[(u32, Species)]) -> Result<Vec<Term>, ChemParseError> {
+    side.iter()
+        .map(|(coefficient, species)| {
+            let formula = match species {
+                Species::Written(formula) => parser.parse_formula(formula)?,
+                Species::Parsed(formula) => formula.clone(),
+            };
+            if *coefficient == 0 {
+                return Err(ChemParseError::InvalidCoefficientFormat(format!(
+                    "0{}",
+                    formula.formula
+                )));
+            }
+            Ok(Term {
+                coefficient: *coefficient,
+                formula,
+                state: None,
+            })
+        })
+        .collect()
+}
@@ -0,0 +1,172 @@
+//! # Formula cache Module
+//!
+//! This module keeps the formulas a parser has parsed in a least-recently-used cache keyed by
+//! the input, so that formulas repeated across a large equation file, such as `H2O` in nearly
+//! every line, are parsed and their masses summed only once.
+
+use crate::element::Formula;
+use crate::{ChemParseError, ChemParser};
+use serde::Serialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::{Arc, Mutex, PoisonError};
+
+/// Statistics of a formula cache, for tuning its capacity.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct CacheStats {
+    /// Number of formulas found in the cache.
+    pub hits: u64,
+    /// Number of formulas that had to be parsed.
+    pub misses: u64,
+    /// Number of formulas removed to make room for others.
+    pub evictions: u64,
+    /// Number of formulas in the cache.
+    pub len: usize,
+    /// Largest number of formulas the cache holds.
+    pub capacity: usize,
+}
+
+impl CacheStats {
+    /// Returns the share of lookups found in the cache, from 0 to 1, or 0 before any lookup.
+    pub fn hit_rate(&self) -> f64 {
+        let lookups = self.hits + self.misses;
+        if lookups == 0 {
+            0.0
+        } else {
+            self.hits as f64 / lookups as f64
+        }
+    }
+}
+
+/// Least-recently-used cache of parsed formulas, shared by the clones of a parser.
+#[derive(Debug)]
+pub(crate) struct FormulaCache {
+    capacity: usize,
+    state: Mutex<CacheState>,
+}
+
+/// Entries of a cache with the time of their last use, and its statistics.
+#[derive(Debug, Default)]
+struct CacheState {
+    entries: HashMap<String, (Formula, u64)>,
+    recency: BTreeMap<u64, String>,
+    clock: u64,
+    stats: CacheStats,
+}
+
+impl FormulaCache {
+    /// Creates an empty cache holding up to `capacity` formulas.
+    pub(crate) fn new(capacity: usize) -> Self {
+        FormulaCache {
+            capacity,
+            state: Mutex::new(CacheState::default()),
+        }
+    }
+
+    /// Returns the formula cached for an input, or parses it with `parse` and caches the result.
+    /// Inputs that fail to parse are not cached.
+    pub(crate) fn get_or_parse(
+        &self,
+        input: &str,
+        parse: impl FnOnce() -> Result<Formula, ChemParseError>,
+    ) -> Result<Formula, ChemParseError> {
+        if let Some(formula) = self.lock().get(input) {
+            return Ok(formula);
+        }
+        // The lock is not held while parsing, so that threads parsing other formulas do not wait.
+        let formula = parse()?;
+        self.lock().insert(input, formula.clone(), self.capacity);
+        Ok(formula)
+    }
+
+    /// Returns the statistics of the cache.
+    pub(crate) fn stats(&self) -> CacheStats {
+        let state = self.lock();
+        CacheStats {
+            len: state.entries.len(),
+            capacity: self.capacity,
+            ..state.stats
+        }
+    }
+
+    /// Removes every formula from the cache and resets its statistics.
+    pub(crate) fn clear(&self) {
+        *self.lock() = CacheState::default();
+    }
+
+    fn lock(&self) -> std::sync::MutexGuard<'_, CacheState> {
+        self.state.lock().unwrap_or_else(PoisonError::into_inner)
+    }
+}
+
+impl CacheState {
+    /// Returns a cached formula and marks it as the most recently used.
+    fn get(&mut self, input: &str) -> Option<Formula> {
+        self.clock += 1;
+        let clock = self.clock;
+        match self.entries.get_mut(input) {
+            Some((formula, last_used)) => {
+                self.recency.remove(last_used);
+                self.recency.insert(clock, input.to_string());
+                *last_used = clock;
+                self.stats.hits += 1;
+                Some(formula.clone())
+            }
+            None => {
+                self.stats.misses += 1;
+                None
+            }
+        }
+    }
+
+    /// Caches a formula, removing the least recently used ones beyond the capacity.
+    fn insert(&mut self, input: &str, formula: Formula, capacity: usize) {
+        if capacity == 0 {
+            return;
+        }
+        self.clock += 1;
+        if let Some((_, last_used)) = self
+            .entries
+            .insert(input.to_string(), (formula, self.clock))
+        {
+            self.recency.remove(&last_used);
+        }
+        self.recency.insert(self.clock, input.to_string());
+        while self.entries.len() > capacity {
+            let Some((_, oldest)) = self.recency.pop_first() else {
+                break;
+            };
+            self.entries.remove(&oldest);
+            self.stats.evictions += 1;
+        }
+    }
+}
+
+impl ChemParser {
+    /// Caches up to `capacity` parsed formulas by their input, so that repeated formulas are
+    /// parsed once, including those of equations. The cache is shared by the clones of the
+    /// parser and starts empty again when its options change.
+    pub fn with_formula_cache(mut self, capacity: usize) -> Self {
+        self.formula_cache = Some(Arc::new(FormulaCache::new(capacity)));
+        self
+    }
+
+    /// Returns the statistics of the formula cache, or `None` if the parser has none.
+    pub fn cache_stats(&self) -> Option<CacheStats> {
+        self.formula_cache.as_ref().map(|cache| cache.stats())
+    }
+
+    /// Removes every formula from the formula cache, if any, and resets its statistics.
+    pub fn clear_formula_cache(&self) {
+        if let Some(cache) = &self.formula_cache {
+            cache.clear();
+        }
+    }
+
+    /// Starts an empty formula cache of the same capacity, if the parser has one, for when the
+    /// way formulas are parsed changes.
+    pub(crate) fn reset_formula_cache(&mut self) {
+        if let Some(cache) = &self.formula_cache {
+            self.formula_cache = Some(Arc::new(FormulaCache::new(cache.capacity)));
+        }
+    }
+}
@@ -0,0 +1,449 @@
+//! # Calculation Module
+//!
+//! This module evaluates arithmetic expressions that combine numbers, quantities with units and
+//! formula functions, for example `mass(H2SO4) * 2 + mass(H2O)` or `5 g of NaCl in 250 mL -> molarity`,
+//! and keeps named variables of a calculation session.
+
+use crate::element::Formula;
+use crate::{normalize_scripts, syntax_error, ChemParseError, ChemParser, Rule};
+use pest::iterators::Pair;
+use pest::Parser;
+use std::collections::HashMap;
+use std::error::Error;
+use std::fmt;
+use std::fs;
+
+/// Names that cannot be used as variables.
+const RESERVED_NAMES: [&str; 13] = [
+    "let", "mass", "atoms", "of", "in", "molarity", "g", "kg", "mg", "mol", "mmol", "mL", "mM",
+];
+
+/// Represents the dimension of a quantity as exponents of mass, amount of substance and volume.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub struct Dimension {
+    /// Exponent of mass.
+    pub mass: i32,
+    /// Exponent of amount of substance.
+    pub amount: i32,
+    /// Exponent of volume.
+    pub volume: i32,
+}
+
+impl Dimension {
+    /// Dimension of plain numbers.
+    pub const NONE: Dimension = Dimension::new(0, 0, 0);
+    /// Dimension of mass, measured in grams.
+    pub const MASS: Dimension = Dimension::new(1, 0, 0);
+    /// Dimension of amount of substance, measured in moles.
+    pub const AMOUNT: Dimension = Dimension::new(0, 1, 0);
+    /// Dimension of volume, measured in liters.
+    pub const VOLUME: Dimension = Dimension::new(0, 0, 1);
+    /// Dimension of molar mass, measured in grams per mole.
+    pub const MOLAR_MASS: Dimension = Dimension::new(1, -1, 0);
+    /// Dimension of molar concentration, measured in moles per liter.
+    pub const CONCENTRATION: Dimension = Dimension::new(0, 1, -1);
+
+    /// Creates a new Dimension from the exponents of mass, amount of substance and volume.
+    pub const fn new(mass: i32, amount: i32, volume: i32) -> Self {
+        Dimension {
+            mass,
+            amount,
+            volume,
+        }
+    }
+
+    fn combine(self, other: Dimension, sign: i32) -> Dimension {
+        Dimension::new(
+            self.mass + sign * other.mass,
+            self.amount + sign * other.amount,
+            self.volume + sign * other.volume,
+        )
+    }
+}
+
+impl fmt::Display for Dimension {
+    /// Writes the dimension in base units, for example `g / mol`.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let parts = [("g", self.mass), ("mol", self.amount), ("L", self.volume)];
+        let numerator: Vec<&str> = parts
+            .iter()
+            .flat_map(|(unit, exponent)| {
+                std::iter::repeat_n(*unit, exponent.max(&0).unsigned_abs() as usize)
+            })
+            .collect();
+        let denominator: Vec<&str> = parts
+            .iter()
+            .flat_map(|(unit, exponent)| {
+                std::iter::repeat_n(*unit, exponent.min(&0).unsigned_abs() as usize)
+            })
+            .collect();
+
+        write!(f, "{}", numerator.join(" * "))?;
+        for unit in denominator {
+            write!(f, " / {}", unit)?;
+        }
+        Ok(())
+    }
+}
+
+/// Represents a unit of measurement accepted in calculations.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Unit {
+    /// Symbol of the unit.
+    pub name: &'static str,
+    /// Size of the unit in base units (g, mol, L).
+    pub factor: f64,
+    /// Dimension measured by the unit.
+    pub dimension: Dimension,
+}
+
+/// Units accepted in calculations.
+pub const UNITS: [Unit; 9] = [
+    Unit {
+        name: "g",
+        factor: 1.0,
+        dimension: Dimension::MASS,
+    },
+    Unit {
+        name: "kg",
+        factor: 1000.0,
+        dimension: Dimension::MASS,
+    },
+    Unit {
+        name: "mg",
+        factor: 0.001,
+        dimension: Dimension::MASS,
+    },
+    Unit {
+        name: "mol",
+        factor: 1.0,
+        dimension: Dimension::AMOUNT,
+    },
+    Unit {
+        name: "mmol",
+        factor: 0.001,
+        dimension: Dimension::AMOUNT,
+    },
+    Unit {
+        name: "L",
+        factor: 1.0,
+        dimension: Dimension::VOLUME,
+    },
+    Unit {
+        name: "mL",
+        factor: 0.001,
+        dimension: Dimension::VOLUME,
+    },
+    Unit {
+        name: "M",
+        factor: 1.0,
+        dimension: Dimension::CONCENTRATION,
+    },
+    Unit {
+        name: "mM",
+        factor: 0.001,
+        dimension: Dimension::CONCENTRATION,
+    },
+];
+
+/// Returns the unit with the given symbol, accepting `molarity` for `M`.
+pub fn find_unit(name: &str) -> Option<&'static Unit> {
+    let name = if name == "molarity" { "M" } else { name };
+    UNITS.iter().find(|unit| unit.name == name)
+}
+
+/// Represents the result of a calculation: a value in base units with its dimension.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Quantity {
+    /// Value in base units (g, mol, L).
+    pub value: f64,
+    /// Dimension of the value.
+    pub dimension: Dimension,
+    /// Unit the quantity is displayed in, set by a `->` conversion or by a number written with a unit.
+    pub unit: Option<&'static Unit>,
+}
+
+impl Quantity {
+    /// Creates a new Quantity from a value in base units and its dimension.
+    pub fn new(value: f64, dimension: Dimension) -> Self {
+        Quantity {
+            value,
+            dimension,
+            unit: None,
+        }
+    }
+
+    /// Returns the value in the display unit, or in base units when no unit was chosen.
+    pub fn magnitude(&self) -> f64 {
+        self.unit
+            .map_or(self.value, |unit| self.value / unit.factor)
+    }
+}
+
+impl fmt::Display for Quantity {
+    /// Writes the magnitude followed by its unit, for example `0.3422 M` or `58.44 g / mol`.
+    /// The precision of the formatter applies to the magnitude.
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match f.precision() {
+            Some(precision) => write!(f, "{:.*}", precision, self.magnitude())?,
+            None => write!(f, "{}", self.magnitude())?,
+        }
+        match self.unit {
+            Some(unit) => write!(f, " {}", unit.name),
+            None if self.dimension == Dimension::NONE => Ok(()),
+            None => write!(f, " {}", self.dimension),
+        }
+    }
+}
+
+/// Represents the value of a session variable.
+#[derive(Debug, Clone)]
+pub enum Variable {
+    /// A chemical formula, usable as the argument of `mass` and `atoms` and after `of`.
+    Formula(Formula),
+    /// The result of a calculation.
+    Quantity(Quantity),
+}
+
+impl fmt::Display for Variable {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Variable::Formula(formula) => write!(f, "{}", formula.formula),
+            Variable::Quantity(quantity) => write!(f, "{}", quantity),
+        }
+    }
+}
+
+/// Represents a calculation session with named variables.
+pub struct Calculator<'a> {
+    parser: &'a ChemParser,
+    variables: HashMap<String, Variable>,
+}
+
+impl<'a> Calculator<'a> {
+    /// Creates a new Calculator session without variables.
+    pub fn new(parser: &'a ChemParser) -> Self {
+        Calculator {
+            parser,
+            variables: HashMap::new(),
+        }
+    }
+
+    /// Returns the value of a variable.
+    pub fn get(&self, name: &str) -> Option<&Variable> {
+        self.variables.get(name)
+    }
+
+    /// Executes an assignment such as `let aspirin = C9H8O4` or evaluates an expression.
+    /// Returns the value of an expression, or `None` for an assignment.
+    pub fn execute(&mut self, line: &str) -> Result<Option<Quantity>, ChemParseError> {
+        let line = &normalize_scripts(line);
+
+        if let Ok(mut assignment) = ChemParser::parse(Rule::calc_assignment, line) {
+            let mut inner = assignment.next().unwrap().into_inner();
+            let name = inner.next().unwrap().as_str();
+            if RESERVED_NAMES.contains(&name) {
+                return Err(ChemParseError::EvaluationError(
+                    String::from(line),
+                    format!("{} is a reserved name", name),
+                ));
+            }
+
+            let value = inner.next().unwrap();
+            let variable = match value.as_rule() {
+                Rule::formula => Variable::Formula(self.parser.parse_formula(value.as_str())?),
+                _ => Variable::Quantity(evaluate(self.parser, value, line, &self.variables)?),
+            };
+            self.variables.insert(name.to_string(), variable);
+            return Ok(None);
+        }
+
+        let mut expression_parse = ChemParser::parse(Rule::calculation, line)
+            .map_err(|e| syntax_error("expression", line, &e))?;
+        let expression = expression_parse
+            .next()
+            .unwrap()
+            .into_inner()
+            .next()
+            .unwrap();
+        evaluate(self.parser, expression, line, &self.variables).map(Some)
+    }
+
+    /// Loads variables from a session file written by [`Calculator::save`].
+    pub fn load(&mut self, path: &str) -> Result<(), Box<dyn Error>> {
+        for line in fs::read_to_string(path)?.lines() {
+            if !line.trim().is_empty() {
+                self.execute(line)?;
+            }
+        }
+        Ok(())
+    }
+
+    /// Saves variables to a session file as `let` assignments, one per line.
+    pub fn save(&self, path: &str) -> Result<(), Box<dyn Error>> {
+        let mut names: Vec<&String> = self.variables.keys().collect();
+        names.sort();
+
+        let content: String = names
+            .into_iter()
+            .map(|name| format!("let {} = {}\n", name, self.variables[name]))
+            .collect();
+        fs::write(path, content)?;
+        Ok(())
+    }
+}
+
+/// Evaluates a parsed `calc_expression`, `calc_sum`, `calc_product` or `calc_factor` pair.
+pub(crate) fn evaluate(
+    parser: &ChemParser,
+    pair: Pair<Rule>,
+    expression: &str,
+    variables: &HashMap<String, Variable>,
+) -> Result<Quantity, ChemParseError> {
+    let evaluation_error =
+        |reason: String| ChemParseError::EvaluationError(String::from(expression), reason);
+
+    match pair.as_rule() {
+        Rule::calc_expression => {
+            let mut inner = pair.into_inner();
+            let mut quantity = evaluate(parser, inner.next().unwrap(), expression, variables)?;
+            if let Some(target) = inner.next() {
+                let unit = find_unit(target.as_str()).unwrap();
+                if quantity.dimension != unit.dimension {
+                    return Err(evaluation_error(format!(
+                        "cannot convert {} to {}",
+                        quantity, unit.name
+                    )));
+                }
+                quantity.unit = Some(unit);
+            }
+            Ok(quantity)
+        }
+        Rule::calc_sum | Rule::calc_product => {
+            let mut inner = pair.into_inner();
+            let mut value = evaluate(parser, inner.next().unwrap(), expression, variables)?;
+            while let (Some(operator), Some(operand)) = (inner.next(), inner.next()) {
+                let operand = evaluate(parser, operand, expression, variables)?;
+                value = match operator.as_str() {
+                    "+" | "-" if value.dimension != operand.dimension => {
+                        return Err(evaluation_error(format!(
+                            "cannot combine {} and {}",
+                            value, operand
+                        )))
+                    }
+                    "+" => Quantity::new(value.value + operand.value, value.dimension),
+                    "-" => Quantity::new(value.value - operand.value, value.dimension),
+                    "*" => Quantity::new(
+                        value.value * operand.value,
+                        value.dimension.combine(operand.dimension, 1),
+                    ),
+                    _ if operand.value == 0.0 => {
+                        return Err(evaluation_error(String::from("division by zero")))
+                    }
+                    _ => Quantity::new(
+                        value.value / operand.value,
+                        value.dimension.combine(operand.dimension, -1),
+                    ),
+                };
+            }
+            Ok(value)
+        }
+        Rule::calc_factor => {
+            let mut inner = pair.into_inner();
+            let quantity = evaluate(parser, inner.next().unwrap(), expression, variables)?;
+            let Some(substance) = inner.next() else {
+                return Ok(quantity);
+            };
+
+            let molar_mass = formula_argument(parser, substance, expression, variables)?.mass;
+            match quantity.dimension {
+                Dimension::MASS => Ok(Quantity::new(
+                    quantity.value / molar_mass,
+                    Dimension::AMOUNT,
+                )),
+                Dimension::AMOUNT => {
+                    Ok(Quantity::new(quantity.value * molar_mass, Dimension::MASS))
+                }
+                _ => Err(evaluation_error(format!(
+                    "{} is neither a mass nor an amount of substance",
+                    quantity
+                ))),
+            }
+        }
+        Rule::calc_negation => {
+            let quantity = evaluate(
+                parser,
+                pair.into_inner().next().unwrap(),
+                expression,
+                variables,
+            )?;
+            Ok(Quantity::new(-quantity.value, quantity.dimension))
+        }
+        Rule::calc_quantity => {
+            let mut inner = pair.into_inner();
+            let value: f64 = inner.next().unwrap().as_str().parse().unwrap();
+            match inner.next() {
+                Some(unit) => {
+                    let unit = find_unit(unit.as_str()).unwrap();
+                    Ok(Quantity {
+                        unit: Some(unit),
+                        ..Quantity::new(value * unit.factor, unit.dimension)
+                    })
+                }
+                None => Ok(Quantity::new(value, Dimension::NONE)),
+            }
+        }
+        Rule::calc_unit => {
+            let unit = find_unit(pair.as_str()).unwrap();
+            Ok(Quantity::new(unit.factor, unit.dimension))
+        }
+        Rule::calc_variable => match variables.get(pair.as_str()) {
+            Some(Variable::Quantity(quantity)) => Ok(*quantity),
+            Some(Variable::Formula(_)) => Err(evaluation_error(format!(
+                "{0} is a formula, use mass({0})",
+                pair.as_str()
+            ))),
+            None => Err(evaluation_error(format!(
+                "unknown variable {}",
+                pair.as_str()
+            ))),
+        },
+        Rule::calc_function => {
+            let mut inner = pair.into_inner();
+            let name = inner.next().unwrap().as_str();
+            let formula = formula_argument(parser, inner.next().unwrap(), expression, variables)?;
+            match name {
+                "mass" => Ok(Quantity::new(formula.mass, Dimension::MOLAR_MASS)),
+                _ => Ok(Quantity::new(
+                    formula.elements.values().map(|count| *count as f64).sum(),
+                    Dimension::NONE,
+                )),
+            }
+        }
+        _ => unreachable!("unexpected rule in calculation: {:?}", pair.as_rule()),
+    }
+}
+
+/// Resolves a `formula` or formula `calc_variable` pair to a parsed formula.
+fn formula_argument(
+    parser: &ChemParser,
+    argument: Pair<Rule>,
+    expression: &str,
+    variables: &HashMap<String, Variable>,
+) -> Result<Formula, ChemParseError> {
+    if argument.as_rule() == Rule::formula {
+        return parser.parse_formula(argument.as_str());
+    }
+    let name = argument.as_str();
+    match variables.get(name) {
+        Some(Variable::Formula(formula)) => Ok(formula.clone()),
+        Some(Variable::Quantity(_)) => Err(ChemParseError::EvaluationError(
+            String::from(expression),
+            format!("{} is not a formula", name),
+        )),
+        None => Err(ChemParseError::EvaluationError(
+            String::from(expression),
+            format!("unknown variable {}", name),
+        )),
+    }
+}
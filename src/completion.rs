@@ -0,0 +1,148 @@
+//! # Completion Module
+//!
+//! This module supports interactive frontends with completion of the word being typed, from
+//! commands, element symbols and names, with the `datasets` feature ion names and with the
+//! `compounds` feature compound names, and with the highlighting of valid and invalid element
+//! symbols in the input.
+
+use crate::ChemParser;
+use serde::Serialize;
+use std::ops::Range;
+
+/// Candidate of a completion, with the text to show in a list and the text that replaces the word.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Candidate {
+    /// Text shown to the user, such as `sulfate (SO4^2-)`.
+    pub display: String,
+    /// Text that replaces the completed word, such as `SO4^2-`.
+    pub replacement: String,
+}
+
+/// Completion of the word at the end of a line.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Completion {
+    /// Byte position in the line where the completed word starts.
+    pub start: usize,
+    /// Candidates to replace the word with, in a stable order.
+    pub candidates: Vec<Candidate>,
+}
+
+/// Kind of a highlighted part of the input.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Highlight {
+    /// Symbol of an element of the table.
+    Valid,
+    /// Symbol-like text that is not an element of the table.
+    Invalid,
+}
+
+impl ChemParser {
+    /// Completes the word at the end of a line. The first word of a line completes to the given
+    /// commands too. Lowercase words complete to element names and ion formulas by name, so that
+    /// `sulf` offers `SO4^2-`, and other words complete their last element symbol, so that `H2S`
+    /// offers `H2S`, `H2Sb`, `H2Sc` and so on.
+    pub fn complete(&self, line: &str, commands: &[&str]) -> Completion {
+        let start = line
+            .char_indices()
+            .rev()
+            .find(|(_, c)| c.is_whitespace())
+            .map_or(0, |(position, c)| position + c.len_utf8());
+        let word = &line[start..];
+        let mut candidates = Vec::new();
+        if line[..start].trim().is_empty() {
+            candidates.extend(
+                commands
+                    .iter()
+                    .filter(|command| command.starts_with(word))
+                    .map(|command| plain(command)),
+            );
+        }
+        if word.is_empty() {
+            return Completion { start, candidates };
+        }
+
+        if word.chars().all(|c| c.is_ascii_lowercase()) {
+            let mut names: Vec<String> = self
+                .get_table()
+                .iter()
+                .map(|element| element.name.to_lowercase())
+                .filter(|name| name.starts_with(word))
+                .collect();
+            names.sort();
+            candidates.extend(names.iter().map(|name| plain(name)));
+            #[cfg(feature = "datasets")]
+            candidates.extend(
+                crate::names::named_ions()
+                    .iter()
+                    .filter(|(name, _)| name.starts_with(word))
+                    .map(|(name, formula)| Candidate {
+                        display: format!("{} ({})", name, formula),
+                        replacement: formula.clone(),
+                    }),
+            );
+            #[cfg(feature = "compounds")]
+            candidates.extend(
+                crate::compounds::CompoundDb::embedded()
+                    .find_by_name_prefix(word)
+                    .into_iter()
+                    .map(|compound| Candidate {
+                        display: format!("{} ({})", compound.name, compound.formula),
+                        replacement: compound.formula.clone(),
+                    }),
+            );
+        } else if let Some(symbol_start) = word.rfind(|c: char| c.is_ascii_uppercase()) {
+            let partial = &word[symbol_start..];
+            if partial.chars().all(|c| c.is_ascii_alphabetic()) {
+                let mut symbols: Vec<&str> = self
+                    .get_table()
+                    .iter()
+                    .map(|element| element.symbol.as_str())
+                    .filter(|symbol| symbol.starts_with(partial))
+                    .collect();
+                symbols.sort();
+                candidates.extend(
+                    symbols
+                        .into_iter()
+                        .map(|symbol| plain(&format!("{}{}", &word[..symbol_start], symbol))),
+                );
+            }
+        }
+        Completion { start, candidates }
+    }
+
+    /// Returns the byte ranges of the element symbols in the input, an uppercase letter followed by
+    /// lowercase letters, with whether the table has them as elements or isotopes such as `D`, for example `Cl` as valid and `Xx` as
+    /// invalid in `NaCl + Xx`. Lowercase words, such as element names, are not highlighted.
+    pub fn highlight(&self, input: &str) -> Vec<(Range<usize>, Highlight)> {
+        let mut spans = Vec::new();
+        let mut chars = input.char_indices().peekable();
+        while let Some((start, c)) = chars.next() {
+            if !c.is_ascii_uppercase() {
+                continue;
+            }
+            let mut end = start + 1;
+            while let Some(&(position, next)) = chars.peek() {
+                if !next.is_ascii_lowercase() {
+                    break;
+                }
+                end = position + 1;
+                chars.next();
+            }
+            let highlight = match self.get_table().get_mass(&input[start..end]) {
+                Some(_) => Highlight::Valid,
+                None => Highlight::Invalid,
+            };
+            spans.push((start..end, highlight));
+        }
+        spans
+    }
+}
+
+/// Creates a candidate that is shown as it is inserted.
+fn plain(text: &str) -> Candidate {
+    Candidate {
+        display: text.to_string(),
+        replacement: text.to_string(),
+    }
+}
@@ -0,0 +1,191 @@
+//! # Compounds Module
+//!
+//! This module holds a database of common compounds with their names, formulas, molar masses and
+//! categories, embedded from `data/compounds.csv`, for looking compounds up by formula and for
+//! completing their names as they are typed.
+
+use crate::element::{composition_key, Formula};
+use crate::{ChemParseError, ChemParser};
+use serde::{Deserialize, Serialize};
+use std::error::Error;
+use std::sync::OnceLock;
+
+/// Compounds embedded at compile time from `data/compounds.csv`.
+const COMPOUNDS_CSV: &str = include_str!("../data/compounds.csv");
+
+/// Compound as stored in a database file.
+#[derive(Deserialize)]
+struct CompoundRecord {
+    name: String,
+    formula: String,
+    category: Option<String>,
+}
+
+/// Represents a known compound.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Compound {
+    /// Common name of the compound, such as `sodium chloride`.
+    pub name: String,
+    /// Formula of the compound as written in the database, such as `NaCl`.
+    pub formula: String,
+    /// Molar mass of the compound.
+    pub mass: f64,
+    /// Category of the compound, such as `acid`, `salt` or `organic`.
+    pub category: Option<String>,
+    /// Describes the atoms and charge of the formula, to match formulas written differently.
+    #[serde(skip)]
+    composition: String,
+}
+
+/// Formula parsed together with the known compound it matches, see
+/// [`ChemParser::parse_formula_named`].
+#[derive(Debug, Clone, Serialize)]
+pub struct NamedFormula {
+    /// Parsed formula.
+    pub formula: Formula,
+    /// Compound of the database with the atoms and charge of the formula, if any.
+    pub compound: Option<Compound>,
+}
+
+/// Database of known compounds, in the order they were added.
+#[derive(Debug, Clone, Default)]
+pub struct CompoundDb {
+    compounds: Vec<Compound>,
+}
+
+impl CompoundDb {
+    /// Creates an empty database.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the database embedded in the library.
+    pub fn embedded() -> &'static CompoundDb {
+        static EMBEDDED: OnceLock<CompoundDb> = OnceLock::new();
+        EMBEDDED.get_or_init(|| {
+            let mut db = CompoundDb::new();
+            db.load_csv_str(COMPOUNDS_CSV, &ChemParser::new())
+                .expect("embedded compounds are valid");
+            db
+        })
+    }
+
+    /// Adds a compound with its name, formula and category, parsing the formula with the parser
+    /// to find its molar mass.
+    ///
+    /// Fails with the error of [`ChemParser::parse_formula`] if the formula cannot be parsed.
+    pub fn insert(
+        &mut self,
+        name: &str,
+        formula: &str,
+        category: Option<&str>,
+        parser: &ChemParser,
+    ) -> Result<(), ChemParseError> {
+        let parsed = parser.parse_formula(formula)?;
+        self.compounds.push(Compound {
+            name: name.trim().to_string(),
+            formula: formula.trim().to_string(),
+            mass: parsed.mass,
+            category: category.map(str::to_string),
+            composition: composition_key(&parsed),
+        });
+        Ok(())
+    }
+
+    /// Returns the compounds in the order they were added.
+    pub fn compounds(&self) -> &[Compound] {
+        &self.compounds
+    }
+
+    /// Returns the number of compounds in the database.
+    pub fn len(&self) -> usize {
+        self.compounds.len()
+    }
+
+    /// Checks whether the database has no compounds.
+    pub fn is_empty(&self) -> bool {
+        self.compounds.is_empty()
+    }
+
+    /// Returns the compounds with the atoms and charge of a formula, however it is written, so
+    /// that `C2H6O` finds both ethanol and dimethyl ether, in the order they were added.
+    pub fn find_by_formula(&self, formula: &Formula) -> Vec<&Compound> {
+        let composition = composition_key(formula);
+        self.compounds
+            .iter()
+            .filter(|compound| compound.composition == composition)
+            .collect()
+    }
+
+    /// Returns the compounds whose name starts with a prefix, ignoring case, in alphabetical
+    /// order of their names, such as `sodium bicarbonate`, `sodium carbonate` and the other
+    /// sodium salts for `sodium`.
+    pub fn find_by_name_prefix(&self, prefix: &str) -> Vec<&Compound> {
+        let prefix = prefix.trim_start().to_lowercase();
+        let mut compounds: Vec<&Compound> = self
+            .compounds
+            .iter()
+            .filter(|compound| compound.name.to_lowercase().starts_with(&prefix))
+            .collect();
+        compounds.sort_by(|a, b| a.name.cmp(&b.name));
+        compounds
+    }
+
+    /// Returns the compound of the given name, ignoring case.
+    pub fn find_by_name(&self, name: &str) -> Option<&Compound> {
+        self.compounds
+            .iter()
+            .find(|compound| compound.name.eq_ignore_ascii_case(name.trim()))
+    }
+
+    /// Pairs a formula with the first compound of the database that matches it.
+    pub fn annotate(&self, formula: Formula) -> NamedFormula {
+        let compound = self.find_by_formula(&formula).first().map(|&c| c.clone());
+        NamedFormula { formula, compound }
+    }
+
+    /// Loads compounds from a CSV file with `name`, `formula` and optional `category` columns,
+    /// parsing the formulas with the parser.
+    pub fn load_csv(
+        &mut self,
+        path: &str,
+        parser: &ChemParser,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.load(csv::Reader::from_path(path)?, parser)
+    }
+
+    /// Loads compounds from CSV data with `name`, `formula` and optional `category` columns,
+    /// parsing the formulas with the parser.
+    pub fn load_csv_str(
+        &mut self,
+        data: &str,
+        parser: &ChemParser,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.load(csv::Reader::from_reader(data.as_bytes()), parser)
+    }
+
+    fn load<R: std::io::Read>(
+        &mut self,
+        mut rdr: csv::Reader<R>,
+        parser: &ChemParser,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for result in rdr.deserialize() {
+            let record: CompoundRecord = result?;
+            self.insert(
+                &record.name,
+                &record.formula,
+                record.category.as_deref(),
+                parser,
+            )?;
+        }
+        Ok(())
+    }
+}
+
+impl ChemParser {
+    /// Parses a formula like [`ChemParser::parse_formula`] and names it after the first compound
+    /// of the embedded database with its atoms and charge, such as `sodium chloride` for `NaCl`.
+    pub fn parse_formula_named(&self, formula: &str) -> Result<NamedFormula, ChemParseError> {
+        Ok(CompoundDb::embedded().annotate(self.parse_formula(formula)?))
+    }
+}
@@ -0,0 +1,202 @@
+//! # Electron configuration Module
+//!
+//! This module computes the ground-state electron configurations of elements from their atomic
+//! numbers by the Aufbau principle, correcting the elements whose configurations break it, such
+//! as chromium and copper, from a table of known exceptions.
+
+use crate::element::Element;
+use serde::Serialize;
+use std::fmt::{self, Display, Formatter};
+
+/// Subshells in the order they fill by the Madelung rule, as principal quantum number and letter.
+const FILLING_ORDER: [(u8, char); 20] = [
+    (1, 's'),
+    (2, 's'),
+    (2, 'p'),
+    (3, 's'),
+    (3, 'p'),
+    (4, 's'),
+    (3, 'd'),
+    (4, 'p'),
+    (5, 's'),
+    (4, 'd'),
+    (5, 'p'),
+    (6, 's'),
+    (4, 'f'),
+    (5, 'd'),
+    (6, 'p'),
+    (7, 's'),
+    (5, 'f'),
+    (6, 'd'),
+    (7, 'p'),
+    (8, 's'),
+];
+
+/// Noble gases by atomic number and symbol, used as the cores of shorthand configurations.
+const NOBLE_GASES: [(u8, &str); 7] = [
+    (2, "He"),
+    (10, "Ne"),
+    (18, "Ar"),
+    (36, "Kr"),
+    (54, "Xe"),
+    (86, "Rn"),
+    (118, "Og"),
+];
+
+/// Elements whose ground state differs from the Aufbau configuration, by atomic number, with the
+/// electrons moved between subshells, such as one from `4s` to `3d` for chromium.
+const EXCEPTIONS: &[(u8, &[(&str, i8)])] = &[
+    (24, &[("4s", -1), ("3d", 1)]),
+    (29, &[("4s", -1), ("3d", 1)]),
+    (41, &[("5s", -1), ("4d", 1)]),
+    (42, &[("5s", -1), ("4d", 1)]),
+    (44, &[("5s", -1), ("4d", 1)]),
+    (45, &[("5s", -1), ("4d", 1)]),
+    (46, &[("5s", -2), ("4d", 2)]),
+    (47, &[("5s", -1), ("4d", 1)]),
+    (57, &[("4f", -1), ("5d", 1)]),
+    (58, &[("4f", -1), ("5d", 1)]),
+    (64, &[("4f", -1), ("5d", 1)]),
+    (78, &[("6s", -1), ("5d", 1)]),
+    (79, &[("6s", -1), ("5d", 1)]),
+    (89, &[("5f", -1), ("6d", 1)]),
+    (90, &[("5f", -2), ("6d", 2)]),
+    (91, &[("5f", -1), ("6d", 1)]),
+    (92, &[("5f", -1), ("6d", 1)]),
+    (93, &[("5f", -1), ("6d", 1)]),
+    (96, &[("5f", -1), ("6d", 1)]),
+    (103, &[("6d", -1), ("7p", 1)]),
+];
+
+/// Occupied subshell of an electron configuration, such as `3d5`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Subshell {
+    /// Principal quantum number.
+    pub n: u8,
+    /// Letter of the angular momentum, `s`, `p`, `d` or `f`.
+    pub letter: char,
+    /// Number of electrons in the subshell.
+    pub electrons: u8,
+}
+
+impl Subshell {
+    /// Returns the name of the subshell without its electrons, such as `3d`.
+    pub fn name(&self) -> String {
+        format!("{}{}", self.n, self.letter)
+    }
+}
+
+impl Display for Subshell {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}{}{}", self.n, self.letter, self.electrons)
+    }
+}
+
+/// Ground-state electron configuration of an element, with its subshells in filling order.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ElectronConfiguration {
+    /// Occupied subshells in the order they fill.
+    pub subshells: Vec<Subshell>,
+    /// Noble gas whose configuration is the core of the shorthand form, if any.
+    pub core: Option<&'static str>,
+    /// Number of subshells of the core.
+    #[serde(skip)]
+    core_subshells: usize,
+}
+
+impl ElectronConfiguration {
+    /// Returns the full configuration, such as `1s2 2s2 2p6 3s1` for sodium.
+    pub fn full(&self) -> String {
+        join(&self.subshells)
+    }
+
+    /// Returns the configuration with the preceding noble gas as its core, such as `[Ne] 3s1`
+    /// for sodium, or the full configuration for hydrogen and helium.
+    pub fn shorthand(&self) -> String {
+        match self.core {
+            Some(core) => format!(
+                "[{}] {}",
+                core,
+                join(&self.subshells[self.core_subshells..])
+            ),
+            None => self.full(),
+        }
+    }
+}
+
+impl Display for ElectronConfiguration {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}", self.full())
+    }
+}
+
+impl Element {
+    /// Returns the ground-state electron configuration of the element, computed from its atomic
+    /// number by the Aufbau principle with the known exceptions, such as `[Ar] 4s1 3d5` for
+    /// chromium instead of `[Ar] 4s2 3d4`.
+    pub fn electron_configuration(&self) -> ElectronConfiguration {
+        let mut electrons = aufbau(self.atomic_number);
+        if let Some((_, moves)) = EXCEPTIONS
+            .iter()
+            .find(|(number, _)| *number == self.atomic_number)
+        {
+            for (name, change) in moves.iter() {
+                if let Some(index) = FILLING_ORDER
+                    .iter()
+                    .position(|(n, letter)| format!("{}{}", n, letter) == *name)
+                {
+                    electrons[index] = electrons[index].saturating_add_signed(*change);
+                }
+            }
+        }
+
+        let core = NOBLE_GASES
+            .iter()
+            .rev()
+            .find(|(number, _)| *number < self.atomic_number);
+        let core_subshells = core.map_or(0, |(number, _)| {
+            aufbau(*number).iter().filter(|&&count| count > 0).count()
+        });
+        ElectronConfiguration {
+            subshells: FILLING_ORDER
+                .iter()
+                .zip(electrons)
+                .filter(|(_, electrons)| *electrons > 0)
+                .map(|(&(n, letter), electrons)| Subshell {
+                    n,
+                    letter,
+                    electrons,
+                })
+                .collect(),
+            core: core.map(|(_, symbol)| *symbol),
+            core_subshells,
+        }
+    }
+}
+
+/// Returns the electrons of every subshell of [`FILLING_ORDER`] for an atomic number, filled in
+/// the Madelung order.
+fn aufbau(atomic_number: u8) -> [u8; FILLING_ORDER.len()] {
+    let mut remaining = atomic_number;
+    let mut electrons = [0; FILLING_ORDER.len()];
+    for (count, (_, letter)) in electrons.iter_mut().zip(FILLING_ORDER) {
+        let capacity = match letter {
+            's' => 2,
+            'p' => 6,
+            'd' => 10,
+            _ => 14,
+        };
+        *count = remaining.min(capacity);
+        remaining -= *count;
+    }
+    electrons
+}
+
+/// Joins subshells with spaces.
+fn join(subshells: &[Subshell]) -> String {
+    subshells
+        .iter()
+        .map(Subshell::to_string)
+        .collect::<Vec<_>>()
+        .join(" ")
+}
@@ -0,0 +1,399 @@
+//! # Convert Module
+//!
+//! This module reads and writes batches of equations in the supported file formats, plain text,
+//! CSV, JSON Lines, CML reaction lists and Markdown reports, and detects the format of an input,
+//! so that equation files can be converted from one format to another.
+
+use crate::element::{Equation, Term};
+use crate::ChemParseError;
+use std::io::{self, Write};
+
+/// File format of a batch of equations.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BatchFormat {
+    /// One equation per line, with blank lines and `#` comments skipped.
+    Text,
+    /// CSV with an `equation` column, or the first column if there is none.
+    Csv,
+    /// One JSON object per line, as serialized by [`Equation`], or one JSON string.
+    Jsonl,
+    /// CML reaction list with the coefficient, state and inline formula of every reactant and
+    /// product. Arrows other than `->` and conditions are not kept.
+    Cml,
+    /// Markdown report with a table of the equations and whether they are balanced.
+    Markdown,
+}
+
+impl BatchFormat {
+    /// All formats.
+    pub const ALL: [BatchFormat; 5] = [
+        BatchFormat::Text,
+        BatchFormat::Csv,
+        BatchFormat::Jsonl,
+        BatchFormat::Cml,
+        BatchFormat::Markdown,
+    ];
+
+    /// Returns the format with the given name, such as `jsonl`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        BatchFormat::ALL
+            .into_iter()
+            .find(|format| format.name() == name.to_lowercase())
+    }
+
+    /// Returns the name of the format, such as `jsonl`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            BatchFormat::Text => "text",
+            BatchFormat::Csv => "csv",
+            BatchFormat::Jsonl => "jsonl",
+            BatchFormat::Cml => "cml",
+            BatchFormat::Markdown => "markdown",
+        }
+    }
+
+    /// Returns the format of a file by its extension, such as `.md` for Markdown.
+    pub fn from_path(path: &str) -> Option<Self> {
+        let (_, extension) = path.rsplit_once('.')?;
+        match extension.to_lowercase().as_str() {
+            "txt" | "text" => Some(BatchFormat::Text),
+            "csv" => Some(BatchFormat::Csv),
+            "jsonl" | "ndjson" => Some(BatchFormat::Jsonl),
+            "cml" | "xml" => Some(BatchFormat::Cml),
+            "md" | "markdown" => Some(BatchFormat::Markdown),
+            _ => None,
+        }
+    }
+
+    /// Detects the format of an input by the extension of its path, or else by its content:
+    /// markup is CML, JSON objects are JSON Lines, tables are Markdown and a header line with
+    /// an `equation` column is CSV. Anything else is plain text.
+    pub fn detect(path: &str, content: &str) -> Self {
+        if let Some(format) = BatchFormat::from_path(path) {
+            return format;
+        }
+        let first = content
+            .lines()
+            .map(str::trim)
+            .find(|line| !line.is_empty())
+            .unwrap_or("");
+        if first.starts_with('<') {
+            BatchFormat::Cml
+        } else if first.starts_with('{') {
+            BatchFormat::Jsonl
+        } else if content
+            .lines()
+            .any(|line| line.trim_start().starts_with('|'))
+        {
+            BatchFormat::Markdown
+        } else if first.split(',').any(|column| {
+            column
+                .trim()
+                .trim_matches('"')
+                .eq_ignore_ascii_case("equation")
+        }) {
+            BatchFormat::Csv
+        } else {
+            BatchFormat::Text
+        }
+    }
+}
+
+/// Reads the equations of a batch in the given format, as written, in order.
+///
+/// Fails with [`ChemParseError::ParsingError`] or [`ChemParseError::DataError`] if the content
+/// is not valid in the format.
+pub fn read_batch(content: &str, format: BatchFormat) -> Result<Vec<String>, ChemParseError> {
+    match format {
+        BatchFormat::Text => Ok(content
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(String::from)
+            .collect()),
+        BatchFormat::Csv => read_csv(content),
+        BatchFormat::Jsonl => read_jsonl(content),
+        BatchFormat::Cml => read_cml(content),
+        BatchFormat::Markdown => read_markdown(content),
+    }
+}
+
+/// Writes equations in the given format.
+pub fn write_batch<W: Write>(
+    equations: &[Equation],
+    format: BatchFormat,
+    mut writer: W,
+) -> io::Result<()> {
+    match format {
+        BatchFormat::Text => {
+            for equation in equations {
+                writeln!(writer, "{}", equation.equation)?;
+            }
+            Ok(())
+        }
+        BatchFormat::Csv => write_csv(equations, writer),
+        BatchFormat::Jsonl => {
+            for equation in equations {
+                serde_json::to_writer(&mut writer, equation)?;
+                writeln!(writer)?;
+            }
+            Ok(())
+        }
+        BatchFormat::Cml => write_cml(equations, writer),
+        BatchFormat::Markdown => write_markdown(equations, writer),
+    }
+}
+
+#[cfg(feature = "csv")]
+fn read_csv(content: &str) -> Result<Vec<String>, ChemParseError> {
+    let error = |e: csv::Error| ChemParseError::DataError(String::from("CSV input"), Box::new(e));
+    let mut reader = csv::Reader::from_reader(content.as_bytes());
+    let column = reader
+        .headers()
+        .map_err(error)?
+        .iter()
+        .position(|header| header.trim().eq_ignore_ascii_case("equation"))
+        .unwrap_or(0);
+    let mut equations = Vec::new();
+    for record in reader.records() {
+        let record = record.map_err(error)?;
+        match record.get(column).map(str::trim) {
+            Some(equation) if !equation.is_empty() => equations.push(equation.to_string()),
+            _ => {}
+        }
+    }
+    Ok(equations)
+}
+
+#[cfg(not(feature = "csv"))]
+fn read_csv(_content: &str) -> Result<Vec<String>, ChemParseError> {
+    Err(ChemParseError::ParsingError(
+        String::from("CSV input"),
+        String::from("the library was built without the csv feature"),
+    ))
+}
+
+#[cfg(feature = "csv")]
+fn write_csv<W: Write>(equations: &[Equation], writer: W) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(["equation"])?;
+    for equation in equations {
+        writer.write_record([&equation.equation])?;
+    }
+    writer.flush()
+}
+
+#[cfg(not(feature = "csv"))]
+fn write_csv<W: Write>(_equations: &[Equation], _writer: W) -> io::Result<()> {
+    Err(io::Error::new(
+        io::ErrorKind::Unsupported,
+        "the library was built without the csv feature",
+    ))
+}
+
+fn read_jsonl(content: &str) -> Result<Vec<String>, ChemParseError> {
+    let mut equations = Vec::new();
+    for (index, line) in content.lines().enumerate() {
+        let line = line.trim();
+        if line.is_empty() {
+            continue;
+        }
+        let error = |reason: String| {
+            ChemParseError::ParsingError(format!("JSON Lines input on line {}", index + 1), reason)
+        };
+        let value: serde_json::Value =
+            serde_json::from_str(line).map_err(|e| error(e.to_string()))?;
+        let equation = value
+            .as_str()
+            .or_else(|| value.get("equation")?.as_str())
+            .ok_or_else(|| error(String::from("expected an object with an equation")))?;
+        equations.push(equation.to_string());
+    }
+    Ok(equations)
+}
+
+fn read_cml(content: &str) -> Result<Vec<String>, ChemParseError> {
+    let error = |reason: &str| {
+        ChemParseError::ParsingError(String::from("CML input"), String::from(reason))
+    };
+    let mut equations = Vec::new();
+    for reaction in content.split("<reaction").skip(1) {
+        if reaction.starts_with("List") {
+            continue;
+        }
+        let side = |list: &str, species: &str| -> Result<String, ChemParseError> {
+            let body = element_body(reaction, list)
+                .ok_or_else(|| error(&format!("a reaction has no {}", list)))?;
+            let terms = body
+                .split(&format!("<{}", species))
+                .skip(1)
+                .filter(|term| !term.starts_with("List"))
+                .map(|term| {
+                    let count = attribute(term, "count").unwrap_or_else(|| String::from("1"));
+                    let formula = term
+                        .split("<formula")
+                        .nth(1)
+                        .and_then(|formula| attribute(formula, "inline"))
+                        .ok_or_else(|| error(&format!("a {} has no inline formula", species)))?;
+                    let state = attribute(term, "state")
+                        .map(|state| format!("({})", state))
+                        .unwrap_or_default();
+                    Ok(match count.as_str() {
+                        "1" => format!("{}{}", formula, state),
+                        count => format!("{}{}{}", count, formula, state),
+                    })
+                })
+                .collect::<Result<Vec<_>, ChemParseError>>()?;
+            Ok(terms.join(" + "))
+        };
+        equations.push(format!(
+            "{} -> {}",
+            side("reactantList", "reactant")?,
+            side("productList", "product")?
+        ));
+    }
+    Ok(equations)
+}
+
+/// Returns the content of the first element with the given name.
+fn element_body<'a>(xml: &'a str, name: &str) -> Option<&'a str> {
+    let start = xml.find(&format!("<{}", name))?;
+    let rest = &xml[start..];
+    let end = rest.find(&format!("</{}>", name))?;
+    Some(&rest[..end])
+}
+
+/// Returns the unescaped value of an attribute of the tag that the markup starts in.
+fn attribute(markup: &str, name: &str) -> Option<String> {
+    let tag = &markup[..markup.find('>')?];
+    let start = tag.find(&format!(" {}=\"", name))? + name.len() + 3;
+    let end = tag[start..].find('"')? + start;
+    Some(
+        tag[start..end]
+            .replace("&lt;", "<")
+            .replace("&gt;", ">")
+            .replace("&quot;", "\"")
+            .replace("&amp;", "&"),
+    )
+}
+
+/// Escapes text for an XML attribute.
+fn escape_xml(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+fn write_cml<W: Write>(equations: &[Equation], mut writer: W) -> io::Result<()> {
+    writeln!(writer, "<?xml version=\"1.0\" encoding=\"UTF-8\"?>")?;
+    writeln!(writer, "<cml xmlns=\"http://www.xml-cml.org/schema\">")?;
+    writeln!(writer, "  <reactionList>")?;
+    for (index, equation) in equations.iter().enumerate() {
+        writeln!(writer, "    <reaction id=\"r{}\">", index + 1)?;
+        for (list, species, terms) in [
+            ("reactantList", "reactant", &equation.reactant_terms),
+            ("productList", "product", &equation.product_terms),
+        ] {
+            writeln!(writer, "      <{}>", list)?;
+            for Term {
+                coefficient,
+                formula,
+                state,
+            } in terms
+            {
+                let state = state
+                    .map(|state| format!(" state=\"{}\"", state.symbol()))
+                    .unwrap_or_default();
+                writeln!(
+                    writer,
+                    "        <{} count=\"{}\"{}>",
+                    species, coefficient, state
+                )?;
+                writeln!(
+                    writer,
+                    "          <molecule><formula inline=\"{}\"/></molecule>",
+                    escape_xml(&formula.formula)
+                )?;
+                writeln!(writer, "        </{}>", species)?;
+            }
+            writeln!(writer, "      </{}>", list)?;
+        }
+        writeln!(writer, "    </reaction>")?;
+    }
+    writeln!(writer, "  </reactionList>")?;
+    writeln!(writer, "</cml>")
+}
+
+fn read_markdown(content: &str) -> Result<Vec<String>, ChemParseError> {
+    let mut rows = content
+        .lines()
+        .map(str::trim)
+        .filter(|line| line.starts_with('|'))
+        .map(table_cells);
+    let header = rows.next().ok_or_else(|| {
+        ChemParseError::ParsingError(
+            String::from("Markdown input"),
+            String::from("no table of equations"),
+        )
+    })?;
+    let column = header
+        .iter()
+        .position(|cell| cell.eq_ignore_ascii_case("equation"))
+        .ok_or_else(|| {
+            ChemParseError::ParsingError(
+                String::from("Markdown input"),
+                String::from("the table has no Equation column"),
+            )
+        })?;
+    Ok(rows
+        .filter(|cells| {
+            !cells
+                .iter()
+                .all(|cell| cell.chars().all(|c| matches!(c, '-' | ':')))
+        })
+        .filter_map(|mut cells| Some(cells.swap_remove(column)).filter(|cell| !cell.is_empty()))
+        .collect())
+}
+
+/// Splits a row of a Markdown table into its cells, unescaping `\|`.
+fn table_cells(row: &str) -> Vec<String> {
+    let row = row.trim_start_matches('|');
+    let row = row.strip_suffix('|').unwrap_or(row);
+    row.replace("\\|", "\u{0}")
+        .split('|')
+        .map(|cell| cell.trim().replace('\u{0}', "|").replace('`', ""))
+        .collect()
+}
+
+fn write_markdown<W: Write>(equations: &[Equation], mut writer: W) -> io::Result<()> {
+    let balanced = equations
+        .iter()
+        .filter(|equation| equation.check_stoichiometric().is_ok())
+        .count();
+    writeln!(writer, "# Equations")?;
+    writeln!(writer)?;
+    writeln!(
+        writer,
+        "{} equations, {} balanced.",
+        equations.len(),
+        balanced
+    )?;
+    writeln!(writer)?;
+    writeln!(writer, "| # | Equation | Balanced |")?;
+    writeln!(writer, "|---|---|---|")?;
+    for (index, equation) in equations.iter().enumerate() {
+        writeln!(
+            writer,
+            "| {} | `{}` | {} |",
+            index + 1,
+            equation.equation.replace('|', "\\|"),
+            if equation.check_stoichiometric().is_ok() {
+                "yes"
+            } else {
+                "no"
+            }
+        )?;
+    }
+    Ok(())
+}
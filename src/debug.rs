@@ -0,0 +1,62 @@
+//! # Debug Module
+//!
+//! This module renders the parse tree that the grammar builds for an input, one rule per line
+//! indented by depth with the text it matched, so that grammar extensions and parse bugs can be
+//! inspected and reported.
+
+use crate::analysis::is_equation;
+use crate::{normalize_scripts, syntax_error, ChemParseError, ChemParser, Rule};
+use pest::iterators::Pair;
+use pest::Parser;
+
+/// Renders the parse tree of an input for a rule of the grammar, such as [`Rule::formula`]. The
+/// input is normalized like the parse functions do, so that subscripts are digits. For `H2O`:
+///
+/// ```text
+/// formula "H2O"
+///   element "H"
+///   index "2"
+///   element "O"
+/// ```
+///
+/// Input after the text the rule matched is shown on a last `unmatched` line, since the parse
+/// functions ignore it.
+///
+/// Fails with [`ChemParseError::SyntaxError`] if the input does not match the rule.
+pub fn tree(rule: Rule, input: &str) -> Result<String, ChemParseError> {
+    let input = &normalize_scripts(input);
+    let pairs = ChemParser::parse(rule, input)
+        .map_err(|e| syntax_error(&format!("{:?}", rule), input, &e))?;
+    let mut lines = Vec::new();
+    let mut end = 0;
+    for pair in pairs {
+        end = end.max(pair.as_span().end());
+        render(pair, 0, &mut lines);
+    }
+    if end < input.len() {
+        lines.push(format!("unmatched {:?}", &input[end..]));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Returns the rule that an input is parsed with: [`Rule::equation`] if it has an arrow or an
+/// equals sign, or else [`Rule::formula`].
+pub fn input_rule(input: &str) -> Rule {
+    match is_equation(input) {
+        true => Rule::equation,
+        false => Rule::formula,
+    }
+}
+
+/// Adds the lines of a pair and its inner pairs.
+fn render(pair: Pair<Rule>, depth: usize, lines: &mut Vec<String>) {
+    lines.push(format!(
+        "{}{:?} {:?}",
+        "  ".repeat(depth),
+        pair.as_rule(),
+        pair.as_str()
+    ));
+    for inner in pair.into_inner() {
+        render(inner, depth + 1, lines);
+    }
+}
@@ -0,0 +1,78 @@
+//! # Density Module
+//!
+//! This module converts between masses and volumes with densities in g/cm³, which is g/mL: those
+//! of the elements from the periodic table, and those supplied for compounds, for example the
+//! milliliters of a liquid reactant to measure out for a reaction.
+
+use crate::element::{Element, Equation, Formula};
+use crate::ChemParseError;
+
+impl Element {
+    /// Returns the volume in cm³ (mL) of a mass in grams of the element, or `None` if its density
+    /// is unknown.
+    pub fn volume_of_mass(&self, grams: f64) -> Option<f64> {
+        (self.density > 0.0).then_some(grams / self.density)
+    }
+
+    /// Returns the mass in grams of a volume in cm³ (mL) of the element, or `None` if its density
+    /// is unknown.
+    pub fn mass_of_volume(&self, milliliters: f64) -> Option<f64> {
+        (self.density > 0.0).then_some(milliliters * self.density)
+    }
+}
+
+impl Formula {
+    /// Returns the volume in mL of an amount in moles of the compound with a density in g/mL,
+    /// such as 18.0 mL for a mole of water with a density of 1.0.
+    pub fn volume_of_moles(&self, moles: f64, density: f64) -> f64 {
+        moles * self.mass / density
+    }
+
+    /// Returns the amount in moles in a volume in mL of the compound with a density in g/mL.
+    pub fn moles_of_volume(&self, milliliters: f64, density: f64) -> f64 {
+        milliliters * density / self.mass
+    }
+}
+
+impl Equation {
+    /// Returns the volume in mL of the liquid `target`, with a density in g/mL, that reacts with
+    /// or is produced from the given mass in grams of another species, as in
+    /// `liquid_volume_of("C2H5OH", ("CO2", 88.0), 0.789)` for `C2H5OH + 3O2 -> 2CO2 + 3H2O`.
+    ///
+    /// Fails like [`Equation::mass_of`], and for a density that is not positive.
+    pub fn liquid_volume_of(
+        &self,
+        target: &str,
+        given: (&str, f64),
+        density: f64,
+    ) -> Result<f64, ChemParseError> {
+        if density <= 0.0 || !density.is_finite() {
+            return Err(ChemParseError::EvaluationError(
+                format!("liquid volume of {}", target),
+                format!("density {} is not positive", density),
+            ));
+        }
+        Ok(self.mass_of(target, given)? / density)
+    }
+
+    /// Returns the mass in grams of `target` that reacts with or is produced from a volume in mL
+    /// of the liquid `given`, with a density in g/mL, as in
+    /// `mass_from_liquid("H2", ("H2O", 18.0), 1.0)` for `2H2O -> 2H2 + O2`.
+    ///
+    /// Fails like [`Equation::liquid_volume_of`].
+    pub fn mass_from_liquid(
+        &self,
+        target: &str,
+        given: (&str, f64),
+        density: f64,
+    ) -> Result<f64, ChemParseError> {
+        let (given, milliliters) = given;
+        if density <= 0.0 || !density.is_finite() {
+            return Err(ChemParseError::EvaluationError(
+                format!("mass of {}", target),
+                format!("density {} is not positive", density),
+            ));
+        }
+        self.mass_of(target, (given, milliliters * density))
+    }
+}
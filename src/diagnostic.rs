@@ -0,0 +1,113 @@
+//! # Diagnostic Module
+//!
+//! This module describes where the parsing of an input failed and what was expected there,
+//! so that error messages can point at the exact position in a formula or equation.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::Rule;
+use core::fmt::Display;
+use core::ops::Range;
+use pest::error::{Error, ErrorVariant, InputLocation, LineColLocation};
+
+/// Location and expectations of a syntax error in a parsed input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Diagnostic {
+    /// Input that failed to parse.
+    pub input: String,
+    /// Line of the error, starting from 1.
+    pub line: usize,
+    /// Column of the error in characters, starting from 1.
+    pub column: usize,
+    /// Byte range of the input the error points at.
+    pub span: Range<usize>,
+    /// Grammar rules that were expected at the position, such as `formula` or `coefficient`.
+    pub expected: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Creates a diagnostic from an error of the pest parser.
+    pub(crate) fn from_pest(input: &str, error: &Error<Rule>) -> Self {
+        let span = match error.location {
+            InputLocation::Pos(position) => position..position,
+            InputLocation::Span((start, end)) => start..end,
+        };
+        let (line, column) = match error.line_col {
+            LineColLocation::Pos(position) | LineColLocation::Span(position, _) => position,
+        };
+        let expected = match &error.variant {
+            ErrorVariant::ParsingError { positives, .. } => positives
+                .iter()
+                .map(|rule| format!("{:?}", rule).replace('_', " "))
+                .collect(),
+            ErrorVariant::CustomError { message } => vec![message.clone()],
+        };
+        Diagnostic {
+            input: input.to_string(),
+            line,
+            column,
+            span,
+            expected,
+        }
+    }
+
+    /// Moves the diagnostic from a rewritten input to the input as written, given the byte offset
+    /// in `original` of every byte of the rewritten input and its end.
+    pub(crate) fn map_to_original(mut self, original: &str, offsets: &[usize]) -> Self {
+        self.span = offsets[self.span.start]..offsets[self.span.end];
+        let before = &original[..self.span.start];
+        self.line = before.matches('\n').count() + 1;
+        self.column = before
+            .rsplit('\n')
+            .next()
+            .unwrap_or_default()
+            .chars()
+            .count()
+            + 1;
+        self.input = original.to_string();
+        self
+    }
+
+    /// Renders the line of the input with the error, underlined with carets and followed by
+    /// the expected rules, for example:
+    ///
+    /// ```text
+    /// 2H2 + -> 2H2O
+    ///       ^ expected coefficient or formula
+    /// ```
+    pub fn render_snippet(&self) -> String {
+        let line = self.input.lines().nth(self.line - 1).unwrap_or_default();
+        let width = self.input[self.span.clone()]
+            .lines()
+            .next()
+            .map_or(0, |spanned| spanned.chars().count());
+        format!(
+            "{}\n{}{} {}",
+            line,
+            " ".repeat(self.column - 1),
+            "^".repeat(width.max(1)),
+            self.expectation()
+        )
+    }
+
+    /// Describes the expected rules, such as `expected coefficient or formula`.
+    fn expectation(&self) -> String {
+        match self.expected.as_slice() {
+            [] => String::from("unexpected input"),
+            [rule] => format!("expected {}", rule),
+            [rest @ .., last] => format!("expected {} or {}", rest.join(", "), last),
+        }
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: {}",
+            self.line,
+            self.column,
+            self.expectation()
+        )
+    }
+}
@@ -2,11 +2,32 @@
 //!
 //! This module defines structs that represent chemical elements, formulas, and equations
 
+#[cfg(any(feature = "datasets", not(feature = "std")))]
+use crate::embedded::{
+    self, ELEMENTS_CSV, ELEMENT_IDENTIFIERS_CSV, ELEMENT_PROPERTIES_CSV, ISOTOPES_CSV,
+};
+use crate::nuclear::DecayMode;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::query::ElementQuery;
+use crate::temperature::{deserialize_transition, TransitionTemperature};
+use crate::ChemParseError;
+use alloc::collections::{BTreeMap, BTreeSet};
+#[cfg(feature = "datasets")]
+use alloc::sync::Arc;
+use core::cmp::Ordering;
+use core::error::Error;
+use core::fmt::Display;
+use core::hash::{Hash, Hasher};
+use core::ops::{Add, Mul, Sub};
+use core::str::FromStr;
 use serde::{Deserialize, Serialize};
-use serde_json::Value;
-use std::collections::HashMap;
-use std::error::Error;
-use std::fmt::Display;
+#[cfg(feature = "std")]
+use std::fs;
+#[cfg(feature = "csv")]
+use std::io;
+#[cfg(feature = "datasets")]
+use std::sync::OnceLock;
 
 /// Represents a chemical element with its properties from periodic table.
 #[derive(Debug, Clone, Deserialize, Serialize)]
@@ -16,21 +37,175 @@ pub struct Element {
     /// Symbol of the element
     pub symbol: String,
     /// Atomic number of the element from periodic table
+    #[serde(alias = "number")]
     pub atomic_number: u8,
     /// Atomic mass of the element from periodic table
     pub atomic_mass: f64,
-    /// Density of the element.
+    /// Uncertainty of the atomic mass, if known. For elements whose atomic weight varies in
+    /// nature, such as hydrogen or lead, it is the uncertainty of the conventional value.
+    #[serde(default)]
+    pub atomic_mass_uncertainty: Option<f64>,
+    /// Density of the element, zero when unknown.
+    #[serde(default, deserialize_with = "deserialize_density")]
     pub density: f64,
     /// Optional group number of the element in the periodic table.
+    #[serde(default)]
     pub group: Option<u8>,
-    /// Optional melting point of the element.
-    pub melting_point: Option<Value>,
-    /// Optional boiling point of the element.
-    pub boiling_point: Option<Value>,
+    /// Melting point of the element, if known, for each allotrope of elements that have several.
+    #[serde(default, alias = "melt", deserialize_with = "deserialize_transition")]
+    pub melting_point: Option<TransitionTemperature>,
+    /// Boiling point of the element, if known, for each allotrope of elements that have several.
+    #[serde(default, alias = "boil", deserialize_with = "deserialize_transition")]
+    pub boiling_point: Option<TransitionTemperature>,
+    /// Electronegativity of the element on the Pauling scale, if known.
+    #[serde(default)]
+    pub electronegativity: Option<f64>,
+    /// CAS registry number of the element, such as `7439-89-6` for iron, if known.
+    #[serde(default)]
+    pub cas: Option<String>,
+    /// PubChem compound identifier (CID) of the element, if known.
+    #[serde(default)]
+    pub cid: Option<u64>,
+    /// Category of the element, such as `transition metal` or `halogen`, if known.
+    #[serde(default)]
+    pub category: Option<String>,
+    /// Successive ionization energies of the element in kJ/mol, starting with the first, as far
+    /// as they are known.
+    #[serde(default, deserialize_with = "deserialize_energies")]
+    pub ionization_energies: Vec<f64>,
+    /// Empirical atomic radius of the element in picometres, if known.
+    #[serde(default)]
+    pub atomic_radius: Option<f64>,
+    /// Single-bond covalent radius of the element in picometres, if known.
+    #[serde(default)]
+    pub covalent_radius: Option<f64>,
+    /// Abundance of the element in the Earth's crust in mg/kg, if known.
+    #[serde(default)]
+    pub abundance: Option<f64>,
+    /// Year the element was discovered, or `None` for elements known since antiquity.
+    #[serde(default)]
+    pub discovery_year: Option<i32>,
 }
 
+/// Ionization energies as written in element data: a list, a single number, or text with the
+/// energies separated by spaces, as in the CSV data.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawEnergies {
+    List(Vec<f64>),
+    Single(f64),
+    Text(String),
+}
+
+/// Reads ionization energies, which are empty when missing.
+fn deserialize_energies<'de, D: serde::Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Vec<f64>, D::Error> {
+    match Option::<RawEnergies>::deserialize(deserializer)? {
+        None => Ok(Vec::new()),
+        Some(RawEnergies::List(energies)) => Ok(energies),
+        Some(RawEnergies::Single(energy)) => Ok(vec![energy]),
+        Some(RawEnergies::Text(text)) => text
+            .split_whitespace()
+            .map(|energy| {
+                energy.parse().map_err(|_| {
+                    serde::de::Error::custom(format!("invalid ionization energy: {}", energy))
+                })
+            })
+            .collect(),
+    }
+}
+
+/// Reads a density that may be empty or `null` in public datasets as zero.
+fn deserialize_density<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<f64, D::Error> {
+    Ok(Option::<f64>::deserialize(deserializer)?.unwrap_or(0.0))
+}
+
+impl Element {
+    /// Returns the period (row) of the element in the periodic table, computed from its atomic number.
+    pub fn period(&self) -> u8 {
+        match self.atomic_number {
+            0..=2 => 1,
+            3..=10 => 2,
+            11..=18 => 3,
+            19..=36 => 4,
+            37..=54 => 5,
+            55..=86 => 6,
+            _ => 7,
+        }
+    }
+
+    /// Returns `true` for the nonmetals: hydrogen, the noble gases, the halogens up to iodine,
+    /// and carbon, nitrogen, oxygen, phosphorus, sulfur and selenium.
+    pub fn is_nonmetal(&self) -> bool {
+        NONMETALS.contains(&self.atomic_number)
+    }
+
+    /// Returns `true` for the commonly recognised metalloids: B, Si, Ge, As, Sb and Te.
+    pub fn is_metalloid(&self) -> bool {
+        METALLOIDS.contains(&self.atomic_number)
+    }
+
+    /// Returns `true` for elements that are neither nonmetals nor metalloids.
+    pub fn is_metal(&self) -> bool {
+        !self.is_nonmetal() && !self.is_metalloid()
+    }
+
+    /// Returns whether the element is a metal, a metalloid or a nonmetal.
+    pub fn class(&self) -> ElementClass {
+        if self.is_nonmetal() {
+            ElementClass::Nonmetal
+        } else if self.is_metalloid() {
+            ElementClass::Metalloid
+        } else {
+            ElementClass::Metal
+        }
+    }
+
+    /// Returns the first ionization energy of the element in kJ/mol, if known.
+    pub fn first_ionization_energy(&self) -> Option<f64> {
+        self.ionization_energies.first().copied()
+    }
+
+    /// Returns the ionization energy in kJ/mol needed to remove the `n`-th electron, counting
+    /// from 1, if known.
+    pub fn ionization_energy(&self, n: usize) -> Option<f64> {
+        n.checked_sub(1)
+            .and_then(|index| self.ionization_energies.get(index))
+            .copied()
+    }
+}
+
+/// Broad classification of the elements by their metallic character.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum ElementClass {
+    /// Elements that are neither nonmetals nor metalloids.
+    Metal,
+    /// The metalloids B, Si, Ge, As, Sb and Te.
+    Metalloid,
+    /// Hydrogen, the noble gases, the halogens up to iodine, and C, N, O, P, S and Se.
+    Nonmetal,
+}
+
+impl Display for ElementClass {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            ElementClass::Metal => "metal",
+            ElementClass::Metalloid => "metalloid",
+            ElementClass::Nonmetal => "nonmetal",
+        })
+    }
+}
+
+/// Atomic numbers of the nonmetals.
+const NONMETALS: [u8; 17] = [1, 2, 6, 7, 8, 9, 10, 15, 16, 17, 18, 34, 35, 36, 53, 54, 86];
+
+/// Atomic numbers of the metalloids.
+const METALLOIDS: [u8; 6] = [5, 14, 32, 33, 51, 52];
+
 impl Display for Element {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
             "{} ({})\nAtomic number: {}\nAtomic mass: {}",
@@ -39,15 +214,105 @@ impl Display for Element {
     }
 }
 
+/// Represents an isotope of a chemical element with its exact mass, natural abundance and, for
+/// radioactive isotopes, its half-life and decay mode.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Isotope {
+    /// Symbol of the element
+    pub symbol: String,
+    /// Mass number of the isotope (the number of protons and neutrons)
+    pub mass_number: u16,
+    /// Exact mass of the isotope
+    pub mass: f64,
+    /// Natural abundance as a fraction of the atoms of the element, or `None` for isotopes
+    /// that do not occur naturally in measurable amounts
+    #[serde(default)]
+    pub abundance: Option<f64>,
+    /// Half-life in seconds, or `None` for stable isotopes
+    #[serde(default)]
+    pub half_life: Option<f64>,
+    /// Main mode of radioactive decay, or `None` for stable isotopes
+    #[serde(default)]
+    pub decay_mode: Option<DecayMode>,
+}
+
+impl Isotope {
+    /// Returns the key under which the isotope is stored in formula compositions.
+    pub fn key(&self) -> String {
+        isotope_key(&self.symbol, self.mass_number)
+    }
+
+    /// Checks whether the isotope is stable, i.e. has no known decay mode.
+    pub fn is_stable(&self) -> bool {
+        self.decay_mode.is_none()
+    }
+}
+
+/// Returns the formula composition key of an isotope, for example `13C`.
+/// Hydrogen-2 and hydrogen-3 use the `D` and `T` shorthand.
+pub fn isotope_key(symbol: &str, mass_number: u16) -> String {
+    match (symbol, mass_number) {
+        ("H", 2) => String::from("D"),
+        ("H", 3) => String::from("T"),
+        _ => format!("{}{}", mass_number, symbol),
+    }
+}
+
+/// Splits an isotope composition key into its element symbol and mass number.
+/// Returns `None` for keys of plain elements.
+pub fn parse_isotope_key(key: &str) -> Option<(&str, u16)> {
+    match key {
+        "D" => Some(("H", 2)),
+        "T" => Some(("H", 3)),
+        _ => {
+            let symbol = key.trim_start_matches(|c: char| c.is_ascii_digit());
+            let mass_number = key[..key.len() - symbol.len()].parse().ok()?;
+            Some((symbol, mass_number))
+        }
+    }
+}
+
 /// Represents a chemical formula with its elements with corresponding indices, and molecular mass.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Formula {
     /// String representation of the formula.
     pub formula: String,
     /// Map of element symbols to their counts.
-    pub elements: HashMap<String, u8>,
+    pub elements: BTreeMap<String, u32>,
     /// Molecular mass of the formula.
     pub mass: f64,
+    /// Electric charge of the formula, zero for neutral species.
+    #[serde(default)]
+    pub charge: i32,
+    /// Repeat unit of a polymer written with a symbolic count, such as `C2H4` in `(C2H4)n`.
+    /// The elements and mass of the formula count the repeat unit once.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub repeat_unit: Option<Box<Formula>>,
+}
+
+/// Difference in the number of atoms of an element or isotope between two formulas.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ElementDifference {
+    /// Symbol of the element, or the key of an isotope such as `13C`.
+    pub element: String,
+    /// Number of atoms in the first formula.
+    pub first: u32,
+    /// Number of atoms in the second formula.
+    pub second: u32,
+}
+
+/// Comparison of two formulas, see [`Formula::compare`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct FormulaComparison {
+    /// Whether the formulas have the same atoms and charge.
+    pub same_composition: bool,
+    /// Mass of the second formula minus the mass of the first.
+    pub mass_difference: f64,
+    /// Charge of the second formula minus the charge of the first, wide enough for any two
+    /// charges.
+    pub charge_difference: i64,
+    /// Elements and isotopes whose numbers of atoms differ, in alphabetical order.
+    pub differences: Vec<ElementDifference>,
 }
 
 impl Formula {
@@ -55,14 +320,504 @@ impl Formula {
     pub fn new(formula_str: &str) -> Self {
         Formula {
             formula: formula_str.to_string(),
-            elements: HashMap::new(),
+            elements: BTreeMap::new(),
+            mass: 0.0,
+            charge: 0,
+            repeat_unit: None,
+        }
+    }
+
+    /// Creates a neutral formula from element counts, such as `[("C", 6), ("H", 12), ("O", 6)]`,
+    /// written in Hill notation with its mass from the table. Symbols can also be isotope keys
+    /// such as `13C` or `D`; repeated symbols are added up and zero counts are left out.
+    pub fn from_counts(
+        counts: &[(&str, u32)],
+        table: &PeriodicTable,
+    ) -> Result<Self, ChemParseError> {
+        let mut elements: BTreeMap<String, u32> = BTreeMap::new();
+        let mut mass = 0.0;
+        for &(symbol, count) in counts {
+            let atomic_mass = table.get_mass(symbol).ok_or_else(|| {
+                ChemParseError::InvalidElement(symbol.to_string(), table.suggest_symbols(symbol))
+            })?;
+            if count == 0 {
+                continue;
+            }
+            let total = elements.entry(symbol.to_string()).or_insert(0);
+            *total = total
+                .checked_add(count)
+                .ok_or_else(|| ChemParseError::Overflow(format!("count of {}", symbol)))?;
+            mass += atomic_mass * count as f64;
+        }
+        Ok(Formula::from_composition(elements, mass, 0))
+    }
+
+    /// Creates the electron of a half-reaction, written as `e^-`.
+    /// Like ion masses, which do not account for missing or extra electrons, it is massless.
+    pub fn electron() -> Self {
+        Formula {
+            formula: String::from("e^-"),
+            elements: BTreeMap::new(),
+            mass: 0.0,
+            charge: -1,
+            repeat_unit: None,
+        }
+    }
+
+    /// Creates the photon of a photochemical reaction, written as `hν`. It has no atoms, charge
+    /// or mass, so balance checks count nothing for it.
+    pub fn photon() -> Self {
+        Formula::energy(PHOTON)
+    }
+
+    /// Creates the heat absorbed or released by a reaction, written as `heat`. It has no atoms,
+    /// charge or mass, so balance checks count nothing for it.
+    pub fn heat() -> Self {
+        Formula::energy(HEAT)
+    }
+
+    fn energy(notation: &str) -> Self {
+        Formula {
+            formula: String::from(notation),
+            elements: BTreeMap::new(),
             mass: 0.0,
+            charge: 0,
+            repeat_unit: None,
+        }
+    }
+
+    /// Returns the kind of a term that is not a chemical formula, such as an electron or heat.
+    pub fn special_species(&self) -> Option<SpecialSpecies> {
+        if !self.elements.is_empty() {
+            return None;
+        }
+        match (self.formula.as_str(), self.charge) {
+            ("e^-", -1) => Some(SpecialSpecies::Electron),
+            (PHOTON, 0) => Some(SpecialSpecies::Photon),
+            (HEAT, 0) => Some(SpecialSpecies::Heat),
+            _ => None,
+        }
+    }
+
+    /// Checks if the formula stands for energy, a photon or heat, rather than matter.
+    pub fn is_energy(&self) -> bool {
+        matches!(
+            self.special_species(),
+            Some(SpecialSpecies::Photon | SpecialSpecies::Heat)
+        )
+    }
+
+    /// Checks if both formulas have the same atoms and charge, however they are written,
+    /// so `CH3COOH` has the same composition as `C2H4O2`. This is also how formulas are compared with `==`.
+    pub fn same_composition(&self, other: &Formula) -> bool {
+        self.charge == other.charge && self.elements == other.elements
+    }
+
+    /// Compares the formula with another one, element by element. Formulas written differently
+    /// with the same composition, such as `CH3COOH` and `HCOOCH3`, are isomers or notations of
+    /// the same compound, while a small difference such as one hydrogen in `C2H5OH` and `C2H6OH`
+    /// often points to a typo.
+    pub fn compare(&self, other: &Formula) -> FormulaComparison {
+        let keys: BTreeSet<&String> = self.elements.keys().chain(other.elements.keys()).collect();
+        let differences = keys
+            .into_iter()
+            .filter_map(|key| {
+                let first = self.elements.get(key).copied().unwrap_or(0);
+                let second = other.elements.get(key).copied().unwrap_or(0);
+                (first != second).then(|| ElementDifference {
+                    element: key.clone(),
+                    first,
+                    second,
+                })
+            })
+            .collect();
+        FormulaComparison {
+            same_composition: self.same_composition(other),
+            mass_difference: other.mass - self.mass,
+            charge_difference: i64::from(other.charge) - i64::from(self.charge),
+            differences,
         }
     }
+
+    /// Returns a string representation of the formula that can be parsed back into the same formula.
+    pub fn to_parseable_string(&self) -> String {
+        self.formula.clone()
+    }
+
+    /// Returns the formula in Hill notation, built from the element counts: carbon first, hydrogen
+    /// second, then the other elements alphabetically, or all elements alphabetically without carbon.
+    /// For example `HO2C2H3` becomes `C2H4O2` and `SO4^2-` becomes `O4S^2-`.
+    ///
+    /// Isotopes follow the plain atoms of their element and are written in square brackets, as in `C[13C]H4`,
+    /// so the result can be parsed back into a formula with the same composition.
+    pub fn canonical(&self) -> String {
+        let mut canonical: String = self
+            .hill_keys()
+            .into_iter()
+            .map(|key| {
+                let written = match parse_isotope_key(key) {
+                    Some(_) if key == "D" || key == "T" => key.to_string(),
+                    Some(_) => format!("[{}]", key),
+                    None => key.to_string(),
+                };
+                match self.elements[key] {
+                    1 => written,
+                    count => format!("{}{}", written, count),
+                }
+            })
+            .collect();
+        if self.charge != 0 {
+            let sign = if self.charge > 0 { '+' } else { '-' };
+            match self.charge.unsigned_abs() {
+                1 => canonical.push_str(&format!("^{}", sign)),
+                magnitude => canonical.push_str(&format!("^{}{}", magnitude, sign)),
+            }
+        }
+        canonical
+    }
+
+    /// Returns the element symbols and isotope keys of the formula in Hill order, with isotopes
+    /// after the plain atoms of their element.
+    pub(crate) fn hill_keys(&self) -> Vec<&String> {
+        let has_carbon = self.elements.keys().any(|key| element_symbol(key) == "C");
+        let mut keys: Vec<&String> = self.elements.keys().collect();
+        keys.sort_by_key(|key| {
+            let symbol = element_symbol(key);
+            let rank = match symbol {
+                "C" if has_carbon => 0,
+                "H" if has_carbon => 1,
+                _ => 2,
+            };
+            let mass_number = parse_isotope_key(key).map_or(0, |(_, mass_number)| mass_number);
+            (rank, symbol, mass_number)
+        });
+        keys
+    }
+
+    /// Returns a copy of the formula with every hydrogen atom replaced by deuterium, with the
+    /// masses of hydrogen and deuterium from `table`, or `None` if the table has no mass for them.
+    pub fn deuterated(&self, table: &PeriodicTable) -> Option<Formula> {
+        let hydrogens = self.elements.get("H").copied().unwrap_or(0);
+        self.exchange_h(table, hydrogens)
+    }
+
+    /// Returns a copy of the formula with `n` hydrogen atoms replaced by deuterium, with the
+    /// masses of hydrogen and deuterium from `table`, or `None` if the formula has fewer than `n`
+    /// hydrogens or the table has no mass for them.
+    ///
+    /// When every hydrogen is exchanged, `H` symbols in the formula string are replaced by `D`,
+    /// otherwise the formula is written in Hill notation with the deuterium after the hydrogen
+    /// (for example `C2H5DO`), so it can be parsed back.
+    pub fn exchange_h(&self, table: &PeriodicTable, n: u32) -> Option<Formula> {
+        let hydrogens = self.elements.get("H").copied().unwrap_or(0);
+        if n > hydrogens {
+            return None;
+        }
+
+        let mut exchanged = self.clone();
+        if n == 0 {
+            return Some(exchanged);
+        }
+
+        let shift = table.get_mass("D")? - table.get_mass("H")?;
+        if n == hydrogens {
+            exchanged.elements.remove("H");
+        } else {
+            exchanged.elements.insert(String::from("H"), hydrogens - n);
+        }
+        *exchanged.elements.entry(String::from("D")).or_insert(0) += n;
+        exchanged.formula = if n == hydrogens {
+            replace_hydrogen_symbols(&self.formula)
+        } else {
+            exchanged.canonical()
+        };
+        exchanged.mass += shift * n as f64;
+
+        Some(exchanged)
+    }
+}
+
+/// Replaces hydrogen symbols in a formula string with `D`, leaving `He`, `Hg`, etc. intact.
+fn replace_hydrogen_symbols(formula: &str) -> String {
+    let chars: Vec<char> = formula.chars().collect();
+    chars
+        .iter()
+        .enumerate()
+        .map(|(i, &c)| {
+            let next_is_lower = chars.get(i + 1).is_some_and(|n| n.is_ascii_lowercase());
+            if c == 'H' && !next_is_lower {
+                'D'
+            } else {
+                c
+            }
+        })
+        .collect()
+}
+
+impl Formula {
+    /// Adds the atoms, charge and mass of another formula, or returns `None` if an atom count overflows.
+    /// The result is written in Hill notation, as in `CH2O + H2O = CH4O2`.
+    pub fn checked_add(&self, other: &Formula) -> Option<Formula> {
+        let mut elements = self.elements.clone();
+        for (key, count) in &other.elements {
+            let total = elements.entry(key.clone()).or_insert(0);
+            *total = total.checked_add(*count)?;
+        }
+        Some(Formula::from_composition(
+            elements,
+            self.mass + other.mass,
+            self.charge.checked_add(other.charge)?,
+        ))
+    }
+
+    /// Removes the atoms, charge and mass of another formula, for example to split off `H2O`,
+    /// or returns `None` if this formula does not contain all of its atoms.
+    pub fn checked_sub(&self, other: &Formula) -> Option<Formula> {
+        let mut elements = self.elements.clone();
+        for (key, count) in &other.elements {
+            let total = elements.get_mut(key)?;
+            *total = total.checked_sub(*count)?;
+            if *total == 0 {
+                elements.remove(key);
+            }
+        }
+        Some(Formula::from_composition(
+            elements,
+            (self.mass - other.mass).max(0.0),
+            self.charge.checked_sub(other.charge)?,
+        ))
+    }
+
+    /// Multiplies the atoms, charge and mass of the formula, or returns `None` if an atom count overflows.
+    pub fn checked_mul(&self, factor: u32) -> Option<Formula> {
+        let elements = self
+            .elements
+            .iter()
+            .map(|(key, count)| Some((key.clone(), count.checked_mul(factor)?)))
+            .collect::<Option<BTreeMap<String, u32>>>()?;
+        Some(Formula::from_composition(
+            elements,
+            self.mass * factor as f64,
+            self.charge.checked_mul(i32::try_from(factor).ok()?)?,
+        ))
+    }
+
+    /// Returns the formula of a polymer with `n` repeat units, such as `(C2H4)100` with 200 carbon
+    /// atoms for `(C2H4)n`. Returns `None` if the formula has no [repeat unit](Formula::repeat_unit),
+    /// `n` is zero or the counts overflow.
+    pub fn with_repeat(&self, n: u32) -> Option<Formula> {
+        let repeat_unit = self.repeat_unit.as_ref()?;
+        let extra = repeat_unit.checked_mul(n.checked_sub(1)?)?;
+        let mut elements = self.elements.clone();
+        for (key, count) in &extra.elements {
+            let total = elements.entry(key.clone()).or_insert(0);
+            *total = total.checked_add(*count)?;
+        }
+        Some(Formula {
+            formula: self.formula.replacen(")n", &format!("){}", n), 1),
+            elements,
+            mass: self.mass + extra.mass,
+            charge: self.charge,
+            repeat_unit: None,
+        })
+    }
+
+    /// Returns the mass of the repeat unit of a polymer, such as 28.05 for `(C2H4)n`, or `None`
+    /// if the formula has no repeat unit.
+    pub fn mass_per_repeat_unit(&self) -> Option<f64> {
+        self.repeat_unit
+            .as_ref()
+            .map(|repeat_unit| repeat_unit.mass)
+    }
+
+    /// Returns the monoisotopic mass of the formula, the sum of the exact masses of the most
+    /// abundant isotope of each element, as measured by mass spectrometry. Isotopes written in the
+    /// formula keep their own mass. Returns `None` if an element has no isotope with a known abundance.
+    pub fn monoisotopic_mass(&self, table: &PeriodicTable) -> Option<f64> {
+        self.elements
+            .iter()
+            .map(|(key, count)| Some(table.get_monoisotopic_mass(key)? * *count as f64))
+            .sum()
+    }
+
+    /// Returns the atom counts of the formula keyed by atomic number, in ascending order, with
+    /// isotopes counted as their element, such as `{1: 2, 8: 1}` for `H2O` and `HDO`.
+    /// Returns `None` if an element of the formula is not in the table.
+    pub fn counts_by_atomic_number(&self, table: &PeriodicTable) -> Option<BTreeMap<u8, u32>> {
+        let mut counts = BTreeMap::new();
+        for (key, count) in &self.elements {
+            let atomic_number = table.get_element(element_symbol(key))?.atomic_number;
+            *counts.entry(atomic_number).or_insert(0) += *count;
+        }
+        Some(counts)
+    }
+
+    /// Returns the empirical formula, with the atom counts divided by their greatest common divisor,
+    /// for example `CH2O` for `C6H12O6`. The charge is divided too, so it is kept in the divisor.
+    pub fn empirical(&self) -> Formula {
+        let divisor = self
+            .elements
+            .values()
+            .copied()
+            .chain((self.charge != 0).then(|| self.charge.unsigned_abs()))
+            .fold(0, gcd)
+            .max(1);
+        let elements = self
+            .elements
+            .iter()
+            .map(|(key, count)| (key.clone(), count / divisor))
+            .collect();
+        Formula::from_composition(
+            elements,
+            self.mass / divisor as f64,
+            self.charge / divisor as i32,
+        )
+    }
+
+    /// Returns the degrees of unsaturation of the formula, the number of rings and pi bonds,
+    /// computed as (2C + 2 + N − H − X) / 2 where X counts the halogens, such as 4 for `C6H6`
+    /// and 0 for `C2H5Cl`. Isotopes count as their element; oxygen, sulfur and the other elements
+    /// do not change the result. The value is a half-integer for radicals and negative for
+    /// formulas that no neutral molecule has, and the charge is ignored.
+    pub fn degrees_of_unsaturation(&self) -> f64 {
+        let mut carbons = 0;
+        let mut nitrogens = 0;
+        let mut monovalent = 0;
+        for (key, count) in &self.elements {
+            match element_symbol(key) {
+                "C" => carbons += i64::from(*count),
+                "N" => nitrogens += i64::from(*count),
+                "H" | "F" | "Cl" | "Br" | "I" | "At" | "Ts" => monovalent += i64::from(*count),
+                _ => {}
+            }
+        }
+        (2 * carbons + 2 + nitrogens - monovalent) as f64 / 2.0
+    }
+
+    /// Creates a formula from its composition, written in Hill notation.
+    pub(crate) fn from_composition(
+        elements: BTreeMap<String, u32>,
+        mass: f64,
+        charge: i32,
+    ) -> Formula {
+        let mut formula = Formula {
+            formula: String::new(),
+            elements,
+            mass,
+            charge,
+            repeat_unit: None,
+        };
+        formula.formula = formula.canonical();
+        formula
+    }
+}
+
+/// Notation of a photon in equations.
+const PHOTON: &str = "hν";
+
+/// Notation of heat in equations.
+const HEAT: &str = "heat";
+
+/// Kind of an equation term that is not a chemical formula.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+pub enum SpecialSpecies {
+    /// An electron of a half-reaction, written as `e^-` or `e-`.
+    Electron,
+    /// Light absorbed or emitted, written as `hν` or `hv`.
+    Photon,
+    /// Heat absorbed or released, written as `heat` or `Δ`.
+    Heat,
+}
+
+/// Describes the atoms and the charge of a formula independently of how it is written.
+#[cfg(feature = "std")]
+pub(crate) fn composition_key(formula: &Formula) -> String {
+    let elements: BTreeMap<&String, &u32> = formula.elements.iter().collect();
+    format!("{:?}{}", elements, formula.charge)
+}
+
+/// Greatest common divisor of two counts.
+pub(crate) fn gcd(a: u32, b: u32) -> u32 {
+    if b == 0 {
+        a
+    } else {
+        gcd(b, a % b)
+    }
+}
+
+impl Add for Formula {
+    type Output = Formula;
+
+    /// Adds two formulas. Atom counts and the charge saturate instead of overflowing; use
+    /// [`Formula::checked_add`] to detect overflow.
+    fn add(self, other: Formula) -> Formula {
+        let mut elements = self.elements;
+        for (key, count) in other.elements {
+            let total = elements.entry(key).or_insert(0);
+            *total = total.saturating_add(count);
+        }
+        Formula::from_composition(
+            elements,
+            self.mass + other.mass,
+            self.charge.saturating_add(other.charge),
+        )
+    }
+}
+
+impl Sub for Formula {
+    type Output = Option<Formula>;
+
+    /// Subtracts a formula, or returns `None` if it has atoms this one lacks. See [`Formula::checked_sub`].
+    fn sub(self, other: Formula) -> Option<Formula> {
+        self.checked_sub(&other)
+    }
+}
+
+impl Mul<u32> for Formula {
+    type Output = Formula;
+
+    /// Multiplies a formula. Atom counts and the charge saturate instead of overflowing; use
+    /// [`Formula::checked_mul`] to detect overflow.
+    fn mul(self, factor: u32) -> Formula {
+        let elements = self
+            .elements
+            .into_iter()
+            .map(|(key, count)| (key, count.saturating_mul(factor)))
+            .collect();
+        let charge = self
+            .charge
+            .saturating_mul(i32::try_from(factor).unwrap_or(i32::MAX));
+        Formula::from_composition(elements, self.mass * factor as f64, charge)
+    }
+}
+
+impl Mul<Formula> for u32 {
+    type Output = Formula;
+
+    /// Multiplies a formula written with the factor first, as in `6 * formula`.
+    fn mul(self, formula: Formula) -> Formula {
+        formula * self
+    }
+}
+
+impl PartialEq for Formula {
+    fn eq(&self, other: &Self) -> bool {
+        self.same_composition(other)
+    }
+}
+
+impl Eq for Formula {}
+
+impl Hash for Formula {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        let elements: BTreeMap<&String, &u32> = self.elements.iter().collect();
+        elements.hash(state);
+        self.charge.hash(state);
+    }
 }
 
 impl Display for Formula {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
             "{} \nMass: {}\nElements: {:?}",
@@ -71,89 +826,1193 @@ impl Display for Formula {
     }
 }
 
+/// Represents the kind of arrow separating reactants and products in an equation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Arrow {
+    /// Irreversible reaction arrow (`->`).
+    #[default]
+    Irreversible,
+    /// Reversible reaction or equilibrium arrow (`<->`, `<=>`, `⇌`).
+    Reversible,
+    /// Equality sign (`=`).
+    Equality,
+}
+
+impl Arrow {
+    /// Determines the arrow kind from its symbol.
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "->" => Some(Arrow::Irreversible),
+            "<->" | "<=>" | "⇌" => Some(Arrow::Reversible),
+            "=" => Some(Arrow::Equality),
+            _ => None,
+        }
+    }
+
+    /// Returns the canonical symbol of the arrow.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            Arrow::Irreversible => "->",
+            Arrow::Reversible => "<=>",
+            Arrow::Equality => "=",
+        }
+    }
+}
+
+/// Represents the physical state of a species in an equation.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum State {
+    /// Solid (`(s)`).
+    Solid,
+    /// Liquid (`(l)`).
+    Liquid,
+    /// Gas (`(g)`).
+    Gas,
+    /// Aqueous solution (`(aq)`).
+    Aqueous,
+}
+
+impl State {
+    /// Determines the state from its symbol, such as `aq`.
+    pub fn from_symbol(symbol: &str) -> Option<Self> {
+        match symbol {
+            "s" => Some(State::Solid),
+            "l" => Some(State::Liquid),
+            "g" => Some(State::Gas),
+            "aq" => Some(State::Aqueous),
+            _ => None,
+        }
+    }
+
+    /// Returns the symbol of the state, written in brackets after a formula.
+    pub fn symbol(&self) -> &'static str {
+        match self {
+            State::Solid => "s",
+            State::Liquid => "l",
+            State::Gas => "g",
+            State::Aqueous => "aq",
+        }
+    }
+}
+
+/// Represents a single term of an equation side: a formula with its coefficient.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct Term {
+    /// Number of molecules of the formula, multiplied by the [`Equation::denominator`] of an
+    /// equation written with fractional coefficients. [`Equation::coefficient`] returns the
+    /// coefficient as written.
+    pub coefficient: u32,
+    /// Parsed formula of the term.
+    pub formula: Formula,
+    /// Physical state of the species, if it is written.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub state: Option<State>,
+}
+
+impl Display for Term {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if self.coefficient != 1 {
+            write!(f, "{}", self.coefficient)?;
+        }
+        write!(f, "{}", self.formula.to_parseable_string())?;
+        if let Some(state) = self.state {
+            write!(f, "({})", state.symbol())?;
+        }
+        Ok(())
+    }
+}
+
+/// Coefficient of a term as a fraction in lowest terms, such as `1/2` for the oxygen of
+/// `H2 + 1/2O2 -> H2O`. It is parsed from a whole number, a fraction or a decimal number.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Coefficient {
+    /// Numerator of the fraction.
+    pub numerator: u32,
+    /// Denominator of the fraction, 1 for whole coefficients.
+    pub denominator: u32,
+}
+
+impl Coefficient {
+    /// Creates a coefficient from a fraction, reduced to lowest terms.
+    /// Returns `None` if the denominator is zero.
+    pub fn new(numerator: u32, denominator: u32) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(numerator, denominator);
+        Some(Coefficient {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    /// Returns the coefficient as a number.
+    pub fn value(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+
+    /// Checks if the coefficient is a whole number.
+    pub fn is_integer(&self) -> bool {
+        self.denominator == 1
+    }
+}
+
+impl FromStr for Coefficient {
+    type Err = ChemParseError;
+
+    /// Parses a coefficient written as `2`, `1/2` or `0.5`. Zero is not a coefficient.
+    fn from_str(text: &str) -> Result<Self, Self::Err> {
+        let invalid = || ChemParseError::InvalidCoefficientFormat(text.to_string());
+        let (numerator, denominator) = match (text.split_once('/'), text.split_once('.')) {
+            (Some((numerator, denominator)), _) => (
+                numerator.parse::<u32>().map_err(|_| invalid())?,
+                denominator.parse::<u32>().map_err(|_| invalid())?,
+            ),
+            (None, Some((whole, fraction))) => {
+                let denominator = u32::try_from(fraction.len())
+                    .ok()
+                    .and_then(|digits| 10u32.checked_pow(digits))
+                    .ok_or_else(invalid)?;
+                let whole = whole.parse::<u32>().map_err(|_| invalid())?;
+                let fraction = fraction.parse::<u32>().map_err(|_| invalid())?;
+                let numerator = whole
+                    .checked_mul(denominator)
+                    .and_then(|whole| whole.checked_add(fraction))
+                    .ok_or_else(invalid)?;
+                (numerator, denominator)
+            }
+            (None, None) => (text.parse::<u32>().map_err(|_| invalid())?, 1),
+        };
+        if numerator == 0 {
+            return Err(invalid());
+        }
+        Coefficient::new(numerator, denominator).ok_or_else(invalid)
+    }
+}
+
+impl Display for Coefficient {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
 /// Represents a chemical equation with its reactants and products.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, Deserialize, Serialize)]
 pub struct Equation {
     /// String representation of the equation.
     pub equation: String,
-    /// Map of reactants and their coefficients.
-    pub reactants: HashMap<String, u8>,
-    /// Map of products and their coefficients.
-    pub products: HashMap<String, u8>,
-    /// Map of reactant formulas and their Formula structures.
-    reactants_formulas: HashMap<String, Formula>,
-    /// Map of product formulas and their Formula structures.
-    products_formulas: HashMap<String, Formula>,
+    /// Map of reactants and their coefficients, summed over repeated formulas and multiplied by
+    /// the denominator like those of the terms.
+    pub reactants: BTreeMap<String, u32>,
+    /// Map of products and their coefficients, summed over repeated formulas and multiplied by
+    /// the denominator like those of the terms.
+    pub products: BTreeMap<String, u32>,
+    /// Reactant terms in the order they appear in the equation, including repeated formulas.
+    #[serde(default)]
+    pub reactant_terms: Vec<Term>,
+    /// Product terms in the order they appear in the equation, including repeated formulas.
+    #[serde(default)]
+    pub product_terms: Vec<Term>,
+    /// Kind of arrow separating reactants and products.
+    #[serde(default)]
+    pub arrow: Arrow,
+    /// Reaction conditions written over the arrow or after the products, in their original order.
+    #[serde(default)]
+    pub conditions: Vec<String>,
+    /// Conditions that are chemical formulas, such as `Fe` in `->[Fe, 450°C]`.
+    /// Catalysts are not part of the reactants and are excluded from balance checking.
+    #[serde(default)]
+    pub catalysts: Vec<String>,
+    /// Common denominator of the coefficients of an equation written with fractions, such as 2
+    /// for `H2 + 1/2O2 -> H2O`, whose terms are stored as `2H2 + O2 -> 2H2O`. The coefficient of
+    /// a term is its `coefficient` divided by this, see [`Equation::coefficient`]; it is 1 for
+    /// equations with whole coefficients.
+    ///
+    /// Reaction enthalpies, Hess's law combinations, equilibrium exponents, reaction extents and
+    /// rendered equations use the coefficients as written, while [`Equation::normalize`],
+    /// [`Equation::diagnose`] and the stoichiometric matrix clear the denominator first, as
+    /// [`Equation::to_integer_coefficients`] does.
+    #[serde(
+        default = "whole_denominator",
+        skip_serializing_if = "is_whole_denominator"
+    )]
+    pub denominator: u32,
+}
+
+/// Denominator of equations with whole coefficients.
+fn whole_denominator() -> u32 {
+    1
+}
+
+/// Checks if the denominator of an equation is that of whole coefficients.
+fn is_whole_denominator(denominator: &u32) -> bool {
+    *denominator == 1
 }
 
 impl Display for Equation {
-    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
         write!(
             f,
-            "{} \nReactants: {:?}\nProducts: {:?}",
-            self.equation, self.reactants, self.products
+            "{} \nReactants: {}\nProducts: {}",
+            self.equation,
+            side_to_string(&self.reactant_terms, self.denominator),
+            side_to_string(&self.product_terms, self.denominator)
         )
     }
 }
 
 impl Equation {
-    /// Creates a new Equation instance with reactants, products, and their formulas.
+    /// Creates a new Equation instance from maps of reactants, products, and their formulas.
+    /// The terms of each side are ordered alphabetically by formula.
     pub fn new(
         equation: String,
-        reactants: HashMap<String, u8>,
-        products: HashMap<String, u8>,
-        reactants_formulas: HashMap<String, Formula>,
-        products_formulas: HashMap<String, Formula>,
+        reactants: BTreeMap<String, u32>,
+        products: BTreeMap<String, u32>,
+        reactants_formulas: BTreeMap<String, Formula>,
+        products_formulas: BTreeMap<String, Formula>,
+    ) -> Self {
+        Equation::from_terms(
+            equation,
+            terms_from_map(&reactants, &reactants_formulas),
+            terms_from_map(&products, &products_formulas),
+        )
+    }
+
+    /// Creates a new Equation instance from ordered reactant and product terms.
+    pub fn from_terms(
+        equation: String,
+        reactant_terms: Vec<Term>,
+        product_terms: Vec<Term>,
     ) -> Self {
         Equation {
             equation,
-            reactants,
-            products,
-            reactants_formulas,
-            products_formulas,
+            reactants: side_map(&reactant_terms),
+            products: side_map(&product_terms),
+            reactant_terms,
+            product_terms,
+            arrow: Arrow::default(),
+            conditions: Vec::new(),
+            catalysts: Vec::new(),
+            denominator: 1,
+        }
+    }
+
+    /// Sets the kind of arrow separating reactants and products.
+    pub fn with_arrow(mut self, arrow: Arrow) -> Self {
+        self.arrow = arrow;
+        self
+    }
+
+    /// Sets the reaction conditions and the subset of them that are catalysts.
+    pub fn with_conditions(mut self, conditions: Vec<String>, catalysts: Vec<String>) -> Self {
+        self.conditions = conditions;
+        self.catalysts = catalysts;
+        self
+    }
+
+    /// Returns an equation with new terms and the arrow, conditions and denominator of this one.
+    pub(crate) fn with_terms(
+        &self,
+        reactant_terms: Vec<Term>,
+        product_terms: Vec<Term>,
+    ) -> Equation {
+        let mut equation = Equation::from_terms(String::new(), reactant_terms, product_terms)
+            .with_arrow(self.arrow)
+            .with_conditions(self.conditions.clone(), self.catalysts.clone());
+        equation.denominator = self.denominator;
+        equation.equation = equation.to_parseable_string();
+        equation
+    }
+
+    /// Returns a string representation of the equation that can be parsed back into the same equation,
+    /// keeping the order of terms, coefficients and the arrow kind.
+    pub fn to_parseable_string(&self) -> String {
+        let conditions = if self.conditions.is_empty() {
+            String::new()
+        } else {
+            format!("[{}]", self.conditions.join(", "))
+        };
+        format!(
+            "{} {}{} {}",
+            side_to_string(&self.reactant_terms, self.denominator),
+            self.arrow.symbol(),
+            conditions,
+            side_to_string(&self.product_terms, self.denominator)
+        )
+    }
+
+    /// Returns the coefficient of a term of the equation as written, such as `1/2` for the
+    /// oxygen of `H2 + 1/2O2 -> H2O`.
+    pub fn coefficient(&self, term: &Term) -> Coefficient {
+        Coefficient::new(term.coefficient, self.denominator.max(1)).unwrap_or(Coefficient {
+            numerator: term.coefficient,
+            denominator: 1,
+        })
+    }
+
+    /// Returns the equation with its fractional coefficients multiplied by their common
+    /// denominator, such as `2H2 + O2 -> 2H2O` for `H2 + 1/2O2 -> H2O`. Equations with whole
+    /// coefficients are returned unchanged.
+    pub fn to_integer_coefficients(&self) -> Equation {
+        let mut equation = self.clone();
+        if equation.denominator != 1 {
+            equation.denominator = 1;
+            equation.equation = equation.to_parseable_string();
         }
+        equation
     }
 
     /// Checks if the equation is balanced by comparing the total mass of reactants and products.
     pub fn check_equation(&self) -> bool {
-        let reactant_mass: f64 = self
-            .reactants
-            .iter()
-            .map(|(form, coefficient)| (*coefficient as f64) * self.reactants_formulas[form].mass)
-            .sum();
-        let product_mass: f64 = self
-            .products
+        (side_mass(&self.reactant_terms) - side_mass(&self.product_terms)).abs() < 0.000001
+    }
+
+    /// Checks if the equation is balanced by comparing the number of atoms of each element
+    /// on both sides.
+    ///
+    /// When `isotope_aware` is set, isotope-labelled atoms (for example `13C` or `D`) must
+    /// balance separately from the unlabelled element, otherwise they are counted together.
+    pub fn check_atom_balance(&self, isotope_aware: bool) -> bool {
+        count_atoms(&self.reactant_terms, isotope_aware)
+            == count_atoms(&self.product_terms, isotope_aware)
+    }
+
+    /// Checks if the total charge of the reactants equals the total charge of the products,
+    /// counting electrons written as `e^-`.
+    pub fn check_charge_balance(&self) -> bool {
+        side_charge(&self.reactant_terms) == side_charge(&self.product_terms)
+    }
+
+    /// Returns the equation in canonical form, so that equations from different sources can be
+    /// compared or stored: formulas are written in Hill notation, terms of the same formula and
+    /// state are merged, the terms of each side are ordered by formula, and the coefficients are
+    /// divided by their greatest common divisor. For example `4H2O -> 2O2 + 4H2` becomes
+    /// `2H2O -> 2H2 + O2`, and fractional coefficients are cleared, so `H2 + 1/2O2 -> H2O`
+    /// becomes `2H2 + O2 -> 2H2O`. The arrow and conditions are kept.
+    pub fn normalize(&self) -> Equation {
+        let equation = self.to_integer_coefficients();
+        let mut reactant_terms = canonical_terms(&equation.reactant_terms);
+        let mut product_terms = canonical_terms(&equation.product_terms);
+        let divisor = reactant_terms
             .iter()
-            .map(|(form, coefficient)| (*coefficient as f64) * self.products_formulas[form].mass)
-            .sum();
+            .chain(&product_terms)
+            .map(|term| term.coefficient)
+            .fold(0, gcd)
+            .max(1);
+        for term in reactant_terms.iter_mut().chain(&mut product_terms) {
+            term.coefficient /= divisor;
+        }
+        equation.with_terms(reactant_terms, product_terms)
+    }
+
+    /// Checks if the equation is already in the canonical form of [`Equation::normalize`].
+    pub fn is_normalized(&self) -> bool {
+        let normalized = self.normalize();
+        self.denominator <= 1
+            && terms_written_alike(&self.reactant_terms, &normalized.reactant_terms)
+            && terms_written_alike(&self.product_terms, &normalized.product_terms)
+    }
+}
+
+/// Writes the formulas of terms in Hill notation, merges terms of the same formula and state, and
+/// orders them by formula and state. Electrons keep their notation.
+fn canonical_terms(terms: &[Term]) -> Vec<Term> {
+    let mut canonical: Vec<Term> = Vec::with_capacity(terms.len());
+    for term in terms {
+        let mut formula = term.formula.clone();
+        if !formula.elements.is_empty() {
+            formula.formula = formula.canonical();
+        }
+        match canonical
+            .iter_mut()
+            .find(|other| other.formula.formula == formula.formula && other.state == term.state)
+        {
+            Some(other) => other.coefficient = other.coefficient.saturating_add(term.coefficient),
+            None => canonical.push(Term {
+                coefficient: term.coefficient,
+                formula,
+                state: term.state,
+            }),
+        }
+    }
+    canonical.sort_by(|a, b| {
+        a.formula.formula.cmp(&b.formula.formula).then_with(|| {
+            a.state
+                .as_ref()
+                .map(State::symbol)
+                .cmp(&b.state.as_ref().map(State::symbol))
+        })
+    });
+    canonical
+}
+
+/// Checks if two lists of terms have the same coefficients, notations and states in order.
+fn terms_written_alike(a: &[Term], b: &[Term]) -> bool {
+    a.len() == b.len()
+        && a.iter().zip(b).all(|(a, b)| {
+            a.coefficient == b.coefficient
+                && a.formula.formula == b.formula.formula
+                && a.state == b.state
+        })
+}
+
+/// Builds the terms of one side from a coefficient map, ordered alphabetically by formula.
+fn terms_from_map(side: &BTreeMap<String, u32>, formulas: &BTreeMap<String, Formula>) -> Vec<Term> {
+    let mut terms: Vec<(&String, &u32)> = side.iter().collect();
+    terms.sort();
+    terms
+        .into_iter()
+        .map(|(form, coefficient)| Term {
+            coefficient: *coefficient,
+            formula: formulas[form].clone(),
+            state: None,
+        })
+        .collect()
+}
+
+/// Sums the coefficients of the terms of one side by formula, saturating at `u32::MAX`.
+fn side_map(terms: &[Term]) -> BTreeMap<String, u32> {
+    let mut side = BTreeMap::new();
+    for term in terms {
+        let coefficient = side.entry(term.formula.formula.clone()).or_insert(0u32);
+        *coefficient = coefficient.saturating_add(term.coefficient);
+    }
+    side
+}
+
+/// Joins the terms of one side of an equation with `+`, prefixing coefficients other than 1,
+/// which are written as fractions of the common denominator of the equation.
+fn side_to_string(terms: &[Term], denominator: u32) -> String {
+    terms
+        .iter()
+        .map(|term| {
+            let coefficient = Coefficient::new(term.coefficient, denominator.max(1));
+            let mut written = match coefficient {
+                Some(coefficient) if coefficient.numerator != coefficient.denominator => {
+                    coefficient.to_string()
+                }
+                _ => String::new(),
+            };
+            written.push_str(&term.formula.to_parseable_string());
+            if let Some(state) = term.state {
+                written.push_str(&format!("({})", state.symbol()));
+            }
+            written
+        })
+        .collect::<Vec<String>>()
+        .join(" + ")
+}
+
+/// Sums the charge of one side of an equation, multiplying formula charges by coefficients.
+pub(crate) fn side_charge(terms: &[Term]) -> i64 {
+    terms
+        .iter()
+        .map(|term| i64::from(term.coefficient) * i64::from(term.formula.charge))
+        .sum()
+}
+
+/// Sums the mass of one side of an equation, multiplying formula masses by coefficients.
+fn side_mass(terms: &[Term]) -> f64 {
+    terms
+        .iter()
+        .map(|term| term.coefficient as f64 * term.formula.mass)
+        .sum()
+}
+
+/// Sums the atoms of one side of an equation, multiplying element counts by coefficients.
+pub(crate) fn count_atoms(terms: &[Term], isotope_aware: bool) -> BTreeMap<String, u64> {
+    let mut atoms = BTreeMap::new();
+    for term in terms {
+        for (key, count) in &term.formula.elements {
+            let key = if isotope_aware {
+                key.as_str()
+            } else {
+                element_symbol(key)
+            };
+            *atoms.entry(key.to_string()).or_insert(0) += term.coefficient as u64 * *count as u64;
+        }
+    }
+    atoms
+}
+
+/// Returns the element symbol behind a formula composition key.
+///
+/// Isotope-labelled keys carry a leading mass number (`13C`) or use the `D`/`T`
+/// shorthand for hydrogen isotopes. Plain element symbols are returned unchanged.
+pub fn element_symbol(key: &str) -> &str {
+    parse_isotope_key(key).map_or(key, |(symbol, _)| symbol)
+}
+
+/// Largest number of symbols suggested for an unknown one.
+const MAX_SUGGESTIONS: usize = 5;
 
-        (reactant_mass - product_mass).abs() < 0.000001
+/// Returns the Levenshtein distance between two strings.
+fn edit_distance(a: &str, b: &str) -> usize {
+    let b: Vec<char> = b.chars().collect();
+    let mut previous: Vec<usize> = (0..=b.len()).collect();
+    for (i, a_char) in a.chars().enumerate() {
+        let mut current = vec![i + 1];
+        for (j, b_char) in b.iter().enumerate() {
+            let substitution = previous[j] + usize::from(a_char != *b_char);
+            current.push(substitution.min(previous[j + 1] + 1).min(current[j] + 1));
+        }
+        previous = current;
+    }
+    previous[b.len()]
+}
+
+/// Standard atomic weights revised between IUPAC editions, embedded from `data/atomic_weights.csv`.
+#[cfg(feature = "datasets")]
+const ATOMIC_WEIGHTS_CSV: &str = include_str!("../data/atomic_weights.csv");
+
+/// Represents a bundled edition of the IUPAC standard atomic weights.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Deserialize, Serialize)]
+pub enum DatasetVersion {
+    /// Standard atomic weights 2013.
+    Iupac2013,
+    /// Standard atomic weights 2021.
+    Iupac2021,
+    /// Standard atomic weights 2021 abridged to four significant figures, as printed in most
+    /// textbooks and periodic table charts.
+    Abridged2021,
+}
+
+impl DatasetVersion {
+    /// All bundled dataset versions, from the oldest to the newest.
+    pub const ALL: [DatasetVersion; 3] = [
+        DatasetVersion::Iupac2013,
+        DatasetVersion::Iupac2021,
+        DatasetVersion::Abridged2021,
+    ];
+
+    /// Returns the version with the given name, such as `iupac-2021`, `2021` or `abridged-2021`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        match name
+            .to_lowercase()
+            .trim_start_matches("iupac")
+            .trim_start_matches(['-', '_'])
+        {
+            "2013" => Some(DatasetVersion::Iupac2013),
+            "2021" => Some(DatasetVersion::Iupac2021),
+            "abridged" | "abridged-2021" | "abridged_2021" => Some(DatasetVersion::Abridged2021),
+            _ => None,
+        }
+    }
+
+    /// Returns the name of the version, such as `iupac-2021`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            DatasetVersion::Iupac2013 => "iupac-2013",
+            DatasetVersion::Iupac2021 => "iupac-2021",
+            DatasetVersion::Abridged2021 => "abridged-2021",
+        }
+    }
+}
+
+/// Rounds a value to the given number of significant figures.
+#[cfg(feature = "datasets")]
+pub(crate) fn round_significant(value: f64, figures: i32) -> f64 {
+    if value == 0.0 || !value.is_finite() {
+        return value;
     }
+    let scale = 10f64.powi(figures - 1 - value.abs().log10().floor() as i32);
+    (value * scale).round() / scale
+}
+
+/// Molar mass of a formula in one dataset version, as reported by [`crate::compare_datasets`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct DatasetMass {
+    /// Dataset version the mass was calculated with.
+    pub version: DatasetVersion,
+    /// Molar mass of the formula in this version.
+    pub mass: f64,
+    /// Change of the molar mass relative to the first compared version.
+    pub change: f64,
+}
+
+/// Atomic weights of one element in every bundled dataset version.
+#[cfg(feature = "datasets")]
+#[derive(Deserialize)]
+struct AtomicWeights {
+    symbol: String,
+    iupac_2013: f64,
+    iupac_2021: f64,
+}
+
+/// External identifiers of an element as stored in an identifier file.
+#[cfg(any(feature = "csv", not(feature = "std")))]
+#[derive(Deserialize)]
+struct ElementIdentifiers {
+    symbol: String,
+    #[serde(default)]
+    cas: Option<String>,
+    #[serde(default)]
+    cid: Option<u64>,
+    #[serde(default)]
+    category: Option<String>,
+}
+
+/// Physical properties of an element as stored in a property file.
+#[cfg(any(feature = "csv", not(feature = "std")))]
+#[derive(Deserialize)]
+struct ElementProperties {
+    symbol: String,
+    #[serde(default, deserialize_with = "deserialize_energies")]
+    ionization_energies: Vec<f64>,
+    #[serde(default)]
+    atomic_radius: Option<f64>,
+    #[serde(default)]
+    covalent_radius: Option<f64>,
+    #[serde(default)]
+    abundance: Option<f64>,
+    #[serde(default)]
+    discovery_year: Option<i32>,
+    #[serde(default)]
+    atomic_mass_uncertainty: Option<f64>,
+}
+
+/// Elements and isotopes of a periodic table stored in JSON or TOML.
+#[derive(Deserialize)]
+struct TableData {
+    elements: Vec<Element>,
+    #[serde(default)]
+    isotopes: Vec<Isotope>,
+}
+
+/// Accepted layouts of a JSON periodic table.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum JsonTableData {
+    Table(TableData),
+    Elements(Vec<Element>),
 }
 
 /// Represents a collection of chemical elements from periodic table.
 pub struct PeriodicTable {
-    elements: HashMap<String, Element>,
+    elements: BTreeMap<String, Element>,
+    isotopes: BTreeMap<String, Isotope>,
 }
 
 impl PeriodicTable {
-    /// Loads elements from a CSV file and creates a `PeriodicTable` instance.
-    pub fn from_csv(path: &str) -> Result<Self, Box<dyn Error>> {
-        let mut elements = HashMap::new();
+    /// Creates a `PeriodicTable` from the element and isotope data embedded in the library,
+    /// so that it does not depend on the working directory. It is also available in `no_std`
+    /// builds.
+    #[cfg(any(feature = "datasets", not(feature = "std")))]
+    pub fn embedded() -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let mut table = PeriodicTable::from_data(TableData {
+            elements: embedded::rows(ELEMENTS_CSV)?,
+            isotopes: embedded::rows(ISOTOPES_CSV)?,
+        });
+        for identifiers in embedded::rows(ELEMENT_IDENTIFIERS_CSV)? {
+            table.set_identifiers(identifiers)?;
+        }
+        for properties in embedded::rows(ELEMENT_PROPERTIES_CSV)? {
+            table.set_properties(properties)?;
+        }
+        Ok(table)
+    }
+
+    /// Returns the table of the embedded data shared by the whole process. It is loaded on first
+    /// use, so parsers created with [`crate::ChemParser::new`] do not read the data again.
+    ///
+    /// The embedded data is checked by the test suite, so this does not panic in practice.
+    #[cfg(feature = "datasets")]
+    pub fn global() -> &'static Arc<PeriodicTable> {
+        PeriodicTable::try_global().expect("embedded element data is valid")
+    }
+
+    /// Returns the table shared by the whole process, or the error of loading the embedded data.
+    #[cfg(feature = "datasets")]
+    pub(crate) fn try_global() -> Result<&'static Arc<PeriodicTable>, Box<dyn Error + Send + Sync>>
+    {
+        static GLOBAL: OnceLock<Arc<PeriodicTable>> = OnceLock::new();
+        if let Some(table) = GLOBAL.get() {
+            return Ok(table);
+        }
+        let table = PeriodicTable::embedded()?;
+        Ok(GLOBAL.get_or_init(|| Arc::new(table)))
+    }
+
+    /// Creates a `PeriodicTable` from the embedded data with the standard atomic weights
+    /// of the given IUPAC edition, abridged to four significant figures for
+    /// [`DatasetVersion::Abridged2021`].
+    #[cfg(feature = "datasets")]
+    pub fn bundled(version: DatasetVersion) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let mut table = PeriodicTable::embedded()?;
         let mut rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(ATOMIC_WEIGHTS_CSV.as_bytes());
+
+        for result in rdr.deserialize() {
+            let weights: AtomicWeights = result?;
+            if let Some(element) = table.elements.get_mut(&weights.symbol) {
+                element.atomic_mass = match version {
+                    DatasetVersion::Iupac2013 => weights.iupac_2013,
+                    DatasetVersion::Iupac2021 | DatasetVersion::Abridged2021 => weights.iupac_2021,
+                };
+            }
+        }
+        if version == DatasetVersion::Abridged2021 {
+            for element in table.elements.values_mut() {
+                element.atomic_mass = round_significant(element.atomic_mass, 4);
+            }
+        }
+
+        Ok(table)
+    }
+
+    /// Loads elements from a CSV file and creates a `PeriodicTable` instance.
+    #[cfg(feature = "csv")]
+    pub fn from_csv(path: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let rdr = csv::ReaderBuilder::new()
             .has_headers(true)
             .from_path(path)?;
+        PeriodicTable::from_reader(rdr)
+    }
+
+    /// Loads elements from CSV data and creates a `PeriodicTable` instance.
+    #[cfg(feature = "csv")]
+    pub fn from_csv_str(data: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(data.as_bytes());
+        PeriodicTable::from_reader(rdr)
+    }
+
+    /// Loads elements, and optionally isotopes, from a JSON file and creates a `PeriodicTable` instance.
+    /// See [`PeriodicTable::from_json_str`] for the accepted layouts.
+    #[cfg(feature = "std")]
+    pub fn from_json(path: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        PeriodicTable::from_json_str(&fs::read_to_string(path)?)
+    }
+
+    /// Loads elements, and optionally isotopes, from JSON data and creates a `PeriodicTable` instance.
+    /// The data is either an array of elements or an object with `elements` and optional `isotopes` arrays,
+    /// using the field names of the CSV schema; `number`, `melt` and `boil` are accepted as aliases.
+    pub fn from_json_str(data: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let data = match serde_json::from_str(data)? {
+            JsonTableData::Table(data) => data,
+            JsonTableData::Elements(elements) => TableData {
+                elements,
+                isotopes: Vec::new(),
+            },
+        };
+        Ok(PeriodicTable::from_data(data))
+    }
 
+    /// Loads elements, and optionally isotopes, from a TOML file and creates a `PeriodicTable` instance.
+    #[cfg(feature = "toml")]
+    pub fn from_toml(path: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        PeriodicTable::from_toml_str(&fs::read_to_string(path)?)
+    }
+
+    /// Loads elements, and optionally isotopes, from TOML data with `[[elements]]` and `[[isotopes]]` tables
+    /// and creates a `PeriodicTable` instance.
+    #[cfg(feature = "toml")]
+    pub fn from_toml_str(data: &str) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        Ok(PeriodicTable::from_data(toml::from_str(data)?))
+    }
+
+    fn from_data(data: TableData) -> Self {
+        PeriodicTable {
+            elements: data
+                .elements
+                .into_iter()
+                .map(|element| (element.symbol.clone(), element))
+                .collect(),
+            isotopes: data
+                .isotopes
+                .into_iter()
+                .map(|isotope| (isotope.key(), isotope))
+                .collect(),
+        }
+    }
+
+    #[cfg(feature = "csv")]
+    fn from_reader<R: io::Read>(
+        mut rdr: csv::Reader<R>,
+    ) -> Result<Self, Box<dyn Error + Send + Sync>> {
+        let mut elements = BTreeMap::new();
         for result in rdr.deserialize() {
             let element: Element = result?;
             elements.insert(element.symbol.clone(), element);
         }
 
-        Ok(PeriodicTable { elements })
+        Ok(PeriodicTable {
+            elements,
+            isotopes: BTreeMap::new(),
+        })
+    }
+
+    /// Loads isotopes from a CSV file with `symbol`, `mass_number` and `mass` columns
+    /// and an optional `abundance` column.
+    #[cfg(feature = "csv")]
+    pub fn load_isotopes_csv(&mut self, path: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_path(path)?;
+        self.load_isotopes(rdr)
+    }
+
+    /// Loads isotopes from CSV data with `symbol`, `mass_number` and `mass` columns
+    /// and an optional `abundance` column.
+    #[cfg(feature = "csv")]
+    pub fn load_isotopes_csv_str(
+        &mut self,
+        data: &str,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(data.as_bytes());
+        self.load_isotopes(rdr)
+    }
+
+    #[cfg(feature = "csv")]
+    fn load_isotopes<R: io::Read>(
+        &mut self,
+        mut rdr: csv::Reader<R>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for result in rdr.deserialize() {
+            let isotope: Isotope = result?;
+            self.isotopes.insert(isotope.key(), isotope);
+        }
+
+        Ok(())
+    }
+
+    /// Loads external identifiers of the elements from a CSV file with a `symbol` column and
+    /// optional `cas`, `cid` and `category` columns. Empty fields keep the identifiers the
+    /// elements already have; a symbol that is not in the table is an error.
+    #[cfg(feature = "csv")]
+    pub fn load_identifiers_csv(&mut self, path: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_path(path)?;
+        self.load_identifiers(rdr)
+    }
+
+    /// Loads external identifiers of the elements from CSV data with a `symbol` column and
+    /// optional `cas`, `cid` and `category` columns.
+    #[cfg(feature = "csv")]
+    pub fn load_identifiers_csv_str(
+        &mut self,
+        data: &str,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(data.as_bytes());
+        self.load_identifiers(rdr)
+    }
+
+    #[cfg(feature = "csv")]
+    fn load_identifiers<R: io::Read>(
+        &mut self,
+        mut rdr: csv::Reader<R>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for result in rdr.deserialize() {
+            self.set_identifiers(result?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the identifiers of an element that are not empty.
+    #[cfg(any(feature = "csv", not(feature = "std")))]
+    fn set_identifiers(
+        &mut self,
+        identifiers: ElementIdentifiers,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let element = self
+            .elements
+            .get_mut(&identifiers.symbol)
+            .ok_or_else(|| format!("unknown element {}", identifiers.symbol))?;
+        if identifiers.cas.is_some() {
+            element.cas = identifiers.cas;
+        }
+        if identifiers.cid.is_some() {
+            element.cid = identifiers.cid;
+        }
+        if identifiers.category.is_some() {
+            element.category = identifiers.category;
+        }
+        Ok(())
+    }
+
+    /// Loads physical properties of the elements from a CSV file with a `symbol` column and
+    /// optional `ionization_energies` (kJ/mol, separated by spaces), `atomic_radius` and
+    /// `covalent_radius` (pm), `abundance` (mg/kg), `discovery_year` and `atomic_mass_uncertainty`
+    /// columns. Empty fields keep the properties the elements already have; a symbol that is not
+    /// in the table is an error.
+    #[cfg(feature = "csv")]
+    pub fn load_properties_csv(&mut self, path: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_path(path)?;
+        self.load_properties(rdr)
+    }
+
+    /// Loads physical properties of the elements from CSV data with the columns of
+    /// [`PeriodicTable::load_properties_csv`].
+    #[cfg(feature = "csv")]
+    pub fn load_properties_csv_str(
+        &mut self,
+        data: &str,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let rdr = csv::ReaderBuilder::new()
+            .has_headers(true)
+            .from_reader(data.as_bytes());
+        self.load_properties(rdr)
+    }
+
+    #[cfg(feature = "csv")]
+    fn load_properties<R: io::Read>(
+        &mut self,
+        mut rdr: csv::Reader<R>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for result in rdr.deserialize() {
+            self.set_properties(result?)?;
+        }
+
+        Ok(())
+    }
+
+    /// Sets the properties of an element that are not empty.
+    #[cfg(any(feature = "csv", not(feature = "std")))]
+    fn set_properties(
+        &mut self,
+        properties: ElementProperties,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        let element = self
+            .elements
+            .get_mut(&properties.symbol)
+            .ok_or_else(|| format!("unknown element {}", properties.symbol))?;
+        if !properties.ionization_energies.is_empty() {
+            element.ionization_energies = properties.ionization_energies;
+        }
+        if properties.atomic_radius.is_some() {
+            element.atomic_radius = properties.atomic_radius;
+        }
+        if properties.covalent_radius.is_some() {
+            element.covalent_radius = properties.covalent_radius;
+        }
+        if properties.abundance.is_some() {
+            element.abundance = properties.abundance;
+        }
+        if properties.discovery_year.is_some() {
+            element.discovery_year = properties.discovery_year;
+        }
+        if properties.atomic_mass_uncertainty.is_some() {
+            element.atomic_mass_uncertainty = properties.atomic_mass_uncertainty;
+        }
+        Ok(())
+    }
+
+    /// Iterates over the elements of the table in order of atomic number.
+    pub fn iter(&self) -> impl Iterator<Item = &Element> {
+        self.sorted_by(|a, b| a.atomic_number.cmp(&b.atomic_number))
+            .into_iter()
+    }
+
+    /// Starts a search over the elements of the table by their properties.
+    pub fn query(&self) -> ElementQuery<'_> {
+        ElementQuery::new(self)
+    }
+
+    /// Returns the number of elements in the table.
+    pub fn len(&self) -> usize {
+        self.elements.len()
+    }
+
+    /// Returns `true` if the table has no elements.
+    pub fn is_empty(&self) -> bool {
+        self.elements.is_empty()
+    }
+
+    /// Returns the elements of the table ordered by atomic mass.
+    pub fn sorted_by_mass(&self) -> Vec<&Element> {
+        self.sorted_by(|a, b| a.atomic_mass.total_cmp(&b.atomic_mass))
+    }
+
+    /// Returns the elements of the table ordered alphabetically by symbol.
+    pub fn sorted_by_symbol(&self) -> Vec<&Element> {
+        self.sorted_by(|a, b| a.symbol.cmp(&b.symbol))
+    }
+
+    /// Returns the elements of the given group (column) of the periodic table in order of atomic number.
+    pub fn elements_in_group(&self, group: u8) -> Vec<&Element> {
+        self.iter()
+            .filter(|element| element.group == Some(group))
+            .collect()
+    }
+
+    /// Returns the elements of the given period (row) of the periodic table in order of atomic number.
+    pub fn elements_in_period(&self, period: u8) -> Vec<&Element> {
+        self.iter()
+            .filter(|element| element.period() == period)
+            .collect()
+    }
+
+    /// Returns the metals of the table in order of atomic number.
+    pub fn metals(&self) -> Vec<&Element> {
+        self.iter().filter(|element| element.is_metal()).collect()
+    }
+
+    /// Returns the nonmetals of the table in order of atomic number.
+    pub fn nonmetals(&self) -> Vec<&Element> {
+        self.iter()
+            .filter(|element| element.is_nonmetal())
+            .collect()
+    }
+
+    /// Returns the metalloids of the table in order of atomic number.
+    pub fn metalloids(&self) -> Vec<&Element> {
+        self.iter()
+            .filter(|element| element.is_metalloid())
+            .collect()
+    }
+
+    fn sorted_by(&self, compare: impl Fn(&Element, &Element) -> Ordering) -> Vec<&Element> {
+        let mut elements: Vec<&Element> = self.elements.values().collect();
+        elements.sort_by(|a, b| compare(a, b));
+        elements
     }
 
     /// Retrieves an element by its symbol.
     pub fn get_element(&self, symbol: &str) -> Option<&Element> {
         self.elements.get(symbol)
     }
+
+    /// Retrieves an element by its atomic number.
+    pub fn get_by_atomic_number(&self, atomic_number: u8) -> Option<&Element> {
+        self.elements
+            .values()
+            .find(|element| element.atomic_number == atomic_number)
+    }
+
+    /// Retrieves an element by its full name, ignoring case, for example `oxygen`.
+    pub fn get_by_name(&self, name: &str) -> Option<&Element> {
+        self.elements
+            .values()
+            .find(|element| element.name.eq_ignore_ascii_case(name))
+    }
+
+    /// Retrieves an element by its CAS registry number, for example `7439-89-6` for iron.
+    pub fn get_by_cas(&self, cas: &str) -> Option<&Element> {
+        let cas = cas.trim();
+        self.elements
+            .values()
+            .find(|element| element.cas.as_deref() == Some(cas))
+    }
+
+    /// Suggests valid element symbols close to an unknown one, best first: the symbol with fixed
+    /// letter case (`CL` → `Cl`), then symbols one edit away (`Nx` → `Na`, `Nb`, …).
+    pub fn suggest_symbols(&self, symbol: &str) -> Vec<String> {
+        let lowercase = symbol.to_lowercase();
+        let mut candidates: Vec<(usize, bool, &str)> = self
+            .elements
+            .keys()
+            .filter(|candidate| candidate.as_str() != symbol)
+            .filter_map(|candidate| {
+                let distance = edit_distance(&lowercase, &candidate.to_lowercase());
+                let same_start = candidate.chars().next() == symbol.chars().next();
+                (distance <= 1).then_some((distance, !same_start, candidate.as_str()))
+            })
+            .collect();
+        candidates.sort();
+        candidates
+            .into_iter()
+            .take(MAX_SUGGESTIONS)
+            .map(|(_, _, candidate)| candidate.to_string())
+            .collect()
+    }
+
+    /// Checks whether the table has isotope data, which isotope notations need.
+    pub fn has_isotopes(&self) -> bool {
+        !self.isotopes.is_empty()
+    }
+
+    /// Retrieves an isotope by its composition key, for example `13C` or `D`.
+    pub fn get_isotope(&self, key: &str) -> Option<&Isotope> {
+        self.isotopes.get(key)
+    }
+
+    /// Returns the known isotopes of an element in order of mass number.
+    pub fn isotopes_of(&self, symbol: &str) -> Vec<&Isotope> {
+        let mut isotopes: Vec<&Isotope> = self
+            .isotopes
+            .values()
+            .filter(|isotope| isotope.symbol == symbol)
+            .collect();
+        isotopes.sort_by_key(|isotope| isotope.mass_number);
+        isotopes
+    }
+
+    /// Returns the naturally most abundant isotope of an element, or `None` if the element
+    /// has no isotope with a known abundance.
+    pub fn most_abundant_isotope(&self, symbol: &str) -> Option<&Isotope> {
+        self.isotopes_of(symbol)
+            .into_iter()
+            .filter(|isotope| isotope.abundance.is_some())
+            .max_by(|a, b| {
+                a.abundance
+                    .partial_cmp(&b.abundance)
+                    .unwrap_or(Ordering::Equal)
+            })
+    }
+
+    /// Returns the monoisotopic mass of a formula composition key: the exact mass of the most
+    /// abundant isotope for an element symbol, or of the isotope itself for an isotope key.
+    pub fn get_monoisotopic_mass(&self, key: &str) -> Option<f64> {
+        match self.get_element(key) {
+            Some(element) => self
+                .most_abundant_isotope(&element.symbol)
+                .map(|isotope| isotope.mass),
+            None => self.get_isotope(key).map(|isotope| isotope.mass),
+        }
+    }
+
+    /// Returns the copy of a formula composition key stored in the table, either an element symbol
+    /// or an isotope key, so compositions can be counted without allocating a string per atom.
+    pub(crate) fn interned_key(&self, key: &str) -> Option<&str> {
+        self.elements
+            .get_key_value(key)
+            .map(|(symbol, _)| symbol)
+            .or_else(|| self.isotopes.get_key_value(key).map(|(key, _)| key))
+            .map(String::as_str)
+    }
+
+    /// Returns the mass of a formula composition key, which is either an element symbol
+    /// (standard atomic mass) or an isotope key (exact isotopic mass).
+    pub fn get_mass(&self, key: &str) -> Option<f64> {
+        match self.get_element(key) {
+            Some(element) => Some(element.atomic_mass),
+            None => self.get_isotope(key).map(|isotope| isotope.mass),
+        }
+    }
+
+    /// Returns a checksum of the element and isotope masses of the table, such as `fnv1a64:5c3e…`.
+    /// Tables with the same data have the same checksum regardless of how they were loaded.
+    pub fn checksum(&self) -> String {
+        let mut elements: Vec<&Element> = self.elements.values().collect();
+        elements.sort_by(|a, b| (a.atomic_number, &a.symbol).cmp(&(b.atomic_number, &b.symbol)));
+        let mut isotopes: Vec<&Isotope> = self.isotopes.values().collect();
+        isotopes.sort_by_key(|isotope| isotope.key());
+
+        let mut hash: u64 = 0xcbf2_9ce4_8422_2325;
+        let lines = elements
+            .iter()
+            .map(|e| format!("{}:{}:{}\n", e.symbol, e.atomic_number, e.atomic_mass))
+            .chain(isotopes.iter().map(|i| format!("{}:{}\n", i.key(), i.mass)));
+        for byte in lines.flat_map(String::into_bytes) {
+            hash ^= byte as u64;
+            hash = hash.wrapping_mul(0x0100_0000_01b3);
+        }
+        format!("fnv1a64:{:016x}", hash)
+    }
 }
@@ -0,0 +1,87 @@
+//! # Embedded data Module
+//!
+//! This module holds the element, isotope, identifier and property data embedded in the library and reads
+//! it without the `csv` crate or the standard library, so that `no_std` builds, such as firmware
+//! of lab instruments, parse formulas with the same periodic table as the rest of the library.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use serde::de::DeserializeOwned;
+use serde_json::{Map, Number, Value};
+
+/// Element data embedded at compile time from `data/elements.csv`.
+pub(crate) const ELEMENTS_CSV: &str = include_str!("../data/elements.csv");
+
+/// Isotope data embedded at compile time from `data/isotopes.csv`.
+pub(crate) const ISOTOPES_CSV: &str = include_str!("../data/isotopes.csv");
+
+/// CAS registry numbers and categories of the elements, embedded from
+/// `data/element_identifiers.csv`.
+pub(crate) const ELEMENT_IDENTIFIERS_CSV: &str = include_str!("../data/element_identifiers.csv");
+
+/// Ionization energies, radii, crustal abundances, discovery years and atomic mass uncertainties
+/// of the elements, embedded from `data/element_properties.csv`.
+pub(crate) const ELEMENT_PROPERTIES_CSV: &str = include_str!("../data/element_properties.csv");
+
+/// Deserializes the rows of embedded CSV data with a header line. Fields are read like the `csv`
+/// crate reads them into untyped values: booleans and numbers where they parse as such, strings
+/// otherwise, and empty fields as missing.
+pub(crate) fn rows<T: DeserializeOwned>(data: &str) -> Result<Vec<T>, serde_json::Error> {
+    let mut lines = data.lines().filter(|line| !line.trim().is_empty());
+    let headers = lines.next().map(fields).unwrap_or_default();
+    lines
+        .map(|line| {
+            let record: Map<String, Value> = headers
+                .iter()
+                .zip(fields(line))
+                .filter(|(_, field)| !field.is_empty())
+                .map(|(header, field)| (header.to_string(), value(field)))
+                .collect();
+            serde_json::from_value(Value::Object(record))
+        })
+        .collect()
+}
+
+/// Splits a line of CSV data into its fields, removing the quotes around quoted fields. Quoted
+/// fields may contain commas but no quotes, which the embedded data does not use.
+fn fields(line: &str) -> Vec<&str> {
+    let mut fields = Vec::new();
+    let mut rest = line.trim_end_matches('\r');
+    loop {
+        let (field, next) = match rest.strip_prefix('"') {
+            Some(quoted) => {
+                let end = quoted.find('"').unwrap_or(quoted.len());
+                let after = &quoted[(end + 1).min(quoted.len())..];
+                (&quoted[..end], after.strip_prefix(','))
+            }
+            None => match rest.split_once(',') {
+                Some((field, next)) => (field, Some(next)),
+                None => (rest, None),
+            },
+        };
+        fields.push(field);
+        match next {
+            Some(next) => rest = next,
+            None => return fields,
+        }
+    }
+}
+
+/// Reads a field as a boolean, an integer, a float or a string, in that order.
+fn value(field: &str) -> Value {
+    match field {
+        "true" => Value::Bool(true),
+        "false" => Value::Bool(false),
+        _ => {
+            if let Ok(number) = field.parse::<u64>() {
+                Value::Number(number.into())
+            } else if let Ok(number) = field.parse::<i64>() {
+                Value::Number(number.into())
+            } else if let Some(number) = field.parse::<f64>().ok().and_then(Number::from_f64) {
+                Value::Number(number)
+            } else {
+                Value::String(field.to_string())
+            }
+        }
+    }
+}
@@ -0,0 +1,117 @@
+//! # Equilibrium Module
+//!
+//! This module builds the equilibrium constant expression of an equation, the concentrations of
+//! the products over those of the reactants, each raised to its coefficient, and renders it as text
+//! or LaTeX.
+
+use crate::element::{Coefficient, Equation, State, Term};
+use serde::Serialize;
+use std::fmt::{self, Display, Formatter};
+
+/// Equilibrium constant expression, such as `[NH3]^2 / ([N2][H2]^3)` for `N2 + 3H2 <=> 2NH3`.
+///
+/// Pure solids and liquids, written with `(s)` or `(l)`, do not take part, and neither do the
+/// electrons of half-reactions.
+#[derive(Debug, Clone, Serialize)]
+pub struct EquilibriumExpression {
+    /// Products in the numerator, with their coefficients as exponents.
+    pub numerator: Vec<Term>,
+    /// Reactants in the denominator, with their coefficients as exponents.
+    pub denominator: Vec<Term>,
+    /// Common denominator of the exponents, greater than 1 for an equation written with
+    /// fractional coefficients, as [`Equation::denominator`].
+    pub exponent_denominator: u32,
+}
+
+impl EquilibriumExpression {
+    /// Returns the exponent of a term of the expression, such as `1/2` for the oxygen of
+    /// `H2 + 1/2O2 <=> H2O`.
+    pub fn exponent(&self, term: &Term) -> Coefficient {
+        Coefficient::new(term.coefficient, self.exponent_denominator.max(1)).unwrap_or(
+            Coefficient {
+                numerator: term.coefficient,
+                denominator: 1,
+            },
+        )
+    }
+
+    /// Renders the expression as math-mode LaTeX, for example
+    /// `\frac{[\mathrm{NH}_{3}]^{2}}{[\mathrm{N}_{2}][\mathrm{H}_{2}]^{3}}`.
+    pub fn to_latex(&self) -> String {
+        let side = |terms: &[Term]| -> String {
+            if terms.is_empty() {
+                return String::from("1");
+            }
+            terms
+                .iter()
+                .map(|term| match self.exponent(term) {
+                    power if power.numerator == 1 && power.is_integer() => {
+                        format!("[{}]", term.formula.to_latex_math())
+                    }
+                    power => format!("[{}]^{{{}}}", term.formula.to_latex_math(), power),
+                })
+                .collect()
+        };
+        format!(
+            "\\frac{{{}}}{{{}}}",
+            side(&self.numerator),
+            side(&self.denominator)
+        )
+    }
+}
+
+impl Display for EquilibriumExpression {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let side = |terms: &[Term]| -> String {
+            terms
+                .iter()
+                .map(|term| match self.exponent(term) {
+                    power if power.numerator == 1 && power.is_integer() => {
+                        format!("[{}]", term.formula.formula)
+                    }
+                    power if power.is_integer() => format!("[{}]^{}", term.formula.formula, power),
+                    power => format!("[{}]^({})", term.formula.formula, power),
+                })
+                .collect()
+        };
+        let numerator = match self.numerator.len() {
+            0 => String::from("1"),
+            _ => side(&self.numerator),
+        };
+        match self.denominator.len() {
+            0 => write!(f, "{}", numerator),
+            1 => write!(f, "{} / {}", numerator, side(&self.denominator)),
+            _ => write!(f, "{} / ({})", numerator, side(&self.denominator)),
+        }
+    }
+}
+
+impl Equation {
+    /// Returns the equilibrium constant expression of the equation as written. Species written
+    /// more than once on a side are merged, adding up their coefficients.
+    pub fn equilibrium_expression(&self) -> EquilibriumExpression {
+        EquilibriumExpression {
+            numerator: concentration_terms(&self.product_terms),
+            denominator: concentration_terms(&self.reactant_terms),
+            exponent_denominator: self.denominator.max(1),
+        }
+    }
+}
+
+/// Merges the terms of a side by formula and leaves out solids, liquids and species without atoms.
+fn concentration_terms(terms: &[Term]) -> Vec<Term> {
+    let mut merged: Vec<Term> = Vec::new();
+    for term in terms.iter().filter(|term| {
+        !term.formula.elements.is_empty()
+            && !matches!(term.state, Some(State::Solid | State::Liquid))
+    }) {
+        match merged
+            .iter_mut()
+            .find(|other| other.formula.formula == term.formula.formula)
+        {
+            Some(other) => other.coefficient += term.coefficient,
+            None => merged.push(term.clone()),
+        }
+    }
+    merged
+}
@@ -0,0 +1,181 @@
+//! # Export Module
+//!
+//! This module builds reference tables from parsed formulas, such as a molar-mass sheet for a
+//! list of compounds or the breakdown of the molar mass of one formula by element, and writes
+//! them as CSV or JSON.
+
+use crate::element::{Formula, PeriodicTable};
+use serde::Serialize;
+use std::fmt::{self, Display, Formatter};
+use std::io::{self, Write};
+
+/// Number of decimal places of the masses written to reference tables.
+pub const MASS_DECIMALS: usize = 4;
+
+/// Row of a molar-mass table as written to JSON.
+#[derive(Serialize)]
+struct MassRow<'a> {
+    formula: &'a str,
+    molar_mass: f64,
+}
+
+/// Returns every formula as written with its molar mass, in the order of the formulas.
+pub fn mass_table(formulas: &[Formula]) -> Vec<(String, f64)> {
+    formulas
+        .iter()
+        .map(|formula| (formula.formula.clone(), formula.mass))
+        .collect()
+}
+
+/// Writes a molar-mass table as CSV with `formula` and `molar_mass` columns.
+#[cfg(feature = "csv")]
+pub fn write_mass_table_csv<W: Write>(table: &[(String, f64)], writer: W) -> io::Result<()> {
+    let mut writer = csv::Writer::from_writer(writer);
+    writer.write_record(["formula", "molar_mass"])?;
+    for (formula, mass) in table {
+        writer.write_record([formula, &format!("{:.*}", MASS_DECIMALS, mass)])?;
+    }
+    writer.flush()
+}
+
+/// Writes a molar-mass table as a JSON array of objects with `formula` and `molar_mass` fields.
+pub fn write_mass_table_json<W: Write>(table: &[(String, f64)], mut writer: W) -> io::Result<()> {
+    let scale = 10f64.powi(MASS_DECIMALS as i32);
+    let rows: Vec<MassRow> = table
+        .iter()
+        .map(|(formula, mass)| MassRow {
+            formula,
+            molar_mass: (mass * scale).round() / scale,
+        })
+        .collect();
+    serde_json::to_writer_pretty(&mut writer, &rows)?;
+    writeln!(writer)
+}
+
+/// Contribution of an element or isotope to the molar mass of a formula.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct ElementMass {
+    /// Symbol of the element, or the key of an isotope such as `13C`.
+    pub element: String,
+    /// Number of atoms in the formula.
+    pub count: u32,
+    /// Mass of one atom, the standard atomic mass or the exact mass of an isotope.
+    pub atom_mass: f64,
+    /// Mass of all the atoms, `count` times `atom_mass`.
+    pub subtotal: f64,
+    /// Share of the molar mass, in percent.
+    pub percent: f64,
+}
+
+/// Molar mass of a formula broken down by element, see [`Formula::mass_breakdown`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct MassBreakdown {
+    /// Formula as written.
+    pub formula: String,
+    /// Contributions of the elements and isotopes, in Hill order.
+    pub elements: Vec<ElementMass>,
+    /// Molar mass, the sum of the subtotals.
+    pub total: f64,
+}
+
+impl Formula {
+    /// Breaks the molar mass of the formula down by element in Hill order, with the number of
+    /// atoms, the mass of one atom from the table, their subtotal and its percentage of the molar
+    /// mass, such as 2 × 1.008 = 2.016, or 11.19 %, for the hydrogen of `H2O`.
+    ///
+    /// Returns `None` if an element or isotope of the formula is not in the table.
+    pub fn mass_breakdown(&self, table: &PeriodicTable) -> Option<MassBreakdown> {
+        let mut elements = self
+            .hill_keys()
+            .into_iter()
+            .map(|key| {
+                let count = self.elements[key];
+                let atom_mass = table.get_mass(key)?;
+                Some(ElementMass {
+                    element: key.clone(),
+                    count,
+                    atom_mass,
+                    subtotal: atom_mass * count as f64,
+                    percent: 0.0,
+                })
+            })
+            .collect::<Option<Vec<ElementMass>>>()?;
+        let total: f64 = elements.iter().map(|element| element.subtotal).sum();
+        if total > 0.0 {
+            for element in &mut elements {
+                element.percent = element.subtotal / total * 100.0;
+            }
+        }
+        Some(MassBreakdown {
+            formula: self.formula.clone(),
+            elements,
+            total,
+        })
+    }
+}
+
+impl MassBreakdown {
+    /// Writes the breakdown as CSV with `element`, `count`, `atom_mass`, `subtotal` and `percent`
+    /// columns, one row per element, and a last `total` row.
+    #[cfg(feature = "csv")]
+    pub fn write_csv<W: Write>(&self, writer: W) -> io::Result<()> {
+        let mut writer = csv::Writer::from_writer(writer);
+        writer.write_record(["element", "count", "atom_mass", "subtotal", "percent"])?;
+        for element in &self.elements {
+            writer.write_record([
+                element.element.clone(),
+                element.count.to_string(),
+                format!("{:.*}", MASS_DECIMALS, element.atom_mass),
+                format!("{:.*}", MASS_DECIMALS, element.subtotal),
+                format!("{:.2}", element.percent),
+            ])?;
+        }
+        let percent = if self.elements.is_empty() { 0.0 } else { 100.0 };
+        writer.write_record([
+            String::from("total"),
+            String::new(),
+            String::new(),
+            format!("{:.*}", MASS_DECIMALS, self.total),
+            format!("{:.2}", percent),
+        ])?;
+        writer.flush()
+    }
+}
+
+/// Writes the breakdown as an aligned table under the formula, one line per element such as
+/// `O        1 ×   15.9990 = 15.9990   88.81 %`, and a last line with the molar mass.
+impl Display for MassBreakdown {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        let width = self
+            .elements
+            .iter()
+            .map(|element| element.element.chars().count())
+            .max()
+            .unwrap_or(0)
+            .max("Total".len());
+        let subtotal = format!("{:.*}", MASS_DECIMALS, self.total).len();
+        writeln!(f, "{}", self.formula)?;
+        for element in &self.elements {
+            writeln!(
+                f,
+                "{:<width$} {:>4} × {:>9.*} = {:>subtotal$.*} {:>7.2} %",
+                element.element,
+                element.count,
+                MASS_DECIMALS,
+                element.atom_mass,
+                MASS_DECIMALS,
+                element.subtotal,
+                element.percent,
+            )?;
+        }
+        write!(
+            f,
+            "{:<width$} {:>18} {:>subtotal$.*} {:>7.2} %",
+            "Total",
+            "",
+            MASS_DECIMALS,
+            self.total,
+            if self.elements.is_empty() { 0.0 } else { 100.0 },
+        )
+    }
+}
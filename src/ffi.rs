@@ -0,0 +1,294 @@
+//! # FFI Module
+//!
+//! This module exposes the parser to C and C++ through `extern "C"` functions, declared in
+//! `include/chemistry_parser.h`, which is generated from this file with `make header`.
+//! Parsed formulas and equations are returned as opaque handles and strings as NUL-terminated
+//! UTF-8, all owned by the caller and released with the matching `chem_free_*` function. A
+//! failed call returns a null pointer and leaves its message in [`chem_last_error`]. Panics are
+//! caught at the boundary and reported the same way, so they never unwind into the caller.
+
+use crate::balance::Balance;
+use crate::element::{Equation, Formula};
+use crate::ChemParser;
+use std::any::Any;
+use std::cell::RefCell;
+use std::ffi::{c_char, CStr, CString};
+use std::panic::{self, AssertUnwindSafe};
+use std::ptr;
+use std::sync::OnceLock;
+
+/// Opaque handle of a parsed formula.
+pub struct ChemFormula(Formula);
+
+/// Opaque handle of a parsed equation.
+pub struct ChemEquation(Equation);
+
+thread_local! {
+    /// Message of the last failed call on this thread.
+    static LAST_ERROR: RefCell<Option<CString>> = const { RefCell::new(None) };
+}
+
+/// Returns the parser shared by all calls, created on first use.
+fn parser() -> &'static ChemParser {
+    static PARSER: OnceLock<ChemParser> = OnceLock::new();
+    PARSER.get_or_init(ChemParser::new)
+}
+
+/// Records the message of a failed call.
+fn set_error(message: impl ToString) {
+    let message = CString::new(message.to_string().replace('\0', " ")).unwrap_or_default();
+    LAST_ERROR.with(|error| *error.borrow_mut() = Some(message));
+}
+
+/// Runs the body of a call, returning `fallback` and recording the message of a panic as the
+/// error of the call instead of unwinding across the C boundary.
+fn guard<T>(fallback: T, body: impl FnOnce() -> T) -> T {
+    panic::catch_unwind(AssertUnwindSafe(body)).unwrap_or_else(|payload| {
+        set_error(format!(
+            "internal error: {}",
+            panic_message(payload.as_ref())
+        ));
+        fallback
+    })
+}
+
+/// Returns the message of a panic payload.
+fn panic_message(payload: &(dyn Any + Send)) -> &str {
+    payload
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| payload.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("the library panicked")
+}
+
+/// Reads a string argument, recording an error for null pointers and invalid UTF-8.
+unsafe fn read_str<'a>(text: *const c_char) -> Option<&'a str> {
+    if text.is_null() {
+        set_error("null pointer passed as a string");
+        return None;
+    }
+    match CStr::from_ptr(text).to_str() {
+        Ok(text) => Some(text),
+        Err(e) => {
+            set_error(e);
+            None
+        }
+    }
+}
+
+/// Moves a string to the caller, or returns null and records an error if it contains NUL.
+fn into_c_string(text: String) -> *mut c_char {
+    match CString::new(text) {
+        Ok(text) => text.into_raw(),
+        Err(e) => {
+            set_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Moves the result of a call to the caller, or returns null and records its error.
+fn into_handle<T, E: ToString>(result: Result<T, E>) -> *mut T {
+    match result {
+        Ok(value) => Box::into_raw(Box::new(value)),
+        Err(e) => {
+            set_error(e);
+            ptr::null_mut()
+        }
+    }
+}
+
+/// Returns the message of the last failed call on this thread, or null if no call failed. The
+/// message is owned by the library and valid until the next failed call on the thread.
+#[no_mangle]
+pub extern "C" fn chem_last_error() -> *const c_char {
+    guard(ptr::null(), || {
+        LAST_ERROR.with(|error| error.borrow().as_ref().map_or(ptr::null(), |e| e.as_ptr()))
+    })
+}
+
+/// Parses a formula, such as `H2SO4`, returning null on errors.
+///
+/// # Safety
+///
+/// `formula` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn chem_parse_formula(formula: *const c_char) -> *mut ChemFormula {
+    guard(ptr::null_mut(), || match read_str(formula) {
+        Some(formula) => into_handle(parser().parse_formula(formula).map(ChemFormula)),
+        None => ptr::null_mut(),
+    })
+}
+
+/// Returns the molecular mass of a formula in g/mol, or NaN for a null formula.
+///
+/// # Safety
+///
+/// `formula` must be null or a handle returned by [`chem_parse_formula`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_formula_mass(formula: *const ChemFormula) -> f64 {
+    guard(f64::NAN, || {
+        formula.as_ref().map_or(f64::NAN, |formula| formula.0.mass)
+    })
+}
+
+/// Returns the electric charge of a formula, zero for neutral species and null formulas.
+///
+/// # Safety
+///
+/// `formula` must be null or a handle returned by [`chem_parse_formula`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_formula_charge(formula: *const ChemFormula) -> i32 {
+    guard(0, || formula.as_ref().map_or(0, |formula| formula.0.charge))
+}
+
+/// Returns the JSON of a formula, to be freed with [`chem_free_string`], or null on errors.
+///
+/// # Safety
+///
+/// `formula` must be null or a handle returned by [`chem_parse_formula`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_formula_to_json(formula: *const ChemFormula) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let Some(ChemFormula(formula)) = formula.as_ref() else {
+            set_error("null pointer passed as a formula");
+            return ptr::null_mut();
+        };
+        match serde_json::to_string(formula) {
+            Ok(json) => into_c_string(json),
+            Err(e) => {
+                set_error(e);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Parses an equation, such as `2H2 + O2 -> 2H2O`, returning null on errors.
+///
+/// # Safety
+///
+/// `equation` must be null or a NUL-terminated string.
+#[no_mangle]
+pub unsafe extern "C" fn chem_parse_equation(equation: *const c_char) -> *mut ChemEquation {
+    guard(ptr::null_mut(), || match read_str(equation) {
+        Some(equation) => into_handle(parser().parse_equation(equation).map(ChemEquation)),
+        None => ptr::null_mut(),
+    })
+}
+
+/// Returns whether an equation conserves every element and the charge, false for null.
+///
+/// # Safety
+///
+/// `equation` must be null or a handle returned by [`chem_parse_equation`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_equation_is_balanced(equation: *const ChemEquation) -> bool {
+    guard(false, || {
+        equation
+            .as_ref()
+            .is_some_and(|equation| equation.0.validate().is_ok())
+    })
+}
+
+/// Returns an equation with the smallest balancing coefficients, each at most
+/// `max_coefficient`, to be freed with [`chem_free_string`], or null if it cannot be balanced.
+///
+/// # Safety
+///
+/// `equation` must be null or a handle returned by [`chem_parse_equation`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_equation_balance(
+    equation: *const ChemEquation,
+    max_coefficient: u32,
+) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let Some(ChemEquation(equation)) = equation.as_ref() else {
+            set_error("null pointer passed as an equation");
+            return ptr::null_mut();
+        };
+        match equation.balance(max_coefficient) {
+            Ok(Balance::AlreadyBalanced) => into_c_string(equation.equation.clone()),
+            Ok(Balance::Balanced(balanced)) => into_c_string(balanced.equation),
+            Ok(Balance::Independent(_)) => {
+                set_error("the equation combines independent reactions and has no single balance");
+                ptr::null_mut()
+            }
+            Ok(Balance::Impossible(keys)) => {
+                set_error(format!(
+                    "the equation cannot be balanced, {} appear on only one side",
+                    keys.join(", ")
+                ));
+                ptr::null_mut()
+            }
+            Err(e) => {
+                set_error(e);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Returns the JSON of an equation, to be freed with [`chem_free_string`], or null on errors.
+///
+/// # Safety
+///
+/// `equation` must be null or a handle returned by [`chem_parse_equation`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_equation_to_json(equation: *const ChemEquation) -> *mut c_char {
+    guard(ptr::null_mut(), || {
+        let Some(ChemEquation(equation)) = equation.as_ref() else {
+            set_error("null pointer passed as an equation");
+            return ptr::null_mut();
+        };
+        match serde_json::to_string(equation) {
+            Ok(json) => into_c_string(json),
+            Err(e) => {
+                set_error(e);
+                ptr::null_mut()
+            }
+        }
+    })
+}
+
+/// Frees a formula returned by [`chem_parse_formula`]; null is ignored.
+///
+/// # Safety
+///
+/// `formula` must be null or a handle returned by [`chem_parse_formula`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_free_formula(formula: *mut ChemFormula) {
+    guard((), || {
+        if !formula.is_null() {
+            drop(Box::from_raw(formula));
+        }
+    })
+}
+
+/// Frees an equation returned by [`chem_parse_equation`]; null is ignored.
+///
+/// # Safety
+///
+/// `equation` must be null or a handle returned by [`chem_parse_equation`] and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_free_equation(equation: *mut ChemEquation) {
+    guard((), || {
+        if !equation.is_null() {
+            drop(Box::from_raw(equation));
+        }
+    })
+}
+
+/// Frees a string returned by the library; null is ignored.
+///
+/// # Safety
+///
+/// `text` must be null or a string returned by the library and not yet freed.
+#[no_mangle]
+pub unsafe extern "C" fn chem_free_string(text: *mut c_char) {
+    guard((), || {
+        if !text.is_null() {
+            drop(CString::from_raw(text));
+        }
+    })
+}
@@ -0,0 +1,135 @@
+//! # Gas Module
+//!
+//! This module applies the ideal gas law, PV = nRT, with pressures and temperatures that carry their
+//! unit, and converts amounts of species of an equation to gas volumes, for example the liters of
+//! oxygen at STP needed to burn a gram of methane.
+
+use crate::element::Equation;
+use crate::ChemParseError;
+use serde::Serialize;
+
+pub use crate::temperature::Temperature;
+
+/// Molar gas constant in J/(mol·K), which is Pa·m³/(mol·K).
+pub const GAS_CONSTANT: f64 = 8.314_462_618;
+
+/// Standard pressure of IUPAC STP, 100 kPa.
+pub const STANDARD_PRESSURE: Pressure = Pressure { pascals: 100_000.0 };
+
+/// Standard temperature of IUPAC STP, 0 °C.
+pub const STANDARD_TEMPERATURE: Temperature = Temperature::kelvins(273.15);
+
+/// Pascals per standard atmosphere.
+const PASCALS_PER_ATMOSPHERE: f64 = 101_325.0;
+
+/// Pascals per millimeter of mercury.
+const PASCALS_PER_MMHG: f64 = PASCALS_PER_ATMOSPHERE / 760.0;
+
+/// Gas constant in L·Pa/(mol·K), for volumes in liters.
+const GAS_CONSTANT_LITERS: f64 = GAS_CONSTANT * 1000.0;
+
+/// Pressure, stored in pascals and created from or read in any supported unit.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+pub struct Pressure {
+    pascals: f64,
+}
+
+impl Pressure {
+    /// Creates a pressure in pascals.
+    pub fn pascals(pascals: f64) -> Self {
+        Pressure { pascals }
+    }
+
+    /// Creates a pressure in kilopascals.
+    pub fn kilopascals(kilopascals: f64) -> Self {
+        Pressure::pascals(kilopascals * 1000.0)
+    }
+
+    /// Creates a pressure in bars.
+    pub fn bars(bars: f64) -> Self {
+        Pressure::pascals(bars * 100_000.0)
+    }
+
+    /// Creates a pressure in standard atmospheres.
+    pub fn atmospheres(atmospheres: f64) -> Self {
+        Pressure::pascals(atmospheres * PASCALS_PER_ATMOSPHERE)
+    }
+
+    /// Creates a pressure in millimeters of mercury, or torr.
+    pub fn mmhg(mmhg: f64) -> Self {
+        Pressure::pascals(mmhg * PASCALS_PER_MMHG)
+    }
+
+    /// Returns the pressure in pascals.
+    pub fn in_pascals(&self) -> f64 {
+        self.pascals
+    }
+
+    /// Returns the pressure in kilopascals.
+    pub fn in_kilopascals(&self) -> f64 {
+        self.pascals / 1000.0
+    }
+
+    /// Returns the pressure in standard atmospheres.
+    pub fn in_atmospheres(&self) -> f64 {
+        self.pascals / PASCALS_PER_ATMOSPHERE
+    }
+
+    /// Returns the pressure in millimeters of mercury.
+    pub fn in_mmhg(&self) -> f64 {
+        self.pascals / PASCALS_PER_MMHG
+    }
+}
+
+/// Returns the amount in moles of an ideal gas of a volume in liters.
+pub fn moles(pressure: Pressure, volume: f64, temperature: Temperature) -> f64 {
+    pressure.pascals * volume / (GAS_CONSTANT_LITERS * temperature.in_kelvins())
+}
+
+/// Returns the volume in liters of an amount in moles of an ideal gas.
+pub fn volume(moles: f64, pressure: Pressure, temperature: Temperature) -> f64 {
+    moles * GAS_CONSTANT_LITERS * temperature.in_kelvins() / pressure.pascals
+}
+
+/// Returns the pressure of an amount in moles of an ideal gas in a volume in liters.
+pub fn pressure(moles: f64, volume: f64, temperature: Temperature) -> Pressure {
+    Pressure::pascals(moles * GAS_CONSTANT_LITERS * temperature.in_kelvins() / volume)
+}
+
+/// Returns the temperature of an amount in moles of an ideal gas in a volume in liters.
+pub fn temperature(moles: f64, pressure: Pressure, volume: f64) -> Temperature {
+    Temperature::kelvins(pressure.pascals * volume / (GAS_CONSTANT_LITERS * moles))
+}
+
+impl Equation {
+    /// Returns the volume in liters of the gas `target` that reacts with or is produced from the
+    /// given mass in grams of another species, at a pressure and temperature, as in
+    /// `gas_volume_of("O2", ("CH4", 1.0), STANDARD_PRESSURE, STANDARD_TEMPERATURE)` for
+    /// `CH4 + 2O2 -> CO2 + 2H2O`.
+    ///
+    /// Fails like [`Equation::moles_of`], and for a given species without mass.
+    pub fn gas_volume_of(
+        &self,
+        target: &str,
+        given: (&str, f64),
+        pressure: Pressure,
+        temperature: Temperature,
+    ) -> Result<f64, ChemParseError> {
+        let (given, grams) = given;
+        let (_, given_formula) = self.species(given)?;
+        if given_formula.mass <= 0.0 {
+            return Err(ChemParseError::EvaluationError(
+                format!("gas volume of {}", target),
+                format!("{} has no mass", given_formula.formula),
+            ));
+        }
+        let moles = self.moles_of(target, (given, grams / given_formula.mass))?;
+        Ok(volume(moles, pressure, temperature))
+    }
+
+    /// Returns the volume in liters at IUPAC STP of the gas `target` that reacts with or is
+    /// produced from the given mass in grams of another species, like [`Equation::gas_volume_of`].
+    pub fn stp_volume_of(&self, target: &str, given: (&str, f64)) -> Result<f64, ChemParseError> {
+        self.gas_volume_of(target, given, STANDARD_PRESSURE, STANDARD_TEMPERATURE)
+    }
+}
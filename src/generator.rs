@@ -0,0 +1,413 @@
+//! # Generator Module
+//!
+//! This module generates practice problems for worksheets with the engine that checks their
+//! answers: formulas of salts to name, skeleton equations to balance and stoichiometry problems,
+//! in three tiers of difficulty. A generator started from the same seed gives the same problems,
+//! on every platform and with every version of `rand`, since it uses the ChaCha8 generator.
+
+use crate::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use crate::element::{gcd, round_significant, Equation, Term};
+use crate::{ChemParseError, ChemParser};
+use rand::seq::SliceRandom;
+use rand::{Rng, SeedableRng};
+use rand_chacha::ChaCha8Rng;
+use serde::Serialize;
+
+/// Monatomic cations of main-group metals with their charges.
+const MAIN_GROUP_CATIONS: &[(&str, u32)] = &[
+    ("Li", 1),
+    ("Na", 1),
+    ("K", 1),
+    ("Mg", 2),
+    ("Ca", 2),
+    ("Ba", 2),
+    ("Al", 3),
+];
+
+/// Cations of transition and post-transition metals with more than one common charge, which are
+/// named in Stock notation.
+const VARIABLE_CATIONS: &[(&str, u32)] = &[
+    ("Fe", 2),
+    ("Fe", 3),
+    ("Cu", 1),
+    ("Cu", 2),
+    ("Co", 2),
+    ("Cr", 3),
+    ("Pb", 2),
+    ("Sn", 4),
+];
+
+/// Monatomic anions of nonmetals with their charges and the molecules of their elements.
+const MONATOMIC_ANIONS: &[(&str, u32, &str)] = &[
+    ("F", 1, "F2"),
+    ("Cl", 1, "Cl2"),
+    ("Br", 1, "Br2"),
+    ("I", 1, "I2"),
+    ("O", 2, "O2"),
+    ("S", 2, "S"),
+    ("N", 3, "N2"),
+];
+
+/// Polyatomic anions with their charges and the acids they form, if any.
+const POLYATOMIC_ANIONS: &[(&str, u32, Option<&str>)] = &[
+    ("OH", 1, None),
+    ("NO3", 1, Some("HNO3")),
+    ("HCO3", 1, None),
+    ("SO4", 2, Some("H2SO4")),
+    ("CO3", 2, Some("H2CO3")),
+    ("PO4", 3, Some("H3PO4")),
+];
+
+/// Skeleton equations of redox reactions, which are hard to balance by inspection.
+const REDOX_EQUATIONS: &[&str] = &[
+    "KMnO4 + HCl -> KCl + MnCl2 + Cl2 + H2O",
+    "Cu + HNO3 -> Cu(NO3)2 + NO + H2O",
+    "K2Cr2O7 + HCl -> KCl + CrCl3 + Cl2 + H2O",
+    "FeSO4 + KMnO4 + H2SO4 -> Fe2(SO4)3 + MnSO4 + K2SO4 + H2O",
+    "NH3 + O2 -> NO + H2O",
+    "Zn + HNO3 -> Zn(NO3)2 + NH4NO3 + H2O",
+];
+
+/// Relative difficulty of generated problems.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Difficulty {
+    /// Binary salts of main-group metals and syntheses from the elements.
+    Easy,
+    /// Salts of polyatomic ions and combustions of hydrocarbons and alcohols.
+    Medium,
+    /// Salts of metals named in Stock notation, neutralizations and redox reactions.
+    Hard,
+}
+
+/// Practice problem with its answer.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum Exercise {
+    /// Name the compound of a formula.
+    NameFormula {
+        /// Formula to name, such as `Fe2(SO4)3`.
+        formula: String,
+        /// Systematic name of the formula, such as `iron(III) sulfate`.
+        name: String,
+    },
+    /// Balance a skeleton equation.
+    BalanceEquation {
+        /// Skeleton equation without coefficients.
+        equation: String,
+        /// Equation with the smallest balancing coefficients.
+        solution: String,
+    },
+    /// Find the mass of a species that reacts with or is produced from a mass of another one.
+    Stoichiometry {
+        /// Balanced equation of the reaction.
+        equation: String,
+        /// Species whose mass is given.
+        given: String,
+        /// Given mass in grams.
+        grams: f64,
+        /// Species whose mass is asked for.
+        target: String,
+        /// Mass of the target in grams, to six significant figures.
+        answer: f64,
+    },
+}
+
+impl Exercise {
+    /// Returns the problem as a sentence for a worksheet.
+    pub fn question(&self) -> String {
+        match self {
+            Exercise::NameFormula { formula, .. } => format!("Name the compound {}.", formula),
+            Exercise::BalanceEquation { equation, .. } => {
+                format!("Balance the equation {}.", equation)
+            }
+            Exercise::Stoichiometry {
+                equation,
+                given,
+                grams,
+                target,
+                ..
+            } => format!(
+                "How many grams of {} correspond to {} g of {} in {}?",
+                target, grams, given, equation
+            ),
+        }
+    }
+
+    /// Returns the answer as it is written on an answer key.
+    pub fn answer(&self) -> String {
+        match self {
+            Exercise::NameFormula { name, .. } => name.clone(),
+            Exercise::BalanceEquation { solution, .. } => solution.clone(),
+            Exercise::Stoichiometry { target, answer, .. } => {
+                format!("{:.2} g of {}", answer, target)
+            }
+        }
+    }
+
+    /// Checks a response to the problem with the parser: names are compared ignoring case and
+    /// extra whitespace, equations must have the formulas and the smallest coefficients of the
+    /// solution, in any order, and masses must be within 1% of the answer.
+    pub fn check(&self, parser: &ChemParser, response: &str) -> bool {
+        match self {
+            Exercise::NameFormula { name, .. } => {
+                let words = |text: &str| {
+                    text.to_lowercase()
+                        .split_whitespace()
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                };
+                words(response) == words(name)
+            }
+            Exercise::BalanceEquation { solution, .. } => {
+                match (
+                    parser.parse_equation(response),
+                    parser.parse_equation(solution),
+                ) {
+                    (Ok(response), Ok(solution)) => {
+                        response.check_equation()
+                            && response.normalize().equation == solution.normalize().equation
+                            && coefficient_sum(&response) == coefficient_sum(&solution)
+                    }
+                    _ => false,
+                }
+            }
+            Exercise::Stoichiometry { answer, .. } => {
+                let value = response.trim().trim_end_matches('g').trim();
+                value
+                    .parse::<f64>()
+                    .is_ok_and(|value| (value - answer).abs() <= answer.abs() * 0.01)
+            }
+        }
+    }
+}
+
+/// Generator of practice problems of one difficulty, seeded for repeatable worksheets.
+pub struct Generator {
+    parser: ChemParser,
+    difficulty: Difficulty,
+    rng: ChaCha8Rng,
+}
+
+impl Generator {
+    /// Creates a generator of problems of the given difficulty from a seed, which gives the same
+    /// problems each time.
+    pub fn new(seed: u64, difficulty: Difficulty) -> Self {
+        Generator {
+            parser: ChemParser::new(),
+            difficulty,
+            rng: ChaCha8Rng::seed_from_u64(seed),
+        }
+    }
+
+    /// Returns the difficulty of the problems.
+    pub fn difficulty(&self) -> Difficulty {
+        self.difficulty
+    }
+
+    /// Generates the formula of a salt to name: of a main-group metal and a nonmetal when easy,
+    /// with a polyatomic ion at medium difficulty, and of a metal named in Stock notation when
+    /// hard, such as `Fe2(SO4)3`.
+    pub fn name_formula(&mut self) -> Result<Exercise, ChemParseError> {
+        let (cation, anion) = match self.difficulty {
+            Difficulty::Easy => (
+                self.pick(MAIN_GROUP_CATIONS).copied(),
+                self.pick(MONATOMIC_ANIONS)
+                    .map(|&(anion, charge, _)| (anion, charge)),
+            ),
+            Difficulty::Medium => {
+                let cations = [MAIN_GROUP_CATIONS, &[("NH4", 1)]].concat();
+                (
+                    self.pick(&cations).copied(),
+                    self.pick(POLYATOMIC_ANIONS)
+                        .map(|&(anion, charge, _)| (anion, charge)),
+                )
+            }
+            Difficulty::Hard => {
+                let anions: Vec<(&str, u32)> = MONATOMIC_ANIONS
+                    .iter()
+                    .map(|&(anion, charge, _)| (anion, charge))
+                    .chain(
+                        POLYATOMIC_ANIONS
+                            .iter()
+                            .map(|&(anion, charge, _)| (anion, charge)),
+                    )
+                    .collect();
+                (
+                    self.pick(VARIABLE_CATIONS).copied(),
+                    self.pick(&anions).copied(),
+                )
+            }
+        };
+        let (cation, anion) = cation.zip(anion).expect("ion tables are not empty");
+        let formula = salt(cation, anion);
+        let name = self
+            .parser
+            .systematic_name(&self.parser.parse_formula(&formula)?)?;
+        Ok(Exercise::NameFormula { formula, name })
+    }
+
+    /// Generates a skeleton equation to balance with its solution: the synthesis of a salt from
+    /// its elements when easy, the combustion of an alkane or an alcohol at medium difficulty,
+    /// and a neutralization or a redox reaction when hard.
+    pub fn balance_equation(&mut self) -> Result<Exercise, ChemParseError> {
+        let equation = self.skeleton();
+        let solution = self.solve(&equation)?;
+        Ok(Exercise::BalanceEquation {
+            equation,
+            solution: solution.equation,
+        })
+    }
+
+    /// Generates a stoichiometry problem on a balanced equation of the difficulty: the mass of a
+    /// product formed from a whole number of grams of a reactant.
+    pub fn stoichiometry(&mut self) -> Result<Exercise, ChemParseError> {
+        let skeleton = self.skeleton();
+        let equation = self.solve(&skeleton)?;
+        let formulas = |terms: &[Term]| -> Vec<String> {
+            terms
+                .iter()
+                .map(|term| term.formula.formula.clone())
+                .collect()
+        };
+        let reactants = formulas(&equation.reactant_terms);
+        let products = formulas(&equation.product_terms);
+        let given = reactants.choose(&mut self.rng).cloned().unwrap_or_default();
+        let target = products.choose(&mut self.rng).cloned().unwrap_or_default();
+        let grams = f64::from(self.rng.gen_range(1..=20u32) * 5);
+        let answer = round_significant(equation.mass_of(&target, (&given, grams))?, 6);
+        Ok(Exercise::Stoichiometry {
+            equation: equation.equation,
+            given,
+            grams,
+            target,
+            answer,
+        })
+    }
+
+    /// Generates a worksheet of problems of every kind in turn.
+    pub fn worksheet(&mut self, count: usize) -> Result<Vec<Exercise>, ChemParseError> {
+        (0..count)
+            .map(|index| match index % 3 {
+                0 => self.name_formula(),
+                1 => self.balance_equation(),
+                _ => self.stoichiometry(),
+            })
+            .collect()
+    }
+
+    /// Writes a skeleton equation of the difficulty.
+    fn skeleton(&mut self) -> String {
+        match self.difficulty {
+            Difficulty::Easy => {
+                let (metal, charge) = *self.pick(MAIN_GROUP_CATIONS).expect("cations");
+                let (anion, anion_charge, element) = *self.pick(MONATOMIC_ANIONS).expect("anions");
+                format!(
+                    "{} + {} -> {}",
+                    metal,
+                    element,
+                    salt((metal, charge), (anion, anion_charge))
+                )
+            }
+            Difficulty::Medium => {
+                let carbons = self.rng.gen_range(1..=8);
+                let fuel = if self.rng.gen_bool(0.5) {
+                    format!("{}H{}", atoms("C", carbons), 2 * carbons + 2)
+                } else {
+                    format!("{}H{}OH", atoms("C", carbons), 2 * carbons + 1)
+                };
+                format!("{} + O2 -> CO2 + H2O", fuel)
+            }
+            Difficulty::Hard => {
+                if self.rng.gen_bool(0.5) {
+                    return self
+                        .pick(REDOX_EQUATIONS)
+                        .expect("redox equations")
+                        .to_string();
+                }
+                let (metal, charge) = *self.pick(MAIN_GROUP_CATIONS).expect("cations");
+                let acids: Vec<(&str, u32, &str)> = MONATOMIC_ANIONS
+                    .iter()
+                    .filter(|&&(anion, _, _)| ["Cl", "Br", "I"].contains(&anion))
+                    .map(|&(anion, charge, _)| (anion, charge, ""))
+                    .chain(
+                        POLYATOMIC_ANIONS
+                            .iter()
+                            .filter_map(|&(anion, charge, acid)| {
+                                acid.map(|acid| (anion, charge, acid))
+                            }),
+                    )
+                    .collect();
+                let (anion, anion_charge, acid) = *self.pick(&acids).expect("acids");
+                let acid = if acid.is_empty() {
+                    format!("H{}", anion)
+                } else {
+                    acid.to_string()
+                };
+                format!(
+                    "{} + {} -> {} + H2O",
+                    salt((metal, charge), ("OH", 1)),
+                    acid,
+                    salt((metal, charge), (anion, anion_charge))
+                )
+            }
+        }
+    }
+
+    /// Balances a skeleton equation with the parser.
+    fn solve(&self, skeleton: &str) -> Result<Equation, ChemParseError> {
+        let equation = self.parser.parse_equation(skeleton)?;
+        match equation.balance(DEFAULT_MAX_COEFFICIENT)? {
+            Balance::AlreadyBalanced => Ok(equation),
+            Balance::Balanced(balanced) => Ok(balanced),
+            _ => Err(ChemParseError::BalancingError(
+                skeleton.to_string(),
+                String::from("it has no single solution"),
+            )),
+        }
+    }
+
+    /// Picks a random entry of a table.
+    fn pick<'a, T>(&mut self, table: &'a [T]) -> Option<&'a T> {
+        table.choose(&mut self.rng)
+    }
+}
+
+/// Writes the formula of the neutral salt of a cation and an anion with their charges, putting
+/// polyatomic ions that occur more than once in parentheses, such as `Ca3(PO4)2`.
+fn salt(cation: (&str, u32), anion: (&str, u32)) -> String {
+    let (cation, cation_charge) = cation;
+    let (anion, anion_charge) = anion;
+    let divisor = gcd(cation_charge, anion_charge);
+    let ion = |ion: &str, count: u32| {
+        let polyatomic = ion.chars().filter(char::is_ascii_uppercase).count() > 1;
+        if polyatomic && count > 1 {
+            format!("({}){}", ion, count)
+        } else {
+            atoms(ion, count)
+        }
+    };
+    format!(
+        "{}{}",
+        ion(cation, anion_charge / divisor),
+        ion(anion, cation_charge / divisor)
+    )
+}
+
+/// Writes a symbol or group with a count, leaving out a count of 1.
+fn atoms(symbol: &str, count: u32) -> String {
+    match count {
+        1 => symbol.to_string(),
+        _ => format!("{}{}", symbol, count),
+    }
+}
+
+/// Adds up the coefficients of an equation.
+fn coefficient_sum(equation: &Equation) -> u64 {
+    equation
+        .reactant_terms
+        .iter()
+        .chain(&equation.product_terms)
+        .map(|term| u64::from(term.coefficient))
+        .sum()
+}
@@ -0,0 +1,221 @@
+//! # Hess Module
+//!
+//! This module applies Hess's law: it finds the multipliers that combine several balanced
+//! equations into a target net reaction, cancelling the intermediates, so that properties such as
+//! the reaction enthalpy of the target follow from those of known reactions. Equations can also
+//! be reversed and added up step by step, to write the net reaction itself.
+
+use crate::balance::{gcd, reduce};
+use crate::element::{composition_key, Equation, Term};
+use crate::ChemParseError;
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// Multiplier of an equation in a combination, a fraction in lowest terms whose sign tells whether
+/// the equation is used as written or reversed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Multiplier {
+    /// Numerator, negative for reversed equations.
+    pub numerator: i64,
+    /// Denominator, always positive.
+    pub denominator: i64,
+}
+
+impl Multiplier {
+    /// Creates a multiplier from a fraction, reduced to lowest terms with a positive denominator,
+    /// such as `-1/2` for `Multiplier::new(1, -2)`. Returns `None` if the denominator is zero.
+    pub fn new(numerator: i64, denominator: i64) -> Option<Self> {
+        if denominator == 0 {
+            return None;
+        }
+        let divisor = gcd(i128::from(numerator).abs(), i128::from(denominator).abs())
+            * i128::from(denominator.signum());
+        Some(Multiplier {
+            numerator: i64::try_from(i128::from(numerator) / divisor).ok()?,
+            denominator: i64::try_from(i128::from(denominator) / divisor).ok()?,
+        })
+    }
+
+    /// Returns the multiplier as a number.
+    pub fn value(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl Display for Multiplier {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        if self.denominator == 1 {
+            write!(f, "{}", self.numerator)
+        } else {
+            write!(f, "{}/{}", self.numerator, self.denominator)
+        }
+    }
+}
+
+/// Returns the multiplier of each equation, in their order, such that their sum gives the target
+/// reaction, for example `2` and `-1` to get `2C + O2 -> 2CO` from `C + O2 -> CO2` and
+/// `2CO + O2 -> 2CO2`. Species are matched by their atoms and charge.
+///
+/// When several combinations give the target, equations that are not needed get a zero multiplier.
+/// Fails if an equation is not balanced or no combination gives the target.
+pub fn combine(
+    equations: &[Equation],
+    target: &Equation,
+) -> Result<Vec<Multiplier>, ChemParseError> {
+    let error = |reason: &str| {
+        ChemParseError::EvaluationError(
+            format!("combination of reactions giving {}", target.equation),
+            String::from(reason),
+        )
+    };
+    for equation in equations.iter().chain([target]) {
+        equation.check_stoichiometric()?;
+    }
+
+    // Equations with fractional coefficients are brought to a common denominator, so that the
+    // multipliers apply to the equations as written.
+    let denominator = |equation: &Equation| i128::from(equation.denominator.max(1));
+    let common = equations
+        .iter()
+        .chain([target])
+        .fold(1, |common, equation| {
+            common / gcd(common, denominator(equation)) * denominator(equation)
+        });
+    let mut rows: BTreeMap<String, Vec<i128>> = BTreeMap::new();
+    for (column, equation) in equations.iter().chain([target]).enumerate() {
+        let scale = common / denominator(equation);
+        let sides = [(&equation.reactant_terms, -1), (&equation.product_terms, 1)];
+        for (terms, sign) in sides {
+            for term in terms {
+                let row = rows
+                    .entry(composition_key(&term.formula))
+                    .or_insert_with(|| vec![0; equations.len() + 1]);
+                row[column] += sign * term.coefficient as i128 * scale;
+            }
+        }
+    }
+
+    let mut matrix: Vec<Vec<i128>> = rows.into_values().collect();
+    let pivots = reduce(&mut matrix, equations.len());
+    if matrix[pivots.len()..]
+        .iter()
+        .any(|row| row[equations.len()] != 0)
+    {
+        return Err(error("no combination of the equations gives the target"));
+    }
+
+    let mut multipliers = vec![
+        Multiplier {
+            numerator: 0,
+            denominator: 1,
+        };
+        equations.len()
+    ];
+    for (row, &pivot) in matrix.iter().zip(&pivots) {
+        let (numerator, denominator) = (row[equations.len()], row[pivot]);
+        let divisor = gcd(numerator.abs(), denominator.abs()) * denominator.signum();
+        let overflow = || ChemParseError::Overflow(String::from("multiplier of a reaction"));
+        multipliers[pivot] = Multiplier {
+            numerator: i64::try_from(numerator / divisor).map_err(|_| overflow())?,
+            denominator: i64::try_from(denominator / divisor).map_err(|_| overflow())?,
+        };
+    }
+    Ok(multipliers)
+}
+
+impl Equation {
+    /// Returns the reverse reaction, with the reactants and products swapped, such as
+    /// `2H2O -> 2H2 + O2` for `2H2 + O2 -> 2H2O`. The arrow, the conditions and fractional
+    /// coefficients are kept.
+    pub fn reverse(&self) -> Equation {
+        self.with_terms(self.product_terms.clone(), self.reactant_terms.clone())
+    }
+
+    /// Adds another equation multiplied by a factor to this one, as when adding up the steps of
+    /// Hess's law, and cancels the species that appear on both sides. A negative factor adds the
+    /// reverse reaction, so `C + O2 -> CO2` combined with `2CO + O2 -> 2CO2` by `-1/2` gives
+    /// `C + 1/2O2 -> CO`. Species are matched by their atoms, charge and state, so `H2O(l)` does
+    /// not cancel `H2O(g)`, and electrons, photons and heat by their kind, so heat does not
+    /// cancel light. They keep the order in which they first appear.
+    ///
+    /// The result has the arrow of this equation, no conditions, and coefficients in lowest terms.
+    /// Fails if every species cancels or only one side is left, or with
+    /// [`ChemParseError::Overflow`] if a coefficient does not fit.
+    pub fn combine(
+        &self,
+        other: &Equation,
+        factor: Multiplier,
+    ) -> Result<Equation, ChemParseError> {
+        let overflow =
+            || ChemParseError::Overflow(String::from("coefficient of a combined reaction"));
+        if factor.denominator <= 0 {
+            return Err(ChemParseError::EvaluationError(
+                format!("combination of {} and {}", self.equation, other.equation),
+                format!("{} is not a valid multiplier", factor),
+            ));
+        }
+
+        // Both equations are brought to a common denominator, taking the factor into account,
+        // and each species gets a net coefficient, positive for products.
+        let own = i128::from(self.denominator.max(1));
+        let scaled = i128::from(other.denominator.max(1)) * i128::from(factor.denominator);
+        let denominator = own / gcd(own, scaled) * scaled;
+        let mut species: Vec<(String, Term, i128)> = Vec::new();
+        let parts = [
+            (self, denominator / own),
+            (other, denominator / scaled * i128::from(factor.numerator)),
+        ];
+        for (equation, scale) in parts {
+            let sides = [(&equation.reactant_terms, -1), (&equation.product_terms, 1)];
+            for (terms, sign) in sides {
+                for term in terms {
+                    // Electrons, photons and heat have no atoms, so they are told apart by
+                    // their kind rather than their composition.
+                    let composition = match term.formula.special_species() {
+                        Some(_) => term.formula.formula.clone(),
+                        None => composition_key(&term.formula),
+                    };
+                    let key = match term.state {
+                        Some(state) => format!("{}({})", composition, state.symbol()),
+                        None => composition,
+                    };
+                    let amount = sign * scale * i128::from(term.coefficient);
+                    match species.iter_mut().find(|(existing, _, _)| *existing == key) {
+                        Some((_, _, net)) => *net += amount,
+                        None => species.push((key, term.clone(), amount)),
+                    }
+                }
+            }
+        }
+
+        let divisor = species
+            .iter()
+            .fold(denominator, |divisor, (_, _, net)| gcd(divisor, net.abs()));
+        let mut reactant_terms = Vec::new();
+        let mut product_terms = Vec::new();
+        for (_, mut term, net) in species {
+            if net == 0 {
+                continue;
+            }
+            term.coefficient = u32::try_from(net.abs() / divisor).map_err(|_| overflow())?;
+            if net < 0 {
+                reactant_terms.push(term);
+            } else {
+                product_terms.push(term);
+            }
+        }
+        if reactant_terms.is_empty() || product_terms.is_empty() {
+            return Err(ChemParseError::EvaluationError(
+                format!("combination of {} and {}", self.equation, other.equation),
+                String::from("no reaction is left after cancelling the species on both sides"),
+            ));
+        }
+
+        let mut combined = Equation::from_terms(String::new(), reactant_terms, product_terms)
+            .with_arrow(self.arrow);
+        combined.denominator = u32::try_from(denominator / divisor).map_err(|_| overflow())?;
+        combined.equation = combined.to_parseable_string();
+        Ok(combined)
+    }
+}
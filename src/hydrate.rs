@@ -0,0 +1,95 @@
+//! # Hydrate Module
+//!
+//! This module works with the water of crystallization written in formulas such as `CuSO4·5H2O`:
+//! the percentage of water in a hydrate, and the number of waters estimated from the loss of
+//! mass of a sample on drying.
+
+use crate::element::Formula;
+use crate::{normalize_scripts, ChemParseError, ChemParser, Rule};
+use pest::Parser;
+
+/// Returns the number of waters of crystallization written in a formula, such as 5 for
+/// `CuSO4·5H2O`, or 0 for a formula without water adducts. Adducts are parsed with the parser
+/// and count as water when they have the composition of `H2O`, however they are written, so
+/// `HOH` is water while heavy water `D2O` is not.
+pub fn water_count(parser: &ChemParser, formula: &Formula) -> u32 {
+    let Ok(water) = parser.parse_formula("H2O") else {
+        return 0;
+    };
+    let written = normalize_scripts(&formula.formula);
+    let Some(pair) = ChemParser::parse(Rule::formula, &written)
+        .ok()
+        .and_then(|mut pairs| pairs.next())
+    else {
+        return 0;
+    };
+    pair.into_inner()
+        .filter(|part| part.as_rule() == Rule::adduct)
+        .map(|adduct| {
+            let mut count = 1;
+            let mut is_water = false;
+            for inner in adduct.into_inner() {
+                match inner.as_rule() {
+                    Rule::adduct_count => count = inner.as_str().parse().unwrap_or(0),
+                    Rule::adduct_formula => {
+                        is_water = parser
+                            .parse_formula(inner.as_str())
+                            .is_ok_and(|adduct| adduct.same_composition(&water))
+                    }
+                    _ => {}
+                }
+            }
+            if is_water {
+                count
+            } else {
+                0
+            }
+        })
+        .sum()
+}
+
+/// Returns the percentage by mass of water of crystallization in a hydrate, such as 36.1 for
+/// `CuSO4·5H2O`, with the mass of water from the periodic table of the parser, or `None` if the
+/// formula has no mass or the table has no hydrogen or oxygen.
+pub fn percent_water(parser: &ChemParser, formula: &Formula) -> Option<f64> {
+    let water = parser.parse_formula("H2O").ok()?.mass;
+    (formula.mass > 0.0).then(|| water_count(parser, formula) as f64 * water / formula.mass * 100.0)
+}
+
+/// Estimates the number of waters of crystallization of a hydrate of `anhydrous` from the mass in
+/// grams of a sample before and after drying it, for example 5 for `CuSO4` when 2.50 g of the
+/// hydrate leave 1.60 g. The mass of water comes from the periodic table of the parser.
+///
+/// Fails if water cannot be parsed with the table, or if the final mass is not positive or is
+/// larger than the initial mass.
+pub fn loss_on_drying(
+    parser: &ChemParser,
+    anhydrous: &Formula,
+    initial_mass: f64,
+    final_mass: f64,
+) -> Result<u32, ChemParseError> {
+    let error = |reason: &str| {
+        ChemParseError::EvaluationError(
+            format!("loss on drying of {}", anhydrous.formula),
+            String::from(reason),
+        )
+    };
+    if anhydrous.mass <= 0.0 {
+        return Err(error("the anhydrous formula has no mass"));
+    }
+    if final_mass.is_nan() || final_mass <= 0.0 || final_mass > initial_mass {
+        return Err(error(
+            "the final mass must be positive and at most the initial mass",
+        ));
+    }
+    let water_moles = (initial_mass - final_mass) / parser.parse_formula("H2O")?.mass;
+    let anhydrous_moles = final_mass / anhydrous.mass;
+    let count = (water_moles / anhydrous_moles).round();
+    if count > u32::MAX as f64 {
+        return Err(ChemParseError::Overflow(format!(
+            "waters of {}",
+            anhydrous.formula
+        )));
+    }
+    Ok(count as u32)
+}
@@ -0,0 +1,791 @@
+//! # Localization Module
+//!
+//! This module contains the message tables used to print CLI output in English or Ukrainian.
+
+use chemistry_parser::element::{element_symbol, Element, Equation, Formula, PeriodicTable, Term};
+use chemistry_parser::nuclear::NuclearEquation;
+use chemistry_parser::stats::{EquationStats, NotationCluster};
+use chemistry_parser::validation::Diagnosis;
+use std::env;
+
+/// Represents a language supported by the CLI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Lang {
+    /// English
+    En,
+    /// Ukrainian
+    Uk,
+}
+
+impl Lang {
+    /// Determines the language from a code such as `en`, `uk` or `uk_UA.UTF-8`.
+    pub fn from_code(code: &str) -> Option<Self> {
+        let code = code.to_lowercase();
+        if code.starts_with("uk") || code.starts_with("ua") {
+            Some(Lang::Uk)
+        } else if code.starts_with("en") || code == "c" || code == "posix" {
+            Some(Lang::En)
+        } else {
+            None
+        }
+    }
+
+    /// Returns the code of the language, such as `en`.
+    pub fn code(self) -> &'static str {
+        match self {
+            Lang::En => "en",
+            Lang::Uk => "uk",
+        }
+    }
+
+    /// Detects the language from the `LC_ALL`, `LC_MESSAGES` and `LANG` environment variables,
+    /// falling back to English.
+    pub fn detect() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|var| env::var(var).ok())
+            .find(|value| !value.is_empty())
+            .and_then(|value| Lang::from_code(&value))
+            .unwrap_or(Lang::En)
+    }
+}
+
+/// Represents a translatable CLI message.
+#[derive(Debug, Clone, Copy)]
+pub enum Msg {
+    HelpHeader,
+    HelpHelp,
+    HelpCredits,
+    HelpSymbol,
+    HelpFormula,
+    HelpEquation,
+    HelpCompare,
+    HelpCompareDatasets,
+    HelpCheck,
+    HelpBalance,
+    HelpBalanceMaxCoefficient,
+    HelpBalanceRedox,
+    HelpBalanceRedoxMedium,
+    HelpCheckNuclear,
+    HelpStoich,
+    HelpStoichGiven,
+    HelpStoichFind,
+    HelpAnalyze,
+    HelpSchema,
+    HelpCalc,
+    HelpCalcInteractive,
+    HelpCalcSession,
+    HelpRepl,
+    HelpFile,
+    HelpCheckpoint,
+    HelpErrorsTo,
+    HelpProfile,
+    HelpQuiet,
+    HelpFileReport,
+    HelpStats,
+    HelpNotations,
+    HelpNetwork,
+    HelpSample,
+    HelpSampleOptions,
+    HelpSampleFilter,
+    HelpAnnotateCsv,
+    HelpAnnotateCsvOptions,
+    HelpMassTable,
+    HelpMassTableOptions,
+    HelpRewrite,
+    HelpConvertFile,
+    HelpConvertFileOptions,
+    HelpTable,
+    HelpTableHighlight,
+    HelpTableSearch,
+    ElementsFound,
+    HelpLang,
+    HelpLog,
+    HelpCopy,
+    HelpDebugTree,
+    HelpLenient,
+    HelpFormat,
+    HelpDataset,
+    Credits,
+    Element,
+    Formula,
+    Equation,
+    Balanced,
+    NotBalanced,
+    SuggestedFix,
+    Coefficients,
+    OneSided,
+    IndependentReactions,
+    Oxidation,
+    Reduction,
+    Error,
+    TryAgain,
+    UnknownCommand,
+    UnknownLanguage,
+    UnknownDataset,
+    ReadFileFailed,
+    CheckpointFailed,
+    WriteFileFailed,
+    #[cfg(feature = "clipboard")]
+    CopyFailed,
+    #[cfg(not(feature = "clipboard"))]
+    ClipboardUnavailable,
+    UnknownFormat,
+    UnknownMedium,
+    StatsEquations,
+    StatsFailed,
+    StatsSpecies,
+    StatsAverageTerms,
+    StatsCoefficients,
+    StatsCommonReactants,
+    StatsCommonProducts,
+    StatsUnusedElements,
+    NoNotationClusters,
+    ErrorOnLine,
+    ErrorInEntry,
+    Warning,
+    UnknownProfile,
+    MissingRules,
+    MissingGiven,
+    LimitingReactant,
+    LeftOver,
+    SameComposition,
+    DifferentComposition,
+    MassDifference,
+    Charge,
+    LinesChecked,
+    LinesFailed,
+    AtomicNumber,
+    AtomicMass,
+    ElectronConfiguration,
+    Mass,
+    Elements,
+    Reactants,
+    Products,
+    MassNumbers,
+    AtomicNumbers,
+    Legend,
+    Result,
+    Name,
+}
+
+/// Returns the text of a message in the given language.
+pub fn tr(lang: Lang, msg: Msg) -> &'static str {
+    match lang {
+        Lang::En => match msg {
+            Msg::HelpHeader => "Use following commands:",
+            Msg::HelpHelp => "Show all commands",
+            Msg::HelpCredits => "Show credits",
+            Msg::HelpSymbol => "Parse the element and print information about it",
+            Msg::HelpFormula => "Parse the formula and print information about it",
+            Msg::HelpEquation => "Parse the chemical equation and print its formulas",
+            Msg::HelpCompare => "Compare the composition and mass of two formulas",
+            Msg::HelpCompareDatasets => {
+                "Compare the molar mass of the formula across bundled datasets"
+            }
+            Msg::HelpCheck => "Check if the chemical equation is balanced",
+            Msg::HelpBalance => {
+                "Balance the chemical equation with the smallest integer coefficients"
+            }
+            Msg::HelpBalanceMaxCoefficient => "Largest coefficient to search (default: 1000)",
+            Msg::HelpBalanceRedox => "Balance a redox equation by the half-reaction method",
+            Msg::HelpBalanceRedoxMedium => "Medium of the reaction (default: acidic)",
+            Msg::HelpCheckNuclear => {
+                "Check if mass and atomic numbers of the nuclear equation are balanced"
+            }
+            Msg::HelpStoich => "Balance the equation if needed and find its limiting reactant",
+            Msg::HelpStoichGiven => "Amounts of reactants, such as H2=4g,O2=1mol",
+            Msg::HelpStoichFind => "Species to find the amount of (default: every product)",
+            Msg::HelpAnalyze => {
+                "Detect an element, formula or equation and print everything known about it as JSON"
+            }
+            Msg::HelpSchema => {
+                "Print the JSON schemas of the outputs and the features of this build as JSON"
+            }
+            Msg::HelpCalc => "Evaluate an expression such as \"mass(H2SO4) * 2 + mass(H2O)\"",
+            Msg::HelpCalcInteractive => {
+                "Read calculations and assignments such as \"let aspirin = C9H8O4\" from stdin"
+            }
+            Msg::HelpCalcSession => "Load variables from the file and save assignments to it",
+            Msg::HelpRepl => "Describe elements, formulas and equations typed one per line",
+            Msg::HelpFile => "Parse the file with chemical equations, or stdin for - or no path, and solve them",
+            Msg::HelpCheckpoint => {
+                "Save progress to the file and resume from it after an interruption"
+            }
+            Msg::HelpErrorsTo => "Write failed lines with their errors to the file as JSON lines",
+            Msg::HelpProfile => {
+                "Validation profile: default, strict-teaching or data-ingest (default: default)"
+            }
+            Msg::HelpQuiet => "Print only the unbalanced lines with their numbers",
+            Msg::HelpFileReport => "Write a CSV report of every line to the file instead",
+            Msg::HelpStats => {
+                "Summarize the species, terms and coefficients of a file with equations"
+            }
+            Msg::HelpNotations => {
+                "Find formulas written in different ways and suggest one notation"
+            }
+            Msg::HelpNetwork => "Print the reaction network of a file as a Graphviz graph",
+            Msg::HelpSample => "Print a random sample of the equations of a file",
+            Msg::HelpSampleOptions => "Sample size (default: 20) and only unbalanced equations",
+            Msg::HelpSampleFilter => {
+                "Only equations with the element, and a seed to repeat a sample"
+            }
+            Msg::HelpAnnotateCsv => {
+                "Append molar mass, Hill formula and element counts to a CSV with formulas"
+            }
+            Msg::HelpAnnotateCsvOptions => {
+                "Formula column (default: formula) and output file (default: stdout)"
+            }
+            Msg::HelpMassTable => "Write the molar masses of the formulas of a file as a table",
+            Msg::HelpMassTableOptions => {
+                "Table format (default: csv) and output file (default: stdout)"
+            }
+            Msg::HelpRewrite => "Apply the rewrite rules of the file, such as \"OH -> OD\", to every formula or equation",
+            Msg::HelpConvertFile => "Convert an equation file between text, CSV, JSONL, CML and Markdown, detecting the input format",
+            Msg::HelpConvertFileOptions => "Output format, by default that of the extension of the output file",
+            Msg::HelpTable => "Print the periodic table",
+            Msg::HelpTableSearch => "List the elements of a group or matching a query such as mass>200",
+            Msg::ElementsFound => "Elements found",
+            Msg::HelpTableHighlight => {
+                "Print the periodic table with the elements of the formula highlighted"
+            }
+            Msg::HelpLang => "Select the output language (en, uk)",
+            Msg::HelpDataset => "Use the standard atomic weights of an IUPAC edition",
+            Msg::HelpLog => "Append every command, input and result to a JSONL audit log",
+            Msg::HelpCopy => "Copy the main result to the clipboard",
+            Msg::HelpDebugTree => "Show the parse tree of the input instead of the result",
+            Msg::HelpLenient => "Tolerate stray spaces, trailing text and arrows like →",
+            Msg::HelpFormat => "Output format of symbol, formula, equation and file (default: plain)",
+            Msg::Credits => "This program was created by Liliia Parashchak, @lillydaystar",
+            Msg::Element => "Element",
+            Msg::Formula => "Formula",
+            Msg::Equation => "Equation",
+            Msg::Balanced => "Equation is balanced.",
+            Msg::NotBalanced => "Equation is not balanced.",
+            Msg::SuggestedFix => "Suggested fix",
+            Msg::Coefficients => "Coefficients",
+            Msg::IndependentReactions => "The equation combines independent reactions:",
+            Msg::Oxidation => "Oxidation",
+            Msg::Reduction => "Reduction",
+            Msg::OneSided => {
+                "The equation cannot be balanced, check it for a typo. Found on one side only:"
+            }
+            Msg::Error => "Error",
+            Msg::TryAgain => "try again",
+            Msg::UnknownCommand => "Unknown command",
+            Msg::UnknownLanguage => "Unknown language",
+            Msg::UnknownDataset => "Unknown dataset",
+            Msg::ReadFileFailed => "Failed to read file",
+            Msg::CheckpointFailed => "Checkpoint is unreadable or belongs to another or a changed input",
+            Msg::WriteFileFailed => "Failed to write file",
+            #[cfg(feature = "clipboard")]
+            Msg::CopyFailed => "Failed to copy to the clipboard",
+            #[cfg(not(feature = "clipboard"))]
+            Msg::ClipboardUnavailable => {
+                "Clipboard support is not enabled, rebuild with the clipboard feature"
+            }
+            Msg::UnknownFormat => "Unknown format",
+            Msg::UnknownMedium => "Unknown medium",
+            Msg::StatsEquations => "Equations",
+            Msg::StatsFailed => "Failed lines",
+            Msg::StatsSpecies => "Distinct species",
+            Msg::StatsAverageTerms => "Average terms per side",
+            Msg::StatsCoefficients => "Coefficients",
+            Msg::StatsCommonReactants => "Most common reactants",
+            Msg::StatsCommonProducts => "Most common products",
+            Msg::StatsUnusedElements => "Elements never used",
+            Msg::NoNotationClusters => "Every composition is written in one way",
+            Msg::ErrorOnLine => "Error on line",
+            Msg::ErrorInEntry => "Error in entry",
+            Msg::Warning => "Warning",
+            Msg::UnknownProfile => "Unknown profile",
+            Msg::MissingRules => "No rules file given with --rules",
+            Msg::MissingGiven => "No amounts given with --given",
+            Msg::LimitingReactant => "Limiting reactant",
+            Msg::LeftOver => "Left over",
+            Msg::SameComposition => "Same composition",
+            Msg::DifferentComposition => "Different composition",
+            Msg::MassDifference => "Mass difference",
+            Msg::Charge => "charge",
+            Msg::LinesChecked => "Lines",
+            Msg::LinesFailed => "failed",
+            Msg::AtomicNumber => "Atomic number",
+            Msg::AtomicMass => "Atomic mass",
+            Msg::ElectronConfiguration => "Electron configuration",
+            Msg::Mass => "Mass",
+            Msg::Elements => "Elements",
+            Msg::Reactants => "Reactants",
+            Msg::Products => "Products",
+            Msg::MassNumbers => "Mass numbers",
+            Msg::AtomicNumbers => "Atomic numbers",
+            Msg::Legend => "Legend",
+            Msg::Result => "Result",
+            Msg::Name => "Name",
+        },
+        Lang::Uk => match msg {
+            Msg::HelpHeader => "Використовуйте такі команди:",
+            Msg::HelpHelp => "Показати всі команди",
+            Msg::HelpCredits => "Показати авторів",
+            Msg::HelpSymbol => "Розібрати елемент і вивести інформацію про нього",
+            Msg::HelpFormula => "Розібрати формулу і вивести інформацію про неї",
+            Msg::HelpEquation => "Розібрати хімічне рівняння і вивести його формули",
+            Msg::HelpCompare => "Порівняти склад і масу двох формул",
+            Msg::HelpCompareDatasets => "Порівняти молярну масу формули у вбудованих наборах даних",
+            Msg::HelpCheck => "Перевірити, чи зрівняне хімічне рівняння",
+            Msg::HelpBalance => "Зрівняти хімічне рівняння найменшими цілими коефіцієнтами",
+            Msg::HelpBalanceMaxCoefficient => "Найбільший коефіцієнт для пошуку (типово: 1000)",
+            Msg::HelpBalanceRedox => "Зрівняти окисно-відновне рівняння методом напівреакцій",
+            Msg::HelpBalanceRedoxMedium => "Середовище реакції (типово: acidic)",
+            Msg::HelpCheckNuclear => {
+                "Перевірити, чи зрівняні масові та атомні числа ядерного рівняння"
+            }
+            Msg::HelpStoich => "Зрівняти рівняння за потреби і знайти лімітувальний реагент",
+            Msg::HelpStoichGiven => "Кількості реагентів, наприклад H2=4g,O2=1mol",
+            Msg::HelpStoichFind => "Речовина, кількість якої знайти (типово: усі продукти)",
+            Msg::HelpAnalyze => {
+                "Розпізнати елемент, формулу чи рівняння і вивести все відоме про них у JSON"
+            }
+            Msg::HelpSchema => "Вивести JSON-схеми результатів і можливості цієї збірки у JSON",
+            Msg::HelpCalc => "Обчислити вираз на кшталт \"mass(H2SO4) * 2 + mass(H2O)\"",
+            Msg::HelpCalcInteractive => {
+                "Читати обчислення і присвоєння на кшталт \"let aspirin = C9H8O4\" зі stdin"
+            }
+            Msg::HelpCalcSession => "Завантажити змінні з файлу і зберігати в нього присвоєння",
+            Msg::HelpRepl => "Описувати елементи, формули та рівняння, введені по одному в рядку",
+            Msg::HelpFile => "Розібрати файл з хімічними рівняннями, або stdin для - чи без шляху, і перевірити їх",
+            Msg::HelpCheckpoint => {
+                "Зберігати поступ у файл і продовжувати з нього після переривання"
+            }
+            Msg::HelpErrorsTo => "Записувати рядки з помилками у файл як рядки JSON",
+            Msg::HelpProfile => {
+                "Профіль перевірки: default, strict-teaching або data-ingest (типово: default)"
+            }
+            Msg::HelpQuiet => "Виводити лише незрівняні рядки з їхніми номерами",
+            Msg::HelpFileReport => "Натомість записати CSV-звіт про кожен рядок у файл",
+            Msg::HelpStats => "Підсумувати речовини, члени і коефіцієнти файлу з рівняннями",
+            Msg::HelpNotations => "Знайти формули, записані по-різному, і запропонувати один запис",
+            Msg::HelpNetwork => "Вивести мережу реакцій файлу як граф Graphviz",
+            Msg::HelpSample => "Вивести випадкову вибірку рівнянь з файлу",
+            Msg::HelpSampleOptions => "Розмір вибірки (типово: 20) і лише незбалансовані рівняння",
+            Msg::HelpSampleFilter => "Лише рівняння з елементом і зерно для повторення вибірки",
+            Msg::HelpAnnotateCsv => {
+                "Додати молярну масу, формулу Гілла і кількість елементів до CSV з формулами"
+            }
+            Msg::HelpAnnotateCsvOptions => {
+                "Стовпець з формулами (типово: formula) і вихідний файл (типово: stdout)"
+            }
+            Msg::HelpMassTable => "Записати молярні маси формул з файлу у вигляді таблиці",
+            Msg::HelpMassTableOptions => {
+                "Формат таблиці (типово: csv) і вихідний файл (типово: stdout)"
+            }
+            Msg::HelpRewrite => "Застосувати правила заміни з файлу, як-от \"OH -> OD\", до кожної формули чи рівняння",
+            Msg::HelpConvertFile => "Перетворити файл рівнянь між форматами text, CSV, JSONL, CML і Markdown, визначивши формат вхідного файлу",
+            Msg::HelpConvertFileOptions => "Формат виводу, за замовчуванням за розширенням вихідного файлу",
+            Msg::HelpTable => "Вивести періодичну таблицю",
+            Msg::HelpTableSearch => "Вивести елементи групи або за запитом на кшталт mass>200",
+            Msg::ElementsFound => "Знайдено елементів",
+            Msg::HelpTableHighlight => "Вивести періодичну таблицю з виділеними елементами формули",
+            Msg::HelpLang => "Вибрати мову виводу (en, uk)",
+            Msg::HelpDataset => "Використати стандартні атомні маси видання IUPAC",
+            Msg::HelpLog => {
+                "Дописувати кожну команду, вхідні дані і результат до журналу аудиту JSONL"
+            }
+            Msg::HelpCopy => "Скопіювати основний результат до буфера обміну",
+            Msg::HelpDebugTree => "Показати дерево розбору введення замість результату",
+            Msg::HelpLenient => "Допускати зайві пробіли, текст у кінці та стрілки на кшталт →",
+            Msg::HelpFormat => "Формат виводу symbol, formula, equation і file (типово: plain)",
+            Msg::Credits => "Цю програму створила Лілія Паращак, @lillydaystar",
+            Msg::Element => "Елемент",
+            Msg::Formula => "Формула",
+            Msg::Equation => "Рівняння",
+            Msg::Balanced => "Рівняння зрівняне.",
+            Msg::NotBalanced => "Рівняння не зрівняне.",
+            Msg::SuggestedFix => "Можливе виправлення",
+            Msg::Coefficients => "Коефіцієнти",
+            Msg::IndependentReactions => "Рівняння поєднує незалежні реакції:",
+            Msg::Oxidation => "Окиснення",
+            Msg::Reduction => "Відновлення",
+            Msg::OneSided => {
+                "Рівняння неможливо зрівняти, перевірте його на помилки. Лише з одного боку:"
+            }
+            Msg::Error => "Помилка",
+            Msg::TryAgain => "спробуйте ще раз",
+            Msg::UnknownCommand => "Невідома команда",
+            Msg::UnknownLanguage => "Невідома мова",
+            Msg::UnknownDataset => "Невідомий набір даних",
+            Msg::ReadFileFailed => "Не вдалося прочитати файл",
+            Msg::CheckpointFailed => "Контрольна точка не читається або належить іншому чи зміненому файлу",
+            Msg::WriteFileFailed => "Не вдалося записати файл",
+            #[cfg(feature = "clipboard")]
+            Msg::CopyFailed => "Не вдалося скопіювати до буфера обміну",
+            #[cfg(not(feature = "clipboard"))]
+            Msg::ClipboardUnavailable => {
+                "Підтримку буфера обміну не ввімкнено, зберіть програму з функцією clipboard"
+            }
+            Msg::UnknownFormat => "Невідомий формат",
+            Msg::UnknownMedium => "Невідоме середовище",
+            Msg::StatsEquations => "Рівняння",
+            Msg::StatsFailed => "Рядки з помилками",
+            Msg::StatsSpecies => "Різні речовини",
+            Msg::StatsAverageTerms => "Середня кількість членів у частині",
+            Msg::StatsCoefficients => "Коефіцієнти",
+            Msg::StatsCommonReactants => "Найчастіші реагенти",
+            Msg::StatsCommonProducts => "Найчастіші продукти",
+            Msg::StatsUnusedElements => "Елементи, що не використовуються",
+            Msg::NoNotationClusters => "Кожен склад записано одним способом",
+            Msg::ErrorOnLine => "Помилка в рядку",
+            Msg::ErrorInEntry => "Помилка в записі",
+            Msg::Warning => "Попередження",
+            Msg::UnknownProfile => "Невідомий профіль",
+            Msg::MissingRules => "Не вказано файл правил у --rules",
+            Msg::MissingGiven => "Не вказано кількості у --given",
+            Msg::LimitingReactant => "Лімітувальний реагент",
+            Msg::LeftOver => "Залишок",
+            Msg::SameComposition => "Однаковий склад",
+            Msg::DifferentComposition => "Різний склад",
+            Msg::MassDifference => "Різниця мас",
+            Msg::Charge => "заряд",
+            Msg::LinesChecked => "Рядків",
+            Msg::LinesFailed => "з помилками",
+            Msg::AtomicNumber => "Атомний номер",
+            Msg::AtomicMass => "Атомна маса",
+            Msg::ElectronConfiguration => "Електронна конфігурація",
+            Msg::Mass => "Маса",
+            Msg::Elements => "Елементи",
+            Msg::Reactants => "Реагенти",
+            Msg::Products => "Продукти",
+            Msg::MassNumbers => "Масові числа",
+            Msg::AtomicNumbers => "Атомні числа",
+            Msg::Legend => "Легенда",
+            Msg::Result => "Результат",
+            Msg::Name => "Назва",
+        },
+    }
+}
+
+/// Ukrainian element names, indexed by atomic number minus one.
+const UK_ELEMENT_NAMES: [&str; 118] = [
+    "Гідроген",
+    "Гелій",
+    "Літій",
+    "Берилій",
+    "Бор",
+    "Карбон",
+    "Нітроген",
+    "Оксиген",
+    "Флуор",
+    "Неон",
+    "Натрій",
+    "Магній",
+    "Алюміній",
+    "Силіцій",
+    "Фосфор",
+    "Сульфур",
+    "Хлор",
+    "Аргон",
+    "Калій",
+    "Кальцій",
+    "Скандій",
+    "Титан",
+    "Ванадій",
+    "Хром",
+    "Манган",
+    "Ферум",
+    "Кобальт",
+    "Нікол",
+    "Купрум",
+    "Цинк",
+    "Галій",
+    "Германій",
+    "Арсен",
+    "Селен",
+    "Бром",
+    "Криптон",
+    "Рубідій",
+    "Стронцій",
+    "Ітрій",
+    "Цирконій",
+    "Ніобій",
+    "Молібден",
+    "Технецій",
+    "Рутеній",
+    "Родій",
+    "Паладій",
+    "Аргентум",
+    "Кадмій",
+    "Індій",
+    "Станум",
+    "Стибій",
+    "Телур",
+    "Йод",
+    "Ксенон",
+    "Цезій",
+    "Барій",
+    "Лантан",
+    "Церій",
+    "Празеодим",
+    "Неодим",
+    "Прометій",
+    "Самарій",
+    "Європій",
+    "Гадоліній",
+    "Тербій",
+    "Диспрозій",
+    "Гольмій",
+    "Ербій",
+    "Тулій",
+    "Ітербій",
+    "Лютецій",
+    "Гафній",
+    "Тантал",
+    "Вольфрам",
+    "Реній",
+    "Осмій",
+    "Іридій",
+    "Платина",
+    "Аурум",
+    "Меркурій",
+    "Талій",
+    "Плюмбум",
+    "Бісмут",
+    "Полоній",
+    "Астат",
+    "Радон",
+    "Францій",
+    "Радій",
+    "Актиній",
+    "Торій",
+    "Протактиній",
+    "Уран",
+    "Нептуній",
+    "Плутоній",
+    "Америцій",
+    "Кюрій",
+    "Берклій",
+    "Каліфорній",
+    "Ейнштейній",
+    "Фермій",
+    "Менделєвій",
+    "Нобелій",
+    "Лоуренсій",
+    "Резерфордій",
+    "Дубній",
+    "Сиборгій",
+    "Борій",
+    "Гасій",
+    "Мейтнерій",
+    "Дармштадтій",
+    "Рентгеній",
+    "Коперницій",
+    "Ніхоній",
+    "Флеровій",
+    "Московій",
+    "Ліверморій",
+    "Теннессин",
+    "Оганесон",
+];
+
+/// Returns the name of the element in the given language.
+pub fn element_name(lang: Lang, element: &Element) -> &str {
+    match lang {
+        Lang::En => &element.name,
+        Lang::Uk => UK_ELEMENT_NAMES
+            .get((element.atomic_number as usize).wrapping_sub(1))
+            .copied()
+            .unwrap_or(&element.name),
+    }
+}
+
+/// Renders an element with localized headings.
+pub fn render_element(lang: Lang, element: &Element) -> String {
+    format!(
+        "{} ({})\n{}: {}\n{}: {}\n{}: {}",
+        element.symbol,
+        element_name(lang, element),
+        tr(lang, Msg::AtomicNumber),
+        element.atomic_number,
+        tr(lang, Msg::AtomicMass),
+        element.atomic_mass,
+        tr(lang, Msg::ElectronConfiguration),
+        element.electron_configuration().shorthand()
+    )
+}
+
+/// Renders a formula with localized headings.
+pub fn render_formula(lang: Lang, formula: &Formula) -> String {
+    format!(
+        "{} \n{}: {}\n{}: {:?}",
+        formula.formula,
+        tr(lang, Msg::Mass),
+        formula.mass,
+        tr(lang, Msg::Elements),
+        formula.elements
+    )
+}
+
+/// Renders an equation with localized headings.
+pub fn render_equation(lang: Lang, equation: &Equation) -> String {
+    let side = |terms: &[Term]| {
+        terms
+            .iter()
+            .map(Term::to_string)
+            .collect::<Vec<String>>()
+            .join(" + ")
+    };
+    format!(
+        "{} \n{}: {}\n{}: {}",
+        equation.equation,
+        tr(lang, Msg::Reactants),
+        side(&equation.reactant_terms),
+        tr(lang, Msg::Products),
+        side(&equation.product_terms)
+    )
+}
+
+/// Renders the imbalances of a diagnosed equation, one per line as `H: 2 -> 4 (+2)`, followed by
+/// the suggested fix with the coefficients it changes.
+pub fn render_diagnosis(lang: Lang, diagnosis: &Diagnosis) -> String {
+    let mut lines: Vec<String> = diagnosis
+        .imbalances()
+        .map(|element| {
+            format!(
+                "  {}: {} -> {} ({:+})",
+                element.element, element.reactants, element.products, element.delta
+            )
+        })
+        .collect();
+    if let Some(suggestion) = &diagnosis.suggestion {
+        let changes: Vec<String> = suggestion
+            .changes
+            .iter()
+            .map(|change| {
+                format!(
+                    "{} {} -> {}",
+                    change.formula, change.written, change.suggested
+                )
+            })
+            .collect();
+        lines.push(format!(
+            "{}: {} ({})",
+            tr(lang, Msg::SuggestedFix),
+            suggestion.equation,
+            changes.join(", ")
+        ));
+    }
+    lines.join("\n")
+}
+
+/// Renders a nuclear equation with localized headings.
+pub fn render_nuclear_equation(lang: Lang, equation: &NuclearEquation) -> String {
+    let (reactant_mass, product_mass) = equation.mass_numbers();
+    let (reactant_charge, product_charge) = equation.atomic_numbers();
+    format!(
+        "{} \n{}: {} -> {}\n{}: {} -> {}",
+        equation.equation,
+        tr(lang, Msg::MassNumbers),
+        reactant_mass,
+        product_mass,
+        tr(lang, Msg::AtomicNumbers),
+        reactant_charge,
+        product_charge
+    )
+}
+
+/// Renders the legend of a highlighted formula: the count of each element and its contribution to the mass.
+pub fn render_highlight_legend(lang: Lang, table: &PeriodicTable, formula: &Formula) -> String {
+    let mut keys: Vec<&String> = formula.elements.keys().collect();
+    keys.sort();
+
+    let mut lines = vec![format!("{} ({}):", tr(lang, Msg::Legend), formula.formula)];
+    for key in keys {
+        let count = formula.elements[key];
+        let contribution = table.get_mass(key).unwrap_or(0.0) * count as f64;
+        let name = table
+            .get_element(element_symbol(key))
+            .map_or(key.as_str(), |element| element_name(lang, element));
+        lines.push(format!(
+            "  {:<6} {:<14} x{:<4} {:>10.3} ({:.2}%)",
+            format!("[{}]", key),
+            name,
+            count,
+            contribution,
+            contribution / formula.mass * 100.0
+        ));
+    }
+    lines.push(format!("  {}: {:.3}", tr(lang, Msg::Mass), formula.mass));
+    lines.join("\n")
+}
+
+/// Renders the statistics of an equation file with localized headings.
+pub fn render_stats(lang: Lang, stats: &EquationStats, table: &PeriodicTable) -> String {
+    let counts = |entries: Vec<(&str, usize)>| {
+        entries
+            .iter()
+            .map(|(formula, count)| format!("{} ({})", formula, count))
+            .collect::<Vec<String>>()
+            .join(", ")
+    };
+    let coefficients = stats
+        .coefficients
+        .iter()
+        .map(|(coefficient, count)| format!("{}: {}", coefficient, count))
+        .collect::<Vec<String>>()
+        .join(", ");
+    [
+        format!("{}: {}", tr(lang, Msg::StatsEquations), stats.equations),
+        format!("{}: {}", tr(lang, Msg::StatsFailed), stats.failed),
+        format!("{}: {}", tr(lang, Msg::StatsSpecies), stats.species()),
+        format!(
+            "{}: {} {:.2}, {} {:.2}",
+            tr(lang, Msg::StatsAverageTerms),
+            tr(lang, Msg::Reactants).to_lowercase(),
+            stats.average_reactant_terms(),
+            tr(lang, Msg::Products).to_lowercase(),
+            stats.average_product_terms()
+        ),
+        format!("{}: {}", tr(lang, Msg::StatsCoefficients), coefficients),
+        format!(
+            "{}: {}",
+            tr(lang, Msg::StatsCommonReactants),
+            counts(stats.most_common_reactants(STATS_TOP))
+        ),
+        format!(
+            "{}: {}",
+            tr(lang, Msg::StatsCommonProducts),
+            counts(stats.most_common_products(STATS_TOP))
+        ),
+        format!(
+            "{}: {}",
+            tr(lang, Msg::StatsUnusedElements),
+            stats.unused_elements(table).join(", ")
+        ),
+    ]
+    .join("\n")
+}
+
+/// Renders the compositions written in different ways, each with its notations and the suggested one,
+/// for example `H2O: H2O (2), OH2 (1) -> H2O`.
+pub fn render_notation_clusters(lang: Lang, clusters: &[NotationCluster]) -> String {
+    if clusters.is_empty() {
+        return tr(lang, Msg::NoNotationClusters).to_string();
+    }
+    clusters
+        .iter()
+        .map(|cluster| {
+            let notations = cluster
+                .notations
+                .iter()
+                .map(|(notation, count)| format!("{} ({})", notation, count))
+                .collect::<Vec<String>>()
+                .join(", ");
+            format!(
+                "{}: {} -> {}",
+                cluster.canonical, notations, cluster.suggestion
+            )
+        })
+        .collect::<Vec<String>>()
+        .join("\n")
+}
+
+/// Number of most common reactants and products listed by the `stats` command.
+const STATS_TOP: usize = 5;
@@ -0,0 +1,115 @@
+//! # InChI Module
+//!
+//! This module converts between formulas and the formula layer of InChI identifiers, such as
+//! `C6H12O6` in `InChI=1S/C6H12O6/c7-1-2-3(9)4(10)5(11)6(12)13-2/...`, so that formulas can be
+//! matched against chemical databases.
+
+use crate::element::{element_symbol, Formula};
+use crate::{ChemParseError, ChemParser};
+use std::collections::BTreeMap;
+
+impl Formula {
+    /// Returns the formula layer of the InChI of the formula: the formula in Hill notation with
+    /// isotopes counted as their element and without the charge, which InChI keeps in separate
+    /// layers, such as `C2H4O2` for `CH3COOH` and `O4S` for `SO4^2-`.
+    pub fn inchi_formula_layer(&self) -> String {
+        let mut elements: BTreeMap<String, u32> = BTreeMap::new();
+        for (key, count) in &self.elements {
+            *elements.entry(element_symbol(key).to_string()).or_insert(0) += count;
+        }
+        Formula::from_composition(elements, 0.0, 0).formula
+    }
+}
+
+impl ChemParser {
+    /// Parses the formula of an InChI identifier, such as `InChI=1S/C6H12O6/...`. The
+    /// components of the formula layer, separated by `.` and optionally preceded by a count as
+    /// in `2ClH.Ca`, are added up, and the charge (`/q`) and proton (`/p`) layers are applied, so
+    /// `InChI=1S/H3N/h1H3/p+1` is `H4N^+`. The other layers are ignored, and the formula is
+    /// written in Hill notation.
+    ///
+    /// Fails with [`ChemParseError::ParsingError`] if the input is not an InChI with a formula
+    /// layer, or with the error of [`ChemParser::parse_formula`] for a component.
+    pub fn parse_inchi(&self, inchi: &str) -> Result<Formula, ChemParseError> {
+        let error = |reason: &str| {
+            ChemParseError::ParsingError(format!("InChI \"{}\"", inchi), String::from(reason))
+        };
+        let mut layers = inchi
+            .trim()
+            .strip_prefix("InChI=")
+            .ok_or_else(|| error("missing InChI= prefix"))?
+            .split('/');
+        if !layers
+            .next()
+            .is_some_and(|version| version.starts_with('1'))
+        {
+            return Err(error("unsupported InChI version"));
+        }
+        let formula_layer = layers
+            .next()
+            .filter(|layer| !layer.is_empty())
+            .ok_or_else(|| error("missing formula layer"))?;
+
+        let mut elements: BTreeMap<String, u32> = BTreeMap::new();
+        for component in formula_layer.split('.') {
+            let digits = component.len()
+                - component
+                    .trim_start_matches(|c: char| c.is_ascii_digit())
+                    .len();
+            let count = match digits {
+                0 => 1,
+                _ => component[..digits]
+                    .parse::<u32>()
+                    .map_err(|_| error("invalid component count"))?,
+            };
+            let formula = self.parse_formula(&component[digits..])?;
+            for (key, atoms) in &formula.elements {
+                let total = elements.entry(key.clone()).or_insert(0);
+                *total = atoms
+                    .checked_mul(count)
+                    .and_then(|atoms| total.checked_add(atoms))
+                    .ok_or_else(|| ChemParseError::Overflow(format!("count of {}", key)))?;
+            }
+        }
+
+        let mut charge: i32 = 0;
+        for layer in layers {
+            if let Some(charges) = layer.strip_prefix('q') {
+                for component in charges.split(';').filter(|part| !part.is_empty()) {
+                    let (count, value) = match component.split_once('*') {
+                        Some((count, value)) => (count.parse::<i32>(), value),
+                        None => (Ok(1), component),
+                    };
+                    let value = count
+                        .ok()
+                        .zip(value.parse::<i32>().ok())
+                        .and_then(|(count, value)| count.checked_mul(value))
+                        .ok_or_else(|| error("invalid charge layer"))?;
+                    charge = charge
+                        .checked_add(value)
+                        .ok_or_else(|| ChemParseError::Overflow(String::from("charge")))?;
+                }
+            } else if let Some(protons) = layer.strip_prefix('p') {
+                let protons = protons
+                    .parse::<i32>()
+                    .map_err(|_| error("invalid proton layer"))?;
+                let hydrogens = elements.entry(String::from("H")).or_insert(0);
+                *hydrogens = hydrogens.checked_add_signed(protons).ok_or_else(|| {
+                    error("the proton layer removes more hydrogens than there are")
+                })?;
+                if *hydrogens == 0 {
+                    elements.remove("H");
+                }
+                charge = charge
+                    .checked_add(protons)
+                    .ok_or_else(|| ChemParseError::Overflow(String::from("charge")))?;
+            }
+        }
+
+        let mass = elements
+            .iter()
+            .filter_map(|(key, count)| Some(self.get_table().get_mass(key)? * f64::from(*count)))
+            .sum();
+        Ok(Formula::from_composition(elements, mass, charge))
+    }
+}
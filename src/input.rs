@@ -0,0 +1,489 @@
+//! # Input Module
+//!
+//! This module reads batch inputs, such as files of equations, one line at a time so that
+//! files larger than memory can be processed. With the `mmap` feature a file can also be
+//! memory-mapped, which avoids copying it through a read buffer. Long jobs can save a
+//! [`Checkpoint`] and resume from it after an interruption. Applications that want every
+//! equation of an input at once can use [`ChemParser::parse_equations_file`].
+
+use crate::element::Equation;
+use crate::{ChemParseError, ChemParser};
+use serde::{Deserialize, Serialize};
+use std::collections::BTreeMap;
+use std::fmt::{self, Display};
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Seek, SeekFrom};
+use std::path::Path;
+use std::time::UNIX_EPOCH;
+
+/// Line of a batch input.
+#[derive(Debug, Clone, PartialEq)]
+pub struct InputLine {
+    /// Number of the line, starting from 1.
+    pub number: usize,
+    /// Byte offset right after the line, which is the progress made through the input.
+    pub end: u64,
+    /// Text of the line without the line break.
+    pub text: String,
+}
+
+/// Equation parsed from a line of a batch input, or the error of the line.
+#[derive(Debug)]
+pub struct ParsedLine {
+    /// Number of the line, starting from 1.
+    pub number: usize,
+    /// Text of the line without the line break.
+    pub text: String,
+    /// Parsed equation, or the error of the line.
+    pub result: Result<Equation, ChemParseError>,
+}
+
+/// Error of a line of a batch input, as collected by [`ChemParser::parse_all`].
+#[derive(Debug)]
+pub struct LineError {
+    /// Number of the line, starting from 1.
+    pub line: usize,
+    /// Column of the error in characters, starting from 1: the failing position of a syntax error
+    /// or the first character of an invalid symbol, and 1 when the error has no position.
+    pub column: usize,
+    /// Text of the line without the line break.
+    pub text: String,
+    /// Error of the line.
+    pub error: ChemParseError,
+}
+
+impl Display for LineError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(
+            f,
+            "line {}, column {}: [{}] {}",
+            self.line,
+            self.column,
+            self.error.code(),
+            self.error
+        )
+    }
+}
+
+/// Equations and errors of a whole batch input, as returned by [`ChemParser::parse_all`].
+#[derive(Debug, Default)]
+pub struct BatchReport {
+    /// Equations that parsed, with the numbers of their lines.
+    pub equations: Vec<(usize, Equation)>,
+    /// Errors of the lines that did not parse, in the order of the lines.
+    pub errors: Vec<LineError>,
+}
+
+impl BatchReport {
+    /// Collects the results of parsed lines into a report.
+    pub fn from_lines(lines: Vec<ParsedLine>) -> Self {
+        let mut report = BatchReport::default();
+        for line in lines {
+            match line.result {
+                Ok(equation) => report.equations.push((line.number, equation)),
+                Err(error) => report.errors.push(LineError {
+                    line: line.number,
+                    column: error_column(&line.text, &error),
+                    text: line.text,
+                    error,
+                }),
+            }
+        }
+        report
+    }
+
+    /// Returns the number of lines with an equation, parsed or not.
+    pub fn total(&self) -> usize {
+        self.equations.len() + self.errors.len()
+    }
+
+    /// Returns `true` if every line parsed.
+    pub fn is_success(&self) -> bool {
+        self.errors.is_empty()
+    }
+
+    /// Counts the errors by their [code](ChemParseError::code), such as 2 for `E0002`.
+    pub fn error_counts(&self) -> BTreeMap<&'static str, usize> {
+        let mut counts = BTreeMap::new();
+        for error in &self.errors {
+            *counts.entry(error.error.code()).or_insert(0) += 1;
+        }
+        counts
+    }
+
+    /// Summarizes the report in one line, such as `3 lines, 2 parsed, 1 failed (E0002: 1)`.
+    pub fn summary(&self) -> String {
+        let mut summary = format!(
+            "{} lines, {} parsed, {} failed",
+            self.total(),
+            self.equations.len(),
+            self.errors.len()
+        );
+        if !self.errors.is_empty() {
+            let counts: Vec<String> = self
+                .error_counts()
+                .into_iter()
+                .map(|(code, count)| format!("{}: {}", code, count))
+                .collect();
+            summary.push_str(&format!(" ({})", counts.join(", ")));
+        }
+        summary
+    }
+}
+
+impl Display for BatchReport {
+    /// Writes the summary followed by one line per error.
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.summary())?;
+        for error in &self.errors {
+            write!(f, "\n{}", error)?;
+        }
+        Ok(())
+    }
+}
+
+/// Returns the column of an error in the text of its line, starting from 1.
+fn error_column(text: &str, error: &ChemParseError) -> usize {
+    let offset = match error {
+        ChemParseError::SyntaxError(_, diagnostic) => return diagnostic.column,
+        ChemParseError::InvalidFormula(formula, symbol, _) => text
+            .find(formula.as_str())
+            .and_then(|start| Some(start + formula.find(symbol.as_str())?)),
+        ChemParseError::InvalidElement(symbol, _) | ChemParseError::InvalidIsotope(symbol) => {
+            text.find(symbol.as_str())
+        }
+        _ => None,
+    };
+    offset.map_or(1, |offset| text[..offset].chars().count() + 1)
+}
+
+impl ChemParser {
+    /// Parses equations written one per line and collects the equations and the errors of all
+    /// lines into a [`BatchReport`], instead of stopping at the first line that does not parse.
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn parse_all(&self, input: &str) -> BatchReport {
+        BatchReport::from_lines(self.parse_equations_str(input))
+    }
+
+    /// Parses the equations of a file, one per line, returning the result of each line with
+    /// its number. Blank lines and lines starting with `#` are skipped. With the `mmap` feature
+    /// the file is memory-mapped.
+    ///
+    /// Fails with [`ChemParseError::DataError`] if the file cannot be read; equations that do not
+    /// parse are reported in their [`ParsedLine`].
+    pub fn parse_equations_file<P: AsRef<Path>>(
+        &self,
+        path: P,
+    ) -> Result<Vec<ParsedLine>, ChemParseError> {
+        let data_error =
+            |e: io::Error| ChemParseError::DataError(path.as_ref().display().to_string(), e.into());
+        let mut parsed = Vec::new();
+        for line in LineReader::open_file(&path)
+            .map_err(data_error)?
+            .equation_lines()
+        {
+            let line = line.map_err(data_error)?;
+            parsed.push(ParsedLine {
+                number: line.number,
+                result: self.parse_equation(&line.text),
+                text: line.text,
+            });
+        }
+        Ok(parsed)
+    }
+
+    /// Parses equations written one per line, returning the result of each line with its number.
+    /// Blank lines and lines starting with `#` are skipped.
+    pub fn parse_equations_str(&self, input: &str) -> Vec<ParsedLine> {
+        input
+            .lines()
+            .enumerate()
+            .filter_map(|(index, text)| self.parse_line(index + 1, text.to_string()))
+            .collect()
+    }
+
+    /// Parses equations written one per line like [`ChemParser::parse_equations_str`], on all
+    /// cores, for inputs of many thousands of lines. The lines are returned in their order.
+    #[cfg(feature = "rayon")]
+    pub fn parse_equations_parallel(&self, input: &str) -> Vec<ParsedLine> {
+        use rayon::prelude::*;
+
+        let lines: Vec<(usize, &str)> = input.lines().enumerate().collect();
+        lines
+            .into_par_iter()
+            .filter_map(|(index, text)| self.parse_line(index + 1, text.to_string()))
+            .collect()
+    }
+
+    fn parse_line(&self, number: usize, text: String) -> Option<ParsedLine> {
+        if is_skipped(&text) {
+            return None;
+        }
+        Some(ParsedLine {
+            number,
+            result: self.parse_equation(&text),
+            text,
+        })
+    }
+}
+
+/// Checks if a line of a batch input is blank or a `#` comment, which hold no equation.
+fn is_skipped(text: &str) -> bool {
+    let trimmed = text.trim();
+    trimmed.is_empty() || trimmed.starts_with('#')
+}
+
+/// Iterator over the lines of a file, read through a buffer or memory-mapped, or of stdin.
+pub struct LineReader {
+    source: Source,
+    len: u64,
+    number: usize,
+    position: u64,
+}
+
+enum Source {
+    Buffered(BufReader<File>),
+    Stdin(io::StdinLock<'static>),
+    #[cfg(feature = "mmap")]
+    Mapped(memmap2::Mmap),
+}
+
+impl LineReader {
+    /// Opens a file to be read line by line through a buffer.
+    pub fn open<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        Ok(LineReader::new(Source::Buffered(BufReader::new(file)), len))
+    }
+
+    /// Opens a file to be read line by line from a memory map, falling back to a buffer for inputs
+    /// that cannot be mapped, such as pipes.
+    ///
+    /// The file must not be truncated while it is read, as the map would then point past its end.
+    #[cfg(feature = "mmap")]
+    pub fn open_mapped<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        let file = File::open(path)?;
+        let len = file.metadata()?.len();
+        // SAFETY: the map is only read, and the caller keeps the file from being truncated while
+        // it is in use, as documented above.
+        match unsafe { memmap2::Mmap::map(&file) } {
+            Ok(map) => Ok(LineReader::new(Source::Mapped(map), len)),
+            Err(_) => Ok(LineReader::new(Source::Buffered(BufReader::new(file)), len)),
+        }
+    }
+
+    /// Opens a file to be read line by line, memory-mapped with the `mmap` feature and through a
+    /// buffer otherwise, as [`ChemParser::parse_equations_file`] reads it.
+    pub fn open_file<P: AsRef<Path>>(path: P) -> io::Result<Self> {
+        #[cfg(feature = "mmap")]
+        return LineReader::open_mapped(path);
+        #[cfg(not(feature = "mmap"))]
+        return LineReader::open(path);
+    }
+
+    /// Reads stdin line by line, such as the output of another command in a pipeline. Its size is
+    /// not known in advance, so [`LineReader::len`] is zero.
+    pub fn stdin() -> Self {
+        LineReader::new(Source::Stdin(io::stdin().lock()), 0)
+    }
+
+    fn new(source: Source, len: u64) -> Self {
+        LineReader {
+            source,
+            len,
+            number: 0,
+            position: 0,
+        }
+    }
+
+    /// Returns the size of the input in bytes, as it was when the file was opened, or zero for
+    /// stdin.
+    pub fn len(&self) -> u64 {
+        self.len
+    }
+
+    /// Returns `true` if the input was empty when the file was opened.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns the lines that hold equations, leaving out blank lines and lines starting with
+    /// `#`, so that large inputs can be processed one equation at a time like
+    /// [`ChemParser::parse_equations_file`] does.
+    pub fn equation_lines(self) -> EquationLines {
+        EquationLines { reader: self }
+    }
+
+    /// Continues reading after the last line recorded in a checkpoint, so that the lines before it
+    /// are not read again. Lines of stdin cannot be skipped without reading them, so they are read
+    /// and discarded.
+    pub fn resume(&mut self, checkpoint: &Checkpoint) -> io::Result<()> {
+        match &mut self.source {
+            Source::Buffered(reader) => {
+                reader.seek(SeekFrom::Start(checkpoint.offset))?;
+            }
+            Source::Stdin(reader) => {
+                let mut line = String::new();
+                for _ in 0..checkpoint.line {
+                    line.clear();
+                    if reader.read_line(&mut line)? == 0 {
+                        break;
+                    }
+                }
+            }
+            #[cfg(feature = "mmap")]
+            Source::Mapped(_) => {}
+        }
+        self.number = checkpoint.line;
+        self.position = checkpoint.offset;
+        Ok(())
+    }
+
+    /// Reads the next line with its line break, or `None` at the end of the input.
+    fn next_raw(&mut self) -> io::Result<Option<String>> {
+        match &mut self.source {
+            Source::Buffered(reader) => read_line(reader),
+            Source::Stdin(reader) => read_line(reader),
+            #[cfg(feature = "mmap")]
+            Source::Mapped(map) => {
+                let rest = map.get(self.position as usize..).unwrap_or_default();
+                if rest.is_empty() {
+                    return Ok(None);
+                }
+                let end = rest
+                    .iter()
+                    .position(|&byte| byte == b'\n')
+                    .map_or(rest.len(), |position| position + 1);
+                std::str::from_utf8(&rest[..end])
+                    .map(|line| Some(line.to_string()))
+                    .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))
+            }
+        }
+    }
+}
+
+/// Reads the next line of a buffered reader with its line break, or `None` at its end.
+fn read_line(reader: &mut impl BufRead) -> io::Result<Option<String>> {
+    let mut line = String::new();
+    match reader.read_line(&mut line)? {
+        0 => Ok(None),
+        _ => Ok(Some(line)),
+    }
+}
+
+impl Iterator for LineReader {
+    type Item = io::Result<InputLine>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let line = match self.next_raw() {
+            Ok(line) => line?,
+            Err(e) => return Some(Err(e)),
+        };
+        self.number += 1;
+        self.position += line.len() as u64;
+        Some(Ok(InputLine {
+            number: self.number,
+            end: self.position,
+            text: line
+                .trim_end_matches('\n')
+                .trim_end_matches('\r')
+                .to_string(),
+        }))
+    }
+}
+
+/// Lines of a batch input that hold equations, as returned by [`LineReader::equation_lines`].
+pub struct EquationLines {
+    reader: LineReader,
+}
+
+impl Iterator for EquationLines {
+    type Item = io::Result<InputLine>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        self.reader
+            .by_ref()
+            .find(|line| line.as_ref().map_or(true, |line| !is_skipped(&line.text)))
+    }
+}
+
+/// Last line of an input that a job has finished, saved so that an interrupted job can resume.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Checkpoint {
+    /// Path of the input the job reads.
+    pub input: String,
+    /// Number of the last finished line, or 0 before the first one.
+    pub line: usize,
+    /// Byte offset right after the last finished line.
+    pub offset: u64,
+    /// Size of the input in bytes when the job started, or `None` for stdin.
+    #[serde(default)]
+    pub size: Option<u64>,
+    /// Modification time of the input in nanoseconds since the Unix epoch when the job started,
+    /// or `None` for stdin and file systems without modification times.
+    #[serde(default)]
+    pub modified: Option<u64>,
+}
+
+impl Checkpoint {
+    /// Creates a checkpoint at the start of an input, with the size and modification time of the
+    /// input file so that a changed file is not resumed.
+    pub fn new(input: &str) -> Self {
+        let metadata = fs::metadata(input).ok();
+        Checkpoint {
+            input: input.to_string(),
+            line: 0,
+            offset: 0,
+            size: metadata.as_ref().map(|metadata| metadata.len()),
+            modified: metadata
+                .and_then(|metadata| metadata.modified().ok())
+                .and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+                .and_then(|duration| u64::try_from(duration.as_nanos()).ok()),
+        }
+    }
+
+    /// Checks if the checkpoint was saved for an input with the same path, size and modification
+    /// time, so that resuming it does not skip or repeat lines of a changed file.
+    pub fn matches_input(&self, input: &str) -> bool {
+        let current = Checkpoint::new(input);
+        self.input == current.input
+            && self.size == current.size
+            && self.modified == current.modified
+    }
+
+    /// Moves the checkpoint past a finished line.
+    pub fn advance(&mut self, line: &InputLine) {
+        self.line = line.number;
+        self.offset = line.end;
+    }
+
+    /// Loads a checkpoint from a JSON file, or returns `None` if the file does not exist.
+    pub fn load<P: AsRef<Path>>(path: P) -> io::Result<Option<Self>> {
+        match fs::read_to_string(path) {
+            Ok(data) => serde_json::from_str(&data)
+                .map(Some)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e)),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => Ok(None),
+            Err(e) => Err(e),
+        }
+    }
+
+    /// Saves the checkpoint as JSON. The file is replaced in one step, so an interruption while saving
+    /// leaves the previous checkpoint intact.
+    pub fn save<P: AsRef<Path>>(&self, path: P) -> io::Result<()> {
+        let path = path.as_ref();
+        let mut temporary = path.as_os_str().to_owned();
+        temporary.push(".tmp");
+        fs::write(&temporary, serde_json::to_string(self)?)?;
+        fs::rename(&temporary, path)
+    }
+
+    /// Removes a saved checkpoint once its job has finished, so that a later run starts over.
+    /// A checkpoint that does not exist is not an error.
+    pub fn remove<P: AsRef<Path>>(path: P) -> io::Result<()> {
+        match fs::remove_file(path) {
+            Err(e) if e.kind() != io::ErrorKind::NotFound => Err(e),
+            _ => Ok(()),
+        }
+    }
+}
@@ -0,0 +1,254 @@
+//! # Ionic Module
+//!
+//! This module writes the complete and net ionic forms of a molecular equation: soluble strong
+//! electrolytes are split into their ions following a built-in table of solubility rules, and the
+//! spectator ions, which appear unchanged on both sides, are cancelled.
+
+use crate::element::{composition_key, gcd, Equation, Formula, State, Term};
+use crate::{ChemParseError, ChemParser};
+
+/// Cations of the alkali metals, whose salts and hydroxides are soluble strong electrolytes.
+const ALKALI_CATIONS: &[&str] = &["Li^+", "Na^+", "K^+", "Rb^+", "Cs^+"];
+
+/// Other cations that salts dissociate into.
+const CATIONS: &[&str] = &[
+    "NH4^+", "Ag^+", "Mg^2+", "Ca^2+", "Sr^2+", "Ba^2+", "Mn^2+", "Fe^2+", "Co^2+", "Ni^2+",
+    "Cu^2+", "Zn^2+", "Hg2^2+", "Pb^2+", "Al^3+", "Cr^3+", "Fe^3+",
+];
+
+/// Anions of the strong acids, which dissociate completely in water.
+const STRONG_ACID_ANIONS: &[&str] = &["Cl^-", "Br^-", "I^-", "NO3^-", "ClO3^-", "ClO4^-", "SO4^2-"];
+
+/// Solubility rules: every anion with whether its salts are soluble and the cations that are
+/// exceptions to the rule. Salts of the alkali metals and ammonium are always soluble.
+const SOLUBILITY_RULES: &[(&str, bool, &[&str])] = &[
+    ("NO3^-", true, &[]),
+    ("CH3COO^-", true, &[]),
+    ("ClO3^-", true, &[]),
+    ("ClO4^-", true, &[]),
+    ("Cl^-", true, &["Ag^+", "Hg2^2+", "Pb^2+"]),
+    ("Br^-", true, &["Ag^+", "Hg2^2+", "Pb^2+"]),
+    ("I^-", true, &["Ag^+", "Hg2^2+", "Pb^2+"]),
+    (
+        "SO4^2-",
+        true,
+        &["Ca^2+", "Sr^2+", "Ba^2+", "Ag^+", "Hg2^2+", "Pb^2+"],
+    ),
+    ("F^-", true, &["Mg^2+", "Ca^2+", "Sr^2+", "Ba^2+", "Pb^2+"]),
+    ("OH^-", false, &["Ca^2+", "Sr^2+", "Ba^2+"]),
+    ("CO3^2-", false, &[]),
+    ("HCO3^-", false, &[]),
+    ("SO3^2-", false, &[]),
+    ("S^2-", false, &[]),
+    ("PO4^3-", false, &[]),
+    ("CrO4^2-", false, &[]),
+    ("C2O4^2-", false, &[]),
+];
+
+/// Ions with the formulas they are written with, parsed with the table of a parser.
+pub(crate) type ParsedIons = Vec<(&'static str, Formula)>;
+
+/// Parses ion formulas with the table of a parser.
+pub(crate) fn parse_ions(
+    parser: &ChemParser,
+    ions: impl IntoIterator<Item = &'static str>,
+) -> Result<ParsedIons, ChemParseError> {
+    ions.into_iter()
+        .map(|ion| Ok((ion, parser.parse_formula(ion)?)))
+        .collect()
+}
+
+/// Returns the anions of the solubility rules.
+pub(crate) fn rule_anions() -> impl Iterator<Item = &'static str> {
+    SOLUBILITY_RULES.iter().map(|(anion, _, _)| *anion)
+}
+
+/// Ion of a compound with its count and the formula it is written with.
+pub(crate) type IonCount<'a> = (u32, &'a str, &'a Formula);
+
+/// Splits a neutral compound into one of the cations and one of the anions with their counts,
+/// such as `Al2(SO4)3` into 2 `Al^3+` and 3 `SO4^2-`. The first pair of ions that makes up the
+/// compound is returned.
+pub(crate) fn split_salt<'a>(
+    formula: &Formula,
+    cations: &'a [(&'static str, Formula)],
+    anions: &'a [(&'static str, Formula)],
+) -> Option<[IonCount<'a>; 2]> {
+    if formula.charge != 0 || formula.elements.is_empty() {
+        return None;
+    }
+    for (cation_name, cation) in cations {
+        for (anion_name, anion) in anions {
+            let (cation_charge, anion_charge) =
+                (cation.charge.unsigned_abs(), anion.charge.unsigned_abs());
+            let divisor = gcd(cation_charge, anion_charge);
+            let (cation_count, anion_count) = (anion_charge / divisor, cation_charge / divisor);
+            let Some(unit) = cation
+                .checked_mul(cation_count)
+                .zip(anion.checked_mul(anion_count))
+                .and_then(|(cation, anion)| cation.checked_add(&anion))
+            else {
+                continue;
+            };
+            let Some((key, count)) = unit.elements.iter().next() else {
+                continue;
+            };
+            let total = formula.elements.get(key).copied().unwrap_or(0);
+            if total == 0 || total % count != 0 {
+                continue;
+            }
+            let units = total / count;
+            if unit
+                .checked_mul(units)
+                .is_some_and(|scaled| scaled.same_composition(formula))
+            {
+                return Some([
+                    (cation_count * units, cation_name, cation),
+                    (anion_count * units, anion_name, anion),
+                ]);
+            }
+        }
+    }
+    None
+}
+
+/// Cations and anions of the solubility rules, parsed with the table of a parser.
+struct Ions {
+    cations: ParsedIons,
+    anions: ParsedIons,
+}
+
+impl Ions {
+    fn new(parser: &ChemParser) -> Result<Self, ChemParseError> {
+        Ok(Ions {
+            cations: parse_ions(
+                parser,
+                ["H^+"].iter().chain(ALKALI_CATIONS).chain(CATIONS).copied(),
+            )?,
+            anions: parse_ions(parser, rule_anions())?,
+        })
+    }
+
+    /// Splits a neutral compound of one cation and one anion into the counts of its ions, if it
+    /// is a soluble strong electrolyte. The first pair of ions that makes up the compound decides.
+    fn dissociate(&self, formula: &Formula) -> Option<[IonCount<'_>; 2]> {
+        split_salt(formula, &self.cations, &self.anions)
+            .filter(|[(_, cation, _), (_, anion, _)]| dissociates(cation, anion))
+    }
+}
+
+/// Checks whether a compound of the ions is a soluble strong electrolyte: a strong acid, a strong
+/// base or a soluble salt.
+fn dissociates(cation: &str, anion: &str) -> bool {
+    if cation == "H^+" {
+        return STRONG_ACID_ANIONS.contains(&anion);
+    }
+    // Ammonium hydroxide is a weak base.
+    if cation == "NH4^+" && anion == "OH^-" {
+        return false;
+    }
+    if ALKALI_CATIONS.contains(&cation) || cation == "NH4^+" {
+        return true;
+    }
+    SOLUBILITY_RULES
+        .iter()
+        .find(|(name, _, _)| *name == anion)
+        .is_some_and(|(_, soluble, exceptions)| *soluble != exceptions.contains(&cation))
+}
+
+impl Equation {
+    /// Returns the complete ionic equation, with every soluble strong electrolyte split into its
+    /// aqueous ions, such as `Ag^+(aq) + NO3^-(aq) + Na^+(aq) + Cl^-(aq) -> AgCl(s) + Na^+(aq) +
+    /// NO3^-(aq)` for `AgNO3(aq) + NaCl(aq) -> AgCl(s) + NaNO3(aq)`.
+    ///
+    /// Species written as solids, liquids or gases are kept whole, as are weak electrolytes and
+    /// insoluble compounds by the solubility rules. Species without a state are split when the
+    /// rules say they dissolve. The ion formulas are parsed with the parser. Fails if the
+    /// equation is not balanced.
+    pub fn to_ionic(&self, parser: &ChemParser) -> Result<Equation, ChemParseError> {
+        self.check_stoichiometric()?;
+        let ions = Ions::new(parser)?;
+        let side = |terms: &[Term]| -> Result<Vec<Term>, ChemParseError> {
+            let mut ionic: Vec<Term> = Vec::new();
+            for term in terms {
+                let parts = match term.state {
+                    Some(State::Solid | State::Liquid | State::Gas) => None,
+                    _ => ions.dissociate(&term.formula),
+                };
+                let Some(parts) = parts else {
+                    merge(&mut ionic, term.clone());
+                    continue;
+                };
+                for (count, _, ion) in parts {
+                    let coefficient = term.coefficient.checked_mul(count).ok_or_else(|| {
+                        ChemParseError::Overflow(format!("coefficient of {}", ion.formula))
+                    })?;
+                    merge(
+                        &mut ionic,
+                        Term {
+                            coefficient,
+                            formula: ion.clone(),
+                            state: Some(State::Aqueous),
+                        },
+                    );
+                }
+            }
+            Ok(ionic)
+        };
+        Ok(self.with_terms(side(&self.reactant_terms)?, side(&self.product_terms)?))
+    }
+
+    /// Returns the net ionic equation: the complete ionic equation from [`Equation::to_ionic`]
+    /// without the spectator ions, in lowest whole terms, such as `Ag^+(aq) + Cl^-(aq) -> AgCl(s)`
+    /// for `AgNO3(aq) + NaCl(aq) -> AgCl(s) + NaNO3(aq)`.
+    ///
+    /// Fails like [`Equation::to_ionic`], and if every species is a spectator, so that no
+    /// reaction takes place.
+    pub fn to_net_ionic(&self, parser: &ChemParser) -> Result<Equation, ChemParseError> {
+        let whole = self.to_integer_coefficients();
+        let ionic = whole.to_ionic(parser)?;
+        let mut reactants = ionic.reactant_terms;
+        let mut products = ionic.product_terms;
+        for reactant in &mut reactants {
+            if let Some(product) = products
+                .iter_mut()
+                .find(|product| same_species(product, reactant))
+            {
+                let spectators = reactant.coefficient.min(product.coefficient);
+                reactant.coefficient -= spectators;
+                product.coefficient -= spectators;
+            }
+        }
+        reactants.retain(|term| term.coefficient > 0);
+        products.retain(|term| term.coefficient > 0);
+        if reactants.is_empty() || products.is_empty() {
+            return Err(ChemParseError::EvaluationError(
+                format!("net ionic equation of {}", self.equation),
+                String::from("every species is a spectator, so no reaction takes place"),
+            ));
+        }
+        let divisor = reactants
+            .iter()
+            .chain(&products)
+            .fold(0, |divisor, term| gcd(divisor, term.coefficient));
+        for term in reactants.iter_mut().chain(&mut products) {
+            term.coefficient /= divisor;
+        }
+        Ok(whole.with_terms(reactants, products))
+    }
+}
+
+/// Adds a term to a side, adding up the coefficients of a species that is already on it.
+fn merge(side: &mut Vec<Term>, term: Term) {
+    match side.iter_mut().find(|other| same_species(other, &term)) {
+        Some(other) => other.coefficient += term.coefficient,
+        None => side.push(term),
+    }
+}
+
+/// Checks whether two terms are the same species, with the same atoms and charge and the same
+/// state where both have one.
+fn same_species(first: &Term, second: &Term) -> bool {
+    composition_key(&first.formula) == composition_key(&second.formula)
+        && (first.state.is_none() || second.state.is_none() || first.state == second.state)
+}
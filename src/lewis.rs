@@ -0,0 +1,133 @@
+//! # Lewis structure Module
+//!
+//! This module counts valence electrons of elements and formulas and distributes the electrons of
+//! simple molecules and ions into bonding pairs and lone pairs, the numbers a Lewis structure is
+//! drawn from.
+
+use crate::element::{element_symbol, Element, Formula, PeriodicTable};
+use crate::ChemParseError;
+use serde::Serialize;
+
+/// Electron pairs of a Lewis structure in which every atom has a full valence shell: an octet,
+/// or two electrons for hydrogen and helium.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct LewisCounts {
+    /// Valence electrons of the formula, corrected for its charge.
+    pub valence_electrons: u32,
+    /// Pairs of electrons shared in bonds, two for a double bond.
+    pub bonding_pairs: u32,
+    /// Pairs of electrons that are not shared.
+    pub lone_pairs: u32,
+}
+
+impl Element {
+    /// Returns the number of valence electrons: the electrons of the outermost shell of the
+    /// [electron configuration](Element::electron_configuration) and of incompletely filled inner
+    /// `d` and `f` subshells, such as 7 for chlorine, 8 for iron and 2 for zinc.
+    pub fn valence_electrons(&self) -> u32 {
+        let configuration = self.electron_configuration();
+        let outermost = configuration
+            .subshells
+            .iter()
+            .map(|subshell| subshell.n)
+            .max()
+            .unwrap_or(0);
+        configuration
+            .subshells
+            .iter()
+            .filter(|subshell| {
+                let capacity = match subshell.letter {
+                    'd' => 10,
+                    'f' => 14,
+                    _ => 0,
+                };
+                subshell.n == outermost || subshell.electrons < capacity
+            })
+            .map(|subshell| u32::from(subshell.electrons))
+            .sum()
+    }
+}
+
+impl Formula {
+    /// Returns the total number of valence electrons of the formula, less its charge, such as 8
+    /// for `H2O` and 32 for `SO4^2-`. Isotopes count as their element. Returns `None` if an
+    /// element is not in the table or the charge exceeds the electrons.
+    pub fn total_valence_electrons(&self, table: &PeriodicTable) -> Option<u32> {
+        let mut total: u32 = 0;
+        for (key, count) in &self.elements {
+            let valence = table.get_element(element_symbol(key))?.valence_electrons();
+            total = total.checked_add(valence.checked_mul(*count)?)?;
+        }
+        u32::try_from(i64::from(total) - i64::from(self.charge)).ok()
+    }
+
+    /// Distributes the valence electrons of a simple molecule or ion into bonding and lone pairs
+    /// so that every atom has a full valence shell, such as 2 bonding and 2 lone pairs for `H2O`
+    /// or 4 bonding and 4 lone pairs for `CO2`. The shared electrons are the electrons needed for
+    /// full shells less the valence electrons available.
+    ///
+    /// Fails with [`ChemParseError::MissingData`] if an element is not in the table, and with
+    /// [`ChemParseError::EvaluationError`] if the electrons cannot give every atom a full shell
+    /// with the atoms connected, as for molecules with an expanded octet such as `SF6`, with too
+    /// few electrons such as `BH3` or with an odd number of electrons such as `NO`, and with
+    /// [`ChemParseError::Overflow`] if the electrons of a very large formula cannot be counted.
+    pub fn lewis_counts(&self, table: &PeriodicTable) -> Result<LewisCounts, ChemParseError> {
+        let missing: Vec<String> = self
+            .elements
+            .keys()
+            .filter(|key| table.get_element(element_symbol(key)).is_none())
+            .cloned()
+            .collect();
+        if !missing.is_empty() {
+            return Err(ChemParseError::MissingData(
+                String::from("element data"),
+                missing,
+            ));
+        }
+        let error = |reason: &str| {
+            ChemParseError::EvaluationError(
+                format!("Lewis structure of {}", self.formula),
+                String::from(reason),
+            )
+        };
+        let available = self
+            .total_valence_electrons(table)
+            .ok_or_else(|| error("the charge exceeds the valence electrons"))?;
+        let overflow = || ChemParseError::Overflow(format!("electron count of {}", self.formula));
+        let needed = self
+            .elements
+            .iter()
+            .try_fold(0u32, |needed, (key, count)| {
+                let shell = match element_symbol(key) {
+                    "H" | "He" => 2,
+                    _ => 8,
+                };
+                needed.checked_add(count.checked_mul(shell)?)
+            })
+            .ok_or_else(overflow)?;
+        let atoms = self
+            .elements
+            .values()
+            .try_fold(0u32, |atoms, count| atoms.checked_add(*count))
+            .ok_or_else(overflow)?;
+        if available % 2 != 0 {
+            return Err(error("the number of valence electrons is odd"));
+        }
+        let shared = needed.saturating_sub(available);
+        if shared > available {
+            return Err(error(
+                "there are too few valence electrons to share, as with BH3",
+            ));
+        }
+        if shared / 2 + 1 < atoms {
+            return Err(error(
+                "the atoms cannot all have a full shell, as with an expanded octet",
+            ));
+        }
+        Ok(LewisCounts {
+            valence_electrons: available,
+            bonding_pairs: shared / 2,
+            lone_pairs: (available - shared) / 2,
+        })
+    }
+}
@@ -18,223 +18,789 @@
 //! let equation_struct = parser.parse_equation("2H2 + O2 -> 2H2O").unwrap();
 //! println!("{}", equation_struct);
 //! ```
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+// The library is also built as a cdylib, which needs the panic handler and allocator of the
+// standard library on targets that have one; bare-metal targets drop the cdylib.
+#[cfg(all(not(feature = "std"), not(target_os = "none")))]
+extern crate std as _;
+
+#[cfg(feature = "std")]
+pub mod acid_base;
+#[cfg(feature = "std")]
+pub mod activity;
+#[cfg(feature = "std")]
+pub mod analysis;
+pub mod ast;
+#[cfg(feature = "std")]
+pub mod balance;
+#[cfg(feature = "std")]
+pub mod bond;
+#[cfg(feature = "std")]
+pub mod builder;
+#[cfg(feature = "std")]
+pub mod cache;
+#[cfg(feature = "std")]
+pub mod calc;
+#[cfg(feature = "std")]
+pub mod completion;
+#[cfg(feature = "compounds")]
+pub mod compounds;
+#[cfg(feature = "std")]
+pub mod configuration;
+#[cfg(feature = "std")]
+pub mod convert;
+#[cfg(feature = "std")]
+pub mod debug;
+#[cfg(feature = "std")]
+pub mod density;
+pub mod diagnostic;
 pub mod element;
+#[cfg(any(feature = "datasets", not(feature = "std")))]
+mod embedded;
+#[cfg(feature = "std")]
+pub mod equilibrium;
+#[cfg(feature = "std")]
+pub mod export;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "std")]
+pub mod gas;
+#[cfg(feature = "generator")]
+pub mod generator;
+#[cfg(feature = "std")]
+pub mod hess;
+#[cfg(feature = "std")]
+pub mod hydrate;
+#[cfg(feature = "std")]
+pub mod inchi;
+#[cfg(feature = "std")]
+pub mod input;
+#[cfg(feature = "std")]
+pub mod ionic;
+#[cfg(feature = "std")]
+pub mod lewis;
+#[cfg(feature = "std")]
+pub mod matrix;
+#[cfg(feature = "std")]
+pub mod metadata;
+#[cfg(feature = "datasets")]
+pub mod names;
+#[cfg(feature = "std")]
+pub mod network;
+#[cfg(feature = "datasets")]
+pub mod nomenclature;
+#[cfg(feature = "std")]
+pub mod normalize;
+pub mod nuclear;
+pub mod options;
+#[cfg(feature = "datasets")]
+pub mod polyatomic;
+#[cfg(feature = "std")]
+pub mod predict;
+#[cfg(feature = "std")]
+pub mod progress;
+pub mod provenance;
+#[cfg(feature = "python")]
+pub mod python;
+pub mod query;
+#[cfg(feature = "std")]
+pub mod redox;
+#[cfg(feature = "std")]
+pub mod render;
+#[cfg(feature = "std")]
+pub mod rewrite;
+#[cfg(feature = "std")]
+pub mod schema;
+#[cfg(feature = "std")]
+pub mod sequence;
+#[cfg(feature = "smiles")]
+pub mod smiles;
+#[cfg(feature = "std")]
+pub mod solution;
+#[cfg(feature = "std")]
+pub mod solve;
+#[cfg(feature = "std")]
+pub mod spectrum;
+#[cfg(feature = "std")]
+pub mod stats;
+#[cfg(feature = "std")]
+pub mod stoichiometry;
+pub mod tagged;
+pub mod temperature;
+#[cfg(feature = "std")]
+pub mod testing;
+#[cfg(feature = "std")]
+pub mod thermo;
+pub mod trivial;
+#[cfg(feature = "std")]
+pub mod uncertainty;
+#[cfg(feature = "std")]
+pub mod validation;
+pub mod visit;
+#[cfg(feature = "wasm")]
+pub mod wasm;
+
+/// Items of the standard prelude that `no_std` builds import from `alloc`.
+#[cfg(not(feature = "std"))]
+mod prelude {
+    pub(crate) use alloc::boxed::Box;
+    pub(crate) use alloc::format;
+    pub(crate) use alloc::string::{String, ToString};
+    pub(crate) use alloc::vec;
+    pub(crate) use alloc::vec::Vec;
+}
 
-use crate::element::{Element, Equation, Formula, PeriodicTable};
-use pest::iterators::Pair;
+#[cfg(feature = "std")]
+use crate::cache::FormulaCache;
+use crate::diagnostic::Diagnostic;
+use crate::element::{
+    gcd, isotope_key, parse_isotope_key, Arrow, Coefficient, Element, Equation, Formula,
+    PeriodicTable, State, Term,
+};
+#[cfg(feature = "datasets")]
+use crate::element::{DatasetMass, DatasetVersion};
+#[cfg(feature = "datasets")]
+use crate::embedded::ISOTOPES_CSV;
+use crate::nuclear::{NuclearEquation, Nuclide};
+use crate::options::{GrammarOptions, ParseOptions};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::provenance::Provenance;
+use crate::tagged::{collect_occurrences, TaggedFormula};
+use crate::trivial::NameDictionary;
+use alloc::collections::BTreeMap;
+use alloc::sync::Arc;
+use core::error::Error;
+use pest::iterators::{Pair, Pairs};
 use pest::Parser;
 use pest_derive::Parser;
-use std::collections::HashMap;
+#[cfg(feature = "csv")]
+use std::path::Path;
 use thiserror::Error;
 
 /// Represents possible errors in chemical parsing.
+///
+/// New kinds of errors can be added in minor versions, so matches need a wildcard arm.
+/// Errors caused by data that cannot be read keep the underlying error as their source.
 #[derive(Debug, Error)]
+#[non_exhaustive]
 pub enum ChemParseError {
-    /// Custom Error for cases, when the parsed element symbol is not in the periodic table
-    #[error("Invalid element symbol: {0}")]
-    InvalidElement(String),
+    /// Custom Error for cases, when the parsed element symbol is not in the periodic table,
+    /// with suggestions of similar valid symbols
+    ///
+    /// Code `E0001`.
+    #[error("Invalid element symbol: {0}{hint}", hint = did_you_mean(.1))]
+    InvalidElement(String, Vec<String>),
 
-    /// Custom Error for cases, when the parsed formula contains element symbol that is not in the periodic table
-    #[error("Invalid chemical formula \"{0}\" with invalid element symbol {1}")]
-    InvalidFormula(String, String),
+    /// Custom Error for cases, when the parsed formula contains element symbol that is not in the periodic table,
+    /// with suggestions of similar valid symbols
+    ///
+    /// Code `E0002`.
+    #[error("Invalid chemical formula \"{0}\" with invalid element symbol {1}{hint}", hint = did_you_mean(.2))]
+    InvalidFormula(String, String, Vec<String>),
 
     /// Custom Error for unsuccessful parsing cases
+    ///
+    /// Code `E0003`.
     #[error("Failed to parse {0}: {1}")]
     ParsingError(String, String),
 
+    /// Custom Error for input that does not match the grammar, with the position of the error
+    ///
+    /// Code `E0004`.
+    #[error("Failed to parse {0} \"{input}\" at {1}", input = .1.input)]
+    SyntaxError(String, Box<Diagnostic>),
+
+    /// Custom Error for cases, when the parsed isotope is not in the isotope table
+    ///
+    /// Code `E0005`.
+    #[error("Unknown isotope: {0}")]
+    InvalidIsotope(String),
+
     /// Custom Error for invalid index format in formula
+    ///
+    /// Code `E0006`.
     #[error("Invalid index format: {0}")]
     InvalidIndexFormat(String),
 
     /// Custom Error for invalid coefficient format in equation
+    ///
+    /// Code `E0007`.
     #[error("Invalid coefficient format: {0}")]
     InvalidCoefficientFormat(String),
+
+    /// Custom Error for element or isotope data that cannot be loaded
+    ///
+    /// Code `E0008`.
+    #[error("Failed to load data from {0}: {1}")]
+    DataError(String, #[source] Box<dyn Error + Send + Sync>),
+
+    /// Custom Error for equations that cannot be balanced with positive coefficients within the cap
+    ///
+    /// Code `E0009`.
+    #[error("Failed to balance {0}: {1}")]
+    BalancingError(String, String),
+
+    /// Custom Error for calculations that have no result, such as division by zero
+    ///
+    /// Code `E0010`.
+    #[error("Failed to evaluate {0}: {1}")]
+    EvaluationError(String, String),
+
+    /// Custom Error for atom counts or coefficients that do not fit into their integer type
+    ///
+    /// Code `E0011`.
+    #[error("Overflow in {0}")]
+    Overflow(String),
+
+    /// Custom Error for species without an entry in a data table, listing all of them
+    ///
+    /// Code `E0012`.
+    #[error("Missing {0} for {species}", species = .1.join(", "))]
+    MissingData(String, Vec<String>),
+
+    /// Custom Error for equations that fail the checks of a validation profile, listing the issues
+    ///
+    /// Code `E0013`.
+    #[error("{0} fails validation: {issues}", issues = .1.join(", "))]
+    ValidationError(String, Vec<String>),
+
+    /// Custom Error for reactants that match no reaction class whose products can be predicted
+    ///
+    /// Code `E0014`.
+    #[error("Cannot predict the products of {0}: {1}")]
+    CannotPredict(String, String),
+}
+
+impl ChemParseError {
+    /// Returns the stable code of the error kind, such as `E0001` for [`ChemParseError::InvalidElement`],
+    /// for branching on errors without matching their messages. Codes are never reused or renumbered.
+    pub fn code(&self) -> &'static str {
+        match self {
+            ChemParseError::InvalidElement(..) => "E0001",
+            ChemParseError::InvalidFormula(..) => "E0002",
+            ChemParseError::ParsingError(..) => "E0003",
+            ChemParseError::SyntaxError(..) => "E0004",
+            ChemParseError::InvalidIsotope(..) => "E0005",
+            ChemParseError::InvalidIndexFormat(..) => "E0006",
+            ChemParseError::InvalidCoefficientFormat(..) => "E0007",
+            ChemParseError::DataError(..) => "E0008",
+            ChemParseError::BalancingError(..) => "E0009",
+            ChemParseError::EvaluationError(..) => "E0010",
+            ChemParseError::Overflow(..) => "E0011",
+            ChemParseError::MissingData(..) => "E0012",
+            ChemParseError::ValidationError(..) => "E0013",
+            ChemParseError::CannotPredict(..) => "E0014",
+        }
+    }
+
+    /// Renders the input of a syntax error with the failing position underlined, or `None`
+    /// for other errors. See [`Diagnostic::render_snippet`].
+    pub fn snippet(&self) -> Option<String> {
+        match self {
+            ChemParseError::SyntaxError(_, diagnostic) => Some(diagnostic.render_snippet()),
+            _ => None,
+        }
+    }
+}
+
+/// Creates a syntax error for an input of the given kind from an error of the pest parser.
+pub(crate) fn syntax_error(
+    kind: &str,
+    input: &str,
+    error: &pest::error::Error<Rule>,
+) -> ChemParseError {
+    ChemParseError::SyntaxError(
+        String::from(kind),
+        Box::new(Diagnostic::from_pest(input, error)),
+    )
+}
+
+/// Formats suggestions of an error as `, did you mean Cl or C?`, or nothing without suggestions.
+fn did_you_mean(suggestions: &[String]) -> String {
+    match suggestions {
+        [] => String::new(),
+        [suggestion] => format!(", did you mean {}?", suggestion),
+        [rest @ .., last] => format!(", did you mean {} or {}?", rest.join(", "), last),
+    }
 }
 
 /// Parser for chemical elements, formulas, and equations.
-#[derive(Parser)]
+///
+/// The periodic table is shared between clones of a parser and between parsers created with
+/// [`ChemParser::new`], so parsers are cheap to create and clone, for example per request.
+#[derive(Parser, Clone)]
 #[grammar = "chem.pest"]
 pub struct ChemParser {
-    periodic_table: PeriodicTable,
+    periodic_table: Arc<PeriodicTable>,
+    names: NameDictionary,
+    options: ParseOptions,
+    grammar_options: GrammarOptions,
+    #[cfg(feature = "std")]
+    formula_cache: Option<Arc<FormulaCache>>,
 }
 
 impl ChemParser {
-    /// Creates a new ChemParser instance and loads elements from a CSV file.
+    /// Creates a new ChemParser instance with the elements and isotopes embedded in the library.
+    /// The data is loaded once and shared through [`PeriodicTable::global`].
+    ///
+    /// The embedded data is checked by the test suite; use [`ChemParser::try_new`] to handle
+    /// a failure instead of panicking.
+    #[cfg(any(feature = "datasets", not(feature = "std")))]
     pub fn new() -> Self {
+        Self::try_new().expect("embedded element data is valid")
+    }
+
+    /// Creates a new ChemParser instance with the embedded elements and isotopes,
+    /// returning an error instead of panicking if the data cannot be loaded.
+    #[cfg(any(feature = "datasets", not(feature = "std")))]
+    pub fn try_new() -> Result<Self, ChemParseError> {
+        #[cfg(feature = "datasets")]
+        let periodic_table = PeriodicTable::try_global().cloned();
+        #[cfg(not(feature = "std"))]
+        let periodic_table = PeriodicTable::embedded().map(Arc::new);
+        periodic_table
+            .map(Self::with_shared_table)
+            .map_err(|e| ChemParseError::DataError(String::from("embedded data"), e))
+    }
+
+    /// Creates a new ChemParser instance with the standard atomic weights of a bundled IUPAC edition.
+    #[cfg(feature = "datasets")]
+    pub fn with_dataset(version: DatasetVersion) -> Result<Self, ChemParseError> {
+        PeriodicTable::bundled(version)
+            .map(Self::with_table)
+            .map_err(|e| ChemParseError::DataError(String::from(version.name()), e))
+    }
+
+    /// Creates a new ChemParser instance with a custom periodic table.
+    pub fn with_table(periodic_table: PeriodicTable) -> Self {
+        Self::with_shared_table(Arc::new(periodic_table))
+    }
+
+    /// Creates a new ChemParser instance with a periodic table shared with other parsers.
+    pub fn with_shared_table(periodic_table: Arc<PeriodicTable>) -> Self {
         ChemParser {
-            periodic_table: PeriodicTable::from_csv("./data/elements.csv")
-                .expect("Failed to parse periodic table"),
+            periodic_table,
+            names: NameDictionary::new(),
+            options: ParseOptions::default(),
+            grammar_options: GrammarOptions::default(),
+            #[cfg(feature = "std")]
+            formula_cache: None,
         }
     }
 
+    /// Creates a new ChemParser instance with elements loaded from the CSV file at `path`
+    /// and the embedded isotopes.
+    #[cfg(feature = "datasets")]
+    pub fn from_csv_path(path: &str) -> Result<Self, ChemParseError> {
+        let data_error =
+            |e: Box<dyn Error + Send + Sync>| ChemParseError::DataError(String::from(path), e);
+        let mut periodic_table = PeriodicTable::from_csv(path).map_err(data_error)?;
+        periodic_table
+            .load_isotopes_csv_str(ISOTOPES_CSV)
+            .map_err(data_error)?;
+        Ok(Self::with_table(periodic_table))
+    }
+
+    /// Creates a new ChemParser instance and loads elements and isotopes from the
+    /// `elements.csv` and `isotopes.csv` files in the given directory.
+    #[cfg(feature = "csv")]
+    pub fn from_data_dir(dir: &str) -> Result<Self, ChemParseError> {
+        let data_error =
+            |e: Box<dyn Error + Send + Sync>| ChemParseError::DataError(String::from(dir), e);
+        let dir = Path::new(dir);
+        let mut periodic_table =
+            PeriodicTable::from_csv(&dir.join("elements.csv").to_string_lossy())
+                .map_err(data_error)?;
+        periodic_table
+            .load_isotopes_csv(&dir.join("isotopes.csv").to_string_lossy())
+            .map_err(data_error)?;
+        Ok(Self::with_table(periodic_table))
+    }
+
     /// Returns a reference to the PeriodicTable.
     pub fn get_table(&self) -> &PeriodicTable {
         &self.periodic_table
     }
 
-    /// Parses and validates an element symbol.
-    pub fn parse_element(&self, element: &str) -> Result<&Element, ChemParseError> {
-        let mut element_parse = ChemParser::parse(Rule::element, element).map_err(|_| {
-            ChemParseError::ParsingError(String::from("element"), String::from(element))
-        })?;
+    /// Returns the shared periodic table, for creating other parsers with
+    /// [`ChemParser::with_shared_table`].
+    pub fn shared_table(&self) -> &Arc<PeriodicTable> {
+        &self.periodic_table
+    }
 
-        let element_symbol = element_parse.next().unwrap().as_str();
+    /// Returns the provenance of results computed by this parser: the crate version and dataset checksum.
+    pub fn provenance(&self) -> Provenance {
+        Provenance::new(&self.periodic_table)
+    }
 
-        if !self.validate_element(element_symbol) {
-            return Err(ChemParseError::InvalidElement(String::from(element_symbol)));
+    /// Parses and validates an element symbol, or resolves a full element name such as `oxygen`.
+    pub fn parse_element(&self, element: &str) -> Result<&Element, ChemParseError> {
+        if let Some(named_element) = self.periodic_table.get_by_name(element.trim()) {
+            return Ok(named_element);
         }
 
-        Ok(self.get_table().get_element(element_symbol).unwrap())
+        let mut element_parse = ChemParser::parse(Rule::element, element)
+            .map_err(|e| syntax_error("element", element, &e))?;
+
+        let element_symbol = next_pair(&mut element_parse, "element")?.as_str();
+
+        match self.get_table().get_element(element_symbol) {
+            Some(found) if element_symbol == element.trim() => Ok(found),
+            _ => {
+                let element = element.trim();
+                Err(ChemParseError::InvalidElement(
+                    String::from(element),
+                    self.periodic_table.suggest_symbols(element),
+                ))
+            }
+        }
     }
 
-    /// Parses and validates a chemical formula string.
+    /// Parses and validates a chemical formula string, as tolerantly as the [`ParseOptions`]
+    /// of the parser allow. Parsers with a formula cache (see [`ChemParser::with_formula_cache`])
+    /// return formulas parsed before from the cache.
     pub fn parse_formula(&self, formula: &str) -> Result<Formula, ChemParseError> {
-        let mut formula_parse = ChemParser::parse(Rule::formula, formula).map_err(|_| {
-            ChemParseError::ParsingError(String::from("formula"), String::from(formula))
-        })?;
+        #[cfg(feature = "std")]
+        if let Some(cache) = &self.formula_cache {
+            return cache.get_or_parse(formula, || self.parse_formula_uncached(formula));
+        }
+        self.parse_formula_uncached(formula)
+    }
 
-        let mut inside_pairs = formula_parse.next().unwrap();
+    fn parse_formula_uncached(&self, formula: &str) -> Result<Formula, ChemParseError> {
+        let candidates = self.formula_candidates(formula);
+        if candidates.len() > 1 {
+            for candidate in &candidates {
+                match self.parse_formula_as_written(candidate) {
+                    Ok(parsed) if parsed.formula == *candidate => return Ok(parsed),
+                    _ => {}
+                }
+            }
+        }
+        self.parse_formula_as_written(&candidates[0])
+    }
+
+    fn parse_formula_as_written(&self, formula: &str) -> Result<Formula, ChemParseError> {
+        let formula = &normalize_scripts(formula);
+
+        let mut formula_parse = ChemParser::parse(Rule::formula, formula)
+            .map_err(|e| syntax_error("formula", formula, &e))?;
+
+        let inside_pairs = next_pair(&mut formula_parse, "formula")?;
 
         let mut formula_struct = Formula::new(inside_pairs.as_str());
 
+        // Symbols are counted under the keys interned in the periodic table, so the composition
+        // allocates one string per distinct element instead of one per occurrence.
+        let mut elements: BTreeMap<&str, u32> = BTreeMap::new();
         self.process_pairs(
             &formula_struct.formula,
-            &mut formula_struct.elements,
-            &mut inside_pairs,
+            &mut elements,
+            inside_pairs.clone(),
             1,
         )?;
 
-        formula_struct.mass = formula_struct
-            .elements
-            .iter()
-            .fold(0.0, |acc, (symbol, count)| {
-                let element = self.get_table().get_element(symbol).unwrap();
-                acc + (element.atomic_mass * *count as f64)
-            });
+        formula_struct.mass = elements.iter().try_fold(0.0, |acc, (symbol, count)| {
+            let mass = self
+                .get_table()
+                .get_mass(symbol)
+                .ok_or_else(|| ChemParseError::InvalidElement(symbol.to_string(), Vec::new()))?;
+            Ok(acc + (mass * *count as f64))
+        })?;
+        formula_struct.elements = elements
+            .into_iter()
+            .map(|(symbol, count)| (symbol.to_string(), count))
+            .collect();
+
+        let units: Vec<Pair<Rule>> = inside_pairs.clone().into_inner().collect();
+        let repeat_groups: Vec<&Pair<Rule>> = units
+            .windows(2)
+            .filter(|pairs| pairs[1].as_rule() == Rule::repeat_index)
+            .map(|pairs| &pairs[0])
+            .collect();
+        let repeat_indices = inside_pairs
+            .clone()
+            .into_inner()
+            .flatten()
+            .filter(|pair| pair.as_rule() == Rule::repeat_index)
+            .count();
+        if repeat_indices > 1 || repeat_indices != repeat_groups.len() {
+            return Err(ChemParseError::ParsingError(
+                formula_struct.formula,
+                String::from("a formula can have one repeat unit, outside of groups and adducts"),
+            ));
+        }
+        if let Some(group) = repeat_groups.first() {
+            let inner = next_pair(&mut (*group).clone().into_inner(), "repeat unit")?;
+            formula_struct.repeat_unit = Some(Box::new(self.parse_formula(inner.as_str())?));
+        }
+
+        formula_struct.charge = inside_pairs
+            .into_inner()
+            .filter(|pair| pair.as_rule() == Rule::charge)
+            .try_fold(0i32, |total, pair| {
+                total
+                    .checked_add(charge_value(&pair)?)
+                    .ok_or_else(|| ChemParseError::Overflow(format!("charge of {}", formula)))
+            })?;
 
         Ok(formula_struct)
     }
 
-    fn process_pairs(
-        &self,
+    /// Parses a chemical formula string into a formula whose element occurrences can be tagged.
+    pub fn parse_tagged_formula(&self, formula: &str) -> Result<TaggedFormula, ChemParseError> {
+        let formula = &normalize_scripts(formula);
+
+        let formula_struct = self.parse_formula(formula)?;
+
+        let mut formula_parse = ChemParser::parse(Rule::formula, formula)
+            .map_err(|e| syntax_error("formula", formula, &e))?;
+
+        let mut occurrences = Vec::new();
+        collect_occurrences(
+            next_pair(&mut formula_parse, "formula")?,
+            1,
+            &mut occurrences,
+        );
+
+        Ok(TaggedFormula::new(formula_struct, occurrences))
+    }
+
+    /// Adds the atoms of the units of a formula pair to the composition in a single pass,
+    /// looking ahead only to the index that follows a group.
+    fn process_pairs<'t>(
+        &'t self,
         formula_name: &str,
-        elements: &mut HashMap<String, u8>,
-        pairs: &mut Pair<Rule>,
-        multiplier: u8,
+        elements: &mut BTreeMap<&'t str, u32>,
+        pairs: Pair<Rule>,
+        multiplier: u32,
     ) -> Result<(), ChemParseError> {
-        let mut prev_elem: Option<String> = None;
+        let mut prev_elem: Option<&'t str> = None;
+        let mut units = pairs.into_inner().peekable();
 
-        for (pair_id, pair) in pairs.clone().into_inner().enumerate() {
+        while let Some(pair) = units.next() {
             match pair.as_rule() {
-                Rule::element => {
-                    let symbol = pair.as_str().to_string();
+                Rule::element | Rule::isotope => {
+                    let interned = if pair.as_rule() == Rule::isotope {
+                        let key = isotope_pair_key(&pair);
+                        let key = self.periodic_table.interned_key(&key).ok_or_else(|| {
+                            ChemParseError::InvalidIsotope(pair.as_str().to_string())
+                        })?;
+                        Some(key)
+                    } else {
+                        self.periodic_table.interned_key(pair.as_str())
+                    };
 
-                    if !self.validate_element(&symbol) {
+                    let Some(symbol) = interned else {
+                        let symbol = pair.as_str().to_string();
+                        let mut suggestions = self.periodic_table.suggest_symbols(&symbol);
+                        // A capitalised second letter, as in `CL`, splits one symbol into two.
+                        if let Some(joined) = prev_elem
+                            .map(|prev_symbol| prev_symbol.to_string() + &symbol.to_lowercase())
+                            .filter(|joined| self.validate_element(joined))
+                        {
+                            let len = suggestions.len().max(1);
+                            suggestions.retain(|suggestion| *suggestion != joined);
+                            suggestions.insert(0, joined);
+                            suggestions.truncate(len);
+                        }
                         return Err(ChemParseError::InvalidFormula(
                             String::from(formula_name),
                             symbol,
+                            suggestions,
                         ));
-                    }
+                    };
 
-                    if prev_elem.is_some() {
-                        let prev_symbol = prev_elem.unwrap().clone();
-                        *elements.entry(prev_symbol).or_insert(0) += multiplier;
+                    if let Some(prev_symbol) = prev_elem {
+                        add_atoms(elements, prev_symbol, 1, multiplier, formula_name)?;
                     }
                     prev_elem = Some(symbol);
                 }
-                Rule::group => {
-                    if prev_elem.is_some() {
-                        let prev_symbol = prev_elem.unwrap().clone();
-                        *elements.entry(prev_symbol).or_insert(0) += multiplier;
-                        prev_elem = None;
+                Rule::group | Rule::complex => {
+                    if let Some(prev_symbol) = prev_elem.take() {
+                        add_atoms(elements, prev_symbol, 1, multiplier, formula_name)?;
+                    }
+                    let group_multiplier = match units.next_if(|next| next.as_rule() == Rule::index)
+                    {
+                        Some(index) => index.as_str().parse::<u32>().map_err(|_| {
+                            ChemParseError::InvalidIndexFormat(index.as_str().to_string())
+                        })?,
+                        None => 1,
+                    };
+                    // Multipliers of enclosing groups accumulate, so nested groups of any depth
+                    // are counted correctly as long as the total fits into the element count type.
+                    let nested_multiplier =
+                        multiplier.checked_mul(group_multiplier).ok_or_else(|| {
+                            ChemParseError::InvalidIndexFormat(pair.as_str().to_string())
+                        })?;
+                    let inner_pairs = next_pair(&mut pair.into_inner(), "group")?;
+                    self.process_pairs(formula_name, elements, inner_pairs, nested_multiplier)?;
+                }
+                Rule::adduct => {
+                    if let Some(prev_symbol) = prev_elem.take() {
+                        add_atoms(elements, prev_symbol, 1, multiplier, formula_name)?;
                     }
-                    let mut inner_pairs = pair.clone().into_inner().next().unwrap();
-                    let mut group_multiplier = 1;
-                    let pairs_vec: Vec<Pair<Rule>> = pairs.clone().into_inner().collect();
-                    if pairs_vec.len() > pair_id + 1 {
-                        let next_pair = &pairs_vec[pair_id + 1];
-                        if next_pair.as_rule() == Rule::index {
-                            group_multiplier = next_pair.as_str().parse::<u8>().unwrap();
+                    let adduct = pair.as_str();
+                    let mut adduct_count = 1;
+                    for inner in pair.into_inner() {
+                        match inner.as_rule() {
+                            Rule::adduct_count => {
+                                adduct_count = inner.as_str().parse::<u32>().map_err(|_| {
+                                    ChemParseError::InvalidIndexFormat(inner.as_str().to_string())
+                                })?;
+                            }
+                            Rule::adduct_formula => {
+                                let adduct_multiplier =
+                                    multiplier.checked_mul(adduct_count).ok_or_else(|| {
+                                        ChemParseError::InvalidIndexFormat(adduct.to_string())
+                                    })?;
+                                self.process_pairs(
+                                    formula_name,
+                                    elements,
+                                    inner,
+                                    adduct_multiplier,
+                                )?;
+                            }
+                            _ => {}
                         }
                     }
-                    self.process_pairs(
-                        formula_name,
-                        elements,
-                        &mut inner_pairs,
-                        multiplier * group_multiplier,
-                    )?;
                 }
                 Rule::index => {
-                    if prev_elem.is_some() {
-                        let index = pair.as_str().parse::<u8>().map_err(|_| {
+                    if let Some(symbol) = prev_elem.take() {
+                        let index = pair.as_str().parse::<u32>().map_err(|_| {
                             ChemParseError::InvalidIndexFormat(pair.as_str().to_string())
                         })?;
-                        let symbol = prev_elem.unwrap().clone();
-                        *elements.entry(symbol).or_insert(0) += index * multiplier;
+                        add_atoms(elements, symbol, index, multiplier, formula_name)?;
                     }
-                    prev_elem = None;
                 }
                 _ => {}
             }
         }
-        if prev_elem.is_some() {
-            let prev_symbol = prev_elem.unwrap().clone();
-            *elements.entry(prev_symbol).or_insert(0) += multiplier;
+        if let Some(prev_symbol) = prev_elem {
+            add_atoms(elements, prev_symbol, 1, multiplier, formula_name)?;
         }
 
         Ok(())
     }
 
-    /// Parses and validates a chemical equation string.
+    /// Parses and validates a chemical equation string, as tolerantly as the [`ParseOptions`]
+    /// of the parser allow.
     pub fn parse_equation(&self, equation: &str) -> Result<Equation, ChemParseError> {
-        let mut equation_parse = ChemParser::parse(Rule::equation, equation).map_err(|_| {
-            ChemParseError::ParsingError(String::from("equation"), String::from(equation))
-        })?;
+        let input = equation;
+        let equation = &normalize_scripts(&self.normalize_equation(equation));
 
-        let mut reactants = HashMap::new();
-        let mut products = HashMap::new();
-        let mut reactants_formulas = HashMap::new();
-        let mut products_formulas = HashMap::new();
+        let mut equation_parse = ChemParser::parse(Rule::equation, equation)
+            .map_err(|e| self.rewritten_syntax_error("equation", input, equation, &e))?;
+        let parsed = equation_parse.peek().map(|pair| pair.as_str());
+        let equation = match parsed {
+            Some(parsed) if self.options.ignore_trailing_text => parsed,
+            _ => equation,
+        };
 
-        let mut parts = equation_parse.next().unwrap().into_inner();
-        let reactant_part = parts.next().unwrap();
-        let product_part = parts.next().unwrap();
+        let mut reactants = Vec::new();
+        let mut products = Vec::new();
 
-        self.process_side(&mut reactants, &mut reactants_formulas, &reactant_part)?;
-        self.process_side(&mut products, &mut products_formulas, &product_part)?;
+        let mut arrow = Arrow::default();
+        let mut conditions = Vec::new();
 
-        Ok(Equation::new(
-            String::from(equation),
-            reactants,
-            products,
-            reactants_formulas,
-            products_formulas,
-        ))
+        let equation_pair = next_pair(&mut equation_parse, "equation")?;
+        let denominator = coefficient_denominator(
+            equation_pair
+                .clone()
+                .into_inner()
+                .filter(|part| matches!(part.as_rule(), Rule::reactants | Rule::products)),
+        )?;
+        for part in equation_pair.into_inner() {
+            match part.as_rule() {
+                Rule::reactants => self.process_side(&mut reactants, &part, denominator)?,
+                Rule::arrow => arrow = Arrow::from_symbol(part.as_str()).unwrap_or_default(),
+                Rule::conditions | Rule::note => conditions.extend(
+                    part.into_inner()
+                        .map(|condition| condition.as_str().trim().to_string()),
+                ),
+                Rule::products => self.process_side(&mut products, &part, denominator)?,
+                _ => {}
+            }
+        }
+
+        let catalysts = conditions
+            .iter()
+            .filter(|condition| self.is_catalyst(condition))
+            .cloned()
+            .collect();
+
+        let mut equation = Equation::from_terms(String::from(equation), reactants, products)
+            .with_arrow(arrow)
+            .with_conditions(conditions, catalysts);
+        equation.denominator = denominator;
+        Ok(equation)
     }
 
+    /// A reaction condition is treated as a catalyst when it is a valid chemical formula as a whole.
+    pub(crate) fn is_catalyst(&self, condition: &str) -> bool {
+        self.parse_formula(condition)
+            .is_ok_and(|formula| formula.formula == condition)
+    }
+
+    /// Adds the terms of one side of an equation, with their coefficients multiplied by the
+    /// common `denominator` of the equation so that they are whole numbers.
     fn process_side(
         &self,
-        side: &mut HashMap<String, u8>,
-        formulas: &mut HashMap<String, Formula>,
+        side: &mut Vec<Term>,
         side_part: &Pair<Rule>,
+        denominator: u32,
     ) -> Result<(), ChemParseError> {
-        let mut prev_coefficient = 1;
+        let mut prev_coefficient = denominator;
         for compound in side_part.clone().into_inner() {
             match compound.as_rule() {
                 Rule::coefficient => {
-                    let coefficient: u8 = compound.as_str().parse().map_err(|_| {
-                        ChemParseError::InvalidCoefficientFormat(compound.as_str().to_string())
-                    })?;
-                    prev_coefficient = coefficient;
+                    let written = compound.as_str();
+                    let coefficient: Coefficient = written.parse()?;
+                    if !denominator.is_multiple_of(coefficient.denominator) {
+                        return Err(ChemParseError::InvalidCoefficientFormat(
+                            written.to_string(),
+                        ));
+                    }
+                    prev_coefficient = coefficient
+                        .numerator
+                        .checked_mul(denominator / coefficient.denominator)
+                        .ok_or_else(|| {
+                            ChemParseError::Overflow(format!("coefficient {}", written))
+                        })?;
+                }
+                Rule::formula | Rule::electron | Rule::photon | Rule::heat => {
+                    let formula = match compound.as_rule() {
+                        Rule::electron => Formula::electron(),
+                        Rule::photon => Formula::photon(),
+                        Rule::heat => Formula::heat(),
+                        _ => self.parse_formula(compound.as_str())?,
+                    };
+                    // Repeated formulas are summed into one coefficient of the equation.
+                    side.iter()
+                        .filter(|term| term.formula.formula == formula.formula)
+                        .try_fold(prev_coefficient, |total, term| {
+                            total.checked_add(term.coefficient)
+                        })
+                        .ok_or_else(|| {
+                            ChemParseError::Overflow(format!("coefficient of {}", formula.formula))
+                        })?;
+                    side.push(Term {
+                        coefficient: prev_coefficient,
+                        formula,
+                        state: None,
+                    });
+                    prev_coefficient = denominator;
                 }
-                Rule::formula => {
-                    let formula_part = compound.clone();
-                    let formula = formula_part.as_str().to_string();
-                    let formula_struct = self.parse_formula(formula_part.as_str())?;
-
-                    side.insert(formula.clone(), prev_coefficient);
-                    formulas.insert(formula, formula_struct);
-                    prev_coefficient = 1;
+                Rule::state => {
+                    if let Some(term) = side.last_mut() {
+                        term.state = compound
+                            .into_inner()
+                            .next()
+                            .and_then(|symbol| State::from_symbol(symbol.as_str()));
+                    }
                 }
                 _ => {}
             }
@@ -242,11 +808,316 @@ impl ChemParser {
         Ok(())
     }
 
+    /// Parses and validates a nuclear equation string, such as `^235U + n -> ^141Ba + ^92Kr + 3n`,
+    /// accepting the tokens of the grammar options of the parser.
+    pub fn parse_nuclear_equation(
+        &self,
+        equation: &str,
+    ) -> Result<NuclearEquation, ChemParseError> {
+        let input = equation;
+        let equation = &normalize_scripts(&self.grammar_options.rewrite(equation));
+
+        let mut equation_parse = ChemParser::parse(Rule::nuclear_equation, equation)
+            .map_err(|e| self.rewritten_syntax_error("nuclear equation", input, equation, &e))?;
+
+        let mut parts = next_pair(&mut equation_parse, "nuclear equation")?.into_inner();
+        let reactants = self.process_nuclear_side(next_pair(&mut parts, "nuclear equation")?)?;
+        let products = self.process_nuclear_side(next_pair(&mut parts, "nuclear equation")?)?;
+
+        Ok(NuclearEquation::new(
+            String::from(equation),
+            reactants,
+            products,
+        ))
+    }
+
+    /// Evaluates an arithmetic expression over formula functions and quantities, such as `mass(H2SO4) * 2 + mass(H2O)`.
+    /// Returns the value in the target unit of a `->` conversion, otherwise in base units (g, mol, L).
+    #[cfg(feature = "std")]
+    pub fn eval(&self, expression: &str) -> Result<f64, ChemParseError> {
+        let expression = &normalize_scripts(expression);
+
+        let mut expression_parse = ChemParser::parse(Rule::calculation, expression)
+            .map_err(|e| syntax_error("expression", expression, &e))?;
+
+        let calculation = next_pair(
+            &mut next_pair(&mut expression_parse, "expression")?.into_inner(),
+            "expression",
+        )?;
+        calc::evaluate(
+            self,
+            calculation,
+            expression,
+            &std::collections::HashMap::new(),
+        )
+        .map(|quantity| quantity.magnitude())
+    }
+
+    /// Scales an empirical formula to the molecular formula whose molar mass, computed with the table
+    /// of this parser, is within `tolerance` of a measured `target_mass`, for example `CH2O` with
+    /// 180.16 g/mol to `C6H12O6`.
+    ///
+    /// Fails with [`ChemParseError::EvaluationError`] unless the target mass is a finite positive
+    /// number and the tolerance a finite number of at least zero, or if no multiple of the formula
+    /// is close enough.
+    #[cfg(feature = "std")]
+    pub fn molecular_from_empirical(
+        &self,
+        empirical: &Formula,
+        target_mass: f64,
+        tolerance: f64,
+    ) -> Result<Formula, ChemParseError> {
+        let empirical = empirical.empirical();
+        let mut empirical_mass = 0.0;
+        for (key, count) in &empirical.elements {
+            let mass = self
+                .periodic_table
+                .get_mass(key)
+                .ok_or_else(|| ChemParseError::InvalidElement(key.clone(), Vec::new()))?;
+            empirical_mass += mass * *count as f64;
+        }
+
+        let error = |reason: String| {
+            ChemParseError::EvaluationError(
+                format!("molecular formula of {}", empirical.formula),
+                reason,
+            )
+        };
+        if !(target_mass.is_finite() && target_mass > 0.0) {
+            return Err(error(String::from(
+                "the target mass must be a positive number",
+            )));
+        }
+        if !(tolerance.is_finite() && tolerance >= 0.0) {
+            return Err(error(String::from(
+                "the tolerance must be a number of at least zero",
+            )));
+        }
+        if empirical_mass <= 0.0 {
+            return Err(error(String::from("the formula has no mass")));
+        }
+        let multiple = (target_mass / empirical_mass).round().max(1.0);
+        if (multiple * empirical_mass - target_mass).abs() > tolerance {
+            return Err(error(format!(
+                "no multiple of {:.3} g/mol is within {} of {} g/mol",
+                empirical_mass, tolerance, target_mass
+            )));
+        }
+        let overflow =
+            || ChemParseError::Overflow(format!("molecular formula of {}", empirical.formula));
+        if multiple > u32::MAX as f64 {
+            return Err(overflow());
+        }
+        let mut molecular = empirical
+            .checked_mul(multiple as u32)
+            .ok_or_else(overflow)?;
+        molecular.mass = empirical_mass * multiple;
+        Ok(molecular)
+    }
+
+    fn process_nuclear_side(
+        &self,
+        side_part: Pair<Rule>,
+    ) -> Result<Vec<(u32, Nuclide)>, ChemParseError> {
+        let mut side = Vec::new();
+        let mut prev_coefficient = 1;
+        for term in side_part.into_inner() {
+            let name = term.as_str();
+            let nuclide = match term.as_rule() {
+                Rule::coefficient => {
+                    prev_coefficient = name
+                        .parse()
+                        .map_err(|_| ChemParseError::InvalidCoefficientFormat(name.to_string()))?;
+                    continue;
+                }
+                Rule::particle => Nuclide::particle(name),
+                Rule::isotope | Rule::nuclear_hydrogen => {
+                    let key = if term.as_rule() == Rule::isotope {
+                        isotope_pair_key(&term)
+                    } else {
+                        name.to_string()
+                    };
+                    parse_isotope_key(&key).and_then(|(symbol, mass_number)| {
+                        self.periodic_table.get_element(symbol).map(|element| {
+                            Nuclide::new(name, mass_number, element.atomic_number as i16)
+                        })
+                    })
+                }
+                _ => continue,
+            }
+            .ok_or_else(|| ChemParseError::InvalidIsotope(name.to_string()))?;
+
+            side.push((prev_coefficient, nuclide));
+            prev_coefficient = 1;
+        }
+        Ok(side)
+    }
+
     fn validate_element(&self, element: &str) -> bool {
         self.periodic_table.get_element(element).is_some()
     }
 }
 
+/// Calculates the molar mass of a formula with each bundled dataset version and reports how it
+/// changes relative to the first version.
+#[cfg(feature = "datasets")]
+pub fn compare_datasets(
+    formula: &str,
+    versions: &[DatasetVersion],
+) -> Result<Vec<DatasetMass>, ChemParseError> {
+    let mut masses: Vec<DatasetMass> = Vec::with_capacity(versions.len());
+    for &version in versions {
+        let mass = ChemParser::with_dataset(version)?
+            .parse_formula(formula)?
+            .mass;
+        let change = masses.first().map_or(0.0, |first| mass - first.mass);
+        masses.push(DatasetMass {
+            version,
+            mass,
+            change,
+        });
+    }
+    Ok(masses)
+}
+
+/// Replaces Unicode subscript digits with ASCII digits and runs of superscript digits and signs
+/// with caret notation, so `SO₄²⁻` becomes `SO4^2-` and `¹³CH₄` becomes `^13CH4`.
+pub fn normalize_scripts(input: &str) -> String {
+    let mut normalized = String::with_capacity(input.len());
+    push_normalized_scripts(input, |c, _| normalized.push(c));
+    normalized
+}
+
+/// Normalizes the scripts of an input and returns, for every byte of the normalized input and its
+/// end, the byte offset in the original input.
+pub(crate) fn normalize_with_offsets(input: &str) -> (String, Vec<usize>) {
+    let mut normalized = String::with_capacity(input.len());
+    let mut offsets = Vec::with_capacity(input.len() + 1);
+    push_normalized_scripts(input, |c, offset| {
+        normalized.push(c);
+        offsets.extend(core::iter::repeat_n(offset, c.len_utf8()));
+    });
+    offsets.push(input.len());
+    (normalized, offsets)
+}
+
+/// Passes the characters of an input with normalized scripts to `push`, together with the byte
+/// offset of the input character each one was written as. See [`normalize_scripts`].
+pub(crate) fn push_normalized_scripts(input: &str, mut push: impl FnMut(char, usize)) {
+    let mut in_superscript = false;
+    for (offset, c) in input.char_indices() {
+        let superscript = match c {
+            '⁰' => Some('0'),
+            '¹' => Some('1'),
+            '²' => Some('2'),
+            '³' => Some('3'),
+            '⁴'..='⁹' => char::from_u32(c as u32 - '⁴' as u32 + '4' as u32),
+            '⁺' => Some('+'),
+            '⁻' => Some('-'),
+            _ => None,
+        };
+        match superscript {
+            Some(ascii) => {
+                if !in_superscript {
+                    push('^', offset);
+                }
+                push(ascii, offset);
+            }
+            None => match c {
+                '₀'..='₉' => {
+                    if let Some(digit) = char::from_u32(c as u32 - '₀' as u32 + '0' as u32) {
+                        push(digit, offset);
+                    }
+                }
+                _ => push(c, offset),
+            },
+        }
+        in_superscript = superscript.is_some();
+    }
+}
+
+/// Adds `index * multiplier` atoms of an element to the composition,
+/// failing instead of overflowing when the count does not fit.
+fn add_atoms<'t>(
+    elements: &mut BTreeMap<&'t str, u32>,
+    symbol: &'t str,
+    index: u32,
+    multiplier: u32,
+    formula_name: &str,
+) -> Result<(), ChemParseError> {
+    let count = elements.entry(symbol).or_insert(0);
+    *count = index
+        .checked_mul(multiplier)
+        .and_then(|atoms| count.checked_add(atoms))
+        .ok_or_else(|| ChemParseError::InvalidIndexFormat(formula_name.to_string()))?;
+    Ok(())
+}
+
+/// Returns the signed value of a parsed `charge` pair, for example -2 for `^2-`,
+/// failing when the magnitude does not fit into an `i32`.
+pub(crate) fn charge_value(pair: &Pair<Rule>) -> Result<i32, ChemParseError> {
+    let mut magnitude = 1;
+    let mut sign = 1;
+    for inner in pair.clone().into_inner() {
+        match inner.as_rule() {
+            Rule::charge_number => {
+                magnitude = inner
+                    .as_str()
+                    .parse()
+                    .map_err(|_| ChemParseError::Overflow(format!("charge {}", pair.as_str())))?
+            }
+            Rule::sign if inner.as_str() == "-" => sign = -1,
+            _ => {}
+        }
+    }
+    Ok(sign * magnitude)
+}
+
+/// Returns the least common multiple of the denominators of the coefficients of `reactants` and
+/// `products` pairs, by which their coefficients are multiplied to store them as whole numbers.
+pub(crate) fn coefficient_denominator<'i>(
+    sides: impl IntoIterator<Item = Pair<'i, Rule>>,
+) -> Result<u32, ChemParseError> {
+    sides
+        .into_iter()
+        .flat_map(|side| side.into_inner())
+        .filter(|pair| pair.as_rule() == Rule::coefficient)
+        .try_fold(1u32, |denominator, pair| {
+            let coefficient: Coefficient = pair.as_str().parse()?;
+            (denominator / gcd(denominator, coefficient.denominator))
+                .checked_mul(coefficient.denominator)
+                .ok_or_else(|| {
+                    ChemParseError::Overflow(String::from("denominator of the coefficients"))
+                })
+        })
+}
+
+/// Returns the next pair of a parse, or a parsing error instead of panicking if the grammar
+/// produced fewer pairs than expected.
+pub(crate) fn next_pair<'i>(
+    pairs: &mut Pairs<'i, Rule>,
+    kind: &str,
+) -> Result<Pair<'i, Rule>, ChemParseError> {
+    pairs.next().ok_or_else(|| {
+        ChemParseError::ParsingError(String::from(kind), String::from("unexpected end of input"))
+    })
+}
+
+/// Returns the composition key of a parsed `isotope` pair, for example `235U` for `^235U`.
+pub(crate) fn isotope_pair_key(pair: &Pair<Rule>) -> String {
+    let mut symbol = "";
+    let mut mass_number = 0;
+    for inner in pair.clone().into_inner() {
+        match inner.as_rule() {
+            Rule::element => symbol = inner.as_str(),
+            Rule::mass_number => mass_number = inner.as_str().parse().unwrap_or(0),
+            _ => {}
+        }
+    }
+    isotope_key(symbol, mass_number)
+}
+
+#[cfg(any(feature = "datasets", not(feature = "std")))]
 impl Default for ChemParser {
     fn default() -> Self {
         Self::new()
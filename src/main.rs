@@ -1,117 +1,1675 @@
-use chemistry_parser::ChemParser;
+mod annotate;
+mod audit;
+mod i18n;
+mod repl;
+
+use crate::annotate::annotate_csv;
+use crate::audit::{audit_entry_schema, csv_line, ErrorLog, Output, OutputFormat};
+use crate::i18n::{
+    render_diagnosis, render_element, render_equation, render_formula, render_highlight_legend,
+    render_notation_clusters, render_nuclear_equation, render_stats, tr, Lang, Msg,
+};
+use chemistry_parser::analysis::Parsed;
+use chemistry_parser::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use chemistry_parser::calc::{find_unit, Calculator, Dimension};
+use chemistry_parser::convert::{read_batch, write_batch, BatchFormat};
+use chemistry_parser::element::{
+    element_symbol, DatasetMass, DatasetVersion, Element, Equation, Formula, Term,
+};
+use chemistry_parser::export::{
+    mass_table, write_mass_table_csv, write_mass_table_json, MASS_DECIMALS,
+};
+use chemistry_parser::input::{Checkpoint, LineReader};
+use chemistry_parser::network::ReactionNetwork;
+use chemistry_parser::options::ParseOptions;
+use chemistry_parser::progress::{NoProgress, ProgressSink};
+use chemistry_parser::redox::Medium;
+use chemistry_parser::render::periodic_grid;
+use chemistry_parser::stats::{EquationStats, NotationCluster, NotationClusters};
+use chemistry_parser::stoichiometry::Amount;
+use chemistry_parser::validation::{Profile, ValidationRules};
+use chemistry_parser::{compare_datasets, debug, ChemParseError, ChemParser};
+use rand::rngs::StdRng;
+use rand::seq::IteratorRandom;
+use rand::SeedableRng;
+use std::env;
+use std::fs::{File, OpenOptions};
+use std::io;
 use std::path::Path;
-use std::{env, fs};
+use std::process;
+use std::time::{Duration, Instant};
+
+/// Path that stands for stdin in the `file` command.
+const STDIN_PATH: &str = "-";
+
+/// Number of equations printed by the `sample` command without `-n`.
+const DEFAULT_SAMPLE_SIZE: usize = 20;
+
+/// Number of lines after which a `file` job saves its checkpoint at the latest.
+const CHECKPOINT_INTERVAL: usize = 1000;
+
+/// Time after which a `file` job saves its checkpoint at the latest.
+const CHECKPOINT_PERIOD: Duration = Duration::from_secs(5);
 
 fn main() {
-    let args: Vec<String> = env::args().collect();
-    let parser = ChemParser::new();
+    let mut args: Vec<String> = env::args().collect();
+    let lang = match take_lang(&mut args) {
+        Ok(lang) => lang,
+        Err(code) => {
+            eprintln!(
+                "{}: {} '{}'",
+                tr(Lang::En, Msg::Error),
+                tr(Lang::En, Msg::UnknownLanguage),
+                code
+            );
+            Lang::En
+        }
+    };
+    let column_option = take_option(&mut args, "--column");
+    let column = column_option
+        .clone()
+        .unwrap_or_else(|| String::from("formula"));
+    let output = take_option(&mut args, "--out");
+    let format = take_option(&mut args, "--format");
+    let session = take_option(&mut args, "--session");
+    let log = take_option(&mut args, "--log");
+    let dataset = take_option(&mut args, "--dataset");
+    let max_coefficient = take_option(&mut args, "--max-coefficient");
+    let medium = take_option(&mut args, "--medium");
+    let given = take_option(&mut args, "--given");
+    let find = take_option(&mut args, "--find");
+    let group = take_option(&mut args, "--group");
+    let search = take_option(&mut args, "--search");
+    let checkpoint = take_option(&mut args, "--checkpoint");
+    let errors_to = take_option(&mut args, "--errors-to");
+    let profile = take_option(&mut args, "--profile");
+    let rules = take_option(&mut args, "--rules");
+    let sample_size = take_option(&mut args, "-n");
+    let element_filter = take_option(&mut args, "--element");
+    let seed = take_option(&mut args, "--seed");
+    let unbalanced_only = take_flag(&mut args, "--unbalanced-only");
+    let copy = take_flag(&mut args, "--copy");
+    let debug_tree = take_flag(&mut args, "--debug-tree");
+    let lenient = take_flag(&mut args, "--lenient");
+    let quiet = take_flag(&mut args, "--quiet");
+    let requested_format = format
+        .as_deref()
+        .map_or(Some(OutputFormat::Plain), OutputFormat::from_name);
+    let output_format = requested_format.unwrap_or_default();
+    let parser = match dataset.as_deref() {
+        Some(name) => match DatasetVersion::from_name(name) {
+            Some(version) => ChemParser::with_dataset(version),
+            None => {
+                eprintln!(
+                    "{}: {} '{}'",
+                    tr(lang, Msg::Error),
+                    tr(lang, Msg::UnknownDataset),
+                    name
+                );
+                return;
+            }
+        },
+        None => ChemParser::try_new(),
+    };
+    let parser = match parser {
+        Ok(parser) if lenient => parser.with_options(ParseOptions::lenient()),
+        Ok(parser) => parser,
+        Err(e) => {
+            eprintln!("{}: {}", tr(lang, Msg::Error), e);
+            return;
+        }
+    };
 
-    if args.len() < 2 {
-        print_help();
-        return;
+    let mut provenance = parser.provenance().with_option("lang", lang.code());
+    for (name, value) in [
+        ("column", column_option.as_deref()),
+        ("out", output.as_deref()),
+        ("format", format.as_deref()),
+        ("session", session.as_deref()),
+        ("dataset", dataset.as_deref()),
+        ("max-coefficient", max_coefficient.as_deref()),
+        ("medium", medium.as_deref()),
+        ("given", given.as_deref()),
+        ("find", find.as_deref()),
+        ("group", group.as_deref()),
+        ("search", search.as_deref()),
+        ("checkpoint", checkpoint.as_deref()),
+        ("errors-to", errors_to.as_deref()),
+        ("profile", profile.as_deref()),
+        ("rules", rules.as_deref()),
+        ("n", sample_size.as_deref()),
+        ("element", element_filter.as_deref()),
+        ("seed", seed.as_deref()),
+        ("log", log.as_deref()),
+    ] {
+        if let Some(value) = value {
+            provenance = provenance.with_option(name, value);
+        }
+    }
+    if unbalanced_only {
+        provenance = provenance.with_option("unbalanced-only", "true");
+    }
+    if lenient {
+        provenance = provenance.with_option("lenient", "true");
+    }
+    if quiet {
+        provenance = provenance.with_option("quiet", "true");
     }
 
-    if args.len() == 2 {
-        match args[1].as_str() {
-            "credits" => {
-                println!("This program was created by Liliia Parashchak, @lillydaystar")
+    let mut command: Vec<&str> = args.iter().skip(1).map(String::as_str).collect();
+    if command == ["file"] {
+        command.push(STDIN_PATH);
+    }
+    let mut out = match Output::new(log.as_deref(), &command, provenance.clone()) {
+        Ok(out) => out,
+        Err(e) => {
+            eprintln!("{}: {}", tr(lang, Msg::Error), e);
+            return;
+        }
+    };
+
+    // Set when a line of the `file` command fails, so that CI jobs can fail on invalid datasets.
+    let mut failed = false;
+    match command.as_slice() {
+        [] => out.print("", &help_text(lang)),
+        ["credits"] => out.print("", tr(lang, Msg::Credits)),
+        ["help"] => out.print("", &help_text(lang)),
+        [input @ ("symbol" | "formula" | "equation" | "file"), ..]
+            if requested_format.is_none() =>
+        {
+            out.error(
+                input,
+                &format!(
+                    "{}: {} '{}'",
+                    tr(lang, Msg::Error),
+                    tr(lang, Msg::UnknownFormat),
+                    format.unwrap_or_default()
+                ),
+            )
+        }
+        ["symbol", element] => match parser.parse_element(element) {
+            Ok(parsed_element) => out.print(
+                element,
+                &element_output(lang, parsed_element, output_format),
+            ),
+            Err(e) => print_error(&mut out, lang, element, e),
+        },
+        ["formula", formula] => match parser.parse_formula(formula) {
+            Ok(parsed_formula) => {
+                out.print(
+                    formula,
+                    &formula_output(&parser, lang, formula, &parsed_formula, output_format),
+                );
+                // Plain output copies the molar mass, other formats the printed result.
+                if output_format == OutputFormat::Plain {
+                    out.set_primary(&format!("{:.*}", MASS_DECIMALS, parsed_formula.mass));
+                }
             }
-            "help" => {
-                print_help();
+            Err(e) => print_error(&mut out, lang, formula, e),
+        },
+        ["compare", first, second] => match parser
+            .parse_formula(first)
+            .and_then(|first| Ok((first, parser.parse_formula(second)?)))
+        {
+            Ok((first, second)) => out.print(
+                &format!("{} {}", first.formula, second.formula),
+                &comparison_text(lang, &first, &second),
+            ),
+            Err(e) => print_error(&mut out, lang, &format!("{} {}", first, second), e),
+        },
+        ["compare-datasets", formula] => match compare_datasets(formula, &DatasetVersion::ALL) {
+            Ok(masses) => out.print(formula, &dataset_comparison_text(&masses)),
+            Err(e) => print_error(&mut out, lang, formula, e),
+        },
+        ["equation", equation] => match parser.parse_equation(equation) {
+            Ok(parsed_equation) => out.print(
+                equation,
+                &equation_output(lang, &parsed_equation, output_format),
+            ),
+            Err(e) => print_error(&mut out, lang, equation, e),
+        },
+        ["check", equation] => match parser.parse_equation(equation) {
+            Ok(parsed_equation) => {
+                let mut text = format!(
+                    "{}: \n{}\n{}",
+                    tr(lang, Msg::Equation),
+                    render_equation(lang, &parsed_equation),
+                    balance_text(lang, parsed_equation.check_equation())
+                );
+                if !parsed_equation.check_equation() {
+                    let diagnosis = parsed_equation.diagnose();
+                    text = format!("{}\n{}", text, render_diagnosis(lang, &diagnosis));
+                }
+                out.print(equation, &text)
             }
-            _ => {
-                eprintln!("Error: Unknown command '{}'", args[1]);
-                print_help();
+            Err(e) => print_error(&mut out, lang, equation, e),
+        },
+        ["balance", equation] => match max_coefficient
+            .as_deref()
+            .map_or(Ok(DEFAULT_MAX_COEFFICIENT), |value| {
+                value
+                    .parse()
+                    .map_err(|_| ChemParseError::InvalidCoefficientFormat(value.to_string()))
+            })
+            .and_then(|max_coefficient| parser.parse_equation(equation)?.balance(max_coefficient))
+        {
+            Ok(Balance::Balanced(balanced)) => {
+                out.print(
+                    equation,
+                    &format!(
+                        "{}: {}\n{}: {}",
+                        tr(lang, Msg::Equation),
+                        balanced.equation,
+                        tr(lang, Msg::Coefficients),
+                        balanced
+                            .reactant_terms
+                            .iter()
+                            .chain(&balanced.product_terms)
+                            .map(|term| format!("{} {}", term.coefficient, term.formula.formula))
+                            .collect::<Vec<_>>()
+                            .join(", ")
+                    ),
+                );
+                out.set_primary(&balanced.equation);
             }
-        }
-    } else if args.len() == 3 {
-        match args[1].as_str() {
-            "symbol" => {
-                let element = &args[2];
-                match parser.parse_element(element) {
-                    Ok(parsed_element) => println!("Element: {}", parsed_element),
-                    Err(e) => eprintln!("Error: {}, try again", e),
-                }
+            Ok(Balance::AlreadyBalanced) => {
+                out.print(
+                    equation,
+                    &format!(
+                        "{}: {}\n{}",
+                        tr(lang, Msg::Equation),
+                        equation,
+                        tr(lang, Msg::Balanced)
+                    ),
+                );
+                out.set_primary(equation);
             }
-            "formula" => {
-                let formula = &args[2];
-                match parser.parse_formula(formula) {
-                    Ok(parsed_formula) => println!("Formula: {}", parsed_formula),
-                    Err(e) => eprintln!("Error: {}, try again", e),
+            Ok(Balance::Independent(reactions)) => out.print(
+                equation,
+                &format!(
+                    "{}\n{}",
+                    tr(lang, Msg::IndependentReactions),
+                    reactions
+                        .iter()
+                        .map(|reaction| format!("  {}", reaction.equation))
+                        .collect::<Vec<_>>()
+                        .join("\n")
+                ),
+            ),
+            Ok(Balance::Impossible(keys)) => out.error(
+                equation,
+                &format!(
+                    "{}: {} {}",
+                    tr(lang, Msg::Error),
+                    tr(lang, Msg::OneSided),
+                    keys.join(", ")
+                ),
+            ),
+            Err(e) => print_error(&mut out, lang, equation, e),
+        },
+        ["balance-redox", equation] => match medium.as_deref() {
+            None | Some("acidic") => {
+                balance_redox(&parser, &mut out, lang, equation, Medium::Acidic)
+            }
+            Some("basic") => balance_redox(&parser, &mut out, lang, equation, Medium::Basic),
+            Some(unknown) => out.error(
+                equation,
+                &format!(
+                    "{}: {} '{}'",
+                    tr(lang, Msg::Error),
+                    tr(lang, Msg::UnknownMedium),
+                    unknown
+                ),
+            ),
+        },
+        ["check-nuclear", equation] => match parser.parse_nuclear_equation(equation) {
+            Ok(parsed_equation) => out.print(
+                equation,
+                &format!(
+                    "{}: \n{}\n{}",
+                    tr(lang, Msg::Equation),
+                    render_nuclear_equation(lang, &parsed_equation),
+                    balance_text(lang, parsed_equation.check_equation())
+                ),
+            ),
+            Err(e) => print_error(&mut out, lang, equation, e),
+        },
+        ["calc"] => run_calculator(
+            &parser,
+            &mut out,
+            lang,
+            session.as_deref(),
+            io::stdin().lines(),
+        ),
+        ["calc", expression] => run_calculator(
+            &parser,
+            &mut out,
+            lang,
+            session.as_deref(),
+            std::iter::once(Ok(expression.to_string())),
+        ),
+        ["repl"] => {
+            let described = repl::run(&parser, |input| {
+                if let Err(e) = describe_input(&parser, &mut out, lang, input) {
+                    print_error(&mut out, lang, input, e);
                 }
+            });
+            if let Err(e) = described {
+                out.error("", &format!("{}: {}", tr(lang, Msg::Error), e));
             }
-            "equation" => {
-                let equation = &args[2];
-                match parser.parse_equation(equation) {
-                    Ok(parsed_equation) => println!("Equation: {}", parsed_equation),
-                    Err(e) => eprintln!("Error: {}, try again", e),
+        }
+        ["file", file_path] => match Profile::from_name(profile.as_deref().unwrap_or("default")) {
+            Some(profile) => {
+                // The size of stdin is not known in advance, so its progress cannot be shown.
+                let mut progress = match *file_path {
+                    STDIN_PATH => Box::new(NoProgress),
+                    _ => progress_sink(false),
+                };
+                match parse_file_equations(
+                    &parser,
+                    &mut out,
+                    file_path,
+                    lang,
+                    &FileOptions {
+                        rules: profile.rules(),
+                        checkpoint_path: checkpoint.as_deref(),
+                        errors_path: errors_to.as_deref(),
+                        format: output_format,
+                        quiet,
+                        report_path: output.as_deref(),
+                    },
+                    progress.as_mut(),
+                ) {
+                    Ok(0) => {}
+                    Ok(_) => failed = true,
+                    Err(e) => {
+                        out.error(file_path, &format!("{}: {}", tr(lang, Msg::Error), e));
+                        failed = true;
+                    }
                 }
             }
-            "check" => {
-                let equation = &args[2];
-                match parser.parse_equation(equation) {
-                    Ok(parsed_equation) => {
-                        println!("Equation: \n{}", parsed_equation);
-                        if parsed_equation.check_equation() {
-                            println!("Equation is balanced.")
-                        } else {
-                            println!("Equation is not balanced.");
-                        }
+            None => out.error(
+                file_path,
+                &format!(
+                    "{}: {} '{}'",
+                    tr(lang, Msg::Error),
+                    tr(lang, Msg::UnknownProfile),
+                    profile.unwrap_or_default()
+                ),
+            ),
+        },
+        ["stats", file_path] => match file_stats(&parser, file_path) {
+            Ok(stats) => out.print(file_path, &render_stats(lang, &stats, parser.get_table())),
+            Err(_) => out.error(
+                file_path,
+                &format!(
+                    "{}: {}: {}",
+                    tr(lang, Msg::Error),
+                    tr(lang, Msg::ReadFileFailed),
+                    file_path
+                ),
+            ),
+        },
+        ["network", file_path] => match reaction_network(&parser, file_path) {
+            Ok(network) => out.print(file_path, network.to_dot().trim_end()),
+            Err(_) => out.error(
+                file_path,
+                &format!(
+                    "{}: {}: {}",
+                    tr(lang, Msg::Error),
+                    tr(lang, Msg::ReadFileFailed),
+                    file_path
+                ),
+            ),
+        },
+        ["notations", file_path] => match notation_clusters(&parser, file_path) {
+            Ok(clusters) => out.print(file_path, &render_notation_clusters(lang, &clusters)),
+            Err(_) => out.error(
+                file_path,
+                &format!(
+                    "{}: {}: {}",
+                    tr(lang, Msg::Error),
+                    tr(lang, Msg::ReadFileFailed),
+                    file_path
+                ),
+            ),
+        },
+        ["sample", file_path] => {
+            let filter = SampleFilter {
+                unbalanced_only,
+                element: element_filter.as_deref(),
+            };
+            let size = sample_size
+                .as_deref()
+                .map_or(Ok(DEFAULT_SAMPLE_SIZE), str::parse);
+            let seed = seed.as_deref().map(str::parse).transpose();
+            match (size, seed) {
+                (Ok(size), Ok(seed)) => {
+                    if let Err(e) =
+                        sample_equations(&parser, &mut out, file_path, lang, size, seed, &filter)
+                    {
+                        out.error(file_path, &format!("{}: {}", tr(lang, Msg::Error), e));
                     }
-                    Err(e) => eprintln!("Error: {}, try again", e),
+                }
+                (Err(e), _) | (_, Err(e)) => {
+                    out.error(file_path, &format!("{}: {}", tr(lang, Msg::Error), e))
                 }
             }
-            "file" => {
-                let file_path = &args[2];
-                if let Err(e) = parse_file_equations(&parser, file_path) {
-                    eprintln!("Error: {}", e);
+        }
+        ["annotate-csv", input] => match annotate_csv(
+            &parser,
+            input,
+            &column,
+            output.as_deref(),
+            progress_sink(output.is_some()).as_mut(),
+        ) {
+            Ok(rows) => out.record(input, &format!("{} rows", rows), true),
+            Err(e) => out.error(input, &format!("{}: {}", tr(lang, Msg::Error), e)),
+        },
+        ["mass-table", file_path] => match write_mass_table(
+            &parser,
+            &mut out,
+            lang,
+            file_path,
+            format.as_deref(),
+            output.as_deref(),
+        ) {
+            Ok(formulas) => out.record(file_path, &format!("{} formulas", formulas), true),
+            Err(e) => out.error(file_path, &format!("{}: {}", tr(lang, Msg::Error), e)),
+        },
+        ["stoich", equation] => match given.as_deref() {
+            Some(given) => match stoichiometry_text(
+                &parser,
+                lang,
+                equation,
+                given,
+                find.as_deref(),
+                max_coefficient.as_deref(),
+            ) {
+                Ok(text) => out.print(equation, &text),
+                Err(e) => print_error(&mut out, lang, equation, e),
+            },
+            None => out.error(
+                equation,
+                &format!("{}: {}", tr(lang, Msg::Error), tr(lang, Msg::MissingGiven)),
+            ),
+        },
+        ["rewrite", file_path] => match rules.as_deref() {
+            Some(rules_path) => {
+                match rewrite_file(&parser, &mut out, lang, file_path, rules_path) {
+                    Ok(lines) => out.record(file_path, &format!("{} lines", lines), true),
+                    Err(e) => out.error(file_path, &format!("{}: {}", tr(lang, Msg::Error), e)),
                 }
             }
-            _ => {
-                eprintln!("Error: Unknown command '{}'", args[1]);
-                print_help();
+            None => out.error(
+                file_path,
+                &format!("{}: {}", tr(lang, Msg::Error), tr(lang, Msg::MissingRules)),
+            ),
+        },
+        ["convert-file", input, output] => {
+            match convert_file(&parser, &mut out, lang, input, output, format.as_deref()) {
+                Ok(equations) => out.record(input, &format!("{} equations", equations), true),
+                Err(e) => out.error(input, &format!("{}: {}", tr(lang, Msg::Error), e)),
             }
         }
-    } else {
-        eprintln!("Error: Unknown command '{}'", args[1]);
-        print_help();
+        ["analyze", input] => match serde_json::to_string_pretty(&parser.analyze(input)) {
+            Ok(json) => out.print(input, &json),
+            Err(e) => out.error(input, &format!("{}: {}", tr(lang, Msg::Error), e)),
+        },
+        ["schema"] => {
+            let mut capabilities = parser.capabilities();
+            capabilities.commands = COMMANDS
+                .iter()
+                .map(|(command, _)| command.trim().to_string())
+                .collect();
+            capabilities
+                .schemas
+                .insert(String::from("audit_entry"), audit_entry_schema());
+            match serde_json::to_string_pretty(&capabilities) {
+                Ok(json) => out.print("", &json),
+                Err(e) => out.error("", &format!("{}: {}", tr(lang, Msg::Error), e)),
+            }
+        }
+        ["table"] if group.is_some() || search.is_some() => {
+            match element_search(&parser, group.as_deref(), search.as_deref()) {
+                Ok(elements) => out.print(
+                    search.as_deref().unwrap_or_default(),
+                    &element_search_text(&parser, lang, &elements),
+                ),
+                Err(e) => print_error(&mut out, lang, search.as_deref().unwrap_or_default(), e),
+            }
+        }
+        ["table"] => out.print("", &periodic_grid(parser.get_table(), &[])),
+        ["table", "--highlight", formula] => match parser.parse_formula(formula) {
+            Ok(parsed_formula) => {
+                let symbols: Vec<&str> = parsed_formula
+                    .elements
+                    .keys()
+                    .map(|key| element_symbol(key))
+                    .collect();
+                out.print(
+                    formula,
+                    &format!(
+                        "{}\n\n{}",
+                        periodic_grid(parser.get_table(), &symbols),
+                        render_highlight_legend(lang, parser.get_table(), &parsed_formula)
+                    ),
+                );
+            }
+            Err(e) => print_error(&mut out, lang, formula, e),
+        },
+        [input] if debug_tree => match debug::tree(debug::input_rule(input), input) {
+            Ok(tree) => out.print(input, &tree),
+            Err(e) => print_error(&mut out, lang, input, e),
+        },
+        [input] => {
+            if describe_input(&parser, &mut out, lang, input).is_err() {
+                unknown_command(&mut out, lang, input);
+            }
+        }
+        [command, ..] => unknown_command(&mut out, lang, command),
+    }
+
+    if copy {
+        if let Some(primary) = out.primary().map(str::to_string) {
+            copy_result(&mut out, lang, &primary);
+        }
+    }
+    if failed {
+        process::exit(1);
+    }
+}
+
+/// Prints an element, formula or equation given without a command, detecting which it is.
+fn describe_input(
+    parser: &ChemParser,
+    out: &mut Output,
+    lang: Lang,
+    input: &str,
+) -> Result<(), ChemParseError> {
+    match parser.parse_any(input)? {
+        Parsed::Element(element) => out.print(input, &element_text(lang, &element)),
+        Parsed::Formula(formula) => {
+            out.print(input, &formula_text(parser, lang, input, &formula));
+            out.set_primary(&format!("{:.*}", MASS_DECIMALS, formula.mass));
+        }
+        Parsed::Equation(equation) => out.print(input, &equation_text(lang, &equation)),
+    }
+    Ok(())
+}
+
+/// Copies the main result of a command to the system clipboard.
+#[cfg(feature = "clipboard")]
+fn copy_result(out: &mut Output, lang: Lang, result: &str) {
+    if let Err(e) = arboard::Clipboard::new().and_then(|mut clipboard| clipboard.set_text(result)) {
+        out.error(
+            result,
+            &format!(
+                "{}: {}: {}",
+                tr(lang, Msg::Error),
+                tr(lang, Msg::CopyFailed),
+                e
+            ),
+        );
+    }
+}
+
+/// Reports that the main result cannot be copied without the `clipboard` feature.
+#[cfg(not(feature = "clipboard"))]
+fn copy_result(out: &mut Output, lang: Lang, result: &str) {
+    out.error(
+        result,
+        &format!(
+            "{}: {}",
+            tr(lang, Msg::Error),
+            tr(lang, Msg::ClipboardUnavailable)
+        ),
+    );
+}
+
+/// Prints the half-reactions and the balanced form of a redox equation.
+fn balance_redox(
+    parser: &ChemParser,
+    out: &mut Output,
+    lang: Lang,
+    equation: &str,
+    medium: Medium,
+) {
+    match parser
+        .parse_equation(equation)
+        .and_then(|parsed| parser.balance_redox(&parsed, medium))
+    {
+        Ok(redox) => {
+            out.print(
+                equation,
+                &format!(
+                    "{}: {}\n{}: {}\n{}: {}",
+                    tr(lang, Msg::Oxidation),
+                    redox.oxidation.equation,
+                    tr(lang, Msg::Reduction),
+                    redox.reduction.equation,
+                    tr(lang, Msg::Equation),
+                    redox.balanced.equation
+                ),
+            );
+            out.set_primary(&redox.balanced.equation);
+        }
+        Err(e) => print_error(out, lang, equation, e),
     }
 }
 
-fn print_help() {
-    println!("Use following commands:");
-    println!("  help                            Show all commands");
-    println!("  credits                         Show credits");
-    println!("  symbol <element-symbol>         Parse the element and print information about it");
-    println!("  formula <chemical-formula>      Parse the formula and print information about it");
-    println!(
-        "  equation <chemical-equation>    Parse the chemical equation and print its formulas"
+fn unknown_command(out: &mut Output, lang: Lang, command: &str) {
+    out.error(
+        command,
+        &format!(
+            "{}: {} '{}'",
+            tr(lang, Msg::Error),
+            tr(lang, Msg::UnknownCommand),
+            command
+        ),
     );
-    println!("  check <chemical-equation>       Check if the chemical equation is balanced");
-    println!(
-        "  file <file-path>                Parse the file with chemical equations and solve them"
+    println!("{}", help_text(lang));
+}
+
+/// Removes the `--lang <code>` or `--lang=<code>` option from the arguments and returns the selected language.
+/// Without the option the language is detected from the environment.
+fn take_lang(args: &mut Vec<String>) -> Result<Lang, String> {
+    match take_option(args, "--lang") {
+        Some(code) => Lang::from_code(&code).ok_or(code),
+        None => Ok(Lang::detect()),
+    }
+}
+
+/// Removes the `<name>` flag from the arguments and returns whether it was given.
+fn take_flag(args: &mut Vec<String>, name: &str) -> bool {
+    match args.iter().position(|arg| arg == name) {
+        Some(position) => {
+            args.remove(position);
+            true
+        }
+        None => false,
+    }
+}
+
+/// Removes the `<name> <value>` or `<name>=<value>` option from the arguments and returns its value.
+fn take_option(args: &mut Vec<String>, name: &str) -> Option<String> {
+    let prefix = format!("{}=", name);
+    let position = args
+        .iter()
+        .position(|arg| arg == name || arg.starts_with(&prefix))?;
+
+    let option = args.remove(position);
+    match option.strip_prefix(&prefix) {
+        Some(value) => Some(value.to_string()),
+        None if position < args.len() => Some(args.remove(position)),
+        None => Some(String::new()),
+    }
+}
+
+/// Commands and options of the CLI with their descriptions, in the order of the help.
+const COMMANDS: &[(&str, Msg)] = &[
+    ("help", Msg::HelpHelp),
+    ("credits", Msg::HelpCredits),
+    ("symbol <element-symbol>", Msg::HelpSymbol),
+    ("formula <chemical-formula>", Msg::HelpFormula),
+    ("compare <formula> <formula>", Msg::HelpCompare),
+    (
+        "compare-datasets <chemical-formula>",
+        Msg::HelpCompareDatasets,
+    ),
+    ("equation <chemical-equation>", Msg::HelpEquation),
+    ("check <chemical-equation>", Msg::HelpCheck),
+    ("balance <chemical-equation>", Msg::HelpBalance),
+    ("  [--max-coefficient <n>]", Msg::HelpBalanceMaxCoefficient),
+    ("balance-redox <chemical-equation>", Msg::HelpBalanceRedox),
+    ("  [--medium <acidic|basic>]", Msg::HelpBalanceRedoxMedium),
+    ("stoich <chemical-equation>", Msg::HelpStoich),
+    ("  --given <species=amount,...>", Msg::HelpStoichGiven),
+    ("  [--find <species>]", Msg::HelpStoichFind),
+    ("check-nuclear <nuclear-equation>", Msg::HelpCheckNuclear),
+    ("analyze <input>", Msg::HelpAnalyze),
+    ("schema", Msg::HelpSchema),
+    ("calc <expression>", Msg::HelpCalc),
+    ("calc", Msg::HelpCalcInteractive),
+    ("  [--session <file-path>]", Msg::HelpCalcSession),
+    ("repl", Msg::HelpRepl),
+    ("file [<file-path>|-]", Msg::HelpFile),
+    ("  [--checkpoint <file-path>]", Msg::HelpCheckpoint),
+    ("  [--errors-to <file-path>]", Msg::HelpErrorsTo),
+    ("  [--profile <name>]", Msg::HelpProfile),
+    ("  [--quiet]", Msg::HelpQuiet),
+    ("  [--out <csv-path>]", Msg::HelpFileReport),
+    ("stats <file-path>", Msg::HelpStats),
+    ("notations <file-path>", Msg::HelpNotations),
+    ("network <file-path>", Msg::HelpNetwork),
+    ("sample <file-path>", Msg::HelpSample),
+    ("  [-n <count>] [--unbalanced-only]", Msg::HelpSampleOptions),
+    ("  [--element <symbol>] [--seed <n>]", Msg::HelpSampleFilter),
+    ("annotate-csv <csv-path>", Msg::HelpAnnotateCsv),
+    (
+        "  [--column <name>] [--out <csv-path>]",
+        Msg::HelpAnnotateCsvOptions,
+    ),
+    ("mass-table <file-path>", Msg::HelpMassTable),
+    (
+        "  [--format <csv|json>] [--out <path>]",
+        Msg::HelpMassTableOptions,
+    ),
+    ("rewrite <file-path> --rules <path>", Msg::HelpRewrite),
+    ("convert-file <in-path> <out-path>", Msg::HelpConvertFile),
+    (
+        "  [--format <text|csv|jsonl|cml|md>]",
+        Msg::HelpConvertFileOptions,
+    ),
+    ("table", Msg::HelpTable),
+    (
+        "table [--group <n>] [--search <query>]",
+        Msg::HelpTableSearch,
+    ),
+    (
+        "table --highlight <chemical-formula>",
+        Msg::HelpTableHighlight,
+    ),
+    ("--lang <en|uk>", Msg::HelpLang),
+    (
+        "--dataset <iupac-2013|iupac-2021|abridged-2021>",
+        Msg::HelpDataset,
+    ),
+    ("--log <file-path>", Msg::HelpLog),
+    ("--copy", Msg::HelpCopy),
+    ("--debug-tree", Msg::HelpDebugTree),
+    ("--lenient", Msg::HelpLenient),
+    ("--format <plain|json|csv>", Msg::HelpFormat),
+];
+
+fn help_text(lang: Lang) -> String {
+    let mut lines = vec![tr(lang, Msg::HelpHeader).to_string()];
+    for &(command, description) in COMMANDS {
+        lines.push(format!("  {:<40}{}", command, tr(lang, description)));
+    }
+    lines.join("\n")
+}
+
+fn element_text(lang: Lang, element: &Element) -> String {
+    format!(
+        "{}: {}",
+        tr(lang, Msg::Element),
+        render_element(lang, element)
+    )
+}
+
+/// Describes a formula with its common name when it has one.
+fn formula_text(parser: &ChemParser, lang: Lang, input: &str, formula: &Formula) -> String {
+    let result = format!(
+        "{}: {}",
+        tr(lang, Msg::Formula),
+        render_formula(lang, formula)
     );
+    let name = parser.pretty_name(input);
+    if name != input {
+        format!("{}\n{}: {}", result, tr(lang, Msg::Name), name)
+    } else {
+        result
+    }
 }
 
-fn parse_file_equations(parser: &ChemParser, file_path: &str) -> anyhow::Result<(), String> {
-    let path = Path::new(file_path);
+/// Describes an element in the requested format.
+fn element_output(lang: Lang, element: &Element, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Plain => element_text(lang, element),
+        OutputFormat::Json => serde_json::to_string_pretty(element).unwrap_or_default(),
+        OutputFormat::Csv => [
+            csv_line(&["symbol", "name", "atomic_number", "atomic_mass"]),
+            csv_line(&[
+                element.symbol.clone(),
+                element.name.clone(),
+                element.atomic_number.to_string(),
+                element.atomic_mass.to_string(),
+            ]),
+        ]
+        .join("\n"),
+    }
+}
 
-    let content =
-        fs::read_to_string(path).map_err(|_| format!("Failed to read file: {}", file_path))?;
+/// Describes a formula in the requested format.
+fn formula_output(
+    parser: &ChemParser,
+    lang: Lang,
+    input: &str,
+    formula: &Formula,
+    format: OutputFormat,
+) -> String {
+    match format {
+        OutputFormat::Plain => formula_text(parser, lang, input, formula),
+        OutputFormat::Json => serde_json::to_string_pretty(formula).unwrap_or_default(),
+        OutputFormat::Csv => [
+            csv_line(&["formula", "molar_mass", "charge"]),
+            csv_line(&[
+                formula.formula.clone(),
+                format!("{:.*}", MASS_DECIMALS, formula.mass),
+                formula.charge.to_string(),
+            ]),
+        ]
+        .join("\n"),
+    }
+}
 
-    for (i, line) in content.lines().enumerate() {
-        match parser.parse_equation(line) {
-            Ok(parsed_equation) => {
-                println!("{}. {}", i + 1, parsed_equation);
-                if parsed_equation.check_equation() {
-                    println!("Equation is balanced.")
+/// Describes an equation in the requested format.
+fn equation_output(lang: Lang, equation: &Equation, format: OutputFormat) -> String {
+    match format {
+        OutputFormat::Plain => equation_text(lang, equation),
+        OutputFormat::Json => serde_json::to_string_pretty(equation).unwrap_or_default(),
+        OutputFormat::Csv => [
+            csv_line(&["equation", "balanced"]),
+            csv_line(&[
+                equation.equation.clone(),
+                equation.check_equation().to_string(),
+            ]),
+        ]
+        .join("\n"),
+    }
+}
+
+fn equation_text(lang: Lang, equation: &Equation) -> String {
+    format!(
+        "{}: {}",
+        tr(lang, Msg::Equation),
+        render_equation(lang, equation)
+    )
+}
+
+/// Describes whether two formulas have the same composition and how their atoms and masses differ.
+fn comparison_text(lang: Lang, first: &Formula, second: &Formula) -> String {
+    let comparison = first.compare(second);
+    let mut lines = vec![
+        format!(
+            "{}: {}, {}",
+            tr(
+                lang,
+                if comparison.same_composition {
+                    Msg::SameComposition
                 } else {
-                    println!("Equation is not balanced.");
+                    Msg::DifferentComposition
+                }
+            ),
+            first.formula,
+            second.formula
+        ),
+        format!(
+            "{}: {:+.*}",
+            tr(lang, Msg::MassDifference),
+            MASS_DECIMALS,
+            comparison.mass_difference
+        ),
+    ];
+    lines.extend(comparison.differences.iter().map(|difference| {
+        format!(
+            "  {}: {} → {}",
+            difference.element, difference.first, difference.second
+        )
+    }));
+    if comparison.charge_difference != 0 {
+        lines.push(format!(
+            "  {}: {} → {}",
+            tr(lang, Msg::Charge),
+            first.charge,
+            second.charge
+        ));
+    }
+    lines.join("\n")
+}
+
+fn dataset_comparison_text(masses: &[DatasetMass]) -> String {
+    masses
+        .iter()
+        .map(|entry| {
+            format!(
+                "{}: {:.4} ({:+.4})",
+                entry.version.name(),
+                entry.mass,
+                entry.change
+            )
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+fn print_error(out: &mut Output, lang: Lang, input: &str, error: ChemParseError) {
+    let mut message = format!(
+        "{}: {}, {}",
+        tr(lang, Msg::Error),
+        error,
+        tr(lang, Msg::TryAgain)
+    );
+    if let Some(snippet) = error.snippet() {
+        message = format!("{}\n{}", message, snippet);
+    }
+    out.fail(input, &message, &error);
+}
+
+fn balance_text(lang: Lang, balanced: bool) -> &'static str {
+    if balanced {
+        tr(lang, Msg::Balanced)
+    } else {
+        tr(lang, Msg::NotBalanced)
+    }
+}
+
+/// Executes calculator lines, such as `let aspirin = C9H8O4` or `mass(aspirin) * 3`.
+/// With a session file, variables are loaded before the first line and saved after each assignment.
+fn run_calculator(
+    parser: &ChemParser,
+    out: &mut Output,
+    lang: Lang,
+    session: Option<&str>,
+    lines: impl Iterator<Item = io::Result<String>>,
+) {
+    let mut calculator = Calculator::new(parser);
+    if let Some(path) = session.filter(|path| Path::new(path).exists()) {
+        if let Err(e) = calculator.load(path) {
+            out.error(path, &format!("{}: {}", tr(lang, Msg::Error), e));
+            return;
+        }
+    }
+
+    for line in lines.map_while(Result::ok) {
+        let line = line.trim();
+        if line == "exit" || line == "quit" {
+            break;
+        }
+        if line.is_empty() {
+            continue;
+        }
+
+        match calculator.execute(line) {
+            Ok(Some(quantity)) => {
+                out.print(line, &format!("{}: {:.4}", tr(lang, Msg::Result), quantity))
+            }
+            Ok(None) => {
+                out.record(line, "", true);
+                if let Some(path) = session {
+                    if let Err(e) = calculator.save(path) {
+                        out.error(path, &format!("{}: {}", tr(lang, Msg::Error), e));
+                    }
                 }
             }
-            Err(e) => eprintln!("Error on line {}: {}", i + 1, e),
+            Err(e) => print_error(out, lang, line, e),
         }
     }
+}
 
+/// Runs an equation, balanced first if needed, with the given amounts of its reactants, such as
+/// `H2=4g, O2=1mol`, and describes its limiting reactant, the reactants left over and the amount
+/// formed of `find` or of every product.
+fn stoichiometry_text(
+    parser: &ChemParser,
+    lang: Lang,
+    equation: &str,
+    given: &str,
+    find: Option<&str>,
+    max_coefficient: Option<&str>,
+) -> Result<String, ChemParseError> {
+    let max_coefficient = max_coefficient.map_or(Ok(DEFAULT_MAX_COEFFICIENT), |value| {
+        value
+            .parse()
+            .map_err(|_| ChemParseError::InvalidCoefficientFormat(value.to_string()))
+    })?;
+    let parsed = parser.parse_equation(equation)?;
+    let equation = match parsed.balance(max_coefficient)? {
+        Balance::Balanced(balanced) => balanced,
+        Balance::AlreadyBalanced => parsed,
+        Balance::Independent(_) | Balance::Impossible(_) => {
+            return Err(ChemParseError::BalancingError(
+                parsed.equation,
+                String::from("no single balanced form"),
+            ))
+        }
+    };
+
+    let mut amounts = Vec::new();
+    for amount in given.split(',') {
+        amounts.push(given_moles(parser, amount)?);
+    }
+    let amounts: Vec<(&str, f64)> = amounts
+        .iter()
+        .map(|(species, moles)| (species.as_str(), *moles))
+        .collect();
+    let analysis = equation.limiting_reactant(&amounts)?;
+
+    let amount_text = |amount: &Amount| {
+        format!(
+            "{}: {:.4} mol, {:.4} g",
+            amount.species, amount.moles, amount.mass
+        )
+    };
+    let mut lines = vec![
+        format!("{}: {}", tr(lang, Msg::Equation), equation.equation),
+        format!("{}: {}", tr(lang, Msg::LimitingReactant), analysis.reactant),
+    ];
+    match find {
+        Some(find) => {
+            // The limiting reactant is used up, so all of it takes part in the reaction.
+            let limiting = amounts
+                .iter()
+                .find(|(species, _)| *species == analysis.reactant)
+                .map_or(0.0, |(_, moles)| *moles);
+            let moles = equation.moles_of(find, (&analysis.reactant, limiting))?;
+            let formula = parser.parse_formula(find.trim())?;
+            lines.push(amount_text(&Amount {
+                species: find.trim().to_string(),
+                moles,
+                mass: moles * formula.mass,
+            }));
+        }
+        None => lines.extend(analysis.products.iter().map(amount_text)),
+    }
+    let excess: Vec<String> = analysis
+        .excess
+        .iter()
+        .filter(|amount| amount.moles > 0.0)
+        .map(amount_text)
+        .collect();
+    if !excess.is_empty() {
+        lines.push(format!("{}:", tr(lang, Msg::LeftOver)));
+        lines.extend(excess.iter().map(|line| format!("  {}", line)));
+    }
+    Ok(lines.join("\n"))
+}
+
+/// Reads an amount of a reactant, such as `H2=4g` or `2H2=1.5mol`, as the species and its moles.
+/// A coefficient before the species is ignored and masses are converted with its molar mass.
+fn given_moles(parser: &ChemParser, amount: &str) -> Result<(String, f64), ChemParseError> {
+    let invalid = |reason: &str| {
+        ChemParseError::ParsingError(format!("amount \"{}\"", amount.trim()), reason.to_string())
+    };
+    let (species, quantity) = amount
+        .split_once('=')
+        .ok_or_else(|| invalid("expected <species>=<amount><unit>"))?;
+    let species = species
+        .trim()
+        .trim_start_matches(|c: char| c.is_ascii_digit());
+    let quantity = quantity.trim();
+    let unit_start = quantity
+        .find(|c: char| c.is_alphabetic())
+        .ok_or_else(|| invalid("missing unit, such as g or mol"))?;
+    let value: f64 = quantity[..unit_start]
+        .trim()
+        .parse()
+        .map_err(|_| invalid("the amount is not a number"))?;
+    let unit = find_unit(&quantity[unit_start..])
+        .filter(|unit| [Dimension::MASS, Dimension::AMOUNT].contains(&unit.dimension))
+        .ok_or_else(|| invalid("unit must be a mass or an amount, such as g or mol"))?;
+    let value = value * unit.factor;
+    let moles = if unit.dimension == Dimension::MASS {
+        value / parser.parse_formula(species)?.mass
+    } else {
+        value
+    };
+    if !(moles.is_finite() && moles > 0.0) {
+        return Err(invalid("the amount must be a positive number"));
+    }
+    Ok((species.to_string(), moles))
+}
+
+/// Selects the elements of a group and matching a search expression, such as `mass>200`.
+fn element_search<'a>(
+    parser: &'a ChemParser,
+    group: Option<&str>,
+    search: Option<&str>,
+) -> Result<Vec<&'a Element>, ChemParseError> {
+    let mut query = parser.get_table().query();
+    if let Some(group) = group {
+        let number = group.parse().map_err(|_| {
+            ChemParseError::ParsingError(
+                format!("group \"{}\"", group),
+                String::from("expected a number from 1 to 18"),
+            )
+        })?;
+        query = query.group(number);
+    }
+    if let Some(search) = search {
+        query = query.search(search)?;
+    }
+    Ok(query.elements())
+}
+
+/// Draws the periodic table with the found elements highlighted, followed by a list of them.
+fn element_search_text(parser: &ChemParser, lang: Lang, elements: &[&Element]) -> String {
+    let symbols: Vec<&str> = elements
+        .iter()
+        .map(|element| element.symbol.as_str())
+        .collect();
+    let mut lines = vec![
+        periodic_grid(parser.get_table(), &symbols),
+        String::new(),
+        format!("{}: {}", tr(lang, Msg::ElementsFound), elements.len()),
+    ];
+    lines.extend(elements.iter().map(|element| {
+        format!(
+            "  {:>3} {:<3} {:<14} {}",
+            element.atomic_number, element.symbol, element.name, element.atomic_mass
+        )
+    }));
+    lines.join("\n")
+}
+
+/// Options of the `file` command.
+struct FileOptions<'a> {
+    rules: ValidationRules,
+    checkpoint_path: Option<&'a str>,
+    errors_path: Option<&'a str>,
+    format: OutputFormat,
+    quiet: bool,
+    report_path: Option<&'a str>,
+}
+
+fn parse_file_equations(
+    parser: &ChemParser,
+    out: &mut Output,
+    file_path: &str,
+    lang: Lang,
+    options: &FileOptions,
+    progress: &mut dyn ProgressSink,
+) -> anyhow::Result<usize, String> {
+    let FileOptions {
+        rules,
+        checkpoint_path,
+        errors_path,
+        format,
+        quiet,
+        report_path,
+    } = *options;
+    let read_failed = || format!("{}: {}", tr(lang, Msg::ReadFileFailed), file_path);
+    let checkpoint_failed = |path: &str| format!("{}: {}", tr(lang, Msg::CheckpointFailed), path);
+    let write_failed = |path: &str| format!("{}: {}", tr(lang, Msg::WriteFileFailed), path);
+    let mut reader = if file_path == STDIN_PATH {
+        LineReader::stdin()
+    } else {
+        LineReader::open_file(file_path).map_err(|_| read_failed())?
+    };
+
+    let mut checkpoint = Checkpoint::new(file_path);
+    if let Some(path) = checkpoint_path {
+        match Checkpoint::load(path).map_err(|_| checkpoint_failed(path))? {
+            Some(saved) if saved.matches_input(file_path) => {
+                reader.resume(&saved).map_err(|_| read_failed())?;
+                checkpoint = saved;
+            }
+            Some(_) => return Err(checkpoint_failed(path)),
+            None => {}
+        }
+    }
+    // A resumed job appends to the output of the interrupted one, which already has the header.
+    // JSON lines have none, so that every line of the output parses, and neither has quiet output
+    // or output that goes to a report.
+    if checkpoint.line == 0 && report_path.is_none() {
+        match format {
+            OutputFormat::Plain if quiet => {}
+            OutputFormat::Plain => out.print(file_path, &format!("# {}", out.provenance())),
+            OutputFormat::Json => {}
+            OutputFormat::Csv => out.print(
+                file_path,
+                &csv_line(&["line", "equation", "balanced", "warnings"]),
+            ),
+        }
+    }
+    let mut errors = match errors_path {
+        Some(path) => {
+            Some(ErrorLog::open(path, checkpoint.line > 0).map_err(|_| write_failed(path))?)
+        }
+        None => None,
+    };
+    let mut report = match report_path {
+        Some(path) => Some(open_report(path, checkpoint.line > 0).map_err(|_| write_failed(path))?),
+        None => None,
+    };
+    let (mut lines, mut failed) = (0, 0);
+    let (mut saved_line, mut saved_at) = (checkpoint.line, Instant::now());
+    progress.start(reader.len());
+
+    for line in reader.equation_lines() {
+        let Ok(line) = line else {
+            if let Some(path) = checkpoint_path {
+                save_checkpoint(&checkpoint, path, report.as_mut())
+                    .map_err(|_| checkpoint_failed(path))?;
+            }
+            return Err(read_failed());
+        };
+        let parsed = parser.validate_equation(&line.text, &rules);
+        progress.advance(line.end, parsed.is_err());
+        lines += 1;
+        if parsed.is_err() {
+            failed += 1;
+        }
+        if let Some(report) = report.as_mut() {
+            let row = match &parsed {
+                Ok(validation) => {
+                    let equation = &validation.equation;
+                    let side_mass = |terms: &[Term]| -> f64 {
+                        terms
+                            .iter()
+                            .map(|term| equation.coefficient(term).value() * term.formula.mass)
+                            .sum()
+                    };
+                    [
+                        String::from("true"),
+                        equation.check_equation().to_string(),
+                        format!("{:.*}", MASS_DECIMALS, side_mass(&equation.reactant_terms)),
+                        format!("{:.*}", MASS_DECIMALS, side_mass(&equation.product_terms)),
+                        String::new(),
+                    ]
+                }
+                Err(e) => [
+                    String::from("false"),
+                    String::new(),
+                    String::new(),
+                    String::new(),
+                    e.to_string(),
+                ],
+            };
+            report
+                .write_record(
+                    [&line.number.to_string(), &line.text]
+                        .into_iter()
+                        .chain(&row),
+                )
+                .map_err(|_| write_failed(report_path.unwrap_or_default()))?;
+            match &parsed {
+                Ok(_) => out.record(&line.text, &row.join(","), true),
+                Err(e) => out.record_failure(&line.text, &e.to_string(), e),
+            }
+        } else {
+            match parsed {
+                Ok(validation) => {
+                    let equation = &validation.equation;
+                    let warnings: Vec<String> = validation
+                        .warnings
+                        .iter()
+                        .map(ToString::to_string)
+                        .collect();
+                    let result = match format {
+                        OutputFormat::Plain if quiet => format!("{}. {}", line.number, line.text),
+                        OutputFormat::Plain => format!(
+                            "{}. {}\n{}{}",
+                            line.number,
+                            render_equation(lang, equation),
+                            balance_text(lang, equation.check_equation()),
+                            warnings
+                                .iter()
+                                .map(|warning| format!("\n{}: {}", tr(lang, Msg::Warning), warning))
+                                .collect::<String>()
+                        ),
+                        OutputFormat::Json => serde_json::json!({
+                            "line": line.number,
+                            "equation": equation,
+                            "balanced": equation.check_equation(),
+                            "warnings": warnings,
+                        })
+                        .to_string(),
+                        OutputFormat::Csv => csv_line(&[
+                            line.number.to_string(),
+                            equation.equation.clone(),
+                            equation.check_equation().to_string(),
+                            warnings.join("; "),
+                        ]),
+                    };
+                    if quiet && equation.check_equation() {
+                        out.record(&line.text, &result, true);
+                    } else {
+                        out.print(&line.text, &result);
+                    }
+                }
+                Err(e) => {
+                    let message = format!("{} {}: {}", tr(lang, Msg::ErrorOnLine), line.number, e);
+                    match errors.as_mut() {
+                        Some(errors) => {
+                            out.record_failure(&line.text, &message, &e);
+                            errors
+                                .write(line.number, &line.text, &e)
+                                .map_err(|_| write_failed(errors_path.unwrap_or_default()))?;
+                        }
+                        None => out.fail(&line.text, &message, &e),
+                    }
+                }
+            }
+        }
+
+        // The checkpoint is saved periodically, after the results up to its line are written out,
+        // so that a resumed job loses no results and repeats at most the lines since the last save.
+        checkpoint.advance(&line);
+        if let Some(path) = checkpoint_path {
+            if checkpoint.line - saved_line >= CHECKPOINT_INTERVAL
+                || saved_at.elapsed() >= CHECKPOINT_PERIOD
+            {
+                save_checkpoint(&checkpoint, path, report.as_mut())
+                    .map_err(|_| checkpoint_failed(path))?;
+                (saved_line, saved_at) = (checkpoint.line, Instant::now());
+            }
+        }
+    }
+    if let Some(path) = checkpoint_path {
+        Checkpoint::remove(path).map_err(|_| checkpoint_failed(path))?;
+    }
+    if let (Some(report), Some(path)) = (report.as_mut(), report_path) {
+        report.flush().map_err(|_| write_failed(path))?;
+        out.print(
+            path,
+            &format!(
+                "{}: {}, {}: {}",
+                tr(lang, Msg::LinesChecked),
+                lines,
+                tr(lang, Msg::LinesFailed),
+                failed
+            ),
+        );
+    }
+
+    progress.finish();
+    Ok(failed)
+}
+
+/// Opens the CSV report of the `file` command, writing its header unless it continues a report.
+/// Writes out the report of a `file` job and saves its checkpoint, so that the checkpoint never
+/// moves past results that are not written yet. Printed results and the error log are written
+/// out line by line.
+fn save_checkpoint(
+    checkpoint: &Checkpoint,
+    path: &str,
+    report: Option<&mut csv::Writer<File>>,
+) -> io::Result<()> {
+    if let Some(report) = report {
+        report.flush()?;
+    }
+    checkpoint.save(path)
+}
+
+fn open_report(path: &str, append: bool) -> io::Result<csv::Writer<File>> {
+    let file = OpenOptions::new()
+        .create(true)
+        .write(true)
+        .append(append)
+        .truncate(!append)
+        .open(path)?;
+    let mut report = csv::Writer::from_writer(file);
+    if !append {
+        report.write_record([
+            "line",
+            "input",
+            "parsed",
+            "balanced",
+            "reactant_mass",
+            "product_mass",
+            "error",
+        ])?;
+    }
+    Ok(report)
+}
+
+/// Collects the equations of a file that parse into a reaction network.
+fn reaction_network(parser: &ChemParser, file_path: &str) -> io::Result<ReactionNetwork> {
+    let mut network = ReactionNetwork::new();
+    for line in LineReader::open(file_path)? {
+        if let Ok(equation) = parser.parse_equation(&line?.text) {
+            network.add(equation);
+        }
+    }
+    Ok(network)
+}
+
+/// Groups the formulas of the equations of a file by composition, skipping lines that fail to parse.
+fn notation_clusters(parser: &ChemParser, file_path: &str) -> io::Result<Vec<NotationCluster>> {
+    let mut clusters = NotationClusters::default();
+    for line in LineReader::open(file_path)? {
+        if let Ok(equation) = parser.parse_equation(&line?.text) {
+            clusters.add_equation(&equation);
+        }
+    }
+    Ok(clusters.clusters())
+}
+
+/// Conditions an equation must meet to be sampled.
+struct SampleFilter<'a> {
+    unbalanced_only: bool,
+    element: Option<&'a str>,
+}
+
+impl SampleFilter<'_> {
+    fn matches(&self, equation: &Equation) -> bool {
+        if self.unbalanced_only && equation.check_atom_balance(false) {
+            return false;
+        }
+        self.element.is_none_or(|symbol| {
+            equation
+                .reactant_terms
+                .iter()
+                .chain(&equation.product_terms)
+                .flat_map(|term| term.formula.elements.keys())
+                .any(|key| element_symbol(key) == symbol)
+        })
+    }
+}
+
+/// Prints a random sample of the equations of a file that match the filter, in file order.
+/// Lines that fail to parse are not sampled. The same seed gives the same sample.
+fn sample_equations(
+    parser: &ChemParser,
+    out: &mut Output,
+    file_path: &str,
+    lang: Lang,
+    size: usize,
+    seed: Option<u64>,
+    filter: &SampleFilter,
+) -> anyhow::Result<(), String> {
+    let read_failed = || format!("{}: {}", tr(lang, Msg::ReadFileFailed), file_path);
+    let mut rng = match seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+
+    let mut lines = Vec::new();
+    for line in LineReader::open(file_path).map_err(|_| read_failed())? {
+        let line = line.map_err(|_| read_failed())?;
+        if let Ok(equation) = parser.parse_equation(&line.text) {
+            if filter.matches(&equation) {
+                lines.push((line.number, equation));
+            }
+        }
+    }
+    let mut sample = lines.into_iter().choose_multiple(&mut rng, size);
+    sample.sort_by_key(|(number, _)| *number);
+
+    for (number, equation) in sample {
+        out.print(
+            &equation.equation,
+            &format!(
+                "{}. {}\n{}",
+                number,
+                render_equation(lang, &equation),
+                balance_text(lang, equation.check_atom_balance(false))
+            ),
+        );
+    }
     Ok(())
 }
+
+/// Applies the rewrite rules of a file to every formula or equation of another, one per line,
+/// printing the results and skipping blank lines and `#` comments.
+fn rewrite_file(
+    parser: &ChemParser,
+    out: &mut Output,
+    lang: Lang,
+    file_path: &str,
+    rules_path: &str,
+) -> Result<usize, String> {
+    let rules = std::fs::read_to_string(rules_path)
+        .map_err(|_| format!("{}: {}", tr(lang, Msg::ReadFileFailed), rules_path))?;
+    let rules = parser
+        .parse_rewrite_rules(&rules)
+        .map_err(|e| e.to_string())?;
+    let read_failed = || format!("{}: {}", tr(lang, Msg::ReadFileFailed), file_path);
+
+    let mut rewritten = 0;
+    let reader = LineReader::open(file_path).map_err(|_| read_failed())?;
+    for line in reader.equation_lines() {
+        let line = line.map_err(|_| read_failed())?;
+        let text = line.text.trim();
+        let result = match parser.parse_any(text) {
+            Ok(Parsed::Equation(equation)) => rules
+                .apply_equation(&equation)
+                .map(|equation| equation.equation),
+            Ok(Parsed::Formula(formula)) => {
+                rules.apply_formula(&formula).map(|formula| formula.formula)
+            }
+            Ok(Parsed::Element(_)) => parser
+                .parse_formula(text)
+                .and_then(|formula| rules.apply_formula(&formula))
+                .map(|formula| formula.formula),
+            Err(e) => Err(e),
+        };
+        match result {
+            Ok(result) => {
+                out.print(text, &result);
+                rewritten += 1;
+            }
+            Err(e) => out.fail(
+                text,
+                &format!("{} {}: {}", tr(lang, Msg::ErrorOnLine), line.number, e),
+                &e,
+            ),
+        }
+    }
+    Ok(rewritten)
+}
+
+/// Converts the equation file at `input` to `output`, detecting the input format and taking the
+/// output format from `format` or the extension of the output path. Equations that fail to parse
+/// are reported and left out. Returns the number of converted equations.
+fn convert_file(
+    parser: &ChemParser,
+    out: &mut Output,
+    lang: Lang,
+    input: &str,
+    output: &str,
+    format: Option<&str>,
+) -> Result<usize, String> {
+    let target = match format {
+        Some(name) => {
+            BatchFormat::from_name(name).or(BatchFormat::from_path(&format!(".{}", name)))
+        }
+        None => BatchFormat::from_path(output),
+    }
+    .ok_or_else(|| {
+        format!(
+            "{} '{}'",
+            tr(lang, Msg::UnknownFormat),
+            format.unwrap_or(output)
+        )
+    })?;
+    let content = std::fs::read_to_string(input)
+        .map_err(|_| format!("{}: {}", tr(lang, Msg::ReadFileFailed), input))?;
+    let entries =
+        read_batch(&content, BatchFormat::detect(input, &content)).map_err(|e| e.to_string())?;
+
+    let mut equations = Vec::new();
+    for (index, entry) in entries.iter().enumerate() {
+        match parser.parse_equation(entry) {
+            Ok(equation) => equations.push(equation),
+            Err(e) => out.fail(
+                entry,
+                &format!("{} {}: {}", tr(lang, Msg::ErrorInEntry), index + 1, e),
+                &e,
+            ),
+        }
+    }
+
+    let write_failed = || format!("{}: {}", tr(lang, Msg::WriteFileFailed), output);
+    let mut writer = io::BufWriter::new(File::create(output).map_err(|_| write_failed())?);
+    write_batch(&equations, target, &mut writer)
+        .and_then(|_| io::Write::flush(&mut writer))
+        .map_err(|_| write_failed())?;
+    Ok(equations.len())
+}
+
+/// Writes the molar masses of the formulas of a file, one per line, as a CSV or JSON table to `output`
+/// or to stdout. Blank lines and `#` comments are skipped, and formulas that cannot be parsed are
+/// reported and left out. Returns the number of formulas in the table.
+fn write_mass_table(
+    parser: &ChemParser,
+    out: &mut Output,
+    lang: Lang,
+    file_path: &str,
+    format: Option<&str>,
+    output: Option<&str>,
+) -> Result<usize, String> {
+    let format = format.unwrap_or("csv");
+    if !["csv", "json"].contains(&format) {
+        return Err(format!("{} '{}'", tr(lang, Msg::UnknownFormat), format));
+    }
+    let read_failed = || format!("{}: {}", tr(lang, Msg::ReadFileFailed), file_path);
+
+    let mut formulas = Vec::new();
+    let reader = LineReader::open(file_path).map_err(|_| read_failed())?;
+    for line in reader.equation_lines() {
+        let line = line.map_err(|_| read_failed())?;
+        let text = line.text.trim();
+        match parser.parse_formula(text) {
+            Ok(formula) => formulas.push(formula),
+            Err(e) => out.fail(
+                text,
+                &format!("{} {}: {}", tr(lang, Msg::ErrorOnLine), line.number, e),
+                &e,
+            ),
+        }
+    }
+
+    let table = mass_table(&formulas);
+    let writer: Box<dyn io::Write> = match output {
+        Some(path) => Box::new(
+            File::create(path)
+                .map_err(|_| format!("{}: {}", tr(lang, Msg::WriteFileFailed), path))?,
+        ),
+        None => Box::new(io::stdout()),
+    };
+    let written = match format {
+        "json" => write_mass_table_json(&table, writer),
+        _ => write_mass_table_csv(&table, writer),
+    };
+    written.map_err(|_| {
+        format!(
+            "{}: {}",
+            tr(lang, Msg::WriteFileFailed),
+            output.unwrap_or("stdout")
+        )
+    })?;
+    Ok(table.len())
+}
+
+/// Collects the statistics of the equations of a file, skipping blank lines.
+fn file_stats(parser: &ChemParser, file_path: &str) -> io::Result<EquationStats> {
+    let mut stats = EquationStats::default();
+    for line in LineReader::open(file_path)? {
+        let line = line?;
+        if line.text.trim().is_empty() {
+            continue;
+        }
+        match parser.parse_equation(&line.text) {
+            Ok(equation) => stats.add(&equation),
+            Err(_) => stats.add_failure(),
+        }
+    }
+    Ok(stats)
+}
+
+/// Returns a progress bar on stderr when the `progress` feature is enabled and stderr is a terminal,
+/// unless the results are printed to the same terminal and `to_file` is not set.
+fn progress_sink(to_file: bool) -> Box<dyn ProgressSink> {
+    #[cfg(feature = "progress")]
+    {
+        use std::io::IsTerminal;
+        if io::stderr().is_terminal() && (to_file || !io::stdout().is_terminal()) {
+            return Box::new(chemistry_parser::progress::ProgressBar::new());
+        }
+    }
+    let _ = to_file;
+    Box::new(NoProgress)
+}
@@ -0,0 +1,122 @@
+//! # Matrix Module
+//!
+//! This module exports parsed equations as stoichiometric matrices for linear-algebra pipelines:
+//! the element×species composition matrix of an equation and the species×reaction matrix of a
+//! reaction network. With the `ndarray` feature the matrices convert to `ndarray::Array2`.
+
+use crate::element::{composition_key, Equation};
+use serde::Serialize;
+use std::collections::BTreeSet;
+
+/// Label of the row of the charge in an element×species matrix.
+pub const CHARGE_ROW: &str = "charge";
+
+/// Matrix of integers with labelled rows and columns.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct StoichiometricMatrix {
+    /// Labels of the rows, such as element symbols or formulas.
+    pub rows: Vec<String>,
+    /// Labels of the columns, such as formulas or equations.
+    pub columns: Vec<String>,
+    /// Entries of the matrix, one vector per row.
+    pub values: Vec<Vec<i64>>,
+}
+
+impl StoichiometricMatrix {
+    /// Returns the entry of a row and a column by their labels.
+    pub fn get(&self, row: &str, column: &str) -> Option<i64> {
+        let row = self.rows.iter().position(|label| label == row)?;
+        let column = self.columns.iter().position(|label| label == column)?;
+        Some(self.values[row][column])
+    }
+
+    /// Converts the entries into a two-dimensional array.
+    #[cfg(feature = "ndarray")]
+    pub fn to_array(&self) -> ndarray::Array2<i64> {
+        ndarray::Array2::from_shape_fn((self.rows.len(), self.columns.len()), |(row, column)| {
+            self.values[row][column]
+        })
+    }
+}
+
+impl Equation {
+    /// Returns the element×species matrix of the equation: a row per element or isotope, in
+    /// alphabetical order, and a column per species, reactants first, with the number of atoms
+    /// of the element in one unit of the species. A [`CHARGE_ROW`] with the charge of each
+    /// species follows when a species is charged. For `2H2 + O2 -> 2H2O` the rows are `H` and
+    /// `O` and the columns `H2`, `O2` and `H2O`; the coefficients of the balanced equation span
+    /// its null space once the products are negated.
+    pub fn stoichiometric_matrix(&self) -> StoichiometricMatrix {
+        let terms = self.terms();
+        let mut rows: Vec<String> = terms
+            .iter()
+            .flat_map(|term| term.formula.elements.keys().cloned())
+            .collect::<BTreeSet<String>>()
+            .into_iter()
+            .collect();
+        let mut values: Vec<Vec<i64>> = rows
+            .iter()
+            .map(|key| {
+                terms
+                    .iter()
+                    .map(|term| i64::from(term.formula.elements.get(key).copied().unwrap_or(0)))
+                    .collect()
+            })
+            .collect();
+        if terms.iter().any(|term| term.formula.charge != 0) {
+            rows.push(String::from(CHARGE_ROW));
+            values.push(
+                terms
+                    .iter()
+                    .map(|term| i64::from(term.formula.charge))
+                    .collect(),
+            );
+        }
+        StoichiometricMatrix {
+            rows,
+            columns: terms
+                .iter()
+                .map(|term| term.formula.formula.clone())
+                .collect(),
+            values,
+        }
+    }
+}
+
+/// Returns the species×reaction matrix of equations: a row per species, in the order they first
+/// appear, and a column per equation, with the coefficient of the species, negative for
+/// reactants and positive for products. Species are matched by their atoms and charge, so a
+/// species written on both sides of an equation gets its net coefficient. The column of an
+/// equation written with fractional coefficients has them cleared, as in
+/// [`Equation::to_integer_coefficients`].
+pub fn stoichiometric_matrix(equations: &[Equation]) -> StoichiometricMatrix {
+    let mut keys: Vec<String> = Vec::new();
+    let mut rows: Vec<String> = Vec::new();
+    let mut values: Vec<Vec<i64>> = Vec::new();
+    for (column, equation) in equations.iter().enumerate() {
+        let sides = [(&equation.reactant_terms, -1), (&equation.product_terms, 1)];
+        for (terms, sign) in sides {
+            for term in terms {
+                let key = composition_key(&term.formula);
+                let row = match keys.iter().position(|other| *other == key) {
+                    Some(row) => row,
+                    None => {
+                        keys.push(key);
+                        rows.push(term.formula.formula.clone());
+                        values.push(vec![0; equations.len()]);
+                        rows.len() - 1
+                    }
+                };
+                values[row][column] += sign * i64::from(term.coefficient);
+            }
+        }
+    }
+    StoichiometricMatrix {
+        rows,
+        columns: equations
+            .iter()
+            .map(|equation| equation.equation.clone())
+            .collect(),
+        values,
+    }
+}
@@ -0,0 +1,159 @@
+//! # Compound metadata Module
+//!
+//! This module keeps external identifiers of compounds, such as CAS registry numbers and PubChem
+//! CIDs, in a catalog beside the parsed formulas, so that inventory software can attach them to
+//! the species it parses. Identifiers of elements are stored on [`crate::element::Element`].
+
+use crate::element::{composition_key, Formula};
+#[cfg(feature = "csv")]
+use crate::ChemParser;
+use serde::{Deserialize, Serialize};
+#[cfg(feature = "csv")]
+use std::error::Error;
+
+/// External identifiers of a compound.
+#[derive(Debug, Clone, Default, PartialEq, Eq, Deserialize, Serialize)]
+pub struct CompoundMetadata {
+    /// Name of the compound, such as `sodium chloride`.
+    #[serde(default)]
+    pub name: Option<String>,
+    /// CAS registry number, such as `7647-14-5`.
+    #[serde(default)]
+    pub cas: Option<String>,
+    /// PubChem compound identifier (CID).
+    #[serde(default)]
+    pub cid: Option<u64>,
+    /// Category of the compound, such as `salt` or `solvent`.
+    #[serde(default)]
+    pub category: Option<String>,
+}
+
+/// Metadata of a compound as stored in a catalog file, with the formula of the compound.
+#[cfg(feature = "csv")]
+#[derive(Deserialize)]
+struct CatalogRow {
+    formula: String,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    cas: Option<String>,
+    #[serde(default)]
+    cid: Option<u64>,
+    #[serde(default)]
+    category: Option<String>,
+}
+
+/// Catalog of compounds with their metadata. Compounds are looked up by composition, so
+/// `CH3COOH` finds an entry for `C2H4O2`; isomers share a composition and are all returned.
+#[derive(Debug, Clone, Default)]
+pub struct CompoundCatalog {
+    entries: Vec<(Formula, CompoundMetadata)>,
+}
+
+impl CompoundCatalog {
+    /// Creates an empty catalog.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a compound with its metadata.
+    pub fn insert(&mut self, formula: Formula, metadata: CompoundMetadata) {
+        self.entries.push((formula, metadata));
+    }
+
+    /// Returns the metadata of the compounds with the same composition as a formula, in the
+    /// order they were added.
+    pub fn lookup(&self, formula: &Formula) -> Vec<&CompoundMetadata> {
+        let key = composition_key(formula);
+        self.entries
+            .iter()
+            .filter(|(entry, _)| composition_key(entry) == key)
+            .map(|(_, metadata)| metadata)
+            .collect()
+    }
+
+    /// Returns the compound with a CAS registry number, with its metadata.
+    pub fn get_by_cas(&self, cas: &str) -> Option<(&Formula, &CompoundMetadata)> {
+        let cas = cas.trim();
+        self.entries
+            .iter()
+            .find(|(_, metadata)| metadata.cas.as_deref() == Some(cas))
+            .map(|(formula, metadata)| (formula, metadata))
+    }
+
+    /// Returns the number of compounds in the catalog.
+    pub fn len(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Checks whether the catalog has no compounds.
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+
+    /// Loads compounds from a CSV file with a `formula` column and optional `name`, `cas`,
+    /// `cid` and `category` columns, parsing the formulas with a parser.
+    #[cfg(feature = "csv")]
+    pub fn load_csv(
+        &mut self,
+        parser: &ChemParser,
+        path: &str,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.load(parser, csv::Reader::from_path(path)?)
+    }
+
+    /// Loads compounds from CSV data with a `formula` column and optional `name`, `cas`, `cid`
+    /// and `category` columns, parsing the formulas with a parser.
+    #[cfg(feature = "csv")]
+    pub fn load_csv_str(
+        &mut self,
+        parser: &ChemParser,
+        data: &str,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.load(parser, csv::Reader::from_reader(data.as_bytes()))
+    }
+
+    #[cfg(feature = "csv")]
+    fn load<R: std::io::Read>(
+        &mut self,
+        parser: &ChemParser,
+        mut rdr: csv::Reader<R>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for result in rdr.deserialize() {
+            let row: CatalogRow = result?;
+            let metadata = CompoundMetadata {
+                name: row.name,
+                cas: row.cas,
+                cid: row.cid,
+                category: row.category,
+            };
+            self.insert(parser.parse_formula(&row.formula)?, metadata);
+        }
+        Ok(())
+    }
+}
+
+/// Checks whether a CAS registry number is well formed: two to seven digits, two digits and a
+/// check digit separated by hyphens, with the check digit matching the other digits, as in
+/// `7732-18-5`.
+pub fn is_valid_cas(cas: &str) -> bool {
+    let parts: Vec<&str> = cas.trim().split('-').collect();
+    let [first, second, check] = parts[..] else {
+        return false;
+    };
+    if !(2..=7).contains(&first.len())
+        || second.len() != 2
+        || check.len() != 1
+        || !cas.trim().chars().all(|c| c.is_ascii_digit() || c == '-')
+    {
+        return false;
+    }
+    let sum: u32 = first
+        .chars()
+        .chain(second.chars())
+        .rev()
+        .zip(1..)
+        .filter_map(|(digit, weight)| Some(digit.to_digit(10)? * weight))
+        .sum();
+    check.chars().next().and_then(|c| c.to_digit(10)) == Some(sum % 10)
+}
@@ -0,0 +1,144 @@
+//! # Names Module
+//!
+//! This module renders formulas by their common names in reports, for example `SO4^2-` as
+//! "sulfate ion" and `CuSO4·5H2O` as "CuSO4 pentahydrate", using the ion names embedded from
+//! `data/ion_names.csv`.
+
+use crate::element::{composition_key, Formula};
+use crate::{normalize_scripts, ChemParser, Rule};
+use pest::iterators::Pair;
+use pest::Parser;
+use serde::Deserialize;
+use std::collections::{BTreeMap, HashMap};
+use std::sync::OnceLock;
+
+/// Ion names embedded at compile time from `data/ion_names.csv`.
+const ION_NAMES_CSV: &str = include_str!("../data/ion_names.csv");
+
+/// Greek numeral prefixes from 1 (`mono`) to 12 (`dodeca`).
+const GREEK_PREFIXES: [&str; 12] = [
+    "mono", "di", "tri", "tetra", "penta", "hexa", "hepta", "octa", "nona", "deca", "undeca",
+    "dodeca",
+];
+
+/// Name of an ion as stored in the naming dataset.
+#[derive(Deserialize)]
+struct IonName {
+    formula: String,
+    name: String,
+}
+
+/// Returns the Greek numeral prefix of a count, such as `penta` for 5.
+pub fn greek_prefix(count: u32) -> Option<&'static str> {
+    GREEK_PREFIXES
+        .get((count as usize).checked_sub(1)?)
+        .copied()
+}
+
+/// Returns the name of an ion from the embedded naming dataset, such as `sulfate` for `SO4^2-`.
+/// Ions are matched by their atoms and charge, so `CH3COO^-` and `C2H3O2^-` are both acetate.
+pub fn ion_name(formula: &Formula) -> Option<&'static str> {
+    ion_names()
+        .get(&composition_key(formula))
+        .map(String::as_str)
+}
+
+impl ChemParser {
+    /// Renders a term of a report by its common name: `SO4^2-` as `sulfate ion`, `CuSO4·5H2O` as
+    /// `CuSO4 pentahydrate` and a lone `·5H2O` as `pentahydrate`.
+    /// Terms without a known name, or that cannot be parsed, are returned as written.
+    pub fn pretty_name(&self, term: &str) -> String {
+        self.known_name(&normalize_scripts(term.trim()))
+            .unwrap_or_else(|| term.to_string())
+    }
+
+    fn known_name(&self, term: &str) -> Option<String> {
+        if let Some(adduct) = parse_whole(Rule::adduct, term) {
+            return self.hydrate_name(adduct);
+        }
+
+        let formula = parse_whole(Rule::formula, term)?;
+        let start = formula.as_span().start();
+        let adducts: Vec<Pair<Rule>> = formula
+            .into_inner()
+            .filter(|pair| pair.as_rule() == Rule::adduct)
+            .collect();
+        let base_end = adducts
+            .first()
+            .map_or(term.len(), |adduct| adduct.as_span().start() - start);
+        let base = &term[..base_end];
+
+        let base_name =
+            ion_name(&self.parse_formula(base).ok()?).map(|name| format!("{} ion", name));
+        if adducts.is_empty() {
+            return base_name;
+        }
+        let mut parts = vec![base_name.unwrap_or_else(|| base.to_string())];
+        for adduct in adducts {
+            parts.push(self.hydrate_name(adduct)?);
+        }
+        Some(parts.join(" "))
+    }
+
+    /// Names an adduct of water, such as `pentahydrate` for `·5H2O`.
+    fn hydrate_name(&self, adduct: Pair<Rule>) -> Option<String> {
+        let mut count = 1;
+        let mut is_water = false;
+        for inner in adduct.into_inner() {
+            match inner.as_rule() {
+                Rule::adduct_count => count = inner.as_str().parse().ok()?,
+                Rule::adduct_formula => {
+                    let formula = self.parse_formula(inner.as_str()).ok()?;
+                    is_water = formula.charge == 0
+                        && formula.elements
+                            == BTreeMap::from([(String::from("H"), 2), (String::from("O"), 1)]);
+                }
+                _ => {}
+            }
+        }
+        if !is_water {
+            return None;
+        }
+        greek_prefix(count).map(|prefix| format!("{}hydrate", prefix))
+    }
+}
+
+/// Parses the whole input with a rule, or returns `None` if the rule does not match all of it.
+fn parse_whole(rule: Rule, input: &str) -> Option<Pair<'_, Rule>> {
+    ChemParser::parse(rule, input)
+        .ok()?
+        .next()
+        .filter(|pair| pair.as_str().len() == input.len())
+}
+
+/// Returns the names and formulas of the ions of the embedded dataset, in the order of the dataset.
+pub(crate) fn named_ions() -> &'static [(String, String)] {
+    static NAMED_IONS: OnceLock<Vec<(String, String)>> = OnceLock::new();
+    NAMED_IONS.get_or_init(|| {
+        csv::Reader::from_reader(ION_NAMES_CSV.as_bytes())
+            .deserialize::<IonName>()
+            .map(|ion| {
+                let ion = ion.expect("embedded ion names are valid");
+                (ion.name, ion.formula)
+            })
+            .collect()
+    })
+}
+
+/// Returns the ion names of the embedded dataset by the composition key of their formulas.
+fn ion_names() -> &'static HashMap<String, String> {
+    static ION_NAMES: OnceLock<HashMap<String, String>> = OnceLock::new();
+    ION_NAMES.get_or_init(|| {
+        let parser = ChemParser::new();
+        csv::Reader::from_reader(ION_NAMES_CSV.as_bytes())
+            .deserialize::<IonName>()
+            .map(|ion| {
+                let ion = ion.expect("embedded ion names are valid");
+                let formula = parser
+                    .parse_formula(&ion.formula)
+                    .expect("embedded ion formulas are valid");
+                (composition_key(&formula), ion.name)
+            })
+            .collect()
+    })
+}
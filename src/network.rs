@@ -0,0 +1,326 @@
+//! # Network Module
+//!
+//! This module treats a set of equations as a reaction network, with species as nodes and
+//! reactions as edges from their reactants to their products. A [`ReactionNetwork`] collects
+//! equations, answers which reactions consume or produce a species and exports the graph in the
+//! DOT language of Graphviz, and [`PathSearch`] finds the shortest sequence of reactions that
+//! turns a starting material into a target.
+
+use crate::element::{composition_key, Arrow, Equation, Formula, Term};
+use serde::Serialize;
+use std::collections::{HashMap, VecDeque};
+
+/// Default largest number of reactions in a path.
+pub const DEFAULT_MAX_DEPTH: usize = 5;
+
+/// Reaction of a path, turning one species into the next one.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct PathStep {
+    /// Index of the reaction in the searched equations.
+    pub reaction: usize,
+    /// Species consumed by the reaction, as written in it.
+    pub from: String,
+    /// Species produced by the reaction, as written in it.
+    pub to: String,
+}
+
+/// Search for the shortest reaction path between two species of a set of equations, for example
+/// `PathSearch::new(&equations).max_depth(3).ban("CO").find("CH4", "CH3OH")`.
+///
+/// Reactions go from their reactants to their products; reversible reactions go both ways.
+/// Species are named by their formula as written or in Hill notation.
+pub struct PathSearch<'a> {
+    equations: &'a [Equation],
+    max_depth: usize,
+    banned: Vec<String>,
+}
+
+impl<'a> PathSearch<'a> {
+    /// Creates a search over the equations with [`DEFAULT_MAX_DEPTH`] and no banned species.
+    pub fn new(equations: &'a [Equation]) -> Self {
+        PathSearch {
+            equations,
+            max_depth: DEFAULT_MAX_DEPTH,
+            banned: Vec::new(),
+        }
+    }
+
+    /// Sets the largest number of reactions in a path.
+    pub fn max_depth(mut self, max_depth: usize) -> Self {
+        self.max_depth = max_depth;
+        self
+    }
+
+    /// Leaves out every reaction that consumes or produces a species.
+    pub fn ban(mut self, species: &str) -> Self {
+        self.banned.push(species.trim().to_string());
+        self
+    }
+
+    /// Returns the reactions of a shortest path from one species to another, or `None` if there
+    /// is no path within the maximum depth. The path from a species to itself is empty.
+    pub fn find(&self, from: &str, to: &str) -> Option<Vec<PathStep>> {
+        let allowed: Vec<(usize, &Equation)> = self
+            .equations
+            .iter()
+            .enumerate()
+            .filter(|(_, equation)| {
+                !equation
+                    .terms()
+                    .iter()
+                    .any(|term| self.banned.iter().any(|name| is_named(&term.formula, name)))
+            })
+            .collect();
+        let start = self.key_of(from)?;
+        let target = self.key_of(to)?;
+
+        // Breadth-first search, remembering the step that first reached each species.
+        let mut reached: HashMap<String, Option<(String, PathStep)>> = HashMap::new();
+        reached.insert(start.clone(), None);
+        let mut queue = VecDeque::from([(start, 0)]);
+        while let Some((species, depth)) = queue.pop_front() {
+            if species == target {
+                return Some(path_to(&reached, &target));
+            }
+            if depth == self.max_depth {
+                continue;
+            }
+            for &(reaction, equation) in &allowed {
+                for (consumed, produced) in directions(equation) {
+                    let Some(from_term) = consumed
+                        .iter()
+                        .find(|term| composition_key(&term.formula) == species)
+                    else {
+                        continue;
+                    };
+                    for term in produced {
+                        let key = composition_key(&term.formula);
+                        if reached.contains_key(&key) {
+                            continue;
+                        }
+                        let step = PathStep {
+                            reaction,
+                            from: from_term.formula.formula.clone(),
+                            to: term.formula.formula.clone(),
+                        };
+                        reached.insert(key.clone(), Some((species.clone(), step)));
+                        queue.push_back((key, depth + 1));
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// Returns the composition key of a species named in one of the equations.
+    fn key_of(&self, name: &str) -> Option<String> {
+        let name = name.trim();
+        self.equations
+            .iter()
+            .flat_map(Equation::terms)
+            .find(|term| is_named(&term.formula, name))
+            .map(|term| composition_key(&term.formula))
+    }
+}
+
+/// Returns the shortest reaction path between two species of the equations, searched with
+/// [`DEFAULT_MAX_DEPTH`] and no banned species. See [`PathSearch`].
+pub fn path(equations: &[Equation], from: &str, to: &str) -> Option<Vec<PathStep>> {
+    PathSearch::new(equations).find(from, to)
+}
+
+/// Edge of a reaction network, from a species consumed by a reaction to a species it produces.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Edge {
+    /// Index of the consumed species in [`ReactionNetwork::species`].
+    pub from: usize,
+    /// Index of the produced species in [`ReactionNetwork::species`].
+    pub to: usize,
+    /// Index of the reaction in [`ReactionNetwork::reactions`].
+    pub reaction: usize,
+}
+
+/// Network of reactions collected from parsed equations, with each species stored once.
+/// Species are matched by their atoms and charge and named by their formula as first written or
+/// in Hill notation.
+#[derive(Debug, Clone, Default, Serialize)]
+pub struct ReactionNetwork {
+    species: Vec<Formula>,
+    #[serde(skip)]
+    keys: HashMap<String, usize>,
+    reactions: Vec<Equation>,
+}
+
+impl ReactionNetwork {
+    /// Creates a network without reactions.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds a reaction and its new species, returning the index of the reaction.
+    pub fn add(&mut self, equation: Equation) -> usize {
+        for term in equation.terms() {
+            let key = composition_key(&term.formula);
+            if !self.keys.contains_key(&key) {
+                self.keys.insert(key, self.species.len());
+                self.species.push(term.formula.clone());
+            }
+        }
+        self.reactions.push(equation);
+        self.reactions.len() - 1
+    }
+
+    /// Returns the species of the network, in the order they were added.
+    pub fn species(&self) -> &[Formula] {
+        &self.species
+    }
+
+    /// Returns the reactions of the network, in the order they were added.
+    pub fn reactions(&self) -> &[Equation] {
+        &self.reactions
+    }
+
+    /// Returns the index of a species by its formula as written in a reaction or in Hill notation.
+    pub fn species_index(&self, name: &str) -> Option<usize> {
+        let name = name.trim();
+        self.species
+            .iter()
+            .position(|formula| is_named(formula, name))
+    }
+
+    /// Returns the indices of the reactions that consume a species, such as those with `H2O`
+    /// among their reactants. Reversible reactions consume the species of both sides.
+    pub fn consuming(&self, name: &str) -> Vec<usize> {
+        self.reactions_with(name, |(consumed, _)| consumed)
+    }
+
+    /// Returns the indices of the reactions that produce a species. Reversible reactions produce
+    /// the species of both sides.
+    pub fn producing(&self, name: &str) -> Vec<usize> {
+        self.reactions_with(name, |(_, produced)| produced)
+    }
+
+    fn reactions_with<'a>(
+        &'a self,
+        name: &str,
+        side: impl Fn((&'a [Term], &'a [Term])) -> &'a [Term],
+    ) -> Vec<usize> {
+        let Some(index) = self.species_index(name) else {
+            return Vec::new();
+        };
+        let key = composition_key(&self.species[index]);
+        (0..self.reactions.len())
+            .filter(|&reaction| {
+                directions(&self.reactions[reaction])
+                    .into_iter()
+                    .any(|sides| {
+                        side(sides)
+                            .iter()
+                            .any(|term| composition_key(&term.formula) == key)
+                    })
+            })
+            .collect()
+    }
+
+    /// Returns the edges of the network, from each species a reaction consumes to each species it
+    /// produces, in both directions for reversible reactions.
+    pub fn edges(&self) -> Vec<Edge> {
+        let index = |term: &Term| self.keys[&composition_key(&term.formula)];
+        let mut edges = Vec::new();
+        for (reaction, equation) in self.reactions.iter().enumerate() {
+            for (consumed, produced) in directions(equation) {
+                for from in consumed {
+                    for to in produced {
+                        edges.push(Edge {
+                            from: index(from),
+                            to: index(to),
+                            reaction,
+                        });
+                    }
+                }
+            }
+        }
+        edges
+    }
+
+    /// Returns the reactions of a shortest path from one species to another. See [`PathSearch`].
+    pub fn path(&self, from: &str, to: &str) -> Option<Vec<PathStep>> {
+        PathSearch::new(&self.reactions).find(from, to)
+    }
+
+    /// Writes the network as a Graphviz digraph in the DOT language, with a node per species and
+    /// an edge per consumed and produced species of a reaction, labelled `R1`, `R2`, ... in the
+    /// order of the reactions. Reversible reactions get one edge with arrows at both ends.
+    pub fn to_dot(&self) -> String {
+        let mut dot = String::from("digraph reactions {\n");
+        for (index, formula) in self.species.iter().enumerate() {
+            dot.push_str(&format!(
+                "    s{} [label=\"{}\"];\n",
+                index,
+                escape(&formula.formula)
+            ));
+        }
+        for (reaction, equation) in self.reactions.iter().enumerate() {
+            let both = if equation.arrow == Arrow::Reversible {
+                ", dir=both"
+            } else {
+                ""
+            };
+            for from in &equation.reactant_terms {
+                for to in &equation.product_terms {
+                    dot.push_str(&format!(
+                        "    s{} -> s{} [label=\"R{}\"{}];\n",
+                        self.keys[&composition_key(&from.formula)],
+                        self.keys[&composition_key(&to.formula)],
+                        reaction + 1,
+                        both
+                    ));
+                }
+            }
+        }
+        dot.push_str("}\n");
+        dot
+    }
+}
+
+impl FromIterator<Equation> for ReactionNetwork {
+    fn from_iter<I: IntoIterator<Item = Equation>>(equations: I) -> Self {
+        let mut network = ReactionNetwork::new();
+        for equation in equations {
+            network.add(equation);
+        }
+        network
+    }
+}
+
+/// Escapes a label for a quoted DOT string.
+fn escape(label: &str) -> String {
+    label.replace('\\', "\\\\").replace('"', "\\\"")
+}
+
+/// Returns the sides of a reaction as pairs of consumed and produced terms, in both directions for
+/// reversible reactions.
+fn directions(equation: &Equation) -> Vec<(&[Term], &[Term])> {
+    let forward = (&equation.reactant_terms[..], &equation.product_terms[..]);
+    match equation.arrow {
+        Arrow::Reversible => vec![forward, (forward.1, forward.0)],
+        Arrow::Irreversible | Arrow::Equality => vec![forward],
+    }
+}
+
+/// Checks whether a formula is written as the name or has it as its Hill notation.
+fn is_named(formula: &Formula, name: &str) -> bool {
+    formula.formula == name || formula.canonical() == name
+}
+
+/// Follows the remembered steps back from the target to the start.
+fn path_to(reached: &HashMap<String, Option<(String, PathStep)>>, target: &str) -> Vec<PathStep> {
+    let mut steps = Vec::new();
+    let mut species = target.to_string();
+    while let Some(Some((previous, step))) = reached.get(&species) {
+        steps.push(step.clone());
+        species = previous.clone();
+    }
+    steps.reverse();
+    steps
+}
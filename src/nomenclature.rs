@@ -0,0 +1,160 @@
+//! # Nomenclature Module
+//!
+//! This module names parsed formulas: binary ionic compounds and salts of the ions that
+//! [`Formula::identify_ions`] finds, with the charge of a metal in Stock notation where the ion
+//! dataset does not list it, and simple covalent compounds of two nonmetals with Greek prefixes, such as
+//! "dinitrogen tetroxide" for `N2O4`.
+
+use crate::element::{element_symbol, Formula};
+use crate::names::{greek_prefix, ion_name};
+use crate::{ChemParseError, ChemParser};
+
+/// Compounds known by a name that is not systematic.
+const COMMON_NAMES: &[(&str, &str)] = &[
+    ("H2O", "water"),
+    ("H2O2", "hydrogen peroxide"),
+    ("NH3", "ammonia"),
+    ("CH4", "methane"),
+];
+
+/// Nonmetals and metalloids in the order they are cited in binary covalent names, from the
+/// most electropositive, with the name each has as the second element.
+const COVALENT_SEQUENCE: &[(&str, &str)] = &[
+    ("B", "boride"),
+    ("Si", "silicide"),
+    ("C", "carbide"),
+    ("Sb", "antimonide"),
+    ("As", "arsenide"),
+    ("P", "phosphide"),
+    ("N", "nitride"),
+    ("H", "hydride"),
+    ("Te", "telluride"),
+    ("Se", "selenide"),
+    ("S", "sulfide"),
+    ("At", "astatide"),
+    ("I", "iodide"),
+    ("Br", "bromide"),
+    ("Cl", "chloride"),
+    ("O", "oxide"),
+    ("F", "fluoride"),
+];
+
+/// Roman numerals of the charges of metal cations in Stock notation.
+const ROMAN_NUMERALS: [&str; 8] = ["I", "II", "III", "IV", "V", "VI", "VII", "VIII"];
+
+impl ChemParser {
+    /// Returns the name of a compound, such as "sodium chloride" for `NaCl`, "iron(III) sulfate"
+    /// for `Fe2(SO4)3`, "lead(IV) oxide" for `PbO2` and "dinitrogen tetroxide" for `N2O4`.
+    ///
+    /// Compounds that [`Formula::identify_ions`] splits into a cation and an anion are named by
+    /// their ions, with a metal cation that the ion dataset does not list in Stock notation. Acids are named as hydrogen salts, such as "dihydrogen sulfate" for
+    /// `H2SO4`. Compounds of two nonmetals are named with Greek prefixes, without `mono` on the
+    /// first element. Elements are named with a prefix for their atoms, such as "dioxygen", and
+    /// ions of the dataset by their names.
+    ///
+    /// Fails with [`ChemParseError::EvaluationError`] if the formula is none of these.
+    pub fn systematic_name(&self, formula: &Formula) -> Result<String, ChemParseError> {
+        let error = |reason: &str| {
+            ChemParseError::EvaluationError(
+                format!("name of {}", formula.formula),
+                String::from(reason),
+            )
+        };
+        for (common, name) in COMMON_NAMES {
+            if self.parse_formula(common)?.same_composition(formula) {
+                return Ok(String::from(*name));
+            }
+        }
+        if formula.charge != 0 {
+            return ion_name(formula)
+                .map(|name| format!("{} ion", name))
+                .ok_or_else(|| error("the ion is not in the naming dataset"));
+        }
+        if let [(key, count)] = formula.elements.iter().collect::<Vec<_>>()[..] {
+            let element = self
+                .get_table()
+                .get_element(element_symbol(key))
+                .ok_or_else(|| error("the element is not in the table"))?;
+            let prefix = prefix(*count, true).ok_or_else(|| error("it has too many atoms"))?;
+            return Ok(format!("{}{}", prefix, element.name.to_lowercase()));
+        }
+
+        if let Some([(count, cation), (anion_count, anion)]) =
+            formula.identify_ions(self)?.as_deref()
+        {
+            if cation.formula != "H^+" {
+                let cation = self
+                    .cation_name(cation)
+                    .ok_or_else(|| error("the charge of the cation has no Roman numeral"))?;
+                return Ok(format!("{} {}", cation, name_of(anion)));
+            }
+            // An acid has a single anion, so that `C6H12O6` is not named as an acetate.
+            if let (1, Some(prefix)) = (anion_count, prefix(*count, true)) {
+                return Ok(format!("{}hydrogen {}", prefix, name_of(anion)));
+            }
+        }
+        covalent_name(self, formula).ok_or_else(|| {
+            error("it is neither a salt of known ions nor a compound of two nonmetals")
+        })
+    }
+
+    /// Names a cation by the ion dataset, or a metal cation that the dataset does not list in
+    /// Stock notation, such as "lead(IV)".
+    fn cation_name(&self, cation: &Formula) -> Option<String> {
+        if let Some(name) = ion_name(cation) {
+            return Some(String::from(name));
+        }
+        let key = cation.elements.keys().next()?;
+        let element = self.get_table().get_element(element_symbol(key))?;
+        let numeral =
+            ROMAN_NUMERALS.get((cation.charge.unsigned_abs() as usize).checked_sub(1)?)?;
+        Some(format!("{}({})", element.name.to_lowercase(), numeral))
+    }
+}
+
+/// Names a compound of two nonmetals with Greek prefixes, such as "dinitrogen tetroxide".
+fn covalent_name(parser: &ChemParser, formula: &Formula) -> Option<String> {
+    let mut elements: Vec<(usize, &str, u32)> = formula
+        .elements
+        .iter()
+        .map(|(key, count)| {
+            let symbol = element_symbol(key);
+            COVALENT_SEQUENCE
+                .iter()
+                .position(|(other, _)| *other == symbol)
+                .map(|position| (position, symbol, *count))
+        })
+        .collect::<Option<_>>()?;
+    elements.sort_unstable();
+    let [(_, first, first_count), (position, _, second_count)] = elements[..] else {
+        return None;
+    };
+    let first_name = parser.get_table().get_element(first)?.name.to_lowercase();
+    let second_name = COVALENT_SEQUENCE[position].1;
+    let mut second_prefix = prefix(second_count, false)?;
+    if second_name.starts_with('o')
+        && (second_prefix.ends_with('a') || second_prefix.ends_with('o'))
+    {
+        second_prefix = &second_prefix[..second_prefix.len() - 1];
+    }
+    Some(format!(
+        "{}{} {}{}",
+        prefix(first_count, true)?,
+        first_name,
+        second_prefix,
+        second_name
+    ))
+}
+
+/// Returns the Greek prefix of a count, or nothing for one when `omit_mono` is set.
+fn prefix(count: u32, omit_mono: bool) -> Option<&'static str> {
+    match count {
+        1 if omit_mono => Some(""),
+        _ => greek_prefix(count),
+    }
+}
+
+/// Returns the name of an ion of the dataset.
+fn name_of(ion: &Formula) -> &'static str {
+    ion_name(ion).unwrap_or_default()
+}
@@ -0,0 +1,139 @@
+//! # Normalize Module
+//!
+//! This module repairs the letter case of sloppy input, such as `h2o` or `NACL` typed by
+//! students, by matching runs of letters against the element symbols of the periodic table, and
+//! parses the corrected input, returning it with the result so that an application can show
+//! what it understood.
+
+use crate::element::{Equation, Formula, PeriodicTable};
+use crate::{ChemParseError, ChemParser};
+
+/// Result of parsing input whose letter case may have been corrected.
+#[derive(Debug, Clone)]
+pub struct Normalized<T> {
+    /// Input that was parsed, with the letter case corrected, such as `NaCl` for `NACL`.
+    pub input: String,
+    /// Whether the letter case of the input was corrected.
+    pub corrected: bool,
+    /// Parsed result.
+    pub parsed: T,
+}
+
+impl ChemParser {
+    /// Parses a formula after correcting the letter case of its element symbols, so that `h2o`
+    /// gives `H2O` and `NACL` gives `NaCl`. Symbols that are valid as written are kept.
+    ///
+    /// Fails with the error of [`ChemParser::parse_formula`] for the input as written if the
+    /// corrected input does not parse either.
+    pub fn parse_formula_normalized(
+        &self,
+        formula: &str,
+    ) -> Result<Normalized<Formula>, ChemParseError> {
+        self.parse_normalized(formula, |input| self.parse_formula(input))
+    }
+
+    /// Parses an equation after correcting the letter case of its element symbols, so that
+    /// `h2 + o2 -> h2o` gives `H2 + O2 -> H2O`. State symbols and reaction conditions are kept
+    /// as written.
+    ///
+    /// Fails with the error of [`ChemParser::parse_equation`] for the input as written if the
+    /// corrected input does not parse either.
+    pub fn parse_equation_normalized(
+        &self,
+        equation: &str,
+    ) -> Result<Normalized<Equation>, ChemParseError> {
+        self.parse_normalized(equation, |input| self.parse_equation(input))
+    }
+
+    fn parse_normalized<T>(
+        &self,
+        input: &str,
+        parse: impl Fn(&str) -> Result<T, ChemParseError>,
+    ) -> Result<Normalized<T>, ChemParseError> {
+        let corrected = normalize_case(input, self.get_table());
+        let changed = corrected != input;
+        match parse(&corrected) {
+            Ok(parsed) => Ok(Normalized {
+                input: corrected,
+                corrected: changed,
+                parsed,
+            }),
+            Err(e) if changed => Err(parse(input).err().unwrap_or(e)),
+            Err(e) => Err(e),
+        }
+    }
+}
+
+/// Fixes the letter case of runs of letters that are not valid element symbols as written but
+/// split into valid symbols ignoring case, preferring two-letter symbols, so that `NACL` becomes
+/// `NaCl`. Matching is greedy, so an ambiguous run such as `co` becomes `Co` rather than `CO`.
+/// State symbols and reaction conditions in square brackets after the arrow are kept.
+pub fn normalize_case(input: &str, table: &PeriodicTable) -> String {
+    let chars: Vec<char> = input.chars().collect();
+    let mut fixed = String::with_capacity(input.len());
+    let mut in_conditions = false;
+    let mut index = 0;
+    while index < chars.len() {
+        let c = chars[index];
+        if !c.is_ascii_alphabetic() || in_conditions {
+            match c {
+                '[' if index > 0 && chars[index - 1] == '>' => in_conditions = true,
+                ']' => in_conditions = false,
+                _ => {}
+            }
+            fixed.push(c);
+            index += 1;
+            continue;
+        }
+        let end = (index..chars.len())
+            .find(|&end| !chars[end].is_ascii_alphabetic())
+            .unwrap_or(chars.len());
+        let run: String = chars[index..end].iter().collect();
+        let is_state = index > 0
+            && chars[index - 1] == '('
+            && chars.get(end) == Some(&')')
+            && ["aq", "s", "l", "g"].contains(&run.as_str());
+        match split_symbols(&run, table) {
+            Some(symbols) if !is_state && !valid_as_written(&run, table) => {
+                fixed.push_str(&symbols)
+            }
+            _ => fixed.push_str(&run),
+        }
+        index = end;
+    }
+    fixed
+}
+
+/// Checks whether a run of letters is a sequence of valid element symbols as written.
+fn valid_as_written(run: &str, table: &PeriodicTable) -> bool {
+    let mut symbols: Vec<String> = Vec::new();
+    for c in run.chars() {
+        match symbols.last_mut() {
+            Some(symbol) if c.is_ascii_lowercase() && symbol.len() == 1 => symbol.push(c),
+            _ if c.is_ascii_uppercase() => symbols.push(c.to_string()),
+            _ => return false,
+        }
+    }
+    symbols
+        .iter()
+        .all(|symbol| table.get_element(symbol).is_some())
+}
+
+/// Splits letters into valid element symbols ignoring case, preferring two-letter symbols.
+fn split_symbols(letters: &str, table: &PeriodicTable) -> Option<String> {
+    if letters.is_empty() {
+        return Some(String::new());
+    }
+    [2, 1].into_iter().find_map(|length| {
+        let symbol = letters.get(..length)?;
+        let mut chars = symbol.chars();
+        let symbol: String = chars
+            .next()
+            .map(|first| first.to_ascii_uppercase())
+            .into_iter()
+            .chain(chars.map(|c| c.to_ascii_lowercase()))
+            .collect();
+        table.get_element(&symbol)?;
+        Some(symbol + &split_symbols(&letters[length..], table)?)
+    })
+}
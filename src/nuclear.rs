@@ -0,0 +1,282 @@
+//! # Nuclear equations Module
+//!
+//! This module defines structs that represent nuclear equations with nuclides and particles,
+//! and checks that mass numbers and atomic numbers are conserved. Nuclides found in the isotope
+//! data of a periodic table can be followed through their radioactive decay down to a stable one.
+
+use crate::element::{isotope_key, Isotope, PeriodicTable};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+/// Length of a Julian year in seconds, the unit of half-lives in the isotope data, for times
+/// given in years.
+pub const JULIAN_YEAR: f64 = 31_557_600.0;
+
+/// Mode of radioactive decay of a nuclide.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Deserialize, Serialize)]
+pub enum DecayMode {
+    /// Emission of an alpha particle, lowering the mass number by 4 and the atomic number by 2.
+    #[serde(rename = "alpha")]
+    Alpha,
+    /// Emission of an electron, raising the atomic number by 1.
+    #[serde(rename = "beta-")]
+    BetaMinus,
+    /// Emission of a positron, lowering the atomic number by 1.
+    #[serde(rename = "beta+")]
+    BetaPlus,
+    /// Capture of an inner electron, lowering the atomic number by 1 without emitting a particle.
+    #[serde(rename = "EC", alias = "ec")]
+    ElectronCapture,
+}
+
+impl DecayMode {
+    /// Returns the particle emitted in the decay, or `None` for electron capture.
+    pub fn particle(&self) -> Option<Nuclide> {
+        match self {
+            DecayMode::Alpha => Nuclide::particle("alpha"),
+            DecayMode::BetaMinus => Nuclide::particle("beta-"),
+            DecayMode::BetaPlus => Nuclide::particle("beta+"),
+            DecayMode::ElectronCapture => None,
+        }
+    }
+
+    /// Returns the mass number and atomic number of the daughter of a nuclide with the given mass
+    /// number and atomic number, or `None` if it is too light to decay in this mode.
+    pub fn daughter(&self, mass_number: u16, atomic_number: i16) -> Option<(u16, i16)> {
+        match self {
+            DecayMode::Alpha => Some((mass_number.checked_sub(4)?, atomic_number - 2)),
+            DecayMode::BetaMinus => Some((mass_number, atomic_number + 1)),
+            DecayMode::BetaPlus | DecayMode::ElectronCapture => {
+                (atomic_number > 1).then_some((mass_number, atomic_number - 1))
+            }
+        }
+    }
+}
+
+impl Display for DecayMode {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.write_str(match self {
+            DecayMode::Alpha => "alpha",
+            DecayMode::BetaMinus => "beta-",
+            DecayMode::BetaPlus => "beta+",
+            DecayMode::ElectronCapture => "EC",
+        })
+    }
+}
+
+/// Represents a nuclide or a particle taking part in a nuclear reaction.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Nuclide {
+    /// Notation of the nuclide or particle as written in the equation.
+    pub name: String,
+    /// Mass number (the number of nucleons).
+    pub mass_number: u16,
+    /// Atomic number (the charge number), negative for electrons.
+    pub atomic_number: i16,
+}
+
+impl Nuclide {
+    /// Creates a new Nuclide with the specified notation, mass number and atomic number.
+    pub fn new(name: &str, mass_number: u16, atomic_number: i16) -> Self {
+        Nuclide {
+            name: name.to_string(),
+            mass_number,
+            atomic_number,
+        }
+    }
+
+    /// Returns the particle with the given notation, for example `alpha`, `β-` or `n`.
+    pub fn particle(name: &str) -> Option<Self> {
+        let (mass_number, atomic_number) = match name {
+            "alpha" | "α" => (4, 2),
+            "beta" | "beta-" | "β" | "β-" => (0, -1),
+            "beta+" | "positron" | "β+" => (0, 1),
+            "gamma" | "γ" => (0, 0),
+            "n" => (1, 0),
+            "p" => (1, 1),
+            _ => return None,
+        };
+        Some(Nuclide::new(name, mass_number, atomic_number))
+    }
+
+    /// Returns the isotope of the table this nuclide is, or `None` for particles and nuclides
+    /// without isotope data.
+    pub fn isotope<'a>(&self, table: &'a PeriodicTable) -> Option<&'a Isotope> {
+        let element = table.get_by_atomic_number(u8::try_from(self.atomic_number).ok()?)?;
+        table.get_isotope(&isotope_key(&element.symbol, self.mass_number))
+    }
+
+    /// Returns the half-life of the nuclide in seconds, or `None` if it is stable or has no
+    /// isotope data.
+    pub fn half_life(&self, table: &PeriodicTable) -> Option<f64> {
+        self.isotope(table)?.half_life
+    }
+
+    /// Returns the mode in which the nuclide decays, or `None` if it is stable or has no isotope
+    /// data.
+    pub fn decay_mode(&self, table: &PeriodicTable) -> Option<DecayMode> {
+        self.isotope(table)?.decay_mode
+    }
+
+    /// Returns the products of the radioactive decay of the nuclide: the daughter nuclide, named
+    /// like `^234Th`, followed by the emitted particle, if any. Returns `None` if the nuclide is
+    /// stable or has no isotope data.
+    pub fn decay_products(&self, table: &PeriodicTable) -> Option<Vec<Nuclide>> {
+        let mode = self.decay_mode(table)?;
+        let (mass_number, atomic_number) = mode.daughter(self.mass_number, self.atomic_number)?;
+        let element = table.get_by_atomic_number(u8::try_from(atomic_number).ok()?)?;
+        let name = format!("^{}{}", mass_number, element.symbol);
+        let daughter = Nuclide::new(&name, mass_number, atomic_number);
+        Some(core::iter::once(daughter).chain(mode.particle()).collect())
+    }
+
+    /// Returns the nuclear equation of the radioactive decay of the nuclide, such as
+    /// `^238U -> ^234Th + alpha`. The electron taken up by electron capture is written as `beta-`
+    /// among the reactants, so that the equation is balanced.
+    pub fn decay_equation(&self, table: &PeriodicTable) -> Option<NuclearEquation> {
+        let mut reactants = vec![(1, self.clone())];
+        if self.decay_mode(table)? == DecayMode::ElectronCapture {
+            reactants.extend(Nuclide::particle("beta-").map(|electron| (1, electron)));
+        }
+        let products: Vec<(u32, Nuclide)> = self
+            .decay_products(table)?
+            .into_iter()
+            .map(|nuclide| (1, nuclide))
+            .collect();
+        let equation = format!("{} -> {}", side_names(&reactants), side_names(&products));
+        Some(NuclearEquation::new(equation, reactants, products))
+    }
+
+    /// Returns the decay chain of the nuclide: the nuclide itself followed by the daughter of each
+    /// decay in turn, ending with the first nuclide that is stable or has no isotope data, such as
+    /// `^206Pb` for `^238U`.
+    pub fn decay_chain(&self, table: &PeriodicTable) -> Vec<Nuclide> {
+        let mut chain = vec![self.clone()];
+        while let Some(daughter) = chain
+            .last()
+            .and_then(|nuclide| nuclide.decay_products(table))
+            .and_then(|products| products.into_iter().next())
+        {
+            let seen = chain.iter().any(|nuclide| {
+                (nuclide.mass_number, nuclide.atomic_number)
+                    == (daughter.mass_number, daughter.atomic_number)
+            });
+            if seen {
+                break;
+            }
+            chain.push(daughter);
+        }
+        chain
+    }
+
+    /// Returns the quantity of the nuclide remaining from an initial quantity after a time in
+    /// seconds, which is the initial quantity for stable nuclides. Returns `None` for nuclides
+    /// without isotope data.
+    #[cfg(feature = "std")]
+    pub fn remaining_after(
+        &self,
+        table: &PeriodicTable,
+        initial: f64,
+        seconds: f64,
+    ) -> Option<f64> {
+        Some(match self.isotope(table)?.half_life {
+            Some(half_life) => remaining_quantity(initial, half_life, seconds),
+            None => initial,
+        })
+    }
+}
+
+/// Returns the quantity of a radioactive nuclide remaining from an initial quantity after a time
+/// given in the unit of its half-life, as `initial × 2^(−time / half_life)`.
+#[cfg(feature = "std")]
+pub fn remaining_quantity(initial: f64, half_life: f64, time: f64) -> f64 {
+    initial * 0.5f64.powf(time / half_life)
+}
+
+impl Display for Nuclide {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "{} (A = {}, Z = {})",
+            self.name, self.mass_number, self.atomic_number
+        )
+    }
+}
+
+/// Represents a nuclear equation with its reactants and products.
+#[derive(Debug, Clone, Deserialize, Serialize)]
+pub struct NuclearEquation {
+    /// String representation of the equation.
+    pub equation: String,
+    /// Reactant nuclides with their coefficients.
+    pub reactants: Vec<(u32, Nuclide)>,
+    /// Product nuclides with their coefficients.
+    pub products: Vec<(u32, Nuclide)>,
+}
+
+impl NuclearEquation {
+    /// Creates a new NuclearEquation instance with reactants and products.
+    pub fn new(
+        equation: String,
+        reactants: Vec<(u32, Nuclide)>,
+        products: Vec<(u32, Nuclide)>,
+    ) -> Self {
+        NuclearEquation {
+            equation,
+            reactants,
+            products,
+        }
+    }
+
+    /// Returns the total mass numbers of reactants and products.
+    pub fn mass_numbers(&self) -> (u32, u32) {
+        (
+            side_total(&self.reactants, |n| n.mass_number as i64) as u32,
+            side_total(&self.products, |n| n.mass_number as i64) as u32,
+        )
+    }
+
+    /// Returns the total atomic numbers of reactants and products.
+    pub fn atomic_numbers(&self) -> (i64, i64) {
+        (
+            side_total(&self.reactants, |n| n.atomic_number as i64),
+            side_total(&self.products, |n| n.atomic_number as i64),
+        )
+    }
+
+    /// Checks if the equation is balanced, i.e. both mass numbers and atomic numbers are conserved.
+    pub fn check_equation(&self) -> bool {
+        let (reactant_mass, product_mass) = self.mass_numbers();
+        let (reactant_charge, product_charge) = self.atomic_numbers();
+        reactant_mass == product_mass && reactant_charge == product_charge
+    }
+}
+
+impl Display for NuclearEquation {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        let (reactant_mass, product_mass) = self.mass_numbers();
+        let (reactant_charge, product_charge) = self.atomic_numbers();
+        write!(
+            f,
+            "{} \nMass numbers: {} -> {}\nAtomic numbers: {} -> {}",
+            self.equation, reactant_mass, product_mass, reactant_charge, product_charge
+        )
+    }
+}
+
+/// Writes the names of the nuclides of one side of an equation, separated by `+`.
+fn side_names(side: &[(u32, Nuclide)]) -> String {
+    side.iter()
+        .map(|(_, nuclide)| nuclide.name.as_str())
+        .collect::<Vec<_>>()
+        .join(" + ")
+}
+
+/// Sums a nuclide property over one side of the equation, multiplying it by coefficients.
+fn side_total(side: &[(u32, Nuclide)], value: impl Fn(&Nuclide) -> i64) -> i64 {
+    side.iter()
+        .map(|(coefficient, nuclide)| *coefficient as i64 * value(nuclide))
+        .sum()
+}
@@ -0,0 +1,335 @@
+//! # Parse options Module
+//!
+//! This module defines how tolerant a parser is of the way formulas and equations are written.
+//! Strict parsing, the default, reads input as written from its start, as suits curated files;
+//! lenient parsing accepts stray whitespace, trailing text and typographic arrows, as found in
+//! OCR'd worksheets.
+//! Grammar options add tokens of regional or legacy notations, which are rewritten to those of
+//! the grammar before parsing.
+
+use crate::diagnostic::Diagnostic;
+use crate::element::Arrow;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{normalize_with_offsets, syntax_error, ChemParseError, ChemParser, Rule};
+use alloc::borrow::Cow;
+use serde::Serialize;
+
+/// Typographic arrows accepted by lenient parsing, with the arrow of the grammar they stand for.
+const ALTERNATIVE_ARROWS: [(&str, &str); 8] = [
+    ("-->", "->"),
+    ("→", "->"),
+    ("⟶", "->"),
+    ("⇒", "->"),
+    ("⟹", "->"),
+    ("↔", "<->"),
+    ("⟷", "<->"),
+    ("⇄", "<->"),
+];
+
+/// Arrows of the grammar, which separate the sides of an equation.
+const ARROWS: [&str; 5] = ["->", "<->", "<=>", "⇌", "="];
+
+/// Tolerances of a parser for the way formulas and equations are written.
+///
+/// Without [`ParseOptions::ignore_trailing_text`], parsing still reads the longest formula or
+/// equation at the start of the input, and text after it, such as `garbage` in
+/// `H2 + O2 -> H2O garbage`, is not checked and is left out of the parsed species.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct ParseOptions {
+    /// Trim whitespace around the input and remove it inside formulas and between coefficients
+    /// and formulas, so that `2 H 2 O` reads as `2H2O`.
+    pub ignore_whitespace: bool,
+    /// Leave text after a formula or equation out of the result, so that the equation of
+    /// `H2 + O2 -> H2O; unbalanced` is written `H2 + O2 -> H2O`.
+    pub ignore_trailing_text: bool,
+    /// Accept typographic arrows such as `→`, `⟶`, `-->` and `↔` in equations.
+    pub alternative_arrows: bool,
+}
+
+impl ParseOptions {
+    /// Reads input as written, from its start. This is the default.
+    pub fn strict() -> Self {
+        Self::default()
+    }
+
+    /// Tolerates stray whitespace, trailing text and typographic arrows.
+    pub fn lenient() -> Self {
+        ParseOptions {
+            ignore_whitespace: true,
+            ignore_trailing_text: true,
+            alternative_arrows: true,
+        }
+    }
+}
+
+/// Tokens that a parser accepts in equations besides those of the grammar, such as `==>` for an
+/// arrow or `&` for a plus sign. They are replaced by the tokens of the grammar before parsing,
+/// longest first, so the grammar file does not need to change.
+///
+/// Tokens are matched literally anywhere in an equation. A token that the grammar also reads
+/// inside formulas takes precedence: registering `·` as a plus sign makes `CuSO4·5H2O` two
+/// species, while registering ` · ` with its spaces keeps the dot of hydrates. The positions of
+/// syntax errors refer to the input as written, tokens included.
+#[derive(Debug, Clone, PartialEq, Eq, Default, Serialize)]
+pub struct GrammarOptions {
+    arrows: Vec<(String, Arrow)>,
+    plus_signs: Vec<String>,
+}
+
+impl GrammarOptions {
+    /// Creates options with no additional tokens.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Accepts a token as an arrow of the given kind, as in
+    /// `GrammarOptions::new().with_arrow("==>", Arrow::Irreversible)`. Blank tokens are ignored.
+    pub fn with_arrow(mut self, token: &str, arrow: Arrow) -> Self {
+        if !token.trim().is_empty() {
+            self.arrows.push((token.to_string(), arrow));
+        }
+        self
+    }
+
+    /// Accepts a token as the `+` separating the species of a side. Whitespace at the ends of the
+    /// token is kept around the `+`, so ` & ` reads `A & B` as `A + B`. Blank tokens are ignored.
+    pub fn with_plus_sign(mut self, token: &str) -> Self {
+        if !token.trim().is_empty() {
+            self.plus_signs.push(token.to_string());
+        }
+        self
+    }
+
+    /// Returns the additional arrow tokens with the arrows they stand for.
+    pub fn arrows(&self) -> &[(String, Arrow)] {
+        &self.arrows
+    }
+
+    /// Returns the additional plus sign tokens.
+    pub fn plus_signs(&self) -> &[String] {
+        &self.plus_signs
+    }
+
+    /// Checks whether no additional tokens are accepted.
+    pub fn is_empty(&self) -> bool {
+        self.arrows.is_empty() && self.plus_signs.is_empty()
+    }
+
+    /// Replaces the additional tokens of an equation by those of the grammar, longest first.
+    pub(crate) fn rewrite<'a>(&self, equation: &'a str) -> Cow<'a, str> {
+        if self.is_empty() {
+            return Cow::Borrowed(equation);
+        }
+        Cow::Owned(self.rewrite_with_offsets(equation).0)
+    }
+
+    /// Replaces the additional tokens of an equation like [`GrammarOptions::rewrite`] and returns,
+    /// for every byte of the result and its end, the byte offset in the equation. The equation is
+    /// scanned once, so a replacement is never matched again by a shorter token.
+    pub(crate) fn rewrite_with_offsets(&self, equation: &str) -> (String, Vec<usize>) {
+        // Each token is replaced by the whitespace at its ends and the grammar token between
+        // them, so that the whitespace kept around a `+` maps back to itself.
+        let mut replacements: Vec<(&str, &str, &str, &str)> = self
+            .arrows
+            .iter()
+            .map(|(token, arrow)| (token.as_str(), "", arrow.symbol(), ""))
+            .chain(self.plus_signs.iter().map(|token| {
+                let leading = &token[..token.len() - token.trim_start().len()];
+                let trailing = &token[token.trim_end().len()..];
+                (token.as_str(), leading, "+", trailing)
+            }))
+            .collect();
+        replacements.sort_by_key(|(token, ..)| core::cmp::Reverse(token.len()));
+
+        let mut rewritten = String::with_capacity(equation.len());
+        let mut offsets = Vec::with_capacity(equation.len() + 1);
+        let mut position = 0;
+        while let Some(c) = equation[position..].chars().next() {
+            let rest = &equation[position..];
+            match replacements
+                .iter()
+                .find(|(token, ..)| rest.starts_with(token))
+            {
+                Some((token, leading, symbol, trailing)) => {
+                    let trailing_start = position + token.len() - trailing.len();
+                    rewritten.push_str(leading);
+                    offsets.extend(position..position + leading.len());
+                    rewritten.push_str(symbol);
+                    offsets.extend(core::iter::repeat_n(position + leading.len(), symbol.len()));
+                    rewritten.push_str(trailing);
+                    offsets.extend(trailing_start..trailing_start + trailing.len());
+                    position += token.len();
+                }
+                None => {
+                    rewritten.push(c);
+                    offsets.extend(position..position + c.len_utf8());
+                    position += c.len_utf8();
+                }
+            }
+        }
+        offsets.push(equation.len());
+        (rewritten, offsets)
+    }
+
+    /// Rewrites the additional tokens of an input and normalizes its scripts, returning for every
+    /// byte of the result and its end the byte offset in the input.
+    pub(crate) fn normalize_with_offsets(&self, input: &str) -> (String, Vec<usize>) {
+        let (rewritten, rewritten_offsets) = self.rewrite_with_offsets(input);
+        let (normalized, offsets) = normalize_with_offsets(&rewritten);
+        let offsets = offsets
+            .into_iter()
+            .map(|offset| rewritten_offsets[offset])
+            .collect();
+        (normalized, offsets)
+    }
+}
+
+impl ChemParser {
+    /// Sets the tolerances of the parser for the way formulas and equations are written, as in
+    /// `ChemParser::new().with_options(ParseOptions::lenient())`.
+    pub fn with_options(mut self, options: ParseOptions) -> Self {
+        self.options = options;
+        #[cfg(feature = "std")]
+        self.reset_formula_cache();
+        self
+    }
+
+    /// Returns the tolerances of the parser.
+    pub fn options(&self) -> ParseOptions {
+        self.options
+    }
+
+    /// Sets the tokens that the parser accepts in equations besides those of the grammar, as in
+    /// `ChemParser::new().with_grammar_options(GrammarOptions::new().with_plus_sign(" & "))`.
+    pub fn with_grammar_options(mut self, grammar_options: GrammarOptions) -> Self {
+        self.grammar_options = grammar_options;
+        self
+    }
+
+    /// Returns the tokens that the parser accepts in equations besides those of the grammar.
+    pub fn grammar_options(&self) -> &GrammarOptions {
+        &self.grammar_options
+    }
+
+    /// Creates a syntax error for an input parsed as `parsed` after its grammar tokens were
+    /// rewritten and its scripts normalized, with the position in the input as written. An input
+    /// that lenient parsing changed further is reported as it was parsed.
+    pub(crate) fn rewritten_syntax_error(
+        &self,
+        kind: &str,
+        input: &str,
+        parsed: &str,
+        error: &pest::error::Error<Rule>,
+    ) -> ChemParseError {
+        let (normalized, offsets) = self.grammar_options.normalize_with_offsets(input);
+        if normalized != parsed {
+            return syntax_error(kind, parsed, error);
+        }
+        ChemParseError::SyntaxError(
+            String::from(kind),
+            Box::new(Diagnostic::from_pest(parsed, error).map_to_original(input, &offsets)),
+        )
+    }
+
+    /// Returns the ways to read a formula under the options of the parser, most complete first:
+    /// the input as written, or with whitespace removed, followed by ever shorter runs of its
+    /// leading words when trailing text is ignored.
+    ///
+    /// A standalone `+` or `-` word, or a number that does not follow an element symbol or a
+    /// closing bracket, ends the formula, so `H2 + O2` is not read as a cation and `H2O 2 bottles`
+    /// not as `H2O2`. The words after it are trailing text.
+    pub(crate) fn formula_candidates<'a>(&self, formula: &'a str) -> Vec<Cow<'a, str>> {
+        if !self.options.ignore_whitespace {
+            return vec![Cow::Borrowed(formula)];
+        }
+        let all_words: Vec<&str> = formula.split_whitespace().collect();
+        let end = (1..all_words.len())
+            .find(|&index| ends_formula(all_words[index], all_words[index - 1]))
+            .unwrap_or(all_words.len());
+        let (words, rest) = all_words.split_at(end);
+        if !self.options.ignore_trailing_text && !rest.is_empty() {
+            return vec![Cow::Owned(format!("{} {}", words.concat(), rest.join(" ")))];
+        }
+        let mut candidates = vec![Cow::Owned(words.concat())];
+        if self.options.ignore_trailing_text {
+            candidates.extend(
+                (1..words.len())
+                    .rev()
+                    .map(|n| Cow::Owned(words[..n].concat())),
+            );
+        }
+        candidates
+    }
+
+    /// Rewrites an equation under the options of the parser: the tokens of its grammar options and
+    /// typographic arrows are replaced by those of the grammar, and whitespace is kept only around
+    /// `+` separators, arrows and a trailing note such as `(Δ)`.
+    pub(crate) fn normalize_equation<'a>(&self, equation: &'a str) -> Cow<'a, str> {
+        let mut equation = self.grammar_options.rewrite(equation);
+        if self.options.alternative_arrows {
+            for (alternative, arrow) in ALTERNATIVE_ARROWS {
+                if equation.contains(alternative) {
+                    equation = Cow::Owned(equation.replace(alternative, arrow));
+                }
+            }
+        }
+        if !self.options.ignore_whitespace {
+            return equation;
+        }
+
+        let words: Vec<&str> = equation.split_whitespace().collect();
+        let is_arrow = |word: &str| ARROWS.iter().any(|arrow| word.starts_with(arrow));
+        let is_separator = |word: &str| word == "+" || is_arrow(word);
+        let mut normalized = String::with_capacity(equation.len());
+        // Conditions in brackets over an arrow, such as `-> [Pt]`, are attached to the arrow and
+        // keep their inner spaces, and the species after them is spaced like after the arrow.
+        let mut in_conditions = false;
+        let mut after_conditions = false;
+        for (index, word) in words.iter().enumerate() {
+            let is_note = index + 1 == words.len()
+                && word.starts_with('(')
+                && !["(s)", "(l)", "(g)", "(aq)"].contains(word);
+            let opens_conditions = index > 0
+                && word.starts_with('[')
+                && is_arrow(words[index - 1])
+                && !words[index - 1].contains('[');
+            let spaced = index > 0
+                && !opens_conditions
+                && (in_conditions
+                    || after_conditions
+                    || is_separator(word)
+                    || is_separator(words[index - 1])
+                    || is_note)
+                && !words[index - 1].ends_with('[');
+            if spaced {
+                normalized.push(' ');
+            }
+            normalized.push_str(word);
+
+            after_conditions = false;
+            if opens_conditions || (is_arrow(word) && word.contains('[')) {
+                in_conditions = true;
+            }
+            if in_conditions && word.contains(']') {
+                in_conditions = false;
+                after_conditions = true;
+            }
+        }
+        Cow::Owned(normalized)
+    }
+}
+
+/// Checks whether a word ends a formula read with whitespace removed: a standalone `+` or `-`,
+/// which would otherwise be read as a charge, or a number that does not follow an element
+/// symbol or a closing bracket, which would otherwise be read as a count.
+fn ends_formula(word: &str, previous: &str) -> bool {
+    if word == "+" || word == "-" {
+        return true;
+    }
+    let counts_previous = previous.ends_with([')', ']'])
+        || (previous.starts_with(|c: char| c.is_ascii_uppercase())
+            && previous.len() <= 2
+            && previous.chars().skip(1).all(|c| c.is_ascii_lowercase()));
+    word.chars().all(|c| c.is_ascii_digit()) && !counts_previous
+}
@@ -0,0 +1,136 @@
+//! # Polyatomic ion Module
+//!
+//! This module recognises the ions that a compound is made of, such as 2 `NH4^+` and 1 `SO4^2-`
+//! for `(NH4)2SO4`, using the ion table embedded from `data/ion_names.csv` parsed with the periodic
+//! table of the caller's parser. Monatomic metal cations that the table does not list get the
+//! charge that balances their anion.
+
+use crate::element::{element_symbol, Formula};
+use crate::ionic::{parse_ions, split_salt, IonCount, ParsedIons};
+use crate::names::named_ions;
+use crate::{ChemParseError, ChemParser};
+
+/// Ions of the embedded table, parsed with the table of a parser.
+struct KnownIons {
+    cations: ParsedIons,
+    anions: ParsedIons,
+}
+
+impl KnownIons {
+    fn new(parser: &ChemParser) -> Result<Self, ChemParseError> {
+        let ions = parse_ions(parser, named_ions().iter().map(|(_, ion)| ion.as_str()))?;
+        let (cations, anions) = ions.into_iter().partition(|(_, ion)| ion.charge > 0);
+        Ok(KnownIons { cations, anions })
+    }
+}
+
+/// Returns the ions of the embedded table that have more than one atom, as formula and name,
+/// such as `SO4^2-` and `sulfate`.
+///
+/// Fails if an ion of the table cannot be parsed with the parser.
+pub fn polyatomic_ions(
+    parser: &ChemParser,
+) -> Result<Vec<(&'static str, &'static str)>, ChemParseError> {
+    let ions = KnownIons::new(parser)?;
+    Ok(ions
+        .cations
+        .iter()
+        .chain(&ions.anions)
+        .filter(|(_, ion)| ion.elements.values().sum::<u32>() > 1)
+        .filter_map(|(written, _)| {
+            named_ions()
+                .iter()
+                .find(|(_, formula)| formula == written)
+                .map(|(name, _)| (*written, name.as_str()))
+        })
+        .collect())
+}
+
+impl Formula {
+    /// Splits a neutral compound into the ions it is made of, with their counts, such as
+    /// `[(2, NH4^+), (1, SO4^2-)]` for `(NH4)2SO4`, `[(1, Pb^4+), (2, O^2-)]` for `PbO2` and
+    /// `[(2, H^+), (1, SO4^2-)]` for `H2SO4`. An ion of the table is returned as itself.
+    ///
+    /// Cations other than `H^+` are tried first, then a metal with the charge that balances an
+    /// anion of the table, and hydrogen last. Only the alkali and alkaline earth metals are taken
+    /// to form peroxides. Returns `None` if the formula is not made of one cation and one anion.
+    ///
+    /// The ions and elements are taken from the periodic table of the parser. Fails if an ion of
+    /// the table cannot be parsed with it.
+    pub fn identify_ions(
+        &self,
+        parser: &ChemParser,
+    ) -> Result<Option<Vec<(u32, Formula)>>, ChemParseError> {
+        let ions = KnownIons::new(parser)?;
+        if self.charge != 0 {
+            return Ok(ions
+                .cations
+                .iter()
+                .chain(&ions.anions)
+                .find(|(_, ion)| ion.same_composition(self))
+                .map(|(_, ion)| vec![(1, ion.clone())]));
+        }
+
+        let table = parser.get_table();
+        let forms_peroxide = self.elements.keys().any(|key| {
+            table
+                .get_element(element_symbol(key))
+                .is_some_and(|element| element.is_metal() && element.group <= Some(2))
+        });
+        let anions: ParsedIons = ions
+            .anions
+            .iter()
+            .filter(|(ion, _)| forms_peroxide || *ion != "O2^2-")
+            .cloned()
+            .collect();
+        let (hydrogen, cations): (ParsedIons, ParsedIons) = ions
+            .cations
+            .iter()
+            .cloned()
+            .partition(|(ion, _)| *ion == "H^+");
+
+        let owned = |[(cation_count, _, cation), (anion_count, _, anion)]: [IonCount; 2]| {
+            vec![(cation_count, cation.clone()), (anion_count, anion.clone())]
+        };
+        Ok(split_salt(self, &cations, &anions)
+            .map(owned)
+            .or_else(|| self.metal_salt(parser, &anions))
+            .or_else(|| split_salt(self, &hydrogen, &anions).map(owned)))
+    }
+
+    /// Splits a compound of one metal and an anion, with the charge of the metal cation inferred
+    /// from the anion.
+    fn metal_salt(&self, parser: &ChemParser, anions: &ParsedIons) -> Option<Vec<(u32, Formula)>> {
+        let (metal_key, metal_count) = self.elements.iter().find(|(key, _)| {
+            parser
+                .get_table()
+                .get_element(element_symbol(key))
+                .is_some_and(|element| element.is_metal())
+        })?;
+        let mut rest = self.elements.clone();
+        rest.remove(metal_key);
+        for (_, anion) in anions {
+            let (key, count) = anion.elements.iter().next()?;
+            let total = rest.get(key).copied().unwrap_or(0);
+            if total == 0 || total % count != 0 {
+                continue;
+            }
+            let units = total / count;
+            let charge = units * anion.charge.unsigned_abs();
+            if charge % metal_count != 0
+                || anion
+                    .checked_mul(units)
+                    .is_none_or(|scaled| scaled.elements != rest)
+            {
+                continue;
+            }
+            let cation = match charge / metal_count {
+                1 => format!("{}^+", metal_key),
+                charge => format!("{}^{}+", metal_key, charge),
+            };
+            let cation = parser.parse_formula(&cation).ok()?;
+            return Some(vec![(*metal_count, cation), (units, anion.clone())]);
+        }
+        None
+    }
+}
@@ -0,0 +1,120 @@
+//! # Predict Module
+//!
+//! This module predicts the products of reactions of simple classes: the combination of a metal
+//! or hydrogen with a nonmetal, the combustion of hydrocarbons, the neutralization of an acid by a
+//! hydroxide and single replacement, and writes the balanced equation.
+
+use crate::activity::{is_element, rank, salt_formula, METAL_ACTIVITY_SERIES};
+use crate::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use crate::element::{Equation, Formula};
+use crate::{ChemParseError, ChemParser};
+
+/// Nonmetals as free elements with the anion each forms in a combination with a metal.
+pub const NONMETAL_ANIONS: &[(&str, &str)] = &[
+    ("F2", "F^-"),
+    ("Cl2", "Cl^-"),
+    ("Br2", "Br^-"),
+    ("I2", "I^-"),
+    ("O2", "O^2-"),
+    ("S", "S^2-"),
+    ("N2", "N^3-"),
+    ("P", "P^3-"),
+];
+
+impl ChemParser {
+    /// Predicts the products of reactants separated by ` + `, such as `Na + Cl2`, and returns
+    /// the balanced equation, `2Na + Cl2 -> 2NaCl`. The recognized reaction classes are:
+    ///
+    /// - combination of a metal or hydrogen with a nonmetal of [`NONMETAL_ANIONS`], giving the
+    ///   compound of the cation the metal forms in [`METAL_ACTIVITY_SERIES`],
+    /// - combustion of a compound of carbon, hydrogen and optionally oxygen in `O2`, giving `CO2`
+    ///   and `H2O`,
+    /// - neutralization of an acid by a base, as written by [`ChemParser::neutralize`],
+    /// - single replacement, as predicted by [`ChemParser::predict_single_replacement`].
+    ///
+    /// Fails with [`ChemParseError::CannotPredict`] if the reactants match no class, or if a
+    /// single replacement does not take place.
+    pub fn predict_products(&self, reactants: &str) -> Result<Equation, ChemParseError> {
+        let reactants = reactants.trim();
+        let cannot = |reason: &str| {
+            ChemParseError::CannotPredict(reactants.to_string(), String::from(reason))
+        };
+        let written: Vec<&str> = reactants.split(" + ").map(str::trim).collect();
+        let formulas = written
+            .iter()
+            .map(|formula| self.parse_formula(formula))
+            .collect::<Result<Vec<_>, _>>()?;
+        let [first, second] = formulas.as_slice() else {
+            return Err(cannot("expected two reactants"));
+        };
+
+        let burnt = combustion(first, second).or_else(|| combustion(second, first));
+        let products = match (is_element(first), is_element(second)) {
+            _ if burnt.is_some() => burnt,
+            (true, true) => self
+                .combination(first, second)
+                .or_else(|| self.combination(second, first)),
+            (false, false) => match (
+                self.classify_acid_base(first)?,
+                self.classify_acid_base(second)?,
+            ) {
+                (Some(first_class), Some(second_class))
+                    if first_class.is_acid() != second_class.is_acid() =>
+                {
+                    let (acid, base) = match first_class.is_acid() {
+                        true => (first, second),
+                        false => (second, first),
+                    };
+                    Some(self.neutralization_products(acid, base)?)
+                }
+                _ => None,
+            },
+            _ => {
+                return match self.predict_single_replacement(&formulas) {
+                    Ok(Some(equation)) => Ok(equation),
+                    Ok(None) => Err(cannot(
+                        "the element is less active than the one it would displace, so no reaction takes place",
+                    )),
+                    Err(ChemParseError::EvaluationError(_, reason)) => Err(cannot(&reason)),
+                    Err(e) => Err(e),
+                }
+            }
+        }
+        .ok_or_else(|| cannot("the reactants match no known reaction class"))?;
+
+        let equation = self.parse_equation(&format!(
+            "{} -> {}",
+            written.join(" + "),
+            products.join(" + ")
+        ))?;
+        match equation.balance(DEFAULT_MAX_COEFFICIENT)? {
+            Balance::AlreadyBalanced => Ok(equation),
+            Balance::Balanced(balanced) => Ok(balanced),
+            _ => Err(cannot("the products cannot be balanced")),
+        }
+    }
+
+    /// Returns the product of a metal or hydrogen and a nonmetal, if they are those.
+    fn combination(&self, metal: &Formula, nonmetal: &Formula) -> Option<Vec<String>> {
+        let metals = self.activity_series(METAL_ACTIVITY_SERIES).ok()?;
+        let nonmetals = self.activity_series(NONMETAL_ANIONS).ok()?;
+        let (_, cation) = METAL_ACTIVITY_SERIES[rank(&metals, metal)?];
+        let (_, anion) = NONMETAL_ANIONS[rank(&nonmetals, nonmetal)?];
+        Some(vec![salt_formula(cation, anion)])
+    }
+}
+
+/// Returns the products of burning a fuel in oxygen, if the fuel is a compound of carbon,
+/// hydrogen and optionally oxygen.
+fn combustion(fuel: &Formula, oxygen: &Formula) -> Option<Vec<String>> {
+    let is_oxygen =
+        oxygen.charge == 0 && oxygen.elements.len() == 1 && oxygen.elements.contains_key("O");
+    let is_fuel = fuel.charge == 0
+        && fuel.elements.contains_key("C")
+        && fuel.elements.contains_key("H")
+        && fuel
+            .elements
+            .keys()
+            .all(|key| ["C", "H", "O"].contains(&key.as_str()));
+    (is_oxygen && is_fuel).then(|| vec![String::from("CO2"), String::from("H2O")])
+}
@@ -0,0 +1,111 @@
+//! # Progress Module
+//!
+//! This module reports the progress of long-running jobs over large inputs, such as parsing a file
+//! of equations, so that a frontend can show a progress bar with the remaining time and errors.
+
+/// Receives the progress of a job over an input of known size in bytes.
+pub trait ProgressSink {
+    /// Starts the job over an input of `total` bytes.
+    fn start(&mut self, total: u64);
+
+    /// Reports that the input has been processed up to `position` bytes and whether the last
+    /// item, such as a line or a row, failed.
+    fn advance(&mut self, position: u64, failed: bool);
+
+    /// Finishes the job.
+    fn finish(&mut self);
+}
+
+/// Progress sink that ignores the progress.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct NoProgress;
+
+impl ProgressSink for NoProgress {
+    fn start(&mut self, _total: u64) {}
+
+    fn advance(&mut self, _position: u64, _failed: bool) {}
+
+    fn finish(&mut self) {}
+}
+
+/// Progress sink that counts processed items and errors, for example for a summary.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ProgressCounter {
+    /// Size of the input in bytes.
+    pub total: u64,
+    /// Number of bytes processed so far.
+    pub position: u64,
+    /// Number of processed items.
+    pub items: u64,
+    /// Number of items that failed.
+    pub errors: u64,
+    /// Whether the job has finished.
+    pub finished: bool,
+}
+
+impl ProgressSink for ProgressCounter {
+    fn start(&mut self, total: u64) {
+        self.total = total;
+    }
+
+    fn advance(&mut self, position: u64, failed: bool) {
+        self.position = position;
+        self.items += 1;
+        if failed {
+            self.errors += 1;
+        }
+    }
+
+    fn finish(&mut self) {
+        self.finished = true;
+    }
+}
+
+/// Progress bar drawn on stderr with the processed size, the remaining time and the number of errors.
+#[cfg(feature = "progress")]
+pub struct ProgressBar {
+    bar: indicatif::ProgressBar,
+    errors: u64,
+}
+
+#[cfg(feature = "progress")]
+impl ProgressBar {
+    /// Creates a hidden progress bar, which is shown when the job starts.
+    pub fn new() -> Self {
+        let bar = indicatif::ProgressBar::new(0);
+        bar.set_style(
+            indicatif::ProgressStyle::with_template(
+                "{bar:40} {bytes}/{total_bytes} ({eta} left) {msg}",
+            )
+            .expect("progress bar template is valid"),
+        );
+        ProgressBar { bar, errors: 0 }
+    }
+}
+
+#[cfg(feature = "progress")]
+impl Default for ProgressBar {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "progress")]
+impl ProgressSink for ProgressBar {
+    fn start(&mut self, total: u64) {
+        self.bar.set_length(total);
+        self.bar.set_message("0 errors");
+    }
+
+    fn advance(&mut self, position: u64, failed: bool) {
+        if failed {
+            self.errors += 1;
+            self.bar.set_message(format!("{} errors", self.errors));
+        }
+        self.bar.set_position(position);
+    }
+
+    fn finish(&mut self) {
+        self.bar.finish();
+    }
+}
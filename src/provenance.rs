@@ -0,0 +1,59 @@
+//! # Provenance Module
+//!
+//! This module describes where a result came from: the crate version, a checksum of the
+//! periodic table dataset and the options used, so that reports can be traced and reproduced.
+
+use crate::element::PeriodicTable;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use alloc::collections::BTreeMap;
+use core::fmt::Display;
+use serde::{Deserialize, Serialize};
+
+/// Represents the crate version, dataset checksum and options that produced a result.
+#[derive(Debug, Clone, PartialEq, Eq, Deserialize, Serialize)]
+pub struct Provenance {
+    /// Version of the chemistry_parser crate.
+    pub version: String,
+    /// Checksum of the periodic table dataset, see [`PeriodicTable::checksum`].
+    pub dataset: String,
+    /// Options used to produce the result, by name.
+    #[serde(default)]
+    pub options: BTreeMap<String, String>,
+}
+
+impl Provenance {
+    /// Creates a new Provenance for results computed with the given periodic table.
+    pub fn new(table: &PeriodicTable) -> Self {
+        Provenance {
+            version: env!("CARGO_PKG_VERSION").to_string(),
+            dataset: table.checksum(),
+            options: BTreeMap::new(),
+        }
+    }
+
+    /// Records an option used to produce the result.
+    pub fn with_option(mut self, name: &str, value: &str) -> Self {
+        self.options.insert(name.to_string(), value.to_string());
+        self
+    }
+}
+
+impl Display for Provenance {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(
+            f,
+            "chemistry_parser {}, dataset {}",
+            self.version, self.dataset
+        )?;
+        if !self.options.is_empty() {
+            let options: Vec<String> = self
+                .options
+                .iter()
+                .map(|(name, value)| format!("{}={}", name, value))
+                .collect();
+            write!(f, ", options: {}", options.join(" "))?;
+        }
+        Ok(())
+    }
+}
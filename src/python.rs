@@ -0,0 +1,213 @@
+//! # Python Module
+//!
+//! This module exposes the parser to Python through PyO3 as the `chemistry_parser` extension
+//! module, with the `ChemParser`, `Formula` and `Equation` classes. Errors of the parser are
+//! raised as `chemistry_parser.ChemParseError`, a subclass of `ValueError`. The module is built
+//! with maturin, which reads its settings from `pyproject.toml`.
+
+use crate::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use crate::element::{composition_key, Equation, Formula};
+use pyo3::create_exception;
+use pyo3::exceptions::PyValueError;
+use pyo3::prelude::*;
+use std::collections::BTreeMap;
+
+create_exception!(
+    chemistry_parser,
+    ChemParseError,
+    PyValueError,
+    "Raised when a formula or an equation cannot be parsed, checked or balanced."
+);
+
+/// Converts an error of the parser into a Python `ChemParseError`.
+fn to_py_err(error: crate::ChemParseError) -> PyErr {
+    ChemParseError::new_err(error.to_string())
+}
+
+/// Parser of chemical formulas and equations.
+#[pyclass(name = "ChemParser", module = "chemistry_parser")]
+pub struct PyChemParser {
+    parser: crate::ChemParser,
+}
+
+#[pymethods]
+impl PyChemParser {
+    /// Creates a parser with the embedded periodic table.
+    #[new]
+    fn new() -> PyResult<Self> {
+        let parser = crate::ChemParser::try_new().map_err(to_py_err)?;
+        Ok(Self { parser })
+    }
+
+    /// Parses a formula, such as `H2SO4`.
+    fn parse_formula(&self, formula: &str) -> PyResult<PyFormula> {
+        let formula = self.parser.parse_formula(formula).map_err(to_py_err)?;
+        Ok(PyFormula { formula })
+    }
+
+    /// Parses an equation, such as `2H2 + O2 -> 2H2O`.
+    fn parse_equation(&self, equation: &str) -> PyResult<PyEquation> {
+        let equation = self.parser.parse_equation(equation).map_err(to_py_err)?;
+        Ok(PyEquation { equation })
+    }
+
+    fn __repr__(&self) -> &'static str {
+        "ChemParser()"
+    }
+}
+
+/// Parsed chemical formula.
+#[pyclass(name = "Formula", module = "chemistry_parser", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyFormula {
+    formula: Formula,
+}
+
+#[pymethods]
+impl PyFormula {
+    /// Formula as it was written.
+    #[getter]
+    fn formula(&self) -> &str {
+        &self.formula.formula
+    }
+
+    /// Molecular mass in g/mol.
+    #[getter]
+    fn mass(&self) -> f64 {
+        self.formula.mass
+    }
+
+    /// Electric charge, zero for neutral species.
+    #[getter]
+    fn charge(&self) -> i32 {
+        self.formula.charge
+    }
+
+    /// Number of atoms of each element.
+    #[getter]
+    fn elements(&self) -> BTreeMap<String, u32> {
+        self.formula.elements.clone()
+    }
+
+    /// Formula in Hill order, such as `H2O4S` for `H2SO4`.
+    fn canonical(&self) -> String {
+        self.formula.canonical()
+    }
+
+    /// Formula as JSON.
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.formula).map_err(|e| ChemParseError::new_err(e.to_string()))
+    }
+
+    /// Whether both formulas have the same atoms and charge, however they are written.
+    fn __eq__(&self, other: &Self) -> bool {
+        composition_key(&self.formula) == composition_key(&other.formula)
+    }
+
+    fn __str__(&self) -> &str {
+        &self.formula.formula
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Formula('{}')", self.formula.formula)
+    }
+}
+
+/// Parsed chemical equation.
+#[pyclass(name = "Equation", module = "chemistry_parser", skip_from_py_object)]
+#[derive(Clone)]
+pub struct PyEquation {
+    equation: Equation,
+}
+
+#[pymethods]
+impl PyEquation {
+    /// Equation as it was written.
+    #[getter]
+    fn equation(&self) -> &str {
+        &self.equation.equation
+    }
+
+    /// Reactants in the order they are written, as `(coefficient, Formula)` pairs. Fractional
+    /// coefficients are multiplied by `denominator`.
+    #[getter]
+    fn reactants(&self) -> Vec<(u32, PyFormula)> {
+        terms(&self.equation.reactant_terms)
+    }
+
+    /// Products in the order they are written, as `(coefficient, Formula)` pairs. Fractional
+    /// coefficients are multiplied by `denominator`.
+    #[getter]
+    fn products(&self) -> Vec<(u32, PyFormula)> {
+        terms(&self.equation.product_terms)
+    }
+
+    /// Common denominator of the coefficients, 1 unless they are written as fractions.
+    #[getter]
+    fn denominator(&self) -> u32 {
+        self.equation.denominator
+    }
+
+    /// Whether the equation conserves every element and the charge.
+    fn is_balanced(&self) -> bool {
+        self.equation.validate().is_ok()
+    }
+
+    /// Raises a `ChemParseError` listing the imbalances unless the equation is balanced.
+    fn validate(&self) -> PyResult<()> {
+        self.equation.validate().map_err(to_py_err)
+    }
+
+    /// Returns the equation with the smallest balancing coefficients, each at most
+    /// `max_coefficient`.
+    #[pyo3(signature = (max_coefficient = DEFAULT_MAX_COEFFICIENT))]
+    fn balance(&self, max_coefficient: u32) -> PyResult<Self> {
+        match self.equation.balance(max_coefficient).map_err(to_py_err)? {
+            Balance::AlreadyBalanced => Ok(self.clone()),
+            Balance::Balanced(equation) => Ok(Self { equation }),
+            Balance::Independent(_) => Err(ChemParseError::new_err(
+                "the equation combines independent reactions and has no single balance",
+            )),
+            Balance::Impossible(keys) => Err(ChemParseError::new_err(format!(
+                "the equation cannot be balanced, {} appear on only one side",
+                keys.join(", ")
+            ))),
+        }
+    }
+
+    /// Equation as JSON.
+    fn to_json(&self) -> PyResult<String> {
+        serde_json::to_string(&self.equation).map_err(|e| ChemParseError::new_err(e.to_string()))
+    }
+
+    fn __str__(&self) -> &str {
+        &self.equation.equation
+    }
+
+    fn __repr__(&self) -> String {
+        format!("Equation('{}')", self.equation.equation)
+    }
+}
+
+/// Converts terms into `(coefficient, Formula)` pairs.
+fn terms(terms: &[crate::element::Term]) -> Vec<(u32, PyFormula)> {
+    terms
+        .iter()
+        .map(|term| {
+            let formula = PyFormula {
+                formula: term.formula.clone(),
+            };
+            (term.coefficient, formula)
+        })
+        .collect()
+}
+
+/// The `chemistry_parser` Python module.
+#[pymodule]
+fn chemistry_parser(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add_class::<PyChemParser>()?;
+    m.add_class::<PyFormula>()?;
+    m.add_class::<PyEquation>()?;
+    m.add("ChemParseError", m.py().get_type::<ChemParseError>())?;
+    Ok(())
+}
@@ -0,0 +1,194 @@
+//! # Query Module
+//!
+//! This module selects elements of a periodic table by their physical properties, for example
+//! `table.query().atomic_mass(10.0..50.0).group(1).melting_point_below(500.0).elements()`, or by a
+//! search expression such as `mass>200, group=17`.
+
+use crate::element::{Element, PeriodicTable};
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::temperature::TransitionTemperature;
+use crate::ChemParseError;
+use core::ops::RangeBounds;
+
+/// Condition that an element must satisfy to match a query.
+type Condition<'a> = Box<dyn Fn(&Element) -> bool + 'a>;
+
+/// Builder of a search over the elements of a periodic table.
+///
+/// Every condition narrows the search, and elements with an unknown value of a queried property
+/// do not match.
+pub struct ElementQuery<'a> {
+    table: &'a PeriodicTable,
+    conditions: Vec<Condition<'a>>,
+}
+
+impl<'a> ElementQuery<'a> {
+    /// Creates a query that matches every element of the table.
+    pub fn new(table: &'a PeriodicTable) -> Self {
+        ElementQuery {
+            table,
+            conditions: Vec::new(),
+        }
+    }
+
+    /// Keeps elements whose atomic number is in the range.
+    pub fn atomic_number(self, range: impl RangeBounds<u8> + 'a) -> Self {
+        self.filter(move |element| range.contains(&element.atomic_number))
+    }
+
+    /// Keeps elements whose atomic mass is in the range.
+    pub fn atomic_mass(self, range: impl RangeBounds<f64> + 'a) -> Self {
+        self.filter(move |element| range.contains(&element.atomic_mass))
+    }
+
+    /// Keeps elements with a known density in the range.
+    pub fn density(self, range: impl RangeBounds<f64> + 'a) -> Self {
+        self.filter(move |element| element.density > 0.0 && range.contains(&element.density))
+    }
+
+    /// Keeps elements of the given group (column) of the periodic table.
+    pub fn group(self, group: u8) -> Self {
+        self.filter(move |element| element.group == Some(group))
+    }
+
+    /// Keeps elements of the given period (row) of the periodic table.
+    pub fn period(self, period: u8) -> Self {
+        self.filter(move |element| element.period() == period)
+    }
+
+    /// Keeps metals.
+    pub fn metals(self) -> Self {
+        self.filter(Element::is_metal)
+    }
+
+    /// Keeps nonmetals.
+    pub fn nonmetals(self) -> Self {
+        self.filter(Element::is_nonmetal)
+    }
+
+    /// Keeps elements with a known melting point in the range, in kelvins.
+    pub fn melting_point(self, range: impl RangeBounds<f64> + 'a) -> Self {
+        self.filter(move |element| {
+            melting_point(element).is_some_and(|melting_point| range.contains(&melting_point))
+        })
+    }
+
+    /// Keeps elements with a known melting point below the temperature, in kelvins.
+    pub fn melting_point_below(self, temperature: f64) -> Self {
+        self.melting_point(..temperature)
+    }
+
+    /// Keeps elements with a known melting point above the temperature, in kelvins.
+    pub fn melting_point_above(self, temperature: f64) -> Self {
+        self.filter(move |element| melting_point(element).is_some_and(|value| value > temperature))
+    }
+
+    /// Keeps elements with a known boiling point in the range, in kelvins.
+    pub fn boiling_point(self, range: impl RangeBounds<f64> + 'a) -> Self {
+        self.filter(move |element| {
+            boiling_point(element).is_some_and(|boiling_point| range.contains(&boiling_point))
+        })
+    }
+
+    /// Keeps elements with a known boiling point below the temperature, in kelvins.
+    pub fn boiling_point_below(self, temperature: f64) -> Self {
+        self.boiling_point(..temperature)
+    }
+
+    /// Keeps elements with a known boiling point above the temperature, in kelvins.
+    pub fn boiling_point_above(self, temperature: f64) -> Self {
+        self.filter(move |element| boiling_point(element).is_some_and(|value| value > temperature))
+    }
+
+    /// Keeps elements that satisfy a search expression, comparisons joined by commas or `and` such
+    /// as `mass>200` or `group=17, period<=4`. The properties are `number`, `mass`, `density`,
+    /// `group`, `period`, `electronegativity`, and `melting` and `boiling` in kelvins, and the
+    /// operators `<`, `<=`, `>`, `>=`, `=` and `!=`.
+    ///
+    /// Fails with [`ChemParseError::ParsingError`] for an unknown property or operator, or a value
+    /// that is not a number.
+    pub fn search(mut self, expression: &str) -> Result<Self, ChemParseError> {
+        for comparison in expression.split(',').flat_map(|part| part.split(" and ")) {
+            let comparison = comparison.trim();
+            if comparison.is_empty() {
+                continue;
+            }
+            let invalid = |reason: String| {
+                ChemParseError::ParsingError(format!("element search \"{}\"", comparison), reason)
+            };
+            let is_operator = |c: char| "<>=!".contains(c);
+            let start = comparison
+                .find(is_operator)
+                .ok_or_else(|| invalid(String::from("expected a comparison such as mass>200")))?;
+            let end = comparison[start..]
+                .find(|c| !is_operator(c))
+                .map_or(comparison.len(), |length| start + length);
+            let property = comparison[..start].trim().to_lowercase();
+            let value: f64 = comparison[end..]
+                .trim()
+                .parse()
+                .map_err(|_| invalid(String::from("the value is not a number")))?;
+
+            let value_of: fn(&Element) -> Option<f64> = match property.as_str() {
+                "number" | "atomic_number" => |element| Some(f64::from(element.atomic_number)),
+                "mass" | "atomic_mass" => |element| Some(element.atomic_mass),
+                "density" => |element| (element.density > 0.0).then_some(element.density),
+                "group" => |element| element.group.map(f64::from),
+                "period" => |element| Some(f64::from(element.period())),
+                "electronegativity" => |element| element.electronegativity,
+                "melting" | "melting_point" => melting_point,
+                "boiling" | "boiling_point" => boiling_point,
+                _ => return Err(invalid(format!("unknown property {}", property))),
+            };
+            let compare: fn(f64, f64) -> bool = match &comparison[start..end] {
+                "<" => |actual, value| actual < value,
+                "<=" => |actual, value| actual <= value,
+                ">" => |actual, value| actual > value,
+                ">=" => |actual, value| actual >= value,
+                "=" | "==" => |actual, value| actual == value,
+                "!=" => |actual, value| actual != value,
+                operator => return Err(invalid(format!("unknown operator {}", operator))),
+            };
+            self = self.filter(move |element| {
+                value_of(element).is_some_and(|actual| compare(actual, value))
+            });
+        }
+        Ok(self)
+    }
+
+    /// Keeps elements that satisfy a custom condition.
+    pub fn filter(mut self, condition: impl Fn(&Element) -> bool + 'a) -> Self {
+        self.conditions.push(Box::new(condition));
+        self
+    }
+
+    /// Returns the matching elements in order of atomic number.
+    pub fn elements(&self) -> Vec<&'a Element> {
+        self.table
+            .iter()
+            .filter(|element| self.conditions.iter().all(|condition| condition(element)))
+            .collect()
+    }
+
+    /// Returns the number of matching elements.
+    pub fn count(&self) -> usize {
+        self.elements().len()
+    }
+}
+
+fn melting_point(element: &Element) -> Option<f64> {
+    element
+        .melting_point
+        .as_ref()
+        .and_then(TransitionTemperature::temperature)
+        .map(|temperature| temperature.in_kelvins())
+}
+
+fn boiling_point(element: &Element) -> Option<f64> {
+    element
+        .boiling_point
+        .as_ref()
+        .and_then(TransitionTemperature::temperature)
+        .map(|temperature| temperature.in_kelvins())
+}
@@ -0,0 +1,313 @@
+//! # Redox Module
+//!
+//! This module balances redox equations by the half-reaction method: it splits an ionic equation
+//! into its oxidation and reduction half-reactions, balances each with water, hydrogen or hydroxide
+//! ions and electrons for an acidic or basic medium, and adds them up so that the electrons cancel.
+
+use crate::balance::{gcd, reduce};
+use crate::element::{composition_key, Equation, Formula, Term};
+use crate::{ChemParseError, ChemParser};
+use serde::Serialize;
+
+/// Medium of a redox reaction, which decides the ions that balance hydrogen and oxygen.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "lowercase")]
+pub enum Medium {
+    /// Acidic solution, balanced with `H2O` and `H^+`.
+    #[default]
+    Acidic,
+    /// Basic solution, balanced with `H2O` and `OH^-`.
+    Basic,
+}
+
+/// Redox equation balanced by the half-reaction method.
+#[derive(Debug, Clone)]
+pub struct RedoxBalance {
+    /// Balanced oxidation half-reaction, which produces electrons.
+    pub oxidation: Equation,
+    /// Balanced reduction half-reaction, which consumes electrons.
+    pub reduction: Equation,
+    /// Sum of the half-reactions with the electrons cancelled, in lowest terms.
+    pub balanced: Equation,
+}
+
+/// Species of a redox equation with its amount on the product side, negative for reactants.
+type Amounts = Vec<(Formula, i128)>;
+
+impl ChemParser {
+    /// Balances a redox equation written with its ions, such as `MnO4^- + Fe^2+ -> Mn^2+ + Fe^3+`,
+    /// by the half-reaction method. Water, `H^+` and `OH^-` may be left out of the equation; they are
+    /// added where the medium needs them.
+    ///
+    /// Species are split into half-reactions by the elements other than hydrogen and oxygen that
+    /// they share, so disproportionations, where one element is both oxidized and reduced, are not
+    /// supported. Fails if the equation does not split into one oxidation and one reduction.
+    pub fn balance_redox(
+        &self,
+        equation: &Equation,
+        medium: Medium,
+    ) -> Result<RedoxBalance, ChemParseError> {
+        let error = |reason: &str| {
+            ChemParseError::BalancingError(equation.equation.clone(), String::from(reason))
+        };
+        let water = self.parse_formula("H2O")?;
+        let ion = match medium {
+            Medium::Acidic => self.parse_formula("H^+")?,
+            Medium::Basic => self.parse_formula("OH^-")?,
+        };
+        let auxiliary: Vec<String> = ["H2O", "H^+", "OH^-"]
+            .iter()
+            .map(|formula| self.parse_formula(formula).map(|f| composition_key(&f)))
+            .collect::<Result<_, _>>()?;
+
+        let species: Vec<(Formula, i128)> = equation
+            .reactant_terms
+            .iter()
+            .map(|term| (term.formula.clone(), 1))
+            .chain(
+                equation
+                    .product_terms
+                    .iter()
+                    .map(|term| (term.formula.clone(), -1)),
+            )
+            .filter(|(formula, _)| {
+                !formula.elements.is_empty() && !auxiliary.contains(&composition_key(formula))
+            })
+            .collect();
+
+        let groups = half_reaction_groups(&species);
+        if groups.len() != 2 {
+            return Err(error(
+                "the species do not split into an oxidation and a reduction half-reaction",
+            ));
+        }
+        let mut halves = Vec::new();
+        for group in groups {
+            let members: Vec<&(Formula, i128)> =
+                group.iter().map(|&index| &species[index]).collect();
+            if !members.iter().any(|(_, sign)| *sign > 0)
+                || !members.iter().any(|(_, sign)| *sign < 0)
+            {
+                return Err(error("a half-reaction has no reactant or no product"));
+            }
+            let amounts = balance_half(&members, &water, &ion)
+                .ok_or_else(|| error("a half-reaction cannot be balanced"))?;
+            halves.push(amounts);
+        }
+
+        let electrons = |amounts: &Amounts| -> i128 {
+            amounts
+                .iter()
+                .find(|(formula, _)| formula.elements.is_empty())
+                .map_or(0, |(_, amount)| *amount)
+        };
+        let (oxidation, reduction) = match (electrons(&halves[0]), electrons(&halves[1])) {
+            (first, second) if first > 0 && second < 0 => (&halves[0], &halves[1]),
+            (first, second) if first < 0 && second > 0 => (&halves[1], &halves[0]),
+            _ => return Err(error("the equation is not a redox reaction")),
+        };
+        let (lost, gained) = (electrons(oxidation), -electrons(reduction));
+        let divisor = gcd(lost, gained);
+
+        let mut total: Amounts = Vec::new();
+        for (amounts, factor) in [(oxidation, gained / divisor), (reduction, lost / divisor)] {
+            for (formula, amount) in amounts {
+                let key = composition_key(formula);
+                match total
+                    .iter_mut()
+                    .find(|(other, _)| composition_key(other) == key)
+                {
+                    Some((_, sum)) => *sum += amount * factor,
+                    None => total.push((formula.clone(), amount * factor)),
+                }
+            }
+        }
+        total.retain(|(_, amount)| *amount != 0);
+        // Species keep their order in the equation, followed by water and the ion of the medium.
+        let rank = |formula: &Formula| -> usize {
+            let key = composition_key(formula);
+            species
+                .iter()
+                .position(|(other, _)| composition_key(other) == key)
+                .unwrap_or(species.len() + usize::from(key != composition_key(&water)))
+        };
+        total.sort_by_key(|(formula, _)| rank(formula));
+        let divisor = total
+            .iter()
+            .fold(0, |divisor, (_, amount)| gcd(divisor, amount.abs()));
+        total
+            .iter_mut()
+            .for_each(|(_, amount)| *amount /= divisor.max(1));
+
+        let to_equation = |amounts: &Amounts| -> Result<Equation, ChemParseError> {
+            let side = |sign: i128| -> Result<Vec<Term>, ChemParseError> {
+                amounts
+                    .iter()
+                    .filter(|(_, amount)| amount.signum() == sign)
+                    .map(|(formula, amount)| {
+                        Ok(Term {
+                            coefficient: u32::try_from(amount.abs()).map_err(|_| {
+                                ChemParseError::Overflow(format!(
+                                    "coefficient of {}",
+                                    formula.formula
+                                ))
+                            })?,
+                            formula: formula.clone(),
+                            state: None,
+                        })
+                    })
+                    .collect()
+            };
+            let mut balanced =
+                Equation::from_terms(String::new(), side(-1)?, side(1)?).with_arrow(equation.arrow);
+            balanced.equation = balanced.to_parseable_string();
+            Ok(balanced)
+        };
+        Ok(RedoxBalance {
+            oxidation: to_equation(oxidation)?,
+            reduction: to_equation(reduction)?,
+            balanced: to_equation(&total)?,
+        })
+    }
+}
+
+/// Groups the species by the elements other than hydrogen and oxygen that they share, or by oxygen
+/// for species of only hydrogen and oxygen, such as `H2O2`. Returns the indices of each group in
+/// the order of their first species.
+fn half_reaction_groups(species: &[(Formula, i128)]) -> Vec<Vec<usize>> {
+    let mut groups: Vec<(Vec<usize>, Vec<String>)> = Vec::new();
+    for (index, (formula, _)) in species.iter().enumerate() {
+        let keys = linking_keys(formula);
+        let (joined, mut rest): (Vec<_>, Vec<_>) = groups
+            .into_iter()
+            .partition(|(_, group_keys)| group_keys.iter().any(|key| keys.contains(key)));
+        let mut merged = (vec![index], keys);
+        for (indices, group_keys) in joined {
+            merged.0.extend(indices);
+            merged.1.extend(group_keys);
+        }
+        merged.0.sort();
+        rest.push(merged);
+        rest.sort_by_key(|(indices, _)| indices[0]);
+        groups = rest;
+    }
+    groups.into_iter().map(|(indices, _)| indices).collect()
+}
+
+/// Returns the elements that link a species to its half-reaction: those other than hydrogen and
+/// oxygen, or oxygen for species of only hydrogen and oxygen.
+fn linking_keys(formula: &Formula) -> Vec<String> {
+    let keys: Vec<String> = formula
+        .elements
+        .keys()
+        .filter(|key| !matches!(key.as_str(), "H" | "O"))
+        .cloned()
+        .collect();
+    if keys.is_empty() {
+        vec![String::from("O")]
+    } else {
+        keys
+    }
+}
+
+/// Balances a half-reaction with water, the ion of the medium and electrons, returning the amount
+/// of every species on the product side, or `None` if it has no single balanced form with the
+/// species on their sides.
+fn balance_half(members: &[&(Formula, i128)], water: &Formula, ion: &Formula) -> Option<Amounts> {
+    let electron = Formula::electron();
+    let mut columns: Vec<(&Formula, i128)> = members
+        .iter()
+        .map(|(formula, sign)| (formula, *sign))
+        .collect();
+    columns.extend([(water, -1), (ion, -1), (&electron, -1)]);
+
+    let mut keys: Vec<&String> = columns
+        .iter()
+        .flat_map(|(formula, _)| formula.elements.keys())
+        .collect();
+    keys.sort();
+    keys.dedup();
+    let mut matrix: Vec<Vec<i128>> = keys
+        .iter()
+        .map(|key| {
+            columns
+                .iter()
+                .map(|(formula, sign)| {
+                    sign * formula.elements.get(*key).copied().unwrap_or(0) as i128
+                })
+                .collect()
+        })
+        .collect();
+    matrix.push(
+        columns
+            .iter()
+            .map(|(formula, sign)| sign * formula.charge as i128)
+            .collect(),
+    );
+    // The linking elements are balanced by the species alone, before water and ions are added, so
+    // that `H2O2 -> O2` keeps its oxygen instead of trading it for water.
+    let mut linking: Vec<String> = members
+        .iter()
+        .flat_map(|(formula, _)| linking_keys(formula))
+        .collect();
+    linking.sort();
+    linking.dedup();
+    for key in linking {
+        matrix.push(
+            columns
+                .iter()
+                .enumerate()
+                .map(|(column, (formula, sign))| {
+                    if column < members.len() {
+                        sign * formula.elements.get(&key).copied().unwrap_or(0) as i128
+                    } else {
+                        0
+                    }
+                })
+                .collect(),
+        );
+    }
+
+    let pivots = reduce(&mut matrix, columns.len());
+    let free: Vec<usize> = (0..columns.len())
+        .filter(|column| !pivots.contains(column))
+        .collect();
+    let [free] = free[..] else {
+        return None;
+    };
+
+    // With the free column set to the product of the pivots, every pivot value is an integer.
+    let scale = pivots
+        .iter()
+        .enumerate()
+        .fold(1i128, |scale, (row, &pivot)| {
+            let value = matrix[row][pivot].abs();
+            scale / gcd(scale, value) * value
+        });
+    let mut solution = vec![0i128; columns.len()];
+    solution[free] = scale;
+    for (row, &pivot) in pivots.iter().enumerate() {
+        solution[pivot] = -matrix[row][free] * scale / matrix[row][pivot];
+    }
+    if solution[0] < 0 {
+        solution.iter_mut().for_each(|value| *value = -*value);
+    }
+    if solution[..members.len()].iter().any(|value| *value <= 0) {
+        return None;
+    }
+    let divisor = solution
+        .iter()
+        .fold(0, |divisor, value| gcd(divisor, value.abs()));
+    Some(
+        columns
+            .iter()
+            .zip(solution)
+            .map(|((formula, sign), value)| {
+                let amount = value / divisor;
+                let amount = if *sign > 0 { -amount } else { amount };
+                ((*formula).clone(), amount)
+            })
+            .filter(|(_, amount)| *amount != 0)
+            .collect(),
+    )
+}
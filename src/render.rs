@@ -0,0 +1,618 @@
+//! # Rendering Module
+//!
+//! This module draws the periodic table in the terminal using the standard 18-column layout,
+//! and renders formulas and equations for end users with Unicode subscripts, superscripts and arrows,
+//! or as LaTeX, HTML and MathML for documents and web pages.
+
+use crate::element::{Arrow, Coefficient, Element, Equation, Formula, PeriodicTable, Term};
+use crate::{ChemParser, Rule};
+use pest::iterators::Pair;
+use pest::Parser;
+
+/// Width of a single cell of the grid in characters.
+const CELL_WIDTH: usize = 4;
+
+/// Number of main rows (periods) of the grid, followed by the lanthanide and actinide rows.
+const MAIN_ROWS: usize = 7;
+
+/// Renders the periodic table as an 18-column text grid.
+///
+/// Elements whose symbols are listed in `highlight` are drawn in square brackets, for example `[Fe]`.
+/// Lanthanides and actinides without a group are drawn in two separate rows below the main table.
+/// Other elements without a group, such as lanthanum and actinium in tables that leave their group
+/// out, are drawn in group 3 of the main table when that cell is free.
+pub fn periodic_grid(table: &PeriodicTable, highlight: &[&str]) -> String {
+    let mut grid: Vec<Vec<Option<&Element>>> = vec![vec![None; 18]; MAIN_ROWS + 2];
+
+    for element in table.iter() {
+        let period = element.period() as usize;
+        match element.group {
+            Some(group @ 1..=18) => grid[period - 1][group as usize - 1] = Some(element),
+            _ => {
+                let row = period.checked_sub(6).map(|row| MAIN_ROWS + row);
+                let column = (element.atomic_number as usize)
+                    .checked_sub(58)
+                    .map(|offset| offset % 32 + 3);
+                let f_block = row
+                    .zip(column)
+                    .and_then(|(row, column)| grid.get_mut(row)?.get_mut(column));
+                match f_block {
+                    Some(cell) => *cell = Some(element),
+                    None => {
+                        let cell = &mut grid[period - 1][2];
+                        if cell.is_none() {
+                            *cell = Some(element);
+                        }
+                    }
+                }
+            }
+        }
+    }
+
+    let mut lines = Vec::new();
+    for (row_id, row) in grid.iter().enumerate() {
+        if row_id == MAIN_ROWS {
+            lines.push(String::new());
+        }
+        let line: String = row
+            .iter()
+            .map(|cell| render_cell(*cell, highlight))
+            .collect();
+        lines.push(line.trim_end().to_string());
+    }
+
+    lines.join("\n")
+}
+
+fn render_cell(cell: Option<&Element>, highlight: &[&str]) -> String {
+    match cell {
+        Some(element) if highlight.contains(&element.symbol.as_str()) => {
+            format!(
+                "{:<width$}",
+                format!("[{}]", element.symbol),
+                width = CELL_WIDTH
+            )
+        }
+        Some(element) => format!(" {:<width$}", element.symbol, width = CELL_WIDTH - 1),
+        None => " ".repeat(CELL_WIDTH),
+    }
+}
+
+impl Formula {
+    /// Renders the formula with Unicode subscripts and superscripts, for example `H2SO4` as `H₂SO₄`,
+    /// `SO4^2-` as `SO₄²⁻`, `^235U` as `²³⁵U` and `CuSO4*5H2O` as `CuSO₄·5H₂O`.
+    /// Formulas that cannot be parsed back are returned as written.
+    pub fn to_pretty_string(&self) -> String {
+        render_formula(&self.formula, &Unicode)
+    }
+
+    /// Renders the formula for the mhchem LaTeX package, for example `\ce{SO4^{2-}}`.
+    pub fn to_latex(&self) -> String {
+        format!("\\ce{{{}}}", render_formula(&self.formula, &Mhchem))
+    }
+
+    /// Renders the formula as math-mode LaTeX without packages, for example `\mathrm{SO}_{4}^{2-}`.
+    pub fn to_latex_math(&self) -> String {
+        render_formula(&self.formula, &LatexMath)
+    }
+
+    /// Renders the formula as HTML with `<sub>` and `<sup>` tags, for example `SO<sub>4</sub><sup>2−</sup>`.
+    pub fn to_html(&self) -> String {
+        render_formula(&self.formula, &Html)
+    }
+
+    /// Renders the formula as a MathML `<math>` element.
+    pub fn to_mathml(&self) -> String {
+        math_element(&render_formula(&self.formula, &MathMl))
+    }
+}
+
+impl Equation {
+    /// Renders the equation with Unicode formulas and arrows, for example `2H2 + O2 -> 2H2O` as
+    /// `2H₂ + O₂ → 2H₂O`. Reversible reactions use `⇌`, and the conditions are kept over the arrow.
+    pub fn to_pretty_string(&self) -> String {
+        render_equation(self, &Unicode)
+    }
+
+    /// Renders the equation for the mhchem LaTeX package, for example `\ce{2H2 + O2 -> 2H2O}`.
+    /// The conditions are written over the arrow, as in `\ce{N2 + 3H2 ->[Fe, 450°C] 2NH3}`.
+    pub fn to_latex(&self) -> String {
+        format!("\\ce{{{}}}", render_equation(self, &Mhchem))
+    }
+
+    /// Renders the equation as math-mode LaTeX without packages, for example
+    /// `2\,\mathrm{H}_{2} + \mathrm{O}_{2} \rightarrow 2\,\mathrm{H}_{2}\mathrm{O}`.
+    pub fn to_latex_math(&self) -> String {
+        render_equation(self, &LatexMath)
+    }
+
+    /// Renders the equation as HTML with `<sub>` and `<sup>` tags and Unicode arrows, for example
+    /// `2H<sub>2</sub> + O<sub>2</sub> → 2H<sub>2</sub>O`.
+    pub fn to_html(&self) -> String {
+        render_equation(self, &Html)
+    }
+
+    /// Renders the equation as a MathML `<math>` element, with the conditions written over the arrow.
+    pub fn to_mathml(&self) -> String {
+        math_element(&render_equation(self, &MathMl))
+    }
+}
+
+/// Part of a formula as written, in the order it appears.
+enum Token<'a> {
+    /// Element symbol, or `e` for an electron.
+    Symbol(&'a str),
+    /// Number of atoms or groups before it.
+    Index(&'a str),
+    /// Mass number of the isotope that follows.
+    MassNumber(&'a str),
+    /// Charge of an ion, such as `2-` or `+`.
+    Charge(String),
+    /// Opening bracket of a group or a complex.
+    Open(char),
+    /// Closing bracket of a group or a complex.
+    Close(char),
+    /// Dot joining an adduct, such as the water of hydration.
+    Adduct,
+    /// Number of molecules of an adduct.
+    Count(&'a str),
+}
+
+/// Output format of formulas and equations.
+trait Notation {
+    /// Renders the tokens of a formula.
+    fn formula(&self, tokens: &[Token]) -> String;
+
+    /// Renders text that is not a parsed formula, such as a condition.
+    fn text(&self, text: &str) -> String;
+
+    /// Renders the coefficient of a term, which is only called for coefficients other than one.
+    fn coefficient(&self, coefficient: Coefficient) -> String {
+        coefficient.to_string()
+    }
+
+    /// Renders the arrow with the rendered conditions written over it.
+    fn arrow(&self, arrow: Arrow, conditions: &[String]) -> String;
+
+    /// Separates the terms of an equation side.
+    fn plus(&self) -> &'static str {
+        " + "
+    }
+}
+
+/// Plain text with Unicode subscripts, superscripts and arrows.
+struct Unicode;
+
+impl Notation for Unicode {
+    fn formula(&self, tokens: &[Token]) -> String {
+        tokens
+            .iter()
+            .map(|token| match token {
+                Token::Symbol(text) | Token::Count(text) => text.to_string(),
+                Token::Index(index) => subscript(index),
+                Token::MassNumber(number) => superscript(number),
+                Token::Charge(charge) => superscript(charge),
+                Token::Open(bracket) | Token::Close(bracket) => bracket.to_string(),
+                Token::Adduct => String::from("·"),
+            })
+            .collect()
+    }
+
+    fn text(&self, text: &str) -> String {
+        text.to_string()
+    }
+
+    fn arrow(&self, arrow: Arrow, conditions: &[String]) -> String {
+        let symbol = match arrow {
+            Arrow::Irreversible => "→",
+            Arrow::Reversible => "⇌",
+            Arrow::Equality => "=",
+        };
+        bracketed_conditions(symbol, conditions)
+    }
+}
+
+/// Contents of the `\ce{}` command of the mhchem LaTeX package.
+struct Mhchem;
+
+impl Notation for Mhchem {
+    fn formula(&self, tokens: &[Token]) -> String {
+        tokens
+            .iter()
+            .map(|token| match token {
+                Token::Symbol(text) | Token::Index(text) | Token::Count(text) => text.to_string(),
+                Token::MassNumber(number) => format!("^{{{}}}", number),
+                Token::Charge(charge) => format!("^{{{}}}", charge),
+                Token::Open(bracket) | Token::Close(bracket) => bracket.to_string(),
+                Token::Adduct => String::from("*"),
+            })
+            .collect()
+    }
+
+    fn text(&self, text: &str) -> String {
+        latex_escape(text)
+    }
+
+    fn arrow(&self, arrow: Arrow, conditions: &[String]) -> String {
+        bracketed_conditions(arrow.symbol(), conditions)
+    }
+}
+
+/// Math-mode LaTeX that needs no packages.
+struct LatexMath;
+
+impl Notation for LatexMath {
+    fn formula(&self, tokens: &[Token]) -> String {
+        let mut rendered = String::new();
+        for (i, token) in tokens.iter().enumerate() {
+            match token {
+                // Neighbouring symbols share one `\mathrm`, so `SO4` renders as `\mathrm{SO}_{4}`.
+                Token::Symbol(symbol) => {
+                    let joined = i > 0 && matches!(tokens[i - 1], Token::Symbol(_));
+                    if joined {
+                        rendered.pop();
+                    } else {
+                        rendered.push_str("\\mathrm{");
+                    }
+                    rendered.push_str(symbol);
+                    rendered.push('}');
+                }
+                Token::Index(index) => rendered.push_str(&format!("_{{{}}}", index)),
+                Token::MassNumber(number) => rendered.push_str(&format!("{{}}^{{{}}}", number)),
+                Token::Charge(charge) => rendered.push_str(&format!("^{{{}}}", charge)),
+                Token::Open('[') => rendered.push_str("\\lbrack "),
+                Token::Close(']') => rendered.push_str("\\rbrack "),
+                Token::Open(bracket) | Token::Close(bracket) => rendered.push(*bracket),
+                Token::Adduct => rendered.push_str("\\cdot "),
+                Token::Count(count) => rendered.push_str(count),
+            }
+        }
+        rendered
+    }
+
+    fn text(&self, text: &str) -> String {
+        format!("\\text{{{}}}", latex_escape(text))
+    }
+
+    fn coefficient(&self, coefficient: Coefficient) -> String {
+        if coefficient.is_integer() {
+            format!("{}\\,", coefficient)
+        } else {
+            format!(
+                "\\tfrac{{{}}}{{{}}}\\,",
+                coefficient.numerator, coefficient.denominator
+            )
+        }
+    }
+
+    fn arrow(&self, arrow: Arrow, conditions: &[String]) -> String {
+        match (arrow, conditions.is_empty()) {
+            (Arrow::Irreversible, true) => String::from("\\rightarrow"),
+            (Arrow::Irreversible, false) => format!("\\xrightarrow{{{}}}", conditions.join(",\\ ")),
+            (Arrow::Reversible, true) => String::from("\\rightleftharpoons"),
+            (Arrow::Reversible, false) => {
+                format!(
+                    "\\overset{{{}}}{{\\rightleftharpoons}}",
+                    conditions.join(",\\ ")
+                )
+            }
+            (Arrow::Equality, true) => String::from("="),
+            (Arrow::Equality, false) => format!("\\overset{{{}}}{{=}}", conditions.join(",\\ ")),
+        }
+    }
+}
+
+/// HTML with `<sub>` and `<sup>` tags.
+struct Html;
+
+impl Notation for Html {
+    fn formula(&self, tokens: &[Token]) -> String {
+        tokens
+            .iter()
+            .map(|token| match token {
+                Token::Symbol(text) | Token::Count(text) => text.to_string(),
+                Token::Index(index) => format!("<sub>{}</sub>", index),
+                Token::MassNumber(number) => format!("<sup>{}</sup>", number),
+                Token::Charge(charge) => format!("<sup>{}</sup>", charge.replace('-', "−")),
+                Token::Open(bracket) | Token::Close(bracket) => bracket.to_string(),
+                Token::Adduct => String::from("·"),
+            })
+            .collect()
+    }
+
+    fn text(&self, text: &str) -> String {
+        xml_escape(text)
+    }
+
+    fn arrow(&self, arrow: Arrow, conditions: &[String]) -> String {
+        Unicode.arrow(arrow, conditions)
+    }
+}
+
+/// Contents of a MathML `<math>` element.
+struct MathMl;
+
+impl Notation for MathMl {
+    fn formula(&self, tokens: &[Token]) -> String {
+        let mut rendered = String::from("<mrow>");
+        let mut tokens = tokens.iter().peekable();
+        while let Some(token) = tokens.next() {
+            let base = match token {
+                Token::Symbol(symbol) => format!("<mi mathvariant=\"normal\">{}</mi>", symbol),
+                Token::Close(bracket) => format!("<mo>{}</mo>", bracket),
+                Token::Open(bracket) => {
+                    rendered.push_str(&format!("<mo>{}</mo>", bracket));
+                    continue;
+                }
+                Token::MassNumber(number) => {
+                    if let Some(Token::Symbol(symbol)) = tokens.next() {
+                        rendered.push_str(&format!(
+                            "<mmultiscripts><mi mathvariant=\"normal\">{}</mi><mprescripts/><none/><mn>{}</mn></mmultiscripts>",
+                            symbol, number
+                        ));
+                    }
+                    continue;
+                }
+                Token::Index(index) | Token::Count(index) => {
+                    rendered.push_str(&format!("<mn>{}</mn>", index));
+                    continue;
+                }
+                Token::Charge(charge) => {
+                    rendered.push_str(&format!("<msup><mrow/>{}</msup>", mathml_charge(charge)));
+                    continue;
+                }
+                Token::Adduct => {
+                    rendered.push_str("<mo>·</mo>");
+                    continue;
+                }
+            };
+
+            // Indices and charges are attached to the symbol or bracket right before them.
+            let index = match tokens.peek() {
+                Some(Token::Index(index)) => {
+                    tokens.next();
+                    Some(format!("<mn>{}</mn>", index))
+                }
+                _ => None,
+            };
+            let charge = match tokens.peek() {
+                Some(Token::Charge(charge)) => {
+                    tokens.next();
+                    Some(mathml_charge(charge))
+                }
+                _ => None,
+            };
+            rendered.push_str(&match (index, charge) {
+                (None, None) => base,
+                (Some(index), None) => format!("<msub>{}{}</msub>", base, index),
+                (None, Some(charge)) => format!("<msup>{}{}</msup>", base, charge),
+                (Some(index), Some(charge)) => {
+                    format!("<msubsup>{}{}{}</msubsup>", base, index, charge)
+                }
+            });
+        }
+        rendered.push_str("</mrow>");
+        rendered
+    }
+
+    fn text(&self, text: &str) -> String {
+        format!("<mtext>{}</mtext>", xml_escape(text))
+    }
+
+    fn coefficient(&self, coefficient: Coefficient) -> String {
+        if coefficient.is_integer() {
+            format!("<mn>{}</mn>", coefficient)
+        } else {
+            format!(
+                "<mfrac><mn>{}</mn><mn>{}</mn></mfrac>",
+                coefficient.numerator, coefficient.denominator
+            )
+        }
+    }
+
+    fn arrow(&self, arrow: Arrow, conditions: &[String]) -> String {
+        let symbol = match arrow {
+            Arrow::Irreversible => "<mo>→</mo>",
+            Arrow::Reversible => "<mo>⇌</mo>",
+            Arrow::Equality => "<mo>=</mo>",
+        };
+        if conditions.is_empty() {
+            symbol.to_string()
+        } else {
+            format!(
+                "<mover>{}<mrow>{}</mrow></mover>",
+                symbol,
+                conditions.join("<mo>,</mo>")
+            )
+        }
+    }
+
+    fn plus(&self) -> &'static str {
+        "<mo>+</mo>"
+    }
+}
+
+/// Wraps MathML contents in a `<math>` element.
+fn math_element(contents: &str) -> String {
+    format!(
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\">{}</math>",
+        contents
+    )
+}
+
+/// Renders a charge such as `2-` as a MathML superscript.
+fn mathml_charge(charge: &str) -> String {
+    let (number, sign) = charge.split_at(charge.len() - 1);
+    let sign = if sign == "-" { "−" } else { "+" };
+    if number.is_empty() {
+        format!("<mo>{}</mo>", sign)
+    } else {
+        format!("<mrow><mn>{}</mn><mo>{}</mo></mrow>", number, sign)
+    }
+}
+
+/// Renders a formula written in ASCII notation, or its text if it cannot be parsed back.
+fn render_formula(formula: &str, notation: &dyn Notation) -> String {
+    match formula_tokens(formula) {
+        Some(tokens) => notation.formula(&tokens),
+        None => notation.text(formula),
+    }
+}
+
+/// Renders the terms, the arrow and the conditions of an equation. Catalysts are rendered as formulas.
+fn render_equation(equation: &Equation, notation: &dyn Notation) -> String {
+    let side = |terms: &[Term]| -> String {
+        terms
+            .iter()
+            .map(|term| {
+                let coefficient = match equation.coefficient(term) {
+                    one if one.numerator == 1 && one.is_integer() => String::new(),
+                    coefficient => notation.coefficient(coefficient),
+                };
+                format!(
+                    "{}{}",
+                    coefficient,
+                    render_formula(&term.formula.formula, notation)
+                )
+            })
+            .collect::<Vec<_>>()
+            .join(notation.plus())
+    };
+    let conditions: Vec<String> = equation
+        .conditions
+        .iter()
+        .map(|condition| {
+            if equation.catalysts.contains(condition) {
+                render_formula(condition, notation)
+            } else {
+                notation.text(condition)
+            }
+        })
+        .collect();
+    format!(
+        "{} {} {}",
+        side(&equation.reactant_terms),
+        notation.arrow(equation.arrow, &conditions),
+        side(&equation.product_terms)
+    )
+}
+
+/// Writes the conditions in square brackets right after the arrow, as in `->[Fe, 450°C]`.
+fn bracketed_conditions(arrow: &str, conditions: &[String]) -> String {
+    if conditions.is_empty() {
+        arrow.to_string()
+    } else {
+        format!("{}[{}]", arrow, conditions.join(", "))
+    }
+}
+
+/// Splits a formula written in ASCII notation into tokens, or returns `None` if it cannot be parsed.
+fn formula_tokens(formula: &str) -> Option<Vec<Token<'_>>> {
+    if parse_whole(Rule::electron, formula).is_some() {
+        return Some(vec![Token::Symbol("e"), Token::Charge(String::from("-"))]);
+    }
+    let pair = parse_whole(Rule::formula, formula)?;
+    let mut tokens = Vec::new();
+    push_tokens(pair, &mut tokens);
+    Some(tokens)
+}
+
+/// Parses the whole input with a rule, or returns `None` if the rule does not match all of it.
+fn parse_whole(rule: Rule, input: &str) -> Option<Pair<'_, Rule>> {
+    ChemParser::parse(rule, input)
+        .ok()?
+        .next()
+        .filter(|pair| pair.as_str().len() == input.len())
+}
+
+/// Appends the tokens of the inner pairs of a formula.
+fn push_tokens<'a>(pair: Pair<'a, Rule>, tokens: &mut Vec<Token<'a>>) {
+    for inner in pair.into_inner() {
+        match inner.as_rule() {
+            Rule::element => tokens.push(Token::Symbol(inner.as_str())),
+            Rule::index => tokens.push(Token::Index(inner.as_str())),
+            Rule::adduct_count => tokens.push(Token::Count(inner.as_str())),
+            Rule::isotope => {
+                let parts: Vec<Pair<Rule>> = inner.into_inner().collect();
+                let number = parts
+                    .iter()
+                    .find(|part| part.as_rule() == Rule::mass_number);
+                let element = parts.iter().find(|part| part.as_rule() == Rule::element);
+                if let (Some(number), Some(element)) = (number, element) {
+                    tokens.push(Token::MassNumber(number.as_str()));
+                    tokens.push(Token::Symbol(element.as_str()));
+                }
+            }
+            Rule::group => {
+                tokens.push(Token::Open('('));
+                push_tokens(inner, tokens);
+                tokens.push(Token::Close(')'));
+            }
+            Rule::complex => {
+                tokens.push(Token::Open('['));
+                push_tokens(inner, tokens);
+                tokens.push(Token::Close(']'));
+            }
+            Rule::charge => {
+                tokens.push(Token::Charge(
+                    inner.into_inner().map(|part| part.as_str()).collect(),
+                ));
+            }
+            Rule::adduct => {
+                tokens.push(Token::Adduct);
+                push_tokens(inner, tokens);
+            }
+            _ => push_tokens(inner, tokens),
+        }
+    }
+}
+
+/// Escapes the characters that have a special meaning in HTML and XML text.
+fn xml_escape(text: &str) -> String {
+    text.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+        .replace('"', "&quot;")
+}
+
+/// Escapes the characters that have a special meaning in LaTeX text.
+fn latex_escape(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '\\' => String::from("\\textbackslash{}"),
+            '&' | '%' | '$' | '#' | '_' | '{' | '}' => format!("\\{}", c),
+            '~' => String::from("\\textasciitilde{}"),
+            '^' => String::from("\\textasciicircum{}"),
+            _ => c.to_string(),
+        })
+        .collect()
+}
+
+/// Converts digits to Unicode subscripts.
+fn subscript(digits: &str) -> String {
+    digits
+        .chars()
+        .map(|c| match c.to_digit(10) {
+            Some(digit) => char::from_u32('₀' as u32 + digit).unwrap_or(c),
+            None => c,
+        })
+        .collect()
+}
+
+/// Converts digits and charge signs to Unicode superscripts.
+fn superscript(text: &str) -> String {
+    text.chars()
+        .map(|c| match c {
+            '1' => '¹',
+            '2' => '²',
+            '3' => '³',
+            '+' => '⁺',
+            '-' => '⁻',
+            _ => match c.to_digit(10) {
+                Some(0) => '⁰',
+                Some(digit) => char::from_u32('⁴' as u32 + digit - 4).unwrap_or(c),
+                None => c,
+            },
+        })
+        .collect()
+}
@@ -0,0 +1,98 @@
+//! # REPL Module
+//!
+//! This module reads the lines of the interactive `repl` command. With the `repl` feature they are
+//! read with line editing, a history of the session and tab completion of element symbols, names
+//! and ion formulas; without it they are read from stdin as they are.
+
+use chemistry_parser::ChemParser;
+use std::io;
+
+/// Words that end the session.
+const EXIT_COMMANDS: [&str; 2] = ["exit", "quit"];
+
+/// Reads lines until the end of the input or an exit command and passes the non-empty ones,
+/// trimmed, to `handle`.
+#[cfg(feature = "repl")]
+pub fn run(parser: &ChemParser, mut handle: impl FnMut(&str)) -> io::Result<()> {
+    use rustyline::error::ReadlineError;
+    use rustyline::Editor;
+
+    let mut editor = Editor::new().map_err(io::Error::other)?;
+    editor.set_helper(Some(ReplHelper { parser }));
+    loop {
+        let line = match editor.readline("chem> ") {
+            Ok(line) => line,
+            Err(ReadlineError::Eof | ReadlineError::Interrupted) => return Ok(()),
+            Err(e) => return Err(io::Error::other(e)),
+        };
+        let line = line.trim();
+        if EXIT_COMMANDS.contains(&line) {
+            return Ok(());
+        }
+        if line.is_empty() {
+            continue;
+        }
+        editor.add_history_entry(line).map_err(io::Error::other)?;
+        handle(line);
+    }
+}
+
+/// Reads lines until the end of the input or an exit command and passes the non-empty ones,
+/// trimmed, to `handle`.
+#[cfg(not(feature = "repl"))]
+pub fn run(_parser: &ChemParser, mut handle: impl FnMut(&str)) -> io::Result<()> {
+    for line in io::stdin().lines() {
+        let line = line?;
+        let line = line.trim();
+        if EXIT_COMMANDS.contains(&line) {
+            break;
+        }
+        if !line.is_empty() {
+            handle(line);
+        }
+    }
+    Ok(())
+}
+
+/// Completes the input of the REPL with the completion of the parser.
+#[cfg(feature = "repl")]
+struct ReplHelper<'a> {
+    parser: &'a ChemParser,
+}
+
+#[cfg(feature = "repl")]
+impl rustyline::completion::Completer for ReplHelper<'_> {
+    type Candidate = rustyline::completion::Pair;
+
+    fn complete(
+        &self,
+        line: &str,
+        pos: usize,
+        _ctx: &rustyline::Context<'_>,
+    ) -> rustyline::Result<(usize, Vec<Self::Candidate>)> {
+        let completion = self.parser.complete(&line[..pos], &EXIT_COMMANDS);
+        let candidates = completion
+            .candidates
+            .into_iter()
+            .map(|candidate| rustyline::completion::Pair {
+                display: candidate.display,
+                replacement: candidate.replacement,
+            })
+            .collect();
+        Ok((completion.start, candidates))
+    }
+}
+
+#[cfg(feature = "repl")]
+impl rustyline::hint::Hinter for ReplHelper<'_> {
+    type Hint = String;
+}
+
+#[cfg(feature = "repl")]
+impl rustyline::highlight::Highlighter for ReplHelper<'_> {}
+
+#[cfg(feature = "repl")]
+impl rustyline::validate::Validator for ReplHelper<'_> {}
+
+#[cfg(feature = "repl")]
+impl rustyline::Helper for ReplHelper<'_> {}
@@ -0,0 +1,114 @@
+//! # Rewrite Module
+//!
+//! This module applies rewrite rules over compositions to formulas and equations, such as
+//! `OH -> OD` to deuterate hydroxyl groups or `Na -> H` to turn sodium salts into their acids, so
+//! that datasets can be transformed systematically without custom code.
+
+use crate::element::{Equation, Formula, Term};
+use crate::{ChemParseError, ChemParser};
+
+/// Rule that replaces every occurrence of a composition in a formula with another one.
+#[derive(Debug, Clone)]
+pub struct RewriteRule {
+    /// Atoms and charge to replace.
+    pub from: Formula,
+    /// Atoms and charge to put in their place, empty to remove them.
+    pub to: Formula,
+}
+
+impl RewriteRule {
+    /// Replaces `from` in the formula with `to` as many times as the formula contains it, so
+    /// `OH -> OD` turns `CH3OH` into `CH3DO` and `Na -> H` turns `Na2SO4` into `H2O4S`. A rewritten
+    /// formula is written in Hill notation; a formula without `from` is returned unchanged.
+    pub fn apply(&self, formula: &Formula) -> Result<Formula, ChemParseError> {
+        let times = self
+            .from
+            .elements
+            .iter()
+            .map(|(key, count)| formula.elements.get(key).copied().unwrap_or(0) / count)
+            .min()
+            .unwrap_or(0);
+        if times == 0 {
+            return Ok(formula.clone());
+        }
+        self.from
+            .checked_mul(times)
+            .zip(self.to.checked_mul(times))
+            .and_then(|(from, to)| formula.checked_sub(&from)?.checked_add(&to))
+            .ok_or_else(|| ChemParseError::Overflow(format!("rewrite of {}", formula.formula)))
+    }
+}
+
+/// Rewrite rules applied in order.
+#[derive(Debug, Clone, Default)]
+pub struct RewriteRules {
+    /// Rules in the order they are applied.
+    pub rules: Vec<RewriteRule>,
+}
+
+impl RewriteRules {
+    /// Applies every rule to a formula in order.
+    pub fn apply_formula(&self, formula: &Formula) -> Result<Formula, ChemParseError> {
+        self.rules
+            .iter()
+            .try_fold(formula.clone(), |formula, rule| rule.apply(&formula))
+    }
+
+    /// Applies every rule to the formulas of an equation, keeping its coefficients, states, arrow
+    /// and conditions. Electrons are kept as they are.
+    pub fn apply_equation(&self, equation: &Equation) -> Result<Equation, ChemParseError> {
+        let side = |terms: &[Term]| -> Result<Vec<Term>, ChemParseError> {
+            terms
+                .iter()
+                .map(|term| {
+                    let formula = if term.formula.elements.is_empty() {
+                        term.formula.clone()
+                    } else {
+                        self.apply_formula(&term.formula)?
+                    };
+                    Ok(Term {
+                        formula,
+                        ..term.clone()
+                    })
+                })
+                .collect()
+        };
+        Ok(equation.with_terms(
+            side(&equation.reactant_terms)?,
+            side(&equation.product_terms)?,
+        ))
+    }
+}
+
+impl ChemParser {
+    /// Parses a rewrite rule written as `from -> to`, such as `OH -> OD`, `Ca -> H2` or `Na ->`
+    /// to remove sodium.
+    pub fn parse_rewrite_rule(&self, rule: &str) -> Result<RewriteRule, ChemParseError> {
+        let error = |reason: &str| {
+            ChemParseError::ParsingError(format!("rewrite rule \"{}\"", rule), reason.to_string())
+        };
+        let (from, to) = rule
+            .split_once("->")
+            .ok_or_else(|| error("expected `from -> to`"))?;
+        let from = self.parse_formula(from.trim())?;
+        if from.elements.is_empty() {
+            return Err(error("nothing to replace"));
+        }
+        let to = match to.trim() {
+            "" => Formula::new(""),
+            to => self.parse_formula(to)?,
+        };
+        Ok(RewriteRule { from, to })
+    }
+
+    /// Parses rewrite rules, one per line, skipping blank lines and `#` comments.
+    pub fn parse_rewrite_rules(&self, text: &str) -> Result<RewriteRules, ChemParseError> {
+        let rules = text
+            .lines()
+            .map(str::trim)
+            .filter(|line| !line.is_empty() && !line.starts_with('#'))
+            .map(|line| self.parse_rewrite_rule(line))
+            .collect::<Result<_, _>>()?;
+        Ok(RewriteRules { rules })
+    }
+}
@@ -0,0 +1,238 @@
+//! # Schema Module
+//!
+//! This module describes what the installed version of the library can do: its version, the
+//! optional features it was built with, the notations its grammar accepts and the JSON schemas of
+//! its serialized output types, so that integrators can discover its capabilities programmatically.
+
+use crate::ChemParser;
+use serde::Serialize;
+use serde_json::{json, Value};
+use std::collections::BTreeMap;
+
+/// Notations accepted by the grammar of this version.
+pub const GRAMMAR_FEATURES: &[&str] = &[
+    "elements",
+    "isotopes",
+    "groups",
+    "complexes",
+    "charges",
+    "adducts",
+    "states",
+    "electrons",
+    "arrows",
+    "conditions",
+    "reaction-sequences",
+    "nuclear-equations",
+    "calculations",
+    "units",
+    "variables",
+];
+
+/// Notations of [`GRAMMAR_FEATURES`] that need the isotope data of the periodic table.
+const ISOTOPE_FEATURES: &[&str] = &["isotopes", "nuclear-equations"];
+
+/// Version, build features, grammar notations and output schemas of the installed library.
+#[derive(Debug, Clone, Serialize)]
+pub struct Capabilities {
+    /// Version of the chemistry_parser crate.
+    pub version: &'static str,
+    /// Optional features the library was built with.
+    pub features: Vec<&'static str>,
+    /// Notations accepted by the grammar, see [`GRAMMAR_FEATURES`].
+    pub grammar: Vec<&'static str>,
+    /// Commands and options of the command-line interface, empty for the library alone.
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub commands: Vec<String>,
+    /// JSON schemas of the output types, by name.
+    pub schemas: BTreeMap<String, Value>,
+}
+
+/// Returns the capabilities of the library as built.
+pub fn capabilities() -> Capabilities {
+    let features = [
+        ("cli", cfg!(feature = "cli")),
+        ("clipboard", cfg!(feature = "clipboard")),
+        ("compounds", cfg!(feature = "compounds")),
+        ("csv", cfg!(feature = "csv")),
+        ("datasets", cfg!(feature = "datasets")),
+        ("ffi", cfg!(feature = "ffi")),
+        ("generator", cfg!(feature = "generator")),
+        ("mmap", cfg!(feature = "mmap")),
+        ("ndarray", cfg!(feature = "ndarray")),
+        ("progress", cfg!(feature = "progress")),
+        ("python", cfg!(feature = "python")),
+        ("rayon", cfg!(feature = "rayon")),
+        ("repl", cfg!(feature = "repl")),
+        ("smiles", cfg!(feature = "smiles")),
+        ("std", cfg!(feature = "std")),
+        ("toml", cfg!(feature = "toml")),
+        ("wasm", cfg!(feature = "wasm")),
+    ];
+    Capabilities {
+        version: env!("CARGO_PKG_VERSION"),
+        features: features
+            .iter()
+            .filter(|(_, enabled)| *enabled)
+            .map(|(feature, _)| *feature)
+            .collect(),
+        grammar: GRAMMAR_FEATURES.to_vec(),
+        commands: Vec::new(),
+        schemas: output_schemas(),
+    }
+}
+
+impl ChemParser {
+    /// Returns the capabilities of the library as built, like [`capabilities`], with only the
+    /// grammar notations that this parser accepts: isotope notations and nuclear equations need
+    /// isotope data, which a custom periodic table may lack.
+    pub fn capabilities(&self) -> Capabilities {
+        Capabilities {
+            grammar: self.grammar_features(),
+            ..capabilities()
+        }
+    }
+
+    /// Checks whether the parser accepts a notation of [`GRAMMAR_FEATURES`], such as `states` or
+    /// `adducts` for hydrates, so that frontends can adapt their input hints.
+    pub fn supports(&self, feature: &str) -> bool {
+        self.grammar_features().contains(&feature)
+    }
+
+    /// Returns the notations of [`GRAMMAR_FEATURES`] that the parser accepts.
+    fn grammar_features(&self) -> Vec<&'static str> {
+        let isotopes = self.get_table().has_isotopes();
+        GRAMMAR_FEATURES
+            .iter()
+            .copied()
+            .filter(|feature| isotopes || !ISOTOPE_FEATURES.contains(feature))
+            .collect()
+    }
+}
+
+/// Returns the JSON schemas of the serialized output types: `analysis` for
+/// [`crate::analysis::Analysis`], `element`, `formula` and `equation` for the parsed structures,
+/// `mass_table` for molar-mass tables written as JSON and `provenance`.
+pub fn output_schemas() -> BTreeMap<String, Value> {
+    let counts =
+        json!({ "type": "object", "additionalProperties": { "type": "integer", "minimum": 0 } });
+    let strings = json!({ "type": "array", "items": { "type": "string" } });
+    let transition = json!({
+        "type": ["number", "object", "null"],
+        "additionalProperties": { "type": ["number", "null"] }
+    });
+    let element = json!({
+        "type": "object",
+        "properties": {
+            "name": { "type": "string" },
+            "symbol": { "type": "string" },
+            "atomic_number": { "type": "integer", "minimum": 0 },
+            "atomic_mass": { "type": "number" },
+            "atomic_mass_uncertainty": { "type": ["number", "null"] },
+            "density": { "type": "number" },
+            "group": { "type": ["integer", "null"] },
+            "melting_point": transition,
+            "boiling_point": transition,
+            "electronegativity": { "type": ["number", "null"] },
+            "cas": { "type": ["string", "null"] },
+            "cid": { "type": ["integer", "null"], "minimum": 0 },
+            "category": { "type": ["string", "null"] },
+            "ionization_energies": { "type": "array", "items": { "type": "number" } },
+            "atomic_radius": { "type": ["number", "null"] },
+            "covalent_radius": { "type": ["number", "null"] },
+            "abundance": { "type": ["number", "null"] },
+            "discovery_year": { "type": ["integer", "null"] }
+        },
+        "required": ["name", "symbol", "atomic_number", "atomic_mass"]
+    });
+    let mut formula = json!({
+        "type": "object",
+        "properties": {
+            "formula": { "type": "string" },
+            "elements": counts,
+            "mass": { "type": "number" },
+            "charge": { "type": "integer" }
+        },
+        "required": ["formula", "elements", "mass"]
+    });
+    formula["properties"]["repeat_unit"] = formula.clone();
+    let term = json!({
+        "type": "object",
+        "properties": {
+            "coefficient": { "type": "integer", "minimum": 0 },
+            "formula": formula,
+            "state": { "enum": ["solid", "liquid", "gas", "aqueous"] }
+        },
+        "required": ["coefficient", "formula"]
+    });
+    let equation = json!({
+        "type": "object",
+        "properties": {
+            "equation": { "type": "string" },
+            "reactants": counts,
+            "products": counts,
+            "reactant_terms": { "type": "array", "items": term },
+            "product_terms": { "type": "array", "items": term },
+            "arrow": { "enum": ["irreversible", "reversible", "equality"] },
+            "conditions": strings,
+            "catalysts": strings,
+            "denominator": { "type": "integer", "minimum": 1 }
+        },
+        "required": ["equation", "reactants", "products"]
+    });
+    let analysis = json!({
+        "type": "object",
+        "properties": {
+            "input": { "type": "string" },
+            "kind": { "enum": ["element", "formula", "equation", "invalid"] },
+            "element": element,
+            "formula": { "type": "string" },
+            "mass": { "type": "number" },
+            "monoisotopic_mass": { "type": "number" },
+            "charge": { "type": "integer" },
+            "composition": counts,
+            "balanced": { "type": "boolean" },
+            "balanced_equations": strings,
+            "one_sided": strings,
+            "error": { "type": "string" },
+            "error_code": { "type": "string" },
+            "suggestions": strings
+        },
+        "required": ["input", "kind"]
+    });
+    let mass_table = json!({
+        "type": "array",
+        "items": {
+            "type": "object",
+            "properties": {
+                "formula": { "type": "string" },
+                "molar_mass": { "type": "number" }
+            },
+            "required": ["formula", "molar_mass"]
+        }
+    });
+    let provenance = json!({
+        "type": "object",
+        "properties": {
+            "version": { "type": "string" },
+            "dataset": { "type": "string" },
+            "options": { "type": "object", "additionalProperties": { "type": "string" } }
+        },
+        "required": ["version", "dataset"]
+    });
+
+    [
+        ("analysis", analysis),
+        ("element", element),
+        ("equation", equation),
+        ("formula", formula),
+        ("mass_table", mass_table),
+        ("provenance", provenance),
+    ]
+    .into_iter()
+    .map(|(name, mut schema)| {
+        schema["$schema"] = json!("https://json-schema.org/draft/2020-12/schema");
+        schema["title"] = json!(name);
+        (name.to_string(), schema)
+    })
+    .collect()
+}
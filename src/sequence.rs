@@ -0,0 +1,174 @@
+//! # Reaction sequence Module
+//!
+//! This module parses multi-step reactions, written as chains such as `C -> CO -> CO2` or as
+//! numbered mechanisms with one step per line, into their steps, and sums the steps into the net
+//! equation with the intermediates cancelled.
+
+use crate::element::{composition_key, gcd, Arrow, Equation, Term};
+use crate::{coefficient_denominator, normalize_scripts, ChemParseError, ChemParser, Rule};
+use pest::Parser;
+use serde::Serialize;
+
+/// Reaction made of several steps, each an equation.
+#[derive(Debug, Clone, Serialize)]
+pub struct ReactionSequence {
+    /// Steps of the reaction, in order.
+    pub steps: Vec<Equation>,
+}
+
+impl ReactionSequence {
+    /// Returns the net equation of the sequence, the sum of its steps with species that are
+    /// formed in one step and used in another cancelled, such as `2C + 2O2 -> 2CO2` for the steps
+    /// `2C + O2 -> 2CO` and `2CO + O2 -> 2CO2`. Species are matched by their atoms and charge,
+    /// and are written in the order they first appear.
+    ///
+    /// Fails with [`ChemParseError::EvaluationError`] if every species cancels.
+    pub fn net_equation(&self) -> Result<Equation, ChemParseError> {
+        let overflow = || ChemParseError::Overflow(String::from("net equation of the sequence"));
+        // Steps written with fractions are brought to the common denominator of all steps.
+        let denominator = self.steps.iter().try_fold(1u32, |total, step| {
+            let step = step.denominator.max(1);
+            (total / gcd(total, step))
+                .checked_mul(step)
+                .ok_or_else(overflow)
+        })?;
+        let mut species: Vec<(String, Term, i64)> = Vec::new();
+        for step in &self.steps {
+            let scale = i64::from(denominator / step.denominator.max(1));
+            let sides = [(&step.reactant_terms, -1), (&step.product_terms, 1)];
+            for (terms, sign) in sides {
+                for term in terms {
+                    let key = composition_key(&term.formula);
+                    let amount = sign * scale * i64::from(term.coefficient);
+                    match species.iter_mut().find(|(other, _, _)| *other == key) {
+                        Some((_, _, net)) => *net += amount,
+                        None => species.push((key, term.clone(), amount)),
+                    }
+                }
+            }
+        }
+
+        let divisor = species
+            .iter()
+            .map(|(_, _, net)| u32::try_from(net.unsigned_abs()))
+            .try_fold(denominator, |divisor, net| Some(gcd(divisor, net.ok()?)))
+            .ok_or_else(overflow)?
+            .max(1);
+        let side = |sign: i64| -> Result<Vec<Term>, ChemParseError> {
+            species
+                .iter()
+                .filter(|(_, _, net)| net.signum() == sign)
+                .map(|(_, term, net)| {
+                    let coefficient = u32::try_from(net.abs()).map_err(|_| {
+                        ChemParseError::Overflow(format!("coefficient of {}", term.formula.formula))
+                    })?;
+                    Ok(Term {
+                        coefficient: coefficient / divisor,
+                        ..term.clone()
+                    })
+                })
+                .collect()
+        };
+        let (reactants, products) = (side(-1)?, side(1)?);
+        if reactants.is_empty() && products.is_empty() {
+            return Err(ChemParseError::EvaluationError(
+                String::from("net equation of a reaction sequence"),
+                String::from("every species cancels"),
+            ));
+        }
+        let mut net = Equation::from_terms(String::new(), reactants, products);
+        net.denominator = denominator / divisor;
+        net.equation = net.to_parseable_string();
+        Ok(net)
+    }
+}
+
+impl ChemParser {
+    /// Parses a multi-step reaction into its steps. Each line is a step or a chain of steps,
+    /// such as `C -> CO -> CO2`, where the products of a step are the reactants of the next,
+    /// and can start with a step number such as `1.`, `2)` or `Step 3:`. Blank lines and lines
+    /// starting with `#` are skipped, and the tokens of the grammar options of the parser are
+    /// accepted.
+    ///
+    /// Fails with [`ChemParseError::SyntaxError`] for a line that is not a reaction, with the
+    /// error of a formula that cannot be parsed, or with [`ChemParseError::ParsingError`] if
+    /// there are no steps.
+    pub fn parse_sequence(&self, input: &str) -> Result<ReactionSequence, ChemParseError> {
+        let mut steps = Vec::new();
+        for line in input.lines().map(str::trim) {
+            if line.is_empty() || line.starts_with('#') {
+                continue;
+            }
+            let input = line;
+            let line = &normalize_scripts(&self.grammar_options.rewrite(line));
+            let mut line_parse = ChemParser::parse(Rule::sequence_line, line)
+                .map_err(|e| self.rewritten_syntax_error("reaction sequence", input, line, &e))?;
+            let chain = line_parse
+                .next()
+                .unwrap()
+                .into_inner()
+                .find(|pair| pair.as_rule() == Rule::reaction_chain)
+                .unwrap();
+
+            // The products of a step are the reactants of the next, so each side is parsed again
+            // with the common denominator of the step it is part of.
+            let mut reactant_side = None;
+            for part in chain.into_inner() {
+                match part.as_rule() {
+                    Rule::reactants => reactant_side = Some(part),
+                    Rule::chain_step => {
+                        let mut arrow = Arrow::default();
+                        let mut conditions = Vec::new();
+                        let mut product_side = None;
+                        for step_part in part.into_inner() {
+                            match step_part.as_rule() {
+                                Rule::arrow => {
+                                    arrow =
+                                        Arrow::from_symbol(step_part.as_str()).unwrap_or_default()
+                                }
+                                Rule::conditions => conditions.extend(
+                                    step_part
+                                        .into_inner()
+                                        .map(|condition| condition.as_str().trim().to_string()),
+                                ),
+                                Rule::products => product_side = Some(step_part),
+                                _ => {}
+                            }
+                        }
+                        let sides = reactant_side.iter().chain(&product_side).cloned();
+                        let denominator = coefficient_denominator(sides)?;
+                        let mut reactants = Vec::new();
+                        if let Some(side) = &reactant_side {
+                            self.process_side(&mut reactants, side, denominator)?;
+                        }
+                        let mut products = Vec::new();
+                        if let Some(side) = &product_side {
+                            self.process_side(&mut products, side, denominator)?;
+                        }
+                        let catalysts = conditions
+                            .iter()
+                            .filter(|condition| self.is_catalyst(condition))
+                            .cloned()
+                            .collect();
+                        let mut step = Equation::from_terms(String::new(), reactants, products)
+                            .with_arrow(arrow)
+                            .with_conditions(conditions, catalysts);
+                        step.denominator = denominator;
+                        step.equation = step.to_parseable_string();
+                        steps.push(step);
+                        reactant_side = product_side;
+                    }
+                    _ => {}
+                }
+            }
+        }
+
+        if steps.is_empty() {
+            return Err(ChemParseError::ParsingError(
+                String::from("reaction sequence"),
+                String::from("no steps"),
+            ));
+        }
+        Ok(ReactionSequence { steps })
+    }
+}
@@ -0,0 +1,367 @@
+//! # SMILES Module
+//!
+//! This module reads the molecular formula of a SMILES string, such as `C6H6` for `c1ccccc1`.
+//! Atoms, bonds, branches and ring closures are parsed just far enough to count the atoms,
+//! including the implicit hydrogens of the organic subset; no molecular graph is built and
+//! stereochemistry is ignored.
+
+use crate::element::{isotope_key, Formula};
+use crate::{ChemParseError, ChemParser};
+use std::collections::{BTreeMap, HashMap};
+
+/// Normal valences of the elements of the organic subset, from the lowest.
+const ORGANIC_VALENCES: &[(&str, &[u32])] = &[
+    ("B", &[3]),
+    ("C", &[4]),
+    ("N", &[3, 5]),
+    ("O", &[2]),
+    ("P", &[3, 5]),
+    ("S", &[2, 4, 6]),
+    ("F", &[1]),
+    ("Cl", &[1]),
+    ("Br", &[1]),
+    ("I", &[1]),
+];
+
+/// Atom of a SMILES string with the bond orders it takes part in.
+struct Atom {
+    /// Composition key of the atom, such as `C` or `13C`.
+    key: String,
+    /// Whether the atom was written in lowercase as part of an aromatic ring.
+    aromatic: bool,
+    /// Whether the atom was written without brackets, so that its hydrogens are implicit.
+    organic: bool,
+    /// Hydrogens written in brackets, such as the 4 of `[NH4+]`.
+    hydrogens: u32,
+    /// Charge written in brackets.
+    charge: i32,
+    /// Sum of the orders of the bonds of the atom, counting aromatic bonds as 1.
+    bonds: u32,
+}
+
+impl Atom {
+    /// Returns the number of hydrogens of the atom: the written ones in brackets, or the ones
+    /// that bring an atom of the organic subset to its lowest normal valence that fits its bonds.
+    /// Aromatic atoms use one valence for the ring, so that `c1ccccc1` has one hydrogen per carbon.
+    fn hydrogen_count(&self) -> u32 {
+        if !self.organic {
+            return self.hydrogens;
+        }
+        let valences = ORGANIC_VALENCES
+            .iter()
+            .find(|(symbol, _)| *symbol == self.key)
+            .map_or(&[][..], |(_, valences)| *valences);
+        if self.aromatic {
+            return valences
+                .first()
+                .map_or(0, |valence| valence.saturating_sub(self.bonds + 1));
+        }
+        valences
+            .iter()
+            .find(|valence| **valence >= self.bonds)
+            .map_or(0, |valence| valence - self.bonds)
+    }
+}
+
+/// Reads a SMILES string one character at a time.
+struct Reader<'a> {
+    smiles: &'a str,
+    chars: Vec<char>,
+    position: usize,
+}
+
+impl Reader<'_> {
+    fn peek(&self) -> Option<char> {
+        self.chars.get(self.position).copied()
+    }
+
+    fn next(&mut self) -> Option<char> {
+        let c = self.peek()?;
+        self.position += 1;
+        Some(c)
+    }
+
+    /// Reads a run of digits as a number, or returns `None` if there are none.
+    fn number(&mut self) -> Option<u32> {
+        let start = self.position;
+        while self.peek().is_some_and(|c| c.is_ascii_digit()) {
+            self.position += 1;
+        }
+        self.chars[start..self.position]
+            .iter()
+            .collect::<String>()
+            .parse()
+            .ok()
+    }
+
+    fn error(&self, reason: &str) -> ChemParseError {
+        ChemParseError::ParsingError(
+            format!("SMILES \"{}\"", self.smiles),
+            format!("{} at position {}", reason, self.position),
+        )
+    }
+}
+
+impl ChemParser {
+    /// Parses a SMILES string into the formula of the molecule, such as `C2H6O` for `CCO` and
+    /// `C6H6` for `c1ccccc1`. Hydrogens of atoms of the organic subset written without brackets
+    /// are implicit and added for their lowest normal valence; atoms in brackets carry their
+    /// hydrogens, charge and mass number, as in `[NH4+]` or `[13CH4]`. Disconnected parts
+    /// separated by `.` are added up, and the formula is written in Hill notation.
+    ///
+    /// Fails with [`ChemParseError::ParsingError`] if the string is not valid SMILES, with
+    /// unclosed branches or rings, with [`ChemParseError::InvalidElement`] or
+    /// [`ChemParseError::InvalidIsotope`] if an atom is not in the table, and with
+    /// [`ChemParseError::Overflow`] if the atoms or the charge add up beyond their range.
+    pub fn parse_smiles(&self, smiles: &str) -> Result<Formula, ChemParseError> {
+        let mut reader = Reader {
+            smiles,
+            chars: smiles.trim().chars().collect(),
+            position: 0,
+        };
+        let mut atoms: Vec<Atom> = Vec::new();
+        let mut previous: Option<usize> = None;
+        let mut branches: Vec<Option<usize>> = Vec::new();
+        let mut rings: HashMap<u32, (usize, Option<u32>)> = HashMap::new();
+        let mut bond: Option<u32> = None;
+
+        while let Some(c) = reader.peek() {
+            match c {
+                '-' | '/' | '\\' | ':' => bond = Some(1),
+                '=' => bond = Some(2),
+                '#' => bond = Some(3),
+                '$' => bond = Some(4),
+                '.' => previous = None,
+                '(' => {
+                    if previous.is_none() {
+                        return Err(reader.error("branch without an atom"));
+                    }
+                    branches.push(previous);
+                }
+                ')' => {
+                    previous = branches
+                        .pop()
+                        .ok_or_else(|| reader.error("unopened branch"))?;
+                }
+                '0'..='9' | '%' => {
+                    let atom = previous.ok_or_else(|| reader.error("ring bond without an atom"))?;
+                    reader.next();
+                    let ring = match c {
+                        '%' => reader
+                            .next()
+                            .zip(reader.next())
+                            .and_then(|(tens, ones)| {
+                                Some(tens.to_digit(10)? * 10 + ones.to_digit(10)?)
+                            })
+                            .ok_or_else(|| {
+                                reader.error("ring number after % without two digits")
+                            })?,
+                        _ => c.to_digit(10).unwrap_or_default(),
+                    };
+                    match rings.remove(&ring) {
+                        Some((other, other_bond)) => {
+                            let order = bond.or(other_bond).unwrap_or(1);
+                            atoms[atom].bonds += order;
+                            atoms[other].bonds += order;
+                        }
+                        None => {
+                            rings.insert(ring, (atom, bond));
+                        }
+                    }
+                    bond = None;
+                    continue;
+                }
+                _ => {
+                    let atom = match c {
+                        '[' => self.bracket_atom(&mut reader)?,
+                        _ => organic_atom(&mut reader)?,
+                    };
+                    let index = atoms.len();
+                    atoms.push(atom);
+                    if let Some(other) = previous {
+                        let order = bond.unwrap_or(1);
+                        atoms[index].bonds += order;
+                        atoms[other].bonds += order;
+                    }
+                    previous = Some(index);
+                    bond = None;
+                    continue;
+                }
+            }
+            reader.next();
+        }
+        if !branches.is_empty() {
+            return Err(reader.error("unclosed branch"));
+        }
+        if !rings.is_empty() {
+            return Err(reader.error("unclosed ring"));
+        }
+        if atoms.is_empty() {
+            return Err(reader.error("no atoms"));
+        }
+
+        let mut elements: BTreeMap<String, u32> = BTreeMap::new();
+        let mut charge: i32 = 0;
+        let overflow =
+            |what: &str| ChemParseError::Overflow(format!("{} of SMILES \"{}\"", what, smiles));
+        for atom in &atoms {
+            let count = elements.entry(atom.key.clone()).or_insert(0);
+            *count = count.checked_add(1).ok_or_else(|| overflow("atom count"))?;
+            let hydrogens = atom.hydrogen_count();
+            if hydrogens > 0 {
+                let count = elements.entry(String::from("H")).or_insert(0);
+                *count = count
+                    .checked_add(hydrogens)
+                    .ok_or_else(|| overflow("hydrogen count"))?;
+            }
+            charge = charge
+                .checked_add(atom.charge)
+                .ok_or_else(|| overflow("charge"))?;
+        }
+        let mut mass = 0.0;
+        for (key, count) in &elements {
+            let atomic_mass = self.get_table().get_mass(key).ok_or_else(|| {
+                ChemParseError::InvalidElement(key.clone(), self.get_table().suggest_symbols(key))
+            })?;
+            mass += atomic_mass * f64::from(*count);
+        }
+        Ok(Formula::from_composition(elements, mass, charge))
+    }
+
+    /// Reads an atom in brackets, such as `[13CH4]`, `[NH4+]` or `[C@@H]`.
+    fn bracket_atom(&self, reader: &mut Reader) -> Result<Atom, ChemParseError> {
+        reader.next();
+        let mass_number = reader.number();
+
+        let mut symbol = String::new();
+        let mut aromatic = false;
+        match reader.next() {
+            Some(first) if first.is_ascii_uppercase() => {
+                symbol.push(first);
+                if let Some(second) = reader.peek().filter(|c| c.is_ascii_lowercase()) {
+                    symbol.push(second);
+                    reader.next();
+                }
+            }
+            Some(first) if first.is_ascii_lowercase() => {
+                aromatic = true;
+                symbol.push(first.to_ascii_uppercase());
+                let pair: String = [first, reader.peek().unwrap_or(' ')].iter().collect();
+                if ["se", "as", "te"].contains(&pair.as_str()) {
+                    symbol.push(pair.chars().nth(1).unwrap_or_default());
+                    reader.next();
+                }
+            }
+            _ => return Err(reader.error("missing element symbol in brackets")),
+        }
+        if self.get_table().get_element(&symbol).is_none() {
+            return Err(ChemParseError::InvalidElement(
+                symbol.clone(),
+                self.get_table().suggest_symbols(&symbol),
+            ));
+        }
+        let key = match mass_number {
+            Some(mass_number) => {
+                let key = isotope_key(&symbol, u16::try_from(mass_number).unwrap_or(0));
+                if self.get_table().get_isotope(&key).is_none() {
+                    return Err(ChemParseError::InvalidIsotope(format!(
+                        "{}{}",
+                        mass_number, symbol
+                    )));
+                }
+                key
+            }
+            None => symbol,
+        };
+
+        // Chirality, such as `@`, `@@` or `@TH1`, does not change the formula.
+        let mut chiral = false;
+        while reader.peek() == Some('@') {
+            reader.next();
+            chiral = true;
+        }
+        if chiral
+            && reader
+                .peek()
+                .is_some_and(|c| c.is_ascii_uppercase() && c != 'H')
+        {
+            reader.next();
+            reader.next();
+            reader.number();
+        }
+
+        let mut hydrogens = 0;
+        if reader.peek() == Some('H') {
+            reader.next();
+            hydrogens = reader.number().unwrap_or(1);
+        }
+
+        let mut charge = 0;
+        if let Some(sign) = reader.peek().filter(|c| *c == '+' || *c == '-') {
+            let sign = if sign == '+' { 1 } else { -1 };
+            reader.next();
+            let mut magnitude = 1;
+            if let Some(number) = reader.number() {
+                magnitude = i32::try_from(number).map_err(|_| reader.error("charge too large"))?;
+            } else {
+                let repeated = if sign > 0 { '+' } else { '-' };
+                while reader.peek() == Some(repeated) {
+                    reader.next();
+                    magnitude += 1;
+                }
+            }
+            charge = sign * magnitude;
+        }
+
+        // Atom classes, such as `:1`, do not change the formula.
+        if reader.peek() == Some(':') {
+            reader.next();
+            reader.number();
+        }
+        if reader.next() != Some(']') {
+            return Err(reader.error("unclosed bracket atom"));
+        }
+
+        Ok(Atom {
+            key,
+            aromatic,
+            organic: false,
+            hydrogens,
+            charge,
+            bonds: 0,
+        })
+    }
+}
+
+/// Reads an atom of the organic subset written without brackets, such as `C`, `Cl` or `c`.
+fn organic_atom(reader: &mut Reader) -> Result<Atom, ChemParseError> {
+    let c = reader.peek().unwrap_or_default();
+    let (symbol, aromatic) = match (c, reader.chars.get(reader.position + 1)) {
+        ('C', Some('l')) => ("Cl", false),
+        ('B', Some('r')) => ("Br", false),
+        ('B', _) => ("B", false),
+        ('C', _) => ("C", false),
+        ('N', _) => ("N", false),
+        ('O', _) => ("O", false),
+        ('P', _) => ("P", false),
+        ('S', _) => ("S", false),
+        ('F', _) => ("F", false),
+        ('I', _) => ("I", false),
+        ('b', _) => ("B", true),
+        ('c', _) => ("C", true),
+        ('n', _) => ("N", true),
+        ('o', _) => ("O", true),
+        ('p', _) => ("P", true),
+        ('s', _) => ("S", true),
+        _ => return Err(reader.error(&format!("unexpected character '{}'", c))),
+    };
+    reader.position += if aromatic { 1 } else { symbol.len() };
+    Ok(Atom {
+        key: String::from(symbol),
+        aromatic,
+        organic: true,
+        hydrogens: 0,
+        charge: 0,
+        bonds: 0,
+    })
+}
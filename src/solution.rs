@@ -0,0 +1,143 @@
+//! # Solution Module
+//!
+//! This module works with solutions of a formula of known molarity and volume: conversions between
+//! molarity and the mass of solute, dilution by M1V1 = M2V2, and the stoichiometry of reactions
+//! between solutions, as in titrations.
+
+use crate::element::{Equation, Formula};
+use crate::ChemParseError;
+use serde::Serialize;
+
+/// Solution of a solute formula, with its molarity in mol/L and its volume in liters.
+#[derive(Debug, Clone, Serialize)]
+pub struct Solution {
+    /// Formula of the solute.
+    pub formula: Formula,
+    /// Concentration of the solute in moles per liter.
+    pub molarity: f64,
+    /// Volume of the solution in liters.
+    pub volume: f64,
+}
+
+impl Solution {
+    /// Creates a solution from its molarity in mol/L and volume in liters.
+    pub fn new(formula: Formula, molarity: f64, volume: f64) -> Self {
+        Solution {
+            formula,
+            molarity,
+            volume,
+        }
+    }
+
+    /// Creates the solution of a mass in grams of solute dissolved to a volume in liters, for
+    /// example 0.1 M for 5.844 g of `NaCl` in 1 L. Fails if the formula has no mass or the volume
+    /// is not positive.
+    pub fn from_mass(formula: Formula, grams: f64, volume: f64) -> Result<Self, ChemParseError> {
+        check_positive(&formula, "volume", volume)?;
+        if formula.mass <= 0.0 {
+            return Err(solution_error(
+                &formula,
+                "a solute without mass cannot be weighed",
+            ));
+        }
+        let molarity = grams / formula.mass / volume;
+        Ok(Solution::new(formula, molarity, volume))
+    }
+
+    /// Returns the amount of solute in moles.
+    pub fn moles(&self) -> f64 {
+        self.molarity * self.volume
+    }
+
+    /// Returns the mass of solute in grams.
+    pub fn mass(&self) -> f64 {
+        self.moles() * self.formula.mass
+    }
+
+    /// Returns the solution diluted to a larger total volume in liters, with the molarity given by
+    /// M1V1 = M2V2. Fails if the volume is smaller than the current one.
+    pub fn diluted_to(&self, volume: f64) -> Result<Solution, ChemParseError> {
+        check_positive(&self.formula, "volume", volume)?;
+        if volume < self.volume {
+            return Err(solution_error(
+                &self.formula,
+                "a dilution cannot reduce the volume",
+            ));
+        }
+        Ok(Solution::new(
+            self.formula.clone(),
+            self.moles() / volume,
+            volume,
+        ))
+    }
+
+    /// Returns the volume in liters of this stock solution needed to prepare a volume of a more
+    /// dilute solution, by M1V1 = M2V2. Fails if the target is more concentrated than the stock.
+    pub fn stock_volume(&self, molarity: f64, volume: f64) -> Result<f64, ChemParseError> {
+        check_positive(&self.formula, "molarity", self.molarity)?;
+        if molarity > self.molarity {
+            return Err(solution_error(
+                &self.formula,
+                "the target is more concentrated than the stock",
+            ));
+        }
+        Ok(molarity * volume / self.molarity)
+    }
+}
+
+impl Equation {
+    /// Returns the amount in moles of `target` that reacts with or is produced from a solution of
+    /// another species, as in [`Equation::moles_of`].
+    pub fn moles_from_solution(
+        &self,
+        target: &str,
+        given: &Solution,
+    ) -> Result<f64, ChemParseError> {
+        self.moles_of(target, (&given.formula.formula, given.moles()))
+    }
+
+    /// Returns the volume in liters of a solution of `target` of the given molarity that reacts
+    /// completely with a solution of another species, for example the volume of titrant needed to
+    /// reach the equivalence point.
+    pub fn volume_to_react(
+        &self,
+        target: &str,
+        molarity: f64,
+        given: &Solution,
+    ) -> Result<f64, ChemParseError> {
+        check_positive(&given.formula, "molarity", molarity)?;
+        Ok(self.moles_from_solution(target, given)? / molarity)
+    }
+
+    /// Returns the molarity of a volume in liters of a solution of `target` that reacted completely
+    /// with a solution of another species, for example the concentration of an analyte from the
+    /// volume of titrant used.
+    pub fn molarity_from_titration(
+        &self,
+        target: &str,
+        volume: f64,
+        given: &Solution,
+    ) -> Result<f64, ChemParseError> {
+        check_positive(&given.formula, "volume", volume)?;
+        Ok(self.moles_from_solution(target, given)? / volume)
+    }
+}
+
+/// Checks that a molarity or volume is positive.
+fn check_positive(formula: &Formula, quantity: &str, value: f64) -> Result<(), ChemParseError> {
+    if value > 0.0 {
+        Ok(())
+    } else {
+        Err(solution_error(
+            formula,
+            &format!("the {} must be positive", quantity),
+        ))
+    }
+}
+
+fn solution_error(formula: &Formula, reason: &str) -> ChemParseError {
+    ChemParseError::EvaluationError(
+        format!("solution of {}", formula.formula),
+        String::from(reason),
+    )
+}
@@ -0,0 +1,59 @@
+//! # Solve Module
+//!
+//! This module answers goal-seeking questions of gravimetric analysis, such as how many repeat
+//! units of a formula or how many waters of crystallization of a salt fit a measured molar mass.
+
+use crate::element::Formula;
+use crate::{ChemParseError, ChemParser};
+
+impl Formula {
+    /// Returns the whole number of formula units whose mass is closest to `target_mass`, with the
+    /// multiplied formula, for example `(6, C6H12O6)` for `CH2O` and 180.
+    ///
+    /// Fails if the formula has no mass, if the target is less than half a formula unit or if an
+    /// atom count overflows.
+    pub fn scale_to_mass(&self, target_mass: f64) -> Result<(u32, Formula), ChemParseError> {
+        let units = whole_units(&self.formula, target_mass, self.mass)?;
+        let scaled = self
+            .checked_mul(units)
+            .ok_or_else(|| ChemParseError::Overflow(format!("{} × {}", self.formula, units)))?;
+        Ok((units, scaled))
+    }
+}
+
+/// Returns the number of waters of crystallization of a hydrate of `anhydrous` whose molar mass is
+/// closest to `observed_mass`, for example 5 for `CuSO4` and 249.7 g/mol. The mass of water comes
+/// from the periodic table of the parser.
+///
+/// Fails if water cannot be parsed with the table, or if the observed mass is smaller than the
+/// mass of the anhydrous salt by more than half a water molecule.
+pub fn hydrate_count(
+    parser: &ChemParser,
+    anhydrous: &Formula,
+    observed_mass: f64,
+) -> Result<u32, ChemParseError> {
+    let water = parser.parse_formula("H2O")?.mass;
+    let water_mass = observed_mass - anhydrous.mass;
+    if water_mass.abs() < water / 2.0 {
+        return Ok(0);
+    }
+    whole_units(&format!("{}·nH2O", anhydrous.formula), water_mass, water)
+}
+
+/// Rounds the number of units of a mass that fit a target mass, failing if none fits.
+fn whole_units(formula: &str, target_mass: f64, unit_mass: f64) -> Result<u32, ChemParseError> {
+    let error = |reason: &str| {
+        ChemParseError::EvaluationError(format!("mass of {}", formula), String::from(reason))
+    };
+    if unit_mass.is_nan() || unit_mass <= 0.0 {
+        return Err(error("a formula without mass cannot be scaled to a mass"));
+    }
+    let units = (target_mass / unit_mass).round();
+    if units.is_nan() || units < 1.0 {
+        return Err(error("the mass is too small for one formula unit"));
+    }
+    if units > u32::MAX as f64 {
+        return Err(ChemParseError::Overflow(format!("units of {}", formula)));
+    }
+    Ok(units as u32)
+}
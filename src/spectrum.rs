@@ -0,0 +1,251 @@
+//! # Spectrum Module
+//!
+//! This module computes the theoretical isotope pattern of a formula for mass spectrometry: the
+//! isotopologue peaks with their m/z and relative intensities, obtained by convolving the natural
+//! isotope distributions of its elements. It also searches the elemental compositions that match
+//! a measured monoisotopic mass.
+
+use crate::element::{Formula, PeriodicTable};
+use crate::ChemParseError;
+use serde::Serialize;
+use std::ops::RangeInclusive;
+
+/// Mass of the electron in unified atomic mass units, removed from the mass of cations per charge.
+pub const ELECTRON_MASS: f64 = 0.000_548_579_909;
+
+/// Peaks with a smaller probability than this fraction of the largest peak are dropped while
+/// convolving, which keeps large formulas fast without visibly changing the pattern.
+const PRUNE_THRESHOLD: f64 = 1e-9;
+
+/// Peak of an isotope pattern.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct IsotopePeak {
+    /// Mass-to-charge ratio of the peak, which is its mass for neutral formulas.
+    pub mz: f64,
+    /// Intensity relative to the most intense peak, which has an intensity of 100.
+    pub intensity: f64,
+}
+
+/// Probability distribution over masses, as pairs of mass and probability sorted by mass.
+type Distribution = Vec<(f64, f64)>;
+
+impl Formula {
+    /// Computes the theoretical isotope pattern of the formula from the natural abundances of the
+    /// table, merging isotopologues whose masses differ by at most `resolution` into one peak at
+    /// their average mass. Isotopes written in the formula, such as `[13C]`, keep their own mass.
+    ///
+    /// Peaks are sorted by m/z; for ions the mass is corrected by the electron mass and divided by
+    /// the charge. Returns `None` if an element has no isotope with a known abundance.
+    pub fn isotope_pattern(
+        &self,
+        table: &PeriodicTable,
+        resolution: f64,
+    ) -> Option<Vec<IsotopePeak>> {
+        let mut keys: Vec<&String> = self.elements.keys().collect();
+        keys.sort();
+
+        let mut pattern: Distribution = vec![(0.0, 1.0)];
+        for key in keys {
+            let atom = atom_distribution(table, key)?;
+            let atoms = power(&atom, self.elements[key], resolution);
+            pattern = convolve(&pattern, &atoms, resolution);
+        }
+
+        let largest = pattern
+            .iter()
+            .map(|&(_, probability)| probability)
+            .fold(0.0, f64::max);
+        let charge = self.charge.unsigned_abs().max(1) as f64;
+        Some(
+            pattern
+                .into_iter()
+                .map(|(mass, probability)| IsotopePeak {
+                    mz: (mass - self.charge as f64 * ELECTRON_MASS) / charge,
+                    intensity: probability / largest * 100.0,
+                })
+                .collect(),
+        )
+    }
+}
+
+/// Returns the isotope distribution of one atom of an element, or of an isotope key.
+fn atom_distribution(table: &PeriodicTable, key: &str) -> Option<Distribution> {
+    if let Some(isotope) = table.get_isotope(key) {
+        return Some(vec![(isotope.mass, 1.0)]);
+    }
+    let distribution: Distribution = table
+        .isotopes_of(key)
+        .into_iter()
+        .filter_map(|isotope| Some((isotope.mass, isotope.abundance?)))
+        .filter(|&(_, abundance)| abundance > 0.0)
+        .collect();
+    (!distribution.is_empty()).then_some(distribution)
+}
+
+/// Returns the distribution of `count` atoms by repeated squaring of the distribution of one atom.
+fn power(atom: &Distribution, mut count: u32, resolution: f64) -> Distribution {
+    let mut result: Distribution = vec![(0.0, 1.0)];
+    let mut base = atom.clone();
+    while count > 0 {
+        if count & 1 == 1 {
+            result = convolve(&result, &base, resolution);
+        }
+        count >>= 1;
+        if count > 0 {
+            base = convolve(&base, &base, resolution);
+        }
+    }
+    result
+}
+
+/// Combines two independent distributions, merging close masses and dropping negligible peaks.
+fn convolve(a: &Distribution, b: &Distribution, resolution: f64) -> Distribution {
+    let mut combined: Distribution = a
+        .iter()
+        .flat_map(|&(mass_a, p_a)| {
+            b.iter()
+                .map(move |&(mass_b, p_b)| (mass_a + mass_b, p_a * p_b))
+        })
+        .collect();
+    combined.sort_by(|x, y| x.0.total_cmp(&y.0));
+
+    let mut merged: Distribution = Vec::new();
+    for (mass, probability) in combined {
+        match merged.last_mut() {
+            Some((last_mass, last_probability)) if mass - *last_mass <= resolution => {
+                let total = *last_probability + probability;
+                *last_mass = (*last_mass * *last_probability + mass * probability) / total;
+                *last_probability = total;
+            }
+            _ => merged.push((mass, probability)),
+        }
+    }
+
+    let largest = merged
+        .iter()
+        .map(|&(_, probability)| probability)
+        .fold(0.0, f64::max);
+    merged.retain(|&(_, probability)| probability >= largest * PRUNE_THRESHOLD);
+    merged
+}
+
+/// Elemental composition that matches a measured mass.
+#[derive(Debug, Clone)]
+pub struct MassCandidate {
+    /// Formula of the composition in Hill notation.
+    pub formula: Formula,
+    /// Monoisotopic mass of the formula.
+    pub mass: f64,
+    /// Difference from the measured mass in parts per million.
+    pub error_ppm: f64,
+}
+
+/// Search for the molecular formulas whose monoisotopic mass is within a tolerance of a measured
+/// mass, built from a set of elements with limits on their counts, for example
+/// `MassSearch::new(&table, 180.0634, 5.0).element("C", 0..=12).element("H", 0..=24).element("O", 0..=12)`.
+pub struct MassSearch<'a> {
+    table: &'a PeriodicTable,
+    target: f64,
+    tolerance_ppm: f64,
+    elements: Vec<(String, RangeInclusive<u32>)>,
+}
+
+impl<'a> MassSearch<'a> {
+    /// Creates a search for a monoisotopic mass with a tolerance in parts per million.
+    pub fn new(table: &'a PeriodicTable, target: f64, tolerance_ppm: f64) -> Self {
+        MassSearch {
+            table,
+            target,
+            tolerance_ppm,
+            elements: Vec::new(),
+        }
+    }
+
+    /// Allows an element, or an isotope key such as `13C`, with a count in the range.
+    pub fn element(mut self, symbol: &str, count: RangeInclusive<u32>) -> Self {
+        self.elements.push((symbol.to_string(), count));
+        self
+    }
+
+    /// Returns the matching formulas, closest to the measured mass first.
+    /// Fails if an allowed element has no monoisotopic mass in the table.
+    pub fn candidates(&self) -> Result<Vec<MassCandidate>, ChemParseError> {
+        let mut elements = Vec::new();
+        for (symbol, count) in &self.elements {
+            let mass = self.table.get_monoisotopic_mass(symbol).ok_or_else(|| {
+                ChemParseError::InvalidElement(symbol.clone(), self.table.suggest_symbols(symbol))
+            })?;
+            elements.push((symbol.as_str(), mass, count.clone()));
+        }
+        // Heavy elements first, so that their few possible counts prune the search early.
+        elements.sort_by(|a, b| b.1.total_cmp(&a.1));
+
+        let tolerance = self.target * self.tolerance_ppm / 1e6;
+        let mut search = Search {
+            lowest: vec![0.0; elements.len() + 1],
+            highest: vec![0.0; elements.len() + 1],
+            window: (self.target - tolerance)..=(self.target + tolerance),
+            counts: Vec::new(),
+            found: Vec::new(),
+            elements,
+        };
+        for index in (0..search.elements.len()).rev() {
+            let (_, mass, count) = &search.elements[index];
+            search.lowest[index] = search.lowest[index + 1] + mass * *count.start() as f64;
+            search.highest[index] = search.highest[index + 1] + mass * *count.end() as f64;
+        }
+        search.visit(0, 0.0);
+
+        let mut candidates = Vec::new();
+        for counts in search.found {
+            let formula = Formula::from_counts(&counts, self.table)?;
+            if formula.elements.is_empty() {
+                continue;
+            }
+            let mass = formula.monoisotopic_mass(self.table).unwrap_or_default();
+            candidates.push(MassCandidate {
+                error_ppm: (mass - self.target) / self.target * 1e6,
+                formula,
+                mass,
+            });
+        }
+        candidates.sort_by(|a, b| a.error_ppm.abs().total_cmp(&b.error_ppm.abs()));
+        Ok(candidates)
+    }
+}
+
+/// State of a depth-first search over the counts of the allowed elements.
+struct Search<'s> {
+    elements: Vec<(&'s str, f64, RangeInclusive<u32>)>,
+    /// Smallest mass that the elements from an index on can add.
+    lowest: Vec<f64>,
+    /// Largest mass that the elements from an index on can add.
+    highest: Vec<f64>,
+    window: RangeInclusive<f64>,
+    counts: Vec<(&'s str, u32)>,
+    found: Vec<Vec<(&'s str, u32)>>,
+}
+
+impl Search<'_> {
+    fn visit(&mut self, index: usize, mass: f64) {
+        if index == self.elements.len() {
+            if self.window.contains(&mass) {
+                self.found.push(self.counts.clone());
+            }
+            return;
+        }
+        let (symbol, atom_mass, count) = self.elements[index].clone();
+        for n in count {
+            let total = mass + atom_mass * n as f64;
+            if total + self.lowest[index + 1] > *self.window.end() {
+                break;
+            }
+            if total + self.highest[index + 1] < *self.window.start() {
+                continue;
+            }
+            self.counts.push((symbol, n));
+            self.visit(index + 1, total);
+            self.counts.pop();
+        }
+    }
+}
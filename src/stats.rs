@@ -0,0 +1,193 @@
+//! # Statistics Module
+//!
+//! This module summarizes a set of equations, such as a reaction dataset, with counts of species,
+//! terms and coefficients, so that datasets can be sanity-checked before they are curated, and finds
+//! formulas that are written in different ways across a dataset.
+
+use crate::element::{element_symbol, Equation, Formula, PeriodicTable, Term};
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+
+/// Distribution of species, terms and coefficients over a set of equations.
+#[derive(Debug, Clone, Default)]
+pub struct EquationStats {
+    /// Number of equations that were parsed.
+    pub equations: usize,
+    /// Number of inputs that failed to parse.
+    pub failed: usize,
+    /// Total number of reactant terms.
+    pub reactant_terms: usize,
+    /// Total number of product terms.
+    pub product_terms: usize,
+    /// Number of terms with each coefficient, with the fractional coefficients of an equation
+    /// cleared as in [`Equation::to_integer_coefficients`].
+    pub coefficients: BTreeMap<u32, usize>,
+    /// Number of equations each formula appears in as a reactant.
+    pub reactants: HashMap<String, usize>,
+    /// Number of equations each formula appears in as a product.
+    pub products: HashMap<String, usize>,
+    /// Element symbols used in any term.
+    pub elements: BTreeSet<String>,
+}
+
+impl EquationStats {
+    /// Adds a parsed equation to the statistics.
+    pub fn add(&mut self, equation: &Equation) {
+        self.equations += 1;
+        self.reactant_terms += equation.reactant_terms.len();
+        self.product_terms += equation.product_terms.len();
+        count_side(&mut self.reactants, &equation.reactant_terms);
+        count_side(&mut self.products, &equation.product_terms);
+        for term in equation
+            .reactant_terms
+            .iter()
+            .chain(&equation.product_terms)
+        {
+            *self.coefficients.entry(term.coefficient).or_insert(0) += 1;
+            self.elements.extend(
+                term.formula
+                    .elements
+                    .keys()
+                    .map(|key| element_symbol(key).to_string()),
+            );
+        }
+    }
+
+    /// Counts an input that failed to parse.
+    pub fn add_failure(&mut self) {
+        self.failed += 1;
+    }
+
+    /// Returns the number of distinct species among the reactants and products.
+    pub fn species(&self) -> usize {
+        self.reactants
+            .keys()
+            .chain(self.products.keys())
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    /// Returns the average number of reactant terms per equation.
+    pub fn average_reactant_terms(&self) -> f64 {
+        average(self.reactant_terms, self.equations)
+    }
+
+    /// Returns the average number of product terms per equation.
+    pub fn average_product_terms(&self) -> f64 {
+        average(self.product_terms, self.equations)
+    }
+
+    /// Returns up to `limit` reactants with the number of equations they appear in, most common first.
+    pub fn most_common_reactants(&self, limit: usize) -> Vec<(&str, usize)> {
+        most_common(&self.reactants, limit)
+    }
+
+    /// Returns up to `limit` products with the number of equations they appear in, most common first.
+    pub fn most_common_products(&self, limit: usize) -> Vec<(&str, usize)> {
+        most_common(&self.products, limit)
+    }
+
+    /// Returns the symbols of the elements of the table that no equation uses, in atomic-number order.
+    pub fn unused_elements<'a>(&self, table: &'a PeriodicTable) -> Vec<&'a str> {
+        table
+            .iter()
+            .filter(|element| !self.elements.contains(&element.symbol))
+            .map(|element| element.symbol.as_str())
+            .collect()
+    }
+}
+
+/// Counts each formula of a side once per equation.
+fn count_side(counts: &mut HashMap<String, usize>, terms: &[Term]) {
+    let formulas: BTreeSet<&String> = terms.iter().map(|term| &term.formula.formula).collect();
+    for formula in formulas {
+        *counts.entry(formula.clone()).or_insert(0) += 1;
+    }
+}
+
+fn average(total: usize, count: usize) -> f64 {
+    if count == 0 {
+        0.0
+    } else {
+        total as f64 / count as f64
+    }
+}
+
+/// Sorts formulas by their counts, breaking ties alphabetically so the order is stable.
+fn most_common(counts: &HashMap<String, usize>, limit: usize) -> Vec<(&str, usize)> {
+    let mut sorted: Vec<(&str, usize)> = counts
+        .iter()
+        .map(|(formula, &count)| (formula.as_str(), count))
+        .collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    sorted.truncate(limit);
+    sorted
+}
+
+/// Formulas of a dataset that have the same composition but are written differently.
+#[derive(Debug, Clone, PartialEq)]
+pub struct NotationCluster {
+    /// Hill notation of the shared composition.
+    pub canonical: String,
+    /// Notations of the composition with the number of times each is used, most common first.
+    pub notations: Vec<(String, usize)>,
+    /// Suggested notation to replace the others with: the most common one, preferring the Hill
+    /// notation on ties.
+    pub suggestion: String,
+}
+
+/// Groups the formulas of a dataset by composition to find inconsistent notations,
+/// such as `OH2` next to `H2O` or `HNaO` next to `NaOH`.
+///
+/// Isomers written structurally, such as `CH3COOH` and `HCOOCH3`, share a composition too,
+/// so clusters are suggestions to review rather than errors.
+#[derive(Debug, Clone, Default)]
+pub struct NotationClusters {
+    notations: HashMap<Formula, BTreeMap<String, usize>>,
+}
+
+impl NotationClusters {
+    /// Adds a formula as written in the dataset.
+    pub fn add(&mut self, formula: &Formula) {
+        let notations = self.notations.entry(formula.clone()).or_default();
+        *notations.entry(formula.formula.clone()).or_insert(0) += 1;
+    }
+
+    /// Adds the formulas of every term of an equation.
+    pub fn add_equation(&mut self, equation: &Equation) {
+        for term in equation
+            .reactant_terms
+            .iter()
+            .chain(&equation.product_terms)
+        {
+            self.add(&term.formula);
+        }
+    }
+
+    /// Returns the compositions written in more than one way, sorted by their Hill notation.
+    pub fn clusters(&self) -> Vec<NotationCluster> {
+        let mut clusters: Vec<NotationCluster> = self
+            .notations
+            .iter()
+            .filter(|(_, notations)| notations.len() > 1)
+            .map(|(formula, notations)| {
+                let canonical = formula.canonical();
+                let mut notations: Vec<(String, usize)> = notations
+                    .iter()
+                    .map(|(notation, &count)| (notation.clone(), count))
+                    .collect();
+                notations.sort_by(|a, b| {
+                    b.1.cmp(&a.1)
+                        .then((b.0 == canonical).cmp(&(a.0 == canonical)))
+                        .then(a.0.cmp(&b.0))
+                });
+                NotationCluster {
+                    suggestion: notations[0].0.clone(),
+                    canonical,
+                    notations,
+                }
+            })
+            .collect();
+        clusters.sort_by(|a, b| a.canonical.cmp(&b.canonical));
+        clusters
+    }
+}
@@ -0,0 +1,313 @@
+//! # Stoichiometry Module
+//!
+//! This module converts between the amounts of two species of a balanced equation using their
+//! coefficients and molar masses, for example how many grams of water burning 4 g of hydrogen gives,
+//! and compares a measured amount of product with the theoretical yield.
+
+use crate::element::{gcd, Equation, Formula, Term};
+use crate::ChemParseError;
+use serde::Serialize;
+use std::fmt::{self, Display, Formatter};
+
+/// Mole ratio between two species of an equation, in lowest terms, such as `1:1` for `H2` to `H2O`
+/// in `2H2 + O2 -> 2H2O`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Ratio {
+    /// Moles of the first species.
+    pub numerator: u32,
+    /// Moles of the second species.
+    pub denominator: u32,
+}
+
+impl Ratio {
+    /// Returns the ratio as a number, the moles of the first species per mole of the second.
+    pub fn value(&self) -> f64 {
+        self.numerator as f64 / self.denominator as f64
+    }
+}
+
+impl Display for Ratio {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{}:{}", self.numerator, self.denominator)
+    }
+}
+
+/// Amount of a species needed or produced, in moles and grams.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct Amount {
+    /// Formula of the species as written in the equation.
+    pub species: String,
+    /// Amount in moles.
+    pub moles: f64,
+    /// Mass in grams, zero for species without mass such as electrons.
+    pub mass: f64,
+}
+
+/// Percent yield of a product with the values it is computed from, for display in lab reports.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct PercentYield {
+    /// Reactant that runs out first and limits the theoretical yield.
+    pub limiting_reactant: String,
+    /// Mass in grams of product that the given reactants can produce.
+    pub theoretical_mass: f64,
+    /// Mass in grams of product actually obtained.
+    pub actual_mass: f64,
+    /// Actual mass as a percentage of the theoretical mass.
+    pub percent: f64,
+}
+
+/// Outcome of a reaction run with given amounts of its reactants until one of them runs out.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct LimitingReactant {
+    /// Reactant that runs out first.
+    pub reactant: String,
+    /// Number of times the equation as written takes place, in moles.
+    pub extent: f64,
+    /// Amount of each given reactant left over, zero for the limiting reactant.
+    pub excess: Vec<Amount>,
+    /// Amount of every product formed.
+    pub products: Vec<Amount>,
+}
+
+impl Equation {
+    /// Returns the amount in moles of `target` that reacts with or is produced from the given amount
+    /// of another species, as in `moles_of("H2O", ("O2", 1.5))` for `2H2 + O2 -> 2H2O`.
+    ///
+    /// Species are named by their formula as written or in Hill notation. Fails if the equation is
+    /// not balanced, a species is not part of it or is written on both sides, such as a catalyst,
+    /// or the given amount is not a finite positive number.
+    pub fn moles_of(&self, target: &str, given: (&str, f64)) -> Result<f64, ChemParseError> {
+        let (given, moles) = given;
+        self.check_amount(given, moles)?;
+        Ok(moles * self.mole_ratio(target, given)?.value())
+    }
+
+    /// Returns the mole ratio of two species in lowest terms, as in `mole_ratio("O2", "H2O")`, which
+    /// is `1:2` for `2H2 + O2 -> 2H2O`.
+    ///
+    /// Species are named like in [`Equation::moles_of`], which fails in the same cases.
+    pub fn mole_ratio(&self, first: &str, second: &str) -> Result<Ratio, ChemParseError> {
+        self.check_stoichiometric()?;
+        let (numerator, _) = self.species(first)?;
+        let (denominator, _) = self.species(second)?;
+        let divisor = gcd(numerator, denominator).max(1);
+        Ok(Ratio {
+            numerator: numerator / divisor,
+            denominator: denominator / divisor,
+        })
+    }
+
+    /// Returns the mass in grams of `target` that reacts with or is produced from the given mass in
+    /// grams of another species, as in `mass_of("H2O", ("H2", 4.0))` for `2H2 + O2 -> 2H2O`.
+    ///
+    /// Fails like [`Equation::moles_of`], and for species without mass, such as electrons.
+    pub fn mass_of(&self, target: &str, given: (&str, f64)) -> Result<f64, ChemParseError> {
+        let (given, grams) = given;
+        self.check_amount(given, grams)?;
+        self.check_stoichiometric()?;
+        let (target_coefficient, target_formula) = self.species(target)?;
+        let (given_coefficient, given_formula) = self.species(given)?;
+        if target_formula.mass <= 0.0 || given_formula.mass <= 0.0 {
+            return Err(self.stoichiometry_error(String::from(
+                "species without mass cannot be converted by mass",
+            )));
+        }
+        let moles =
+            grams / given_formula.mass * target_coefficient as f64 / given_coefficient as f64;
+        Ok(moles * target_formula.mass)
+    }
+
+    /// Returns the amount of every reactant needed to obtain the given mass in grams of `product`,
+    /// in the order of the reactants, as in `scale_for("C9H8O4", 50.0)` for the synthesis of aspirin.
+    ///
+    /// Fails like [`Equation::mass_of`].
+    pub fn scale_for(&self, product: &str, grams: f64) -> Result<Vec<Amount>, ChemParseError> {
+        let (_, product_formula) = self.species(product)?;
+        if product_formula.mass <= 0.0 {
+            return Err(self.stoichiometry_error(String::from(
+                "species without mass cannot be converted by mass",
+            )));
+        }
+        let moles = grams / product_formula.mass;
+        let mut amounts: Vec<Amount> = Vec::new();
+        for term in &self.reactant_terms {
+            let species = &term.formula.formula;
+            if amounts.iter().any(|amount| &amount.species == species) {
+                continue;
+            }
+            let reactant_moles = self.moles_of(species, (product, moles))?;
+            amounts.push(Amount {
+                species: species.clone(),
+                moles: reactant_moles,
+                mass: reactant_moles * term.formula.mass,
+            });
+        }
+        Ok(amounts)
+    }
+
+    /// Returns the percent yield of `product` from its measured mass in grams and the masses in
+    /// grams of the reactants used, as in `percent_yield("H2O", 30.0, &[("H2", 4.0), ("O2", 40.0)])`
+    /// for `2H2 + O2 -> 2H2O`. The reactant giving the least product limits the theoretical yield.
+    ///
+    /// Fails like [`Equation::mass_of`], if no reactant amount is given, or if the actual mass is
+    /// not a finite number of at least zero.
+    pub fn percent_yield(
+        &self,
+        product: &str,
+        actual_mass: f64,
+        reactant_amounts: &[(&str, f64)],
+    ) -> Result<PercentYield, ChemParseError> {
+        if !(actual_mass.is_finite() && actual_mass >= 0.0) {
+            return Err(self.stoichiometry_error(format!(
+                "the actual mass of {} must be a number of at least zero",
+                product.trim()
+            )));
+        }
+        let mut limiting: Option<(&str, f64)> = None;
+        for &(reactant, grams) in reactant_amounts {
+            let mass = self.mass_of(product, (reactant, grams))?;
+            if limiting.is_none_or(|(_, least)| mass < least) {
+                limiting = Some((reactant.trim(), mass));
+            }
+        }
+        let (limiting_reactant, theoretical_mass) = limiting.ok_or_else(|| {
+            self.stoichiometry_error(String::from("no reactant amount was given"))
+        })?;
+        Ok(PercentYield {
+            limiting_reactant: limiting_reactant.to_string(),
+            theoretical_mass,
+            actual_mass,
+            percent: actual_mass / theoretical_mass * 100.0,
+        })
+    }
+
+    /// Finds the reactant that runs out first from the moles of the reactants at hand, as in
+    /// `limiting_reactant(&[("H2", 2.0), ("O2", 2.0)])` for `2H2 + O2 -> 2H2O`, where `H2` runs out
+    /// after 2 moles of water are formed and 1 mole of `O2` is left over.
+    ///
+    /// Fails like [`Equation::moles_of`], if a given species is not a reactant, if an amount is
+    /// not a finite positive number or if no reactant amount is given.
+    pub fn limiting_reactant(
+        &self,
+        reactant_moles: &[(&str, f64)],
+    ) -> Result<LimitingReactant, ChemParseError> {
+        self.check_stoichiometric()?;
+        let mut given = Vec::new();
+        for &(reactant, moles) in reactant_moles {
+            let name = reactant.trim();
+            let is_reactant = self
+                .reactant_terms
+                .iter()
+                .any(|term| term.formula.formula == name || term.formula.canonical() == name);
+            if !is_reactant {
+                return Err(self.stoichiometry_error(format!("{} is not a reactant", name)));
+            }
+            self.check_amount(name, moles)?;
+            let (coefficient, formula) = self.species(name)?;
+            given.push((name, moles, coefficient as f64, formula.mass));
+        }
+        let (reactant, extent) = given
+            .iter()
+            .map(|&(name, moles, coefficient, _)| (name, moles / coefficient))
+            .min_by(|(_, first), (_, second)| first.total_cmp(second))
+            .ok_or_else(|| {
+                self.stoichiometry_error(String::from("no reactant amount was given"))
+            })?;
+
+        let excess = given
+            .iter()
+            .map(|&(name, moles, coefficient, mass)| {
+                let left = if name == reactant {
+                    0.0
+                } else {
+                    moles - extent * coefficient
+                };
+                Amount {
+                    species: name.to_string(),
+                    moles: left,
+                    mass: left * mass,
+                }
+            })
+            .collect();
+        let mut products: Vec<Amount> = Vec::new();
+        for term in &self.product_terms {
+            let species = &term.formula.formula;
+            if products.iter().any(|amount| &amount.species == species) {
+                continue;
+            }
+            let (coefficient, _) = self.species(species)?;
+            let moles = extent * coefficient as f64;
+            products.push(Amount {
+                species: species.clone(),
+                moles,
+                mass: moles * term.formula.mass,
+            });
+        }
+        Ok(LimitingReactant {
+            reactant: reactant.to_string(),
+            // The coefficients are stored multiplied by the denominator of fractional ones.
+            extent: extent * f64::from(self.denominator.max(1)),
+            excess,
+            products,
+        })
+    }
+
+    /// Returns the total coefficient and the formula of a species on the side it is written on.
+    ///
+    /// Fails if the species is not part of the equation, if it is written on both sides, such as
+    /// a catalyst, since it has no mole ratio to the other species, or if its coefficients
+    /// overflow.
+    pub(crate) fn species(&self, name: &str) -> Result<(u32, &Formula), ChemParseError> {
+        let name = name.trim();
+        let matches =
+            |term: &&Term| term.formula.formula == name || term.formula.canonical() == name;
+        let reactants: Vec<&Term> = self.reactant_terms.iter().filter(matches).collect();
+        let products: Vec<&Term> = self.product_terms.iter().filter(matches).collect();
+        let matching = match (reactants.is_empty(), products.is_empty()) {
+            (false, false) => {
+                return Err(
+                    self.stoichiometry_error(format!("{} is on both sides of the equation", name))
+                )
+            }
+            (false, true) => reactants,
+            (true, false) => products,
+            (true, true) => {
+                return Err(
+                    self.stoichiometry_error(format!("{} is not part of the equation", name))
+                )
+            }
+        };
+        let coefficient = matching
+            .iter()
+            .try_fold(0u32, |total, term| total.checked_add(term.coefficient))
+            .ok_or_else(|| ChemParseError::Overflow(format!("coefficient of {}", name)))?;
+        Ok((coefficient, &matching[0].formula))
+    }
+
+    /// Checks that the equation conserves every element, isotope and the charge.
+    pub(crate) fn check_stoichiometric(&self) -> Result<(), ChemParseError> {
+        if self.check_atom_balance(true) && self.check_charge_balance() {
+            Ok(())
+        } else {
+            Err(self.stoichiometry_error(String::from(
+                "the equation is not balanced, balance it first",
+            )))
+        }
+    }
+
+    /// Checks that a given amount of a species is a finite positive number.
+    fn check_amount(&self, species: &str, amount: f64) -> Result<(), ChemParseError> {
+        if amount.is_finite() && amount > 0.0 {
+            Ok(())
+        } else {
+            Err(self.stoichiometry_error(format!(
+                "the amount of {} must be a positive number",
+                species.trim()
+            )))
+        }
+    }
+
+    fn stoichiometry_error(&self, reason: String) -> ChemParseError {
+        ChemParseError::EvaluationError(format!("stoichiometry of {}", self.equation), reason)
+    }
+}
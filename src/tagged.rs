@@ -0,0 +1,153 @@
+//! # Tagged formulas Module
+//!
+//! This module defines formulas whose individual element occurrences can carry user tags,
+//! for example to mark a labelled position or to attribute atoms to their source in mixing calculations.
+
+use crate::element::Formula;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{isotope_pair_key, Rule};
+use alloc::collections::BTreeSet;
+use core::ops::{Add, Mul};
+use pest::iterators::Pair;
+
+/// Represents a single occurrence of an element symbol in a formula.
+#[derive(Debug, Clone, PartialEq)]
+pub struct AtomOccurrence {
+    /// Symbol of the element.
+    pub symbol: String,
+    /// Number of atoms this occurrence stands for, including group multipliers.
+    pub count: u32,
+    /// User tags attached to this occurrence.
+    pub tags: BTreeSet<String>,
+}
+
+/// Represents a chemical formula together with its tagged element occurrences.
+#[derive(Debug, Clone)]
+pub struct TaggedFormula {
+    /// Parsed formula with its composition and mass.
+    pub formula: Formula,
+    occurrences: Vec<AtomOccurrence>,
+}
+
+impl TaggedFormula {
+    /// Creates a new TaggedFormula from a parsed formula and its element occurrences.
+    pub fn new(formula: Formula, occurrences: Vec<AtomOccurrence>) -> Self {
+        TaggedFormula {
+            formula,
+            occurrences,
+        }
+    }
+
+    /// Returns the element occurrences in the order they appear in the formula.
+    pub fn occurrences(&self) -> &[AtomOccurrence] {
+        &self.occurrences
+    }
+
+    /// Attaches a tag to the occurrence at the given position.
+    /// Returns `false` if there is no occurrence at that position.
+    pub fn tag(&mut self, position: usize, tag: &str) -> bool {
+        match self.occurrences.get_mut(position) {
+            Some(occurrence) => {
+                occurrence.tags.insert(tag.to_string());
+                true
+            }
+            None => false,
+        }
+    }
+
+    /// Attaches a tag to every occurrence of the given element symbol.
+    pub fn tag_element(&mut self, symbol: &str, tag: &str) {
+        for occurrence in self.occurrences.iter_mut().filter(|o| o.symbol == symbol) {
+            occurrence.tags.insert(tag.to_string());
+        }
+    }
+
+    /// Counts the atoms of all occurrences carrying the given tag, saturating at `u32::MAX`.
+    pub fn count_tagged(&self, tag: &str) -> u32 {
+        self.occurrences
+            .iter()
+            .filter(|o| o.tags.contains(tag))
+            .fold(0u32, |total, o| total.saturating_add(o.count))
+    }
+}
+
+impl Add for TaggedFormula {
+    type Output = TaggedFormula;
+
+    /// Combines two tagged formulas, keeping the occurrences and tags of both.
+    fn add(mut self, other: TaggedFormula) -> TaggedFormula {
+        let notation = format!("{}{}", self.formula.formula, other.formula.formula);
+        self.formula = self.formula + other.formula;
+        self.formula.formula = notation;
+        self.occurrences.extend(other.occurrences);
+        self
+    }
+}
+
+impl Mul<u32> for TaggedFormula {
+    type Output = TaggedFormula;
+
+    /// Multiplies every occurrence of the formula, keeping its tags. Counts saturate instead of
+    /// overflowing, like those of the formula.
+    fn mul(mut self, factor: u32) -> TaggedFormula {
+        let notation = format!("({}){}", self.formula.formula, factor);
+        self.formula = self.formula * factor;
+        self.formula.formula = notation;
+        for occurrence in &mut self.occurrences {
+            occurrence.count = occurrence.count.saturating_mul(factor);
+        }
+        self
+    }
+}
+
+/// Collects the element occurrences of a parsed `formula` pair, applying group multipliers.
+pub(crate) fn collect_occurrences(
+    formula: Pair<Rule>,
+    multiplier: u32,
+    occurrences: &mut Vec<AtomOccurrence>,
+) {
+    let mut pairs = formula.into_inner().peekable();
+    while let Some(pair) = pairs.next() {
+        let index = match pairs.peek() {
+            Some(next) if next.as_rule() == Rule::index => {
+                let index = next.as_str().parse::<u32>().unwrap_or(1);
+                pairs.next();
+                index
+            }
+            _ => 1,
+        };
+
+        match pair.as_rule() {
+            Rule::element | Rule::isotope => occurrences.push(AtomOccurrence {
+                symbol: if pair.as_rule() == Rule::isotope {
+                    isotope_pair_key(&pair)
+                } else {
+                    pair.as_str().to_string()
+                },
+                count: index.saturating_mul(multiplier),
+                tags: BTreeSet::new(),
+            }),
+            Rule::group | Rule::complex => {
+                if let Some(inner) = pair.into_inner().next() {
+                    collect_occurrences(inner, multiplier.saturating_mul(index), occurrences);
+                }
+            }
+            Rule::adduct => {
+                let mut count = 1;
+                for inner in pair.into_inner() {
+                    match inner.as_rule() {
+                        Rule::adduct_count => count = inner.as_str().parse::<u32>().unwrap_or(1),
+                        Rule::adduct_formula => collect_occurrences(
+                            inner,
+                            multiplier.saturating_mul(count),
+                            occurrences,
+                        ),
+                        _ => {}
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+}
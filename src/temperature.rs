@@ -0,0 +1,203 @@
+//! # Temperature Module
+//!
+//! This module defines absolute temperatures that can be created from and read in kelvins, degrees
+//! Celsius and degrees Fahrenheit, and the phase transition temperatures of elements, which are
+//! given for each allotrope of elements such as carbon or sulfur.
+
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use core::fmt::{self, Display};
+use serde::de::{self, Deserializer};
+use serde::ser::{SerializeMap, Serializer};
+use serde::{Deserialize, Serialize};
+
+/// Absolute temperature, stored in kelvins and created from or read in any supported scale.
+/// Temperatures compare by their value regardless of the scale they were created in.
+#[derive(Debug, Clone, Copy, PartialEq, PartialOrd, Serialize)]
+pub struct Temperature {
+    kelvins: f64,
+}
+
+impl Temperature {
+    /// Creates a temperature in kelvins.
+    pub const fn kelvins(kelvins: f64) -> Self {
+        Temperature { kelvins }
+    }
+
+    /// Creates a temperature in degrees Celsius.
+    pub const fn celsius(degrees: f64) -> Self {
+        Temperature::kelvins(degrees + 273.15)
+    }
+
+    /// Creates a temperature in degrees Fahrenheit.
+    pub const fn fahrenheit(degrees: f64) -> Self {
+        Temperature::celsius((degrees - 32.0) * 5.0 / 9.0)
+    }
+
+    /// Returns the temperature in kelvins.
+    pub fn in_kelvins(&self) -> f64 {
+        self.kelvins
+    }
+
+    /// Returns the temperature in degrees Celsius.
+    pub fn in_celsius(&self) -> f64 {
+        self.kelvins - 273.15
+    }
+
+    /// Returns the temperature in degrees Fahrenheit.
+    pub fn in_fahrenheit(&self) -> f64 {
+        self.in_celsius() * 9.0 / 5.0 + 32.0
+    }
+}
+
+impl Display for Temperature {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} K", self.kelvins)
+    }
+}
+
+/// Temperature of a phase transition of an element, such as its melting point. Elements with
+/// several allotropes, such as graphite and diamond for carbon, have one temperature for each,
+/// which is unknown for some of them.
+#[derive(Debug, Clone, PartialEq)]
+pub enum TransitionTemperature {
+    /// The temperature of an element with a single form.
+    Single(Temperature),
+    /// The temperatures of the allotropes of an element in the order of the data, by name.
+    Allotropes(Vec<(String, Option<Temperature>)>),
+}
+
+impl TransitionTemperature {
+    /// Returns the temperature of an element with a single form, or of the first allotrope with
+    /// a known one, such as graphite for carbon.
+    pub fn temperature(&self) -> Option<Temperature> {
+        match self {
+            TransitionTemperature::Single(temperature) => Some(*temperature),
+            TransitionTemperature::Allotropes(allotropes) => {
+                allotropes.iter().find_map(|(_, temperature)| *temperature)
+            }
+        }
+    }
+
+    /// Returns the temperature of an allotrope by its name, such as `diamond`.
+    pub fn allotrope(&self, name: &str) -> Option<Temperature> {
+        match self {
+            TransitionTemperature::Single(_) => None,
+            TransitionTemperature::Allotropes(allotropes) => allotropes
+                .iter()
+                .find(|(allotrope, _)| allotrope == name)
+                .and_then(|(_, temperature)| *temperature),
+        }
+    }
+}
+
+/// Writes a single temperature as its value in kelvins and allotropes as an object of values.
+impl Serialize for TransitionTemperature {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        match self {
+            TransitionTemperature::Single(temperature) => {
+                serializer.serialize_f64(temperature.in_kelvins())
+            }
+            TransitionTemperature::Allotropes(allotropes) => {
+                let mut map = serializer.serialize_map(Some(allotropes.len()))?;
+                for (name, temperature) in allotropes {
+                    map.serialize_entry(name, &temperature.map(|t| t.in_kelvins()))?;
+                }
+                map.end()
+            }
+        }
+    }
+}
+
+/// Transition temperature as written in element data: a number of kelvins, an object of
+/// allotropes, or text holding either, as in the CSV data.
+#[derive(Deserialize)]
+#[serde(untagged)]
+enum RawTransition {
+    Kelvins(f64),
+    Allotropes(AllotropeMap),
+    Text(String),
+}
+
+/// Allotrope temperatures of an object, in the order they are written.
+struct AllotropeMap(Vec<(String, Option<f64>)>);
+
+impl<'de> Deserialize<'de> for AllotropeMap {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct AllotropeVisitor;
+
+        impl<'de> de::Visitor<'de> for AllotropeVisitor {
+            type Value = AllotropeMap;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                write!(f, "an object of allotrope temperatures")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(
+                self,
+                mut map: A,
+            ) -> Result<AllotropeMap, A::Error> {
+                let mut allotropes = Vec::new();
+                while let Some(entry) = map.next_entry()? {
+                    allotropes.push(entry);
+                }
+                Ok(AllotropeMap(allotropes))
+            }
+        }
+
+        deserializer.deserialize_map(AllotropeVisitor)
+    }
+}
+
+/// Reads an optional transition temperature in kelvins. Text such as
+/// `{'graphite': 4762.15, 'diamond': None}` lists allotropes, and empty text or `{}` is unknown.
+pub(crate) fn deserialize_transition<'de, D: Deserializer<'de>>(
+    deserializer: D,
+) -> Result<Option<TransitionTemperature>, D::Error> {
+    let allotropes = |allotropes: Vec<(String, Option<f64>)>| {
+        (!allotropes.is_empty()).then(|| {
+            TransitionTemperature::Allotropes(
+                allotropes
+                    .into_iter()
+                    .map(|(name, kelvins)| (name, kelvins.map(Temperature::kelvins)))
+                    .collect(),
+            )
+        })
+    };
+    Ok(match Option::<RawTransition>::deserialize(deserializer)? {
+        None => None,
+        Some(RawTransition::Kelvins(kelvins)) => {
+            Some(TransitionTemperature::Single(Temperature::kelvins(kelvins)))
+        }
+        Some(RawTransition::Allotropes(AllotropeMap(list))) => allotropes(list),
+        Some(RawTransition::Text(text)) => match text.trim() {
+            "" => None,
+            text => match text.parse::<f64>() {
+                Ok(kelvins) => Some(TransitionTemperature::Single(Temperature::kelvins(kelvins))),
+                Err(_) => allotropes(parse_allotropes(text).ok_or_else(|| {
+                    de::Error::custom(format!("invalid transition temperature: {}", text))
+                })?),
+            },
+        },
+    })
+}
+
+/// Parses allotrope temperatures written as `{'white': 317.3, 'red': None}`.
+fn parse_allotropes(text: &str) -> Option<Vec<(String, Option<f64>)>> {
+    let inner = text.strip_prefix('{')?.strip_suffix('}')?.trim();
+    if inner.is_empty() {
+        return Some(Vec::new());
+    }
+    inner
+        .split(',')
+        .map(|entry| {
+            let (name, value) = entry.split_once(':')?;
+            let name = name.trim().trim_matches(|c| c == '\'' || c == '"');
+            let kelvins = match value.trim() {
+                "None" | "null" => None,
+                value => Some(value.parse().ok()?),
+            };
+            Some((name.to_string(), kelvins))
+        })
+        .collect()
+}
@@ -0,0 +1,128 @@
+//! # Testing Module
+//!
+//! This module helps snapshot tests of parse results, with `insta` or with plain golden files:
+//! results are serialized into stable strings, with sorted keys and rounded masses, and equation
+//! fixtures are loaded from the `tests/data` directory of the crate under test.
+
+use crate::balance::Balance;
+use crate::ChemParseError;
+use serde::Serialize;
+use serde_json::Value;
+use std::env;
+use std::fs;
+use std::io;
+use std::path::PathBuf;
+
+/// Number of decimal places that masses and other floats are rounded to in snapshots.
+pub const SNAPSHOT_DECIMALS: i32 = 4;
+
+/// Environment variable that makes [`assert_golden`] rewrite the golden files instead of comparing them.
+pub const UPDATE_VARIABLE: &str = "UPDATE_GOLDEN";
+
+/// Equation or formula of a fixture file with its line number.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Fixture {
+    /// Number of the line in the fixture file, starting from 1.
+    pub line: usize,
+    /// Text of the line without surrounding whitespace.
+    pub input: String,
+}
+
+/// Serializes a value, such as a [`Formula`](crate::element::Formula) or an
+/// [`Equation`](crate::element::Equation), into pretty-printed JSON with sorted object keys and
+/// floats rounded to [`SNAPSHOT_DECIMALS`] places, so snapshots do not depend on the order of hash
+/// maps or the last digits of a mass.
+pub fn snapshot<T: Serialize>(value: &T) -> String {
+    match serde_json::to_value(value) {
+        Ok(value) => serde_json::to_string_pretty(&round_floats(value)).unwrap_or_default(),
+        Err(e) => format!("unserializable: {}", e),
+    }
+}
+
+/// Describes the outcome of balancing an equation in one line per reaction, such as
+/// `balanced: 2H2 + O2 -> 2H2O`, or the code and message of the error.
+pub fn snapshot_balance(result: &Result<Balance, ChemParseError>) -> String {
+    match result {
+        Ok(Balance::AlreadyBalanced) => String::from("already balanced"),
+        Ok(Balance::Balanced(equation)) => format!("balanced: {}", equation.to_parseable_string()),
+        Ok(Balance::Independent(reactions)) => reactions
+            .iter()
+            .map(|reaction| format!("independent: {}", reaction.to_parseable_string()))
+            .collect::<Vec<_>>()
+            .join("\n"),
+        Ok(Balance::Impossible(keys)) => format!("impossible: {}", keys.join(", ")),
+        Err(e) => format!("error {}: {}", e.code(), e),
+    }
+}
+
+/// Returns the `tests/data` directory of the crate under test, found through the
+/// `CARGO_MANIFEST_DIR` variable that cargo sets when it runs tests.
+pub fn fixture_dir() -> PathBuf {
+    env::var_os("CARGO_MANIFEST_DIR")
+        .map(PathBuf::from)
+        .unwrap_or_default()
+        .join("tests")
+        .join("data")
+}
+
+/// Loads the lines of a fixture file in `tests/data`, skipping blank lines and `#` comments.
+pub fn load_fixtures(name: &str) -> io::Result<Vec<Fixture>> {
+    let data = fs::read_to_string(fixture_dir().join(name))?;
+    Ok(data
+        .lines()
+        .enumerate()
+        .map(|(index, line)| Fixture {
+            line: index + 1,
+            input: line.trim().to_string(),
+        })
+        .filter(|fixture| !fixture.input.is_empty() && !fixture.input.starts_with('#'))
+        .collect())
+}
+
+/// Compares a snapshot with the golden file `tests/data/<name>`, panicking with both texts when they
+/// differ or when the golden file does not exist. With the `UPDATE_GOLDEN` environment variable set,
+/// the file is written instead.
+pub fn assert_golden(name: &str, actual: &str) {
+    let path = fixture_dir().join(name);
+    if env::var_os(UPDATE_VARIABLE).is_some() {
+        if let Some(dir) = path.parent() {
+            fs::create_dir_all(dir).expect("golden directory can be created");
+        }
+        fs::write(&path, actual).expect("golden file can be written");
+        return;
+    }
+    let Ok(expected) = fs::read_to_string(&path) else {
+        panic!(
+            "golden file {} cannot be read, rerun with {}=1 to create it\n--- actual\n{}",
+            path.display(),
+            UPDATE_VARIABLE,
+            actual
+        );
+    };
+    assert!(
+        expected == actual,
+        "snapshot differs from {}, rerun with {}=1 to update it\n--- expected\n{}\n--- actual\n{}",
+        path.display(),
+        UPDATE_VARIABLE,
+        expected,
+        actual
+    );
+}
+
+/// Rounds every float of a JSON value, leaving integers as they are.
+fn round_floats(value: Value) -> Value {
+    match value {
+        Value::Number(number) if number.is_f64() => {
+            let scale = 10f64.powi(SNAPSHOT_DECIMALS);
+            let rounded = (number.as_f64().unwrap_or_default() * scale).round() / scale;
+            serde_json::Number::from_f64(rounded).map_or(Value::Null, Value::Number)
+        }
+        Value::Array(values) => Value::Array(values.into_iter().map(round_floats).collect()),
+        Value::Object(map) => Value::Object(
+            map.into_iter()
+                .map(|(key, value)| (key, round_floats(value)))
+                .collect(),
+        ),
+        value => value,
+    }
+}
@@ -0,0 +1,236 @@
+//! # Thermo Module
+//!
+//! This module computes standard reaction enthalpies, entropies and Gibbs free energies from
+//! thermodynamic data of formation, and predicts whether a reaction is spontaneous at a
+//! temperature. The data at 298.15 K is embedded from `data/thermo.csv` or loaded from CSV.
+
+use crate::element::{Equation, Formula, State, Term};
+use crate::gas::Temperature;
+use crate::ChemParseError;
+#[cfg(feature = "csv")]
+use crate::ChemParser;
+#[cfg(feature = "csv")]
+use serde::Deserialize;
+use std::collections::HashMap;
+
+/// Standard thermodynamic data of formation embedded at compile time from `data/thermo.csv`.
+#[cfg(feature = "datasets")]
+const THERMO_CSV: &str = include_str!("../data/thermo.csv");
+
+/// Standard temperature of thermodynamic data, 298.15 K.
+pub const STANDARD_THERMO_TEMPERATURE: f64 = 298.15;
+
+/// Row of a thermodynamic dataset for a species in its state.
+#[cfg(feature = "csv")]
+#[derive(Deserialize)]
+struct ThermoRow {
+    formula: String,
+    #[serde(default)]
+    state: Option<String>,
+    enthalpy: f64,
+    #[serde(default)]
+    entropy: Option<f64>,
+    #[serde(default)]
+    gibbs: Option<f64>,
+}
+
+/// Key of a species in a [`ThermoTable`]: the formula as written and its physical state.
+type SpeciesKey = (String, Option<State>);
+
+/// Standard enthalpies of formation in kJ/mol, standard molar entropies in J/(mol·K) and Gibbs
+/// free energies of formation in kJ/mol, looked up by the formula as written and its physical
+/// state, so that isomers such as `C2H5OH` and `CH3OCH3`, and states such as `H2O(l)` and
+/// `H2O(g)`, have their own entries.
+///
+/// A species written without a state uses the first state loaded for its formula, which the
+/// embedded data lists as the standard state.
+#[derive(Debug, Clone, Default)]
+pub struct ThermoTable {
+    enthalpies: HashMap<SpeciesKey, f64>,
+    entropies: HashMap<SpeciesKey, f64>,
+    gibbs_energies: HashMap<SpeciesKey, f64>,
+    standard_states: HashMap<String, Option<State>>,
+}
+
+impl ThermoTable {
+    /// Creates an empty table.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Creates a table from the thermodynamic data embedded in the library.
+    #[cfg(feature = "datasets")]
+    pub fn embedded() -> Result<Self, ChemParseError> {
+        ThermoTable::from_csv_str(THERMO_CSV, &ChemParser::new())
+    }
+
+    /// Loads a table from CSV data with `formula` and `enthalpy` columns and optional `state`,
+    /// `entropy` and `gibbs` columns, parsing the formulas with the parser. States are written
+    /// with their symbols, such as `l` or `aq`.
+    #[cfg(feature = "csv")]
+    pub fn from_csv_str(data: &str, parser: &ChemParser) -> Result<Self, ChemParseError> {
+        let error = |e| ChemParseError::DataError(String::from("thermodynamic data"), e);
+        let mut table = ThermoTable::new();
+        for row in csv::Reader::from_reader(data.as_bytes()).deserialize() {
+            let row: ThermoRow = row.map_err(|e| error(Box::new(e)))?;
+            let formula = parser
+                .parse_formula(&row.formula)
+                .map_err(|e| error(Box::new(e)))?;
+            let state = match row.state.as_deref() {
+                None | Some("") => None,
+                Some(symbol) => Some(State::from_symbol(symbol).ok_or_else(|| {
+                    error(format!("unknown state `{}` of {}", symbol, row.formula).into())
+                })?),
+            };
+            table.insert(&formula, state, row.enthalpy);
+            if let Some(entropy) = row.entropy {
+                table.insert_entropy(&formula, state, entropy);
+            }
+            if let Some(gibbs) = row.gibbs {
+                table.insert_gibbs(&formula, state, gibbs);
+            }
+        }
+        Ok(table)
+    }
+
+    /// Adds or replaces the enthalpy of formation of a species in a state, in kJ/mol.
+    pub fn insert(&mut self, formula: &Formula, state: Option<State>, enthalpy: f64) {
+        let key = self.key_for_insert(formula, state);
+        self.enthalpies.insert(key, enthalpy);
+    }
+
+    /// Adds or replaces the standard molar entropy of a species in a state, in J/(mol·K).
+    pub fn insert_entropy(&mut self, formula: &Formula, state: Option<State>, entropy: f64) {
+        let key = self.key_for_insert(formula, state);
+        self.entropies.insert(key, entropy);
+    }
+
+    /// Adds or replaces the Gibbs free energy of formation of a species in a state, in kJ/mol.
+    pub fn insert_gibbs(&mut self, formula: &Formula, state: Option<State>, gibbs: f64) {
+        let key = self.key_for_insert(formula, state);
+        self.gibbs_energies.insert(key, gibbs);
+    }
+
+    /// Returns the enthalpy of formation of a species in a state in kJ/mol, if the table has it.
+    pub fn get(&self, formula: &Formula, state: Option<State>) -> Option<f64> {
+        self.enthalpies.get(&self.key(formula, state)).copied()
+    }
+
+    /// Returns the standard molar entropy of a species in a state in J/(mol·K), if the table has
+    /// it.
+    pub fn entropy(&self, formula: &Formula, state: Option<State>) -> Option<f64> {
+        self.entropies.get(&self.key(formula, state)).copied()
+    }
+
+    /// Returns the Gibbs free energy of formation of a species in a state in kJ/mol, if the table
+    /// has it.
+    pub fn gibbs(&self, formula: &Formula, state: Option<State>) -> Option<f64> {
+        self.gibbs_energies.get(&self.key(formula, state)).copied()
+    }
+
+    /// Returns the key of a species to store, recording the first state of its formula as the
+    /// standard state.
+    fn key_for_insert(&mut self, formula: &Formula, state: Option<State>) -> SpeciesKey {
+        self.standard_states
+            .entry(formula.formula.clone())
+            .or_insert(state);
+        (formula.formula.clone(), state)
+    }
+
+    /// Returns the key of a species to look up, with the standard state of its formula when the
+    /// state is not written.
+    fn key(&self, formula: &Formula, state: Option<State>) -> SpeciesKey {
+        let state = state.or_else(|| {
+            self.standard_states
+                .get(&formula.formula)
+                .copied()
+                .flatten()
+        });
+        (formula.formula.clone(), state)
+    }
+}
+
+impl Equation {
+    /// Returns the standard reaction enthalpy in kJ/mol of reaction, ΔH°rxn = ΣΔHf(products) −
+    /// ΣΔHf(reactants) weighted by the coefficients, for example −890.5 for
+    /// `CH4 + 2O2 -> CO2 + 2H2O` with the embedded table.
+    ///
+    /// Fails if the equation is not balanced, or with [`ChemParseError::MissingData`] listing
+    /// every species that the table does not have.
+    pub fn reaction_enthalpy(&self, table: &ThermoTable) -> Result<f64, ChemParseError> {
+        self.reaction_sum("enthalpy of formation", |formula, state| {
+            table.get(formula, state)
+        })
+    }
+
+    /// Returns the standard reaction entropy in J/(mol·K), ΔS°rxn = ΣS°(products) − ΣS°(reactants).
+    ///
+    /// Fails like [`Equation::reaction_enthalpy`].
+    pub fn reaction_entropy(&self, table: &ThermoTable) -> Result<f64, ChemParseError> {
+        self.reaction_sum("entropy", |formula, state| table.entropy(formula, state))
+    }
+
+    /// Returns the Gibbs free energy of reaction in kJ/mol at a temperature, ΔG = ΔH° − TΔS°,
+    /// assuming that the standard enthalpy and entropy of reaction do not depend on temperature.
+    ///
+    /// Fails like [`Equation::reaction_enthalpy`].
+    pub fn gibbs_energy(
+        &self,
+        table: &ThermoTable,
+        temperature: Temperature,
+    ) -> Result<f64, ChemParseError> {
+        let enthalpy = self.reaction_enthalpy(table)?;
+        let entropy = self.reaction_entropy(table)?;
+        Ok(enthalpy - temperature.in_kelvins() * entropy / 1000.0)
+    }
+
+    /// Returns the standard Gibbs free energy of reaction in kJ/mol at 298.15 K from the Gibbs free
+    /// energies of formation, ΔG°rxn = ΣΔGf(products) − ΣΔGf(reactants).
+    ///
+    /// Fails like [`Equation::reaction_enthalpy`].
+    pub fn standard_gibbs_energy(&self, table: &ThermoTable) -> Result<f64, ChemParseError> {
+        self.reaction_sum("Gibbs free energy of formation", |formula, state| {
+            table.gibbs(formula, state)
+        })
+    }
+
+    /// Returns whether the reaction is spontaneous at a temperature, that is whether its Gibbs
+    /// free energy from [`Equation::gibbs_energy`] is negative.
+    pub fn is_spontaneous(
+        &self,
+        table: &ThermoTable,
+        temperature: Temperature,
+    ) -> Result<bool, ChemParseError> {
+        Ok(self.gibbs_energy(table, temperature)? < 0.0)
+    }
+
+    /// Sums a property of the products minus the reactants, weighted by the coefficients.
+    fn reaction_sum(
+        &self,
+        property: &str,
+        value: impl Fn(&Formula, Option<State>) -> Option<f64>,
+    ) -> Result<f64, ChemParseError> {
+        self.check_stoichiometric()?;
+        let mut missing: Vec<String> = Vec::new();
+        let mut side_sum = |terms: &[Term]| -> f64 {
+            terms
+                .iter()
+                .map(|term| match value(&term.formula, term.state) {
+                    Some(value) => self.coefficient(term).value() * value,
+                    None => {
+                        if !missing.contains(&term.formula.formula) {
+                            missing.push(term.formula.formula.clone());
+                        }
+                        0.0
+                    }
+                })
+                .sum()
+        };
+        let reactants = side_sum(&self.reactant_terms);
+        let products = side_sum(&self.product_terms);
+        if !missing.is_empty() {
+            return Err(ChemParseError::MissingData(String::from(property), missing));
+        }
+        Ok(products - reactants)
+    }
+}
@@ -0,0 +1,139 @@
+//! # Trivial names Module
+//!
+//! This module resolves trivial and common names of substances, such as "baking soda" or
+//! "table salt", to their formulas, using the names embedded from `data/trivial_names.csv` and
+//! dictionaries that applications add to a parser.
+
+use crate::element::Formula;
+#[cfg(not(feature = "std"))]
+use crate::prelude::*;
+use crate::{ChemParseError, ChemParser};
+use alloc::collections::BTreeMap;
+#[cfg(feature = "csv")]
+use serde::Deserialize;
+#[cfg(feature = "csv")]
+use std::error::Error;
+#[cfg(feature = "datasets")]
+use std::sync::OnceLock;
+
+/// Trivial names embedded at compile time from `data/trivial_names.csv`.
+#[cfg(feature = "datasets")]
+const TRIVIAL_NAMES_CSV: &str = include_str!("../data/trivial_names.csv");
+
+/// Trivial name of a substance as stored in a dictionary file.
+#[cfg(feature = "csv")]
+#[derive(Deserialize)]
+struct TrivialName {
+    name: String,
+    formula: String,
+}
+
+/// Dictionary of trivial names and the formulas they stand for. Names are matched without
+/// regard to case and extra whitespace, so "Baking  Soda" finds "baking soda".
+#[derive(Debug, Clone, Default)]
+pub struct NameDictionary {
+    formulas: BTreeMap<String, String>,
+}
+
+impl NameDictionary {
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the dictionary embedded in the library.
+    #[cfg(feature = "datasets")]
+    pub fn embedded() -> &'static NameDictionary {
+        static EMBEDDED: OnceLock<NameDictionary> = OnceLock::new();
+        EMBEDDED.get_or_init(|| {
+            let mut dictionary = NameDictionary::new();
+            dictionary
+                .load_csv_str(TRIVIAL_NAMES_CSV)
+                .expect("embedded trivial names are valid");
+            dictionary
+        })
+    }
+
+    /// Adds a name for a formula, replacing the formula of a name that is already in the
+    /// dictionary.
+    pub fn insert(&mut self, name: &str, formula: &str) {
+        self.formulas
+            .insert(normalize_name(name), formula.trim().to_string());
+    }
+
+    /// Returns the formula of a name, as written in the dictionary.
+    pub fn get(&self, name: &str) -> Option<&str> {
+        self.formulas.get(&normalize_name(name)).map(String::as_str)
+    }
+
+    /// Returns the number of names in the dictionary.
+    pub fn len(&self) -> usize {
+        self.formulas.len()
+    }
+
+    /// Checks whether the dictionary has no names.
+    pub fn is_empty(&self) -> bool {
+        self.formulas.is_empty()
+    }
+
+    /// Loads names from a CSV file with `name` and `formula` columns.
+    #[cfg(feature = "csv")]
+    pub fn load_csv(&mut self, path: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.load(csv::Reader::from_path(path)?)
+    }
+
+    /// Loads names from CSV data with `name` and `formula` columns.
+    #[cfg(feature = "csv")]
+    pub fn load_csv_str(&mut self, data: &str) -> Result<(), Box<dyn Error + Send + Sync>> {
+        self.load(csv::Reader::from_reader(data.as_bytes()))
+    }
+
+    #[cfg(feature = "csv")]
+    fn load<R: std::io::Read>(
+        &mut self,
+        mut rdr: csv::Reader<R>,
+    ) -> Result<(), Box<dyn Error + Send + Sync>> {
+        for result in rdr.deserialize() {
+            let entry: TrivialName = result?;
+            self.insert(&entry.name, &entry.formula);
+        }
+        Ok(())
+    }
+}
+
+impl ChemParser {
+    /// Adds the names of a dictionary to the parser, taking precedence over the embedded names
+    /// and the names added before.
+    pub fn with_names(mut self, names: NameDictionary) -> Self {
+        self.names.formulas.extend(names.formulas);
+        self
+    }
+
+    /// Returns the names added to the parser, for adding more.
+    pub fn names_mut(&mut self) -> &mut NameDictionary {
+        &mut self.names
+    }
+
+    /// Resolves a trivial name, such as "baking soda", to its parsed formula, `NaHCO3`. Names
+    /// added to the parser are looked up before the embedded names.
+    ///
+    /// Fails with [`ChemParseError::MissingData`] if no dictionary has the name, or with the
+    /// error of [`ChemParser::parse_formula`] if its formula cannot be parsed.
+    pub fn resolve_name(&self, name: &str) -> Result<Formula, ChemParseError> {
+        let formula = self.names.get(name);
+        #[cfg(feature = "datasets")]
+        let formula = formula.or_else(|| NameDictionary::embedded().get(name));
+        let formula = formula.ok_or_else(|| {
+            ChemParseError::MissingData(String::from("formula"), vec![name.trim().to_string()])
+        })?;
+        self.parse_formula(formula)
+    }
+}
+
+/// Lowercases a name and collapses its whitespace.
+fn normalize_name(name: &str) -> String {
+    name.split_whitespace()
+        .collect::<Vec<_>>()
+        .join(" ")
+        .to_lowercase()
+}
@@ -0,0 +1,78 @@
+//! # Uncertainty Module
+//!
+//! This module propagates the uncertainties of the standard atomic weights through the
+//! composition of a formula, so that molar masses can be reported with the number of significant
+//! figures the atomic weights support.
+
+use crate::element::{element_symbol, Formula, PeriodicTable};
+use serde::Serialize;
+use std::fmt::{self, Display};
+
+/// Represents a molar mass together with its uncertainty, in g/mol.
+#[derive(Debug, Clone, Copy, PartialEq, Serialize)]
+pub struct UncertainMass {
+    /// Molar mass of the formula.
+    pub value: f64,
+    /// Uncertainty of the molar mass.
+    pub uncertainty: f64,
+}
+
+impl UncertainMass {
+    /// Returns the uncertainty relative to the molar mass.
+    pub fn relative(&self) -> f64 {
+        self.uncertainty / self.value
+    }
+
+    /// Returns the number of decimal places the mass is significant to: the uncertainty is kept
+    /// to two significant figures when it starts with a 1 and to one otherwise.
+    pub fn decimals(&self) -> usize {
+        if self.uncertainty <= 0.0 || !self.uncertainty.is_finite() {
+            return 0;
+        }
+        let exponent = self.uncertainty.log10().floor() as i32;
+        let leading = (self.uncertainty / 10f64.powi(exponent)).floor();
+        let figures = if leading < 2.0 { 2 } else { 1 };
+        (figures - 1 - exponent).max(0) as usize
+    }
+}
+
+/// Writes the mass and its uncertainty rounded to the significant decimal places, such as
+/// `18.0150 ± 0.0011`.
+impl Display for UncertainMass {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        if self.uncertainty == 0.0 {
+            return write!(f, "{} ± 0", self.value);
+        }
+        let decimals = self.decimals();
+        write!(
+            f,
+            "{:.*} ± {:.*}",
+            decimals, self.value, decimals, self.uncertainty
+        )
+    }
+}
+
+impl Formula {
+    /// Returns the molar mass of the formula with the uncertainty of the atomic masses of its
+    /// elements. The uncertainties of the atoms of one element are added, as they all have the
+    /// same atomic mass, and those of different elements are combined as the root of the sum of
+    /// their squares. Isotopes have exact masses.
+    ///
+    /// Returns `None` if an element of the formula is not in the table or has no known
+    /// uncertainty, such as technetium.
+    pub fn mass_with_uncertainty(&self, table: &PeriodicTable) -> Option<UncertainMass> {
+        let mut variance = 0.0;
+        for (key, count) in &self.elements {
+            if table.get_isotope(key).is_some() {
+                continue;
+            }
+            let element = table.get_element(element_symbol(key))?;
+            let uncertainty = element.atomic_mass_uncertainty? * f64::from(*count);
+            variance += uncertainty * uncertainty;
+        }
+        Some(UncertainMass {
+            value: self.mass,
+            uncertainty: variance.sqrt(),
+        })
+    }
+}
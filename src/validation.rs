@@ -0,0 +1,382 @@
+//! # Validation Module
+//!
+//! This module checks equations against named validation profiles, curated presets of the checks
+//! that different uses of equation files need: a teaching profile that requires balanced equations
+//! with state symbols and coefficients in lowest terms, and a data-ingest profile that tolerates
+//! letter-case mistakes and reports problems as warnings. Unbalanced equations are diagnosed
+//! element by element, with the coefficients that would balance them.
+
+use crate::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use crate::element::{count_atoms, gcd, side_charge, Equation, Term};
+use crate::normalize::normalize_case;
+use crate::{ChemParseError, ChemParser};
+use serde::Serialize;
+use std::collections::BTreeMap;
+use std::fmt::{self, Display, Formatter};
+
+/// Named preset of validation rules.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+#[serde(rename_all = "kebab-case")]
+pub enum Profile {
+    /// Only requires that the equation parses.
+    #[default]
+    Default,
+    /// Requires balanced equations with state symbols and coefficients in lowest terms, as
+    /// expected in teaching material.
+    StrictTeaching,
+    /// Corrects the letter case of element symbols and reports unbalanced equations as warnings,
+    /// for loading data of varying quality.
+    DataIngest,
+}
+
+impl Profile {
+    /// All profiles.
+    pub const ALL: [Profile; 3] = [
+        Profile::Default,
+        Profile::StrictTeaching,
+        Profile::DataIngest,
+    ];
+
+    /// Returns the profile with the given name, such as `strict-teaching`.
+    pub fn from_name(name: &str) -> Option<Self> {
+        Profile::ALL
+            .into_iter()
+            .find(|profile| profile.name() == name.to_lowercase())
+    }
+
+    /// Returns the name of the profile, such as `strict-teaching`.
+    pub fn name(&self) -> &'static str {
+        match self {
+            Profile::Default => "default",
+            Profile::StrictTeaching => "strict-teaching",
+            Profile::DataIngest => "data-ingest",
+        }
+    }
+
+    /// Returns the rules of the profile.
+    pub fn rules(&self) -> ValidationRules {
+        match self {
+            Profile::Default => ValidationRules::default(),
+            Profile::StrictTeaching => ValidationRules {
+                require_balance: true,
+                require_states: true,
+                require_lowest_terms: true,
+                ..ValidationRules::default()
+            },
+            Profile::DataIngest => ValidationRules {
+                require_balance: true,
+                lenient_case: true,
+                warnings_only: true,
+                ..ValidationRules::default()
+            },
+        }
+    }
+}
+
+/// Checks applied to an equation by [`ChemParser::validate_equation`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize)]
+pub struct ValidationRules {
+    /// Require that the equation conserves every element, isotope and the charge.
+    pub require_balance: bool,
+    /// Require a state symbol, such as `(aq)`, on every species other than electrons.
+    pub require_states: bool,
+    /// Require coefficients without a common factor.
+    pub require_lowest_terms: bool,
+    /// Correct the letter case of element symbols, such as `NACL` to `NaCl`, when the equation
+    /// does not parse as written.
+    pub lenient_case: bool,
+    /// Report issues as warnings instead of failing.
+    pub warnings_only: bool,
+}
+
+/// Problem found by a validation rule.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum Issue {
+    /// The equation does not conserve every element, isotope and the charge.
+    Unbalanced,
+    /// The species has no state symbol.
+    MissingState(String),
+    /// The coefficients have this common factor.
+    NotLowestTerms(u32),
+    /// The letter case of element symbols was corrected to parse the equation, giving this input.
+    LetterCase(String),
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            Issue::Unbalanced => write!(f, "the equation is not balanced"),
+            Issue::MissingState(species) => write!(f, "{} has no state symbol", species),
+            Issue::NotLowestTerms(factor) => {
+                write!(f, "the coefficients have the common factor {}", factor)
+            }
+            Issue::LetterCase(corrected) => write!(f, "letter case corrected to {}", corrected),
+        }
+    }
+}
+
+/// Equation that passed validation, with the issues reported as warnings.
+#[derive(Debug, Clone)]
+pub struct Validation {
+    /// Parsed equation.
+    pub equation: Equation,
+    /// Issues of a profile that reports them as warnings.
+    pub warnings: Vec<Issue>,
+}
+
+impl ChemParser {
+    /// Parses an equation and checks it against validation rules, usually those of a
+    /// [`Profile`], as in `validate_equation(input, &Profile::StrictTeaching.rules())`.
+    ///
+    /// Fails if the equation does not parse, or with [`ChemParseError::ValidationError`] listing
+    /// every issue unless the rules report them as warnings.
+    pub fn validate_equation(
+        &self,
+        input: &str,
+        rules: &ValidationRules,
+    ) -> Result<Validation, ChemParseError> {
+        let mut issues = Vec::new();
+        let equation = match self.parse_equation(input) {
+            Ok(equation) => equation,
+            Err(e) if rules.lenient_case => {
+                let corrected = normalize_case(input, self.get_table());
+                let equation = self.parse_equation(&corrected).map_err(|_| e)?;
+                issues.push(Issue::LetterCase(corrected));
+                equation
+            }
+            Err(e) => return Err(e),
+        };
+
+        if rules.require_balance && equation.check_stoichiometric().is_err() {
+            issues.push(Issue::Unbalanced);
+        }
+        if rules.require_states {
+            issues.extend(
+                equation
+                    .terms()
+                    .into_iter()
+                    .filter(|term| term.state.is_none() && !term.formula.elements.is_empty())
+                    .map(|term| Issue::MissingState(term.formula.formula.clone())),
+            );
+        }
+        // Fractional coefficients are written on purpose, so only whole ones are checked.
+        if rules.require_lowest_terms && equation.denominator <= 1 {
+            let factor = equation
+                .terms()
+                .iter()
+                .fold(0, |factor, term| gcd(factor, term.coefficient));
+            if factor > 1 {
+                issues.push(Issue::NotLowestTerms(factor));
+            }
+        }
+
+        if issues.is_empty() || rules.warnings_only {
+            Ok(Validation {
+                equation,
+                warnings: issues,
+            })
+        } else {
+            Err(ChemParseError::ValidationError(
+                input.trim().to_string(),
+                issues.iter().map(Issue::to_string).collect(),
+            ))
+        }
+    }
+}
+
+/// Numbers of atoms of an element or isotope, or the charge, on the two sides of an equation.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ElementBalance {
+    /// Symbol of the element, the key of an isotope such as `13C`, or `charge`.
+    pub element: String,
+    /// Number on the reactant side.
+    pub reactants: i64,
+    /// Number on the product side.
+    pub products: i64,
+    /// Number on the product side minus that on the reactant side.
+    pub delta: i64,
+}
+
+impl ElementBalance {
+    fn new(element: &str, reactants: i64, products: i64) -> Self {
+        ElementBalance {
+            element: element.to_string(),
+            reactants,
+            products,
+            delta: products - reactants,
+        }
+    }
+
+    /// Checks if both sides have the same number.
+    pub fn is_balanced(&self) -> bool {
+        self.delta == 0
+    }
+}
+
+/// Writes the counts as `H: 2 on the left, 4 on the right`.
+impl Display for ElementBalance {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(
+            f,
+            "{}: {} on the left, {} on the right",
+            self.element, self.reactants, self.products
+        )
+    }
+}
+
+/// Coefficient of a species that a suggested fix changes.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct CoefficientChange {
+    /// Formula of the species as written.
+    pub formula: String,
+    /// Coefficient as written.
+    pub written: u32,
+    /// Coefficient that balances the equation.
+    pub suggested: u32,
+}
+
+/// Balanced equation suggested for an unbalanced one, with the coefficients to change.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Suggestion {
+    /// Equation with the smallest balancing coefficients.
+    pub equation: String,
+    /// Coefficients that differ from the written ones, reactants first.
+    pub changes: Vec<CoefficientChange>,
+}
+
+/// Diagnosis of the balance of an equation, see [`Equation::diagnose`].
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct Diagnosis {
+    /// Equation as written, with fractional coefficients cleared.
+    pub equation: String,
+    /// Counts of every element and isotope in alphabetical order, followed by the charge when a
+    /// species is charged.
+    pub elements: Vec<ElementBalance>,
+    /// Smallest coefficients that balance the equation, if it is unbalanced and has a single
+    /// solution.
+    pub suggestion: Option<Suggestion>,
+}
+
+impl Diagnosis {
+    /// Checks if every element, isotope and the charge is conserved.
+    pub fn is_balanced(&self) -> bool {
+        self.elements.iter().all(ElementBalance::is_balanced)
+    }
+
+    /// Returns the elements, isotopes and charge that are not conserved.
+    pub fn imbalances(&self) -> impl Iterator<Item = &ElementBalance> {
+        self.elements
+            .iter()
+            .filter(|element| !element.is_balanced())
+    }
+}
+
+impl Equation {
+    /// Diagnoses the balance of the equation: the numbers of atoms of each element and isotope and
+    /// the charge on both sides, and, for an unbalanced equation, the smallest coefficients that
+    /// balance it with the coefficients to change, as `2H2 + O2 -> 2H2O` changing `H2` from 1 to 2
+    /// and `H2O` from 1 to 2 for `H2 + O2 -> H2O`.
+    ///
+    /// No fix is suggested for equations that [`Equation::balance`] cannot balance with a single
+    /// solution within [`DEFAULT_MAX_COEFFICIENT`]. An equation written with fractional
+    /// coefficients is diagnosed with them cleared by [`Equation::to_integer_coefficients`], so
+    /// that the atoms are counted in whole numbers.
+    pub fn diagnose(&self) -> Diagnosis {
+        let equation = self.to_integer_coefficients();
+        let elements = equation.element_balances();
+        let balanced = elements.iter().all(ElementBalance::is_balanced);
+        let fix = if balanced {
+            None
+        } else {
+            equation.balance(DEFAULT_MAX_COEFFICIENT).ok()
+        };
+        let suggestion = match fix {
+            Some(Balance::Balanced(fixed)) => Some(Suggestion {
+                changes: coefficient_changes(&equation.reactant_terms, &fixed.reactant_terms)
+                    .chain(coefficient_changes(
+                        &equation.product_terms,
+                        &fixed.product_terms,
+                    ))
+                    .collect(),
+                equation: fixed.equation,
+            }),
+            _ => None,
+        };
+        Diagnosis {
+            equation: equation.equation,
+            elements,
+            suggestion,
+        }
+    }
+
+    /// Checks that the equation conserves every element, isotope and the charge, such as
+    /// `Fe^3+ + e^- -> Fe^2+`.
+    ///
+    /// Fails with [`ChemParseError::ValidationError`] listing every imbalance, such as
+    /// `H: 2 on the left, 4 on the right` for each unbalanced element and
+    /// `charge: 2 on the left, 0 on the right`. Atoms of an equation written with fractional
+    /// coefficients are counted with them cleared, as in [`Equation::diagnose`].
+    pub fn validate(&self) -> Result<(), ChemParseError> {
+        let imbalances: Vec<String> = self
+            .to_integer_coefficients()
+            .element_balances()
+            .iter()
+            .filter(|element| !element.is_balanced())
+            .map(ElementBalance::to_string)
+            .collect();
+        if imbalances.is_empty() {
+            Ok(())
+        } else {
+            Err(ChemParseError::ValidationError(
+                self.equation.clone(),
+                imbalances,
+            ))
+        }
+    }
+
+    /// Counts the atoms of every element and isotope, in alphabetical order, and the charge on
+    /// both sides when a species is charged.
+    fn element_balances(&self) -> Vec<ElementBalance> {
+        let reactants = count_atoms(&self.reactant_terms, true);
+        let products = count_atoms(&self.product_terms, true);
+        let mut keys: Vec<&String> = reactants.keys().chain(products.keys()).collect();
+        keys.sort();
+        keys.dedup();
+
+        let count = |side: &BTreeMap<String, u64>, key: &String| {
+            side.get(key).map_or(0, |&count| count as i64)
+        };
+        let mut elements: Vec<ElementBalance> = keys
+            .into_iter()
+            .map(|key| ElementBalance::new(key, count(&reactants, key), count(&products, key)))
+            .collect();
+        if self.terms().iter().any(|term| term.formula.charge != 0) {
+            elements.push(ElementBalance::new(
+                "charge",
+                side_charge(&self.reactant_terms),
+                side_charge(&self.product_terms),
+            ));
+        }
+
+        elements
+    }
+}
+
+/// Pairs the terms of a side as written with those of the balanced side by formula and state,
+/// and returns the coefficients that differ.
+fn coefficient_changes<'a>(
+    written: &'a [Term],
+    balanced: &'a [Term],
+) -> impl Iterator<Item = CoefficientChange> + 'a {
+    written.iter().filter_map(|term| {
+        let fixed = balanced.iter().find(|fixed| {
+            fixed.formula.formula == term.formula.formula && fixed.state == term.state
+        })?;
+        (fixed.coefficient != term.coefficient).then(|| CoefficientChange {
+            formula: term.formula.formula.clone(),
+            written: term.coefficient,
+            suggested: fixed.coefficient,
+        })
+    })
+}
@@ -0,0 +1,131 @@
+//! # Visitor Module
+//!
+//! This module walks the syntax tree of a formula and calls a [`FormulaVisitor`] for every node,
+//! with the multiplier of the groups and adducts around it, so that custom analyses such as
+//! charge accounting, fragment extraction or rendering do not have to repeat the group arithmetic.
+//!
+//! ```
+//! use chemistry_parser::ast::{ElementNode, FormulaAst};
+//! use chemistry_parser::visit::{walk, FormulaVisitor};
+//!
+//! struct HydrogenCounter(u32);
+//!
+//! impl FormulaVisitor for HydrogenCounter {
+//!     fn visit_element(&mut self, element: &ElementNode, multiplier: u32) {
+//!         if element.symbol == "H" {
+//!             self.0 += element.count * multiplier;
+//!         }
+//!     }
+//! }
+//!
+//! let mut counter = HydrogenCounter(0);
+//! walk(&FormulaAst::parse("(NH4)2SO4").unwrap(), &mut counter);
+//! assert_eq!(counter.0, 8);
+//! ```
+
+use crate::ast::{
+    AdductNode, ChargeNode, ElementNode, EquationAst, FormulaAst, FormulaNode, GroupNode,
+    SpeciesNode, TermNode,
+};
+use crate::element::gcd;
+
+/// Callbacks for the nodes of a formula syntax tree, called by [`walk`] in the order the nodes are
+/// written. Every method does nothing by default, so visitors implement only what they need.
+///
+/// The `multiplier` is the product of the counts of the enclosing groups and adducts and of the
+/// coefficient of an equation term, saturating at `u32::MAX`. An element stands for
+/// `element.count * multiplier` atoms.
+pub trait FormulaVisitor {
+    /// Called for every element or isotope.
+    fn visit_element(&mut self, _element: &ElementNode, _multiplier: u32) {}
+
+    /// Called before the nodes of a group, with the multiplier outside of the group.
+    fn enter_group(&mut self, _group: &GroupNode, _multiplier: u32) {}
+
+    /// Called after the nodes of a group, with the multiplier outside of the group.
+    fn leave_group(&mut self, _group: &GroupNode, _multiplier: u32) {}
+
+    /// Called for every charge, with the multiplier of the formula it is written in.
+    fn visit_charge(&mut self, _charge: &ChargeNode, _multiplier: u32) {}
+
+    /// Called before the nodes of an adduct, with the multiplier outside of the adduct.
+    fn enter_adduct(&mut self, _adduct: &AdductNode, _multiplier: u32) {}
+
+    /// Called after the nodes of an adduct, with the multiplier outside of the adduct.
+    fn leave_adduct(&mut self, _adduct: &AdductNode, _multiplier: u32) {}
+
+    /// Called before the formula of an equation term by [`walk_equation`].
+    fn enter_term(&mut self, _term: &TermNode, _reactant: bool) {}
+
+    /// Called after the formula of an equation term by [`walk_equation`].
+    fn leave_term(&mut self, _term: &TermNode, _reactant: bool) {}
+}
+
+/// Walks a formula syntax tree depth first, calling the visitor for every node.
+pub fn walk<V: FormulaVisitor + ?Sized>(formula: &FormulaAst, visitor: &mut V) {
+    walk_with_multiplier(formula, 1, visitor);
+}
+
+/// Walks the formulas of an equation syntax tree, reactants first, with the coefficient of each
+/// term as the multiplier of its formula. Electrons, photons and heat are passed to the term
+/// callbacks only.
+///
+/// Fractional coefficients are multiplied by the common denominator of the equation, as in
+/// [`crate::element::Equation::reactant_terms`], so `H2 + 1/2O2 -> H2O` is walked as
+/// `2H2 + O2 -> 2H2O`.
+pub fn walk_equation<V: FormulaVisitor + ?Sized>(equation: &EquationAst, visitor: &mut V) {
+    let sides = [(&equation.reactants, true), (&equation.products, false)];
+    let denominator =
+        sides
+            .iter()
+            .flat_map(|(terms, _)| terms.iter())
+            .fold(1u32, |denominator, term| {
+                let coefficient = term.coefficient.denominator;
+                (denominator / gcd(denominator, coefficient)).saturating_mul(coefficient)
+            });
+    for (terms, reactant) in sides {
+        for term in terms {
+            visitor.enter_term(term, reactant);
+            if let SpeciesNode::Formula(formula) = &term.species {
+                let multiplier = term
+                    .coefficient
+                    .numerator
+                    .saturating_mul(denominator / term.coefficient.denominator);
+                walk_with_multiplier(formula, multiplier, visitor);
+            }
+            visitor.leave_term(term, reactant);
+        }
+    }
+}
+
+/// Walks a formula whose atoms are multiplied by `multiplier`.
+fn walk_with_multiplier<V: FormulaVisitor + ?Sized>(
+    formula: &FormulaAst,
+    multiplier: u32,
+    visitor: &mut V,
+) {
+    for node in &formula.nodes {
+        match node {
+            FormulaNode::Element(element) => visitor.visit_element(element, multiplier),
+            FormulaNode::Group(group) => {
+                visitor.enter_group(group, multiplier);
+                walk_with_multiplier(
+                    &group.formula,
+                    multiplier.saturating_mul(group.count),
+                    visitor,
+                );
+                visitor.leave_group(group, multiplier);
+            }
+            FormulaNode::Charge(charge) => visitor.visit_charge(charge, multiplier),
+        }
+    }
+    for adduct in &formula.adducts {
+        visitor.enter_adduct(adduct, multiplier);
+        walk_with_multiplier(
+            &adduct.formula,
+            multiplier.saturating_mul(adduct.count),
+            visitor,
+        );
+        visitor.leave_adduct(adduct, multiplier);
+    }
+}
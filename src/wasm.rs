@@ -0,0 +1,56 @@
+//! # WebAssembly Module
+//!
+//! This module exposes the parser to JavaScript through `wasm-bindgen`, so that browser-based
+//! chemistry tools can parse formulas and equations without a server. It uses the periodic table
+//! embedded in the library, as there is no filesystem to load data from, and returns results as
+//! JSON strings to be read with `JSON.parse`. Errors are thrown as JavaScript `Error`s.
+
+use crate::element::Equation;
+use crate::{ChemParseError, ChemParser};
+use serde::Serialize;
+use std::sync::OnceLock;
+use wasm_bindgen::prelude::*;
+
+/// Result of checking an equation, as returned by [`check`].
+#[derive(Debug, Clone, Serialize)]
+pub struct CheckResult {
+    /// The equation as parsed.
+    pub equation: Equation,
+    /// Whether the equation conserves every element and the charge.
+    pub balanced: bool,
+    /// Imbalances of the equation, such as `H: 2 on the left, 1 on the right`.
+    pub issues: Vec<String>,
+}
+
+/// Returns the parser shared by all calls, created on first use.
+fn parser() -> &'static ChemParser {
+    static PARSER: OnceLock<ChemParser> = OnceLock::new();
+    PARSER.get_or_init(ChemParser::new)
+}
+
+/// Parses a formula, such as `H2SO4`, into the JSON of a [`crate::element::Formula`].
+#[wasm_bindgen(js_name = parseFormula)]
+pub fn parse_formula(formula: &str) -> Result<String, JsError> {
+    Ok(serde_json::to_string(&parser().parse_formula(formula)?)?)
+}
+
+/// Parses an equation, such as `2H2 + O2 -> 2H2O`, into the JSON of an [`Equation`].
+#[wasm_bindgen(js_name = parseEquation)]
+pub fn parse_equation(equation: &str) -> Result<String, JsError> {
+    Ok(serde_json::to_string(&parser().parse_equation(equation)?)?)
+}
+
+/// Parses an equation and checks whether it is balanced, returning the JSON of a [`CheckResult`].
+#[wasm_bindgen]
+pub fn check(equation: &str) -> Result<String, JsError> {
+    let equation = parser().parse_equation(equation)?;
+    let issues = match equation.validate() {
+        Err(ChemParseError::ValidationError(_, issues)) => issues,
+        _ => Vec::new(),
+    };
+    Ok(serde_json::to_string(&CheckResult {
+        balanced: issues.is_empty(),
+        equation,
+        issues,
+    })?)
+}
@@ -0,0 +1,68 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::acid_base::AcidBase;
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_classify_acid_base() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for (formula, class) in [
+        ("HCl", Some(AcidBase::StrongAcid)),
+        ("H2SO4", Some(AcidBase::StrongAcid)),
+        ("CH3COOH", Some(AcidBase::WeakAcid)),
+        ("HBrO", Some(AcidBase::WeakAcid)),
+        ("CH3CH2COOH", Some(AcidBase::WeakAcid)),
+        ("Ba(OH)2", Some(AcidBase::StrongBase)),
+        ("NH3", Some(AcidBase::WeakBase)),
+        ("Mg(OH)2", Some(AcidBase::WeakBase)),
+        ("H2O", None),
+        ("CH3OH", None),
+        ("NaCl", None),
+        ("HgO", None),
+    ] {
+        assert_eq!(
+            parser.classify_acid_base(&parser.parse_formula(formula)?)?,
+            class,
+            "{}",
+            formula
+        );
+    }
+    assert!(AcidBase::StrongBase.is_strong() && !AcidBase::StrongBase.is_acid());
+
+    Ok(())
+}
+
+#[test]
+fn test_neutralize() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let neutralize = |acid: &str, base: &str| -> anyhow::Result<String> {
+        let equation =
+            parser.neutralize(&parser.parse_formula(acid)?, &parser.parse_formula(base)?)?;
+        Ok(equation.equation)
+    };
+
+    assert_eq!(
+        neutralize("H2SO4", "NaOH")?,
+        "H2SO4 + 2NaOH -> Na2SO4 + 2H2O"
+    );
+    assert_eq!(
+        neutralize("CH3COOH", "KOH")?,
+        "CH3COOH + KOH -> KCH3COO + H2O"
+    );
+    assert_eq!(
+        neutralize("H3PO4", "Mg(OH)2")?,
+        "2H3PO4 + 3Mg(OH)2 -> Mg3(PO4)2 + 6H2O"
+    );
+    assert_eq!(neutralize("HCl", "NH3")?, "HCl + NH3 -> NH4Cl");
+
+    let error = parser
+        .neutralize(
+            &parser.parse_formula("NaOH")?,
+            &parser.parse_formula("HCl")?,
+        )
+        .unwrap_err();
+    assert!(matches!(error, ChemParseError::CannotPredict(_, _)));
+
+    Ok(())
+}
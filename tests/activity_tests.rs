@@ -0,0 +1,57 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+fn predict(parser: &ChemParser, reactants: &[&str]) -> anyhow::Result<Option<String>> {
+    let formulas = reactants
+        .iter()
+        .map(|formula| parser.parse_formula(formula))
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(parser
+        .predict_single_replacement(&formulas)?
+        .map(|equation| equation.equation))
+}
+
+#[test]
+fn test_single_replacement() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert_eq!(
+        predict(&parser, &["Zn", "CuSO4"])?.as_deref(),
+        Some("Zn + CuSO4 -> ZnSO4 + Cu")
+    );
+    assert_eq!(
+        predict(&parser, &["CuSO4", "Al"])?.as_deref(),
+        Some("3CuSO4 + 2Al -> Al2(SO4)3 + 3Cu")
+    );
+    assert_eq!(
+        predict(&parser, &["Mg", "HCl"])?.as_deref(),
+        Some("Mg + 2HCl -> MgCl2 + H2")
+    );
+    assert_eq!(
+        predict(&parser, &["Na", "H2O"])?.as_deref(),
+        Some("2Na + 2H2O -> 2NaOH + H2")
+    );
+    assert_eq!(
+        predict(&parser, &["Cl2", "NaBr"])?.as_deref(),
+        Some("Cl2 + 2NaBr -> 2NaCl + Br2")
+    );
+
+    assert_eq!(predict(&parser, &["Cu", "ZnSO4"])?, None);
+    assert_eq!(predict(&parser, &["Zn", "H2O"])?, None);
+    assert_eq!(predict(&parser, &["I2", "KCl"])?, None);
+    Ok(())
+}
+
+#[test]
+fn test_single_replacement_errors() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert!(matches!(
+        predict(&parser, &["Zn", "Cu"]),
+        Err(e) if matches!(e.downcast_ref(), Some(ChemParseError::EvaluationError(_, _)))
+    ));
+    assert!(predict(&parser, &["Zn", "CH4"]).is_err());
+    assert!(predict(&parser, &["Ne", "CuSO4"]).is_err());
+    Ok(())
+}
@@ -0,0 +1,65 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::analysis::{InputKind, Parsed};
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_analyze_detects_kind() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let iron = parser.analyze("Fe");
+    assert_eq!(iron.kind, InputKind::Element);
+    assert_eq!(iron.element.as_ref().map(|e| e.atomic_number), Some(26));
+
+    let acid = parser.analyze("H2SO4");
+    assert_eq!(acid.kind, InputKind::Formula);
+    assert_eq!(acid.formula.as_deref(), Some("H2O4S"));
+    assert_eq!(acid.composition["O"], 4);
+    assert!(acid.mass.unwrap() > acid.monoisotopic_mass.unwrap());
+
+    let water = parser.analyze("H2 + O2 -> H2O");
+    assert_eq!(water.kind, InputKind::Equation);
+    assert_eq!(water.balanced, Some(false));
+    assert_eq!(water.balanced_equations, vec!["2H2 + O2 -> 2H2O"]);
+    assert_eq!(parser.analyze("2H2 + O2 -> 2H2O").balanced, Some(true));
+    assert_eq!(parser.analyze("H2 + O2 -> NaOH").one_sided, vec!["Na"]);
+    Ok(())
+}
+
+#[test]
+fn test_analyze_invalid_input() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let typo = parser.analyze("NaCL");
+    assert_eq!(typo.kind, InputKind::Invalid);
+    assert_eq!(typo.error_code, Some("E0002"));
+    assert!(typo.suggestions.contains(&String::from("Cl")));
+
+    let json = serde_json::to_value(parser.analyze("2H2 + -> 2H2O"))?;
+    assert_eq!(json["kind"], "invalid");
+    assert_eq!(json["error_code"], "E0004");
+    assert!(json.get("mass").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_parse_any() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert!(matches!(parser.parse_any("iron")?, Parsed::Element(e) if e.symbol == "Fe"));
+    assert!(matches!(parser.parse_any(" H2O ")?, Parsed::Formula(f) if f.elements["H"] == 2));
+    assert!(matches!(
+        parser.parse_any("2H2 + O2 -> 2H2O")?,
+        Parsed::Equation(e) if e.product_terms.len() == 1
+    ));
+
+    let Err(ChemParseError::InvalidFormula(_, _, suggestions)) = parser.parse_any("NaCL") else {
+        anyhow::bail!("expected an invalid formula");
+    };
+    assert!(suggestions.contains(&String::from("Cl")));
+    assert!(matches!(
+        parser.parse_any("2H2 + -> 2H2O"),
+        Err(ChemParseError::SyntaxError(..))
+    ));
+    Ok(())
+}
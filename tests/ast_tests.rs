@@ -0,0 +1,135 @@
+use chemistry_parser::ast::{EquationAst, FormulaAst, FormulaNode, GroupKind, SpeciesNode};
+use chemistry_parser::element::{Arrow, Coefficient, State};
+use chemistry_parser::ChemParseError;
+
+#[test]
+fn test_formula_ast_nodes_and_spans() -> anyhow::Result<()> {
+    let ast = FormulaAst::parse("K4[Fe(CN)6]")?;
+    assert_eq!(ast.span, 0..11);
+    let [FormulaNode::Element(potassium), FormulaNode::Group(complex)] = ast.nodes.as_slice()
+    else {
+        panic!("unexpected nodes: {:?}", ast.nodes);
+    };
+    assert_eq!(potassium.symbol, "K");
+    assert_eq!(potassium.count, 4);
+    assert_eq!(potassium.symbol_span, 0..1);
+    assert_eq!(potassium.count_span, Some(1..2));
+    assert_eq!(complex.kind, GroupKind::Complex);
+    assert_eq!(complex.count, 1);
+    assert_eq!(complex.span, 2..11);
+
+    let FormulaNode::Group(cyanide) = &complex.formula.nodes[1] else {
+        panic!("expected a group");
+    };
+    assert_eq!(cyanide.kind, GroupKind::Group);
+    assert_eq!(cyanide.count, 6);
+    assert_eq!(cyanide.span, 5..10);
+    Ok(())
+}
+
+#[test]
+fn test_formula_ast_isotopes_charges_and_adducts() -> anyhow::Result<()> {
+    let ast = FormulaAst::parse("[13C]H4")?;
+    let FormulaNode::Element(carbon) = &ast.nodes[0] else {
+        panic!("expected an element");
+    };
+    assert_eq!(carbon.mass_number, Some(13));
+    assert_eq!(carbon.key(), "13C");
+
+    let sulfate = FormulaAst::parse("SO4^2-")?;
+    assert_eq!(sulfate.charge(), -2);
+
+    let hydrate = FormulaAst::parse("CuSO4·5H2O")?;
+    assert_eq!(hydrate.adducts.len(), 1);
+    assert_eq!(hydrate.adducts[0].count, 5);
+    assert_eq!(&"CuSO4·5H2O"[hydrate.adducts[0].span.clone()], "·5H2O");
+
+    let polymer = FormulaAst::parse("(C2H4)n")?;
+    let FormulaNode::Group(unit) = &polymer.nodes[0] else {
+        panic!("expected a group");
+    };
+    assert!(unit.repeat);
+    Ok(())
+}
+
+#[test]
+fn test_formula_ast_spans_of_unicode_input() -> anyhow::Result<()> {
+    let input = "H₂SO₄";
+    let ast = FormulaAst::parse(input)?;
+    assert_eq!(ast.span, 0..input.len());
+    let FormulaNode::Element(oxygen) = &ast.nodes[2] else {
+        panic!("expected an element");
+    };
+    assert_eq!(&input[oxygen.span.clone()], "O₄");
+    assert_eq!(oxygen.count, 4);
+    Ok(())
+}
+
+#[test]
+fn test_equation_ast() -> anyhow::Result<()> {
+    let input = "2H2(g) + O2 ->[Pt] 2H2O + e-";
+    let ast = EquationAst::parse(input)?;
+    assert_eq!(ast.reactants.len(), 2);
+    assert_eq!(ast.products.len(), 2);
+    assert_eq!(ast.arrow.arrow, Arrow::Irreversible);
+    assert_eq!(&input[ast.arrow.span.clone()], "->");
+    assert_eq!(ast.conditions[0].text, "Pt");
+    assert_eq!(&input[ast.conditions[0].span.clone()], "Pt");
+
+    let hydrogen = &ast.reactants[0];
+    assert_eq!(hydrogen.coefficient, Coefficient::new(2, 1).unwrap());
+    assert_eq!(&input[hydrogen.span.clone()], "2H2(g)");
+    assert_eq!(
+        hydrogen.state.as_ref().map(|(state, _)| *state),
+        Some(State::Gas)
+    );
+    assert!(matches!(ast.products[1].species, SpeciesNode::Electron(_)));
+
+    let input = "Cl2 + hν -> 2Cl";
+    let ast = EquationAst::parse(input)?;
+    let SpeciesNode::Photon(span) = &ast.reactants[1].species else {
+        anyhow::bail!("expected a photon");
+    };
+    assert_eq!(&input[span.clone()], "hν");
+    let ast = EquationAst::parse("CaCO3 + Δ -> CaO + CO2")?;
+    assert!(matches!(ast.reactants[1].species, SpeciesNode::Heat(_)));
+    Ok(())
+}
+
+#[test]
+fn test_equation_ast_fractional_coefficients() -> anyhow::Result<()> {
+    let input = "H2 + 1/2O2 -> H2O";
+    let ast = EquationAst::parse(input)?;
+    assert_eq!(
+        ast.reactants[0].coefficient,
+        Coefficient::new(1, 1).unwrap()
+    );
+    assert_eq!(
+        ast.reactants[1].coefficient,
+        Coefficient::new(1, 2).unwrap()
+    );
+    assert_eq!(&input[ast.reactants[1].span.clone()], "1/2O2");
+
+    let ast = EquationAst::parse("0.5N2 + 1.5H2 -> NH3")?;
+    assert_eq!(
+        ast.reactants[0].coefficient,
+        Coefficient::new(1, 2).unwrap()
+    );
+    assert_eq!(
+        ast.reactants[1].coefficient,
+        Coefficient::new(3, 2).unwrap()
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ast_syntax_error() {
+    assert!(matches!(
+        FormulaAst::parse("2H"),
+        Err(ChemParseError::SyntaxError(_, _))
+    ));
+    assert!(matches!(
+        EquationAst::parse("H2 + -> H2O"),
+        Err(ChemParseError::SyntaxError(_, _))
+    ));
+}
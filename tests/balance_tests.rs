@@ -0,0 +1,160 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::balance::{Balance, DEFAULT_MAX_COEFFICIENT};
+use chemistry_parser::element::Equation;
+use chemistry_parser::{ChemParseError, ChemParser};
+
+fn balance(parser: &ChemParser, equation: &str) -> anyhow::Result<Equation> {
+    match parser
+        .parse_equation(equation)?
+        .balance(DEFAULT_MAX_COEFFICIENT)?
+    {
+        Balance::Balanced(balanced) => Ok(balanced),
+        other => anyhow::bail!("{} was not balanced: {:?}", equation, other),
+    }
+}
+
+#[test]
+fn test_balance_equation() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let balanced = balance(&parser, "H2 + O2 -> H2O")?;
+    assert_eq!(balanced.equation, "2H2 + O2 -> 2H2O");
+    assert!(balanced.check_atom_balance(true));
+
+    let balanced = balance(&parser, "KMnO4 + HCl -> KCl + MnCl2 + H2O + Cl2")?;
+    assert_eq!(
+        balanced.equation,
+        "2KMnO4 + 16HCl -> 2KCl + 2MnCl2 + 8H2O + 5Cl2"
+    );
+
+    let balanced = balance(&parser, "hν + H2 + Cl2 -> HCl")?;
+    assert_eq!(balanced.equation, "H2 + Cl2 + hν -> 2HCl");
+    let balanced = balance(&parser, "KClO3 + heat -> KCl + O2")?;
+    assert_eq!(balanced.equation, "2KClO3 + heat -> 2KCl + 3O2");
+
+    let balanced = balance(&parser, "Fe^2+ + MnO4^- + H^+ -> Fe^3+ + Mn^2+ + H2O")?;
+    assert_eq!(
+        balanced
+            .reactant_terms
+            .iter()
+            .map(|term| term.coefficient)
+            .collect::<Vec<_>>(),
+        vec![5, 1, 8]
+    );
+    Ok(())
+}
+
+#[test]
+fn test_balance_keeps_arrow_and_conditions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let balanced = balance(&parser, "N2 + H2 <=>[Fe, 450°C] NH3")?;
+    assert_eq!(balanced.arrow, parser.parse_equation("N2 <=> N2")?.arrow);
+    assert_eq!(balanced.catalysts, vec!["Fe"]);
+    assert_eq!(
+        parser.parse_equation(&balanced.equation)?.products["NH3"],
+        2
+    );
+    Ok(())
+}
+
+#[test]
+fn test_balance_cap() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let equation = parser.parse_equation("KMnO4 + HCl -> KCl + MnCl2 + H2O + Cl2")?;
+    assert!(equation.balance(16).is_ok());
+    assert!(matches!(
+        equation.balance(15),
+        Err(ChemParseError::BalancingError(_, _))
+    ));
+    assert!(matches!(
+        parser
+            .parse_equation("H2O -> H2O2")?
+            .balance(DEFAULT_MAX_COEFFICIENT),
+        Err(ChemParseError::BalancingError(_, _))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_balance_outcomes() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert!(matches!(
+        parser
+            .parse_equation("2H2 + O2 -> 2H2O")?
+            .balance(DEFAULT_MAX_COEFFICIENT)?,
+        Balance::AlreadyBalanced
+    ));
+
+    match parser
+        .parse_equation("Na + Cl2 -> NaCI")?
+        .balance(DEFAULT_MAX_COEFFICIENT)?
+    {
+        Balance::Impossible(keys) => assert_eq!(keys, vec!["C", "Cl", "I"]),
+        other => anyhow::bail!("expected an impossible equation, got {:?}", other),
+    }
+    match parser
+        .parse_equation("H2 -> O2")?
+        .balance(DEFAULT_MAX_COEFFICIENT)?
+    {
+        Balance::Impossible(keys) => assert_eq!(keys, vec!["H", "O"]),
+        other => anyhow::bail!("expected an impossible equation, got {:?}", other),
+    }
+    Ok(())
+}
+
+#[test]
+fn test_independent_reactions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("H2 + O2 -> H2O + H2O2")?;
+
+    match equation.balance(DEFAULT_MAX_COEFFICIENT)? {
+        Balance::Independent(reactions) => {
+            let reactions: Vec<&str> = reactions
+                .iter()
+                .map(|reaction| reaction.equation.as_str())
+                .collect();
+            assert_eq!(reactions, vec!["2H2 + O2 -> 2H2O", "H2 + O2 -> H2O2"]);
+        }
+        other => anyhow::bail!("expected independent reactions, got {:?}", other),
+    }
+
+    let reactions = parser
+        .parse_equation("CH4 + O2 -> CO2 + H2O")?
+        .sub_reactions(DEFAULT_MAX_COEFFICIENT)?;
+    assert_eq!(reactions.len(), 1);
+    assert_eq!(reactions[0].equation, "CH4 + 2O2 -> CO2 + 2H2O");
+    Ok(())
+}
+
+#[test]
+fn test_balance_ionic_and_half_reactions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let balanced = balance(&parser, "Cu + Ag^+ -> Cu^2+ + Ag")?;
+    assert_eq!(balanced.equation, "Cu + 2Ag^+ -> Cu^2+ + 2Ag");
+
+    let balanced = balance(&parser, "MnO4^- + H^+ + e^- -> Mn^2+ + H2O")?;
+    assert_eq!(balanced.equation, "MnO4^- + 8H^+ + 5e^- -> Mn^2+ + 4H2O");
+    assert_eq!(
+        parser.parse_equation(&balanced.equation)?.reactants["e^-"],
+        5
+    );
+
+    assert!(matches!(
+        parser
+            .parse_equation("Fe^3+ + e- -> Fe^2+")?
+            .balance(DEFAULT_MAX_COEFFICIENT)?,
+        Balance::AlreadyBalanced
+    ));
+    assert!(matches!(
+        parser
+            .parse_equation("Fe^3+ -> Fe^2+")?
+            .balance(DEFAULT_MAX_COEFFICIENT),
+        Err(ChemParseError::BalancingError(_, _))
+    ));
+    Ok(())
+}
@@ -0,0 +1,113 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+use std::fs;
+
+#[test]
+fn parses_equations_from_str() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let lines = parser.parse_equations_str(
+        "# combustion\n\
+         CH4 + 2O2 -> CO2 + 2H2O\n\
+         \n\
+         H2 + Xx -> H2Xx\r\n\
+         2H2 + O2 -> 2H2O\n",
+    );
+    let numbers: Vec<usize> = lines.iter().map(|line| line.number).collect();
+    assert_eq!(numbers, [2, 4, 5]);
+    assert!(lines[0]
+        .result
+        .as_ref()
+        .is_ok_and(|e| e.check_atom_balance(false)));
+    assert_eq!(lines[1].text, "H2 + Xx -> H2Xx");
+    assert!(matches!(
+        lines[1].result,
+        Err(ChemParseError::InvalidFormula(..))
+    ));
+    assert!(lines[2].result.is_ok());
+    Ok(())
+}
+
+#[test]
+fn parses_equations_from_file() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let path = std::env::temp_dir().join("chemistry_parser_batch_tests.txt");
+    fs::write(&path, "N2 + 3H2 -> 2NH3\nnot an equation\n")?;
+    let lines = parser.parse_equations_file(&path)?;
+    fs::remove_file(&path)?;
+    assert_eq!(lines.len(), 2);
+    assert!(lines[0].result.is_ok());
+    assert!(lines[1].result.is_err());
+
+    let missing = parser.parse_equations_file("no/such/file.txt").unwrap_err();
+    assert_eq!(missing.code(), "E0008");
+    Ok(())
+}
+
+#[test]
+fn parser_is_shared_between_threads() -> anyhow::Result<()> {
+    fn assert_send_sync<T: Send + Sync>() {}
+    assert_send_sync::<ChemParser>();
+    assert_send_sync::<chemistry_parser::element::PeriodicTable>();
+
+    let parser = ChemParser::new();
+    let masses = std::thread::scope(|scope| {
+        let water = scope.spawn(|| parser.parse_formula("H2O").map(|f| f.mass));
+        let salt = scope.spawn(|| parser.parse_formula("NaCl").map(|f| f.mass));
+        (water.join(), salt.join())
+    });
+    assert!(matches!(masses, (Ok(Ok(_)), Ok(Ok(_)))));
+    Ok(())
+}
+
+#[cfg(feature = "rayon")]
+#[test]
+fn parses_equations_in_parallel() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let input: String = (0..1000)
+        .map(|i| match i % 3 {
+            0 => "2H2 + O2 -> 2H2O\n",
+            1 => "# comment\n",
+            _ => "H2 + Xx -> H2Xx\n",
+        })
+        .collect();
+    let parallel = parser.parse_equations_parallel(&input);
+    let sequential = parser.parse_equations_str(&input);
+    assert_eq!(parallel.len(), sequential.len());
+    assert!(parallel
+        .iter()
+        .zip(&sequential)
+        .all(|(a, b)| a.number == b.number && a.result.is_ok() == b.result.is_ok()));
+    Ok(())
+}
+
+#[test]
+fn collects_errors_of_all_lines() {
+    let parser = ChemParser::new();
+    let report = parser.parse_all(
+        "2H2 + O2 -> 2H2O\n\
+         # comment\n\
+         H2 + Xx -> H2Xx\n\
+         H2 + -> H2O\n\
+         N2 + 3H2 -> 2NH3\n",
+    );
+    assert_eq!(report.total(), 4);
+    assert!(!report.is_success());
+    let lines: Vec<usize> = report.equations.iter().map(|(line, _)| *line).collect();
+    assert_eq!(lines, [1, 5]);
+
+    assert_eq!(report.errors.len(), 2);
+    assert_eq!(report.errors[0].line, 3);
+    assert_eq!(report.errors[0].column, 6);
+    assert!(matches!(
+        report.errors[0].error,
+        ChemParseError::InvalidFormula(..)
+    ));
+    assert_eq!(report.errors[1].line, 4);
+    assert_eq!(report.errors[1].column, 6);
+    assert_eq!(
+        report.summary(),
+        "4 lines, 2 parsed, 2 failed (E0002: 1, E0004: 1)"
+    );
+    assert!(report.to_string().contains("line 3, column 6: [E0002]"));
+}
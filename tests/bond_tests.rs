@@ -0,0 +1,42 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::bond::{predict_bond_type, BondType};
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_electronegativity() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert_eq!(parser.parse_element("F")?.electronegativity, Some(3.98));
+    assert_eq!(parser.parse_element("Cs")?.electronegativity, Some(0.79));
+    assert_eq!(parser.parse_element("Ne")?.electronegativity, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_predict_bond_type() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let bond = |a: &str, b: &str| -> anyhow::Result<BondType> {
+        Ok(predict_bond_type(
+            parser.parse_element(a)?,
+            parser.parse_element(b)?,
+        )?)
+    };
+
+    assert_eq!(bond("Na", "Cl")?, BondType::Ionic);
+    assert_eq!(bond("Cl", "Na")?, BondType::Ionic);
+    assert_eq!(bond("H", "O")?, BondType::PolarCovalent);
+    assert_eq!(bond("C", "H")?, BondType::NonpolarCovalent);
+    assert_eq!(bond("Cl", "Cl")?, BondType::NonpolarCovalent);
+    assert_eq!(BondType::PolarCovalent.to_string(), "polar covalent");
+
+    let error =
+        predict_bond_type(parser.parse_element("He")?, parser.parse_element("H")?).unwrap_err();
+    assert!(matches!(
+        error,
+        ChemParseError::MissingData(_, ref symbols) if symbols == &["He"]
+    ));
+
+    Ok(())
+}
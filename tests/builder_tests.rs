@@ -0,0 +1,71 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::builder::EquationBuilder;
+use chemistry_parser::element::{Arrow, Formula};
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_build_equation() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = EquationBuilder::new()
+        .reactant(2, "H2")
+        .reactant(1, "O2")
+        .product(2, "H2O")
+        .build(&parser)?;
+
+    assert_eq!(equation.equation, "2H2 + O2 -> 2H2O");
+    assert_eq!(equation.reactants["H2"], 2);
+    assert!(equation.check_atom_balance(false));
+
+    let parsed = parser.parse_equation(&equation.equation)?;
+    assert_eq!(parsed.reactants, equation.reactants);
+    assert_eq!(parsed.products, equation.products);
+    Ok(())
+}
+
+#[test]
+fn test_build_equation_with_formulas() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let iron = parser.parse_formula("Fe^3+")?;
+    let equation = EquationBuilder::new()
+        .reactant_formula(1, iron)
+        .reactant_formula(1, Formula::electron())
+        .product(1, "Fe^2+")
+        .arrow(Arrow::Reversible)
+        .build(&parser)?;
+    assert_eq!(equation.to_parseable_string(), "Fe^3+ + e^- <=> Fe^2+");
+
+    let ammonia = EquationBuilder::new()
+        .reactant(1, "N2")
+        .reactant(3, "H2")
+        .product(2, "NH3")
+        .condition("Fe")
+        .condition("450°C")
+        .build(&parser)?;
+    assert_eq!(ammonia.catalysts, vec![String::from("Fe")]);
+    Ok(())
+}
+
+#[test]
+fn test_build_invalid_equation() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert!(matches!(
+        EquationBuilder::new()
+            .reactant(1, "Xx2")
+            .product(1, "H2")
+            .build(&parser),
+        Err(ChemParseError::InvalidFormula(_, _, _))
+    ));
+    assert!(matches!(
+        EquationBuilder::new()
+            .reactant(0, "H2")
+            .product(1, "H2")
+            .build(&parser),
+        Err(ChemParseError::InvalidCoefficientFormat(_))
+    ));
+    assert!(EquationBuilder::new()
+        .reactant(1, "H2")
+        .build(&parser)
+        .is_err());
+    Ok(())
+}
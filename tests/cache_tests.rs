@@ -0,0 +1,97 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::options::ParseOptions;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_cache_hits_and_misses() -> anyhow::Result<()> {
+    let parser = ChemParser::new().with_formula_cache(16);
+    let stats = parser.cache_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses, stats.len), (0, 0, 0));
+    assert_eq!(stats.capacity, 16);
+    assert_eq!(stats.hit_rate(), 0.0);
+
+    let first = parser.parse_formula("H2O")?;
+    let second = parser.parse_formula("H2O")?;
+    assert_eq!(first.mass, second.mass);
+    assert_eq!(first.formula, second.formula);
+    let stats = parser.cache_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses, stats.len), (1, 1, 1));
+    assert_eq!(stats.hit_rate(), 0.5);
+
+    parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    let stats = parser.cache_stats().unwrap();
+    assert_eq!(stats.hits, 2);
+    assert_eq!(stats.len, 3);
+    Ok(())
+}
+
+#[test]
+fn test_cache_matches_uncached() -> anyhow::Result<()> {
+    let cached = ChemParser::new().with_formula_cache(8);
+    let uncached = ChemParser::new();
+    for formula in ["K4[Fe(CN)6]", "CuSO4*5H2O", "SO4^2-", "K4[Fe(CN)6]"] {
+        let a = cached.parse_formula(formula)?;
+        let b = uncached.parse_formula(formula)?;
+        assert!((a.mass - b.mass).abs() < 1e-9);
+        assert_eq!(a.charge, b.charge);
+        assert_eq!(a.elements.len(), b.elements.len());
+    }
+    assert_eq!(cached.cache_stats().unwrap().hits, 1);
+    assert!(uncached.cache_stats().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_cache_eviction() -> anyhow::Result<()> {
+    let parser = ChemParser::new().with_formula_cache(2);
+    parser.parse_formula("H2O")?;
+    parser.parse_formula("CO2")?;
+    parser.parse_formula("H2O")?;
+    parser.parse_formula("NaCl")?;
+    let stats = parser.cache_stats().unwrap();
+    assert_eq!((stats.len, stats.evictions), (2, 1));
+
+    // CO2 was the least recently used, so it is the one that was evicted.
+    parser.parse_formula("H2O")?;
+    parser.parse_formula("CO2")?;
+    let stats = parser.cache_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses), (2, 4));
+
+    let parser = ChemParser::new().with_formula_cache(0);
+    parser.parse_formula("H2O")?;
+    parser.parse_formula("H2O")?;
+    let stats = parser.cache_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses, stats.len), (0, 2, 0));
+    Ok(())
+}
+
+#[test]
+fn test_cache_errors_not_cached() -> anyhow::Result<()> {
+    let parser = ChemParser::new().with_formula_cache(4);
+    assert!(parser.parse_formula("Xx2").is_err());
+    assert!(parser.parse_formula("Xx2").is_err());
+    let stats = parser.cache_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses, stats.len), (0, 2, 0));
+    Ok(())
+}
+
+#[test]
+fn test_cache_clear_and_clones() -> anyhow::Result<()> {
+    let parser = ChemParser::new().with_formula_cache(4);
+    let clone = parser.clone();
+    parser.parse_formula("H2O")?;
+    clone.parse_formula("H2O")?;
+    assert_eq!(parser.cache_stats().unwrap().hits, 1);
+
+    clone.clear_formula_cache();
+    let stats = parser.cache_stats().unwrap();
+    assert_eq!((stats.hits, stats.misses, stats.len), (0, 0, 0));
+
+    parser.parse_formula("H2O")?;
+    let lenient = parser.clone().with_options(ParseOptions::lenient());
+    assert_eq!(lenient.cache_stats().unwrap().len, 0);
+    assert_eq!(lenient.parse_formula(" H2O ")?.formula, "H2O");
+    assert_eq!(parser.cache_stats().unwrap().len, 1);
+    Ok(())
+}
@@ -0,0 +1,154 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::calc::{Calculator, Dimension, Variable};
+use chemistry_parser::{ChemParseError, ChemParser, Rule};
+use pest::Parser;
+
+#[test]
+fn test_calculation_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::calculation, "mass(H2SO4) * 2 + mass(H2O)").is_ok());
+    assert!(ChemParser::parse(Rule::calculation, "-(1 + 2.5) / atoms((NH4)2SO4)").is_ok());
+    assert!(ChemParser::parse(Rule::calculation, "mass(H2O) *").is_err());
+    assert!(ChemParser::parse(Rule::calculation, "volume(H2O)").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_eval_formula_functions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let h2so4 = parser.parse_formula("H2SO4")?.mass;
+    let h2o = parser.parse_formula("H2O")?.mass;
+    let value = parser.eval("mass(H2SO4) * 2 + mass(H2O)")?;
+    assert!((value - (h2so4 * 2.0 + h2o)).abs() < 1e-9);
+
+    assert_eq!(parser.eval("atoms((NH4)2SO4)")?, 15.0);
+    assert_eq!(parser.eval("2 + 3 * (4 - 1) / -2")?, -2.5);
+
+    Ok(())
+}
+
+#[test]
+fn test_eval_errors() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert!(matches!(
+        parser.eval("mass(H2O) / 0"),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+    assert!(matches!(
+        parser.eval("mass(Xy2)"),
+        Err(ChemParseError::InvalidFormula(_, _, _))
+    ));
+    assert!(matches!(
+        parser.eval("mass(H2O) +"),
+        Err(ChemParseError::SyntaxError(_, _))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_calculator_variables() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut calculator = Calculator::new(&parser);
+
+    assert_eq!(calculator.execute("let aspirin = C9H8O4")?, None);
+    assert_eq!(calculator.execute("let doses = 3")?, None);
+    let value = calculator.execute("mass(aspirin) * doses")?.unwrap();
+    assert!((value.value - parser.parse_formula("C9H8O4")?.mass * 3.0).abs() < 1e-9);
+
+    assert!(matches!(
+        calculator.execute("aspirin * 2"),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+    assert!(matches!(
+        calculator.execute("mass(ibuprofen)"),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+    assert!(matches!(
+        calculator.execute("let mass = 2"),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn test_calculator_session_persistence() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let path = std::env::temp_dir().join("chemistry_parser_calc_session.txt");
+    let path = path.to_str().unwrap();
+
+    let mut calculator = Calculator::new(&parser);
+    calculator.execute("let aspirin = C9H8O4")?;
+    calculator.execute("let dose = mass(aspirin) * 2")?;
+    calculator
+        .save(path)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let mut restored = Calculator::new(&parser);
+    restored.load(path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(restored.execute("dose")?, calculator.execute("dose")?);
+    assert!(matches!(
+        restored.get("aspirin"),
+        Some(Variable::Formula(_))
+    ));
+
+    std::fs::remove_file(path)?;
+    Ok(())
+}
+
+#[test]
+fn test_calculator_units() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut calculator = Calculator::new(&parser);
+    let nacl = parser.parse_formula("NaCl")?.mass;
+
+    let molarity = calculator
+        .execute("5 g of NaCl in 250 mL -> molarity")?
+        .unwrap();
+    assert_eq!(molarity.dimension, Dimension::CONCENTRATION);
+    assert!((molarity.magnitude() - 5.0 / nacl / 0.25).abs() < 1e-9);
+    assert!(format!("{:.2}", molarity).ends_with(" M"));
+
+    let needed = calculator
+        .execute("(0.1 M * 500 mL) of NaCl -> mg")?
+        .unwrap();
+    assert!((needed.magnitude() - 0.05 * nacl * 1000.0).abs() < 1e-6);
+
+    let molar_mass = calculator.execute("mass(NaCl)")?.unwrap();
+    assert_eq!(molar_mass.dimension, Dimension::MOLAR_MASS);
+    assert_eq!(format!("{}", Dimension::MOLAR_MASS), "g / mol");
+
+    assert!(matches!(
+        calculator.execute("5 g + 2 mol"),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+    assert!(matches!(
+        calculator.execute("5 g -> M"),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+    assert!(matches!(
+        calculator.execute("5 mL of NaCl"),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+
+    calculator.execute("let stock = 5 g of NaCl in 250 mL -> mM")?;
+    let path = std::env::temp_dir().join("chemistry_parser_calc_units.txt");
+    let path = path.to_str().unwrap();
+    calculator
+        .save(path)
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let mut restored = Calculator::new(&parser);
+    restored.load(path).map_err(|e| anyhow::anyhow!("{}", e))?;
+    let (restored, stock) = (
+        restored.execute("stock")?.unwrap(),
+        calculator.execute("stock")?.unwrap(),
+    );
+    assert_eq!(restored.unit, stock.unit);
+    assert!((restored.magnitude() - stock.magnitude()).abs() < 1e-9);
+    std::fs::remove_file(path)?;
+    Ok(())
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::completion::Highlight;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_complete() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let commands = ["symbol", "formula", "equation", "help"];
+
+    let command = parser.complete("fo", &commands);
+    assert_eq!(command.start, 0);
+    assert_eq!(command.candidates[0].replacement, "formula");
+
+    let symbol = parser.complete("formula H2S", &commands);
+    assert_eq!(symbol.start, 8);
+    let replacements: Vec<&str> = symbol
+        .candidates
+        .iter()
+        .map(|candidate| candidate.replacement.as_str())
+        .collect();
+    assert!(replacements.starts_with(&["H2S", "H2Sb", "H2Sc"]));
+    assert!(!replacements.contains(&"formula"));
+
+    let name = parser.complete("symbol iro", &commands);
+    assert_eq!(name.candidates[0].replacement, "iron");
+
+    let ion = parser.complete("sulfa", &commands);
+    let sulfate = ion
+        .candidates
+        .iter()
+        .find(|candidate| candidate.display == "sulfate (SO4^2-)")
+        .unwrap();
+    assert_eq!(sulfate.replacement, "SO4^2-");
+
+    let spaced = parser.complete("formula\u{a0}H", &commands);
+    assert_eq!(spaced.start, "formula\u{a0}".len());
+    assert!(spaced
+        .candidates
+        .iter()
+        .any(|candidate| candidate.replacement == "He"));
+    Ok(())
+}
+
+#[test]
+fn test_highlight() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let spans = parser.highlight("NaCl + Xx -> D2O");
+    assert_eq!(
+        spans,
+        [
+            (0..2, Highlight::Valid),
+            (2..4, Highlight::Valid),
+            (7..9, Highlight::Invalid),
+            (13..14, Highlight::Valid),
+            (15..16, Highlight::Valid),
+        ]
+    );
+    assert!(parser.highlight("iron").is_empty());
+    Ok(())
+}
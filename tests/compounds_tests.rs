@@ -0,0 +1,93 @@
+#![cfg(feature = "compounds")]
+
+use chemistry_parser::compounds::CompoundDb;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_find_by_formula() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let db = CompoundDb::embedded();
+
+    let found = db.find_by_formula(&parser.parse_formula("NaCl")?);
+    assert_eq!(found.len(), 1);
+    assert_eq!(found[0].name, "sodium chloride");
+    assert_eq!(found[0].category.as_deref(), Some("salt"));
+    assert!((found[0].mass - 58.44).abs() < 0.01);
+
+    let names: Vec<&str> = db
+        .find_by_formula(&parser.parse_formula("C2H6O")?)
+        .iter()
+        .map(|compound| compound.name.as_str())
+        .collect();
+    assert_eq!(names, ["ethanol", "dimethyl ether"]);
+
+    assert!(db
+        .find_by_formula(&parser.parse_formula("XeF4")?)
+        .is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_find_by_name_prefix() -> anyhow::Result<()> {
+    let db = CompoundDb::embedded();
+
+    let names: Vec<&str> = db
+        .find_by_name_prefix("Sodium c")
+        .iter()
+        .map(|compound| compound.name.as_str())
+        .collect();
+    assert_eq!(names, ["sodium carbonate", "sodium chloride"]);
+    assert_eq!(db.find_by_name_prefix("sul").len(), 3);
+    assert!(db.find_by_name_prefix("unobtainium").is_empty());
+    assert_eq!(db.find_by_name("Urea").unwrap().formula, "CO(NH2)2");
+    Ok(())
+}
+
+#[test]
+fn test_parse_formula_named() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let named = parser.parse_formula_named("C6H12O6")?;
+    assert_eq!(named.compound.unwrap().name, "glucose");
+    assert_eq!(named.formula.formula, "C6H12O6");
+
+    let named = parser.parse_formula_named("CH3CH2OH")?;
+    assert_eq!(named.compound.unwrap().name, "ethanol");
+    assert!(parser.parse_formula_named("XeF4")?.compound.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_custom_compounds() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut db = CompoundDb::new();
+    db.load_csv_str(
+        "name,formula,category\naspirin,C9H8O4,drug\ncaffeine,C8H10N4O2,\n",
+        &parser,
+    )
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    db.insert("paracetamol", "C8H9NO2", Some("drug"), &parser)?;
+    assert_eq!(db.len(), 3);
+    assert_eq!(db.find_by_name("caffeine").unwrap().category, None);
+
+    let named = db.annotate(parser.parse_formula("C9H8O4")?);
+    assert_eq!(named.compound.unwrap().name, "aspirin");
+
+    assert!(db.insert("nothing", "Xx2", None, &parser).is_err());
+    assert!(db
+        .load_csv_str("name,formula\nbroken,Qq2\n", &parser)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_complete_compound_names() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let completion = parser.complete("formula gluc", &[]);
+    assert!(completion
+        .candidates
+        .iter()
+        .any(|candidate| candidate.display == "glucose (C6H12O6)"
+            && candidate.replacement == "C6H12O6"));
+    Ok(())
+}
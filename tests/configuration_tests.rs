@@ -0,0 +1,55 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_electron_configuration() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for (symbol, full, shorthand) in [
+        ("H", "1s1", "1s1"),
+        ("He", "1s2", "1s2"),
+        ("Ne", "1s2 2s2 2p6", "[He] 2s2 2p6"),
+        ("Na", "1s2 2s2 2p6 3s1", "[Ne] 3s1"),
+        ("Fe", "1s2 2s2 2p6 3s2 3p6 4s2 3d6", "[Ar] 4s2 3d6"),
+        ("Cr", "1s2 2s2 2p6 3s2 3p6 4s1 3d5", "[Ar] 4s1 3d5"),
+    ] {
+        let configuration = parser.parse_element(symbol)?.electron_configuration();
+        assert_eq!(configuration.full(), full, "{}", symbol);
+        assert_eq!(configuration.shorthand(), shorthand, "{}", symbol);
+    }
+
+    for (symbol, shorthand) in [
+        ("Cu", "[Ar] 4s1 3d10"),
+        ("Pd", "[Kr] 4d10"),
+        ("La", "[Xe] 6s2 5d1"),
+        ("Au", "[Xe] 6s1 4f14 5d10"),
+        ("Lr", "[Rn] 7s2 5f14 7p1"),
+        ("Og", "[Rn] 7s2 5f14 6d10 7p6"),
+    ] {
+        let configuration = parser.parse_element(symbol)?.electron_configuration();
+        assert_eq!(configuration.shorthand(), shorthand, "{}", symbol);
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_electron_configuration_counts() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for atomic_number in 1..=118 {
+        let Some(element) = parser.get_table().get_by_atomic_number(atomic_number) else {
+            continue;
+        };
+        let configuration = element.electron_configuration();
+        let electrons: u32 = configuration
+            .subshells
+            .iter()
+            .map(|subshell| u32::from(subshell.electrons))
+            .sum();
+        assert_eq!(electrons, u32::from(atomic_number), "{}", element.symbol);
+    }
+
+    Ok(())
+}
@@ -0,0 +1,85 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::convert::{read_batch, write_batch, BatchFormat};
+use chemistry_parser::{ChemParseError, ChemParser};
+
+const EQUATIONS: [&str; 3] = [
+    "2H2 + O2 -> 2H2O",
+    "NaOH(aq) + HCl(aq) -> NaCl(aq) + H2O(l)",
+    "Fe^3+ + e^- -> Fe^2+",
+];
+
+#[test]
+fn test_convert_round_trip() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equations = EQUATIONS
+        .iter()
+        .map(|equation| parser.parse_equation(equation))
+        .collect::<Result<Vec<_>, _>>()?;
+
+    for format in BatchFormat::ALL {
+        let mut written = Vec::new();
+        write_batch(&equations, format, &mut written)?;
+        let written = String::from_utf8(written)?;
+        assert_eq!(
+            BatchFormat::detect("input", &written),
+            format,
+            "{}",
+            written
+        );
+        assert_eq!(
+            read_batch(&written, format)?,
+            EQUATIONS,
+            "{}",
+            format.name()
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_convert_detect() -> anyhow::Result<()> {
+    assert_eq!(BatchFormat::detect("reactions.cml", ""), BatchFormat::Cml);
+    assert_eq!(BatchFormat::detect("report.MD", ""), BatchFormat::Markdown);
+    assert_eq!(
+        BatchFormat::detect("data", "id,Equation\n1,H2 + Cl2 -> 2HCl\n"),
+        BatchFormat::Csv
+    );
+    assert_eq!(
+        BatchFormat::detect("data", "# reactions\nH2 + Cl2 -> 2HCl\n"),
+        BatchFormat::Text
+    );
+    assert_eq!(BatchFormat::from_name("JSONL"), Some(BatchFormat::Jsonl));
+    assert_eq!(BatchFormat::from_name("yaml"), None);
+
+    Ok(())
+}
+
+#[test]
+fn test_convert_read() -> anyhow::Result<()> {
+    assert_eq!(
+        read_batch(
+            "id,equation\n1,H2 + Cl2 -> 2HCl\n2,\"N2 + 3H2 -> 2NH3\"\n",
+            BatchFormat::Csv
+        )?,
+        ["H2 + Cl2 -> 2HCl", "N2 + 3H2 -> 2NH3"]
+    );
+    assert_eq!(
+        read_batch(
+            "\"H2 + Cl2 -> 2HCl\"\n{\"equation\": \"N2 + 3H2 -> 2NH3\"}\n",
+            BatchFormat::Jsonl
+        )?,
+        ["H2 + Cl2 -> 2HCl", "N2 + 3H2 -> 2NH3"]
+    );
+    assert!(matches!(
+        read_batch("{\"formula\": \"H2O\"}", BatchFormat::Jsonl),
+        Err(ChemParseError::ParsingError(_, _))
+    ));
+    assert!(matches!(
+        read_batch("| Formula |\n|---|\n| H2O |", BatchFormat::Markdown),
+        Err(ChemParseError::ParsingError(_, _))
+    ));
+
+    Ok(())
+}
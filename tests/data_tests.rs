@@ -0,0 +1,580 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::element::{DatasetVersion, Element, ElementClass, PeriodicTable};
+use chemistry_parser::temperature::{Temperature, TransitionTemperature};
+use chemistry_parser::{compare_datasets, ChemParseError, ChemParser};
+use std::sync::Arc;
+
+#[test]
+fn test_parser_from_data_dir() -> anyhow::Result<()> {
+    let data_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    let parser = ChemParser::from_data_dir(data_dir)?;
+    assert_eq!(parser.parse_element("Na")?.atomic_number, 11);
+
+    assert!(ChemParser::from_data_dir("/nonexistent").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_embedded_table_matches_data_files() -> anyhow::Result<()> {
+    let data_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    let embedded = PeriodicTable::embedded().map_err(|e| anyhow::anyhow!("{}", e))?;
+    let mut files = PeriodicTable::from_csv(&format!("{}/elements.csv", data_dir))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    files
+        .load_isotopes_csv(&format!("{}/isotopes.csv", data_dir))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    files
+        .load_identifiers_csv(&format!("{}/element_identifiers.csv", data_dir))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    files
+        .load_properties_csv(&format!("{}/element_properties.csv", data_dir))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let elements = |table: &PeriodicTable| serde_json::to_value(table.iter().collect::<Vec<_>>());
+    assert_eq!(elements(&embedded)?, elements(&files)?);
+    let carbon = embedded.get_element("C").unwrap();
+    let melting_point = carbon.melting_point.as_ref().unwrap();
+    assert!(matches!(
+        melting_point,
+        TransitionTemperature::Allotropes(allotropes) if allotropes.len() == 2
+    ));
+    assert_eq!(carbon.category.as_deref(), Some("reactive nonmetal"));
+    let isotopes = |table: &PeriodicTable| serde_json::to_value(table.isotopes_of("U"));
+    assert_eq!(isotopes(&embedded)?, isotopes(&files)?);
+    Ok(())
+}
+
+#[test]
+fn test_shared_periodic_table() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert!(Arc::ptr_eq(parser.shared_table(), PeriodicTable::global()));
+    assert!(Arc::ptr_eq(
+        ChemParser::try_new()?.shared_table(),
+        PeriodicTable::global()
+    ));
+
+    let clone = parser.clone();
+    assert!(std::ptr::eq(clone.get_table(), parser.get_table()));
+    let threads: Vec<_> = (0..4)
+        .map(|_| std::thread::spawn(|| Arc::clone(ChemParser::new().shared_table())))
+        .collect();
+    for thread in threads {
+        let table = thread
+            .join()
+            .map_err(|_| anyhow::anyhow!("thread panicked"))?;
+        assert!(Arc::ptr_eq(&table, PeriodicTable::global()));
+    }
+
+    let shared = ChemParser::with_shared_table(Arc::clone(parser.shared_table()));
+    assert_eq!(shared.parse_formula("H2O")?.elements["H"], 2);
+    Ok(())
+}
+
+#[test]
+fn test_fallible_constructors() -> anyhow::Result<()> {
+    let parser = ChemParser::try_new()?;
+    assert_eq!(parser.parse_element("O")?.atomic_number, 8);
+
+    assert!(matches!(
+        ChemParser::from_csv_path("/nonexistent/elements.csv"),
+        Err(ChemParseError::DataError(_, _))
+    ));
+
+    let corrupt = std::env::temp_dir().join("chemistry_parser_corrupt_elements.csv");
+    std::fs::write(&corrupt, "name,symbol\nHydrogen\n")?;
+    assert!(matches!(
+        ChemParser::from_csv_path(corrupt.to_str().unwrap()),
+        Err(ChemParseError::DataError(_, _))
+    ));
+    std::fs::remove_file(corrupt)?;
+    Ok(())
+}
+
+#[test]
+fn test_data_error_source() -> anyhow::Result<()> {
+    let Err(error) = ChemParser::from_csv_path("/nonexistent/elements.csv") else {
+        anyhow::bail!("expected a data error");
+    };
+    assert_eq!(error.code(), "E0008");
+    let source = std::error::Error::source(&error).expect("the cause is kept");
+    assert!(error.to_string().ends_with(&source.to_string()));
+
+    let chained = anyhow::Error::from(error);
+    assert!(chained.chain().count() >= 2);
+    Ok(())
+}
+
+#[test]
+fn test_custom_table_injection() -> anyhow::Result<()> {
+    let elements_csv = concat!(env!("CARGO_MANIFEST_DIR"), "/data/elements.csv");
+    let parser = ChemParser::from_csv_path(elements_csv)?;
+    assert!(parser.parse_formula("[13C]O2").is_ok());
+
+    let header = std::fs::read_to_string(elements_csv)?
+        .lines()
+        .next()
+        .unwrap_or_default()
+        .to_string();
+    let hydrogen = std::fs::read_to_string(elements_csv)?
+        .lines()
+        .find(|line| line.contains(",H,"))
+        .unwrap_or_default()
+        .to_string();
+    let table = PeriodicTable::from_csv_str(&format!("{}\n{}\n", header, hydrogen))
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let parser = ChemParser::with_table(table);
+    assert!(parser.parse_formula("H2").is_ok());
+    assert!(matches!(
+        parser.parse_formula("H2O"),
+        Err(ChemParseError::InvalidFormula(_, _, _))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_provenance_fingerprint() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let provenance = parser.provenance().with_option("lang", "en");
+
+    assert_eq!(provenance.version, env!("CARGO_PKG_VERSION"));
+    assert!(provenance.dataset.starts_with("fnv1a64:"));
+    assert_eq!(
+        provenance.dataset,
+        ChemParser::try_new()?.provenance().dataset
+    );
+    assert!(provenance.to_string().ends_with("options: lang=en"));
+
+    let data_dir = concat!(env!("CARGO_MANIFEST_DIR"), "/data");
+    assert_eq!(
+        ChemParser::from_data_dir(data_dir)?.provenance().dataset,
+        provenance.dataset
+    );
+
+    let table = PeriodicTable::from_csv_str(concat!(
+        "name,symbol,atomic_number,atomic_mass,density,group,melting_point,boiling_point\n",
+        "Hydrogen,H,1,1.008,8.2e-05,1,,\n"
+    ))
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_ne!(
+        ChemParser::with_table(table).provenance().dataset,
+        provenance.dataset
+    );
+
+    let json = serde_json::to_string(&provenance)?;
+    assert!(json.contains("\"options\":{\"lang\":\"en\"}"));
+    Ok(())
+}
+
+#[test]
+fn test_periodic_table_from_json() -> anyhow::Result<()> {
+    let array = PeriodicTable::from_json_str(
+        r#"[
+            {"name": "Hydrogen", "symbol": "H", "number": 1, "atomic_mass": 1.008, "density": 0.08988, "melt": 13.99},
+            {"name": "Oxygen", "symbol": "O", "number": 8, "atomic_mass": 15.999, "density": null, "xpos": 16}
+        ]"#,
+    )
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let parser = ChemParser::with_table(array);
+    assert!((parser.parse_formula("H2O")?.mass - 18.015).abs() < 1e-9);
+    assert_eq!(parser.parse_element("O")?.density, 0.0);
+
+    let table = PeriodicTable::from_json_str(
+        r#"{
+            "elements": [{"name": "Carbon", "symbol": "C", "atomic_number": 6, "atomic_mass": 12.011, "density": 2.267}],
+            "isotopes": [{"symbol": "C", "mass_number": 13, "mass": 13.003354835}]
+        }"#,
+    )
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(table.get_mass("13C"), Some(13.003354835));
+
+    assert!(PeriodicTable::from_json_str(r#"{"symbols": []}"#).is_err());
+    Ok(())
+}
+
+#[cfg(feature = "toml")]
+#[test]
+fn test_periodic_table_from_toml() -> anyhow::Result<()> {
+    let table = PeriodicTable::from_toml_str(
+        r#"
+            [[elements]]
+            name = "Sodium"
+            symbol = "Na"
+            atomic_number = 11
+            atomic_mass = 22.98976928
+            density = 0.968
+
+            [[elements]]
+            name = "Chlorine"
+            symbol = "Cl"
+            atomic_number = 17
+            atomic_mass = 35.45
+            density = 0.003214
+        "#,
+    )
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+
+    let parser = ChemParser::with_table(table);
+    assert!((parser.parse_formula("NaCl")?.mass - 58.43976928).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_bundled_dataset_versions() -> anyhow::Result<()> {
+    let old = ChemParser::with_dataset(DatasetVersion::Iupac2013)?;
+    let new = ChemParser::with_dataset(DatasetVersion::Iupac2021)?;
+
+    assert_eq!(old.parse_element("Yb")?.atomic_mass, 173.054);
+    assert_eq!(new.parse_element("Yb")?.atomic_mass, 173.045);
+    assert_eq!(old.parse_element("Ar")?.atomic_mass, 39.948);
+    assert_eq!(new.parse_element("Ar")?.atomic_mass, 39.95);
+    assert_eq!(
+        old.parse_formula("H2O")?.mass,
+        new.parse_formula("H2O")?.mass
+    );
+    assert!(old.parse_formula("ZrO2")?.mass > new.parse_formula("ZrO2")?.mass);
+    assert_ne!(old.provenance().dataset, new.provenance().dataset);
+
+    assert_eq!(
+        DatasetVersion::from_name("IUPAC-2021"),
+        Some(DatasetVersion::Iupac2021)
+    );
+    assert_eq!(
+        DatasetVersion::from_name("2013"),
+        Some(DatasetVersion::Iupac2013)
+    );
+    assert_eq!(DatasetVersion::from_name("2019"), None);
+
+    let abridged = ChemParser::with_dataset(DatasetVersion::Abridged2021)?;
+    assert_eq!(abridged.parse_element("O")?.atomic_mass, 16.0);
+    assert_eq!(abridged.parse_element("Fe")?.atomic_mass, 55.85);
+    assert_eq!(abridged.parse_element("Yb")?.atomic_mass, 173.0);
+    assert!((abridged.parse_formula("H2O")?.mass - 18.016).abs() < 1e-9);
+    assert_ne!(abridged.provenance().dataset, new.provenance().dataset);
+    assert_eq!(
+        DatasetVersion::from_name("abridged"),
+        Some(DatasetVersion::Abridged2021)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_compare_datasets() -> anyhow::Result<()> {
+    let masses = compare_datasets("Yb2O3", &DatasetVersion::ALL)?;
+
+    assert_eq!(masses.len(), 3);
+    assert_eq!(masses[0].version, DatasetVersion::Iupac2013);
+    assert_eq!(masses[0].change, 0.0);
+    assert!((masses[1].change - (173.045 - 173.054) * 2.0).abs() < 1e-9);
+    assert!((masses[1].mass - masses[0].mass - masses[1].change).abs() < 1e-12);
+
+    assert!((masses[2].mass - (173.0 * 2.0 + 16.0 * 3.0)).abs() < 1e-9);
+
+    let versions = [DatasetVersion::Iupac2013, DatasetVersion::Iupac2021];
+    assert!(compare_datasets("H2O", &versions)?
+        .iter()
+        .all(|entry| entry.change == 0.0));
+    assert!(matches!(
+        compare_datasets("Xy", &DatasetVersion::ALL),
+        Err(ChemParseError::InvalidFormula(_, _, _))
+    ));
+    assert!(compare_datasets("H2O", &[])?.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_element_lookup_by_number_and_name() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    assert_eq!(table.get_by_atomic_number(26).unwrap().symbol, "Fe");
+    assert!(table.get_by_atomic_number(0).is_none());
+    assert_eq!(table.get_by_name("oxygen").unwrap().symbol, "O");
+    assert_eq!(table.get_by_name("SODIUM").unwrap().atomic_number, 11);
+    assert!(table.get_by_name("Natrium").is_none());
+
+    assert_eq!(parser.parse_element("oxygen")?.symbol, "O");
+    assert_eq!(parser.parse_element("Tin")?.symbol, "Sn");
+    assert_eq!(parser.parse_element("Ti")?.symbol, "Ti");
+    assert!(matches!(
+        parser.parse_element("Oxygenium"),
+        Err(ChemParseError::InvalidElement(_, _))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_periodic_table_queries() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    assert_eq!(table.len(), 118);
+    assert!(!table.is_empty());
+    let numbers: Vec<u8> = table.iter().map(|element| element.atomic_number).collect();
+    assert_eq!(numbers, (1..=118).collect::<Vec<u8>>());
+    assert_eq!(table.sorted_by_symbol()[0].symbol, "Ac");
+    assert!(table
+        .sorted_by_mass()
+        .windows(2)
+        .all(|pair| pair[0].atomic_mass <= pair[1].atomic_mass));
+
+    let halogens: Vec<&str> = table
+        .elements_in_group(17)
+        .iter()
+        .map(|element| element.symbol.as_str())
+        .collect();
+    assert_eq!(halogens, vec!["F", "Cl", "Br", "I", "At", "Ts"]);
+    assert_eq!(table.elements_in_period(2).len(), 8);
+
+    assert_eq!(table.nonmetals().len(), 17);
+    assert_eq!(table.metalloids().len(), 6);
+    assert_eq!(table.metals().len(), 118 - 17 - 6);
+    assert!(table.get_element("Fe").unwrap().is_metal());
+    assert!(table.get_element("Si").unwrap().is_metalloid());
+    assert!(table.get_element("O").unwrap().is_nonmetal());
+    Ok(())
+}
+
+#[test]
+fn test_element_query() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    let symbols = |elements: Vec<&Element>| -> Vec<String> {
+        elements
+            .into_iter()
+            .map(|element| element.symbol.clone())
+            .collect()
+    };
+    assert_eq!(
+        symbols(
+            table
+                .query()
+                .atomic_mass(10.0..50.0)
+                .group(1)
+                .melting_point_below(500.0)
+                .elements()
+        ),
+        vec!["Na", "K"]
+    );
+    assert_eq!(
+        symbols(table.query().period(2).nonmetals().elements()),
+        vec!["C", "N", "O", "F", "Ne"]
+    );
+    assert_eq!(
+        symbols(table.query().atomic_number(..=2).elements()),
+        vec!["H", "He"]
+    );
+    assert!(table
+        .query()
+        .boiling_point_above(5000.0)
+        .elements()
+        .iter()
+        .all(|element| element.is_metal()));
+    assert_eq!(table.query().count(), 118);
+    assert_eq!(table.query().melting_point(..0.0).count(), 0);
+    Ok(())
+}
+
+#[test]
+fn test_element_search() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    let halogens: Vec<&str> = table
+        .query()
+        .search("group=17 and period<=4")?
+        .elements()
+        .iter()
+        .map(|element| element.symbol.as_str())
+        .collect();
+    assert_eq!(halogens, vec!["F", "Cl", "Br"]);
+    assert!(table
+        .query()
+        .search("mass>200")?
+        .elements()
+        .iter()
+        .all(|element| element.atomic_mass > 200.0));
+    assert_eq!(table.query().search("number < 3, number != 1")?.count(), 1);
+
+    assert!(table.query().search("colour=red").is_err());
+    assert!(table.query().search("mass~200").is_err());
+    assert!(table.query().search("mass>heavy").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_monoisotopic_mass() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    let carbon = table.most_abundant_isotope("C").unwrap();
+    assert_eq!(carbon.mass_number, 12);
+    assert_eq!(table.isotopes_of("Cl").len(), 3);
+    assert!(table.get_isotope("14C").unwrap().abundance.is_none());
+
+    let water = parser.parse_formula("H2O")?;
+    let exact = water.monoisotopic_mass(table).unwrap();
+    assert!((exact - 18.010565).abs() < 1e-6);
+    assert!((water.mass - exact).abs() > 0.004);
+
+    let labelled = parser.parse_formula("[13C]H4")?;
+    assert!((labelled.monoisotopic_mass(table).unwrap() - 17.034655).abs() < 1e-6);
+
+    let mut custom = PeriodicTable::embedded().map_err(|e| anyhow::anyhow!("{}", e))?;
+    custom
+        .load_isotopes_csv_str("symbol,mass_number,mass\nH,2,2.01410177812\n")
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert!(custom.get_isotope("D").unwrap().abundance.is_none());
+    Ok(())
+}
+
+#[test]
+fn test_isotope_pattern() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    let chlorine = parser
+        .parse_formula("Cl2")?
+        .isotope_pattern(table, 0.5)
+        .unwrap();
+    let nominal: Vec<(u32, f64)> = chlorine
+        .iter()
+        .map(|peak| {
+            (
+                peak.mz.round() as u32,
+                (peak.intensity * 100.0).round() / 100.0,
+            )
+        })
+        .collect();
+    assert_eq!(nominal, vec![(70, 100.0), (72, 63.99), (74, 10.24)]);
+
+    let methane = parser
+        .parse_formula("CH4")?
+        .isotope_pattern(table, 0.5)
+        .unwrap();
+    assert!((methane[0].mz - 16.0313).abs() < 1e-4);
+    assert!((methane[1].intensity - 1.13).abs() < 0.01);
+
+    let labelled = parser
+        .parse_formula("[13C]O2")?
+        .isotope_pattern(table, 0.5)
+        .unwrap();
+    assert_eq!(labelled[0].mz.round(), 45.0);
+    let ion = parser
+        .parse_formula("SO4^2-")?
+        .isotope_pattern(table, 0.5)
+        .unwrap();
+    assert!((ion[0].mz - 47.9764).abs() < 1e-4);
+    Ok(())
+}
+
+#[test]
+fn test_mass_search() -> anyhow::Result<()> {
+    use chemistry_parser::spectrum::MassSearch;
+
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+    let candidates = MassSearch::new(table, 180.06339, 5.0)
+        .element("C", 0..=12)
+        .element("H", 0..=24)
+        .element("N", 0..=4)
+        .element("O", 0..=12)
+        .candidates()?;
+    assert_eq!(candidates[0].formula.formula, "C6H12O6");
+    assert!(candidates[0].error_ppm.abs() < 1.0);
+    assert!(candidates
+        .iter()
+        .all(|candidate| candidate.error_ppm.abs() <= 5.0));
+
+    assert!(MassSearch::new(table, 18.0, 5.0)
+        .element("Xx", 0..=2)
+        .candidates()
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_typed_transition_temperatures() -> anyhow::Result<()> {
+    let table = PeriodicTable::global();
+    let iron = table.get_element("Fe").unwrap();
+    let melting_point = iron.melting_point.as_ref().unwrap().temperature().unwrap();
+    assert!((melting_point.in_celsius() - 1538.0).abs() < 1.0);
+    assert!(melting_point > Temperature::fahrenheit(2000.0));
+
+    let carbon = table.get_element("C").unwrap();
+    let melting_point = carbon.melting_point.as_ref().unwrap();
+    assert_eq!(
+        melting_point.allotrope("diamond"),
+        Some(Temperature::kelvins(4713.15))
+    );
+    assert_eq!(
+        melting_point.temperature(),
+        Some(Temperature::kelvins(4762.15))
+    );
+    let boiling_point = carbon.boiling_point.as_ref().unwrap();
+    assert_eq!(boiling_point.allotrope("diamond"), None);
+    assert!(table.get_element("Og").unwrap().melting_point.is_none());
+
+    let json = serde_json::to_string(carbon)?;
+    let parsed: Element = serde_json::from_str(&json)?;
+    assert_eq!(parsed.melting_point.as_ref(), Some(melting_point));
+    let parsed = PeriodicTable::from_json_str(
+        r#"[{"name": "Iron", "symbol": "Fe", "number": 26, "atomic_mass": 55.845, "melt": 1811.0}]"#,
+    )
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(
+        parsed.get_element("Fe").unwrap().melting_point,
+        Some(TransitionTemperature::Single(Temperature::kelvins(1811.0)))
+    );
+    Ok(())
+}
+
+#[test]
+fn test_extended_element_properties() -> anyhow::Result<()> {
+    let table = PeriodicTable::embedded().map_err(|e| anyhow::anyhow!("{}", e))?;
+    let lithium = table.get_element("Li").unwrap();
+    assert_eq!(lithium.first_ionization_energy(), Some(520.2));
+    assert_eq!(lithium.ionization_energy(2), Some(7298.1));
+    assert_eq!(lithium.ionization_energy(0), None);
+    assert_eq!(lithium.covalent_radius, Some(128.0));
+    assert_eq!(lithium.discovery_year, Some(1817));
+    assert_eq!(lithium.class(), ElementClass::Metal);
+
+    let silicon = table.get_element("Si").unwrap();
+    assert_eq!(silicon.class(), ElementClass::Metalloid);
+    assert_eq!(silicon.abundance, Some(282000.0));
+    let gold = table.get_element("Au").unwrap();
+    assert_eq!(gold.discovery_year, None);
+    assert_eq!(
+        table.get_element("Og").unwrap().ionization_energies,
+        Vec::<f64>::new()
+    );
+    assert_eq!(
+        table.get_element("Ne").unwrap().class().to_string(),
+        "nonmetal"
+    );
+
+    let json = serde_json::to_value(lithium)?;
+    assert_eq!(
+        json["ionization_energies"],
+        serde_json::json!([520.2, 7298.1])
+    );
+    let element: Element = serde_json::from_value(json)?;
+    assert_eq!(element.ionization_energies, lithium.ionization_energies);
+
+    let mut table = PeriodicTable::embedded().map_err(|e| anyhow::anyhow!("{}", e))?;
+    table
+        .load_properties_csv_str(
+            "symbol,ionization_energies,atomic_radius\nNa,495.8 4562 6910.3,\n",
+        )
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let sodium = table.get_element("Na").unwrap();
+    assert_eq!(sodium.ionization_energy(3), Some(6910.3));
+    assert_eq!(sodium.atomic_radius, Some(180.0));
+    assert!(table
+        .load_properties_csv_str("symbol,atomic_radius\nXx,100\n")
+        .is_err());
+    Ok(())
+}
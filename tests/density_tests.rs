@@ -0,0 +1,55 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_element_density_conversions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let iron = parser.parse_element("Fe")?;
+    assert!((iron.volume_of_mass(78.7).unwrap() - 10.0).abs() < 1e-9);
+    assert!((iron.mass_of_volume(2.0).unwrap() - 15.74).abs() < 1e-9);
+
+    let mut unknown = iron.clone();
+    unknown.density = 0.0;
+    assert_eq!(unknown.volume_of_mass(1.0), None);
+    assert_eq!(unknown.mass_of_volume(1.0), None);
+    Ok(())
+}
+
+#[test]
+fn test_formula_density_conversions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let water = parser.parse_formula("H2O")?;
+    assert!((water.volume_of_moles(1.0, 1.0) - 18.015).abs() < 1e-9);
+    assert!((water.moles_of_volume(18.015, 1.0) - 1.0).abs() < 1e-12);
+
+    let ethanol = parser.parse_formula("C2H5OH")?;
+    let moles = ethanol.moles_of_volume(100.0, 0.789);
+    assert!((ethanol.volume_of_moles(moles, 0.789) - 100.0).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_equation_liquid_volumes() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let combustion = parser.parse_equation("C2H5OH + 3O2 -> 2CO2 + 3H2O")?;
+    let co2 = parser.parse_formula("CO2")?.mass;
+    let ethanol = parser.parse_formula("C2H5OH")?.mass;
+
+    let volume = combustion.liquid_volume_of("C2H5OH", ("CO2", 2.0 * co2), 0.789)?;
+    assert!((volume - ethanol / 0.789).abs() < 1e-9);
+    let water = combustion.mass_from_liquid("H2O", ("C2H5OH", ethanol / 0.789), 0.789)?;
+    assert!((water - 3.0 * parser.parse_formula("H2O")?.mass).abs() < 1e-9);
+
+    assert!(matches!(
+        combustion.liquid_volume_of("C2H5OH", ("CO2", 1.0), 0.0),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+    assert!(combustion
+        .liquid_volume_of("C2H5OH", ("CO2", 1.0), -1.0)
+        .is_err());
+    assert!(combustion
+        .mass_from_liquid("H2O", ("NaCl", 1.0), 1.0)
+        .is_err());
+    Ok(())
+}
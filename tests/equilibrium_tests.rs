@@ -0,0 +1,52 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_equilibrium_expression() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let haber = parser
+        .parse_equation("N2 + 3H2 <=> 2NH3")?
+        .equilibrium_expression();
+    assert_eq!(haber.to_string(), "[NH3]^2 / ([N2][H2]^3)");
+    assert_eq!(haber.numerator[0].coefficient, 2);
+    assert_eq!(
+        haber.to_latex(),
+        "\\frac{[\\mathrm{NH}_{3}]^{2}}{[\\mathrm{N}_{2}][\\mathrm{H}_{2}]^{3}}"
+    );
+
+    let dissociation = parser
+        .parse_equation("N2O4 <=> 2NO2")?
+        .equilibrium_expression();
+    assert_eq!(dissociation.to_string(), "[NO2]^2 / [N2O4]");
+
+    let half = parser
+        .parse_equation("Fe^3+ + e^- -> Fe^2+")?
+        .equilibrium_expression();
+    assert_eq!(half.to_string(), "[Fe^2+] / [Fe^3+]");
+
+    let heterogeneous = parser
+        .parse_equation("CaCO3(s) <=> CaO(s) + CO2(g)")?
+        .equilibrium_expression();
+    assert_eq!(heterogeneous.to_string(), "[CO2]");
+
+    let fractional = parser
+        .parse_equation("H2 + 1/2O2 <=> H2O")?
+        .equilibrium_expression();
+    assert_eq!(fractional.to_string(), "[H2O] / ([H2][O2]^(1/2))");
+    assert_eq!(fractional.exponent(&fractional.denominator[1]).value(), 0.5);
+    assert!(fractional.to_latex().ends_with("[\\mathrm{O}_{2}]^{1/2}}"));
+    let scaled = parser
+        .parse_equation("N2 + 3H2 <=> 2NH3")?
+        .equilibrium_expression();
+    assert_eq!(
+        parser
+            .parse_equation("1/2N2 + 3/2H2 <=> NH3")?
+            .equilibrium_expression()
+            .to_string(),
+        "[NH3] / ([N2]^(1/2)[H2]^(3/2))"
+    );
+    assert_eq!(scaled.exponent_denominator, 1);
+    Ok(())
+}
@@ -0,0 +1,75 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::export::{mass_table, write_mass_table_csv, write_mass_table_json};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_mass_table() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let formulas = vec![parser.parse_formula("H2O")?, parser.parse_formula("NaCl")?];
+    let table = mass_table(&formulas);
+    assert_eq!(table.len(), 2);
+    assert_eq!(table[0].0, "H2O");
+    assert_eq!(table[1].1, formulas[1].mass);
+
+    let mut csv = Vec::new();
+    write_mass_table_csv(&table, &mut csv)?;
+    assert_eq!(
+        String::from_utf8(csv)?,
+        "formula,molar_mass\nH2O,18.0150\nNaCl,58.4398\n"
+    );
+
+    let mut json = Vec::new();
+    write_mass_table_json(&table, &mut json)?;
+    let rows: serde_json::Value = serde_json::from_slice(&json)?;
+    assert_eq!(rows[1]["formula"], "NaCl");
+    assert_eq!(rows[1]["molar_mass"], 58.4398);
+    Ok(())
+}
+
+#[test]
+fn test_mass_breakdown() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let formula = parser.parse_formula("H2O")?;
+    let breakdown = formula.mass_breakdown(parser.get_table()).unwrap();
+    let elements: Vec<(&str, u32)> = breakdown
+        .elements
+        .iter()
+        .map(|element| (element.element.as_str(), element.count))
+        .collect();
+    assert_eq!(elements, [("H", 2), ("O", 1)]);
+    assert!((breakdown.total - formula.mass).abs() < 1e-9);
+    assert!((breakdown.elements[0].subtotal - 2.0 * breakdown.elements[0].atom_mass).abs() < 1e-12);
+    assert!((breakdown.elements[0].percent - 11.19).abs() < 0.01);
+    let percent: f64 = breakdown
+        .elements
+        .iter()
+        .map(|element| element.percent)
+        .sum();
+    assert!((percent - 100.0).abs() < 1e-9);
+
+    let glucose = parser.parse_formula("HOCH2(CHOH)4CHO")?;
+    let order: Vec<String> = glucose
+        .mass_breakdown(parser.get_table())
+        .unwrap()
+        .elements
+        .into_iter()
+        .map(|element| element.element)
+        .collect();
+    assert_eq!(order, ["C", "H", "O"]);
+
+    let text = breakdown.to_string();
+    assert_eq!(text.lines().count(), 4);
+    assert!(text.starts_with("H2O\n"));
+    assert!(text.lines().nth(2).unwrap().starts_with("O "));
+    assert!(text.ends_with("100.00 %"));
+
+    let mut csv = Vec::new();
+    breakdown.write_csv(&mut csv)?;
+    let csv = String::from_utf8(csv)?;
+    let lines: Vec<&str> = csv.lines().collect();
+    assert_eq!(lines[0], "element,count,atom_mass,subtotal,percent");
+    assert!(lines[2].starts_with("O,1,"));
+    assert_eq!(lines[3], "total,,,18.0150,100.00");
+    Ok(())
+}
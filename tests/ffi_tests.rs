@@ -0,0 +1,35 @@
+#![cfg(feature = "ffi")]
+
+use chemistry_parser::ffi::*;
+use std::ffi::{CStr, CString};
+
+#[test]
+fn test_ffi_functions() -> anyhow::Result<()> {
+    unsafe {
+        let formula = chem_parse_formula(CString::new("H2SO4")?.as_ptr());
+        assert!(!formula.is_null());
+        assert!((chem_formula_mass(formula) - 98.072).abs() < 0.001);
+        assert_eq!(chem_formula_charge(formula), 0);
+        let json = chem_formula_to_json(formula);
+        assert!(CStr::from_ptr(json)
+            .to_str()?
+            .contains("\"formula\":\"H2SO4\""));
+        chem_free_string(json);
+        chem_free_formula(formula);
+
+        let invalid = chem_parse_formula(CString::new("Xx2")?.as_ptr());
+        assert!(invalid.is_null());
+        assert!(CStr::from_ptr(chem_last_error()).to_str()?.contains("Xx"));
+
+        let equation = chem_parse_equation(CString::new("H2 + O2 -> H2O")?.as_ptr());
+        assert!(!chem_equation_is_balanced(equation));
+        let balanced = chem_equation_balance(equation, 1000);
+        assert_eq!(CStr::from_ptr(balanced).to_str()?, "2H2 + O2 -> 2H2O");
+        chem_free_string(balanced);
+        chem_free_equation(equation);
+
+        assert!(chem_formula_mass(std::ptr::null()).is_nan());
+        assert!(chem_parse_equation(std::ptr::null()).is_null());
+    }
+    Ok(())
+}
@@ -0,0 +1,46 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::gas::{self, Pressure, Temperature, STANDARD_PRESSURE, STANDARD_TEMPERATURE};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_units() -> anyhow::Result<()> {
+    assert!((Pressure::atmospheres(1.0).in_kilopascals() - 101.325).abs() < 1e-9);
+    assert!((Pressure::mmhg(760.0).in_atmospheres() - 1.0).abs() < 1e-12);
+    assert!((Temperature::fahrenheit(212.0).in_celsius() - 100.0).abs() < 1e-9);
+    assert!(Temperature::celsius(25.0) > Temperature::kelvins(273.15));
+    Ok(())
+}
+
+#[test]
+fn test_ideal_gas_law() -> anyhow::Result<()> {
+    let molar_volume = gas::volume(1.0, STANDARD_PRESSURE, STANDARD_TEMPERATURE);
+    assert!((molar_volume - 22.711).abs() < 1e-3);
+
+    let pressure = Pressure::atmospheres(2.0);
+    let temperature = Temperature::celsius(25.0);
+    let moles = gas::moles(pressure, 10.0, temperature);
+    assert!((gas::pressure(moles, 10.0, temperature).in_atmospheres() - 2.0).abs() < 1e-12);
+    assert!((gas::temperature(moles, pressure, 10.0).in_celsius() - 25.0).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_gas_stoichiometry() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let combustion = parser.parse_equation("CH4 + 2O2 -> CO2 + 2H2O")?;
+    let methane = parser.parse_formula("CH4")?.mass;
+
+    let oxygen = combustion.stp_volume_of("O2", ("CH4", methane))?;
+    assert!((oxygen - 2.0 * 22.711).abs() < 1e-2);
+
+    let warm = combustion.gas_volume_of(
+        "CO2",
+        ("CH4", methane),
+        Pressure::atmospheres(1.0),
+        Temperature::celsius(25.0),
+    )?;
+    assert!((warm - 24.465).abs() < 1e-2);
+    assert!(combustion.stp_volume_of("N2", ("CH4", 1.0)).is_err());
+    Ok(())
+}
@@ -0,0 +1,126 @@
+#![cfg(feature = "generator")]
+
+use chemistry_parser::generator::{Difficulty, Exercise, Generator};
+use chemistry_parser::ChemParser;
+
+const DIFFICULTIES: [Difficulty; 3] = [Difficulty::Easy, Difficulty::Medium, Difficulty::Hard];
+
+#[test]
+fn test_same_seed_same_worksheet() -> anyhow::Result<()> {
+    for difficulty in DIFFICULTIES {
+        let first = Generator::new(42, difficulty).worksheet(9)?;
+        let second = Generator::new(42, difficulty).worksheet(9)?;
+        assert_eq!(first, second);
+        assert_ne!(first, Generator::new(43, difficulty).worksheet(9)?);
+    }
+    Ok(())
+}
+
+#[test]
+fn test_name_formula() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    for difficulty in DIFFICULTIES {
+        let mut generator = Generator::new(7, difficulty);
+        for _ in 0..30 {
+            let exercise = generator.name_formula()?;
+            let Exercise::NameFormula { formula, name } = &exercise else {
+                panic!("expected a naming exercise, got {:?}", exercise);
+            };
+            assert_eq!(parser.parse_formula(formula)?.charge, 0);
+            assert!(exercise.check(&parser, &name.to_uppercase()));
+            assert!(!exercise.check(&parser, "water"));
+            if difficulty == Difficulty::Hard {
+                assert!(name.contains(')'), "{} is not in Stock notation", name);
+            }
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_balance_equation() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    for difficulty in DIFFICULTIES {
+        let mut generator = Generator::new(11, difficulty);
+        for _ in 0..30 {
+            let exercise = generator.balance_equation()?;
+            let Exercise::BalanceEquation { equation, solution } = &exercise else {
+                panic!("expected a balancing exercise, got {:?}", exercise);
+            };
+            assert!(parser.parse_equation(solution)?.check_equation());
+            assert!(exercise.check(&parser, solution));
+            if !parser.parse_equation(equation)?.check_equation() {
+                assert!(!exercise.check(&parser, equation));
+            }
+        }
+    }
+
+    let exercise = Exercise::BalanceEquation {
+        equation: String::from("H2 + O2 -> H2O"),
+        solution: String::from("2H2 + O2 -> 2H2O"),
+    };
+    assert!(exercise.check(&parser, "O2 + 2H2 -> 2H2O"));
+    assert!(!exercise.check(&parser, "4H2 + 2O2 -> 4H2O"));
+    assert!(!exercise.check(&parser, "H2 -> H2"));
+    Ok(())
+}
+
+#[test]
+fn test_stoichiometry() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    for difficulty in DIFFICULTIES {
+        let mut generator = Generator::new(3, difficulty);
+        for _ in 0..20 {
+            let exercise = generator.stoichiometry()?;
+            let Exercise::Stoichiometry {
+                equation,
+                given,
+                grams,
+                target,
+                answer,
+            } = &exercise
+            else {
+                panic!("expected a stoichiometry exercise, got {:?}", exercise);
+            };
+            let expected = parser
+                .parse_equation(equation)?
+                .mass_of(target, (given, *grams))?;
+            assert!((answer - expected).abs() < 1e-4 * expected);
+            assert!(exercise.check(&parser, &format!("{:.2} g", answer)));
+            assert!(!exercise.check(&parser, &format!("{}", answer * 1.1)));
+        }
+    }
+    Ok(())
+}
+
+#[test]
+fn test_exercise_text() -> anyhow::Result<()> {
+    let exercise = Exercise::Stoichiometry {
+        equation: String::from("2H2 + O2 -> 2H2O"),
+        given: String::from("H2"),
+        grams: 4.0,
+        target: String::from("H2O"),
+        answer: 35.7424,
+    };
+    assert_eq!(
+        exercise.question(),
+        "How many grams of H2O correspond to 4 g of H2 in 2H2 + O2 -> 2H2O?"
+    );
+    assert_eq!(exercise.answer(), "35.74 g of H2O");
+    assert!(serde_json::to_string(&exercise)?.contains("\"kind\":\"stoichiometry\""));
+    Ok(())
+}
+
+/// The ChaCha8 generator gives the same sequence on every platform, so a seed pins the problems.
+#[test]
+fn test_seed_gives_fixed_problems() -> anyhow::Result<()> {
+    let mut generator = Generator::new(42, Difficulty::Easy);
+    let formulas = (0..3)
+        .map(|_| match generator.name_formula()? {
+            Exercise::NameFormula { formula, .. } => Ok(formula),
+            exercise => anyhow::bail!("expected a naming exercise, got {:?}", exercise),
+        })
+        .collect::<anyhow::Result<Vec<_>>>()?;
+    assert_eq!(formulas, ["Na2O", "Na3N", "BaBr2"]);
+    Ok(())
+}
@@ -1,4 +1,6 @@
-use chemistry_parser::{ChemParser, Rule};
+#![cfg(feature = "std")]
+
+use chemistry_parser::{debug, ChemParseError, ChemParser, Rule};
 use pest::Parser;
 
 #[test]
@@ -184,3 +186,130 @@ fn test_unsuccessful_whitespace_parse() -> anyhow::Result<()> {
 
     Ok(())
 }
+
+#[test]
+fn test_successful_arrow_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::arrow, "->").is_ok());
+    assert!(ChemParser::parse(Rule::arrow, "<->").is_ok());
+    assert!(ChemParser::parse(Rule::arrow, "<=>").is_ok());
+    assert!(ChemParser::parse(Rule::arrow, "⇌").is_ok());
+    assert!(ChemParser::parse(Rule::arrow, "=").is_ok());
+    assert!(ChemParser::parse(Rule::equation, "N2 + 3H2 <=> 2NH3").is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_arrow_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::arrow, "<-").is_err());
+    assert!(ChemParser::parse(Rule::arrow, ">").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_conditions_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::conditions, "[Fe, 450°C]").is_ok());
+    assert!(ChemParser::parse(Rule::note, "(Δ)").is_ok());
+    assert!(ChemParser::parse(Rule::equation, "N2 + 3H2 ->[Fe, 450°C] 2NH3").is_ok());
+    assert!(ChemParser::parse(Rule::equation, "CaCO3 -> CaO + CO2 (Δ)").is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_conditions_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::conditions, "[]").is_err());
+    assert!(ChemParser::parse(Rule::conditions, "[Fe").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_isotope_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::isotope, "^235U").is_ok());
+    assert!(ChemParser::parse(Rule::isotope, "[13C]").is_ok());
+    assert!(ChemParser::parse(Rule::isotope, "U-235").is_ok());
+    assert!(ChemParser::parse(Rule::formula, "[13C]H4").is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_isotope_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::isotope, "^U").is_err());
+    assert!(ChemParser::parse(Rule::isotope, "[C13]").is_err());
+    assert!(ChemParser::parse(Rule::isotope, "U-0").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_complex_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::complex, "[Fe(CN)6]").is_ok());
+    assert!(ChemParser::parse(Rule::formula, "K4[Fe(CN)6]").is_ok());
+    assert!(ChemParser::parse(Rule::formula, "[Cu(NH3)4]SO4").is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_complex_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::complex, "[Fe(CN)6").is_err());
+    assert!(ChemParser::parse(Rule::complex, "[]").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_successful_charge_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::charge, "^2-").is_ok());
+    assert!(ChemParser::parse(Rule::charge, "3-").is_ok());
+    assert!(ChemParser::parse(Rule::charge, "+").is_ok());
+
+    let formula = ChemParser::parse(Rule::formula, "[Fe(CN)6]3-")?
+        .next()
+        .unwrap();
+    assert_eq!(formula.as_str(), "[Fe(CN)6]3-");
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_charge_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::charge, "2").is_err());
+    assert!(ChemParser::parse(Rule::charge, "+2").is_err());
+    assert!(ChemParser::parse(Rule::charge, "->").is_err());
+    assert!(ChemParser::parse(Rule::charge, "+ B").is_err());
+    assert!(ChemParser::parse(Rule::charge, "+ 2B").is_err());
+    assert!(ChemParser::parse(Rule::equation, "A+ B -> C").is_ok());
+    assert!(ChemParser::parse(Rule::equation, "Na+ + Cl- -> NaCl").is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_state_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::state, "(aq)").is_ok());
+    assert!(ChemParser::parse(Rule::state, "(s)").is_ok());
+    assert!(ChemParser::parse(Rule::state, "(x)").is_err());
+    assert!(ChemParser::parse(Rule::equation, "Na+(aq) + Cl-(aq) -> NaCl(s)").is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_debug_tree() -> anyhow::Result<()> {
+    assert_eq!(
+        debug::tree(Rule::formula, "H₂O")?,
+        "formula \"H2O\"\n  element \"H\"\n  index \"2\"\n  element \"O\""
+    );
+    let tree = debug::tree(debug::input_rule("2H2 + O2 -> 2H2O"), "2H2 + O2 -> 2H2O")?;
+    assert!(tree.starts_with("equation \"2H2 + O2 -> 2H2O\"\n  reactants \"2H2 + O2\"\n"));
+    assert!(tree.contains("\n  arrow \"->\"\n"));
+    assert!(debug::tree(Rule::formula, "Ca(OH")?.ends_with("\nunmatched \"(OH\""));
+    assert!(matches!(
+        debug::tree(Rule::formula, "(OH"),
+        Err(ChemParseError::SyntaxError(_, _))
+    ));
+    Ok(())
+}
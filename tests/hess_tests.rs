@@ -0,0 +1,138 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::hess::{combine, Multiplier};
+use chemistry_parser::thermo::ThermoTable;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_combine() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let steps = [
+        parser.parse_equation("C + O2 -> CO2")?,
+        parser.parse_equation("2CO + O2 -> 2CO2")?,
+    ];
+
+    let target = parser.parse_equation("2C + O2 -> 2CO")?;
+    let multipliers = combine(&steps, &target)?;
+    let written: Vec<String> = multipliers.iter().map(ToString::to_string).collect();
+    assert_eq!(written, ["2", "-1"]);
+
+    let half = combine(&steps, &parser.parse_equation("CO2 -> C + O2")?)?;
+    assert_eq!(half[0].value(), -1.0);
+    assert_eq!(half[1].value(), 0.0);
+
+    let reversed = [
+        parser.parse_equation("2C + O2 -> 2CO")?,
+        parser.parse_equation("2CO + O2 -> 2CO2")?,
+    ];
+    let halves = combine(&reversed, &parser.parse_equation("C + O2 -> CO2")?)?;
+    assert_eq!(halves[0].to_string(), "1/2");
+    assert_eq!(halves[1].to_string(), "1/2");
+
+    let per_mole = combine(&steps, &parser.parse_equation("CO + 1/2O2 -> CO2")?)?;
+    assert_eq!(per_mole[0].value(), 0.0);
+    assert_eq!(per_mole[1].to_string(), "1/2");
+
+    let unrelated = parser.parse_equation("N2 + 3H2 -> 2NH3")?;
+    assert!(combine(&steps, &unrelated).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_hess_enthalpy() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = ThermoTable::embedded()?;
+    let combustions = [
+        parser.parse_equation("CH4 + 2O2 -> CO2 + 2H2O")?,
+        parser.parse_equation("C + O2 -> CO2")?,
+        parser.parse_equation("2H2 + O2 -> 2H2O")?,
+    ];
+    let formation = parser.parse_equation("C + 2H2 -> CH4")?;
+
+    let multipliers = combine(&combustions, &formation)?;
+    let mut enthalpy = 0.0;
+    for (equation, multiplier) in combustions.iter().zip(&multipliers) {
+        enthalpy += multiplier.value() * equation.reaction_enthalpy(&table)?;
+    }
+    assert!((enthalpy - formation.reaction_enthalpy(&table)?).abs() < 1e-9);
+    Ok(())
+}
+
+#[test]
+fn test_reverse() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let reversed = parser.parse_equation("2H2 + O2 -> 2H2O")?.reverse();
+    assert_eq!(reversed.equation, "2H2O -> 2H2 + O2");
+    assert_eq!(reversed.reactants["H2O"], 2);
+    assert!(reversed.check_equation());
+
+    let reversed = parser.parse_equation("H2 + 1/2O2 <=> H2O(l)")?.reverse();
+    assert_eq!(reversed.equation, "H2O(l) <=> H2 + 1/2O2");
+    assert_eq!(
+        parser
+            .parse_equation(&reversed.equation)?
+            .reverse()
+            .equation,
+        "H2 + 1/2O2 <=> H2O(l)"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_combine_equations() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let carbon = parser.parse_equation("C + O2 -> CO2")?;
+    let monoxide = parser.parse_equation("2CO + O2 -> 2CO2")?;
+
+    let half = Multiplier::new(1, -2).unwrap();
+    assert_eq!(half.to_string(), "-1/2");
+    let combined = carbon.combine(&monoxide, half)?;
+    assert_eq!(combined.equation, "C + 1/2O2 -> CO");
+    assert!(combined.check_equation());
+
+    let whole = carbon.combine(&monoxide, Multiplier::new(-1, 1).unwrap())?;
+    assert_eq!(whole.equation, "C + CO2 -> 2CO");
+    let doubled = carbon.combine(&carbon, Multiplier::new(1, 1).unwrap())?;
+    assert_eq!(doubled.equation, "2C + 2O2 -> 2CO2");
+    assert_eq!(doubled.to_integer_coefficients().denominator, 1);
+
+    // Species in different states do not cancel.
+    let vapour = parser.parse_equation("2H2 + O2 -> 2H2O(g)")?;
+    let condensation = parser.parse_equation("H2O(g) -> H2O(l)")?;
+    let liquid = vapour.combine(&condensation, Multiplier::new(2, 1).unwrap())?;
+    assert_eq!(liquid.equation, "2H2 + O2 -> 2H2O(l)");
+
+    assert!(carbon
+        .combine(&carbon, Multiplier::new(-1, 1).unwrap())
+        .is_err());
+    assert!(Multiplier::new(1, 0).is_none());
+
+    // Heat and light have no atoms but are not the same species.
+    let burning = parser.parse_equation("2H2 + O2 -> 2H2O + heat")?;
+    let photolysis = parser.parse_equation("H2O2 + hν -> H2O + O")?;
+    let combined = burning.combine(&photolysis, Multiplier::new(1, 1).unwrap())?;
+    assert_eq!(combined.equation, "2H2 + O2 + H2O2 + hν -> 3H2O + heat + O");
+    let ozone = parser.parse_equation("O3 + hν -> O2 + O")?;
+    let recombination = parser.parse_equation("O2 + O -> O3 + heat")?;
+    let cycle = ozone.combine(&recombination, Multiplier::new(1, 1).unwrap())?;
+    assert_eq!(cycle.equation, "hν -> heat");
+    Ok(())
+}
+
+#[test]
+fn test_combine_steps_into_target() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let steps = [
+        parser.parse_equation("CH4 + 2O2 -> CO2 + 2H2O")?,
+        parser.parse_equation("C + O2 -> CO2")?,
+        parser.parse_equation("2H2 + O2 -> 2H2O")?,
+    ];
+    let multipliers = combine(&steps, &parser.parse_equation("C + 2H2 -> CH4")?)?;
+    assert_eq!(multipliers[0].value(), -1.0);
+    let mut net = steps[0].reverse();
+    for (step, multiplier) in steps[1..].iter().zip(&multipliers[1..]) {
+        net = net.combine(step, *multiplier)?;
+    }
+    assert_eq!(net.equation, "C + 2H2 -> CH4");
+    Ok(())
+}
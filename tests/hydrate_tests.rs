@@ -0,0 +1,60 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::hydrate::{loss_on_drying, percent_water, water_count};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_percent_water() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let blue_vitriol = parser.parse_formula("CuSO4·5H2O")?;
+    assert_eq!(water_count(&parser, &blue_vitriol), 5);
+    let percent = percent_water(&parser, &blue_vitriol).unwrap();
+    assert!((percent - 36.08).abs() < 0.05);
+
+    let epsom = parser.parse_formula("MgSO4*7H2O")?;
+    assert_eq!(water_count(&parser, &epsom), 7);
+    assert_eq!(
+        percent_water(&parser, &parser.parse_formula("CuSO4")?),
+        Some(0.0)
+    );
+    assert_eq!(
+        water_count(&parser, &parser.parse_formula("CaCl2·2NH3")?),
+        0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_water_count_by_composition() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert_eq!(
+        water_count(&parser, &parser.parse_formula("CuSO4·5OH2")?),
+        5
+    );
+    assert_eq!(
+        water_count(&parser, &parser.parse_formula("CuSO4·5HOH")?),
+        5
+    );
+    assert_eq!(
+        water_count(&parser, &parser.parse_formula("CuSO4·5D2O")?),
+        0
+    );
+    assert_eq!(
+        water_count(&parser, &parser.parse_formula("CuSO4·H2O2")?),
+        0
+    );
+    Ok(())
+}
+
+#[test]
+fn test_loss_on_drying() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let copper_sulfate = parser.parse_formula("CuSO4")?;
+
+    assert_eq!(loss_on_drying(&parser, &copper_sulfate, 2.50, 1.60)?, 5);
+    assert_eq!(loss_on_drying(&parser, &copper_sulfate, 1.60, 1.60)?, 0);
+    assert!(loss_on_drying(&parser, &copper_sulfate, 1.60, 2.50).is_err());
+    assert!(loss_on_drying(&parser, &copper_sulfate, 1.60, 0.0).is_err());
+    Ok(())
+}
@@ -0,0 +1,59 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_inchi_formula_layer() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for (formula, layer) in [
+        ("C6H12O6", "C6H12O6"),
+        ("CH3COOH", "C2H4O2"),
+        ("H2SO4", "H2O4S"),
+        ("SO4^2-", "O4S"),
+        ("[13C]H4", "CH4"),
+        ("D2O", "H2O"),
+    ] {
+        assert_eq!(
+            parser.parse_formula(formula)?.inchi_formula_layer(),
+            layer,
+            "{}",
+            formula
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_inchi() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let glucose = parser.parse_inchi(
+        "InChI=1S/C6H12O6/c7-1-2-3(9)4(10)5(11)6(12)13-2/h2-11H,1H2/t2-,3-,4+,5-,6?/m1/s1",
+    )?;
+    assert!(glucose.same_composition(&parser.parse_formula("C6H12O6")?));
+    assert!((glucose.mass - parser.parse_formula("C6H12O6")?.mass).abs() < 1e-9);
+
+    let calcium_chloride = parser.parse_inchi("InChI=1S/2ClH.Ca/h2*1H;/q;+2/p-2")?;
+    assert!(calcium_chloride.same_composition(&parser.parse_formula("CaCl2")?));
+
+    let ammonium = parser.parse_inchi("InChI=1S/H3N/h1H3/p+1")?;
+    assert!(ammonium.same_composition(&parser.parse_formula("NH4^+")?));
+
+    let acetate = parser.parse_inchi("InChI=1S/C2H4O2/c1-2(3)4/h1H3,(H,3,4)/p-1")?;
+    assert_eq!(acetate.formula, "C2H3O2^-");
+
+    for inchi in ["C6H12O6", "InChI=1S/", "InChI=2/CH4", "InChI=1S/H2O/p-3"] {
+        assert!(
+            matches!(
+                parser.parse_inchi(inchi).unwrap_err(),
+                ChemParseError::ParsingError(_, _)
+            ),
+            "{}",
+            inchi
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,94 @@
+#![cfg(feature = "std")]
+
+use chemistry_parser::input::{Checkpoint, InputLine, LineReader};
+
+#[test]
+fn test_line_reader() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("chemistry_parser_line_reader.txt");
+    std::fs::write(&path, "2H2 + O2 -> 2H2O\r\n\nNaCl")?;
+
+    let reader = LineReader::open(&path)?;
+    assert_eq!(reader.len(), 23);
+    let lines = reader.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(
+        lines,
+        vec![
+            InputLine {
+                number: 1,
+                end: 18,
+                text: String::from("2H2 + O2 -> 2H2O"),
+            },
+            InputLine {
+                number: 2,
+                end: 19,
+                text: String::new(),
+            },
+            InputLine {
+                number: 3,
+                end: 23,
+                text: String::from("NaCl"),
+            },
+        ]
+    );
+
+    #[cfg(feature = "mmap")]
+    assert_eq!(
+        LineReader::open_mapped(&path)?.collect::<Result<Vec<_>, _>>()?,
+        lines
+    );
+
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_resume_from_checkpoint() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("chemistry_parser_resume.txt");
+    let checkpoint_path = std::env::temp_dir().join("chemistry_parser_resume.json");
+    std::fs::write(&path, "H2 + O2 -> H2O\nNa + Cl2 -> NaCl\nC + O2 -> CO2\n")?;
+    let input = path.to_string_lossy();
+
+    let mut checkpoint = Checkpoint::new(&input);
+    let first = LineReader::open(&path)?.next().transpose()?;
+    checkpoint.advance(&first.ok_or_else(|| anyhow::anyhow!("empty input"))?);
+    checkpoint.save(&checkpoint_path)?;
+
+    let saved = Checkpoint::load(&checkpoint_path)?;
+    assert_eq!(saved.as_ref(), Some(&checkpoint));
+    let mut reader = LineReader::open(&path)?;
+    reader.resume(&checkpoint)?;
+    let rest = reader.collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(rest.len(), 2);
+    assert_eq!(rest[0].number, 2);
+    assert_eq!(rest[0].text, "Na + Cl2 -> NaCl");
+
+    let saved = saved.ok_or_else(|| anyhow::anyhow!("no checkpoint"))?;
+    assert_eq!(saved.size, Some(46));
+    assert!(saved.matches_input(&input));
+    assert!(!saved.matches_input("chemistry_parser_other.txt"));
+    std::fs::write(&path, "H2 + O2 -> H2O\nC + O2 -> CO2\n")?;
+    assert!(!saved.matches_input(&input));
+
+    Checkpoint::remove(&checkpoint_path)?;
+    assert_eq!(Checkpoint::load(&checkpoint_path)?, None);
+    Checkpoint::remove(&checkpoint_path)?;
+    assert_eq!(Checkpoint::new("-").size, None);
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
+
+#[test]
+fn test_equation_lines() -> anyhow::Result<()> {
+    let path = std::env::temp_dir().join("chemistry_parser_equation_lines.txt");
+    std::fs::write(&path, "H2 + O2 -> H2O\n\n  # comment\r\nC + O2 -> CO2")?;
+
+    let lines = LineReader::open_file(&path)?
+        .equation_lines()
+        .collect::<Result<Vec<_>, _>>()?;
+    assert_eq!(lines.len(), 2);
+    assert_eq!((lines[0].number, lines[0].end), (1, 15));
+    assert_eq!((lines[1].number, lines[1].end), (4, 42));
+    assert_eq!(lines[1].text, "C + O2 -> CO2");
+    std::fs::remove_file(&path)?;
+    Ok(())
+}
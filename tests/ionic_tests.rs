@@ -0,0 +1,61 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_ionic_equation() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let precipitation = parser.parse_equation("AgNO3(aq) + NaCl(aq) -> AgCl(s) + NaNO3(aq)")?;
+    assert_eq!(
+        precipitation.to_ionic(&parser)?.equation,
+        "Ag^+(aq) + NO3^-(aq) + Na^+(aq) + Cl^-(aq) -> AgCl(s) + Na^+(aq) + NO3^-(aq)"
+    );
+    assert_eq!(
+        precipitation.to_net_ionic(&parser)?.equation,
+        "Ag^+(aq) + Cl^-(aq) -> AgCl(s)"
+    );
+
+    let gas = parser.parse_equation("2HCl(aq) + Na2CO3(aq) -> 2NaCl(aq) + H2O(l) + CO2(g)")?;
+    assert_eq!(
+        gas.to_net_ionic(&parser)?.equation,
+        "2H^+(aq) + CO3^2-(aq) -> H2O(l) + CO2(g)"
+    );
+    let half =
+        parser.parse_equation("HCl(aq) + 1/2Na2CO3(aq) -> NaCl(aq) + 1/2H2O(l) + 1/2CO2(g)")?;
+    assert_eq!(
+        half.to_ionic(&parser)?.equation,
+        "H^+(aq) + Cl^-(aq) + Na^+(aq) + 1/2CO3^2-(aq) -> Na^+(aq) + Cl^-(aq) + 1/2H2O(l) + 1/2CO2(g)"
+    );
+    assert_eq!(
+        half.to_net_ionic(&parser)?.equation,
+        gas.to_net_ionic(&parser)?.equation
+    );
+
+    let barium = parser.parse_equation("BaCl2 + Na2SO4 -> BaSO4 + 2NaCl")?;
+    assert_eq!(
+        barium.to_net_ionic(&parser)?.equation,
+        "Ba^2+(aq) + SO4^2-(aq) -> BaSO4"
+    );
+
+    let weak_acid = parser.parse_equation("CH3COOH(aq) + NaOH(aq) -> CH3COONa(aq) + H2O(l)")?;
+    assert_eq!(
+        weak_acid.to_net_ionic(&parser)?.equation,
+        "CH3COOH(aq) + OH^-(aq) -> CH3COO^-(aq) + H2O(l)"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_ionic_equation_errors() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let spectators = parser.parse_equation("NaCl(aq) + KNO3(aq) -> NaNO3(aq) + KCl(aq)")?;
+    assert!(matches!(
+        spectators.to_net_ionic(&parser),
+        Err(ChemParseError::EvaluationError(_, _))
+    ));
+    let unbalanced = parser.parse_equation("BaCl2(aq) + Na2SO4(aq) -> BaSO4(s) + NaCl(aq)")?;
+    assert!(unbalanced.to_ionic(&parser).is_err());
+    Ok(())
+}
@@ -0,0 +1,96 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::lewis::LewisCounts;
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_valence_electrons() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for (symbol, valence) in [
+        ("H", 1),
+        ("He", 2),
+        ("C", 4),
+        ("Cl", 7),
+        ("Ne", 8),
+        ("Ga", 3),
+        ("Fe", 8),
+        ("Zn", 2),
+    ] {
+        assert_eq!(
+            parser.parse_element(symbol)?.valence_electrons(),
+            valence,
+            "{}",
+            symbol
+        );
+    }
+
+    let table = parser.get_table();
+    for (formula, total) in [
+        ("H2O", 8),
+        ("CO2", 16),
+        ("SO4^2-", 32),
+        ("NH4^+", 8),
+        ("D2O", 8),
+    ] {
+        assert_eq!(
+            parser
+                .parse_formula(formula)?
+                .total_valence_electrons(table),
+            Some(total),
+            "{}",
+            formula
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_lewis_counts() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    for (formula, bonding_pairs, lone_pairs) in [
+        ("H2O", 2, 2),
+        ("CO2", 4, 4),
+        ("NH3", 3, 1),
+        ("CH4", 4, 0),
+        ("N2", 3, 2),
+        ("CO3^2-", 4, 8),
+        ("HCN", 4, 1),
+    ] {
+        let counts = parser.parse_formula(formula)?.lewis_counts(table)?;
+        assert_eq!(
+            (counts.bonding_pairs, counts.lone_pairs),
+            (bonding_pairs, lone_pairs),
+            "{}",
+            formula
+        );
+    }
+    assert_eq!(
+        parser.parse_formula("O2")?.lewis_counts(table)?,
+        LewisCounts {
+            valence_electrons: 12,
+            bonding_pairs: 2,
+            lone_pairs: 4,
+        }
+    );
+
+    for formula in ["SF6", "BH3", "NO"] {
+        assert!(
+            matches!(
+                parser.parse_formula(formula)?.lewis_counts(table),
+                Err(ChemParseError::EvaluationError(_, _))
+            ),
+            "{}",
+            formula
+        );
+    }
+    assert!(matches!(
+        parser.parse_formula("C600000000")?.lewis_counts(table),
+        Err(ChemParseError::Overflow(_))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,51 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::matrix::{stoichiometric_matrix, CHARGE_ROW};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn element_species_matrix() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let matrix = parser
+        .parse_equation("2H2 + O2 -> 2H2O")?
+        .stoichiometric_matrix();
+    assert_eq!(matrix.rows, ["H", "O"]);
+    assert_eq!(matrix.columns, ["H2", "O2", "H2O"]);
+    assert_eq!(matrix.values, [[2, 0, 2], [0, 2, 1]]);
+    assert_eq!(matrix.get("O", "H2O"), Some(1));
+
+    let ionic = parser
+        .parse_equation("Fe^3+ + e^- -> Fe^2+")?
+        .stoichiometric_matrix();
+    assert_eq!(ionic.rows, ["Fe", CHARGE_ROW]);
+    assert_eq!(ionic.values, [[1, 0, 1], [3, -1, 2]]);
+    Ok(())
+}
+
+#[test]
+fn species_reaction_matrix() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equations = [
+        parser.parse_equation("2C + O2 -> 2CO")?,
+        parser.parse_equation("2CO + O2 -> 2CO2")?,
+    ];
+    let matrix = stoichiometric_matrix(&equations);
+    assert_eq!(matrix.rows, ["C", "O2", "CO", "CO2"]);
+    assert_eq!(matrix.columns, ["2C + O2 -> 2CO", "2CO + O2 -> 2CO2"]);
+    assert_eq!(matrix.values, [[-2, 0], [-1, -1], [2, -2], [0, 2]]);
+    assert!(stoichiometric_matrix(&[]).values.is_empty());
+    Ok(())
+}
+
+#[cfg(feature = "ndarray")]
+#[test]
+fn converts_to_array() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let array = parser
+        .parse_equation("2H2 + O2 -> 2H2O")?
+        .stoichiometric_matrix()
+        .to_array();
+    assert_eq!(array.shape(), [2, 3]);
+    assert_eq!(array[[1, 2]], 1);
+    Ok(())
+}
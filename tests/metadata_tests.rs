@@ -0,0 +1,92 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::element::PeriodicTable;
+use chemistry_parser::metadata::{is_valid_cas, CompoundCatalog, CompoundMetadata};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn elements_have_embedded_identifiers() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let iron = parser.parse_element("Fe")?;
+    assert_eq!(iron.cas.as_deref(), Some("7439-89-6"));
+    assert_eq!(iron.category.as_deref(), Some("transition metal"));
+    assert_eq!(
+        parser
+            .get_table()
+            .get_by_cas(" 7782-50-5 ")
+            .map(|e| e.symbol.as_str()),
+        Some("Cl")
+    );
+    assert!(parser.get_table().get_by_cas("0000-00-0").is_none());
+    assert!(parser
+        .get_table()
+        .iter()
+        .all(|element| element.cas.as_deref().is_some_and(is_valid_cas)));
+    Ok(())
+}
+
+#[test]
+fn identifiers_load_from_csv() -> anyhow::Result<()> {
+    let mut table = PeriodicTable::embedded().map_err(|e| anyhow::anyhow!("{}", e))?;
+    table
+        .load_identifiers_csv_str("symbol,cas,cid,category\nNa,,5360545,\n")
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let sodium = table
+        .get_element("Na")
+        .ok_or_else(|| anyhow::anyhow!("no Na"))?;
+    assert_eq!(sodium.cid, Some(5360545));
+    assert_eq!(sodium.cas.as_deref(), Some("7440-23-5"));
+    assert_eq!(sodium.category.as_deref(), Some("alkali metal"));
+    assert!(table
+        .load_identifiers_csv_str("symbol,cas\nXx,1-11-1\n")
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn catalog_looks_up_compounds() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut catalog = CompoundCatalog::new();
+    catalog
+        .load_csv_str(
+            &parser,
+            "formula,name,cas,cid,category\n\
+             CH3COOH,acetic acid,64-19-7,176,acid\n\
+             HCOOCH3,methyl formate,107-31-3,,ester\n\
+             NaCl,sodium chloride,7647-14-5,5234,salt\n",
+        )
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(catalog.len(), 3);
+
+    let names: Vec<_> = catalog
+        .lookup(&parser.parse_formula("C2H4O2")?)
+        .iter()
+        .filter_map(|metadata| metadata.name.as_deref())
+        .collect();
+    assert_eq!(names, ["acetic acid", "methyl formate"]);
+
+    let (formula, metadata) = catalog
+        .get_by_cas("7647-14-5")
+        .ok_or_else(|| anyhow::anyhow!("no NaCl"))?;
+    assert_eq!(formula.formula, "NaCl");
+    assert_eq!(
+        metadata,
+        &CompoundMetadata {
+            name: Some(String::from("sodium chloride")),
+            cas: Some(String::from("7647-14-5")),
+            cid: Some(5234),
+            category: Some(String::from("salt")),
+        }
+    );
+    Ok(())
+}
+
+#[test]
+fn validates_cas_check_digits() -> anyhow::Result<()> {
+    assert!(is_valid_cas("7732-18-5"));
+    assert!(is_valid_cas("50-00-0"));
+    assert!(!is_valid_cas("7732-18-4"));
+    assert!(!is_valid_cas("7732-1-85"));
+    assert!(!is_valid_cas("water"));
+    Ok(())
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::names::{greek_prefix, ion_name};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_hydrate_formulas() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let blue_vitriol = parser.parse_formula("CuSO4·5H2O")?;
+    assert_eq!(blue_vitriol.elements["H"], 10);
+    assert_eq!(blue_vitriol.elements["O"], 9);
+    let anhydrous = parser.parse_formula("CuSO4")?.mass;
+    let water = parser.parse_formula("H2O")?.mass;
+    assert!((blue_vitriol.mass - (anhydrous + 5.0 * water)).abs() < 1e-9);
+
+    assert_eq!(
+        parser.parse_formula("CuSO4*5H2O")?.elements,
+        blue_vitriol.elements
+    );
+    assert_eq!(parser.parse_formula("CaCl2·2NH3·6H2O")?.elements["N"], 2);
+    Ok(())
+}
+
+#[test]
+fn test_pretty_names() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert_eq!(parser.pretty_name("SO4^2-"), "sulfate ion");
+    assert_eq!(parser.pretty_name("SO₄²⁻"), "sulfate ion");
+    assert_eq!(parser.pretty_name("C2H3O2^-"), "acetate ion");
+    assert_eq!(parser.pretty_name("Fe^3+"), "iron(III) ion");
+    assert_eq!(parser.pretty_name("·5H2O"), "pentahydrate");
+    assert_eq!(parser.pretty_name("CuSO4·5H2O"), "CuSO4 pentahydrate");
+    assert_eq!(parser.pretty_name("Na2CO3·10H2O"), "Na2CO3 decahydrate");
+
+    assert_eq!(parser.pretty_name("H2SO4"), "H2SO4");
+    assert_eq!(parser.pretty_name("CaCl2·2NH3"), "CaCl2·2NH3");
+    assert_eq!(parser.pretty_name("Xy^2-"), "Xy^2-");
+
+    assert_eq!(ion_name(&parser.parse_formula("NH4^+")?), Some("ammonium"));
+    assert_eq!(greek_prefix(1), Some("mono"));
+    assert_eq!(greek_prefix(0), None);
+    Ok(())
+}
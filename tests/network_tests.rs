@@ -0,0 +1,91 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::network::{path, Edge, PathSearch, ReactionNetwork};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_path() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equations = [
+        parser.parse_equation("CH4 + H2O -> CO + 3H2")?,
+        parser.parse_equation("CO + 2H2 -> CH3OH")?,
+        parser.parse_equation("2CH4 + O2 -> 2CH3OH")?,
+        parser.parse_equation("CH3OH + CO -> CH3COOH")?,
+        parser.parse_equation("N2 + 3H2 <=> 2NH3")?,
+    ];
+
+    let direct = path(&equations, "CH4", "CH3OH").unwrap();
+    assert_eq!(direct.len(), 1);
+    assert_eq!(direct[0].reaction, 2);
+
+    let acetic = PathSearch::new(&equations)
+        .ban("O2")
+        .find("CH4", "C2H4O2")
+        .unwrap();
+    let reactions: Vec<usize> = acetic.iter().map(|step| step.reaction).collect();
+    assert_eq!(reactions, [0, 3]);
+    assert_eq!(acetic[1].from, "CO");
+    assert_eq!(acetic[1].to, "CH3COOH");
+
+    let ammonia = path(&equations, "NH3", "N2").unwrap();
+    assert_eq!(ammonia[0].reaction, 4);
+    assert!(path(&equations, "CH3OH", "CH4").is_none());
+    assert!(PathSearch::new(&equations)
+        .max_depth(1)
+        .ban("O2")
+        .find("CH4", "CH3OH")
+        .is_none());
+    assert_eq!(path(&equations, "CO", "CO"), Some(Vec::new()));
+    Ok(())
+}
+
+#[test]
+fn test_reaction_network() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let network: ReactionNetwork = [
+        "CH4 + H2O -> CO + 3H2",
+        "CO + H2O -> CO2 + H2",
+        "N2 + 3H2 <=> 2NH3",
+    ]
+    .into_iter()
+    .map(|equation| parser.parse_equation(equation))
+    .collect::<Result<_, _>>()?;
+
+    let species: Vec<&str> = network
+        .species()
+        .iter()
+        .map(|formula| formula.formula.as_str())
+        .collect();
+    assert_eq!(species, ["CH4", "H2O", "CO", "H2", "CO2", "N2", "NH3"]);
+    assert_eq!(network.consuming("H2O"), [0, 1]);
+    assert_eq!(network.producing("H2"), [0, 1, 2]);
+    assert_eq!(network.consuming("H2"), [2]);
+    assert!(network.consuming("O2").is_empty());
+    assert_eq!(network.species_index("H3N"), Some(6));
+
+    let edges = network.edges();
+    assert!(edges.contains(&Edge {
+        from: 6,
+        to: 5,
+        reaction: 2
+    }));
+    assert_eq!(edges.len(), 4 + 4 + 4);
+    assert_eq!(network.path("CH4", "NH3").map(|steps| steps.len()), Some(2));
+    Ok(())
+}
+
+#[test]
+fn test_network_dot() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut network = ReactionNetwork::new();
+    network.add(parser.parse_equation("2H2 + O2 -> 2H2O")?);
+    network.add(parser.parse_equation("N2 + 3H2 <=> 2NH3")?);
+    assert_eq!(
+        network.to_dot(),
+        "digraph reactions {\n    s0 [label=\"H2\"];\n    s1 [label=\"O2\"];\n    \
+         s2 [label=\"H2O\"];\n    s3 [label=\"N2\"];\n    s4 [label=\"NH3\"];\n    \
+         s0 -> s2 [label=\"R1\"];\n    s1 -> s2 [label=\"R1\"];\n    \
+         s3 -> s4 [label=\"R2\", dir=both];\n    s0 -> s4 [label=\"R2\", dir=both];\n}\n"
+    );
+    Ok(())
+}
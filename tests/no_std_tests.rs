@@ -0,0 +1,19 @@
+#![cfg(not(feature = "std"))]
+
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_no_std_parsing() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert_eq!(parser.parse_element("Fe")?.atomic_number, 26);
+
+    let formula = parser.parse_formula("Ca(OH)2")?;
+    assert_eq!(formula.elements.get("H"), Some(&2));
+    assert!((formula.mass - 74.092).abs() < 0.001);
+    assert!(parser.parse_formula("[13C]H4").is_ok());
+
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    assert!(equation.check_atom_balance(false));
+    assert!(parser.parse_formula("Xx").is_err());
+    Ok(())
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_systematic_name() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for (formula, name) in [
+        ("NaCl", "sodium chloride"),
+        ("MgO", "magnesium oxide"),
+        ("Al2O3", "aluminium oxide"),
+        ("Fe2(SO4)3", "iron(III) sulfate"),
+        ("(NH4)2SO4", "ammonium sulfate"),
+        ("NaHCO3", "sodium hydrogen carbonate"),
+        ("Cu2O", "copper(I) oxide"),
+        ("Na2O2", "sodium peroxide"),
+        ("PbO2", "lead(IV) oxide"),
+        ("SnCl4", "tin(IV) chloride"),
+        ("HCl", "hydrogen chloride"),
+        ("H2SO4", "dihydrogen sulfate"),
+        ("N2O4", "dinitrogen tetroxide"),
+        ("CO", "carbon monoxide"),
+        ("CO2", "carbon dioxide"),
+        ("P4O10", "tetraphosphorus decoxide"),
+        ("SF6", "sulfur hexafluoride"),
+        ("OF2", "oxygen difluoride"),
+        ("CCl4", "carbon tetrachloride"),
+        ("H2O", "water"),
+        ("O2", "dioxygen"),
+        ("Fe", "iron"),
+        ("SO4^2-", "sulfate ion"),
+    ] {
+        assert_eq!(
+            parser.systematic_name(&parser.parse_formula(formula)?)?,
+            name,
+            "{}",
+            formula
+        );
+    }
+
+    for formula in ["C6H12O6", "Fe3O4", "NO3^2-"] {
+        let error = parser
+            .systematic_name(&parser.parse_formula(formula)?)
+            .unwrap_err();
+        assert!(
+            matches!(error, ChemParseError::EvaluationError(_, _)),
+            "{}",
+            formula
+        );
+    }
+
+    Ok(())
+}
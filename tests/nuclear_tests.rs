@@ -0,0 +1,134 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::nuclear::{remaining_quantity, DecayMode, Nuclide, JULIAN_YEAR};
+use chemistry_parser::{ChemParseError, ChemParser, Rule};
+use pest::Parser;
+
+#[test]
+fn test_successful_nuclear_equation_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::nuclear_equation, "^238U -> ^234Th + alpha").is_ok());
+    assert!(ChemParser::parse(Rule::nuclear_equation, "^235U + n -> ^141Ba + ^92Kr + 3n").is_ok());
+    assert!(ChemParser::parse(Rule::nuclear_equation, "D + T -> ^4He + n").is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn test_unsuccessful_nuclear_equation_parse() -> anyhow::Result<()> {
+    assert!(ChemParser::parse(Rule::nuclear_equation, "U -> Th + alpha").is_err());
+    assert!(ChemParser::parse(Rule::particle, "x").is_err());
+    assert!(ChemParser::parse(Rule::nuclear_equation, "^14C -> ").is_err());
+
+    Ok(())
+}
+
+#[test]
+fn test_nuclear_equation_balanced() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let fission = parser.parse_nuclear_equation("^235U + n -> ^141Ba + ^92Kr + 3n")?;
+    assert_eq!(fission.mass_numbers(), (236, 236));
+    assert_eq!(fission.atomic_numbers(), (92, 92));
+    assert!(fission.check_equation());
+
+    assert!(parser
+        .parse_nuclear_equation("^14C -> ^14N + β-")?
+        .check_equation());
+    assert!(parser
+        .parse_nuclear_equation("D + T -> He-4 + n")?
+        .check_equation());
+    Ok(())
+}
+
+#[test]
+fn test_nuclear_equation_unbalanced() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_nuclear_equation("^238U -> ^234U + alpha")?;
+    assert_eq!(equation.mass_numbers(), (238, 238));
+    assert!(!equation.check_equation());
+
+    let result = parser.parse_nuclear_equation("^4Xx -> alpha");
+    assert!(matches!(result, Err(ChemParseError::InvalidIsotope(_))));
+    Ok(())
+}
+
+#[test]
+fn test_decay_products() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    let uranium = Nuclide::new("^238U", 238, 92);
+    assert_eq!(uranium.decay_mode(table), Some(DecayMode::Alpha));
+    let products = uranium.decay_products(table).unwrap();
+    assert_eq!(products[0], Nuclide::new("^234Th", 234, 90));
+    assert_eq!(products[1].name, "alpha");
+
+    let carbon = Nuclide::new("^14C", 14, 6);
+    let equation = carbon.decay_equation(table).unwrap();
+    assert_eq!(equation.equation, "^14C -> ^14N + beta-");
+    assert!(equation.check_equation());
+    assert!(parser
+        .parse_nuclear_equation(&equation.equation)?
+        .check_equation());
+
+    let chromium = Nuclide::new("^51Cr", 51, 24);
+    assert_eq!(chromium.decay_products(table).unwrap().len(), 1);
+    assert!(chromium.decay_equation(table).unwrap().check_equation());
+
+    assert!(Nuclide::new("^12C", 12, 6).decay_products(table).is_none());
+    assert!(Nuclide::particle("n")
+        .unwrap()
+        .decay_products(table)
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn test_decay_chain() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    let chain = Nuclide::new("^238U", 238, 92).decay_chain(table);
+    let names: Vec<&str> = chain.iter().map(|nuclide| nuclide.name.as_str()).collect();
+    assert_eq!(
+        names,
+        [
+            "^238U", "^234Th", "^234Pa", "^234U", "^230Th", "^226Ra", "^222Rn", "^218Po", "^214Pb",
+            "^214Bi", "^214Po", "^210Pb", "^210Bi", "^210Po", "^206Pb"
+        ]
+    );
+    assert!(chain.last().unwrap().isotope(table).unwrap().is_stable());
+
+    let chain = Nuclide::new("^90Sr", 90, 38).decay_chain(table);
+    assert_eq!(chain.last().unwrap().name, "^90Zr");
+    assert_eq!(Nuclide::new("^16O", 16, 8).decay_chain(table).len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_remaining_quantity() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    assert_eq!(remaining_quantity(100.0, 5.0, 10.0), 25.0);
+
+    let iodine = Nuclide::new("^131I", 131, 53);
+    let half_life = iodine.half_life(table).unwrap();
+    assert!((half_life / 86400.0 - 8.025).abs() < 0.01);
+    let remaining = iodine.remaining_after(table, 1.0, 3.0 * half_life).unwrap();
+    assert!((remaining - 0.125).abs() < 1e-9);
+
+    let carbon = Nuclide::new("^14C", 14, 6);
+    let remaining = carbon
+        .remaining_after(table, 1.0, 11460.0 * JULIAN_YEAR)
+        .unwrap();
+    assert!((remaining - 0.25).abs() < 1e-3);
+
+    let stable = Nuclide::new("^12C", 12, 6);
+    assert_eq!(stable.remaining_after(table, 2.0, 1e12), Some(2.0));
+    assert_eq!(
+        Nuclide::new("^4Xx", 4, 0).remaining_after(table, 1.0, 1.0),
+        None
+    );
+    Ok(())
+}
@@ -0,0 +1,225 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::ast::EquationAst;
+use chemistry_parser::element::Arrow;
+use chemistry_parser::options::{GrammarOptions, ParseOptions};
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn strict_parsing_reads_input_as_written() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert_eq!(parser.options(), ParseOptions::strict());
+    assert_eq!(parser.parse_formula("H 2 O")?.formula, "H");
+    // Strict parsing reads the formula or equation at the start and leaves out the text after it.
+    assert_eq!(parser.parse_formula("H2O garbage")?.formula, "H2O");
+    assert_eq!(
+        parser
+            .parse_equation("H2 + O2 -> H2O garbage")?
+            .product_terms
+            .len(),
+        1
+    );
+    assert!(parser.parse_formula(" H2O").is_err());
+    assert!(parser.parse_equation("H2 + O2 → H2O").is_err());
+    assert!(parser.parse_equation("2 H2 + O2 -> 2 H2O").is_err());
+    Ok(())
+}
+
+#[test]
+fn lenient_parsing_tolerates_whitespace() -> anyhow::Result<()> {
+    let parser = ChemParser::new().with_options(ParseOptions::lenient());
+    assert_eq!(parser.parse_formula(" H 2 O ")?.formula, "H2O");
+    assert_eq!(parser.parse_formula("Ca (OH) 2")?.formula, "Ca(OH)2");
+    assert_eq!(parser.parse_formula("NaCl table salt")?.formula, "NaCl");
+
+    let equation = parser.parse_equation(" 2 H2 (g) + O 2 (g) -> 2 H2O (l) ")?;
+    assert_eq!(equation.equation, "2H2(g) + O2(g) -> 2H2O(l)");
+    assert!(equation.check_atom_balance(false));
+
+    let ionic = parser.parse_equation("Na+ + Cl- -> NaCl")?;
+    assert_eq!(ionic.reactant_terms[0].formula.charge, 1);
+    Ok(())
+}
+
+#[test]
+fn lenient_parsing_does_not_join_separators_and_numbers() -> anyhow::Result<()> {
+    let parser = ChemParser::new().with_options(ParseOptions::lenient());
+    let hydrogen = parser.parse_formula("H2 + O2")?;
+    assert_eq!((hydrogen.formula.as_str(), hydrogen.charge), ("H2", 0));
+    let salt = parser.parse_formula("NaCl - sodium chloride")?;
+    assert_eq!((salt.formula.as_str(), salt.charge), ("NaCl", 0));
+    assert_eq!(parser.parse_formula("H2O 2 bottles")?.formula, "H2O");
+    assert_eq!(parser.parse_formula("Fe 2 O 3")?.formula, "Fe2O3");
+
+    let whitespace_only = ChemParser::new().with_options(ParseOptions {
+        ignore_whitespace: true,
+        ..ParseOptions::strict()
+    });
+    assert_eq!(whitespace_only.parse_formula("Na +")?.charge, 0);
+    assert_eq!(whitespace_only.parse_formula("Na+")?.charge, 1);
+    Ok(())
+}
+
+#[test]
+fn lenient_parsing_attaches_conditions_to_the_arrow() -> anyhow::Result<()> {
+    let parser = ChemParser::new().with_options(ParseOptions::lenient());
+    let catalysed = parser.parse_equation("2H2 + O2 -> [Pt] 2H2O")?;
+    assert_eq!(catalysed.equation, "2H2 + O2 ->[Pt] 2H2O");
+    assert_eq!(catalysed.catalysts, ["Pt"]);
+    assert_eq!(catalysed.product_terms[0].formula.formula, "H2O");
+
+    let conditions = parser.parse_equation("N2 + 3H2 -> [Fe, 450°C] 2NH3")?;
+    assert_eq!(conditions.equation, "N2 + 3H2 ->[Fe, 450°C] 2NH3");
+    assert_eq!(conditions.conditions, ["Fe", "450°C"]);
+    Ok(())
+}
+
+#[test]
+fn lenient_parsing_accepts_arrows_and_trailing_text() -> anyhow::Result<()> {
+    let parser = ChemParser::new().with_options(ParseOptions::lenient());
+    for input in [
+        "H2 + O2 → H2O",
+        "H2 + O2 ⟶ H2O",
+        "H2 + O2 --> H2O",
+        "H2 + O2 = H2O",
+    ] {
+        assert_eq!(parser.parse_equation(input)?.reactant_terms.len(), 2);
+    }
+    let reversible = parser.parse_equation("N2 + 3H2 ↔ 2NH3")?;
+    assert_eq!(reversible.equation, "N2 + 3H2 <-> 2NH3");
+
+    let trailing = parser.parse_equation("H2 + O2 -> H2O; unbalanced")?;
+    assert_eq!(trailing.equation, "H2 + O2 -> H2O");
+    let noted = parser.parse_equation("CaCO3 -> CaO + CO2 (Δ)")?;
+    assert_eq!(noted.conditions, ["Δ"]);
+    let catalysed = parser.parse_equation("N2 + 3H2 ->[Fe, 450°C] 2NH3")?;
+    assert_eq!(catalysed.catalysts, ["Fe"]);
+
+    let options = ParseOptions {
+        alternative_arrows: false,
+        ..ParseOptions::lenient()
+    };
+    assert!(ChemParser::new()
+        .with_options(options)
+        .parse_equation("H2 + O2 → H2O")
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn normalizes_letter_case() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let water = parser.parse_formula_normalized("h2o")?;
+    assert_eq!(water.input, "H2O");
+    assert!(water.corrected);
+    assert_eq!(water.parsed.formula, "H2O");
+
+    assert_eq!(parser.parse_formula_normalized("NACL")?.input, "NaCl");
+    assert_eq!(parser.parse_formula_normalized("h2so4")?.input, "H2SO4");
+    let carbon_monoxide = parser.parse_formula_normalized("CO")?;
+    assert!(!carbon_monoxide.corrected);
+    assert_eq!(carbon_monoxide.input, "CO");
+
+    let equation = parser.parse_equation_normalized("2h2(g) + o2(g) -> 2h2o(l)")?;
+    assert_eq!(equation.input, "2H2(g) + O2(g) -> 2H2O(l)");
+    assert!(equation.parsed.check_atom_balance(false));
+
+    assert!(parser.parse_formula_normalized("xyz").is_err());
+    Ok(())
+}
+
+#[test]
+fn grammar_options_add_tokens() -> anyhow::Result<()> {
+    let grammar = GrammarOptions::new()
+        .with_arrow("==>", Arrow::Irreversible)
+        .with_arrow("⇋", Arrow::Reversible)
+        .with_plus_sign(" & ")
+        .with_plus_sign(" · ")
+        .with_plus_sign("  ");
+    assert_eq!(grammar.arrows().len(), 2);
+    assert_eq!(grammar.plus_signs(), [" & ", " · "]);
+    let parser = ChemParser::new().with_grammar_options(grammar.clone());
+    assert_eq!(parser.grammar_options(), &grammar);
+    assert!(ChemParser::new().grammar_options().is_empty());
+
+    let equation = parser.parse_equation("2H2 & O2 ==> 2H2O")?;
+    assert_eq!(equation.equation, "2H2 + O2 -> 2H2O");
+    assert_eq!(equation.arrow, Arrow::Irreversible);
+    assert!(equation.check_equation());
+
+    let reversible = parser.parse_equation("N2 + 3H2 ⇋ 2NH3")?;
+    assert_eq!(reversible.arrow, Arrow::Reversible);
+
+    let adduct = parser.parse_equation("BF3 · NH3 -> BF3NH3")?;
+    assert_eq!(adduct.reactant_terms.len(), 2);
+    let hydrate = parser.parse_equation("CuSO4·5H2O -> CuSO4 + 5H2O")?;
+    assert_eq!(hydrate.reactant_terms.len(), 1);
+
+    assert!(ChemParser::new()
+        .parse_equation("2H2 & O2 ==> 2H2O")
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn grammar_options_combine_with_lenient_parsing() -> anyhow::Result<()> {
+    let parser = ChemParser::new()
+        .with_options(ParseOptions::lenient())
+        .with_grammar_options(GrammarOptions::new().with_plus_sign("＋"));
+    let equation = parser.parse_equation(" 2 H2 ＋ O2 → 2 H2O ")?;
+    assert_eq!(equation.equation, "2H2 + O2 -> 2H2O");
+    Ok(())
+}
+
+#[test]
+fn grammar_options_apply_to_every_equation_syntax() -> anyhow::Result<()> {
+    let grammar = GrammarOptions::new()
+        .with_arrow("==>", Arrow::Irreversible)
+        .with_plus_sign(" & ");
+    let parser = ChemParser::new().with_grammar_options(grammar.clone());
+
+    let sequence = parser.parse_sequence("C & O2 ==> CO2\n2CO2 ==> 2CO & O2")?;
+    assert_eq!(sequence.steps.len(), 2);
+    assert_eq!(sequence.steps[1].equation, "2CO2 -> 2CO + O2");
+
+    let fission = parser.parse_nuclear_equation("^235U & n ==> ^141Ba & ^92Kr & 3n")?;
+    assert_eq!(fission.equation, "^235U + n -> ^141Ba + ^92Kr + 3n");
+
+    let ast = EquationAst::parse_with_grammar("2H2 & O2 ==> 2H2O", &grammar)?;
+    assert_eq!(ast.reactants.len(), 2);
+    assert_eq!(ast.arrow.arrow, Arrow::Irreversible);
+    assert_eq!(ast.arrow.span, 9..12);
+    assert_eq!(ast.products[0].span, 13..17);
+    assert!(EquationAst::parse("2H2 & O2 ==> 2H2O").is_err());
+    Ok(())
+}
+
+#[test]
+fn grammar_options_report_errors_in_the_input_as_written() -> anyhow::Result<()> {
+    let grammar = GrammarOptions::new()
+        .with_arrow("yields", Arrow::Irreversible)
+        .with_plus_sign(" & ");
+    let parser = ChemParser::new().with_grammar_options(grammar.clone());
+    let input = "2H₂ & O₂ yields & 2H2O";
+    let position = |error: ChemParseError| match error {
+        ChemParseError::SyntaxError(_, diagnostic) => {
+            Some((diagnostic.input, diagnostic.column, diagnostic.span))
+        }
+        _ => None,
+    };
+    let expected = Some((String::from(input), 17, 20..20));
+
+    assert_eq!(
+        position(parser.parse_equation(input).unwrap_err()),
+        expected
+    );
+    assert_eq!(
+        position(parser.parse_sequence(input).unwrap_err()),
+        expected
+    );
+    assert_eq!(
+        position(EquationAst::parse_with_grammar(input, &grammar).unwrap_err()),
+        expected
+    );
+    Ok(())
+}
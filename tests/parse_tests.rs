@@ -1,4 +1,9 @@
-use chemistry_parser::{ChemParseError, ChemParser};
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::element::{Arrow, Coefficient, Equation, Formula, SpecialSpecies, State};
+use chemistry_parser::validation::ValidationRules;
+use chemistry_parser::{normalize_scripts, ChemParseError, ChemParser};
+use std::collections::BTreeMap;
 
 #[test]
 fn test_parse_element_valid() -> anyhow::Result<()> {
@@ -13,7 +18,7 @@ fn test_parse_element_valid() -> anyhow::Result<()> {
 fn test_parse_element_invalid() -> anyhow::Result<()> {
     let parser = ChemParser::new();
     let result = parser.parse_element("Ha");
-    assert!(matches!(result, Err(ChemParseError::InvalidElement(_))));
+    assert!(matches!(result, Err(ChemParseError::InvalidElement(_, _))));
     Ok(())
 }
 
@@ -28,6 +33,20 @@ fn test_parse_formula() -> anyhow::Result<()> {
     Ok(())
 }
 
+#[test]
+fn test_counts_by_atomic_number() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let water = parser.parse_formula("HDO")?;
+    let counts = water
+        .counts_by_atomic_number(parser.get_table())
+        .expect("all elements are in the table");
+    assert_eq!(counts.into_iter().collect::<Vec<_>>(), vec![(1, 2), (8, 1)]);
+    let glucose = parser.parse_formula("C6H12O6")?;
+    assert_eq!(glucose.elements.len(), 3);
+    assert_eq!(glucose.elements["C"], 6);
+    Ok(())
+}
+
 #[test]
 fn test_parse_formula_with_groups() -> anyhow::Result<()> {
     let parser = ChemParser::new();
@@ -43,7 +62,77 @@ fn test_parse_formula_with_groups() -> anyhow::Result<()> {
 fn test_parse_formula_invalid_element() -> anyhow::Result<()> {
     let parser = ChemParser::new();
     let result = parser.parse_formula("Yx2");
-    assert!(matches!(result, Err(ChemParseError::InvalidFormula(_, _))));
+    assert!(matches!(
+        result,
+        Err(ChemParseError::InvalidFormula(_, _, _))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_syntax_error_location() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let error = parser.parse_equation("2H2 + -> 2H2O").unwrap_err();
+    let ChemParseError::SyntaxError(kind, diagnostic) = &error else {
+        anyhow::bail!("expected a syntax error, got {:?}", error);
+    };
+    assert_eq!(kind, "equation");
+    assert_eq!((diagnostic.line, diagnostic.column), (1, 7));
+    assert_eq!(diagnostic.span, 6..6);
+    assert!(diagnostic.expected.contains(&String::from("formula")));
+    let snippet = error.snippet().unwrap();
+    assert!(snippet.starts_with("2H2 + -> 2H2O\n      ^ expected "));
+    assert!(error.to_string().contains("at line 1, column 7"));
+
+    assert!(parser.parse_formula("Yx2").unwrap_err().snippet().is_none());
+    Ok(())
+}
+
+#[test]
+fn test_error_codes() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert_eq!(parser.parse_element("Xx").unwrap_err().code(), "E0001");
+    assert_eq!(parser.parse_formula("Yx2").unwrap_err().code(), "E0002");
+    assert_eq!(
+        parser.parse_equation("2H2 + -> 2H2O").unwrap_err().code(),
+        "E0004"
+    );
+    assert_eq!(parser.eval("mass(H2O) / 0").unwrap_err().code(), "E0010");
+    Ok(())
+}
+
+#[test]
+fn test_invalid_symbol_suggestions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    match parser.parse_element("CL") {
+        Err(ChemParseError::InvalidElement(symbol, suggestions)) => {
+            assert_eq!(symbol, "CL");
+            assert_eq!(suggestions[0], "Cl");
+        }
+        other => anyhow::bail!("expected an invalid element, got {:?}", other),
+    }
+    match parser.parse_formula("NaCL") {
+        Err(ChemParseError::InvalidFormula(_, symbol, suggestions)) => {
+            assert_eq!(symbol, "L");
+            assert_eq!(suggestions[0], "Cl");
+        }
+        other => anyhow::bail!("expected an invalid formula, got {:?}", other),
+    }
+    match parser.parse_formula("Nx2O") {
+        Err(error @ ChemParseError::InvalidFormula(_, _, _)) => {
+            let message = error.to_string();
+            assert!(message.contains("did you mean N, Na, "), "{}", message);
+            assert!(message.contains("Nb"), "{}", message);
+        }
+        other => anyhow::bail!("expected an invalid formula, got {:?}", other),
+    }
+    assert_eq!(
+        parser.get_table().suggest_symbols("Qq"),
+        Vec::<String>::new()
+    );
     Ok(())
 }
 
@@ -69,3 +158,685 @@ fn test_parse_equation_unbalanced() -> anyhow::Result<()> {
     assert!(!equation.check_equation());
     Ok(())
 }
+
+#[test]
+fn test_check_atom_balance() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert!(parser
+        .parse_equation("2H2 + O2 -> 2H2O")?
+        .check_atom_balance(false));
+    assert!(!parser
+        .parse_equation("H2 + O2 -> H2O")?
+        .check_atom_balance(false));
+    Ok(())
+}
+
+#[test]
+fn test_check_atom_balance_isotope_aware() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let labelled = parser.parse_formula("CO2")?;
+    let mut labelled_elements = BTreeMap::new();
+    labelled_elements.insert(String::from("13C"), 1);
+    labelled_elements.insert(String::from("O"), 2);
+    let labelled = Formula {
+        formula: String::from("[13C]O2"),
+        elements: labelled_elements,
+        ..labelled
+    };
+    let plain = parser.parse_formula("CO2")?;
+
+    let equation = Equation::new(
+        String::from("[13C]O2 -> CO2"),
+        BTreeMap::from([(String::from("[13C]O2"), 1)]),
+        BTreeMap::from([(String::from("CO2"), 1)]),
+        BTreeMap::from([(String::from("[13C]O2"), labelled)]),
+        BTreeMap::from([(String::from("CO2"), plain)]),
+    );
+
+    assert!(equation.check_atom_balance(false));
+    assert!(!equation.check_atom_balance(true));
+    Ok(())
+}
+
+#[test]
+fn test_deuterated_formula() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let water = parser.parse_formula("H2O")?;
+    let heavy_water = water.deuterated(parser.get_table()).unwrap();
+
+    assert_eq!(heavy_water.formula, "D2O");
+    assert_eq!(heavy_water.elements.get("H"), None);
+    assert_eq!(heavy_water.elements["D"], 2);
+    assert!((heavy_water.mass - 20.027).abs() < 0.001);
+
+    let mercury_hydride = parser
+        .parse_formula("HgH2")?
+        .deuterated(parser.get_table())
+        .unwrap();
+    assert_eq!(mercury_hydride.formula, "HgD2");
+    Ok(())
+}
+
+#[test]
+fn test_exchange_hydrogens() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let ethanol = parser.parse_formula("C2H6O")?;
+
+    let labelled = ethanol.exchange_h(parser.get_table(), 1).unwrap();
+    assert_eq!(labelled.formula, "C2H5DO");
+    assert_eq!(labelled.elements["H"], 5);
+    assert_eq!(labelled.elements["D"], 1);
+    assert!(labelled.mass > ethanol.mass);
+
+    let parsed = parser.parse_formula(&labelled.formula)?;
+    assert_eq!(parsed.elements, labelled.elements);
+    assert!((parsed.mass - labelled.mass).abs() < 1e-9);
+
+    assert!(ethanol.exchange_h(parser.get_table(), 7).is_none());
+    Ok(())
+}
+
+#[test]
+fn test_parse_equation_arrow_kind() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert_eq!(
+        parser.parse_equation("2H2 + O2 -> 2H2O")?.arrow,
+        Arrow::Irreversible
+    );
+    assert_eq!(
+        parser.parse_equation("N2 + 3H2 ⇌ 2NH3")?.arrow,
+        Arrow::Reversible
+    );
+    assert_eq!(
+        parser.parse_equation("2H2 + O2 = 2H2O")?.arrow,
+        Arrow::Equality
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_equation_conditions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("N2 + 3H2 ->[Fe, 450°C] 2NH3")?;
+
+    assert_eq!(equation.conditions, vec!["Fe", "450°C"]);
+    assert_eq!(equation.catalysts, vec!["Fe"]);
+    assert!(!equation.reactants.contains_key("Fe"));
+    assert!(equation.check_equation());
+
+    let heated = parser.parse_equation("CaCO3 -> CaO + CO2 (Δ)")?;
+    assert_eq!(heated.conditions, vec!["Δ"]);
+    assert!(heated.catalysts.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_parse_formula_with_isotopes() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let labelled_methane = parser.parse_formula("[13C]H4")?;
+    assert_eq!(labelled_methane.elements["13C"], 1);
+    assert_eq!(labelled_methane.elements["H"], 4);
+    assert!((labelled_methane.mass - 17.0353).abs() < 0.001);
+
+    let uranium = parser.parse_formula("^235UF6")?;
+    assert_eq!(uranium.elements["235U"], 1);
+    assert_eq!(parser.parse_formula("U-235F6")?.elements, uranium.elements);
+
+    let heavy_water = parser.parse_formula("D2O")?;
+    assert_eq!(heavy_water.elements["D"], 2);
+    assert!(
+        (heavy_water.mass
+            - parser
+                .parse_formula("H2O")?
+                .deuterated(parser.get_table())
+                .unwrap()
+                .mass)
+            .abs()
+            < 1e-6
+    );
+    Ok(())
+}
+
+#[test]
+fn test_parse_formula_unknown_isotope() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let result = parser.parse_formula("[99C]O2");
+    assert!(matches!(result, Err(ChemParseError::InvalidIsotope(_))));
+    Ok(())
+}
+
+#[test]
+fn test_isotope_aware_balance_from_parsed_equation() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("[13C]H4 + 2O2 -> CO2 + 2H2O")?;
+    assert!(equation.check_atom_balance(false));
+    assert!(!equation.check_atom_balance(true));
+    Ok(())
+}
+
+#[test]
+fn test_parse_coordination_complex() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let ferrocyanide = parser.parse_formula("K4[Fe(CN)6]")?;
+    assert_eq!(ferrocyanide.elements["K"], 4);
+    assert_eq!(ferrocyanide.elements["Fe"], 1);
+    assert_eq!(ferrocyanide.elements["C"], 6);
+    assert_eq!(ferrocyanide.elements["N"], 6);
+    assert_eq!(ferrocyanide.charge, 0);
+
+    let ion = parser.parse_formula("[Fe(CN)6]3-")?;
+    assert_eq!(ion.elements["C"], 6);
+    assert_eq!(ion.charge, -3);
+
+    let complex_salt = parser.parse_formula("[Cu(NH3)4]SO4")?;
+    assert_eq!(complex_salt.elements["N"], 4);
+    assert_eq!(complex_salt.elements["H"], 12);
+    assert_eq!(complex_salt.elements["O"], 4);
+    Ok(())
+}
+
+#[test]
+fn test_parse_ion_charges() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert_eq!(parser.parse_formula("SO4^2-")?.charge, -2);
+    assert_eq!(parser.parse_formula("Fe^3+")?.charge, 3);
+    assert_eq!(parser.parse_formula("NH4+")?.charge, 1);
+    assert_eq!(parser.parse_formula("Cl-")?.charge, -1);
+
+    let equation = parser.parse_equation("Ag+ + Cl- -> AgCl")?;
+    assert_eq!(equation.reactants["Ag+"], 1);
+    assert_eq!(equation.reactants["Cl-"], 1);
+
+    // A sign before a space and another species separates species rather than charging one.
+    let spaced = parser.parse_equation("2H2+ O2 -> 2H2O")?;
+    assert_eq!(spaced.reactants["H2"], 2);
+    assert_eq!(spaced.reactants["O2"], 1);
+    assert_eq!(spaced.reactant_terms[0].formula.charge, 0);
+    assert!(spaced.check_equation());
+    let spaced = parser.parse_equation("Na+ Cl -> NaCl")?;
+    assert_eq!(spaced.reactant_terms.len(), 2);
+    Ok(())
+}
+
+#[test]
+fn test_parse_formula_with_nested_groups() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let two_levels = parser.parse_formula("Ca(Al2(SO4)3)2")?;
+    assert_eq!(two_levels.elements["Ca"], 1);
+    assert_eq!(two_levels.elements["Al"], 4);
+    assert_eq!(two_levels.elements["S"], 6);
+    assert_eq!(two_levels.elements["O"], 24);
+
+    let three_levels = parser.parse_formula("K((CH3)2(C(OH)2)3)2")?;
+    assert_eq!(three_levels.elements["K"], 1);
+    assert_eq!(three_levels.elements["C"], 10);
+    assert_eq!(three_levels.elements["H"], 24);
+    assert_eq!(three_levels.elements["O"], 12);
+
+    let four_levels = parser.parse_formula("Mg(Fe(Al(Si(O2)2)2)3)2")?;
+    assert_eq!(four_levels.elements["Fe"], 2);
+    assert_eq!(four_levels.elements["Al"], 6);
+    assert_eq!(four_levels.elements["Si"], 12);
+    assert_eq!(four_levels.elements["O"], 48);
+    Ok(())
+}
+
+#[test]
+fn test_parse_long_formula_of_groups() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let peptide = parser.parse_formula(&format!("{}H2O", "(C2H3NO)(C3H5NO)2".repeat(500)))?;
+    assert_eq!(peptide.elements["C"], 4000);
+    assert_eq!(peptide.elements["H"], 6502);
+    assert_eq!(peptide.elements["N"], 1500);
+    assert_eq!(peptide.elements["O"], 1501);
+    Ok(())
+}
+
+#[test]
+fn test_parse_formula_nested_overflow() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let result = parser.parse_formula("(((H200)1000)1000)1000");
+    assert!(matches!(result, Err(ChemParseError::InvalidIndexFormat(_))));
+    Ok(())
+}
+
+#[test]
+fn test_parse_formula_with_large_counts() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let protein = parser.parse_formula("C2952H4664N812O832S8")?;
+    assert_eq!(protein.elements["C"], 2952);
+    assert_eq!(protein.elements["H"], 4664);
+    assert!(protein.mass > 65_000.0);
+
+    let polymer = parser.parse_formula("(C2H4)5000")?;
+    assert_eq!(polymer.elements["C"], 10_000);
+
+    let equation = parser.parse_equation("300H2 + 150O2 -> 300H2O")?;
+    assert_eq!(equation.reactants["H2"], 300);
+    assert!(equation.check_equation());
+    Ok(())
+}
+
+#[test]
+fn test_parse_unicode_scripts() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    assert_eq!(normalize_scripts("SO₄²⁻"), "SO4^2-");
+    assert_eq!(normalize_scripts("¹³CH₄"), "^13CH4");
+
+    for (unicode, ascii) in [
+        ("H₂O", "H2O"),
+        ("CO₂", "CO2"),
+        ("SO₄²⁻", "SO4^2-"),
+        ("Fe³⁺", "Fe^3+"),
+    ] {
+        let unicode_formula = parser.parse_formula(unicode)?;
+        let ascii_formula = parser.parse_formula(ascii)?;
+        assert_eq!(unicode_formula.formula, ascii_formula.formula);
+        assert_eq!(unicode_formula.elements, ascii_formula.elements);
+        assert_eq!(unicode_formula.charge, ascii_formula.charge);
+    }
+
+    let equation = parser.parse_equation("2H₂ + O₂ -> 2H₂O")?;
+    assert_eq!(equation.products.get("H2O"), Some(&2));
+    assert!(equation.check_equation());
+    Ok(())
+}
+
+#[test]
+fn test_parse_equation_term_order_and_duplicates() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let equation = parser.parse_equation("O2 + 2H2 -> 2H2O")?;
+    let reactants: Vec<(u32, &str)> = equation
+        .reactant_terms
+        .iter()
+        .map(|term| (term.coefficient, term.formula.formula.as_str()))
+        .collect();
+    assert_eq!(reactants, vec![(1, "O2"), (2, "H2")]);
+    assert_eq!(equation.to_parseable_string(), "O2 + 2H2 -> 2H2O");
+
+    let duplicated = parser.parse_equation("H2 + H2 + O2 -> H2O + H2O")?;
+    assert_eq!(duplicated.reactant_terms.len(), 3);
+    assert_eq!(duplicated.product_terms.len(), 2);
+    assert_eq!(duplicated.reactants["H2"], 2);
+    assert_eq!(duplicated.products["H2O"], 2);
+    assert!(duplicated.check_equation());
+    assert!(duplicated.check_atom_balance(false));
+    Ok(())
+}
+
+#[test]
+fn test_formula_composition_equality() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let acetic_acid = parser.parse_formula("CH3COOH")?;
+    let molecular = parser.parse_formula("C2H4O2")?;
+    let methyl_formate = parser.parse_formula("HCOOCH3")?;
+
+    assert!(acetic_acid.same_composition(&molecular));
+    assert_eq!(acetic_acid, molecular);
+    assert_eq!(acetic_acid, methyl_formate);
+    assert_ne!(acetic_acid, parser.parse_formula("C2H6O")?);
+    assert_ne!(
+        parser.parse_formula("SO4")?,
+        parser.parse_formula("SO4^2-")?
+    );
+
+    let unique: std::collections::HashSet<_> = [acetic_acid, molecular, methyl_formate]
+        .into_iter()
+        .collect();
+    assert_eq!(unique.len(), 1);
+    Ok(())
+}
+
+#[test]
+fn test_canonical_hill_notation() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let canonical = |formula: &str| -> anyhow::Result<String> {
+        Ok(parser.parse_formula(formula)?.canonical())
+    };
+
+    assert_eq!(canonical("HO2C2H3")?, "C2H4O2");
+    assert_eq!(canonical("CH3COOH")?, "C2H4O2");
+    assert_eq!(canonical("H2SO4")?, "H2O4S");
+    assert_eq!(canonical("NaCl")?, "ClNa");
+    assert_eq!(canonical("SO4^2-")?, "O4S^2-");
+    assert_eq!(canonical("NH4+")?, "H4N^+");
+    assert_eq!(canonical("[13C]H4")?, "[13C]H4");
+    assert_eq!(canonical("CH3OD")?, "CH3DO");
+
+    let reparsed = parser.parse_formula(&canonical("[Fe(CN)6]3-")?)?;
+    assert_eq!(reparsed, parser.parse_formula("[Fe(CN)6]3-")?);
+    Ok(())
+}
+
+#[test]
+fn test_formula_arithmetic() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let water = parser.parse_formula("H2O")?;
+    let glucose = 6 * parser.parse_formula("CH2O")?;
+
+    assert_eq!(glucose.formula, "C6H12O6");
+    assert_eq!(glucose, parser.parse_formula("C6H12O6")?);
+    assert!((glucose.mass - parser.parse_formula("C6H12O6")?.mass).abs() < 1e-9);
+
+    let hydrate = parser.parse_formula("CuSO4")? + water.clone() * 5;
+    assert_eq!(hydrate, parser.parse_formula("CuSO4·5H2O")?);
+
+    let anhydride = (glucose.clone() - water.clone()).ok_or_else(|| anyhow::anyhow!("no water"))?;
+    assert_eq!(anhydride.formula, "C6H10O5");
+    assert!((anhydride.mass + water.mass - glucose.mass).abs() < 1e-9);
+    assert!((water.clone() - glucose).is_none());
+
+    let sulfate = parser.parse_formula("SO4^2-")? * 2;
+    assert_eq!(sulfate.charge, -4);
+    assert_eq!(sulfate.formula, "O8S2^4-");
+    assert!(water.checked_mul(u32::MAX).is_none());
+    let saturated = water.clone() * u32::MAX;
+    assert_eq!(saturated.elements["H"], u32::MAX);
+    assert_eq!(saturated.elements["O"], u32::MAX);
+    assert_eq!((saturated.clone() + water).elements["H"], u32::MAX);
+    assert_eq!((sulfate * u32::MAX).charge, i32::MIN);
+    Ok(())
+}
+
+#[test]
+fn test_empirical_formula() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let glucose = parser.parse_formula("C6H12O6")?;
+    let empirical = glucose.empirical();
+    assert_eq!(empirical.formula, "CH2O");
+    assert!((empirical.mass * 6.0 - glucose.mass).abs() < 1e-9);
+
+    assert_eq!(parser.parse_formula("H2O")?.empirical().formula, "H2O");
+    assert_eq!(parser.parse_formula("O2^2-")?.empirical().formula, "O^-");
+    assert_eq!(
+        parser.parse_formula("SO4^2-")?.empirical().formula,
+        "O4S^2-"
+    );
+    Ok(())
+}
+
+#[test]
+fn test_molecular_from_empirical() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let empirical = parser.parse_formula("CH2O")?;
+    let glucose = parser.molecular_from_empirical(&empirical, 180.16, 0.5)?;
+    assert_eq!(glucose.formula, "C6H12O6");
+    assert!(parser
+        .molecular_from_empirical(&empirical, 170.0, 0.5)
+        .is_err());
+
+    for (target_mass, tolerance) in [
+        (f64::NAN, 0.5),
+        (f64::INFINITY, 0.5),
+        (-180.16, 0.5),
+        (0.0, 0.5),
+        (180.16, f64::NAN),
+        (180.16, f64::INFINITY),
+        (180.16, -0.5),
+    ] {
+        assert!(matches!(
+            parser.molecular_from_empirical(&empirical, target_mass, tolerance),
+            Err(ChemParseError::EvaluationError(..))
+        ));
+    }
+    assert!(matches!(
+        parser.molecular_from_empirical(&empirical, 1e300, f64::MAX),
+        Err(ChemParseError::Overflow(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_degrees_of_unsaturation() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert_eq!(parser.parse_formula("C6H6")?.degrees_of_unsaturation(), 4.0);
+    assert_eq!(
+        parser.parse_formula("C2H5Cl")?.degrees_of_unsaturation(),
+        0.0
+    );
+    assert_eq!(
+        parser.parse_formula("C5H5N")?.degrees_of_unsaturation(),
+        4.0
+    );
+    assert_eq!(
+        parser.parse_formula("C6H12O6")?.degrees_of_unsaturation(),
+        1.0
+    );
+    assert_eq!(parser.parse_formula("C6D6")?.degrees_of_unsaturation(), 4.0);
+    assert_eq!(parser.parse_formula("CH3")?.degrees_of_unsaturation(), 0.5);
+    Ok(())
+}
+
+#[test]
+fn test_formula_from_counts() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+
+    let glucose = Formula::from_counts(&[("O", 6), ("H", 12), ("C", 6)], table)?;
+    assert_eq!(glucose.formula, "C6H12O6");
+    assert_eq!(glucose, parser.parse_formula("C6H12O6")?);
+    assert!((glucose.mass - parser.parse_formula("C6H12O6")?.mass).abs() < 1e-9);
+
+    let labelled =
+        Formula::from_counts(&[("C", 1), ("13C", 1), ("H", 3), ("H", 3), ("N", 0)], table)?;
+    assert_eq!(labelled.formula, "C[13C]H6");
+
+    assert!(matches!(
+        Formula::from_counts(&[("Xx", 1)], table),
+        Err(ChemParseError::InvalidElement(_, _))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_equation_states() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let equation = parser.parse_equation("2H2(g) + O2(g) -> 2H2O(l)")?;
+    assert_eq!(equation.reactant_terms[0].state, Some(State::Gas));
+    assert_eq!(equation.product_terms[0].state, Some(State::Liquid));
+    assert_eq!(equation.product_terms[0].formula.formula, "H2O");
+    assert_eq!(equation.to_parseable_string(), "2H2(g) + O2(g) -> 2H2O(l)");
+
+    let ions = parser.parse_equation("Na+(aq) + Cl^-(aq) -> NaCl(s)")?;
+    assert_eq!(ions.reactant_terms[0].formula.charge, 1);
+    assert_eq!(ions.reactant_terms[0].state, Some(State::Aqueous));
+    assert_eq!(ions.product_terms[0].state, Some(State::Solid));
+    assert!(parser
+        .parse_equation("H2 + O2 -> H2O")
+        .is_ok_and(|equation| equation
+            .reactant_terms
+            .iter()
+            .all(|term| term.state.is_none())));
+    Ok(())
+}
+
+#[test]
+fn test_formula_compare() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let acetic_acid = parser.parse_formula("CH3COOH")?;
+    let methyl_formate = parser.parse_formula("HCOOCH3")?;
+    let isomers = acetic_acid.compare(&methyl_formate);
+    assert!(isomers.same_composition);
+    assert!(isomers.differences.is_empty());
+    assert!(isomers.mass_difference.abs() < 1e-9);
+
+    let ethanol = parser.parse_formula("C2H5OH")?;
+    let typo = ethanol.compare(&parser.parse_formula("C2H6OH")?);
+    assert!(!typo.same_composition);
+    assert_eq!(typo.differences.len(), 1);
+    assert_eq!(typo.differences[0].element, "H");
+    assert_eq!(
+        (typo.differences[0].first, typo.differences[0].second),
+        (6, 7)
+    );
+    assert!((typo.mass_difference - parser.parse_formula("H")?.mass).abs() < 1e-9);
+
+    let ion = parser
+        .parse_formula("NH3")?
+        .compare(&parser.parse_formula("NH4^+")?);
+    assert_eq!(ion.charge_difference, 1);
+
+    let anion = parser.parse_formula("SO4^2-")? * u32::MAX;
+    let cation = parser.parse_formula("NH4^+")? * u32::MAX;
+    assert_eq!(
+        anion.compare(&cation).charge_difference,
+        i64::from(i32::MAX) - i64::from(i32::MIN)
+    );
+    Ok(())
+}
+
+#[test]
+fn test_equation_normalize() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("4H2O -> 2O2 + 4H2")?;
+    assert!(!equation.is_normalized());
+    let normalized = equation.normalize();
+    assert_eq!(normalized.equation, "2H2O -> 2H2 + O2");
+    assert!(normalized.is_normalized());
+    assert_eq!(normalized.products["O2"], 1);
+
+    let first = parser.parse_equation("CH3COOH + NaOH -> CH3COONa + H2O")?;
+    let second = parser.parse_equation("2NaOH + 2C2H4O2 -> 2H2O + 2C2H3NaO2")?;
+    assert_eq!(first.normalize().equation, second.normalize().equation);
+
+    let merged = parser.parse_equation("H2 + H2 + O2 -> 2H2O")?.normalize();
+    assert_eq!(merged.equation, "2H2 + O2 -> 2H2O");
+    let states = parser.parse_equation("2H2O(l) -> 2H2O(g)")?.normalize();
+    assert_eq!(states.equation, "H2O(l) -> H2O(g)");
+    let ions = parser.parse_equation("Fe^3+ + e^- -> Fe^2+")?;
+    assert!(ions.is_normalized());
+    Ok(())
+}
+
+#[test]
+fn test_fractional_coefficients() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("H2 + 1/2O2 -> H2O")?;
+    assert_eq!(equation.denominator, 2);
+    assert!(equation.check_atom_balance(false));
+    let oxygen = &equation.reactant_terms[1];
+    assert_eq!(equation.coefficient(oxygen).to_string(), "1/2");
+    assert_eq!(equation.coefficient(oxygen).value(), 0.5);
+    assert_eq!(equation.to_parseable_string(), "H2 + 1/2O2 -> H2O");
+
+    let integer = equation.to_integer_coefficients();
+    assert_eq!(integer.equation, "2H2 + O2 -> 2H2O");
+    assert_eq!(integer.denominator, 1);
+    assert_eq!(integer.products["H2O"], 2);
+
+    let decimal = parser.parse_equation("0.5N2 + 1.5H2 -> NH3")?;
+    assert_eq!(
+        decimal.to_integer_coefficients().equation,
+        "N2 + 3H2 -> 2NH3"
+    );
+    let mixed = parser.parse_equation("1/3Fe2O3(s) + 1/2C(s) -> 2/3Fe(s) + 1/2CO2(g)")?;
+    assert_eq!(mixed.denominator, 6);
+    assert_eq!(
+        mixed.to_integer_coefficients().equation,
+        "2Fe2O3(s) + 3C(s) -> 4Fe(s) + 3CO2(g)"
+    );
+    assert_eq!(
+        parser
+            .parse_equation(&mixed.to_parseable_string())?
+            .reactants,
+        mixed.reactants
+    );
+
+    assert_eq!(
+        "0.25".parse::<Coefficient>()?,
+        Coefficient::new(1, 4).unwrap()
+    );
+    assert_eq!("4/2".parse::<Coefficient>()?.to_string(), "2");
+    assert!("0.0".parse::<Coefficient>().is_err());
+    assert!(matches!(
+        parser.parse_equation("0.0H2 + O2 -> H2O"),
+        Err(ChemParseError::InvalidCoefficientFormat(_))
+    ));
+    assert!(matches!(
+        parser.parse_equation("1/4294967295H2 + 1/4294967294O2 -> H2O"),
+        Err(ChemParseError::Overflow(_))
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_fractional_coefficients_as_written() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("H2 + 1/2O2 -> H2O")?;
+    assert!(!equation.is_normalized());
+    assert_eq!(equation.normalize().equation, "2H2 + O2 -> 2H2O");
+    assert_eq!(
+        equation.normalize().equation,
+        parser
+            .parse_equation("2H2 + O2 -> 2H2O")?
+            .normalize()
+            .equation
+    );
+
+    let analysis = equation.limiting_reactant(&[("H2", 4.0), ("O2", 1.0)])?;
+    assert_eq!(analysis.reactant, "O2");
+    assert!((analysis.extent - 2.0).abs() < 1e-12);
+    assert!((analysis.products[0].moles - 2.0).abs() < 1e-12);
+    assert!((equation.moles_of("H2O", ("O2", 1.0))? - 2.0).abs() < 1e-12);
+
+    let unbalanced = parser.parse_equation("H2 + 1/2O2 -> H2O2")?;
+    let diagnosis = unbalanced.diagnose();
+    assert_eq!(diagnosis.equation, "2H2 + O2 -> 2H2O2");
+    let oxygen = diagnosis.imbalances().next().unwrap();
+    assert_eq!((oxygen.reactants, oxygen.products), (2, 4));
+    assert!(unbalanced.validate().is_err());
+    assert!(equation.validate().is_ok());
+
+    let rules = ValidationRules {
+        require_lowest_terms: true,
+        ..ValidationRules::default()
+    };
+    assert!(parser
+        .validate_equation("1/2N2 + 3/2H2 -> NH3", &rules)
+        .is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_energy_terms() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let photolysis = parser.parse_equation("O3 + hν -> O2 + O")?;
+    let photon = &photolysis.reactant_terms[1].formula;
+    assert_eq!(photon.special_species(), Some(SpecialSpecies::Photon));
+    assert!(photon.is_energy());
+    assert_eq!(photolysis.reactants["hν"], 1);
+    assert!(photolysis.check_atom_balance(true));
+    assert!(photolysis.check_charge_balance());
+    assert!(photolysis.check_equation());
+    assert_eq!(
+        parser.parse_equation("O3 + hv -> O2 + O")?.reactants,
+        photolysis.reactants
+    );
+
+    let decomposition = parser.parse_equation("CaCO3 + Δ -> CaO + CO2")?;
+    assert_eq!(
+        decomposition.to_parseable_string(),
+        "CaCO3 + heat -> CaO + CO2"
+    );
+    assert_eq!(
+        parser.parse_equation("2H2 + O2 -> 2H2O + heat")?.products["heat"],
+        1
+    );
+    assert_eq!(
+        parser.parse_equation("Cu^2+ + 2e- -> Cu")?.reactant_terms[1]
+            .formula
+            .special_species(),
+        Some(SpecialSpecies::Electron)
+    );
+    assert_eq!(parser.parse_formula("H2O")?.special_species(), None);
+    assert!(parser.parse_equation("hydrogen -> H").is_err());
+    Ok(())
+}
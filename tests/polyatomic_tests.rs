@@ -0,0 +1,66 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::element::PeriodicTable;
+use chemistry_parser::polyatomic::polyatomic_ions;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_identify_ions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let ions = |formula: &str| -> anyhow::Result<Option<Vec<(u32, String)>>> {
+        Ok(parser
+            .parse_formula(formula)?
+            .identify_ions(&parser)?
+            .map(|ions| {
+                ions.into_iter()
+                    .map(|(count, ion)| (count, ion.formula))
+                    .collect()
+            }))
+    };
+    let pair = |cation: (u32, &str), anion: (u32, &str)| {
+        Some(vec![
+            (cation.0, cation.1.to_string()),
+            (anion.0, anion.1.to_string()),
+        ])
+    };
+
+    assert_eq!(ions("(NH4)2SO4")?, pair((2, "NH4^+"), (1, "SO4^2-")));
+    assert_eq!(ions("Ca3(PO4)2")?, pair((3, "Ca^2+"), (2, "PO4^3-")));
+    assert_eq!(ions("NaHCO3")?, pair((1, "Na^+"), (1, "HCO3^-")));
+    assert_eq!(ions("KMnO4")?, pair((1, "K^+"), (1, "MnO4^-")));
+    assert_eq!(ions("BaO2")?, pair((1, "Ba^2+"), (1, "O2^2-")));
+    assert_eq!(ions("PbO2")?, pair((1, "Pb^4+"), (2, "O^2-")));
+    assert_eq!(ions("H2SO4")?, pair((2, "H^+"), (1, "SO4^2-")));
+    assert_eq!(ions("NO3^-")?, Some(vec![(1, String::from("NO3^-"))]));
+    assert_eq!(ions("CO2")?, None);
+
+    Ok(())
+}
+
+#[test]
+fn test_polyatomic_ions() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let ions = polyatomic_ions(&parser)?;
+
+    assert!(ions.contains(&("NH4^+", "ammonium")));
+    assert!(ions.contains(&("SO4^2-", "sulfate")));
+    assert!(!ions.iter().any(|(formula, _)| *formula == "Cl^-"));
+
+    Ok(())
+}
+
+#[test]
+fn test_identify_ions_uses_parser_table() -> anyhow::Result<()> {
+    let table = PeriodicTable::from_csv_str(concat!(
+        "name,symbol,atomic_number,atomic_mass,density,group,melting_point,boiling_point\n",
+        "Hydrogen,H,1,1.008,8.2e-05,1,,\n",
+        "Chlorine,Cl,17,35.45,0.0032,17,,\n"
+    ))
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let parser = ChemParser::with_table(table);
+    let hydrogen_chloride = parser.parse_formula("HCl")?;
+
+    assert!(hydrogen_chloride.identify_ions(&parser).is_err());
+    assert!(polyatomic_ions(&parser).is_err());
+    Ok(())
+}
@@ -0,0 +1,46 @@
+#![cfg(any(feature = "datasets", not(feature = "std")))]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_repeat_unit() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let polyethylene = parser.parse_formula("(C2H4)n")?;
+    let ethylene = parser.parse_formula("C2H4")?;
+
+    assert!(polyethylene.repeat_unit.as_deref() == Some(&ethylene));
+    assert_eq!(polyethylene.mass_per_repeat_unit(), Some(ethylene.mass));
+    assert_eq!(ethylene.mass_per_repeat_unit(), None);
+
+    let chain = polyethylene.with_repeat(100).unwrap();
+    assert_eq!(chain.formula, "(C2H4)100");
+    assert_eq!(chain.elements["C"], 200);
+    assert_eq!(chain.elements["H"], 400);
+    assert!((chain.mass - 100.0 * ethylene.mass).abs() < 1e-9);
+    assert!(chain.repeat_unit.is_none());
+    assert!(polyethylene.with_repeat(0).is_none());
+
+    let capped = parser
+        .parse_formula("CH3(C2H4)nCH3")?
+        .with_repeat(3)
+        .unwrap();
+    assert!(capped.same_composition(&parser.parse_formula("C8H18")?));
+
+    Ok(())
+}
+
+#[test]
+fn test_repeat_unit_errors() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for formula in ["(C2H4)n(CH2)n", "((C2H4)n)2", "NaCl·(H2O)n"] {
+        let error = parser.parse_formula(formula).unwrap_err();
+        assert!(
+            matches!(error, ChemParseError::ParsingError(_, _)),
+            "{}",
+            formula
+        );
+    }
+
+    Ok(())
+}
@@ -0,0 +1,50 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_predict_products() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for (reactants, equation) in [
+        ("Na + Cl2", "2Na + Cl2 -> 2NaCl"),
+        ("O2 + Al", "3O2 + 4Al -> 2Al2O3"),
+        ("H2 + O2", "2H2 + O2 -> 2H2O"),
+        ("CH4 + O2", "CH4 + 2O2 -> CO2 + 2H2O"),
+        ("C2H5OH + O2", "C2H5OH + 3O2 -> 2CO2 + 3H2O"),
+        ("HCl + NaOH", "HCl + NaOH -> NaCl + H2O"),
+        ("Ca(OH)2 + H3PO4", "3Ca(OH)2 + 2H3PO4 -> Ca3(PO4)2 + 6H2O"),
+        ("Zn + CuSO4", "Zn + CuSO4 -> ZnSO4 + Cu"),
+    ] {
+        assert_eq!(
+            parser.predict_products(reactants)?.equation,
+            equation,
+            "{}",
+            reactants
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_cannot_predict() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for reactants in ["Cu + ZnSO4", "NaCl + KBr", "Na + K", "H2O", "N2 + O2 + H2"] {
+        let error = parser.predict_products(reactants).unwrap_err();
+        assert!(
+            matches!(error, ChemParseError::CannotPredict(_, _)),
+            "{}: {}",
+            reactants,
+            error
+        );
+        assert_eq!(error.code(), "E0014");
+    }
+    assert!(matches!(
+        parser.predict_products("Xx + O2"),
+        Err(ChemParseError::InvalidElement(_, _) | ChemParseError::InvalidFormula(..))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,62 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::redox::Medium;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_balance_redox_acidic() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let permanganate = parser.parse_equation("MnO4^- + Fe^2+ -> Mn^2+ + Fe^3+")?;
+    let redox = parser.balance_redox(&permanganate, Medium::Acidic)?;
+    assert_eq!(
+        redox.balanced.equation,
+        "MnO4^- + 5Fe^2+ + 8H^+ -> Mn^2+ + 5Fe^3+ + 4H2O"
+    );
+    assert_eq!(redox.oxidation.equation, "Fe^2+ -> Fe^3+ + e^-");
+    assert_eq!(
+        redox.reduction.equation,
+        "MnO4^- + 8H^+ + 5e^- -> Mn^2+ + 4H2O"
+    );
+
+    let dichromate = parser.parse_equation("Cr2O7^2- + I^- -> Cr^3+ + I2")?;
+    let redox = parser.balance_redox(&dichromate, Medium::Acidic)?;
+    assert_eq!(
+        redox.balanced.equation,
+        "Cr2O7^2- + 6I^- + 14H^+ -> 2Cr^3+ + 3I2 + 7H2O"
+    );
+
+    let copper = parser.parse_equation("Cu + NO3^- -> Cu^2+ + NO")?;
+    let redox = parser.balance_redox(&copper, Medium::Acidic)?;
+    assert_eq!(
+        redox.balanced.equation,
+        "3Cu + 2NO3^- + 8H^+ -> 3Cu^2+ + 2NO + 4H2O"
+    );
+    assert!(redox.balanced.check_equation());
+    Ok(())
+}
+
+#[test]
+fn test_balance_redox_basic() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let equation = parser.parse_equation("MnO4^- + I^- -> MnO2 + I2")?;
+    let redox = parser.balance_redox(&equation, Medium::Basic)?;
+    assert_eq!(
+        redox.balanced.equation,
+        "2MnO4^- + 6I^- + 4H2O -> 2MnO2 + 3I2 + 8OH^-"
+    );
+
+    let peroxide = parser.parse_equation("H2O2 + MnO4^- -> O2 + MnO2")?;
+    assert!(parser.balance_redox(&peroxide, Medium::Basic).is_ok());
+
+    let neutralization = parser.parse_equation("NaOH + HCl -> NaCl + H2O")?;
+    assert!(parser
+        .balance_redox(&neutralization, Medium::Acidic)
+        .is_err());
+    let disproportionation = parser.parse_equation("Cl2 -> Cl^- + ClO3^-")?;
+    assert!(parser
+        .balance_redox(&disproportionation, Medium::Basic)
+        .is_err());
+    Ok(())
+}
@@ -0,0 +1,156 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::element::PeriodicTable;
+use chemistry_parser::render::periodic_grid;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_periodic_grid_layout() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let grid = periodic_grid(parser.get_table(), &[]);
+    let lines: Vec<&str> = grid.lines().collect();
+
+    assert_eq!(lines.len(), 10);
+    assert!(lines[0].starts_with(" H"));
+    assert!(lines[0].ends_with("He"));
+    assert_eq!(lines[0].len(), 17 * 4 + 3);
+    assert!(lines[3].contains(" Fe  Co  Ni "));
+    assert!(lines[8].starts_with("             Ce  Pr"));
+    assert!(lines[9].contains(" Th  Pa  U "));
+    Ok(())
+}
+
+#[test]
+fn test_periodic_grid_elements_without_group() -> anyhow::Result<()> {
+    let table = PeriodicTable::from_csv_str(concat!(
+        "name,symbol,atomic_number,atomic_mass,density,group,melting_point,boiling_point\n",
+        "Lanthanum,La,57,138.905,6.15,,,\n",
+        "Cerium,Ce,58,140.116,6.77,,,\n",
+        "Actinium,Ac,89,227.0,10.07,,,\n",
+    ))
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let grid = periodic_grid(&table, &[]);
+    let lines: Vec<&str> = grid.lines().collect();
+    assert_eq!(lines[5].trim(), "La");
+    assert_eq!(lines[6].trim(), "Ac");
+    assert_eq!(lines[8].trim(), "Ce");
+    Ok(())
+}
+
+#[test]
+fn test_periodic_grid_highlight() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let grid = periodic_grid(parser.get_table(), &["H", "S", "O"]);
+
+    assert!(grid.starts_with("[H]"));
+    assert!(grid.contains("[O]"));
+    assert!(grid.contains("[S]"));
+    assert!(!grid.contains("[N]"));
+    Ok(())
+}
+
+#[test]
+fn test_pretty_formulas() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let pretty = |formula: &str| -> anyhow::Result<String> {
+        Ok(parser.parse_formula(formula)?.to_pretty_string())
+    };
+
+    assert_eq!(pretty("H2SO4")?, "H₂SO₄");
+    assert_eq!(pretty("Al2(SO4)3")?, "Al₂(SO₄)₃");
+    assert_eq!(pretty("SO4^2-")?, "SO₄²⁻");
+    assert_eq!(pretty("[Fe(CN)6]3-")?, "[Fe(CN)₆]³⁻");
+    assert_eq!(pretty("Na+")?, "Na⁺");
+    assert_eq!(pretty("^235U")?, "²³⁵U");
+    assert_eq!(pretty("U-235")?, "²³⁵U");
+    assert_eq!(pretty("CuSO4*5H2O")?, "CuSO₄·5H₂O");
+    assert_eq!(pretty("H₂O")?, "H₂O");
+    Ok(())
+}
+
+#[test]
+fn test_pretty_equations() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    assert_eq!(equation.to_pretty_string(), "2H₂ + O₂ → 2H₂O");
+
+    let equation = parser.parse_equation("N2 + 3H2 <=> 2NH3")?;
+    assert_eq!(equation.to_pretty_string(), "N₂ + 3H₂ ⇌ 2NH₃");
+
+    let equation = parser.parse_equation("Fe^3+ + e^- -> Fe^2+")?;
+    assert_eq!(equation.to_pretty_string(), "Fe³⁺ + e⁻ → Fe²⁺");
+
+    let equation = parser.parse_equation("H2 + 1/2O2 -> H2O")?;
+    assert_eq!(equation.to_pretty_string(), "H₂ + 1/2O₂ → H₂O");
+    Ok(())
+}
+
+#[test]
+fn test_latex_output() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let formula = parser.parse_formula("SO4^2-")?;
+    assert_eq!(formula.to_latex(), r"\ce{SO4^{2-}}");
+    assert_eq!(formula.to_latex_math(), r"\mathrm{SO}_{4}^{2-}");
+
+    let formula = parser.parse_formula("CuSO4·5H2O")?;
+    assert_eq!(formula.to_latex(), r"\ce{CuSO4*5H2O}");
+
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    assert_eq!(equation.to_latex(), r"\ce{2H2 + O2 -> 2H2O}");
+    assert_eq!(
+        equation.to_latex_math(),
+        r"2\,\mathrm{H}_{2} + \mathrm{O}_{2} \rightarrow 2\,\mathrm{H}_{2}\mathrm{O}"
+    );
+
+    let equation = parser.parse_equation("N2 + 3H2 ->[Fe, 450°C] 2NH3")?;
+    assert_eq!(equation.to_latex(), r"\ce{N2 + 3H2 ->[Fe, 450°C] 2NH3}");
+
+    let equation = parser.parse_equation("H2 + 0.5O2 -> H2O")?;
+    assert_eq!(equation.to_latex(), r"\ce{H2 + 1/2O2 -> H2O}");
+    assert!(equation
+        .to_latex_math()
+        .contains(r"\tfrac{1}{2}\,\mathrm{O}_{2}"));
+    assert!(equation
+        .to_mathml()
+        .contains("<mfrac><mn>1</mn><mn>2</mn></mfrac>"));
+    Ok(())
+}
+
+#[test]
+fn test_html_and_mathml_output() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let formula = parser.parse_formula("SO4^2-")?;
+    assert_eq!(formula.to_html(), "SO<sub>4</sub><sup>2−</sup>");
+    assert_eq!(
+        formula.to_mathml(),
+        "<math xmlns=\"http://www.w3.org/1998/Math/MathML\"><mrow>\
+         <mi mathvariant=\"normal\">S</mi>\
+         <msubsup><mi mathvariant=\"normal\">O</mi><mn>4</mn><mrow><mn>2</mn><mo>−</mo></mrow></msubsup>\
+         </mrow></math>"
+    );
+
+    let formula = parser.parse_formula("Al2(SO4)3")?;
+    assert_eq!(
+        formula.to_html(),
+        "Al<sub>2</sub>(SO<sub>4</sub>)<sub>3</sub>"
+    );
+    assert!(formula
+        .to_mathml()
+        .contains("<msub><mo>)</mo><mn>3</mn></msub>"));
+
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    assert_eq!(
+        equation.to_html(),
+        "2H<sub>2</sub> + O<sub>2</sub> → 2H<sub>2</sub>O"
+    );
+    let mathml = equation.to_mathml();
+    assert!(mathml.starts_with("<math xmlns="));
+    assert!(mathml.contains(
+        "<mn>2</mn><mrow><msub><mi mathvariant=\"normal\">H</mi><mn>2</mn></msub></mrow>"
+    ));
+    assert!(mathml.contains("<mo>→</mo>"));
+    Ok(())
+}
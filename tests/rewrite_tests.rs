@@ -0,0 +1,61 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_rewrite_formula() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let deuterate = parser.parse_rewrite_rule("OH -> OD")?;
+    let methanol = deuterate.apply(&parser.parse_formula("CH3OH")?)?;
+    assert_eq!(methanol.formula, "CH3DO");
+    let glycol = deuterate.apply(&parser.parse_formula("HOCH2CH2OH")?)?;
+    assert_eq!(glycol.formula, "C2H4D2O2");
+
+    let acid = parser.parse_rewrite_rule("Na -> H")?;
+    assert_eq!(
+        acid.apply(&parser.parse_formula("Na2SO4")?)?.formula,
+        "H2O4S"
+    );
+    let untouched = acid.apply(&parser.parse_formula("KCl")?)?;
+    assert_eq!(untouched.formula, "KCl");
+
+    let strip = parser.parse_rewrite_rule("Na ->")?;
+    assert_eq!(strip.apply(&parser.parse_formula("NaCl")?)?.formula, "Cl");
+
+    Ok(())
+}
+
+#[test]
+fn test_rewrite_rules() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let rules = parser.parse_rewrite_rules("# strip sodium\nNa -> H\n\nCl -> Br\n")?;
+    assert_eq!(rules.rules.len(), 2);
+
+    let salt = rules.apply_formula(&parser.parse_formula("NaCl")?)?;
+    assert_eq!(salt.formula, "BrH");
+    let equation = parser.parse_equation("2NaCl(aq) -> 2Na(s) + Cl2(g)")?;
+    let rewritten = rules.apply_equation(&equation)?;
+    assert_eq!(rewritten.equation, "2BrH(aq) -> 2H(s) + Br2(g)");
+    assert_eq!(
+        rewritten.reactant_terms[0].state,
+        equation.reactant_terms[0].state
+    );
+
+    Ok(())
+}
+
+#[test]
+fn test_rewrite_rule_errors() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for rule in ["OH OD", "-> H", "Xx -> H"] {
+        assert!(parser.parse_rewrite_rule(rule).is_err(), "{}", rule);
+    }
+    assert!(matches!(
+        parser.parse_rewrite_rules("Na -> H\nNa H"),
+        Err(ChemParseError::ParsingError(_, _))
+    ));
+
+    Ok(())
+}
@@ -0,0 +1,122 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::ast::{EquationAst, FormulaAst};
+use chemistry_parser::{ChemParseError, ChemParser};
+
+/// Inputs found by fuzzing the parse functions that panicked or gave wrong results.
+const REGRESSIONS: [&str; 12] = [
+    "H4294967296",
+    "(H2)4294967295",
+    "(((H)2)65536)65536",
+    "[Fe]99999999999999",
+    "SO4^99999999999-",
+    "SO4^2147483648-",
+    "[Fe]^2147483647+^1+",
+    "CuSO4·4294967296H2O",
+    "^65536U",
+    "U-99999",
+    "(C2H4)n(C2H4)n",
+    "[",
+];
+
+/// Pieces of formulas and equations that random inputs are built from.
+const PIECES: [&str; 32] = [
+    "H",
+    "O",
+    "C",
+    "Na",
+    "Cl",
+    "Fe",
+    "U",
+    "D",
+    "e",
+    "n",
+    "(",
+    ")",
+    "[",
+    "]",
+    "^",
+    "+",
+    "-",
+    "2",
+    "0",
+    "4294967295",
+    "99",
+    "·",
+    "*",
+    ".",
+    " ",
+    "->",
+    "<=>",
+    "=",
+    "(aq)",
+    "(s)",
+    "[13C]",
+    "β",
+];
+
+/// Calls every parse function of the library on an input; any panic fails the test.
+fn parse_everything(parser: &ChemParser, input: &str) {
+    let _ = parser.parse_element(input);
+    let _ = parser.parse_formula(input);
+    let _ = parser.parse_tagged_formula(input);
+    let _ = parser.parse_equation(input);
+    let _ = parser.parse_nuclear_equation(input);
+    let _ = parser.eval(input);
+    let _ = FormulaAst::parse(input);
+    let _ = EquationAst::parse(input);
+}
+
+#[test]
+fn test_regressions_do_not_panic() {
+    let parser = ChemParser::new();
+    for input in REGRESSIONS {
+        parse_everything(&parser, input);
+        parse_everything(&parser, &format!("{0} + {0} -> {0}", input));
+    }
+}
+
+#[test]
+fn test_overflows_are_errors() {
+    let parser = ChemParser::new();
+    assert!(matches!(
+        parser.parse_formula("SO4^99999999999-"),
+        Err(ChemParseError::Overflow(_))
+    ));
+    assert!(matches!(
+        parser.parse_formula("[Fe]^2147483647+^1+"),
+        Err(ChemParseError::Overflow(_))
+    ));
+    assert!(matches!(
+        parser.parse_formula("(H2)4294967295"),
+        Err(ChemParseError::InvalidIndexFormat(_))
+    ));
+    assert!(matches!(
+        parser.parse_equation("4294967295H2 + 4294967295H2 -> H2"),
+        Err(ChemParseError::Overflow(_))
+    ));
+    assert!(matches!(
+        FormulaAst::parse("SO4^99999999999-"),
+        Err(ChemParseError::Overflow(_))
+    ));
+}
+
+#[test]
+fn test_random_inputs_do_not_panic() {
+    let parser = ChemParser::new();
+    // A fixed xorshift generator keeps the inputs the same on every run.
+    let mut state: u64 = 0x9e37_79b9_7f4a_7c15;
+    let mut next = || {
+        state ^= state << 13;
+        state ^= state >> 7;
+        state ^= state << 17;
+        state
+    };
+    for _ in 0..2000 {
+        let length = next() % 12 + 1;
+        let input: String = (0..length)
+            .map(|_| PIECES[(next() % PIECES.len() as u64) as usize])
+            .collect();
+        parse_everything(&parser, &input);
+    }
+}
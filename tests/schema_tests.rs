@@ -0,0 +1,70 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::element::PeriodicTable;
+use chemistry_parser::schema::{capabilities, output_schemas, GRAMMAR_FEATURES};
+use chemistry_parser::ChemParser;
+use serde_json::Value;
+
+/// Checks that every field of a serialized value is described by the properties of its schema.
+fn assert_described(value: &Value, schema: &Value) {
+    let properties = schema["properties"]
+        .as_object()
+        .expect("schema has properties");
+    for key in value.as_object().expect("value is an object").keys() {
+        assert!(properties.contains_key(key), "{} is not described", key);
+    }
+}
+
+#[test]
+fn test_output_schemas() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let schemas = output_schemas();
+
+    for input in ["Fe", "H2SO4", "H2 + O2 -> H2O", "Xy2"] {
+        assert_described(
+            &serde_json::to_value(parser.analyze(input))?,
+            &schemas["analysis"],
+        );
+    }
+    let equation = parser.parse_equation("2H2(g) + O2(g) ->[Pt] 2H2O(l)")?;
+    assert_described(&serde_json::to_value(&equation)?, &schemas["equation"]);
+    assert_described(
+        &serde_json::to_value(&equation.product_terms[0])?,
+        &schemas["equation"]["properties"]["product_terms"]["items"],
+    );
+    assert_eq!(schemas["formula"]["title"], "formula");
+    Ok(())
+}
+
+#[test]
+fn test_capabilities() -> anyhow::Result<()> {
+    let capabilities = capabilities();
+    assert_eq!(capabilities.version, env!("CARGO_PKG_VERSION"));
+    assert!(capabilities.features.contains(&"datasets"));
+    assert!(capabilities.grammar.contains(&"states"));
+    assert!(capabilities.commands.is_empty());
+
+    let json = serde_json::to_value(&capabilities)?;
+    assert!(json["schemas"]["analysis"].is_object());
+    assert!(json.get("commands").is_none());
+    Ok(())
+}
+
+#[test]
+fn test_parser_capabilities() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    assert_eq!(parser.capabilities().grammar, GRAMMAR_FEATURES);
+    assert!(parser.supports("adducts") && parser.supports("isotopes"));
+    assert!(!parser.supports("hydrogen-bonds"));
+
+    let table = PeriodicTable::from_csv_str(concat!(
+        "name,symbol,atomic_number,atomic_mass,density,group,melting_point,boiling_point\n",
+        "Hydrogen,H,1,1.008,8.2e-05,1,,\n"
+    ))
+    .map_err(|e| anyhow::anyhow!("{}", e))?;
+    let parser = ChemParser::with_table(table);
+    assert!(parser.supports("charges"));
+    assert!(!parser.supports("isotopes"));
+    assert!(!parser.capabilities().grammar.contains(&"nuclear-equations"));
+    Ok(())
+}
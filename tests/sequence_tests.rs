@@ -0,0 +1,72 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::ChemParser;
+
+#[test]
+fn parses_reaction_chains() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let sequence = parser.parse_sequence("2C + O2 -> 2CO ->[Pt] 2CO2")?;
+    assert_eq!(sequence.steps.len(), 2);
+    assert_eq!(sequence.steps[0].equation, "2C + O2 -> 2CO");
+    assert_eq!(sequence.steps[1].equation, "2CO ->[Pt] 2CO2");
+    assert_eq!(sequence.steps[1].catalysts, ["Pt"]);
+    Ok(())
+}
+
+#[test]
+fn parses_numbered_mechanisms() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let sequence = parser.parse_sequence(
+        "# chlorination of methane\n\
+         1. Cl2 -> 2Cl\n\
+         2) Cl + CH4 -> HCl + CH3\n\
+         \n\
+         Step 3: CH3 + Cl2 -> CH3Cl + Cl\n",
+    )?;
+    assert_eq!(sequence.steps.len(), 3);
+    assert_eq!(sequence.steps[1].equation, "Cl + CH4 -> HCl + CH3");
+
+    let net = sequence.net_equation()?;
+    assert_eq!(net.equation, "2Cl2 + CH4 -> 2Cl + HCl + CH3Cl");
+
+    assert!(parser.parse_sequence("1. not a reaction").is_err());
+    assert!(parser.parse_sequence("# nothing\n").is_err());
+    Ok(())
+}
+
+#[test]
+fn net_equation_cancels_intermediates() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let sequence = parser.parse_sequence("2C + O2 -> 2CO\n2CO + O2 -> 2CO2")?;
+    let net = sequence.net_equation()?;
+    assert_eq!(net.equation, "2C + 2O2 -> 2CO2");
+    assert!(net.check_atom_balance(false));
+
+    let cycle = parser.parse_sequence("A -> B\nB -> A");
+    assert!(cycle.is_err());
+    let cycle = parser.parse_sequence("O3 -> O2 + O\nO2 + O -> O3")?;
+    assert!(cycle.net_equation().is_err());
+    Ok(())
+}
+
+#[test]
+fn fractional_coefficients_per_step() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let sequence = parser.parse_sequence("C + 1/2O2 -> CO\nCO + 1/2O2 -> CO2")?;
+    assert_eq!(sequence.steps[0].equation, "C + 1/2O2 -> CO");
+    assert_eq!(sequence.steps[0].denominator, 2);
+    let oxygen = &sequence.steps[1].reactant_terms[1];
+    assert_eq!(sequence.steps[1].coefficient(oxygen).value(), 0.5);
+    assert!(sequence
+        .steps
+        .iter()
+        .all(|step| step.check_atom_balance(false)));
+    assert_eq!(sequence.net_equation()?.equation, "C + O2 -> CO2");
+
+    let chain = parser.parse_sequence("N2 + 3H2 -> 2NH3 -> 1/2N2 + 3/2H2")?;
+    assert_eq!(chain.steps[0].denominator, 1);
+    assert_eq!(chain.steps[1].equation, "2NH3 -> 1/2N2 + 3/2H2");
+    let mixed = parser.parse_sequence("H2 + 1/2O2 -> H2O\n2H2O -> 2H2 + O2")?;
+    assert_eq!(mixed.net_equation()?.equation, "H2O -> H2 + 1/2O2");
+    Ok(())
+}
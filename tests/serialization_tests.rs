@@ -0,0 +1,81 @@
+#![cfg(any(feature = "datasets", not(feature = "std")))]
+
+use chemistry_parser::element::{Arrow, Equation, Formula};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_formula_serde_round_trip() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let formula = parser.parse_formula("Al2(SO4)3")?;
+
+    let json = serde_json::to_string(&formula)?;
+    let restored: Formula = serde_json::from_str(&json)?;
+
+    assert_eq!(restored.formula, formula.formula);
+    assert_eq!(restored.elements, formula.elements);
+    assert_eq!(restored.mass, formula.mass);
+    assert_eq!(restored.to_parseable_string(), "Al2(SO4)3");
+    Ok(())
+}
+
+#[test]
+fn test_equation_serde_round_trip() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("N2 + 3H2 <=> 2NH3")?;
+
+    let json = serde_json::to_string(&equation)?;
+    assert!(json.contains("\"arrow\":\"reversible\""));
+
+    let restored: Equation = serde_json::from_str(&json)?;
+    assert_eq!(restored.arrow, Arrow::Reversible);
+    assert_eq!(restored.reactants, equation.reactants);
+    assert_eq!(restored.products, equation.products);
+    assert!(restored.check_equation());
+    Ok(())
+}
+
+#[test]
+fn test_equation_parseable_string_round_trip() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("2H2 + O2 = 2H2O")?;
+
+    let text = equation.to_parseable_string();
+    assert_eq!(text, "2H2 + O2 = 2H2O");
+
+    let reparsed = parser.parse_equation(&text)?;
+    assert_eq!(reparsed.arrow, equation.arrow);
+    assert_eq!(reparsed.reactants, equation.reactants);
+    assert_eq!(reparsed.products, equation.products);
+    Ok(())
+}
+
+#[test]
+fn test_equation_conditions_round_trip() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("N2 + 3H2 ->[Fe, 450°C] 2NH3")?;
+
+    let text = equation.to_parseable_string();
+    assert_eq!(text, "N2 + 3H2 ->[Fe, 450°C] 2NH3");
+    let reparsed = parser.parse_equation(&text)?;
+    assert_eq!(reparsed.conditions, equation.conditions);
+    assert_eq!(reparsed.catalysts, equation.catalysts);
+
+    let restored: Equation = serde_json::from_str(&serde_json::to_string(&equation)?)?;
+    assert_eq!(restored.conditions, equation.conditions);
+    assert_eq!(restored.catalysts, equation.catalysts);
+    Ok(())
+}
+
+#[test]
+fn test_formula_charge_round_trip() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let ion = parser.parse_formula("[Fe(CN)6]3-")?;
+
+    let restored: Formula = serde_json::from_str(&serde_json::to_string(&ion)?)?;
+    assert_eq!(restored.charge, -3);
+
+    let reparsed = parser.parse_formula(&ion.to_parseable_string())?;
+    assert_eq!(reparsed.charge, ion.charge);
+    assert_eq!(reparsed.elements, ion.elements);
+    Ok(())
+}
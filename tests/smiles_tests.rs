@@ -0,0 +1,69 @@
+#![cfg(feature = "smiles")]
+
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_parse_smiles() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for (smiles, formula) in [
+        ("C", "CH4"),
+        ("CCO", "C2H6O"),
+        ("C=C", "C2H4"),
+        ("C#N", "CHN"),
+        ("O=C=O", "CO2"),
+        ("c1ccccc1", "C6H6"),
+        ("c1ccncc1", "C5H5N"),
+        ("c1cc[nH]c1", "C4H5N"),
+        ("c1ccsc1", "C4H4S"),
+        ("CC(=O)Oc1ccccc1C(=O)O", "C9H8O4"),
+        ("C1CCCCC1", "C6H12"),
+        ("C%10CC%10", "C3H6"),
+        ("ClC(Cl)(Cl)Cl", "CCl4"),
+        ("OS(=O)(=O)O", "H2SO4"),
+        ("[Na+].[Cl-]", "ClNa"),
+        ("[13CH4]", "[13C]H4"),
+        ("N[C@@H](C)C(=O)O", "C3H7NO2"),
+    ] {
+        let parsed = parser.parse_smiles(smiles)?;
+        assert!(
+            parsed.same_composition(&parser.parse_formula(formula)?),
+            "{}: {}",
+            smiles,
+            parsed.formula
+        );
+    }
+
+    let ammonium = parser.parse_smiles("[NH4+]")?;
+    assert_eq!(ammonium.charge, 1);
+    assert_eq!(ammonium.formula, parser.parse_formula("NH4^+")?.canonical());
+
+    Ok(())
+}
+
+#[test]
+fn test_parse_smiles_errors() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for smiles in ["C(C", "C1CC", "C)C", "", "CX", "[C"] {
+        let error = parser.parse_smiles(smiles).unwrap_err();
+        assert!(
+            matches!(error, ChemParseError::ParsingError(_, _)),
+            "{}",
+            smiles
+        );
+    }
+    assert!(matches!(
+        parser.parse_smiles("[Xx]").unwrap_err(),
+        ChemParseError::InvalidElement(_, _)
+    ));
+    for smiles in [
+        "[C+2000000000].[C+2000000000]",
+        "[CH4294967295].[CH4294967295]",
+    ] {
+        let error = parser.parse_smiles(smiles).unwrap_err();
+        assert!(matches!(error, ChemParseError::Overflow(_)), "{}", smiles);
+    }
+
+    Ok(())
+}
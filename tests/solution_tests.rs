@@ -0,0 +1,44 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::solution::Solution;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_molarity_and_mass() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let salt = parser.parse_formula("NaCl")?;
+
+    let solution = Solution::from_mass(salt.clone(), salt.mass / 10.0, 0.5)?;
+    assert!((solution.molarity - 0.2).abs() < 1e-12);
+    assert!((solution.moles() - 0.1).abs() < 1e-12);
+    assert!((solution.mass() - salt.mass / 10.0).abs() < 1e-9);
+    assert!(Solution::from_mass(salt, 1.0, 0.0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_dilution() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let stock = Solution::new(parser.parse_formula("HCl")?, 12.0, 0.1);
+
+    let diluted = stock.diluted_to(1.2)?;
+    assert!((diluted.molarity - 1.0).abs() < 1e-12);
+    assert!(stock.diluted_to(0.05).is_err());
+
+    assert!((stock.stock_volume(1.0, 0.6)? - 0.05).abs() < 1e-12);
+    assert!(stock.stock_volume(15.0, 0.6).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_titration() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("H2SO4 + 2NaOH -> Na2SO4 + 2H2O")?;
+    let titrant = Solution::new(parser.parse_formula("NaOH")?, 0.1, 0.025);
+
+    assert!((equation.moles_from_solution("H2SO4", &titrant)? - 0.00125).abs() < 1e-12);
+    assert!((equation.molarity_from_titration("H2SO4", 0.0125, &titrant)? - 0.1).abs() < 1e-12);
+    assert!((equation.volume_to_react("H2SO4", 0.05, &titrant)? - 0.025).abs() < 1e-12);
+    assert!(equation.volume_to_react("HCl", 0.05, &titrant).is_err());
+    Ok(())
+}
@@ -0,0 +1,35 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::element::Formula;
+use chemistry_parser::solve::hydrate_count;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_scale_to_mass() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let (units, glucose) = parser.parse_formula("CH2O")?.scale_to_mass(180.0)?;
+    assert_eq!(units, 6);
+    assert_eq!(glucose.formula, "C6H12O6");
+
+    let (units, _) = parser.parse_formula("C2H4")?.scale_to_mass(28_054.0)?;
+    assert_eq!(units, 1000);
+    assert!(parser.parse_formula("H2O")?.scale_to_mass(5.0).is_err());
+    assert!(Formula::electron().scale_to_mass(5.0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_hydrate_count() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let copper_sulfate = parser.parse_formula("CuSO4")?;
+    assert_eq!(hydrate_count(&parser, &copper_sulfate, 249.7)?, 5);
+    assert_eq!(hydrate_count(&parser, &copper_sulfate, 159.6)?, 0);
+    assert!(hydrate_count(&parser, &copper_sulfate, 120.0).is_err());
+
+    let gypsum = parser.parse_formula("CaSO4*2H2O")?;
+    let anhydrite = parser.parse_formula("CaSO4")?;
+    assert_eq!(hydrate_count(&parser, &anhydrite, gypsum.mass)?, 2);
+    Ok(())
+}
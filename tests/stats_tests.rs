@@ -0,0 +1,57 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::stats::{EquationStats, NotationClusters};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_equation_stats() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut stats = EquationStats::default();
+    for equation in [
+        "2H2 + O2 -> 2H2O",
+        "CH4 + 2O2 -> CO2 + 2H2O",
+        "2H2O2 -> 2H2O + O2",
+    ] {
+        stats.add(&parser.parse_equation(equation)?);
+    }
+    stats.add_failure();
+
+    assert_eq!(stats.equations, 3);
+    assert_eq!(stats.failed, 1);
+    assert_eq!(stats.species(), 6);
+    assert!((stats.average_reactant_terms() - 5.0 / 3.0).abs() < 1e-9);
+    assert!((stats.average_product_terms() - 5.0 / 3.0).abs() < 1e-9);
+    assert_eq!(stats.coefficients.get(&2), Some(&6));
+    assert_eq!(stats.most_common_products(1), vec![("H2O", 3)]);
+    assert_eq!(stats.most_common_reactants(2), vec![("O2", 2), ("CH4", 1)]);
+
+    let unused = stats.unused_elements(parser.get_table());
+    assert_eq!(unused.first(), Some(&"He"));
+    assert!(!unused.contains(&"C"));
+    Ok(())
+}
+
+#[test]
+fn test_notation_clusters() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut clusters = NotationClusters::default();
+    for equation in [
+        "2H2 + O2 -> 2H2O",
+        "NaOH + HCl -> NaCl + H2O",
+        "2Na + 2OH2 -> 2HNaO + H2",
+    ] {
+        clusters.add_equation(&parser.parse_equation(equation)?);
+    }
+
+    let found = clusters.clusters();
+    assert_eq!(found.len(), 2);
+    assert_eq!(found[0].canonical, "H2O");
+    assert_eq!(
+        found[0].notations,
+        vec![(String::from("H2O"), 2), (String::from("OH2"), 1)]
+    );
+    assert_eq!(found[0].suggestion, "H2O");
+    assert_eq!(found[1].canonical, "HNaO");
+    assert_eq!(found[1].suggestion, "HNaO");
+    Ok(())
+}
@@ -0,0 +1,157 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_moles_of() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+
+    assert_eq!(equation.moles_of("H2O", ("O2", 1.5))?, 3.0);
+    assert_eq!(equation.moles_of("H2", ("H2O", 4.0))?, 4.0);
+    assert_eq!(equation.moles_of("O2", ("OH2", 1.0)).ok(), None);
+    assert_eq!(equation.moles_of("O2", ("H2O", 1.0))?, 0.5);
+    Ok(())
+}
+
+#[test]
+fn test_mole_ratio() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+
+    let ratio = equation.mole_ratio("H2", "H2O")?;
+    assert_eq!((ratio.numerator, ratio.denominator), (1, 1));
+    assert_eq!(equation.mole_ratio("O2", "H2O")?.to_string(), "1:2");
+    assert_eq!(equation.mole_ratio("H2O", "O2")?.value(), 2.0);
+
+    let unbalanced = parser.parse_equation("H2 + O2 -> H2O")?;
+    assert!(unbalanced.mole_ratio("H2", "H2O").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_mass_of() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    let water = parser.parse_formula("H2O")?.mass;
+    let hydrogen = parser.parse_formula("H2")?.mass;
+
+    let grams = equation.mass_of("H2O", ("H2", 4.0))?;
+    assert!((grams - 4.0 / hydrogen * water).abs() < 1e-9);
+
+    let acetic = parser.parse_equation("CH3COOH + NaOH -> CH3COONa + H2O")?;
+    assert!(acetic.mass_of("NaOH", ("C2H4O2", 60.0))? > 39.0);
+    Ok(())
+}
+
+#[test]
+fn test_stoichiometry_errors() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let unbalanced = parser.parse_equation("H2 + O2 -> H2O")?;
+    assert!(unbalanced.moles_of("H2O", ("H2", 1.0)).is_err());
+
+    let balanced = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    assert!(balanced.moles_of("CO2", ("H2", 1.0)).is_err());
+
+    let half = parser.parse_equation("Fe^3+ + e^- -> Fe^2+")?;
+    assert_eq!(half.moles_of("e^-", ("Fe^2+", 2.0))?, 2.0);
+    assert!(half.mass_of("e^-", ("Fe^3+", 56.0)).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_species_on_both_sides() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let catalysed = parser.parse_equation("H2 + Cl2 + Pt -> 2HCl + Pt")?;
+    assert!(catalysed.mole_ratio("Pt", "H2").is_err());
+    assert!(catalysed.moles_of("Pt", ("H2", 1.0)).is_err());
+    assert_eq!(catalysed.moles_of("HCl", ("H2", 1.0))?, 2.0);
+
+    let same = parser.parse_equation("4294967295H2 -> 4294967295H2")?;
+    assert!(same.mole_ratio("H2", "H2").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_invalid_amounts() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    assert!(equation.mass_of("H2O", ("H2", -4.0)).is_err());
+    assert!(equation.mass_of("H2O", ("H2", f64::NAN)).is_err());
+    assert!(equation.moles_of("H2O", ("H2", f64::INFINITY)).is_err());
+    assert!(equation
+        .percent_yield("H2O", f64::NAN, &[("H2", 4.0)])
+        .is_err());
+    assert!(equation
+        .percent_yield("H2O", 30.0, &[("H2", -4.0)])
+        .is_err());
+    assert!(equation.percent_yield("H2O", 0.0, &[("H2", 4.0)]).is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_percent_yield() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    let water = parser.parse_formula("H2O")?.mass;
+    let oxygen = parser.parse_formula("O2")?.mass;
+
+    let report = equation.percent_yield("H2O", 30.0, &[("H2", 10.0), ("O2", 32.0)])?;
+    assert_eq!(report.limiting_reactant, "O2");
+    assert!((report.theoretical_mass - 32.0 / oxygen * 2.0 * water).abs() < 1e-9);
+    assert!((report.percent - 30.0 / report.theoretical_mass * 100.0).abs() < 1e-9);
+    assert!(report.percent > 80.0 && report.percent < 85.0);
+
+    assert!(equation.percent_yield("H2O", 30.0, &[]).is_err());
+    assert!(equation.percent_yield("H2O", 30.0, &[("N2", 1.0)]).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_scale_for() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let synthesis = parser.parse_equation("C7H6O3 + C4H6O3 -> C9H8O4 + C2H4O2")?;
+    let aspirin = parser.parse_formula("C9H8O4")?.mass;
+    let salicylic = parser.parse_formula("C7H6O3")?.mass;
+
+    let amounts = synthesis.scale_for("C9H8O4", 50.0)?;
+    assert_eq!(amounts.len(), 2);
+    assert_eq!(amounts[0].species, "C7H6O3");
+    assert!((amounts[0].moles - 50.0 / aspirin).abs() < 1e-12);
+    assert!((amounts[0].mass - 50.0 / aspirin * salicylic).abs() < 1e-9);
+
+    let water = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    let amounts = water.scale_for("H2O", 36.0)?;
+    assert!((amounts[1].moles * 2.0 - amounts[0].moles).abs() < 1e-12);
+    assert!(water.scale_for("CO2", 1.0).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_limiting_reactant() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let equation = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    let water = parser.parse_formula("H2O")?.mass;
+    let oxygen = parser.parse_formula("O2")?.mass;
+
+    let analysis = equation.limiting_reactant(&[("H2", 2.0), ("O2", 2.0)])?;
+    assert_eq!(analysis.reactant, "H2");
+    assert!((analysis.extent - 1.0).abs() < 1e-12);
+    assert_eq!(analysis.excess[0].moles, 0.0);
+    assert_eq!(analysis.excess[1].species, "O2");
+    assert!((analysis.excess[1].moles - 1.0).abs() < 1e-12);
+    assert!((analysis.excess[1].mass - oxygen).abs() < 1e-9);
+    assert_eq!(analysis.products.len(), 1);
+    assert!((analysis.products[0].moles - 2.0).abs() < 1e-12);
+    assert!((analysis.products[0].mass - 2.0 * water).abs() < 1e-9);
+
+    assert_eq!(equation.limiting_reactant(&[("O2", 0.5)])?.reactant, "O2");
+    assert!(equation.limiting_reactant(&[]).is_err());
+    assert!(equation.limiting_reactant(&[("H2O", 1.0)]).is_err());
+    for moles in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+        assert!(equation
+            .limiting_reactant(&[("H2", moles), ("O2", 1.0)])
+            .is_err());
+    }
+    Ok(())
+}
@@ -0,0 +1,55 @@
+#![cfg(any(feature = "datasets", not(feature = "std")))]
+
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_tagged_formula_occurrences() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let acetic_acid = parser.parse_tagged_formula("CH3COOH")?;
+
+    let symbols: Vec<&str> = acetic_acid
+        .occurrences()
+        .iter()
+        .map(|o| o.symbol.as_str())
+        .collect();
+    assert_eq!(symbols, vec!["C", "H", "C", "O", "O", "H"]);
+    assert_eq!(acetic_acid.occurrences()[1].count, 3);
+
+    let sulfate = parser.parse_tagged_formula("Al2(SO4)3")?;
+    assert_eq!(sulfate.occurrences()[2].count, 12);
+    Ok(())
+}
+
+#[test]
+fn test_tag_occurrence() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut acetic_acid = parser.parse_tagged_formula("CH3COOH")?;
+
+    assert!(acetic_acid.tag(2, "13C-label"));
+    assert!(!acetic_acid.tag(10, "13C-label"));
+    assert_eq!(acetic_acid.count_tagged("13C-label"), 1);
+    assert!(acetic_acid.occurrences()[0].tags.is_empty());
+    Ok(())
+}
+
+#[test]
+fn test_tags_survive_arithmetic() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut water = parser.parse_tagged_formula("H2O")?;
+    water.tag_element("O", "source-a");
+    let mut methane = parser.parse_tagged_formula("CH4")?;
+    methane.tag_element("H", "source-b");
+
+    let mixture = water * 2 + methane;
+    assert_eq!(mixture.formula.formula, "(H2O)2CH4");
+    assert_eq!(mixture.formula.elements["H"], 8);
+    assert_eq!(mixture.count_tagged("source-a"), 2);
+    assert_eq!(mixture.count_tagged("source-b"), 4);
+
+    let mut hydrogen = parser.parse_tagged_formula("H2")?;
+    hydrogen.tag_element("H", "source-c");
+    let saturated = hydrogen.clone() * u32::MAX + hydrogen;
+    assert_eq!(saturated.formula.elements["H"], u32::MAX);
+    assert_eq!(saturated.count_tagged("source-c"), u32::MAX);
+    Ok(())
+}
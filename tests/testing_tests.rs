@@ -0,0 +1,58 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::balance::DEFAULT_MAX_COEFFICIENT;
+use chemistry_parser::testing::{
+    assert_golden, fixture_dir, load_fixtures, snapshot, snapshot_balance, Fixture, UPDATE_VARIABLE,
+};
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_snapshot_is_stable() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let formula = parser.parse_formula("Al2(SO4)3")?;
+
+    let json = snapshot(&formula);
+    assert_eq!(json, snapshot(&parser.parse_formula("Al2(SO4)3")?));
+    assert!(json.find("\"Al\"").unwrap() < json.find("\"O\"").unwrap());
+    assert!(json.find("\"O\"").unwrap() < json.find("\"S\"").unwrap());
+    assert!(json.contains("\"mass\": 342.1311\n"));
+    Ok(())
+}
+
+#[test]
+fn test_load_fixtures() -> anyhow::Result<()> {
+    let fixtures = load_fixtures("balance_fixtures.txt")?;
+    assert_eq!(
+        fixtures[0],
+        Fixture {
+            line: 2,
+            input: String::from("H2 + O2 -> H2O")
+        }
+    );
+    assert!(load_fixtures("missing.txt").is_err());
+    Ok(())
+}
+
+#[test]
+fn test_balance_golden() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut report = String::new();
+    for fixture in load_fixtures("balance_fixtures.txt")? {
+        let result = parser
+            .parse_equation(&fixture.input)?
+            .balance(DEFAULT_MAX_COEFFICIENT);
+        report += &format!("{}\n{}\n\n", fixture.input, snapshot_balance(&result));
+    }
+    assert_golden("balance_fixtures.golden", &report);
+    Ok(())
+}
+
+#[test]
+fn test_missing_golden_fails() {
+    if std::env::var_os(UPDATE_VARIABLE).is_some() {
+        return;
+    }
+    let result = std::panic::catch_unwind(|| assert_golden("missing.golden", "balanced\n"));
+    assert!(result.is_err());
+    assert!(!fixture_dir().join("missing.golden").exists());
+}
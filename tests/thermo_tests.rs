@@ -0,0 +1,101 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::element::State;
+use chemistry_parser::gas::Temperature;
+use chemistry_parser::thermo::{ThermoTable, STANDARD_THERMO_TEMPERATURE};
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_reaction_enthalpy() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = ThermoTable::embedded()?;
+
+    let combustion = parser.parse_equation("CH4 + 2O2 -> CO2 + 2H2O")?;
+    assert!((combustion.reaction_enthalpy(&table)? + 890.5).abs() < 1e-9);
+
+    let ethanol = parser.parse_equation("C2H5OH + 3O2 -> 2CO2 + 3H2O")?;
+    assert!((ethanol.reaction_enthalpy(&table)? + 1366.8).abs() < 1e-9);
+
+    let neutralization = parser.parse_equation("H^+ + OH^- -> H2O")?;
+    assert!((neutralization.reaction_enthalpy(&table)? + 55.8).abs() < 1e-9);
+
+    let formation = parser.parse_equation("H2 + 1/2O2 -> H2O")?;
+    let water = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    let per_mole = formation.reaction_enthalpy(&table)?;
+    assert!((2.0 * per_mole - water.reaction_enthalpy(&table)?).abs() < 1e-9);
+
+    let unbalanced = parser.parse_equation("CH4 + O2 -> CO2 + H2O")?;
+    assert!(unbalanced.reaction_enthalpy(&table).is_err());
+    Ok(())
+}
+
+#[test]
+fn test_missing_enthalpies() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let mut table = ThermoTable::embedded()?;
+    let equation = parser.parse_equation("2XeF2 + 2H2O -> 2Xe + 4HF + O2")?;
+
+    let Err(error) = equation.reaction_enthalpy(&table) else {
+        anyhow::bail!("expected missing data");
+    };
+    assert_eq!(error.code(), "E0012");
+    assert!(
+        matches!(error, ChemParseError::MissingData(_, ref species) if species == &["XeF2", "Xe"])
+    );
+
+    table.insert(&parser.parse_formula("XeF2")?, None, -108.0);
+    table.insert(&parser.parse_formula("Xe")?, None, 0.0);
+    assert!(equation.reaction_enthalpy(&table).is_ok());
+    Ok(())
+}
+
+#[test]
+fn test_gibbs_energy() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = ThermoTable::embedded()?;
+    let calcination = parser.parse_equation("CaCO3 -> CaO + CO2")?;
+
+    assert!((calcination.reaction_entropy(&table)? - 160.2).abs() < 1e-9);
+    let room = Temperature::kelvins(STANDARD_THERMO_TEMPERATURE);
+    let gibbs = calcination.gibbs_energy(&table, room)?;
+    assert!((gibbs - calcination.standard_gibbs_energy(&table)?).abs() < 0.5);
+    assert!(!calcination.is_spontaneous(&table, room)?);
+    assert!(calcination.is_spontaneous(&table, Temperature::celsius(1000.0))?);
+
+    let combustion = parser.parse_equation("CH4 + 2O2 -> CO2 + 2H2O")?;
+    assert!(combustion.is_spontaneous(&table, room)?);
+    Ok(())
+}
+
+#[test]
+fn test_thermo_states_and_isomers() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let data = "formula,state,enthalpy\nH2O,l,-285.8\nH2O,g,-241.8\nH2,g,0.0\nO2,g,0.0\n\
+                C2H5OH,l,-277.6\nCH3OCH3,g,-184.1\n";
+    let table = ThermoTable::from_csv_str(data, &parser)?;
+
+    let water = parser.parse_formula("H2O")?;
+    assert_eq!(table.get(&water, Some(State::Liquid)), Some(-285.8));
+    assert_eq!(table.get(&water, Some(State::Gas)), Some(-241.8));
+    assert_eq!(table.get(&water, None), Some(-285.8));
+    assert_eq!(table.get(&water, Some(State::Solid)), None);
+
+    let steam = parser.parse_equation("2H2(g) + O2(g) -> 2H2O(g)")?;
+    assert!((steam.reaction_enthalpy(&table)? + 483.6).abs() < 1e-9);
+    let liquid = parser.parse_equation("2H2 + O2 -> 2H2O")?;
+    assert!((liquid.reaction_enthalpy(&table)? + 571.6).abs() < 1e-9);
+
+    assert_eq!(
+        table.get(&parser.parse_formula("C2H5OH")?, None),
+        Some(-277.6)
+    );
+    assert_eq!(
+        table.get(&parser.parse_formula("CH3OCH3")?, None),
+        Some(-184.1)
+    );
+    assert_eq!(table.get(&parser.parse_formula("C2H6O")?, None), None);
+
+    let invalid = ThermoTable::from_csv_str("formula,state,enthalpy\nH2O,x,0.0\n", &parser);
+    assert!(matches!(invalid, Err(ChemParseError::DataError(..))));
+    Ok(())
+}
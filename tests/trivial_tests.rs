@@ -0,0 +1,60 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::trivial::NameDictionary;
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_resolve_name() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    for (name, formula) in [
+        ("water", "H2O"),
+        ("Baking  Soda", "NaHCO3"),
+        ("table salt", "NaCl"),
+        ("ammonia", "NH3"),
+        ("blue vitriol", "CuSO4·5H2O"),
+    ] {
+        let resolved = parser.resolve_name(name)?;
+        assert!(
+            resolved.same_composition(&parser.parse_formula(formula)?),
+            "{}",
+            name
+        );
+    }
+
+    let error = parser.resolve_name("unobtainium").unwrap_err();
+    assert!(matches!(error, ChemParseError::MissingData(_, _)));
+
+    Ok(())
+}
+
+#[test]
+fn test_embedded_names_parse() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let content = std::fs::read_to_string("data/trivial_names.csv")?;
+
+    for line in content.lines().skip(1) {
+        let (name, _) = line.split_once(',').expect("name and formula");
+        parser.resolve_name(name)?;
+    }
+
+    Ok(())
+}
+
+#[test]
+fn test_user_dictionary() -> anyhow::Result<()> {
+    let mut names = NameDictionary::new();
+    names
+        .load_csv_str("name,formula\nbrine salt,KCl\ntable salt,KCl\n")
+        .map_err(|e| anyhow::anyhow!("{}", e))?;
+    assert_eq!(names.len(), 2);
+
+    let mut parser = ChemParser::new().with_names(names);
+    parser.names_mut().insert("Sugar of Lead", "Pb(CH3COO)2");
+
+    assert_eq!(parser.resolve_name("table salt")?.formula, "KCl");
+    assert_eq!(parser.resolve_name("sugar of lead")?.formula, "Pb(CH3COO)2");
+    assert_eq!(parser.resolve_name("water")?.formula, "H2O");
+
+    Ok(())
+}
@@ -0,0 +1,56 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::uncertainty::UncertainMass;
+use chemistry_parser::ChemParser;
+
+#[test]
+fn test_mass_with_uncertainty() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let table = parser.get_table();
+    assert_eq!(
+        table.get_element("Fe").unwrap().atomic_mass_uncertainty,
+        Some(0.002)
+    );
+
+    let water = parser
+        .parse_formula("H2O")?
+        .mass_with_uncertainty(table)
+        .unwrap();
+    assert!((water.value - 18.015).abs() < 1e-9);
+    let expected = ((2.0 * 0.0002f64).powi(2) + 0.001f64.powi(2)).sqrt();
+    assert!((water.uncertainty - expected).abs() < 1e-12);
+    assert_eq!(water.decimals(), 4);
+    assert_eq!(water.to_string(), "18.0150 ± 0.0011");
+
+    let lead = parser
+        .parse_formula("PbO")?
+        .mass_with_uncertainty(table)
+        .unwrap();
+    assert_eq!(lead.to_string(), "223.2 ± 1.1");
+
+    let heavy = parser
+        .parse_formula("D2O")?
+        .mass_with_uncertainty(table)
+        .unwrap();
+    assert!((heavy.uncertainty - 0.001).abs() < 1e-12);
+    assert!(parser
+        .parse_formula("TcO4^-")?
+        .mass_with_uncertainty(table)
+        .is_none());
+    Ok(())
+}
+
+#[test]
+fn test_uncertain_mass_display() {
+    let mass = UncertainMass {
+        value: 58.4428,
+        uncertainty: 0.0056,
+    };
+    assert_eq!(mass.to_string(), "58.443 ± 0.006");
+    assert!((mass.relative() - 0.0056 / 58.4428).abs() < 1e-15);
+    let exact = UncertainMass {
+        value: 2.014,
+        uncertainty: 0.0,
+    };
+    assert_eq!(exact.to_string(), "2.014 ± 0");
+}
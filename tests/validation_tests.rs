@@ -0,0 +1,141 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::validation::{CoefficientChange, Issue, Profile, ValidationRules};
+use chemistry_parser::{ChemParseError, ChemParser};
+
+#[test]
+fn test_strict_teaching_profile() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let rules = Profile::StrictTeaching.rules();
+
+    let valid = parser.validate_equation("2H2(g) + O2(g) -> 2H2O(l)", &rules)?;
+    assert!(valid.warnings.is_empty());
+
+    let error = parser
+        .validate_equation("4H2 + 2O2(g) -> 4H2O(l)", &rules)
+        .unwrap_err();
+    assert_eq!(error.code(), "E0013");
+    assert!(matches!(
+        &error,
+        ChemParseError::ValidationError(_, issues) if issues == &vec![
+            String::from("H2 has no state symbol"),
+            String::from("the coefficients have the common factor 2"),
+        ]
+    ));
+    assert!(parser
+        .validate_equation("H2(g) + O2(g) -> H2O(l)", &rules)
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_data_ingest_profile() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let rules = Profile::DataIngest.rules();
+
+    let corrected = parser.validate_equation("NAOH + HCL -> NACL + H2O", &rules)?;
+    assert_eq!(corrected.equation.equation, "NaOH + HCl -> NaCl + H2O");
+    assert_eq!(
+        corrected.warnings,
+        vec![Issue::LetterCase(String::from("NaOH + HCl -> NaCl + H2O"))]
+    );
+
+    let unbalanced = parser.validate_equation("CO + O2 -> CO2", &rules)?;
+    assert_eq!(unbalanced.equation.equation, "CO + O2 -> CO2");
+    assert_eq!(unbalanced.warnings, vec![Issue::Unbalanced]);
+
+    let states = parser.validate_equation("nacl(s) ->[heat] Na(l) + CL2(g)", &rules)?;
+    assert_eq!(
+        states.warnings[0],
+        Issue::LetterCase(String::from("NaCl(s) ->[heat] Na(l) + Cl2(g)"))
+    );
+    assert!(parser.validate_equation("NACL -> ", &rules).is_err());
+    assert!(parser
+        .validate_equation("NAOH -> NaOH", &ValidationRules::default())
+        .is_err());
+    Ok(())
+}
+
+#[test]
+fn test_profile_names() -> anyhow::Result<()> {
+    for profile in Profile::ALL {
+        assert_eq!(Profile::from_name(profile.name()), Some(profile));
+    }
+    assert_eq!(
+        Profile::from_name("Strict-Teaching"),
+        Some(Profile::StrictTeaching)
+    );
+    assert_eq!(Profile::from_name("lenient"), None);
+    assert_eq!(Profile::Default.rules(), ValidationRules::default());
+    Ok(())
+}
+
+#[test]
+fn test_charge_balance() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+    let reduction = parser.parse_equation("Fe^3+ + e^- -> Fe^2+")?;
+    assert!(reduction.check_charge_balance());
+    reduction.validate()?;
+
+    let missing_electron = parser.parse_equation("Fe^3+ -> Fe^2+")?;
+    assert!(missing_electron.check_atom_balance(true));
+    assert!(!missing_electron.check_charge_balance());
+
+    let error = parser
+        .parse_equation("Cu^2+ + H2 -> Cu + H^+")?
+        .validate()
+        .unwrap_err();
+    assert!(matches!(
+        &error,
+        ChemParseError::ValidationError(_, issues) if issues == &vec![
+            String::from("H: 2 on the left, 1 on the right"),
+            String::from("charge: 2 on the left, 1 on the right"),
+        ]
+    ));
+    Ok(())
+}
+
+#[test]
+fn test_diagnose() -> anyhow::Result<()> {
+    let parser = ChemParser::new();
+
+    let diagnosis = parser.parse_equation("H2 + O2 -> H2O")?.diagnose();
+    assert!(!diagnosis.is_balanced());
+    assert_eq!(diagnosis.elements.len(), 2);
+    let imbalances: Vec<String> = diagnosis.imbalances().map(|e| e.to_string()).collect();
+    assert_eq!(imbalances, ["O: 2 on the left, 1 on the right"]);
+    assert_eq!(diagnosis.elements[1].delta, -1);
+
+    let suggestion = diagnosis.suggestion.unwrap();
+    assert_eq!(suggestion.equation, "2H2 + O2 -> 2H2O");
+    assert_eq!(
+        suggestion.changes,
+        [
+            CoefficientChange {
+                formula: String::from("H2"),
+                written: 1,
+                suggested: 2
+            },
+            CoefficientChange {
+                formula: String::from("H2O"),
+                written: 1,
+                suggested: 2
+            }
+        ]
+    );
+
+    let diagnosis = parser.parse_equation("Fe^3+ -> Fe^2+")?.diagnose();
+    let charge = diagnosis.elements.last().unwrap();
+    assert_eq!((charge.element.as_str(), charge.delta), ("charge", -1));
+    assert!(diagnosis.suggestion.is_none());
+
+    let diagnosis = parser.parse_equation("2H2 + O2 -> 2H2O")?.diagnose();
+    assert!(diagnosis.is_balanced());
+    assert!(diagnosis.suggestion.is_none());
+    assert_eq!(diagnosis.imbalances().count(), 0);
+
+    let diagnosis = parser.parse_equation("H2 + O2 -> H2O + XeF4")?.diagnose();
+    assert_eq!(diagnosis.imbalances().count(), 3);
+    assert!(diagnosis.suggestion.is_none());
+    Ok(())
+}
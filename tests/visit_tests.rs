@@ -0,0 +1,82 @@
+use chemistry_parser::ast::{ChargeNode, ElementNode, EquationAst, FormulaAst, GroupNode};
+use chemistry_parser::visit::{walk, walk_equation, FormulaVisitor};
+use std::collections::BTreeMap;
+
+/// Counts atoms per composition key and records the groups with their depth.
+#[derive(Default)]
+struct Counter {
+    atoms: BTreeMap<String, u32>,
+    charge: i32,
+    groups: Vec<(String, usize)>,
+    depth: usize,
+}
+
+impl FormulaVisitor for Counter {
+    fn visit_element(&mut self, element: &ElementNode, multiplier: u32) {
+        *self.atoms.entry(element.key()).or_insert(0) += element.count * multiplier;
+    }
+
+    fn enter_group(&mut self, group: &GroupNode, _multiplier: u32) {
+        self.depth += 1;
+        self.groups.push((format!("{:?}", group.span), self.depth));
+    }
+
+    fn leave_group(&mut self, _group: &GroupNode, _multiplier: u32) {
+        self.depth -= 1;
+    }
+
+    fn visit_charge(&mut self, charge: &ChargeNode, multiplier: u32) {
+        self.charge += charge.value * multiplier as i32;
+    }
+}
+
+#[test]
+fn test_walk_counts_nested_groups() -> anyhow::Result<()> {
+    let mut counter = Counter::default();
+    walk(&FormulaAst::parse("K4[Fe(CN)6]")?, &mut counter);
+    assert_eq!(counter.atoms["K"], 4);
+    assert_eq!(counter.atoms["C"], 6);
+    assert_eq!(counter.atoms["N"], 6);
+    assert_eq!(
+        counter.groups,
+        vec![("2..11".into(), 1), ("5..10".into(), 2)]
+    );
+    assert_eq!(counter.depth, 0);
+    Ok(())
+}
+
+#[test]
+fn test_walk_adducts_and_charges() -> anyhow::Result<()> {
+    let mut counter = Counter::default();
+    walk(&FormulaAst::parse("CuSO4·5H2O")?, &mut counter);
+    assert_eq!(counter.atoms["H"], 10);
+    assert_eq!(counter.atoms["O"], 9);
+
+    let mut counter = Counter::default();
+    walk(&FormulaAst::parse("SO4^2-")?, &mut counter);
+    assert_eq!(counter.charge, -2);
+    Ok(())
+}
+
+#[test]
+fn test_walk_equation_multiplies_by_coefficients() -> anyhow::Result<()> {
+    let mut counter = Counter::default();
+    walk_equation(&EquationAst::parse("2H2 + O2 -> 2H2O")?, &mut counter);
+    assert_eq!(counter.atoms["H"], 8);
+    assert_eq!(counter.atoms["O"], 4);
+    Ok(())
+}
+
+#[test]
+fn test_walk_equation_scales_fractional_coefficients() -> anyhow::Result<()> {
+    let mut counter = Counter::default();
+    walk_equation(&EquationAst::parse("H2 + 1/2O2 -> H2O")?, &mut counter);
+    assert_eq!(counter.atoms["H"], 8);
+    assert_eq!(counter.atoms["O"], 4);
+
+    let mut counter = Counter::default();
+    walk_equation(&EquationAst::parse("0.5N2 + 1.5H2 -> NH3")?, &mut counter);
+    assert_eq!(counter.atoms["N"], 4);
+    assert_eq!(counter.atoms["H"], 12);
+    Ok(())
+}
@@ -0,0 +1,21 @@
+#![cfg(feature = "wasm")]
+
+use chemistry_parser::wasm::{check, parse_equation, parse_formula};
+use serde_json::Value;
+
+#[test]
+fn test_wasm_bindings() -> anyhow::Result<()> {
+    let formula: Value = serde_json::from_str(&parse_formula("H2O").unwrap())?;
+    assert_eq!(formula["formula"], "H2O");
+    assert!((formula["mass"].as_f64().unwrap() - 18.015).abs() < 0.001);
+
+    let equation: Value = serde_json::from_str(&parse_equation("2H2 + O2 -> 2H2O").unwrap())?;
+    assert_eq!(equation["reactant_terms"][0]["coefficient"], 2);
+
+    let balanced: Value = serde_json::from_str(&check("2H2 + O2 -> 2H2O").unwrap())?;
+    assert_eq!(balanced["balanced"], true);
+    let unbalanced: Value = serde_json::from_str(&check("H2 + O2 -> H2O").unwrap())?;
+    assert_eq!(unbalanced["balanced"], false);
+    assert_eq!(unbalanced["issues"][0], "O: 2 on the left, 1 on the right");
+    Ok(())
+}
@@ -0,0 +1,15 @@
+#![cfg(feature = "datasets")]
+
+use chemistry_parser::ChemParser;
+
+/// Changing the working directory affects the whole process, so this test has a binary of its
+/// own instead of sharing one with tests that read files by relative paths.
+#[test]
+fn test_embedded_table_independent_of_working_directory() -> anyhow::Result<()> {
+    std::env::set_current_dir(std::env::temp_dir())?;
+
+    let parser = ChemParser::new();
+    assert_eq!(parser.parse_element("Fe")?.atomic_number, 26);
+    assert!(parser.parse_formula("[13C]H4").is_ok());
+    Ok(())
+}